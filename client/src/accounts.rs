@@ -0,0 +1,194 @@
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::{AccountDeserialize, Discriminator, Result, ZeroCopy};
+
+use ai_dex::state::{
+    AiDexConfig, AiDexPool, AiDexReinvestments, Blocklist, ComputeUnitRegistry, FeatureGate, FeeTier, Keeper, OracleAccount, OraclePriceFeedDefault, PoolFeeAnalytics, PoolMetadata, PoolRevenueSnapshot,
+    PoolSnapshot, Position, PositionGraceCheckpoint, PositionIndex, PositionMetadata, PositionTradeBatch, ProtocolStats, ReferralFeeEscrow, ReinvestmentFeeEscrow, RewardEmissionsPolicy, RewardGraceEscrow, StakingRewards, SuperAdmin, SwapCommitment,
+    SwapRateLimiter, SwapReferral, TickArray, TickArrayBounty, TickArrayFunder, TickArrayV2, TokenBadge,
+};
+
+/// Decodes a Borsh-serialized ai_dex account, checking its discriminator.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+/// Decodes a zero-copy ai_dex account, such as a pool or a tick array, checking its discriminator.
+pub fn decode_zero_copy_account<T: ZeroCopy>(data: &[u8]) -> Result<T> {
+    let size = std::mem::size_of::<T>();
+    if data.len() < 8 + size {
+        return Err(AnchorErrorCode::AccountDidNotDeserialize.into());
+    }
+    if data[..8] != T::DISCRIMINATOR[..] {
+        return Err(AnchorErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    Ok(bytemuck::pod_read_unaligned(&data[8..8 + size]))
+}
+
+/// Any account owned by the ai_dex program.
+pub enum AiDexAccount {
+    SuperAdmin(SuperAdmin),
+    Config(AiDexConfig),
+    FeeTier(FeeTier),
+    Pool(Box<AiDexPool>),
+    OracleAccount(OracleAccount),
+    TickArray(Box<TickArray>),
+    TickArrayV2(Box<TickArrayV2>),
+    Position(Position),
+    PositionTradeBatch(PositionTradeBatch),
+    PositionMetadata(PositionMetadata),
+    SwapReferral(SwapReferral),
+    SwapCommitment(SwapCommitment),
+    Reinvestments(AiDexReinvestments),
+    Keeper(Keeper),
+    PoolSnapshot(PoolSnapshot),
+    TokenBadge(TokenBadge),
+    SwapRateLimiter(SwapRateLimiter),
+    PositionIndex(PositionIndex),
+    Blocklist(Blocklist),
+    PoolFeeAnalytics(PoolFeeAnalytics),
+    FeatureGate(FeatureGate),
+    StakingRewards(StakingRewards),
+    PoolMetadata(PoolMetadata),
+    TickArrayBounty(TickArrayBounty),
+    PoolRevenueSnapshot(PoolRevenueSnapshot),
+    ProtocolStats(ProtocolStats),
+    ComputeUnitRegistry(ComputeUnitRegistry),
+    ReferralFeeEscrow(ReferralFeeEscrow),
+    RewardEmissionsPolicy(RewardEmissionsPolicy),
+    ReinvestmentFeeEscrow(ReinvestmentFeeEscrow),
+    OraclePriceFeedDefault(OraclePriceFeedDefault),
+    RewardGraceEscrow(RewardGraceEscrow),
+    PositionGraceCheckpoint(PositionGraceCheckpoint),
+    TickArrayFunder(TickArrayFunder),
+}
+
+impl AiDexAccount {
+    /// Decodes an account owned by the ai_dex program, dispatching on its discriminator.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 {
+            return Err(AnchorErrorCode::AccountDiscriminatorNotFound.into());
+        }
+        let discriminator = &data[..8];
+        let account = if discriminator == SuperAdmin::DISCRIMINATOR {
+            Self::SuperAdmin(decode_account(data)?)
+        } else if discriminator == AiDexConfig::DISCRIMINATOR {
+            Self::Config(decode_account(data)?)
+        } else if discriminator == FeeTier::DISCRIMINATOR {
+            Self::FeeTier(decode_account(data)?)
+        } else if discriminator == AiDexPool::DISCRIMINATOR {
+            Self::Pool(Box::new(decode_zero_copy_account(data)?))
+        } else if discriminator == OracleAccount::DISCRIMINATOR {
+            Self::OracleAccount(decode_account(data)?)
+        } else if discriminator == TickArray::DISCRIMINATOR {
+            Self::TickArray(Box::new(decode_zero_copy_account(data)?))
+        } else if discriminator == TickArrayV2::DISCRIMINATOR {
+            Self::TickArrayV2(Box::new(decode_zero_copy_account(data)?))
+        } else if discriminator == Position::DISCRIMINATOR {
+            Self::Position(decode_account(data)?)
+        } else if discriminator == PositionTradeBatch::DISCRIMINATOR {
+            Self::PositionTradeBatch(decode_account(data)?)
+        } else if discriminator == PositionMetadata::DISCRIMINATOR {
+            Self::PositionMetadata(decode_account(data)?)
+        } else if discriminator == SwapReferral::DISCRIMINATOR {
+            Self::SwapReferral(decode_account(data)?)
+        } else if discriminator == SwapCommitment::DISCRIMINATOR {
+            Self::SwapCommitment(decode_account(data)?)
+        } else if discriminator == AiDexReinvestments::DISCRIMINATOR {
+            Self::Reinvestments(decode_account(data)?)
+        } else if discriminator == Keeper::DISCRIMINATOR {
+            Self::Keeper(decode_account(data)?)
+        } else if discriminator == PoolSnapshot::DISCRIMINATOR {
+            Self::PoolSnapshot(decode_account(data)?)
+        } else if discriminator == TokenBadge::DISCRIMINATOR {
+            Self::TokenBadge(decode_account(data)?)
+        } else if discriminator == SwapRateLimiter::DISCRIMINATOR {
+            Self::SwapRateLimiter(decode_account(data)?)
+        } else if discriminator == PositionIndex::DISCRIMINATOR {
+            Self::PositionIndex(decode_account(data)?)
+        } else if discriminator == Blocklist::DISCRIMINATOR {
+            Self::Blocklist(decode_account(data)?)
+        } else if discriminator == PoolFeeAnalytics::DISCRIMINATOR {
+            Self::PoolFeeAnalytics(decode_account(data)?)
+        } else if discriminator == FeatureGate::DISCRIMINATOR {
+            Self::FeatureGate(decode_account(data)?)
+        } else if discriminator == StakingRewards::DISCRIMINATOR {
+            Self::StakingRewards(decode_account(data)?)
+        } else if discriminator == PoolMetadata::DISCRIMINATOR {
+            Self::PoolMetadata(decode_account(data)?)
+        } else if discriminator == TickArrayBounty::DISCRIMINATOR {
+            Self::TickArrayBounty(decode_account(data)?)
+        } else if discriminator == PoolRevenueSnapshot::DISCRIMINATOR {
+            Self::PoolRevenueSnapshot(decode_account(data)?)
+        } else if discriminator == ProtocolStats::DISCRIMINATOR {
+            Self::ProtocolStats(decode_account(data)?)
+        } else if discriminator == ComputeUnitRegistry::DISCRIMINATOR {
+            Self::ComputeUnitRegistry(decode_account(data)?)
+        } else if discriminator == ReferralFeeEscrow::DISCRIMINATOR {
+            Self::ReferralFeeEscrow(decode_account(data)?)
+        } else if discriminator == RewardEmissionsPolicy::DISCRIMINATOR {
+            Self::RewardEmissionsPolicy(decode_account(data)?)
+        } else if discriminator == ReinvestmentFeeEscrow::DISCRIMINATOR {
+            Self::ReinvestmentFeeEscrow(decode_account(data)?)
+        } else if discriminator == OraclePriceFeedDefault::DISCRIMINATOR {
+            Self::OraclePriceFeedDefault(decode_account(data)?)
+        } else if discriminator == RewardGraceEscrow::DISCRIMINATOR {
+            Self::RewardGraceEscrow(decode_account(data)?)
+        } else if discriminator == PositionGraceCheckpoint::DISCRIMINATOR {
+            Self::PositionGraceCheckpoint(decode_account(data)?)
+        } else if discriminator == TickArrayFunder::DISCRIMINATOR {
+            Self::TickArrayFunder(decode_account(data)?)
+        } else {
+            return Err(AnchorErrorCode::AccountDiscriminatorMismatch.into());
+        };
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod accounts_tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::AccountSerialize;
+
+    #[test]
+    fn test_decode_position() {
+        let position = Position {
+            position_mint: Pubkey::new_unique(),
+            liquidity: 1_000,
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        position.try_serialize(&mut data).unwrap();
+
+        match AiDexAccount::decode(&data).unwrap() {
+            AiDexAccount::Position(decoded) => {
+                assert_eq!(decoded.position_mint, position.position_mint);
+                assert_eq!(decoded.liquidity, 1_000);
+                assert_eq!(decoded.tick_lower_index, -128);
+            }
+            _ => panic!("expected a position"),
+        }
+    }
+
+    #[test]
+    fn test_decode_pool() {
+        let pool = AiDexPool {
+            liquidity: 42,
+            tick_spacing: 64,
+            ..Default::default()
+        };
+        let mut data = AiDexPool::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&pool));
+
+        let decoded: AiDexPool = decode_zero_copy_account(&data).unwrap();
+        assert_eq!({ decoded.liquidity }, 42);
+        assert_eq!({ decoded.tick_spacing }, 64);
+
+        // Borsh accounts are rejected by the zero-copy decoder
+        data[..8].copy_from_slice(&Position::DISCRIMINATOR);
+        assert!(decode_zero_copy_account::<AiDexPool>(&data).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+use ai_dex::ID;
+
+/// Account structs of every instruction, as generated from the program.
+pub use ai_dex::accounts as instruction_accounts;
+/// Argument structs of every instruction, as generated from the program.
+pub use ai_dex::instruction as instruction_data;
+
+/// Builds an ai_dex instruction from the account and argument structs generated from the
+/// program, appending `remaining_accounts` after the named accounts.
+///
+/// # Example
+///
+/// ```ignore
+/// let ix = build_instruction(
+///     instruction_accounts::InitializeTickArray {
+///         ai_dex_pool,
+///         funder,
+///         tick_array,
+///         system_program,
+///         tick_array_bounty: None,
+///         tick_array_funder: None,
+///     },
+///     instruction_data::InitializeTickArray { start_tick_index },
+///     vec![],
+/// );
+/// ```
+pub fn build_instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+    Instruction {
+        program_id: ID,
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
+
+/// Builds writable, non-signer remaining account metas, such as the trade batch positions
+/// created by `open_trade_batch_positions_multi`.
+pub fn writable_account_metas(addresses: &[Pubkey]) -> Vec<AccountMeta> {
+    addresses
+        .iter()
+        .map(|address| AccountMeta::new(*address, false))
+        .collect()
+}
+
+#[cfg(test)]
+mod instructions_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn test_build_instruction() {
+        let ai_dex_pool = Pubkey::new_unique();
+        let funder = Pubkey::new_unique();
+        let tick_array = Pubkey::new_unique();
+        let extra = Pubkey::new_unique();
+
+        let ix = build_instruction(
+            instruction_accounts::InitializeTickArray {
+                ai_dex_pool,
+                funder,
+                tick_array,
+                system_program: anchor_lang::system_program::ID,
+                tick_array_bounty: None,
+                tick_array_funder: None,
+            },
+            instruction_data::InitializeTickArray { start_tick_index: -5632 },
+            writable_account_metas(&[extra]),
+        );
+
+        assert_eq!(ix.program_id, ID);
+        // The missing tick array bounty and funder record are passed as the program id
+        assert_eq!(ix.accounts.len(), 7);
+        assert_eq!(ix.accounts[4], AccountMeta::new_readonly(ID, false));
+        assert_eq!(ix.accounts[5], AccountMeta::new_readonly(ID, false));
+        assert_eq!(ix.accounts[6], AccountMeta::new(extra, false));
+        assert_eq!(&ix.data[..8], instruction_data::InitializeTickArray::DISCRIMINATOR);
+        assert_eq!(&ix.data[8..], &(-5632i32).to_le_bytes());
+    }
+
+    #[test]
+    fn test_two_hop_swap_v2_encodes_sqrt_price_limits_as_u128() {
+        let sqrt_price_limit_one: u128 = 4_295_048_016;
+        let sqrt_price_limit_two: u128 = 79_226_673_515_401_279_992_447_579_055;
+        let two_hop_swap = instruction_data::TwoHopSwap {
+            amount: 1_000,
+            other_amount_threshold: 900,
+            amount_specified_is_input: true,
+            a_to_b_one: true,
+            a_to_b_two: false,
+            sqrt_price_limit_one: sqrt_price_limit_one.to_le_bytes(),
+            sqrt_price_limit_two: sqrt_price_limit_two.to_le_bytes(),
+            remaining_accounts_info: None,
+            deadline_timestamp: None,
+            tick_index_limit_one: None,
+            tick_index_limit_two: None,
+            max_composite_price_deviation_bps: None,
+            oracle_maximum_age: None,
+        }
+        .data();
+        let two_hop_swap_v2 = instruction_data::TwoHopSwapV2 {
+            amount: 1_000,
+            other_amount_threshold: 900,
+            amount_specified_is_input: true,
+            a_to_b_one: true,
+            a_to_b_two: false,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            remaining_accounts_info: None,
+            deadline_timestamp: None,
+            tick_index_limit_one: None,
+            tick_index_limit_two: None,
+            max_composite_price_deviation_bps: None,
+            oracle_maximum_age: None,
+        }
+        .data();
+
+        // Only the discriminator differs, the u128 limits are serialized as little-endian bytes
+        assert_eq!(&two_hop_swap_v2[..8], instruction_data::TwoHopSwapV2::DISCRIMINATOR);
+        assert_eq!(two_hop_swap_v2[8..], two_hop_swap[8..]);
+    }
+}
@@ -0,0 +1,16 @@
+//! Off-chain helpers for integrating with the ai_dex program.
+//!
+//! The helpers are built on the program's own types, so seeds, account layouts and
+//! instruction arguments stay in sync with the program.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod tick_array;
+
+pub use accounts::*;
+pub use instructions::*;
+pub use pda::*;
+pub use tick_array::*;
+
+pub use ai_dex::ID as PROGRAM_ID;
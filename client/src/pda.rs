@@ -0,0 +1,351 @@
+use anchor_lang::prelude::Pubkey;
+
+use ai_dex::ID;
+
+/// Derives the address of the super admin account.
+pub fn find_super_admin_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"super-admin"], &ID)
+}
+
+/// Derives the address of the feature gate.
+pub fn find_feature_gate_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"feature_gate"], &ID)
+}
+
+/// Derives the address of a config.
+pub fn find_config_address(config_authority: &Pubkey, default_protocol_fee_rate: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"config",
+            config_authority.as_ref(),
+            default_protocol_fee_rate.to_string().as_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Derives the address of the fee tier of a config for a tick spacing.
+pub fn find_fee_tier_address(ai_dex_config: &Pubkey, tick_spacing: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"fee_tier", ai_dex_config.as_ref(), &tick_spacing.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of a pool. The mints must be ordered, `token_mint_a < token_mint_b`; see
+/// `ai_dex::util::canonicalize_token_mints`.
+pub fn find_pool_address(
+    ai_dex_config: &Pubkey,
+    token_mint_a: &Pubkey,
+    token_mint_b: &Pubkey,
+    tick_spacing: u16,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"ai_dex",
+            ai_dex_config.as_ref(),
+            token_mint_a.as_ref(),
+            token_mint_b.as_ref(),
+            &tick_spacing.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Derives the address of the token A vault of a pool.
+pub fn find_token_vault_a_address(ai_dex_pool: &Pubkey, tick_spacing: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"token_vault_a", ai_dex_pool.as_ref(), tick_spacing.to_string().as_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of the token B vault of a pool.
+pub fn find_token_vault_b_address(ai_dex_pool: &Pubkey, tick_spacing: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"token_vault_b", ai_dex_pool.as_ref(), tick_spacing.to_string().as_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of the oracle account of an oracle pool.
+pub fn find_oracle_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"oracle", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of a tick array from its start tick index.
+///
+/// Use [`crate::get_tick_array_start_tick_index`] to find the start tick index of the array
+/// containing a tick.
+pub fn find_tick_array_address(ai_dex_pool: &Pubkey, start_tick_index: i32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"tick_array", ai_dex_pool.as_ref(), start_tick_index.to_string().as_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of the reward vault of a pool.
+pub fn find_reward_vault_address(ai_dex_pool: &Pubkey, reward_mint: &Pubkey, reward_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"reward_vault",
+            reward_mint.as_ref(),
+            reward_index.to_string().as_bytes(),
+            ai_dex_pool.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+/// Derives the address of a position from its mint.
+pub fn find_position_address(position_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &ID)
+}
+
+/// Derives the address of a position mint.
+pub fn find_position_mint_address(
+    ai_dex_pool: &Pubkey,
+    owner: &Pubkey,
+    position_seed: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"position_mint",
+            ai_dex_pool.as_ref(),
+            owner.as_ref(),
+            position_seed.to_string().as_bytes(),
+            tick_lower_index.to_string().as_bytes(),
+            tick_upper_index.to_string().as_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Derives the address of the position metadata of a config.
+pub fn find_position_metadata_address(ai_dex_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position_metadata", ai_dex_config.as_ref()], &ID)
+}
+
+/// Derives the address of a position trade batch mint.
+pub fn find_position_trade_batch_mint_address(position_seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"position_trade_batch", position_seed.to_string().as_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of a position trade batch from its mint.
+pub fn find_position_trade_batch_address(position_trade_batch_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"position_trade_batch", position_trade_batch_mint.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of a position of a trade batch.
+pub fn find_trade_batch_position_address(position_trade_batch_mint: &Pubkey, trade_batch_index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"trade_batch_position",
+            position_trade_batch_mint.as_ref(),
+            trade_batch_index.to_string().as_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Derives the address of a swap referral.
+pub fn find_swap_referral_address(referrer: &Pubkey, referral_code: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"swap-referral", referrer.as_ref(), referral_code.as_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of the reinvestments account of an authority.
+pub fn find_reinvestments_address(reinvestments_authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reinvestments", reinvestments_authority.as_ref()], &ID)
+}
+
+/// Derives the address of the reinvestments account of an authority scoped to a config, created
+/// with `create_market_instance`.
+pub fn find_market_reinvestments_address(
+    ai_dex_config: &Pubkey,
+    reinvestments_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"reinvestments", ai_dex_config.as_ref(), reinvestments_authority.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of a keeper registered on a config.
+pub fn find_keeper_address(ai_dex_config: &Pubkey, keeper_authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"keeper", ai_dex_config.as_ref(), keeper_authority.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the staking rewards of a config.
+pub fn find_staking_rewards_address(ai_dex_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"staking_rewards", ai_dex_config.as_ref()], &ID)
+}
+
+/// Derives the address of the metadata of a pool.
+pub fn find_pool_metadata_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool_metadata", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the tick array bounty of a pool.
+pub fn find_tick_array_bounty_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tick_array_bounty", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the swap commitment of a payer on a pool.
+pub fn find_swap_commitment_address(ai_dex_pool: &Pubkey, payer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"swap_commitment", ai_dex_pool.as_ref(), payer.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the snapshot of a pool.
+pub fn find_pool_snapshot_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool_snapshot", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the fee analytics account of a pool.
+pub fn find_pool_fee_analytics_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool_fee_analytics", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the revenue snapshot of a pool.
+pub fn find_pool_revenue_snapshot_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool_revenue_snapshot", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the protocol stats of a config.
+pub fn find_protocol_stats_address(ai_dex_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"protocol_stats", ai_dex_config.as_ref()], &ID)
+}
+
+/// Derives the address of the compute unit registry of a config.
+pub fn find_compute_unit_registry_address(ai_dex_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"compute_unit_registry", ai_dex_config.as_ref()], &ID)
+}
+
+/// Derives the address of the referral fee escrow of a swap referral on a pool.
+pub fn find_referral_fee_escrow_address(swap_referral: &Pubkey, ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"referral_fee_escrow", swap_referral.as_ref(), ai_dex_pool.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the reward emissions policy of a pool.
+pub fn find_reward_emissions_policy_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_emissions_policy", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the reinvestment fee escrow of a reinvestments account on a pool.
+pub fn find_reinvestment_fee_escrow_address(reinvestments_account: &Pubkey, ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"reinvestment_fee_escrow", reinvestments_account.as_ref(), ai_dex_pool.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the default oracle price feed of a token pair on a config.
+pub fn find_oracle_price_feed_default_address(
+    ai_dex_config: &Pubkey,
+    token_mint_a: &Pubkey,
+    token_mint_b: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"oracle_price_feed_default", ai_dex_config.as_ref(), token_mint_a.as_ref(), token_mint_b.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the token badge of a mint on a config.
+pub fn find_token_badge_address(ai_dex_config: &Pubkey, token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"token_badge", ai_dex_config.as_ref(), token_mint.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the swap rate limiter of a mint on a config.
+pub fn find_swap_rate_limiter_address(ai_dex_config: &Pubkey, token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"swap_rate_limiter", ai_dex_config.as_ref(), token_mint.as_ref()],
+        &ID,
+    )
+}
+
+/// Derives the address of the blocklist of a config.
+pub fn find_blocklist_address(ai_dex_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"blocklist", ai_dex_config.as_ref()], &ID)
+}
+
+/// Derives the address of a page of the position index of an owner.
+pub fn find_position_index_address(owner: &Pubkey, page: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"position_index", owner.as_ref(), &page.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// Derives the address of the reward grace escrow of a pool.
+pub fn find_reward_grace_escrow_address(ai_dex_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_grace_escrow", ai_dex_pool.as_ref()], &ID)
+}
+
+/// Derives the address of the grace checkpoint of a position.
+pub fn find_position_grace_checkpoint_address(position: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position_grace_checkpoint", position.as_ref()], &ID)
+}
+
+/// Derives the address of the funder record of a tick array.
+pub fn find_tick_array_funder_address(tick_array: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tick_array_funder", tick_array.as_ref()], &ID)
+}
+
+#[cfg(test)]
+mod pda_tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_array_address_uses_string_seed() {
+        let ai_dex_pool = Pubkey::new_unique();
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"tick_array", ai_dex_pool.as_ref(), b"-5632"],
+            &ID,
+        );
+        assert_eq!(find_tick_array_address(&ai_dex_pool, -5632).0, expected);
+    }
+
+    #[test]
+    fn test_pool_address_uses_le_tick_spacing() {
+        let ai_dex_config = Pubkey::new_unique();
+        let token_mint_a = Pubkey::new_unique();
+        let token_mint_b = Pubkey::new_unique();
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                b"ai_dex",
+                ai_dex_config.as_ref(),
+                token_mint_a.as_ref(),
+                token_mint_b.as_ref(),
+                &[64, 0],
+            ],
+            &ID,
+        );
+        assert_eq!(
+            find_pool_address(&ai_dex_config, &token_mint_a, &token_mint_b, 64).0,
+            expected
+        );
+    }
+}
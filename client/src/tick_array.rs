@@ -0,0 +1,61 @@
+use ai_dex::state::TICK_ARRAY_SIZE;
+
+/// Returns the start tick index of a tick array relative to the array containing `tick_index`.
+///
+/// # Arguments
+///
+/// * `tick_index` - A tick contained in the reference array.
+/// * `tick_spacing` - The tick spacing of the pool.
+/// * `offset` - The number of arrays to move from the reference array, negative to move down.
+pub fn get_tick_array_start_tick_index(tick_index: i32, tick_spacing: u16, offset: i32) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    // Floor division, so negative ticks round down to the array below
+    (tick_index.div_euclid(ticks_in_array) + offset) * ticks_in_array
+}
+
+/// Returns the start tick indexes of the three tick arrays traversed by a swap, in order.
+///
+/// Swaps from B to A start from the array containing `tick_current_index + tick_spacing`,
+/// as the program searches a range shifted down by one tick spacing in that direction.
+pub fn get_swap_tick_array_start_tick_indexes(tick_current_index: i32, tick_spacing: u16, a_to_b: bool) -> [i32; 3] {
+    let (shift, step) = if a_to_b { (0, -1) } else { (tick_spacing as i32, 1) };
+    let tick_index = tick_current_index + shift;
+    [
+        get_tick_array_start_tick_index(tick_index, tick_spacing, 0),
+        get_tick_array_start_tick_index(tick_index, tick_spacing, step),
+        get_tick_array_start_tick_index(tick_index, tick_spacing, 2 * step),
+    ]
+}
+
+#[cfg(test)]
+mod tick_array_tests {
+    use super::*;
+    use ai_dex::state::Tick;
+
+    #[test]
+    fn test_start_tick_index_rounds_down() {
+        assert_eq!(get_tick_array_start_tick_index(0, 64, 0), 0);
+        assert_eq!(get_tick_array_start_tick_index(5631, 64, 0), 0);
+        assert_eq!(get_tick_array_start_tick_index(5632, 64, 0), 5632);
+        assert_eq!(get_tick_array_start_tick_index(-1, 64, 0), -5632);
+        assert_eq!(get_tick_array_start_tick_index(-5632, 64, 0), -5632);
+        assert_eq!(get_tick_array_start_tick_index(-5633, 64, -1), -16896);
+    }
+
+    #[test]
+    fn test_start_tick_index_is_valid_for_program() {
+        for tick_spacing in [1u16, 8, 64, 128] {
+            for tick_index in [-443_636, -100_001, -1, 0, 1, 77_777, 443_636] {
+                let start_tick_index = get_tick_array_start_tick_index(tick_index, tick_spacing, 0);
+                assert!(Tick::check_is_valid_start_tick(start_tick_index, tick_spacing));
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap_tick_array_start_tick_indexes() {
+        assert_eq!(get_swap_tick_array_start_tick_indexes(100, 64, true), [0, -5632, -11264]);
+        // At the upper edge of an array, swaps from B to A start from the next array
+        assert_eq!(get_swap_tick_array_start_tick_indexes(5600, 64, false), [5632, 11264, 16896]);
+    }
+}
@@ -0,0 +1,4 @@
+/// Version of the layout of all events, emitted as their first field. Bumped whenever the fields
+/// of an event or of a struct it embeds change, so indexers can tell layouts apart instead of
+/// misreading them. The event registry tests pin the current layouts.
+pub const EVENT_VERSION: u8 = 2;
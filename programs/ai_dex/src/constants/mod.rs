@@ -1,5 +1,7 @@
 pub mod nft;
 pub mod transfer_memo;
 pub mod test_constants;
+pub mod events;
 
 pub use nft::*;
+pub use events::*;
@@ -5,6 +5,10 @@ pub mod ai_dex_nft_update_auth {
     declare_id!("updmeGm2r24F2USBMiscZEZr89nxyy2LvmpQwUAmzjD");
 }
 
+pub const MAX_METADATA_NAME_LEN: usize = 32;
+pub const MAX_METADATA_SYMBOL_LEN: usize = 10;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
 // METADATA_NAME   : max  32 bytes
 pub const AD_METADATA_NAME: &str = "Ai Dex Position";
 // METADATA_SYMBOL : max  10 bytes
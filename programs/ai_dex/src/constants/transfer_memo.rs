@@ -4,4 +4,6 @@ pub const TRANSFER_MEMO_COLLECT_REWARD: &str = "Ai Dex CollectReward";
 pub const TRANSFER_MEMO_DECREASE_LIQUIDITY: &str = "Ai Dex Withdraw";
 pub const TRANSFER_MEMO_SWAP: &str = "Ai Dex Trade";
 pub const TRANSFER_MEMO_SEND_REFERRAL_FEES_TO_PDA_ATA: &str = "Ai Dex Referral Fees";
-pub const TRANSFER_MEMO_COLLECT_REFERRAL_FEES: &str = "Ai Dex CollectReferralFees";
\ No newline at end of file
+pub const TRANSFER_MEMO_COLLECT_REFERRAL_FEES: &str = "Ai Dex CollectReferralFees";pub const TRANSFER_MEMO_FUND_REWARDS_FROM_PROTOCOL_FEES: &str = "Ai Dex FundRewardsFromProtocolFees";
+pub const TRANSFER_MEMO_COLLECT_REINVESTMENT_FEES: &str = "Ai Dex CollectReinvestmentFees";
+pub const TRANSFER_MEMO_CLAIM_STAKING_REWARDS: &str = "Ai Dex ClaimStakingRewards";
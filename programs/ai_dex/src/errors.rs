@@ -177,6 +177,216 @@ pub enum ErrorCode {
     FeeRateUnchanged,
     #[msg("Empty admin input")]
     EmptyAdminInput,
+    #[msg("Transaction deadline exceeded")]
+    DeadlineExceeded,
+    #[msg("Pool is locked by an in-progress instruction")]
+    PoolReentrancyLocked,
+    #[msg("Position is not eligible for dust liquidation")]
+    PositionNotLiquidatable,
+    #[msg("Position metadata exceeds maximum length")]
+    PositionMetadataTooLong,
+    #[msg("Full-range reward boost exceeds maximum")]
+    FullRangeRewardBoostExceedsMaximum,
+    #[msg("Default oracle maximum age is not set")]
+    DefaultOracleMaximumAgeNotSet,
+    #[msg("Pool is not a temporary pool")]
+    NotTemporaryPool,
+    #[msg("Early-exit fee exceeds maximum")]
+    EarlyExitFeeExceedsMaximum,
+    #[msg("Swap commitment cannot be revealed in the commit slot")]
+    SwapCommitmentNotMatured,
+    #[msg("Swap commitment has expired")]
+    SwapCommitmentExpired,
+    #[msg("Swap parameters do not match the commitment")]
+    SwapCommitmentMismatch,
+    #[msg("Protocol fee share exceeds maximum")]
+    ProtocolFeeShareExceedsMaximum,
+    #[msg("Tick array v2 has no free tick slot, expand it to a tick array")]
+    TickArrayV2Full,
+    #[msg("Position collection accounts are missing")]
+    PositionCollectionAccountsMissing,
+    #[msg("Invalid position collection account")]
+    InvalidPositionCollection,
+    #[msg("Instruction does not support oracle pools")]
+    OraclePoolNotSupported,
+    #[msg("Split liquidity must be positive and at most the position liquidity")]
+    InvalidSplitLiquidity,
+    #[msg("Positions do not share the same pool and tick range")]
+    PositionRangeMismatch,
+    #[msg("Integrator fee rate exceeds maximum")]
+    IntegratorFeeRateExceededError,
+    #[msg("Integrator fee account is missing or has the wrong mint")]
+    InvalidIntegratorFeeAccount,
+    #[msg("Account type cannot be migrated")]
+    UnsupportedAccountMigration,
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
+    #[msg("Sqrt price limit is outside the supported price range")]
+    SqrtPriceLimitOutOfBounds,
+    #[msg("Out-of-range grace share or period exceeds maximum")]
+    OutOfRangeGraceExceedsMaximum,
+    #[msg("Keeper is not registered for the config of the pool")]
+    InvalidKeeper,
+    #[msg("Keeper rate limit must allow at least one action per window")]
+    InvalidKeeperRateLimit,
+    #[msg("Keeper exceeded its rate limit")]
+    KeeperRateLimitExceeded,
+    #[msg("Pool is not an oracle pool")]
+    NotOraclePool,
+    #[msg("Donation amounts are zero")]
+    ZeroDonationAmount,
+    #[msg("Pool has no in-range liquidity to receive the donation")]
+    DonationWithoutLiquidity,
+    #[msg("Invalid number of trade batch positions")]
+    InvalidTradeBatchPositionCount,
+    #[msg("Invalid trade batch position account")]
+    InvalidTradeBatchPositionAccount,
+    #[msg("Oracle price update was posted too many slots ago")]
+    OraclePriceSlotLagExceeded,
+    #[msg("Protocol fee destination is not owned by the treasury")]
+    InvalidProtocolFeeDestination,
+    #[msg("Pool batch is empty or its pools do not share the same config and token pair")]
+    InvalidPoolBatch,
+    #[msg("Position must be migrated to the current version")]
+    PositionNotMigrated,
+    #[msg("Maximum swap liquidity share exceeds maximum")]
+    MaxSwapLiquidityBpsExceedsMaximum,
+    #[msg("Swap exceeds the maximum share of the in-range liquidity of the pool")]
+    SwapExceedsMaxLiquidityShare,
+    #[msg("Reward owner account is not owned by the reward recipient of the position")]
+    InvalidRewardRecipient,
+    #[msg("Account already has its current length")]
+    AccountAlreadyExtended,
+    #[msg("A swap referral for both hops cannot be combined with per-hop swap referrals")]
+    AmbiguousSwapReferral,
+    #[msg("Pool snapshot is not a writable snapshot of a pool of the instruction")]
+    InvalidPoolSnapshot,
+    #[msg("Liquidity bootstrapping fee schedule must have a duration")]
+    InvalidLbpFeeSchedule,
+    #[msg("Reward mint has a freeze authority or risky extensions and requires a token badge")]
+    RewardMintRequiresTokenBadge,
+    #[msg("Unwrapping SOL requires a wrapped SOL token account owned by the position authority")]
+    InvalidWsolUnwrapAccount,
+    #[msg("Swap rate limit window must be at least as many seconds as it has buckets")]
+    InvalidSwapRateLimit,
+    #[msg("Swap volume exceeds the rate limit of the pool")]
+    SwapRateLimitExceeded,
+    #[msg("Missing or invalid swap rate limiter account of the pool")]
+    InvalidSwapRateLimiter,
+    #[msg("Reinvestment swap input exceeds the fees owed to the position")]
+    ReinvestmentSwapExceedsFeesOwed,
+    #[msg("Swap callback program must be an executable program other than this one")]
+    InvalidSwapCallbackProgram,
+    #[msg("The fee destination account is not a token account of the position authority.")]
+    InvalidFeeDestination,
+    #[msg("A super admin recovery authority requires a non-zero inactivity timeout.")]
+    InvalidSuperAdminRecovery,
+    #[msg("Super admin recovery is disabled, already requested, or the super admin is still active.")]
+    SuperAdminRecoveryUnavailable,
+    #[msg("Super admin recovery is not requested or its claim delay has not passed.")]
+    SuperAdminRecoveryNotClaimable,
+    #[msg("The position index page is full.")]
+    PositionIndexFull,
+    #[msg("The position is already listed by the position index page.")]
+    PositionAlreadyIndexed,
+    #[msg("The position is not listed by the position index page.")]
+    PositionNotIndexed,
+    #[msg("The liquidity share must be between 1 and 10,000 basis points.")]
+    InvalidLiquidityBps,
+    #[msg("The reward vault does not fund the emissions for the minimum funded duration.")]
+    RewardEmissionsUnderfunded,
+    #[msg("Reinvestments account is scoped to a different config")]
+    InvalidReinvestmentsAccount,
+    #[msg("Associated token and system programs are required to create the token account")]
+    MissingAssociatedTokenAccountPrograms,
+    #[msg("The token authority is neither the owner nor the delegate of the token account")]
+    InvalidTokenAuthority,
+    #[msg("The delegated amount of the token account is lower than the transfer amount")]
+    InsufficientDelegatedAmount,
+    #[msg("The oracle account is still used by an oracle pool")]
+    OracleAccountInUse,
+    #[msg("A token account of the swap referral still holds referral fees")]
+    SwapReferralHasPendingFees,
+    #[msg("The composite price check of a two-hop swap requires both pools to be oracle pools")]
+    CompositePriceCheckRequiresOraclePools,
+    #[msg("The maximum composite price deviation must be at most 10,000 basis points")]
+    InvalidCompositePriceDeviation,
+    #[msg("The two-hop swap rate deviates from the composite oracle price by more than the maximum")]
+    CompositePriceDeviationExceeded,
+    #[msg("The blocklist of the config must be passed for a pool with the blocklist enabled")]
+    InvalidBlocklist,
+    #[msg("The blocklist is full")]
+    BlocklistFull,
+    #[msg("The address is already on the blocklist")]
+    AddressAlreadyBlocked,
+    #[msg("The address is not on the blocklist")]
+    AddressNotBlocked,
+    #[msg("The address is blocked from interacting with the pool")]
+    AddressBlocked,
+    #[msg("Fee or reward growth overflowed")]
+    GrowthOverflowError,
+    #[msg("The signer is neither the config authority nor the holder of the config role")]
+    InvalidConfigRoleAuthority,
+    #[msg("The fee analytics account of the pool is missing or invalid")]
+    InvalidPoolFeeAnalytics,
+    #[msg("Feature is disabled")]
+    FeatureDisabled,
+    #[msg("Hop accounts are missing or invalid")]
+    InvalidHopAccounts,
+    #[msg("Position was reinvested in this slot")]
+    PositionReinvestedInSlot,
+    #[msg("Token mints with a permanent delegate are rejected by the config")]
+    PermanentDelegateMintRejected,
+    #[msg("Pool metadata exceeds maximum length")]
+    PoolMetadataTooLong,
+    #[msg("Pool metadata can only be set by the config authority or the pool creator")]
+    InvalidPoolMetadataAuthority,
+    #[msg("Pool price is outside the bounds expected by the deposit")]
+    PoolPriceOutsideDepositBounds,
+    #[msg("Tick array bounties can only be managed by the config authority or the pool creator")]
+    InvalidTickArrayBountyAuthority,
+    #[msg("The revenue snapshot of the pool is missing or invalid")]
+    InvalidPoolRevenueSnapshot,
+    #[msg("Protocol stats already track the maximum number of mints")]
+    ProtocolStatsMintLimitReached,
+    #[msg("A token account of the transfer is frozen by the freeze authority of its mint")]
+    FrozenAccountError,
+    #[msg("Reward emissions are already paused")]
+    RewardAlreadyPaused,
+    #[msg("Reward emissions are not paused")]
+    RewardNotPaused,
+    #[msg("Compute unit registry cannot register more instruction variants")]
+    ComputeUnitRegistryFull,
+    #[msg("Swap has no liquidity in range to trade against")]
+    ZeroTradableLiquidity,
+    #[msg("Pool is not a temporary pool past the end of its liquidity provision window")]
+    PoolNotExpired,
+    #[msg("The referral fee escrow accounts are missing or invalid")]
+    InvalidReferralFeeEscrow,
+    #[msg("Reward emissions exceed the threshold of their baseline and require the co-signature of the config reward manager")]
+    EmissionsChangeRequiresCoSignature,
+    #[msg("Positions attributed to different LP referrals cannot be merged")]
+    PositionReferralMismatch,
+    #[msg("The LP referral token accounts are required to collect the fees of a referred position")]
+    MissingLpReferralAta,
+    #[msg("The maximum reinvestment swap price deviation must be at most 10,000 basis points")]
+    InvalidReinvestmentSwapMaxDeviation,
+    #[msg("Reinvestment swaps are disabled until the position owner sets a maximum price deviation")]
+    ReinvestmentSwapDisabled,
+    #[msg("The pool snapshot must be refreshed in a recent slot before the reinvestment swap")]
+    ReinvestmentSwapSnapshotStale,
+    #[msg("The reinvestment swap rate deviates from the pool snapshot price by more than the maximum of the position")]
+    ReinvestmentSwapPriceDeviationExceeded,
+    #[msg("The full-range reward boost can only be raised before the reward accrues any growth")]
+    FullRangeRewardBoostRaisedAfterGrowth,
+    #[msg("The reward grace escrow of the pool is required while the reward has an out-of-range grace")]
+    MissingRewardGraceEscrow,
+    #[msg("Tick array does not belong to the pool")]
+    InvalidTickArray,
+    #[msg("Tick array still has initialized ticks")]
+    TickArrayNotEmpty,
+    #[msg("Tick arrays containing or neighbouring the current price cannot be closed")]
+    TickArrayNearCurrentPrice,
 }
 
 impl From<TryFromIntError> for ErrorCode {
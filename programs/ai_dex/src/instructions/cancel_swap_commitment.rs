@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::SwapCommitment;
+
+#[event]
+pub struct SwapCommitmentCancelledEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub payer: Pubkey,
+    pub swap_commitment: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct CancelSwapCommitment<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = payer, close = payer)]
+    pub swap_commitment: Account<'info, SwapCommitment>,
+}
+
+/// Closes an unrevealed swap commitment, e.g. once it has expired, and refunds its rent.
+pub fn cancel_swap_commitment_handler(ctx: Context<CancelSwapCommitment>) -> Result<()> {
+    emit!(SwapCommitmentCancelledEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.swap_commitment.ai_dex_pool,
+        payer: ctx.accounts.payer.key(),
+        swap_commitment: ctx.accounts.swap_commitment.key(),
+    });
+
+    Ok(())
+}
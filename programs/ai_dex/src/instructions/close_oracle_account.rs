@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[event]
+pub struct OracleAccountClosedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub oracle_account: Pubkey,
+    pub receiver: Pubkey,
+    pub rent_refunded: u64,
+}
+
+#[derive(Accounts)]
+pub struct CloseOracleAccount<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+        close = receiver,
+    )]
+    pub oracle_account: Account<'info, OracleAccount>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Closes the oracle account of a pool that no longer reads it, refunding its rent to the
+/// receiver.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The pool is an oracle pool and the oracle account is its current oracle.
+pub fn close_oracle_account_handler(ctx: Context<CloseOracleAccount>) -> Result<()> {
+    let ai_dex_pool = ctx.accounts.ai_dex_pool.load()?;
+    if ai_dex_pool.is_oracle_pool && ai_dex_pool.oracle_address == ctx.accounts.oracle_account.key() {
+        return Err(ErrorCode::OracleAccountInUse.into());
+    }
+
+    emit!(OracleAccountClosedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        oracle_account: ctx.accounts.oracle_account.key(),
+        receiver: ctx.accounts.receiver.key(),
+        rent_refunded: ctx.accounts.oracle_account.to_account_info().lamports(),
+    });
+
+    Ok(())
+}
@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::state::*;
 use crate::util::{burn_and_close_user_position_token, verify_position_authority};
 
 #[event]
 pub struct PositionClosedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub position_authority: Pubkey,
     pub receiver: Pubkey,
@@ -15,6 +17,12 @@ pub struct PositionClosedEvent {
     pub position_token_account_amount: u64,
     pub position_token_account_mint: Pubkey,
     pub position: Pubkey,
+    pub deposited_a: u64,
+    pub deposited_b: u64,
+    pub withdrawn_a: u64,
+    pub withdrawn_b: u64,
+    pub realized_pnl_a: i128,
+    pub realized_pnl_b: i128,
 }
 
 #[derive(Accounts)]
@@ -45,6 +53,11 @@ pub struct ClosePosition<'info> {
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
+
+    /// Page of a position index listing the closed position, which may be the page of a previous
+    /// owner when the position was transferred without being reindexed
+    #[account(mut)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
 }
 
 pub fn close_position_handler(ctx: Context<ClosePosition>) -> Result<()> {
@@ -57,6 +70,10 @@ pub fn close_position_handler(ctx: Context<ClosePosition>) -> Result<()> {
         return Err(ErrorCode::NonEmptyPositionCloseError.into());
     }
 
+    if let Some(position_index) = &mut ctx.accounts.position_index {
+        position_index.remove_position(&ctx.accounts.position.key())?;
+    }
+
     burn_and_close_user_position_token(
         &ctx.accounts.position_authority,
         &ctx.accounts.receiver,
@@ -64,8 +81,12 @@ pub fn close_position_handler(ctx: Context<ClosePosition>) -> Result<()> {
         &ctx.accounts.position_token_account,
         &ctx.accounts.token_program,
     )?;
-    
+
+    let position = &ctx.accounts.position;
+    let (realized_pnl_a, realized_pnl_b) = position.realized_pnl();
+
     emit!(PositionClosedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.position.ai_dex_pool.key(),
         position_authority: ctx.accounts.position_authority.key(),
         receiver: ctx.accounts.receiver.key(),
@@ -73,7 +94,13 @@ pub fn close_position_handler(ctx: Context<ClosePosition>) -> Result<()> {
         position_token_account_key: ctx.accounts.position_token_account.key(),
         position_token_account_amount: ctx.accounts.position_token_account.amount,
         position_token_account_mint: ctx.accounts.position_token_account.mint,
-        position: ctx.accounts.position.key(),
+        position: position.key(),
+        deposited_a: position.deposited_a,
+        deposited_b: position.deposited_b,
+        withdrawn_a: position.withdrawn_a,
+        withdrawn_b: position.withdrawn_b,
+        realized_pnl_a,
+        realized_pnl_b,
     });
     
     Ok(())
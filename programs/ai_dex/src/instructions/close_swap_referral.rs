@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{self, extension::StateWithExtensions};
+use anchor_spl::token_interface::Token2022;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[event]
+pub struct SwapReferralClosedEvent {
+    pub event_version: u8,
+    pub swap_referral: Pubkey,
+    pub referrer_address: Pubkey,
+    pub referral_code: String,
+    pub receiver: Pubkey,
+    pub rent_refunded: u64,
+}
+
+#[derive(Accounts)]
+pub struct CloseSwapReferral<'info> {
+    #[account(mut, has_one = referrer_address, close = receiver)]
+    pub swap_referral: Account<'info, SwapReferral>,
+
+    pub referrer_address: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Closes an abandoned swap referral, refunding its rent to the receiver.
+///
+/// The token accounts of the referral that received fees are passed as remaining accounts and
+/// must all be empty, so that no referral fee is left without an authority able to collect it.
+/// Fees sent to a referral token account that was not passed remain recoverable by initializing
+/// the referral again with the same referral code, from the referrer that first initialized it.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - A remaining account is not a token account owned by the swap referral.
+/// - A token account of the swap referral is not empty.
+pub fn close_swap_referral_handler(ctx: Context<CloseSwapReferral>) -> Result<()> {
+    let swap_referral_key = ctx.accounts.swap_referral.key();
+    for referral_token_account in ctx.remaining_accounts.iter() {
+        if *referral_token_account.owner != Token::id() && *referral_token_account.owner != Token2022::id() {
+            return Err(ErrorCode::InvalidSwapReferralAta.into());
+        }
+        let data = referral_token_account.try_borrow_data()?;
+        let token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?.base;
+        if token_account.owner != swap_referral_key {
+            return Err(ErrorCode::InvalidSwapReferralAta.into());
+        }
+        if token_account.amount > 0 {
+            return Err(ErrorCode::SwapReferralHasPendingFees.into());
+        }
+    }
+
+    emit!(SwapReferralClosedEvent {
+        event_version: EVENT_VERSION,
+        swap_referral: swap_referral_key,
+        referrer_address: ctx.accounts.referrer_address.key(),
+        referral_code: ctx.accounts.swap_referral.referral_code.clone(),
+        receiver: ctx.accounts.receiver.key(),
+        rent_refunded: ctx.accounts.swap_referral.to_account_info().lamports(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[event]
+pub struct TickArrayClosedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+    pub funder: Pubkey,
+    pub rent_refunded: u64,
+}
+
+#[derive(Accounts)]
+pub struct CloseTickArray<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tick_array_funder", tick_array.key().as_ref()],
+        bump = tick_array_funder.bump,
+        has_one = tick_array,
+        has_one = funder,
+        close = funder,
+    )]
+    pub tick_array_funder: Account<'info, TickArrayFunder>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+}
+
+/// Closes a tick array (v1 or v2) without any initialized tick, refunding its rent and the rent
+/// of its funder record to the funder recorded when it was initialized.
+///
+/// Tick arrays containing or neighbouring the tick array of the current price stay open, as
+/// swaps from the current price cross them.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The tick array still has initialized ticks.
+/// - The tick array contains or neighbours the tick array of the current price.
+pub fn close_tick_array_handler(ctx: Context<CloseTickArray>) -> Result<()> {
+    let start_tick_index = {
+        let tick_array = load_tick_array(&ctx.accounts.tick_array)?;
+        if (0..TICK_ARRAY_SIZE_USIZE).any(|offset| tick_array.is_initialized_at_offset(offset)) {
+            return Err(ErrorCode::TickArrayNotEmpty.into());
+        }
+        tick_array.start_tick_index()
+    };
+
+    let (tick_current_index, tick_spacing) = {
+        let ai_dex_pool = ctx.accounts.ai_dex_pool.load()?;
+        (ai_dex_pool.tick_current_index, ai_dex_pool.tick_spacing)
+    };
+    if TickArrayBounty::is_eligible_tick_array(start_tick_index, tick_current_index, tick_spacing) {
+        return Err(ErrorCode::TickArrayNearCurrentPrice.into());
+    }
+
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    let rent_refunded = tick_array_info.lamports();
+
+    **tick_array_info.try_borrow_mut_lamports()? = 0;
+    **ctx.accounts.funder.to_account_info().try_borrow_mut_lamports()? += rent_refunded;
+
+    tick_array_info.assign(&system_program::ID);
+    tick_array_info.realloc(0, false)?;
+
+    emit!(TickArrayClosedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index,
+        funder: ctx.accounts.funder.key(),
+        rent_refunded,
+    });
+
+    Ok(())
+}
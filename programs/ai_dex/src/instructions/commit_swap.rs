@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexPool, SwapCommitment};
+
+#[event]
+pub struct SwapCommittedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub payer: Pubkey,
+    pub swap_commitment: Pubkey,
+    pub commitment_hash: [u8; 32],
+    pub commit_slot: u64,
+}
+
+#[derive(Accounts)]
+pub struct CommitSwap<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SwapCommitment::LEN,
+        seeds = [
+            b"swap_commitment".as_ref(),
+            ai_dex_pool.key().as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub swap_commitment: Account<'info, SwapCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Commits to the hash of a swap that will be executed by `reveal_swap` in a later slot.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the commitment.
+/// * `commitment_hash` - The hash computed by `SwapCommitment::compute_hash`.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the commitment is successfully stored,
+/// or an `Err` if an error occurs.
+pub fn commit_swap_handler(ctx: Context<CommitSwap>, commitment_hash: [u8; 32]) -> Result<()> {
+    let commit_slot = Clock::get()?.slot;
+
+    ctx.accounts.swap_commitment.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.accounts.payer.key(),
+        commitment_hash,
+        commit_slot,
+        ctx.bumps.swap_commitment,
+    );
+
+    emit!(SwapCommittedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        payer: ctx.accounts.payer.key(),
+        swap_commitment: ctx.accounts.swap_commitment.key(),
+        commitment_hash,
+        commit_slot,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::orchestrator::liquidity_orchestrator::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
 use crate::state::PositionUpdate;
-use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
-use crate::util::{to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority};
+use crate::util::{check_blocklist, calculate_transfer_fee_excluded_amount, parse_remaining_accounts, refresh_pool_snapshots, AccountsType, RemainingAccountsInfo};
+use crate::util::{check_deadline, current_timestamp_u64, transfer_from_vault_to_owner, unwrap_native_token_account, verify_position_authority};
 use crate::constants::transfer_memo;
 use crate::UpdateTicksEvent;
 
@@ -15,6 +17,7 @@ use super::ModifyLiquidity;
 
 #[event]
 pub struct DecreaseLiquidityEvent {
+    pub event_version: u8,
     pub liquidity_amount: u128,
     pub token_min_a: u64,
     pub token_min_b: u64,
@@ -29,28 +32,51 @@ pub struct DecreaseLiquidityEvent {
     pub token_owner_account_b: Pubkey,
     pub delta_a: u64,
     pub delta_b: u64,
+    pub early_exit_fee_a: u64,
+    pub early_exit_fee_b: u64,
     pub transfer_fee_excluded_delta_a: u64,
     pub transfer_fee_excluded_delta_b: u64,
     pub sqrt_price: u128,
     pub new_liquidity_value: u128,
     pub update_position: PositionUpdate,
     pub referral_code: String,
+    pub unwrap_sol: bool,
     pub timestamp: u64,
 }
 
+#[event]
+pub struct DustWithdrawalOwedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub dust_a: u64,
+    pub dust_b: u64,
+}
+
 /// Handles the decrease of liquidity in the protocol.
 ///
 /// This function verifies the position authority, processes the remaining accounts,
 /// calculates the liquidity delta, and transfers the appropriate amounts from the vault
 /// to the owner's accounts.
 ///
+/// A withdrawal below the dust transfer threshold of its token is not transferred but added to
+/// the principal owed by the position, so tiny decreases accumulate until the next fee collection.
+/// `token_min_a` and `token_min_b` still apply to the whole withdrawal.
+///
+/// With `unwrap_sol`, the owner account of the wrapped SOL side of the pool is closed after the
+/// transfer and its lamports are sent to the position authority, which must own the account.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts required for the liquidity modification.
 /// * `liquidity_amount` - The amount of liquidity to be decreased.
-/// * `token_min_a` - The minimum amount of token A to be transferred.
+/// * `token_min_a` - The minimum amount of token A to be transferred, after any early-exit fee.
 /// * `token_min_b` - The minimum amount of token B to be transferred.
 /// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the operation reverts.
+/// * `unwrap_sol` - Optional flag to close the wrapped SOL owner account into native lamports.
+/// * `emit_tick_updates` - Optional flag, `false` skips the `UpdateTicksEvent` to keep the logs of
+///   the transaction short. Defaults to emitting it.
 ///
 /// # Returns
 ///
@@ -61,6 +87,7 @@ pub struct DecreaseLiquidityEvent {
 /// This function will return an error if:
 /// * The position authority verification fails.
 /// * The liquidity amount is zero.
+/// * The current timestamp is past `deadline_timestamp`.
 /// * Parsing the remaining accounts fails.
 /// * Calculating the liquidity delta fails.
 /// * Calculating the modify liquidity values fails.
@@ -69,6 +96,8 @@ pub struct DecreaseLiquidityEvent {
 /// * Calculating the transfer fee excluded amounts fails.
 /// * The transfer fee excluded amounts are below the minimum thresholds.
 /// * Transferring from the vault to the owner's accounts fails.
+/// * `unwrap_sol` is set and neither token is wrapped SOL, or its owner account is not owned by the
+///   position authority.
 pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
@@ -76,12 +105,16 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
     token_min_b: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
     referral_code: Option<String>,
+    deadline_timestamp: Option<u64>,
+    unwrap_sol: Option<bool>,
+    emit_tick_updates: Option<bool>,
 ) -> Result<()> {
     // Verify position authority
     verify_position_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
     )?;
+    ctx.accounts.position.check_not_reinvested_in_slot(Clock::get()?.slot)?;
 
     // Check for zero liquidity amount
     if liquidity_amount == 0 {
@@ -91,6 +124,8 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
     // Load AiDexPool as mut from the AccountLoader
     let mut ai_dex_pool_mut = ctx.accounts.ai_dex_pool.load_mut()?;  // Mutable borrow
 
+    ai_dex_pool_mut.lock()?;
+
     // Implementing the commented checks
     if ctx.accounts.token_mint_a.key() != ai_dex_pool_mut.token_mint_a {
         return Err(ErrorCode::InvalidInputTokenMint.into());
@@ -137,7 +172,8 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
     }
 
     // Get the current clock timestamp
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let timestamp = current_timestamp_u64(ctx.remaining_accounts)?;
+    check_deadline(timestamp, deadline_timestamp)?;
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
@@ -146,6 +182,18 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         &[
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
+            AccountsType::PoolSnapshots,
+            AccountsType::Blocklists,
+        ],
+    )?;
+
+    check_blocklist(
+        &remaining_accounts.blocklists,
+        &ai_dex_pool_mut,
+        &[
+            ctx.accounts.position_authority.key(),
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
         ],
     )?;
 
@@ -179,16 +227,27 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         liquidity_delta,
     )?;
 
+    // Charge the early-exit fee of temporary pools, the fee stays in the vaults as protocol fees
+    let early_exit_fee_a = ai_dex_pool_mut.early_exit_fee_at(delta_a, timestamp);
+    let early_exit_fee_b = ai_dex_pool_mut.early_exit_fee_at(delta_b, timestamp);
+    ai_dex_pool_mut.add_protocol_fees_owed(early_exit_fee_a, early_exit_fee_b);
+    let withdrawn_a = delta_a - early_exit_fee_a;
+    let withdrawn_b = delta_b - early_exit_fee_b;
+
+    // Withdrawals below the dust transfer thresholds stay in the vaults, owed to the position
+    let (dust_a, dust_b) = ai_dex_pool_mut.dust_withdrawals(withdrawn_a, withdrawn_b);
+    ctx.accounts.position.add_principal_owed(dust_a, dust_b);
+
     drop(ai_dex_pool_mut);
 
     // Calculate transfer fee excluded amounts
     let transfer_fee_excluded_delta_a = calculate_transfer_fee_excluded_amount(
         &ctx.accounts.token_mint_a,
-        delta_a
+        withdrawn_a
     )?;
     let transfer_fee_excluded_delta_b = calculate_transfer_fee_excluded_amount(
         &ctx.accounts.token_mint_b,
-        delta_b
+        withdrawn_b
     )?;
 
     // Check if transfer fee excluded amounts are above minimum thresholds
@@ -199,6 +258,11 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::TokenAmountBelowMinimumError.into());
     }
 
+    ctx.accounts.position.record_withdrawal(
+        transfer_fee_excluded_delta_a.amount,
+        transfer_fee_excluded_delta_b.amount,
+    );
+
     // Transfer from vault to owner for token A
     transfer_from_vault_to_owner(
         &ctx.accounts.ai_dex_pool,
@@ -208,7 +272,7 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.token_program_a,
         &ctx.accounts.memo_program,
         &remaining_accounts.transfer_hook_a,
-        delta_a,
+        withdrawn_a - dust_a,
         transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
     )?;
 
@@ -221,20 +285,60 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.token_program_b,
         &ctx.accounts.memo_program,
         &remaining_accounts.transfer_hook_b,
-        delta_b,
+        withdrawn_b - dust_b,
         transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
     )?;
 
-    emit!(UpdateTicksEvent {
-        tick_lower_index: ctx.accounts.position.tick_lower_index,
-        tick_lower_update: update.tick_lower_update,
-        tick_upper_index: ctx.accounts.position.tick_upper_index,
-        tick_upper_update: update.tick_upper_update,
-        tick_array_lower: ctx.accounts.tick_array_lower.key(),
-        tick_array_upper: ctx.accounts.tick_array_upper.key(),
-    });
+    // Unwrap the withdrawn wrapped SOL to the position authority, saving a separate close
+    let unwrap_sol = unwrap_sol.unwrap_or(false);
+    if unwrap_sol {
+        let unwrap_a = ctx.accounts.token_mint_a.key() == native_mint::ID;
+        let unwrap_b = ctx.accounts.token_mint_b.key() == native_mint::ID;
+        if !unwrap_a && !unwrap_b {
+            return Err(ErrorCode::InvalidWsolUnwrapAccount.into());
+        }
+        if unwrap_a {
+            unwrap_native_token_account(
+                &ctx.accounts.position_authority,
+                &ctx.accounts.token_owner_account_a,
+                &ctx.accounts.token_program_a,
+            )?;
+        }
+        if unwrap_b {
+            unwrap_native_token_account(
+                &ctx.accounts.position_authority,
+                &ctx.accounts.token_owner_account_b,
+                &ctx.accounts.token_program_b,
+            )?;
+        }
+    }
+
+    ctx.accounts.ai_dex_pool.load_mut()?.unlock();
+
+    if emit_tick_updates.unwrap_or(true) {
+        emit!(UpdateTicksEvent {
+            event_version: EVENT_VERSION,
+            tick_lower_index: ctx.accounts.position.tick_lower_index,
+            tick_lower_update: update.tick_lower_update,
+            tick_upper_index: ctx.accounts.position.tick_upper_index,
+            tick_upper_update: update.tick_upper_update,
+            tick_array_lower: ctx.accounts.tick_array_lower.key(),
+            tick_array_upper: ctx.accounts.tick_array_upper.key(),
+        });
+    }
+
+    if dust_a > 0 || dust_b > 0 {
+        emit!(DustWithdrawalOwedEvent {
+            event_version: EVENT_VERSION,
+            ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+            position: ctx.accounts.position.key(),
+            dust_a,
+            dust_b,
+        });
+    }
 
     emit!(DecreaseLiquidityEvent {
+        event_version: EVENT_VERSION,
         liquidity_amount,
         token_min_a,
         token_min_b,
@@ -249,14 +353,19 @@ pub fn decrease_liquidity_handler<'a, 'b, 'c, 'info>(
         token_owner_account_b: ctx.accounts.token_owner_account_b.key(),
         delta_a,
         delta_b,
+        early_exit_fee_a,
+        early_exit_fee_b,
         transfer_fee_excluded_delta_a: transfer_fee_excluded_delta_a.amount,
         transfer_fee_excluded_delta_b: transfer_fee_excluded_delta_b.amount,
         sqrt_price: ctx.accounts.ai_dex_pool.load()?.sqrt_price,
         new_liquidity_value: ctx.accounts.ai_dex_pool.load()?.liquidity,
         update_position: update.position_update,
         referral_code: referral_code.unwrap_or_default(),
+        unwrap_sol,
         timestamp,
     });
 
+    refresh_pool_snapshots(&remaining_accounts.pool_snapshots, &[&ctx.accounts.ai_dex_pool])?;
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::util::RemainingAccountsInfo;
+
+use super::{decrease_liquidity_handler, ModifyLiquidity};
+
+/// Decreases the liquidity of a position by a share of its current liquidity.
+///
+/// The liquidity withdrawn is computed from the position at execution time, so that it stays
+/// accurate when the liquidity of the position changed after the client read it, for instance
+/// after its fees were reinvested. A share of 10,000 basis points withdraws all the liquidity.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the liquidity modification.
+/// * `liquidity_bps` - The share of the liquidity of the position to withdraw, in basis points.
+/// * `token_min_a` - The minimum amount of token A to be transferred, after any early-exit fee.
+/// * `token_min_b` - The minimum amount of token B to be transferred.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the operation reverts.
+/// * `unwrap_sol` - Optional flag to close the wrapped SOL owner account into native lamports.
+/// * `emit_tick_updates` - Optional flag, `false` skips the `UpdateTicksEvent`.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The share is zero or above 10,000 basis points.
+/// * The share of the liquidity rounds down to zero.
+/// * Decreasing the liquidity fails, see `decrease_liquidity_handler`.
+pub fn decrease_liquidity_by_bps_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
+    liquidity_bps: u16,
+    token_min_a: u64,
+    token_min_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    referral_code: Option<String>,
+    deadline_timestamp: Option<u64>,
+    unwrap_sol: Option<bool>,
+    emit_tick_updates: Option<bool>,
+) -> Result<()> {
+    let liquidity_amount = ctx.accounts.position.liquidity_share(liquidity_bps)?;
+
+    decrease_liquidity_handler(
+        ctx,
+        liquidity_amount,
+        token_min_a,
+        token_min_b,
+        remaining_accounts_info,
+        referral_code,
+        deadline_timestamp,
+        unwrap_sol,
+        emit_tick_updates,
+    )
+}
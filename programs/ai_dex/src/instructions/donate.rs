@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{to_timestamp_u64, transfer_from_owner_to_vault};
+
+#[event]
+pub struct DonationEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub donor: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub donated_amount_a: u64,
+    pub donated_amount_b: u64,
+    pub liquidity: u128,
+    pub fee_growth_global_a: u128,
+    pub fee_growth_global_b: u128,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+pub struct Donate<'info> {
+    pub donor: Signer<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        constraint = token_program_a.key() == *token_mint_a.to_account_info().owner
+    )]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = token_program_b.key() == *token_mint_b.to_account_info().owner
+    )]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == token_mint_a.key())]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == token_mint_b.key())]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Donates tokens to the liquidity providers of a pool.
+///
+/// The donated tokens are transferred into the vaults and distributed pro-rata to the in-range
+/// liquidity by growing the global fee growths, so they are collected like swap fees.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the donation.
+/// * `amount_a` - The amount of token A sent by the donor.
+/// * `amount_b` - The amount of token B sent by the donor.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Errors
+///
+/// * `ErrorCode::ZeroDonationAmount` - If both amounts are zero.
+/// * `ErrorCode::DonationWithoutLiquidity` - If the pool has no in-range liquidity.
+pub fn donate_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Donate<'info>>,
+    amount_a: u64,
+    amount_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if amount_a == 0 && amount_b == 0 {
+        return Err(ErrorCode::ZeroDonationAmount.into());
+    }
+
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    ai_dex_pool.lock()?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    // Only the amounts received by the vaults are distributed
+    let donated_amount_a = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, amount_a)?.amount;
+    let donated_amount_b = calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, amount_b)?.amount;
+    ai_dex_pool.add_donation(donated_amount_a, donated_amount_b)?;
+
+    if amount_a > 0 {
+        transfer_from_owner_to_vault(
+            &ctx.accounts.donor,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            amount_a,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_from_owner_to_vault(
+            &ctx.accounts.donor,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            amount_b,
+        )?;
+    }
+
+    ai_dex_pool.unlock();
+
+    emit!(DonationEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        donor: ctx.accounts.donor.key(),
+        amount_a,
+        amount_b,
+        donated_amount_a,
+        donated_amount_b,
+        liquidity: ai_dex_pool.liquidity,
+        fee_growth_global_a: ai_dex_pool.fee_growth_global_a,
+        fee_growth_global_b: ai_dex_pool.fee_growth_global_b,
+        timestamp: to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    });
+
+    Ok(())
+}
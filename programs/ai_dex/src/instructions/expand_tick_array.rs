@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+#[event]
+pub struct TickArrayExpandedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+    pub funder: Pubkey,
+    pub rent_paid: u64,
+}
+
+#[derive(Accounts)]
+pub struct ExpandTickArray<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: checked by load_tick_array, must be a TickArrayV2
+    #[account(mut, constraint = load_tick_array(&tick_array)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Expands a full `TickArrayV2` back to the `TickArray` layout in place.
+///
+/// Anyone can expand a compact tick array once it runs out of slots; the funder pays the
+/// additional rent.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The tick array already uses the `TickArray` layout.
+pub fn expand_tick_array_handler(ctx: Context<ExpandTickArray>) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+
+    let tick_array = {
+        let data = tick_array_info.try_borrow_data()?;
+        if data[..8] != TickArrayV2::DISCRIMINATOR {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        bytemuck::from_bytes::<TickArrayV2>(&data[8..TickArrayV2::LEN]).to_tick_array()
+    };
+
+    let rent_exempt = Rent::get()?.minimum_balance(TickArray::LEN);
+    let rent_paid = rent_exempt.saturating_sub(tick_array_info.lamports());
+    if rent_paid > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: tick_array_info.clone(),
+                },
+            ),
+            rent_paid,
+        )?;
+    }
+
+    tick_array_info.realloc(TickArray::LEN, true)?;
+    {
+        let mut data = tick_array_info.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(&TickArray::DISCRIMINATOR);
+        data[8..TickArray::LEN].copy_from_slice(bytemuck::bytes_of(&tick_array));
+    }
+
+    emit!(TickArrayExpandedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index: tick_array.start_tick_index,
+        funder: ctx.accounts.funder.key(),
+        rent_paid,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+
+use super::migrate_account::{grow_account, versioned_account_layout};
+
+#[event]
+pub struct AccountExtendedEvent {
+    pub event_version: u8,
+    pub account: Pubkey,
+    pub payer: Pubkey,
+    pub old_len: u64,
+    pub new_len: u64,
+    pub rent_paid: u64,
+}
+
+#[derive(Accounts)]
+pub struct ExtendAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: the discriminator is checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows an `AiDexConfig`, `AiDexPool`, `Position`, `SuperAdmin` or `AiDexReinvestments` account
+/// to its current length without changing its version.
+///
+/// Pools and positions keep reserved space for future fields, which `migrate_account` only
+/// has to stamp with the new version. Legacy accounts created before the reserved space can be
+/// grown ahead of time with this instruction, so that an upgrade never has to realloc many
+/// accounts at once. Anyone can extend an account; the payer covers the additional rent.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The account is not an `AiDexConfig`, `AiDexPool`, `Position`, `SuperAdmin` or
+///   `AiDexReinvestments`.
+/// - The account already has its current length.
+pub fn extend_account_handler(ctx: Context<ExtendAccount>) -> Result<()> {
+    let account_info = ctx.accounts.account.to_account_info();
+
+    let (len, _, _) = versioned_account_layout(&account_info)?;
+    let old_len = account_info.data_len();
+    if old_len >= len {
+        return Err(ErrorCode::AccountAlreadyExtended.into());
+    }
+
+    let rent_paid = grow_account(&account_info, &ctx.accounts.payer, &ctx.accounts.system_program, len)?;
+
+    emit!(AccountExtendedEvent {
+        event_version: EVENT_VERSION,
+        account: ctx.accounts.account.key(),
+        payer: ctx.accounts.payer.key(),
+        old_len: old_len as u64,
+        new_len: len as u64,
+        rent_paid,
+    });
+
+    Ok(())
+}
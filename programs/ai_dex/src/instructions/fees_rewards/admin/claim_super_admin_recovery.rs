@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::state::super_admin::SuperAdmin;
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct SuperAdminRecoveryClaimedEvent {
+    pub event_version: u8,
+    pub old_super_admin: Pubkey,
+    pub new_super_admin: Pubkey,
+}
+
+/// Accounts required for claiming the recovery of the SuperAdmin account.
+#[derive(Accounts)]
+pub struct ClaimSuperAdminRecovery<'info> {
+    #[account(mut)]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    #[account(address = super_admin_account.recovery_authority)]
+    pub recovery_authority: Signer<'info>,
+}
+
+/// Hands the super admin role to the recovery authority.
+///
+/// The recovery must have been requested with `request_super_admin_recovery` at least the claim
+/// delay ago, without the super admin signing an admin instruction since.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the required accounts.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the claim is successful,
+/// or an `Err` if an error occurs.
+pub fn claim_super_admin_recovery_handler(ctx: Context<ClaimSuperAdminRecovery>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let super_admin_account = &mut ctx.accounts.super_admin_account;
+    let old_super_admin = super_admin_account.super_admin;
+
+    super_admin_account.claim_recovery(timestamp)?;
+
+    emit!(SuperAdminRecoveryClaimedEvent {
+        event_version: EVENT_VERSION,
+        old_super_admin,
+        new_super_admin: super_admin_account.super_admin,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::super_admin::SuperAdmin;
 use crate::errors::ErrorCode;
+use crate::util::to_timestamp_u64;
 
 /// Account required for creating the SuperAdmin account.
 #[derive(Accounts)]
@@ -51,7 +52,7 @@ pub fn create_super_admin_handler(
     }
 
     // Initialize the super admin
-    super_admin_account.initialize(super_admin);
+    super_admin_account.initialize(super_admin, to_timestamp_u64(Clock::get()?.unix_timestamp)?);
 
     Ok(())
 }
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::state::{FeatureGate, SuperAdmin};
+
+use super::record_super_admin_activity;
+
+#[event]
+pub struct FeatureGateInitializedEvent {
+    pub event_version: u8,
+    pub feature_gate: Pubkey,
+    pub super_admin: Pubkey,
+}
+
+/// Accounts required for creating the FeatureGate account.
+#[derive(Accounts)]
+pub struct InitializeFeatureGate<'info> {
+    #[account(mut)]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    #[account(mut, address = super_admin_account.super_admin)]
+    pub super_admin_address: Signer<'info>,
+
+    #[account(
+        init,
+        payer = super_admin_address,
+        space = FeatureGate::LEN,
+        seeds = [b"feature_gate".as_ref()],
+        bump
+    )]
+    pub feature_gate: Account<'info, FeatureGate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the feature gate of the program, with every feature disabled.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the required accounts.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the initialization is successful,
+/// or an `Err` if an error occurs.
+pub fn initialize_feature_gate_handler(ctx: Context<InitializeFeatureGate>) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
+    ctx.accounts.feature_gate.initialize(ctx.bumps.feature_gate);
+
+    emit!(FeatureGateInitializedEvent {
+        event_version: EVENT_VERSION,
+        feature_gate: ctx.accounts.feature_gate.key(),
+        super_admin: ctx.accounts.super_admin_address.key(),
+    });
+
+    Ok(())
+}
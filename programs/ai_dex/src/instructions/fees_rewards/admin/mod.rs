@@ -1,5 +1,15 @@
+pub mod claim_super_admin_recovery;
 pub mod create_super_admin;
+pub mod initialize_feature_gate;
+pub mod request_super_admin_recovery;
+pub mod set_feature_enabled;
+pub mod set_super_admin_recovery;
 pub mod update_super_admin;
 
+pub use claim_super_admin_recovery::*;
 pub use create_super_admin::*;
-pub use update_super_admin::*;
\ No newline at end of file
+pub use initialize_feature_gate::*;
+pub use request_super_admin_recovery::*;
+pub use set_feature_enabled::*;
+pub use set_super_admin_recovery::*;
+pub use update_super_admin::*;
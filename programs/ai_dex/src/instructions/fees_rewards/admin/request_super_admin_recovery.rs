@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::state::super_admin::{SuperAdmin, SUPER_ADMIN_RECOVERY_CLAIM_DELAY};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct SuperAdminRecoveryRequestedEvent {
+    pub event_version: u8,
+    pub super_admin: Pubkey,
+    pub recovery_authority: Pubkey,
+    pub last_active_timestamp: u64,
+    pub claimable_timestamp: u64,
+}
+
+#[event]
+pub struct SuperAdminRecoveryCancelledEvent {
+    pub event_version: u8,
+    pub super_admin: Pubkey,
+    pub recovery_authority: Pubkey,
+}
+
+/// Accounts required for requesting the recovery of the SuperAdmin account.
+#[derive(Accounts)]
+pub struct RequestSuperAdminRecovery<'info> {
+    #[account(mut)]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    #[account(address = super_admin_account.recovery_authority)]
+    pub recovery_authority: Signer<'info>,
+}
+
+/// Requests the super admin role for the recovery authority.
+///
+/// The request can be made once the super admin has not signed any admin instruction for the
+/// recovery inactivity timeout. The role can be claimed with `claim_super_admin_recovery` after
+/// the claim delay, unless the super admin signs an admin instruction in the meantime.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the required accounts.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the request is successful,
+/// or an `Err` if an error occurs.
+pub fn request_super_admin_recovery_handler(ctx: Context<RequestSuperAdminRecovery>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let super_admin_account = &mut ctx.accounts.super_admin_account;
+
+    super_admin_account.request_recovery(timestamp)?;
+
+    emit!(SuperAdminRecoveryRequestedEvent {
+        event_version: EVENT_VERSION,
+        super_admin: super_admin_account.super_admin,
+        recovery_authority: super_admin_account.recovery_authority,
+        last_active_timestamp: super_admin_account.last_active_timestamp,
+        claimable_timestamp: timestamp.saturating_add(SUPER_ADMIN_RECOVERY_CLAIM_DELAY),
+    });
+
+    Ok(())
+}
+
+/// Records an admin instruction signed by the super admin, which postpones recovery and cancels
+/// any pending recovery request.
+pub fn record_super_admin_activity(super_admin_account: &mut Account<SuperAdmin>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    if super_admin_account.record_activity(timestamp) {
+        emit!(SuperAdminRecoveryCancelledEvent {
+            event_version: EVENT_VERSION,
+            super_admin: super_admin_account.super_admin,
+            recovery_authority: super_admin_account.recovery_authority,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::state::{Feature, FeatureGate, SuperAdmin};
+
+use super::record_super_admin_activity;
+
+#[event]
+pub struct FeatureEnabledSetEvent {
+    pub event_version: u8,
+    pub super_admin: Pubkey,
+    pub feature: Feature,
+    pub enabled: bool,
+    pub enabled_features: u64,
+}
+
+/// Accounts required for switching a feature of the FeatureGate account.
+#[derive(Accounts)]
+pub struct SetFeatureEnabled<'info> {
+    #[account(mut)]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    #[account(address = super_admin_account.super_admin)]
+    pub super_admin_address: Signer<'info>,
+
+    #[account(mut, seeds = [b"feature_gate".as_ref()], bump = feature_gate.bump)]
+    pub feature_gate: Account<'info, FeatureGate>,
+}
+
+/// Enables or disables a feature of the program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the required accounts.
+/// * `feature` - The feature to switch.
+/// * `enabled` - Whether the feature is enabled.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the update is successful,
+/// or an `Err` if an error occurs.
+pub fn set_feature_enabled_handler(
+    ctx: Context<SetFeatureEnabled>,
+    feature: Feature,
+    enabled: bool,
+) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
+    let feature_gate = &mut ctx.accounts.feature_gate;
+    feature_gate.set_feature_enabled(feature, enabled);
+
+    emit!(FeatureEnabledSetEvent {
+        event_version: EVENT_VERSION,
+        super_admin: ctx.accounts.super_admin_address.key(),
+        feature,
+        enabled,
+        enabled_features: feature_gate.enabled_features,
+    });
+
+    Ok(())
+}
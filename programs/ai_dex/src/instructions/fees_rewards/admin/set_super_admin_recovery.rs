@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::state::super_admin::SuperAdmin;
+
+use super::record_super_admin_activity;
+
+#[event]
+pub struct SuperAdminRecoverySetEvent {
+    pub event_version: u8,
+    pub super_admin: Pubkey,
+    pub recovery_authority: Pubkey,
+    pub recovery_inactivity_timeout: u64,
+}
+
+/// Accounts required for setting the recovery authority of the SuperAdmin account.
+#[derive(Accounts)]
+pub struct SetSuperAdminRecovery<'info> {
+    #[account(mut)]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    #[account(address = super_admin_account.super_admin)]
+    pub super_admin_address: Signer<'info>,
+}
+
+/// Sets the key allowed to recover the super admin role after a period of inactivity.
+///
+/// If the super admin does not sign any admin instruction for `recovery_inactivity_timeout`
+/// seconds, the recovery authority can request the role with `request_super_admin_recovery` and
+/// claim it with `claim_super_admin_recovery` once the claim delay has passed. Setting the
+/// default pubkey with a zero timeout disables recovery.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the required accounts.
+/// * `recovery_authority` - The key allowed to recover the super admin role.
+/// * `recovery_inactivity_timeout` - The inactivity period in seconds after which recovery is allowed.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the update is successful,
+/// or an `Err` if an error occurs.
+pub fn set_super_admin_recovery_handler(
+    ctx: Context<SetSuperAdminRecovery>,
+    recovery_authority: Pubkey,
+    recovery_inactivity_timeout: u64,
+) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
+    let super_admin_account = &mut ctx.accounts.super_admin_account;
+    super_admin_account.update_recovery(recovery_authority, recovery_inactivity_timeout)?;
+
+    emit!(SuperAdminRecoverySetEvent {
+        event_version: EVENT_VERSION,
+        super_admin: super_admin_account.super_admin,
+        recovery_authority,
+        recovery_inactivity_timeout,
+    });
+
+    Ok(())
+}
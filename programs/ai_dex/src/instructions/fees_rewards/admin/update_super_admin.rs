@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::super_admin::SuperAdmin;
 
+use super::record_super_admin_activity;
+
 /// Accounts required for updating the SuperAdmin account.
 #[derive(Accounts)]
 pub struct UpdateSuperAdmin<'info> {
@@ -31,6 +33,8 @@ pub struct UpdateSuperAdmin<'info> {
 pub fn update_super_admin_handler(
     ctx: Context<UpdateSuperAdmin>,
 ) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
     let super_admin_account = &mut ctx.accounts.super_admin_account;
 
     // Update the super admin
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, Blocklist};
+
+#[event]
+pub struct BlocklistAddressAddedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub blocklist: Pubkey,
+    pub config_authority: Pubkey,
+    pub address: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ModifyBlocklist<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub blocklist: Account<'info, Blocklist>,
+}
+
+/// Adds an address to the blocklist of a config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for modifying the blocklist.
+/// * `address` - The address to block.
+///
+/// # Errors
+///
+/// This function will return an error if the address is already blocked or the blocklist is full.
+pub fn add_blocklist_address_handler(ctx: Context<ModifyBlocklist>, address: Pubkey) -> Result<()> {
+    ctx.accounts.blocklist.add_address(address)?;
+
+    emit!(BlocklistAddressAddedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        blocklist: ctx.accounts.blocklist.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        address,
+    });
+
+    Ok(())
+}
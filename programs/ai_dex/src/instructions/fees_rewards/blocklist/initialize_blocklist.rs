@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, Blocklist};
+
+#[event]
+pub struct BlocklistInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub blocklist: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBlocklist<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Blocklist::LEN,
+        seeds = [
+            b"blocklist".as_ref(),
+            ai_dex_config.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the empty blocklist of a config, to be enabled on pools with
+/// `set_pool_blocklist_enabled`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for creating the blocklist.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the blocklist is successfully created.
+pub fn initialize_blocklist_handler(ctx: Context<InitializeBlocklist>) -> Result<()> {
+    ctx.accounts.blocklist.initialize(ctx.accounts.ai_dex_config.key(), ctx.bumps.blocklist);
+
+    emit!(BlocklistInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        blocklist: ctx.accounts.blocklist.key(),
+    });
+
+    Ok(())
+}
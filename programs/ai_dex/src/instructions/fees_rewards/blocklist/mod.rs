@@ -0,0 +1,9 @@
+pub mod initialize_blocklist;
+pub mod add_blocklist_address;
+pub mod remove_blocklist_address;
+pub mod set_pool_blocklist_enabled;
+
+pub use initialize_blocklist::*;
+pub use add_blocklist_address::*;
+pub use remove_blocklist_address::*;
+pub use set_pool_blocklist_enabled::*;
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+
+use super::ModifyBlocklist;
+
+#[event]
+pub struct BlocklistAddressRemovedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub blocklist: Pubkey,
+    pub config_authority: Pubkey,
+    pub address: Pubkey,
+}
+
+/// Removes an address from the blocklist of a config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for modifying the blocklist.
+/// * `address` - The address to unblock.
+///
+/// # Errors
+///
+/// This function will return an error if the address is not blocked.
+pub fn remove_blocklist_address_handler(ctx: Context<ModifyBlocklist>, address: Pubkey) -> Result<()> {
+    ctx.accounts.blocklist.remove_address(address)?;
+
+    emit!(BlocklistAddressRemovedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        blocklist: ctx.accounts.blocklist.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        address,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, AiDexPool, Blocklist};
+
+#[event]
+pub struct PoolBlocklistEnabledSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub is_blocklist_enabled: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolBlocklistEnabled<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(has_one = ai_dex_config)]
+    pub blocklist: Account<'info, Blocklist>,
+}
+
+/// Enables or disables the blocklist of the config on a pool.
+///
+/// Once enabled, every swap through the pool and every liquidity change of its positions must
+/// pass the blocklist in the `Blocklists` remaining accounts, and is rejected when the authority
+/// or the owner of a token account of the instruction is blocked.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the flag.
+/// * `is_blocklist_enabled` - Whether the blocklist is checked for the pool.
+pub fn set_pool_blocklist_enabled_handler(
+    ctx: Context<SetPoolBlocklistEnabled>,
+    is_blocklist_enabled: bool,
+) -> Result<()> {
+    ctx.accounts.ai_dex_pool.load_mut()?.is_blocklist_enabled = u8::from(is_blocklist_enabled);
+
+    emit!(PoolBlocklistEnabledSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        is_blocklist_enabled,
+    });
+
+    Ok(())
+}
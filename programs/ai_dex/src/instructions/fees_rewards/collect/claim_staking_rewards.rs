@@ -0,0 +1,152 @@
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::constants::EVENT_VERSION;
+use crate::{
+    constants::transfer_memo,
+    state::*,
+    util::transfer_from_vault_to_owner,
+    errors::ErrorCode,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+#[event]
+pub struct StakingRewardsClaimedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub staking_rewards: Pubkey,
+    pub staking_authority: Pubkey,
+    pub claimed_amount_a: u64,
+    pub claimed_amount_b: u64,
+    pub token_destination_a: Pubkey,
+    pub token_destination_b: Pubkey,
+}
+
+/// Amounts claimed by `claim_staking_rewards`, set as the return data of the instruction so that
+/// the staking program can read them with `get_return_data` after the CPI.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClaimStakingRewardsReturnData {
+    pub claimed_amount_a: u64,
+    pub claimed_amount_b: u64,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        seeds = [b"staking_rewards".as_ref(), ai_dex_pool.load()?.ai_dex_config.as_ref()],
+        bump = staking_rewards.bump,
+    )]
+    pub staking_rewards: Account<'info, StakingRewards>,
+
+    #[account(address = staking_rewards.staking_authority)]
+    pub staking_authority: Signer<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a @ ErrorCode::InvalidVault)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b @ ErrorCode::InvalidVault)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, constraint = token_destination_a.mint == token_mint_a.key())]
+    pub token_destination_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, constraint = token_destination_b.mint == token_mint_b.key())]
+    pub token_destination_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_program_a.key() == token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(constraint = token_program_b.key() == token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Claims the protocol fees a pool owes to the staking rewards of its config.
+///
+/// Swaps of the pool set aside the staking reward share of their protocol fees as they accrue, so
+/// no crank or treasury operation is needed before a claim. Only the staking authority can claim,
+/// and the claimed amounts are returned to the calling program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the claim.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The pool is locked.
+/// * Parsing the remaining accounts fails.
+/// * Transferring the rewards from the vaults to the destination accounts fails.
+pub fn claim_staking_rewards_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ClaimStakingRewards<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<ClaimStakingRewardsReturnData> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    ai_dex_pool.lock()?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+
+    // Take the rewards owed before performing transfers
+    let (claimed_amount_a, claimed_amount_b) = ai_dex_pool.take_staking_rewards_owed();
+    drop(ai_dex_pool);
+
+    if claimed_amount_a > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_destination_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            claimed_amount_a,
+            transfer_memo::TRANSFER_MEMO_CLAIM_STAKING_REWARDS.as_bytes(),
+        )?;
+    }
+
+    if claimed_amount_b > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_destination_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            claimed_amount_b,
+            transfer_memo::TRANSFER_MEMO_CLAIM_STAKING_REWARDS.as_bytes(),
+        )?;
+    }
+
+    ctx.accounts.ai_dex_pool.load_mut()?.unlock();
+
+    emit!(StakingRewardsClaimedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        staking_rewards: ctx.accounts.staking_rewards.key(),
+        staking_authority: ctx.accounts.staking_authority.key(),
+        claimed_amount_a,
+        claimed_amount_b,
+        token_destination_a: ctx.accounts.token_destination_a.key(),
+        token_destination_b: ctx.accounts.token_destination_b.key(),
+    });
+
+    Ok(ClaimStakingRewardsReturnData {
+        claimed_amount_a,
+        claimed_amount_b,
+    })
+}
@@ -1,18 +1,25 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{spl_associated_token_account, AssociatedToken};
 use anchor_spl::token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
+use crate::constants::EVENT_VERSION;
 use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
 use crate::{
     constants::transfer_memo,
     state::*,
-    util::{transfer_from_vault_to_owner, verify_position_authority},
+    util::{
+        create_associated_token_account_if_needed, load_token_account, to_timestamp_u64,
+        transfer_from_vault_to_owner, transfer_from_vault_to_owner_account_info,
+        verify_position_authority,
+    },
     errors::ErrorCode,
 };
 
 #[event]
 pub struct FeesCollectedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub position_authority: Pubkey,
     pub position: Pubkey,
@@ -25,10 +32,17 @@ pub struct FeesCollectedEvent {
     pub token_vault_b: Pubkey,
     pub fee_owed_a: u64,
     pub fee_owed_b: u64,
+    pub principal_owed_a: u64,
+    pub principal_owed_b: u64,
+    pub lp_referral: Pubkey,
+    pub lp_referral_fee_a: u64,
+    pub lp_referral_fee_b: u64,
+    pub to_owned_token_accounts: bool,
 }
 
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
+    #[account(mut)]
     pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
     pub position_authority: Signer<'info>,
@@ -48,16 +62,16 @@ pub struct CollectFees<'info> {
     #[account(mut)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
 
-    // #[account(mut, constraint = token_owner_account_a.mint == ai_dex_pool.token_mint_a)]
+    /// CHECK: created if needed and checked in the handler
     #[account(mut)]
-    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_owner_account_a: UncheckedAccount<'info>,
     // #[account(mut, address = ai_dex_pool.token_vault_a)]
     #[account(mut)]
     pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // #[account(mut, constraint = token_owner_account_b.mint == ai_dex_pool.token_mint_b)]
+    /// CHECK: created if needed and checked in the handler
     #[account(mut)]
-    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_owner_account_b: UncheckedAccount<'info>,
     // #[account(mut, address = ai_dex_pool.token_vault_b)]
     #[account(mut)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
@@ -68,6 +82,24 @@ pub struct CollectFees<'info> {
     pub token_program_b: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, Memo>,
 
+    /// The LP referral's token account for token A, owned by the referral of the position
+    #[account(
+        mut,
+        constraint = lp_referral_ata_a.owner == position.lp_referral,
+        constraint = lp_referral_ata_a.mint == token_mint_a.key()
+    )]
+    pub lp_referral_ata_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// The LP referral's token account for token B, owned by the referral of the position
+    #[account(
+        mut,
+        constraint = lp_referral_ata_b.owner == position.lp_referral,
+        constraint = lp_referral_ata_b.mint == token_mint_b.key()
+    )]
+    pub lp_referral_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Required to create missing associated token accounts of the position authority
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
 /// Handles the collection of fees for a given position.
@@ -75,10 +107,24 @@ pub struct CollectFees<'info> {
 /// This function verifies the authority of the position, processes any remaining accounts,
 /// and transfers the owed fees from the vault to the owner's account.
 ///
+/// If the position is attributed to an LP referral, the referral token accounts must be provided
+/// and the referral share of the protocol fees generated by the collected fees is sent to them.
+/// The referrer withdraws it with `collect_referral_fee`.
+///
+/// The fees are collected to the associated token accounts of the position authority, or, with
+/// `to_owned_token_accounts`, to any token accounts it owns, such as the vaults of a program
+/// auto-compounding its positions.
+///
+/// Missing associated token accounts of the position authority are created within the
+/// instruction when the associated token and system programs are provided, the position
+/// authority paying the rent as a writable signer.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts required for the fee collection.
 /// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `to_owned_token_accounts` - Whether token accounts other than the associated token accounts
+///   of the position authority are accepted. Defaults to `false`.
 ///
 /// # Returns
 ///
@@ -88,18 +134,61 @@ pub struct CollectFees<'info> {
 ///
 /// This function will return an error if:
 /// * The position authority verification fails.
+/// * A missing token owner account cannot be created.
+/// * A token owner account is not an accepted token account of the position authority.
+/// * The position is attributed to an LP referral and its token accounts are missing.
 /// * Parsing the remaining accounts fails.
 /// * Transferring fees from the vault to the owner fails.
 pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectFees<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+    to_owned_token_accounts: Option<bool>,
 ) -> Result<()> {
     verify_position_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
     )?;
+    ctx.accounts.position.check_not_reinvested_in_slot(Clock::get()?.slot)?;
 
-    let ai_dex_pool = ctx.accounts.ai_dex_pool.load()?;
+    create_associated_token_account_if_needed(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+    create_associated_token_account_if_needed(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+    let token_owner_account_a =
+        load_token_account(&ctx.accounts.token_owner_account_a, &ctx.accounts.token_program_a)?;
+    let token_owner_account_b =
+        load_token_account(&ctx.accounts.token_owner_account_b, &ctx.accounts.token_program_b)?;
+
+    let to_owned_token_accounts = to_owned_token_accounts.unwrap_or(false);
+    verify_fee_destination(
+        ctx.accounts.token_owner_account_a.key(),
+        &token_owner_account_a,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program_a,
+        to_owned_token_accounts,
+    )?;
+    verify_fee_destination(
+        ctx.accounts.token_owner_account_b.key(),
+        &token_owner_account_b,
+        &ctx.accounts.position_authority,
+        &ctx.accounts.token_program_b,
+        to_owned_token_accounts,
+    )?;
+
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    ai_dex_pool.check_not_locked()?;
 
     // Validate token mints against the pool's expected mints.
     if ctx.accounts.token_mint_a.key() != ai_dex_pool.token_mint_a {
@@ -108,13 +197,13 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
     if ctx.accounts.token_mint_b.key() != ai_dex_pool.token_mint_b {
         return Err(ErrorCode::InvalidOutputTokenMint.into());
     }
-    if ctx.accounts.token_owner_account_a.mint != ai_dex_pool.token_mint_a {
+    if token_owner_account_a.mint != ai_dex_pool.token_mint_a {
         return Err(ErrorCode::InvalidTokenOwner.into());
     }
     if ctx.accounts.token_vault_a.key() != ai_dex_pool.token_vault_a {
         return Err(ErrorCode::InvalidVault.into());
     }
-    if ctx.accounts.token_owner_account_b.mint != ai_dex_pool.token_mint_b {
+    if token_owner_account_b.mint != ai_dex_pool.token_mint_b {
         return Err(ErrorCode::InvalidTokenOwner.into());
     }
     if ctx.accounts.token_vault_b.key() != ai_dex_pool.token_vault_b {
@@ -136,13 +225,31 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
     // Store the fees owed to use as transfer amounts.
     let fee_owed_a = position.fee_owed_a;
     let fee_owed_b = position.fee_owed_b;
+    let principal_owed_a = position.principal_owed_a;
+    let principal_owed_b = position.principal_owed_b;
+
+    // The referral share is paid out of the protocol fees, not the fees of the position.
+    let (lp_referral_fee_a, lp_referral_fee_b) = if position.lp_referral != Pubkey::default() {
+        if ctx.accounts.lp_referral_ata_a.is_none() || ctx.accounts.lp_referral_ata_b.is_none() {
+            return Err(ErrorCode::MissingLpReferralAta.into());
+        }
+        ai_dex_pool.take_lp_referral_fees(fee_owed_a, fee_owed_b, position.lp_referral_fee_rate)
+    } else {
+        (0, 0)
+    };
+    drop(ai_dex_pool);
 
-    // Reset fees owed on the position before transferring.
+    // Reset fees and principal owed on the position before transferring.
     position.reset_fees_owed();
+    position.reset_principal_owed();
+    position.update_last_updated_timestamp(to_timestamp_u64(Clock::get()?.unix_timestamp)?);
 
-    // Conditionally transfer owed fees for Token A if non-zero.
-    if fee_owed_a > 0 {
-        transfer_from_vault_to_owner(
+    // Conditionally transfer owed fees and principal for Token A if non-zero.
+    let amount_a = fee_owed_a
+        .checked_add(principal_owed_a)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    if amount_a > 0 {
+        transfer_from_vault_to_owner_account_info(
             &ctx.accounts.ai_dex_pool,
             &ctx.accounts.token_mint_a,
             &ctx.accounts.token_vault_a,
@@ -150,14 +257,17 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
             &ctx.accounts.token_program_a,
             &ctx.accounts.memo_program,
             &remaining_accounts.transfer_hook_a,
-            fee_owed_a,
+            amount_a,
             transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
         )?;
     }
 
-    // Conditionally transfer owed fees for Token B if non-zero.
-    if fee_owed_b > 0 {
-        transfer_from_vault_to_owner(
+    // Conditionally transfer owed fees and principal for Token B if non-zero.
+    let amount_b = fee_owed_b
+        .checked_add(principal_owed_b)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    if amount_b > 0 {
+        transfer_from_vault_to_owner_account_info(
             &ctx.accounts.ai_dex_pool,
             &ctx.accounts.token_mint_b,
             &ctx.accounts.token_vault_b,
@@ -165,12 +275,45 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
             &ctx.accounts.token_program_b,
             &ctx.accounts.memo_program,
             &remaining_accounts.transfer_hook_b,
-            fee_owed_b,
+            amount_b,
             transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
         )?;
     }
 
+    // Send the LP referral share of the protocol fees to the referral token accounts.
+    if let Some(lp_referral_ata_a) = &ctx.accounts.lp_referral_ata_a {
+        if lp_referral_fee_a > 0 {
+            transfer_from_vault_to_owner(
+                &ctx.accounts.ai_dex_pool,
+                &ctx.accounts.token_mint_a,
+                &ctx.accounts.token_vault_a,
+                lp_referral_ata_a,
+                &ctx.accounts.token_program_a,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_a,
+                lp_referral_fee_a,
+                transfer_memo::TRANSFER_MEMO_SEND_REFERRAL_FEES_TO_PDA_ATA.as_bytes(),
+            )?;
+        }
+    }
+    if let Some(lp_referral_ata_b) = &ctx.accounts.lp_referral_ata_b {
+        if lp_referral_fee_b > 0 {
+            transfer_from_vault_to_owner(
+                &ctx.accounts.ai_dex_pool,
+                &ctx.accounts.token_mint_b,
+                &ctx.accounts.token_vault_b,
+                lp_referral_ata_b,
+                &ctx.accounts.token_program_b,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_b,
+                lp_referral_fee_b,
+                transfer_memo::TRANSFER_MEMO_SEND_REFERRAL_FEES_TO_PDA_ATA.as_bytes(),
+            )?;
+        }
+    }
+
     emit!(FeesCollectedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         position_authority: ctx.accounts.position_authority.key(),
         position: ctx.accounts.position.key(),
@@ -183,7 +326,40 @@ pub fn collect_fees_handler<'a, 'b, 'c, 'info>(
         token_vault_b: ctx.accounts.token_vault_b.key(),
         fee_owed_a,
         fee_owed_b,
+        principal_owed_a,
+        principal_owed_b,
+        lp_referral: ctx.accounts.position.lp_referral,
+        lp_referral_fee_a,
+        lp_referral_fee_b,
+        to_owned_token_accounts,
     });
 
     Ok(())
 }
+
+/// Checks that a fee destination is owned by the position authority and, unless any owned token
+/// account is accepted, that it is its associated token account.
+fn verify_fee_destination(
+    token_owner_account_key: Pubkey,
+    token_owner_account: &TokenAccount,
+    position_authority: &Signer,
+    token_program: &Interface<TokenInterface>,
+    to_owned_token_accounts: bool,
+) -> Result<()> {
+    if token_owner_account.owner != position_authority.key() {
+        return Err(ErrorCode::InvalidFeeDestination.into());
+    }
+
+    if !to_owned_token_accounts {
+        let expected_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+            &position_authority.key(),
+            &token_owner_account.mint,
+            &token_program.key(),
+        );
+        if token_owner_account_key != expected_ata {
+            return Err(ErrorCode::InvalidFeeDestination.into());
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
-use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{parse_remaining_accounts, record_collected_protocol_fees, AccountsType, RemainingAccountsInfo};
+use crate::constants::EVENT_VERSION;
 use crate::{
     constants::transfer_memo,
     state::*,
@@ -11,9 +12,14 @@ use anchor_spl::memo::Memo;
 
 #[event]
 pub struct CollectProtocolFeesEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub protocol_fee_owed_a: u64,
     pub protocol_fee_owed_b: u64,
+    pub collected_amount_a: u64,
+    pub collected_amount_b: u64,
+    pub remaining_protocol_fee_owed_a: u64,
+    pub remaining_protocol_fee_owed_b: u64,
     pub token_mint_a: Pubkey,
     pub token_vault_a: Pubkey,
     pub token_destination_a: Pubkey,
@@ -69,10 +75,15 @@ pub struct CollectProtocolFees<'info> {
 /// This function processes any remaining accounts and transfers the owed protocol fees
 /// from the vault to the destination accounts.
 ///
+/// `amount_a` and `amount_b` cap the collected amounts, so that the treasury can leave a buffer
+/// in the vaults. The full amount owed is collected when they are not set.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all the accounts required for the protocol fee collection.
 /// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `amount_a` - Optional maximum amount of token A to collect.
+/// * `amount_b` - Optional maximum amount of token B to collect.
 ///
 /// # Returns
 ///
@@ -81,14 +92,20 @@ pub struct CollectProtocolFees<'info> {
 /// # Errors
 ///
 /// This function will return an error if:
+/// * A protocol fee treasury is set on the config and does not own the destination accounts.
+/// * The pool has revenue reporting enabled and its revenue snapshot is not passed.
 /// * Parsing the remaining accounts fails.
 /// * Transferring protocol fees from the vault to the destination accounts fails.
 pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFees<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+    amount_a: Option<u64>,
+    amount_b: Option<u64>,
 ) -> Result<()> {
     let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
 
+    ai_dex_pool.lock()?;
+
     // Validate mints, vaults, and destination accounts against expected pool values.
     if ctx.accounts.token_mint_a.key() != ai_dex_pool.token_mint_a {
         return Err(ErrorCode::InvalidRewardMintError.into());
@@ -108,6 +125,8 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
     if ctx.accounts.token_destination_b.mint != ai_dex_pool.token_mint_b {
         return Err(ErrorCode::InvalidTokenOwner.into());
     }
+    ctx.accounts.ai_dex_config.check_protocol_fee_destination(ctx.accounts.token_destination_a.owner)?;
+    ctx.accounts.ai_dex_config.check_protocol_fee_destination(ctx.accounts.token_destination_b.owner)?;
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
@@ -116,18 +135,21 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
         &[
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
+            AccountsType::PoolRevenueSnapshot,
         ],
     )?;
 
     let protocol_fee_owed_a = ai_dex_pool.protocol_fee_owed_a;
     let protocol_fee_owed_b = ai_dex_pool.protocol_fee_owed_b;
 
-    // Reset fees owed before performing transfers
-    ai_dex_pool.reset_protocol_fees_owed();
+    // Take the collected fees from the fees owed before performing transfers
+    let (collected_amount_a, collected_amount_b) = ai_dex_pool.take_protocol_fees_owed(amount_a, amount_b);
+    let remaining_protocol_fee_owed_a = ai_dex_pool.protocol_fee_owed_a;
+    let remaining_protocol_fee_owed_b = ai_dex_pool.protocol_fee_owed_b;
     drop(ai_dex_pool);
 
-    // Transfer the owed protocol fee for Token A if non-zero.
-    if protocol_fee_owed_a > 0 {
+    // Transfer the collected protocol fee for Token A if non-zero.
+    if collected_amount_a > 0 {
         transfer_from_vault_to_owner(
             &ctx.accounts.ai_dex_pool,
             &ctx.accounts.token_mint_a,
@@ -136,13 +158,13 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
             &ctx.accounts.token_program_a,
             &ctx.accounts.memo_program,
             &remaining_accounts.transfer_hook_a,
-            protocol_fee_owed_a,
+            collected_amount_a,
             transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
         )?;
     }
 
-    // Transfer the owed protocol fee for Token B if non-zero.
-    if protocol_fee_owed_b > 0 {
+    // Transfer the collected protocol fee for Token B if non-zero.
+    if collected_amount_b > 0 {
         transfer_from_vault_to_owner(
             &ctx.accounts.ai_dex_pool,
             &ctx.accounts.token_mint_b,
@@ -151,15 +173,29 @@ pub fn collect_protocol_fees_handler<'a, 'b, 'c, 'info>(
             &ctx.accounts.token_program_b,
             &ctx.accounts.memo_program,
             &remaining_accounts.transfer_hook_b,
-            protocol_fee_owed_b,
+            collected_amount_b,
             transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
         )?;
     }
 
+    ctx.accounts.ai_dex_pool.load_mut()?.unlock();
+
+    record_collected_protocol_fees(
+        &remaining_accounts.pool_revenue_snapshot,
+        &ctx.accounts.ai_dex_pool,
+        collected_amount_a,
+        collected_amount_b,
+    )?;
+
     emit!(CollectProtocolFeesEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         protocol_fee_owed_a,
         protocol_fee_owed_b,
+        collected_amount_a,
+        collected_amount_b,
+        remaining_protocol_fee_owed_a,
+        remaining_protocol_fee_owed_b,
         token_mint_a: ctx.accounts.token_mint_a.key(),
         token_vault_a: ctx.accounts.token_vault_a.key(),
         token_destination_a: ctx.accounts.token_destination_a.key(),
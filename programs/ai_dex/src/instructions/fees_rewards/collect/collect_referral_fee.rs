@@ -1,5 +1,6 @@
 use crate::constants::transfer_memo;
-use crate::util::{parse_remaining_accounts, transfer_from_referral_to_owner, AccountsType, RemainingAccountsInfo};
+use crate::constants::EVENT_VERSION;
+use crate::util::{parse_remaining_accounts, transfer_from_referral_to_owner, transfer_from_vault_to_owner, AccountsType, RemainingAccountsInfo};
 use crate::{
     state::*,
     errors::ErrorCode,
@@ -11,6 +12,7 @@ use anchor_spl::memo::Memo;
 
 #[event]
 pub struct CollectReferralFeesEvent {
+    pub event_version: u8,
     pub swap_referral: Pubkey,
     pub referrer_address: Pubkey,
     pub token_mint: Pubkey,
@@ -18,6 +20,17 @@ pub struct CollectReferralFeesEvent {
     pub destination_token_account: Pubkey,
 }
 
+#[event]
+pub struct CollectEscrowedReferralFeesEvent {
+    pub event_version: u8,
+    pub swap_referral: Pubkey,
+    pub referral_fee_escrow: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub destination_token_account: Pubkey,
+}
+
 #[derive(Accounts)]
 pub struct CollectReferralFees<'info> {
     /// The swap referral account
@@ -59,8 +72,34 @@ pub struct CollectReferralFees<'info> {
 
     /// Rent sysvar
     pub rent: Sysvar<'info, Rent>,
+
+    /// The referral fee escrow of the swap referral on a pool, whose fees owed in the token mint
+    /// are collected from the pool vault
+    #[account(mut, has_one = swap_referral)]
+    pub referral_fee_escrow: Option<Account<'info, ReferralFeeEscrow>>,
+
+    /// The pool of the referral fee escrow
+    pub ai_dex_pool: Option<AccountLoader<'info, AiDexPool>>,
+
+    /// The pool vault of the token mint
+    #[account(mut)]
+    pub token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
+/// Collects referral fees of a swap referral to the token account of the referrer.
+///
+/// `amount` is withdrawn from the referral token account. When the referral fee escrow of the
+/// referral on a pool is passed along with the pool and its vault of the token mint, the fees
+/// owed by the escrow in the token mint are also transferred from the vault, and `amount` may
+/// then be 0.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidSwapReferralAta` - If the referral token account is not the associated
+///   token account of the referral.
+/// * `ErrorCode::ZeroReferralAmountWithdrawal` - If `amount` is 0 and no escrow is passed.
+/// * `ErrorCode::InvalidReferralFeeEscrow` - If the escrow is passed without its pool or vault,
+///   or they do not match the escrow and the token mint.
 pub fn collect_referral_fees_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectReferralFees<'info>>,
     amount: u64,
@@ -77,7 +116,7 @@ pub fn collect_referral_fees_handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::InvalidSwapReferralAta.into());
     }
 
-    if amount == 0 {
+    if amount == 0 && ctx.accounts.referral_fee_escrow.is_none() {
         return Err(ErrorCode::ZeroReferralAmountWithdrawal.into());
     }
 
@@ -88,20 +127,69 @@ pub fn collect_referral_fees_handler<'a, 'b, 'c, 'info>(
         &[AccountsType::TransferHookReferralFee],
     )?;
 
+    if let Some(referral_fee_escrow) = ctx.accounts.referral_fee_escrow.as_mut() {
+        let (Some(ai_dex_pool), Some(token_vault)) = (&ctx.accounts.ai_dex_pool, &ctx.accounts.token_vault) else {
+            return Err(ErrorCode::InvalidReferralFeeEscrow.into());
+        };
+        if referral_fee_escrow.ai_dex_pool != ai_dex_pool.key() {
+            return Err(ErrorCode::InvalidReferralFeeEscrow.into());
+        }
+        let is_token_a = {
+            let pool = ai_dex_pool.load()?;
+            pool.check_not_locked()?;
+            let token_mint = ctx.accounts.token_mint.key();
+            if token_mint == pool.token_mint_a && token_vault.key() == pool.token_vault_a {
+                true
+            } else if token_mint == pool.token_mint_b && token_vault.key() == pool.token_vault_b {
+                false
+            } else {
+                return Err(ErrorCode::InvalidReferralFeeEscrow.into());
+            }
+        };
+
+        let escrowed_amount = referral_fee_escrow.take_fee_owed(is_token_a);
+        if escrowed_amount > 0 {
+            transfer_from_vault_to_owner(
+                ai_dex_pool,
+                &ctx.accounts.token_mint,
+                token_vault,
+                &ctx.accounts.destination_token_account,
+                &ctx.accounts.token_program,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_referral_fee,
+                escrowed_amount,
+                transfer_memo::TRANSFER_MEMO_COLLECT_REFERRAL_FEES.as_bytes(),
+            )?;
+        }
+
+        emit!(CollectEscrowedReferralFeesEvent {
+            event_version: EVENT_VERSION,
+            swap_referral: ctx.accounts.swap_referral.key(),
+            referral_fee_escrow: referral_fee_escrow.key(),
+            ai_dex_pool: ai_dex_pool.key(),
+            token_mint: ctx.accounts.token_mint.key(),
+            amount: escrowed_amount,
+            destination_token_account: ctx.accounts.destination_token_account.key(),
+        });
+    }
+
     // Transfer tokens from referral ATA to the referrer's destination token account
-    transfer_from_referral_to_owner(
-        &ctx.accounts.swap_referral, // Authority: swap_referral PDA
-        &ctx.accounts.token_mint,
-        &ctx.accounts.referral_ata,
-        &ctx.accounts.destination_token_account,
-        &ctx.accounts.token_program,
-        &ctx.accounts.memo_program,
-        &remaining_accounts.transfer_hook_referral_fee,
-        amount,
-        transfer_memo::TRANSFER_MEMO_COLLECT_REFERRAL_FEES.as_bytes()
-    )?;
+    if amount > 0 {
+        transfer_from_referral_to_owner(
+            &ctx.accounts.swap_referral, // Authority: swap_referral PDA
+            &ctx.accounts.token_mint,
+            &ctx.accounts.referral_ata,
+            &ctx.accounts.destination_token_account,
+            &ctx.accounts.token_program,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_referral_fee,
+            amount,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REFERRAL_FEES.as_bytes()
+        )?;
+    }
 
     emit!(CollectReferralFeesEvent {
+        event_version: EVENT_VERSION,
         swap_referral: ctx.accounts.swap_referral.key(),
         referrer_address: ctx.accounts.referrer_address.key(),
         token_mint: ctx.accounts.token_mint.key(),
@@ -0,0 +1,153 @@
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::constants::EVENT_VERSION;
+use crate::{
+    constants::transfer_memo,
+    state::*,
+    util::transfer_from_vault_to_owner,
+    errors::ErrorCode,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+#[event]
+pub struct CollectReinvestmentFeesEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub reinvestments_account: Pubkey,
+    pub reinvestments_authority: Pubkey,
+    pub collected_amount_a: u64,
+    pub collected_amount_b: u64,
+    pub token_destination_a: Pubkey,
+    pub token_destination_b: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct CollectReinvestmentFees<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        constraint = reinvestments_account.is_usable_by_config(&ai_dex_pool.load()?.ai_dex_config)
+            @ ErrorCode::InvalidReinvestmentsAccount
+    )]
+    pub reinvestments_account: Account<'info, AiDexReinvestments>,
+
+    #[account(address = reinvestments_account.reinvestments_authority)]
+    pub reinvestments_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"reinvestment_fee_escrow".as_ref(),
+            reinvestments_account.key().as_ref(),
+            ai_dex_pool.key().as_ref(),
+        ],
+        bump = reinvestment_fee_escrow.bump,
+    )]
+    pub reinvestment_fee_escrow: Box<Account<'info, ReinvestmentFeeEscrow>>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a @ ErrorCode::InvalidVault)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b @ ErrorCode::InvalidVault)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, constraint = token_destination_a.mint == token_mint_a.key())]
+    pub token_destination_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, constraint = token_destination_b.mint == token_mint_b.key())]
+    pub token_destination_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = token_program_a.key() == token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(constraint = token_program_b.key() == token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Collects the reinvestment fees owed by a pool to a reinvestments account.
+///
+/// Reinvestment fees are taken from the fees of positions when they are reinvested, and are owed
+/// separately from the protocol fees of the pool. Each reinvestments account collects only the
+/// fees of the reinvestments made through it, recorded in its escrow for the pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the collection.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The pool is locked.
+/// * Parsing the remaining accounts fails.
+/// * Transferring the fees from the vaults to the destination accounts fails.
+pub fn collect_reinvestment_fees_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectReinvestmentFees<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    ai_dex_pool.lock()?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+
+    // Take the fees owed before performing transfers
+    let (collected_amount_a, collected_amount_b) = ctx.accounts.reinvestment_fee_escrow.take_fees_owed();
+    ai_dex_pool.remove_reinvestment_fees_owed(collected_amount_a, collected_amount_b);
+    drop(ai_dex_pool);
+
+    if collected_amount_a > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_destination_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            collected_amount_a,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REINVESTMENT_FEES.as_bytes(),
+        )?;
+    }
+
+    if collected_amount_b > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_destination_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            collected_amount_b,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REINVESTMENT_FEES.as_bytes(),
+        )?;
+    }
+
+    ctx.accounts.ai_dex_pool.load_mut()?.unlock();
+
+    emit!(CollectReinvestmentFeesEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reinvestments_account: ctx.accounts.reinvestments_account.key(),
+        reinvestments_authority: ctx.accounts.reinvestments_authority.key(),
+        collected_amount_a,
+        collected_amount_b,
+        token_destination_a: ctx.accounts.token_destination_a.key(),
+        token_destination_b: ctx.accounts.token_destination_b.key(),
+    });
+
+    Ok(())
+}
@@ -1,18 +1,24 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
+use crate::constants::EVENT_VERSION;
 use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
 use crate::{
     constants::transfer_memo,
     state::*,
-    util::{transfer_from_vault_to_owner, verify_position_authority},
+    util::{
+        create_associated_token_account_if_needed, load_token_account, to_timestamp_u64,
+        transfer_from_vault_to_owner_account_info, verify_position_authority,
+    },
     errors::ErrorCode,
 };
 
 #[event]
 pub struct RewardCollectedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub position_key: Pubkey,
     pub position_authority: Pubkey,
@@ -39,11 +45,9 @@ pub struct CollectReward<'info> {
     )]
     pub position_token_account: Box<Account<'info, token::TokenAccount>>,
 
-    // #[account(mut,
-    //     constraint = reward_owner_account.mint == ai_dex_pool.reward_infos[reward_index as usize].mint
-    // )]
+    /// CHECK: created if needed and checked in the handler
     #[account(mut)]
-    pub reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub reward_owner_account: UncheckedAccount<'info>,
 
     // #[account(address = ai_dex_pool.reward_infos[reward_index as usize].mint)]
     #[account(mut)]
@@ -57,14 +61,26 @@ pub struct CollectReward<'info> {
     pub reward_token_program: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, Memo>,
 
+    /// Required to create a missing associated token account of the position authority
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
 /// Collects all harvestable tokens for a specified reward.
 ///
+/// The position authority can collect to any token account of the reward mint. The reward
+/// delegate of the position, if any, can collect in its place, to token accounts owned by the
+/// reward recipient of the position only, as long as the position token is held by the owner
+/// who delegated the rewards.
+///
 /// If the AiDex reward vault does not have enough tokens, the maximum number of available
 /// tokens will be debited to the user. The unharvested amount remains tracked, and it can be
 /// harvested in the future.
 ///
+/// A missing associated token account of the position authority is created within the
+/// instruction when the associated token and system programs are provided, the position
+/// authority paying the rent as a writable signer.
+///
 /// # Parameters
 /// - `reward_index` - The reward to harvest. Acceptable values are 0, 1, and 2.
 ///
@@ -72,17 +88,39 @@ pub struct CollectReward<'info> {
 /// - `Ok`: Reward tokens at the specified reward index have been successfully harvested
 /// - `Err`: `RewardNotInitializedError` if the specified reward has not been initialized
 ///          `InvalidRewardIndexError` if the reward index is not 0, 1, or 2
+/// - `Err`: `InvalidRewardRecipient` if the reward delegate collects to another owner
 pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectReward<'info>>,
     reward_index: u8,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    verify_position_authority(
-        &ctx.accounts.position_token_account,
+    create_associated_token_account_if_needed(
         &ctx.accounts.position_authority,
+        &ctx.accounts.reward_owner_account,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
     )?;
+    let reward_owner_account =
+        load_token_account(&ctx.accounts.reward_owner_account, &ctx.accounts.reward_token_program)?;
+
+    if ctx.accounts.position.is_reward_delegate(
+        ctx.accounts.position_authority.key,
+        &ctx.accounts.position_token_account.owner,
+    ) {
+        if reward_owner_account.owner != ctx.accounts.position.reward_recipient {
+            return Err(ErrorCode::InvalidRewardRecipient.into());
+        }
+    } else {
+        verify_position_authority(
+            &ctx.accounts.position_token_account,
+            &ctx.accounts.position_authority,
+        )?;
+    }
 
     let ai_dex_pool = ctx.accounts.ai_dex_pool.load()?;
+    ai_dex_pool.check_not_locked()?;
     let index = reward_index as usize;
 
     // Check if the reward index is valid
@@ -92,7 +130,7 @@ pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
 
     let reward_info = &ai_dex_pool.reward_infos[index];
     // Check that the reward_owner_account mint matches the expected mint from reward_infos
-    if ctx.accounts.reward_owner_account.mint != reward_info.mint {
+    if reward_owner_account.mint != reward_info.mint {
         return Err(ErrorCode::InvalidRewardMintError.into());
     }
 
@@ -124,8 +162,9 @@ pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
     );
 
     position.update_reward_owed(index, updated_amount_owed);
+    position.update_last_updated_timestamp(to_timestamp_u64(Clock::get()?.unix_timestamp)?);
 
-    transfer_from_vault_to_owner(
+    transfer_from_vault_to_owner_account_info(
         &ctx.accounts.ai_dex_pool,
         &ctx.accounts.reward_mint,
         &ctx.accounts.reward_vault,
@@ -138,6 +177,7 @@ pub fn collect_reward_handler<'a, 'b, 'c, 'info>(
     )?;
 
     emit!(RewardCollectedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         position_key: ctx.accounts.position.key(),
         position_authority: ctx.accounts.position_authority.key(),
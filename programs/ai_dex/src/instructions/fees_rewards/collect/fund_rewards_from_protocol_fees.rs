@@ -0,0 +1,150 @@
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::constants::EVENT_VERSION;
+use crate::{
+    constants::transfer_memo,
+    state::*,
+    util::transfer_from_vault_to_owner,
+    errors::ErrorCode,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+#[event]
+pub struct RewardsFundedFromProtocolFeesEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub share_bps: u16,
+    pub amount: u64,
+    pub reward_mint: Pubkey,
+    pub token_vault: Pubkey,
+    pub reward_vault: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct FundRewardsFromProtocolFees<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// The pool vault holding the protocol fees in the reward mint
+    #[account(mut)]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = reward_token_program.key() == *reward_mint.to_account_info().owner)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+}
+
+/// Routes a share of the protocol fees owed into the vault of a reward with the same mint.
+///
+/// The reward mint must be one of the pool tokens, so collected protocol fees can fund the
+/// pool's own incentives without leaving the program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for funding the reward.
+/// * `reward_index` - The index of the reward to fund.
+/// * `share_bps` - The share of the protocol fees owed in the reward mint, in basis points.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the reward is funded, otherwise returns an error.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The reward index is invalid or the reward is not initialized.
+/// * The reward mint is not one of the pool tokens or the vaults do not match.
+/// * The share exceeds 100%.
+/// * Transferring the protocol fees to the reward vault fails.
+pub fn fund_rewards_from_protocol_fees_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FundRewardsFromProtocolFees<'info>>,
+    reward_index: u8,
+    share_bps: u16,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    ai_dex_pool.lock()?;
+
+    let index = reward_index as usize;
+    if index >= NUM_REWARDS {
+        return Err(ErrorCode::InvalidRewardIndexError.into());
+    }
+    let reward_info = ai_dex_pool.reward_infos[index];
+    if !reward_info.initialized() || ctx.accounts.reward_mint.key() != reward_info.mint {
+        return Err(ErrorCode::InvalidRewardMintError.into());
+    }
+    if ctx.accounts.reward_vault.key() != reward_info.vault {
+        return Err(ErrorCode::InvalidVault.into());
+    }
+
+    // The protocol fees can only fund a reward with the same mint
+    let is_token_a = if reward_info.mint == ai_dex_pool.token_mint_a {
+        if ctx.accounts.token_vault.key() != ai_dex_pool.token_vault_a {
+            return Err(ErrorCode::InvalidVault.into());
+        }
+        true
+    } else if reward_info.mint == ai_dex_pool.token_mint_b {
+        if ctx.accounts.token_vault.key() != ai_dex_pool.token_vault_b {
+            return Err(ErrorCode::InvalidVault.into());
+        }
+        false
+    } else {
+        return Err(ErrorCode::InvalidRewardMintError.into());
+    };
+
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookReward,
+        ],
+    )?;
+
+    let amount = ai_dex_pool.take_protocol_fee_share(is_token_a, share_bps)?;
+    drop(ai_dex_pool);
+
+    if amount > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.reward_token_program,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_reward,
+            amount,
+            transfer_memo::TRANSFER_MEMO_FUND_REWARDS_FROM_PROTOCOL_FEES.as_bytes(),
+        )?;
+    }
+
+    ctx.accounts.ai_dex_pool.load_mut()?.unlock();
+
+    emit!(RewardsFundedFromProtocolFeesEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        share_bps,
+        amount,
+        reward_mint: ctx.accounts.reward_mint.key(),
+        token_vault: ctx.accounts.token_vault.key(),
+        reward_vault: ctx.accounts.reward_vault.key(),
+    });
+
+    Ok(())
+}
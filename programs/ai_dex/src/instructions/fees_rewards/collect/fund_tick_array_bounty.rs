@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::TickArrayBounty;
+
+#[event]
+pub struct TickArrayBountyFundedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array_bounty: Pubkey,
+    pub funder: Pubkey,
+    pub lamports: u64,
+}
+
+#[derive(Accounts)]
+pub struct FundTickArrayBounty<'info> {
+    #[account(mut)]
+    pub tick_array_bounty: Account<'info, TickArrayBounty>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfers lamports of the funder to the tick array bounty of a pool. Anyone can fund a bounty.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for funding the bounty.
+/// * `lamports` - The lamports transferred to the bounty.
+pub fn fund_tick_array_bounty_handler(ctx: Context<FundTickArrayBounty>, lamports: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.tick_array_bounty.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    emit!(TickArrayBountyFundedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.tick_array_bounty.ai_dex_pool,
+        tick_array_bounty: ctx.accounts.tick_array_bounty.key(),
+        funder: ctx.accounts.funder.key(),
+        lamports,
+    });
+
+    Ok(())
+}
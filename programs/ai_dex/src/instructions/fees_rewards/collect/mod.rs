@@ -1,9 +1,17 @@
 pub mod collect_fees;
 pub mod collect_protocol_fees;
+pub mod collect_reinvestment_fees;
 pub mod collect_reward;
 pub mod collect_referral_fee;
+pub mod fund_rewards_from_protocol_fees;
+pub mod claim_staking_rewards;
+pub mod fund_tick_array_bounty;
 
 pub use collect_fees::*;
 pub use collect_protocol_fees::*;
+pub use collect_reinvestment_fees::*;
 pub use collect_reward::*;
-pub use collect_referral_fee::*;
\ No newline at end of file
+pub use collect_referral_fee::*;
+pub use fund_rewards_from_protocol_fees::*;
+pub use claim_staking_rewards::*;
+pub use fund_tick_array_bounty::*;
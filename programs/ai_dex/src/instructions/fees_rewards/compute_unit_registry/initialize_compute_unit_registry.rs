@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, ComputeUnitRegistry};
+
+#[event]
+pub struct ComputeUnitRegistryInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub compute_unit_registry: Pubkey,
+    pub config_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeComputeUnitRegistry<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = ComputeUnitRegistry::LEN,
+        seeds = [b"compute_unit_registry".as_ref(), ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub compute_unit_registry: Account<'info, ComputeUnitRegistry>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the compute unit registry of a config, filled by `set_instruction_compute_units`.
+pub fn initialize_compute_unit_registry_handler(ctx: Context<InitializeComputeUnitRegistry>) -> Result<()> {
+    ctx.accounts.compute_unit_registry.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.bumps.compute_unit_registry,
+    );
+
+    emit!(ComputeUnitRegistryInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        compute_unit_registry: ctx.accounts.compute_unit_registry.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+    });
+
+    Ok(())
+}
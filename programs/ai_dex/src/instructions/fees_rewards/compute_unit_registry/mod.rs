@@ -0,0 +1,5 @@
+pub mod initialize_compute_unit_registry;
+pub mod set_instruction_compute_units;
+
+pub use initialize_compute_unit_registry::*;
+pub use set_instruction_compute_units::*;
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, ComputeUnitRegistry};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct InstructionComputeUnitsSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub compute_unit_registry: Pubkey,
+    pub instruction_discriminator: [u8; 8],
+    pub variant: u8,
+    pub compute_units: u32,
+}
+
+#[derive(Accounts)]
+pub struct SetInstructionComputeUnits<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub compute_unit_registry: Account<'info, ComputeUnitRegistry>,
+}
+
+/// Sets the compute units measured for a variant of an instruction in the compute unit registry
+/// of the config, after benchmarking a release of the program.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the compute units.
+/// * `instruction_discriminator` - The Anchor discriminator of the instruction.
+/// * `variant` - The path of the instruction measured, 0 for the base path.
+/// * `compute_units` - The compute units measured. 0 removes the variant from the registry.
+///
+/// # Errors
+///
+/// This function will return an error if the variant is not registered yet and the registry is
+/// full.
+pub fn set_instruction_compute_units_handler(
+    ctx: Context<SetInstructionComputeUnits>,
+    instruction_discriminator: [u8; 8],
+    variant: u8,
+    compute_units: u32,
+) -> Result<()> {
+    ctx.accounts.compute_unit_registry.set_compute_units(
+        instruction_discriminator,
+        variant,
+        compute_units,
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    )?;
+
+    emit!(InstructionComputeUnitsSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        compute_unit_registry: ctx.accounts.compute_unit_registry.key(),
+        instruction_discriminator,
+        variant,
+        compute_units,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+use crate::instructions::record_super_admin_activity;
+
+#[event]
+pub struct MarketInstanceCreatedEvent {
+    pub event_version: u8,
+    pub config_key: Pubkey,
+    pub reinvestments_account: Pubkey,
+    pub super_admin_authority: Pubkey,
+    pub config_authority: Pubkey,
+    pub reinvestments_authority: Pubkey,
+    pub default_protocol_fee_rate: u16,
+    pub default_referral_reward_fee_rate: u16,
+    pub default_reinvestment_fee_rate: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    config_authority: Pubkey,
+    default_protocol_fee_rate: u16,
+    default_referral_reward_fee_rate: u16,
+    reinvestments_authority: Pubkey,
+)]
+pub struct CreateMarketInstance<'info> {
+    /// SuperAdmin account that stores the current super admin's public key.
+    #[account(mut, constraint = super_admin_account.super_admin == super_admin_authority.key())]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    /// Signer must be the current super admin.
+    #[account(mut)]
+    pub super_admin_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = super_admin_authority, space = AiDexConfig::LEN,
+        seeds = [
+            b"config".as_ref(),
+            config_authority.key().as_ref(),
+            default_protocol_fee_rate.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub config: Account<'info, AiDexConfig>,
+
+    #[account(
+        init,
+        payer = super_admin_authority, space = AiDexReinvestments::LEN,
+        seeds = [
+            b"reinvestments".as_ref(),
+            config.key().as_ref(),
+            reinvestments_authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub reinvestments_account: Account<'info, AiDexReinvestments>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an independent market instance: a config with its own authority and fee rates, and a
+/// reinvestments account scoped to it.
+///
+/// Every account a market operates on is isolated by its config:
+/// - Fee tiers, pools, keepers, token badges, swap rate limiters and position metadata are
+///   derived from the config key, and pools reference their config.
+/// - Tick arrays, oracles, vaults, rewards, snapshots and swap commitments are derived from a
+///   pool, and positions reference their pool.
+/// - The reinvestments account created here is derived from the config key and can only
+///   reinvest fees of the config's pools. Global reinvestments accounts created with
+///   `initialize_reinvestments` remain usable by every config.
+/// - Swap referrals identify a referrer across markets. The reward rate applied to a swap comes
+///   from the referral, the pool or the default of the pool's config, and rewards are paid from
+///   that pool's fees.
+/// - The super admin is global and only creates markets; it has no authority over a market's
+///   fees once created.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for creating the market.
+/// * `config_authority` - The public key of the market's config authority.
+/// * `default_protocol_fee_rate` - The default protocol fee rate of the market.
+/// * `default_referral_reward_fee_rate` - The default swap referral reward fee rate of the market.
+/// * `reinvestments_authority` - The public key of the market's reinvestments authority.
+/// * `default_reinvestment_fee_rate` - The default reinvestment fee rate of the market.
+///
+/// # Errors
+///
+/// This function will return an error if any of the fee rates exceeds its maximum.
+pub fn create_market_instance_handler(
+    ctx: Context<CreateMarketInstance>,
+    config_authority: Pubkey,
+    default_protocol_fee_rate: u16,
+    default_referral_reward_fee_rate: u16,
+    reinvestments_authority: Pubkey,
+    default_reinvestment_fee_rate: u16,
+) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
+    let config = &mut ctx.accounts.config;
+    config.initialize(
+        config_authority,
+        default_protocol_fee_rate,
+        default_referral_reward_fee_rate,
+    )?;
+
+    let reinvestments_account = &mut ctx.accounts.reinvestments_account;
+    reinvestments_account.initialize(reinvestments_authority, default_reinvestment_fee_rate)?;
+    reinvestments_account.scope_to_config(config.key());
+
+    emit!(MarketInstanceCreatedEvent {
+        event_version: EVENT_VERSION,
+        config_key: config.key(),
+        reinvestments_account: reinvestments_account.key(),
+        super_admin_authority: ctx.accounts.super_admin_authority.key(),
+        config_authority,
+        reinvestments_authority,
+        default_protocol_fee_rate,
+        default_referral_reward_fee_rate,
+        default_reinvestment_fee_rate,
+    });
+
+    Ok(())
+}
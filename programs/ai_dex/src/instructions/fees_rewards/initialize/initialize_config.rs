@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::*;
+use crate::util::emit_program_version_event;
+use crate::instructions::record_super_admin_activity;
 
 #[event]
 pub struct ConfigInitializedEvent {
+    pub event_version: u8,
     pub config_key: Pubkey,
     pub super_admin_authority: Pubkey,
     pub config_authority: Pubkey,
@@ -15,7 +19,7 @@ pub struct ConfigInitializedEvent {
 #[instruction(config_authority: Pubkey, default_protocol_fee_rate: u16)]
 pub struct InitializeConfig<'info> {
     /// SuperAdmin account that stores the current super admin's public key.
-    #[account(constraint = super_admin_account.super_admin == super_admin_authority.key())]
+    #[account(mut, constraint = super_admin_account.super_admin == super_admin_authority.key())]
     pub super_admin_account: Account<'info, SuperAdmin>,
 
     /// Signer must be the current super admin.
@@ -59,6 +63,8 @@ pub fn initialize_config_handler(
     default_protocol_fee_rate: u16,
     default_referral_reward_fee_rate: u16,
 ) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
     let config = &mut ctx.accounts.config;
 
     config.initialize(
@@ -68,12 +74,14 @@ pub fn initialize_config_handler(
     )?;
 
     emit!(ConfigInitializedEvent {
+        event_version: EVENT_VERSION,
         config_key: config.key(),
         super_admin_authority: ctx.accounts.super_admin_authority.key(),
         config_authority,
         default_protocol_fee_rate,
         default_referral_reward_fee_rate,
     });
+    emit_program_version_event(&ctx.accounts.config);
     
     Ok(())
 }
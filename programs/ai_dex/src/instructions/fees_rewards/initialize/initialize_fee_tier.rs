@@ -1,8 +1,10 @@
 use crate::state::*;
 use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
 
 #[event]
 pub struct FeeTierInitializedEvent {
+    pub event_version: u8,
     pub config_key: Pubkey,
     pub fee_tier_key: Pubkey,
     pub funder: Pubkey,
@@ -58,6 +60,7 @@ pub fn initialize_fee_tier_handler(
         .initialize(&ctx.accounts.config, tick_spacing, default_fee_rate)?;
 
     emit!(FeeTierInitializedEvent {
+        event_version: EVENT_VERSION,
         config_key: ctx.accounts.config.key(),
         fee_tier_key: ctx.accounts.fee_tier.key(),
         funder: ctx.accounts.funder.key(),
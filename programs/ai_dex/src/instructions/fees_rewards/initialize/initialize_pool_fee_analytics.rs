@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole, PoolFeeAnalytics};
+
+#[event]
+pub struct PoolFeeAnalyticsInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub pool_fee_analytics: Pubkey,
+    pub config_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolFeeAnalytics<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = config_authority,
+        space = PoolFeeAnalytics::LEN,
+        seeds = [b"pool_fee_analytics".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_fee_analytics: Account<'info, PoolFeeAnalytics>,
+
+    #[account(
+        mut,
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the fee analytics account of an oracle pool and enables fee analytics for the pool.
+///
+/// From then on every swap of the pool must pass the account in its remaining accounts, so the
+/// recorded fees cover all swaps. Only the config authority or the fee manager can enable it,
+/// as it changes the accounts swaps of the pool require.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The pool is not an oracle pool.
+pub fn initialize_pool_fee_analytics_handler(ctx: Context<InitializePoolFeeAnalytics>) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    if !ai_dex_pool.is_oracle_pool {
+        return Err(ErrorCode::NotOraclePool.into());
+    }
+    ai_dex_pool.is_fee_analytics_enabled = 1;
+
+    ctx.accounts.pool_fee_analytics.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.bumps.pool_fee_analytics,
+    );
+
+    emit!(PoolFeeAnalyticsInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pool_fee_analytics: ctx.accounts.pool_fee_analytics.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole, PoolRevenueSnapshot};
+
+#[event]
+pub struct PoolRevenueSnapshotInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub pool_revenue_snapshot: Pubkey,
+    pub config_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolRevenueSnapshot<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = config_authority,
+        space = PoolRevenueSnapshot::LEN,
+        seeds = [b"pool_revenue_snapshot".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_revenue_snapshot: Account<'info, PoolRevenueSnapshot>,
+
+    #[account(
+        mut,
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the revenue snapshot account of a pool and enables revenue reporting for the pool.
+///
+/// From then on every protocol fee collection of the pool must pass the account in its
+/// remaining accounts, so the protocol stats cover all the protocol fees collected afterwards.
+/// Only the config authority or the fee manager can enable it, as it changes the accounts
+/// collections of the pool require.
+pub fn initialize_pool_revenue_snapshot_handler(ctx: Context<InitializePoolRevenueSnapshot>) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    ai_dex_pool.is_revenue_reporting_enabled = 1;
+
+    ctx.accounts.pool_revenue_snapshot.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.accounts.ai_dex_pool.key(),
+        ai_dex_pool.token_mint_a,
+        ai_dex_pool.token_mint_b,
+        ctx.bumps.pool_revenue_snapshot,
+    );
+
+    emit!(PoolRevenueSnapshotInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pool_revenue_snapshot: ctx.accounts.pool_revenue_snapshot.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+    });
+
+    Ok(())
+}
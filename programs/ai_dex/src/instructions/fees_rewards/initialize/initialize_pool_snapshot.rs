@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexPool, PoolSnapshot};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct PoolSnapshotInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub pool_snapshot: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolSnapshot<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = PoolSnapshot::LEN,
+        seeds = [b"pool_snapshot".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_snapshot: Account<'info, PoolSnapshot>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the snapshot account of a pool, filled with the current parameters of the pool.
+///
+/// Anyone can create the snapshot of a pool, the funder pays its rent.
+pub fn initialize_pool_snapshot_handler(ctx: Context<InitializePoolSnapshot>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.pool_snapshot.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        &*ctx.accounts.ai_dex_pool.load()?,
+        clock.slot,
+        to_timestamp_u64(clock.unix_timestamp)?,
+        ctx.bumps.pool_snapshot,
+    );
+
+    emit!(PoolSnapshotInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pool_snapshot: ctx.accounts.pool_snapshot.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
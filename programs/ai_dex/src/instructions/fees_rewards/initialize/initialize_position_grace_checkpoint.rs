@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{Position, PositionGraceCheckpoint};
+
+#[event]
+pub struct PositionGraceCheckpointInitializedEvent {
+    pub event_version: u8,
+    pub position: Pubkey,
+    pub position_grace_checkpoint: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializePositionGraceCheckpoint<'info> {
+    pub position: Account<'info, Position>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = PositionGraceCheckpoint::LEN,
+        seeds = [b"position_grace_checkpoint".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub position_grace_checkpoint: Account<'info, PositionGraceCheckpoint>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the grace checkpoint of a position, with which `update_fees_and_rewards` credits the
+/// position its out-of-range grace rewards. Its first update only takes the snapshot the grace
+/// rewards are credited from. Anyone can pay for it.
+pub fn initialize_position_grace_checkpoint_handler(ctx: Context<InitializePositionGraceCheckpoint>) -> Result<()> {
+    ctx.accounts.position_grace_checkpoint.initialize(
+        ctx.accounts.position.key(),
+        ctx.bumps.position_grace_checkpoint,
+    );
+
+    emit!(PositionGraceCheckpointInitializedEvent {
+        event_version: EVENT_VERSION,
+        position: ctx.accounts.position.key(),
+        position_grace_checkpoint: ctx.accounts.position_grace_checkpoint.key(),
+    });
+
+    Ok(())
+}
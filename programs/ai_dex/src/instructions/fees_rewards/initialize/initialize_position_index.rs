@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::PositionIndex;
+
+#[event]
+pub struct PositionIndexInitializedEvent {
+    pub event_version: u8,
+    pub position_index: Pubkey,
+    pub owner: Pubkey,
+    pub page: u16,
+    pub funder: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(page: u16)]
+pub struct InitializePositionIndex<'info> {
+    /// CHECK: safe, the owner of the listed positions can be arbitrary
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = PositionIndex::LEN,
+        seeds = [b"position_index".as_ref(), owner.key().as_ref(), page.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub position_index: Account<'info, PositionIndex>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a page of the position index of an owner.
+///
+/// Anyone can create a page for any owner, the funder pays its rent.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for the initialization.
+/// * `page` - The number of the page, starting from 0.
+pub fn initialize_position_index_handler(ctx: Context<InitializePositionIndex>, page: u16) -> Result<()> {
+    ctx.accounts.position_index.initialize(
+        ctx.accounts.owner.key(),
+        page,
+        ctx.bumps.position_index,
+    );
+
+    emit!(PositionIndexInitializedEvent {
+        event_version: EVENT_VERSION,
+        position_index: ctx.accounts.position_index.key(),
+        owner: ctx.accounts.owner.key(),
+        page,
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
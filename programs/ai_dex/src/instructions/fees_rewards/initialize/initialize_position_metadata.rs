@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+#[event]
+pub struct PositionMetadataInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub position_metadata: Pubkey,
+    pub config_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(Accounts)]
+pub struct InitializePositionMetadata<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        init,
+        payer = config_authority,
+        space = PositionMetadata::LEN,
+        seeds = [
+            b"position_metadata".as_ref(),
+            ai_dex_config.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_metadata: Account<'info, PositionMetadata>,
+
+    #[account(mut, address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initializes the position NFT metadata used by all pools of a config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for initializing the metadata.
+/// * `name` - The name of the position NFTs, at most 32 bytes.
+/// * `symbol` - The symbol of the position NFTs, at most 10 bytes.
+/// * `uri` - The uri of the position NFTs, at most 200 bytes.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the metadata is successfully initialized,
+/// or an `Err` if an error occurs.
+pub fn initialize_position_metadata_handler(
+    ctx: Context<InitializePositionMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    ctx
+        .accounts
+        .position_metadata
+        .initialize(ctx.accounts.ai_dex_config.key(), name.clone(), symbol.clone(), uri.clone())?;
+
+    emit!(PositionMetadataInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        position_metadata: ctx.accounts.position_metadata.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        name,
+        symbol,
+        uri,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, ProtocolStats};
+
+#[event]
+pub struct ProtocolStatsInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub protocol_stats: Pubkey,
+    pub funder: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolStats<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = ProtocolStats::LEN,
+        seeds = [b"protocol_stats".as_ref(), ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the protocol stats account of a config, filled by `sync_protocol_stats`.
+///
+/// Anyone can create the protocol stats of a config, the funder pays its rent.
+pub fn initialize_protocol_stats_handler(ctx: Context<InitializeProtocolStats>) -> Result<()> {
+    ctx.accounts.protocol_stats.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.bumps.protocol_stats,
+    );
+
+    emit!(ProtocolStatsInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        protocol_stats: ctx.accounts.protocol_stats.key(),
+        funder: ctx.accounts.funder.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexPool, ReferralFeeEscrow, SwapReferral};
+
+#[event]
+pub struct ReferralFeeEscrowInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub swap_referral: Pubkey,
+    pub referral_fee_escrow: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReferralFeeEscrow<'info> {
+    pub swap_referral: Account<'info, SwapReferral>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = ReferralFeeEscrow::LEN,
+        seeds = [
+            b"referral_fee_escrow".as_ref(),
+            swap_referral.key().as_ref(),
+            ai_dex_pool.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub referral_fee_escrow: Account<'info, ReferralFeeEscrow>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the referral fee escrow of a swap referral on a pool, to which swaps passing it accrue
+/// the referral fee instead of transferring it. Anyone can pay for it.
+pub fn initialize_referral_fee_escrow_handler(ctx: Context<InitializeReferralFeeEscrow>) -> Result<()> {
+    ctx.accounts.referral_fee_escrow.initialize(
+        ctx.accounts.swap_referral.key(),
+        ctx.accounts.ai_dex_pool.key(),
+        ctx.bumps.referral_fee_escrow,
+    );
+
+    emit!(ReferralFeeEscrowInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        swap_referral: ctx.accounts.swap_referral.key(),
+        referral_fee_escrow: ctx.accounts.referral_fee_escrow.key(),
+    });
+
+    Ok(())
+}
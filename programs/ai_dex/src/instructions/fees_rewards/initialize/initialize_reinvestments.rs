@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::*;
+use crate::instructions::record_super_admin_activity;
 
 #[event]
 pub struct ReinvestmentsAuthorityInitializedEvent {
+    pub event_version: u8,
     pub reinvestments_account: Pubkey,
     pub super_admin_authority: Pubkey,
     pub reinvestments_authority: Pubkey,
@@ -14,7 +17,7 @@ pub struct ReinvestmentsAuthorityInitializedEvent {
 #[instruction(reinvestments_authority: Pubkey)]
 pub struct InitializeReinvestmentsAuthority<'info> {
     /// SuperAdmin account that stores the current super admin's public key.
-    #[account(constraint = super_admin_account.super_admin == super_admin_authority.key())]
+    #[account(mut, constraint = super_admin_account.super_admin == super_admin_authority.key())]
     pub super_admin_account: Account<'info, SuperAdmin>,
 
     /// Signer must be the current super admin.
@@ -41,6 +44,8 @@ pub fn initialize_reinvestments_handler(
     reinvestments_authority: Pubkey,
     default_reinvestment_fee_rate: u16,
 ) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
     let reinvestments_account = &mut ctx.accounts.reinvestments_account;
 
     reinvestments_account.initialize(
@@ -49,6 +54,7 @@ pub fn initialize_reinvestments_handler(
     )?;
 
     emit!(ReinvestmentsAuthorityInitializedEvent {
+        event_version: EVENT_VERSION,
         reinvestments_account: reinvestments_account.key(),
         super_admin_authority: ctx.accounts.super_admin_authority.key(),
         reinvestments_authority: reinvestments_authority,
@@ -1,14 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use crate::constants::EVENT_VERSION;
 use crate::{
     errors::ErrorCode,
-    state::AiDexPool,
-    util::is_supported_token_mint
+    state::{AiDexPool, TokenBadge},
+    util::{is_supported_token_mint, requires_token_badge},
 };
 
 #[event]
 pub struct RewardInitializedEvent {
+    pub event_version: u8,
     pub reward_index: u8,
     pub ai_dex_pool: Pubkey,
     pub reward_authority: Pubkey,
@@ -31,6 +33,17 @@ pub struct InitializeReward<'info> {
 
     pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// Token badge of the reward mint, required for mints with a freeze authority or risky extensions
+    #[account(
+        seeds = [
+            b"token_badge",
+            ai_dex_pool.load()?.ai_dex_config.as_ref(),
+            reward_mint.key().as_ref(),
+        ],
+        bump = token_badge.bump,
+    )]
+    pub token_badge: Option<Account<'info, TokenBadge>>,
+
     #[account(
         init,
         payer = funder,
@@ -64,9 +77,14 @@ pub struct InitializeReward<'info> {
 ///
 /// * `Result<()>` - Returns an Ok result if the operation is successful, otherwise returns an error.
 ///
+/// Reward mints go through the same checks as pool mints. Mints with a freeze authority, a
+/// transfer hook, a permanent delegate or a close authority also need a token badge issued by the
+/// config authority.
+///
 /// # Errors
 ///
 /// * `ErrorCode::UnsupportedTokenMintError` - If the token mint is not supported.
+/// * `ErrorCode::RewardMintRequiresTokenBadge` - If the token mint needs a token badge and none is provided.
 pub fn initialize_reward_handler(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
     let ai_dex = &mut ctx.accounts.ai_dex_pool.load_mut()?;
 
@@ -80,9 +98,12 @@ pub fn initialize_reward_handler(ctx: Context<InitializeReward>, reward_index: u
         return Err(ErrorCode::InvalidRewardAuthorityError.into());
     }
 
-    if !is_supported_token_mint(&ctx.accounts.reward_mint).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.reward_mint)? {
         return Err(ErrorCode::UnsupportedTokenMintError.into());
-    }  
+    }
+    if ctx.accounts.token_badge.is_none() && requires_token_badge(&ctx.accounts.reward_mint)? {
+        return Err(ErrorCode::RewardMintRequiresTokenBadge.into());
+    }
 
     ai_dex.initialize_reward(
         reward_index as usize,
@@ -91,6 +112,7 @@ pub fn initialize_reward_handler(ctx: Context<InitializeReward>, reward_index: u
     )?;
 
     emit!(RewardInitializedEvent {
+        event_version: EVENT_VERSION,
         reward_index,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         reward_authority: ctx.accounts.reward_authority.key(),
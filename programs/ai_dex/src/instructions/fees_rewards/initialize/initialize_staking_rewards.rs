@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, StakingRewards};
+
+#[event]
+pub struct StakingRewardsInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub staking_rewards: Pubkey,
+    pub staking_authority: Pubkey,
+    pub config_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStakingRewards<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        init,
+        payer = config_authority,
+        space = StakingRewards::LEN,
+        seeds = [b"staking_rewards".as_ref(), ai_dex_config.key().as_ref()],
+        bump,
+    )]
+    pub staking_rewards: Account<'info, StakingRewards>,
+
+    #[account(mut, address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the staking rewards of a config, claimable by the given staking authority.
+///
+/// Pools of the config owe nothing to the staking rewards until their share of the protocol fees
+/// is set with `set_staking_reward_share`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for initializing the staking rewards.
+/// * `staking_authority` - The key allowed to claim the staking rewards, usually a PDA of the staking program.
+pub fn initialize_staking_rewards_handler(
+    ctx: Context<InitializeStakingRewards>,
+    staking_authority: Pubkey,
+) -> Result<()> {
+    ctx.accounts.staking_rewards.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        staking_authority,
+        ctx.bumps.staking_rewards,
+    );
+
+    emit!(StakingRewardsInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        staking_rewards: ctx.accounts.staking_rewards.key(),
+        staking_authority,
+        config_authority: ctx.accounts.config_authority.key(),
+    });
+
+    Ok(())
+}
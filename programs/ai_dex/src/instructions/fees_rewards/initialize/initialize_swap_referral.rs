@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
 use crate::state::{AiDexConfig, SwapReferral};
 
 #[event]
 pub struct SwapReferralInitialized {
+    pub event_version: u8,
     pub config_account: Pubkey,
     pub referrer: Pubkey,
     pub referral_code: String,
@@ -45,6 +47,7 @@ pub fn initialize_swap_referral_handler(
     )?;
 
     emit!(SwapReferralInitialized {
+        event_version: EVENT_VERSION,
         config_account: *ctx.accounts.config_account.to_account_info().key,
         referrer: ctx.accounts.referrer.key(),
         referral_code,
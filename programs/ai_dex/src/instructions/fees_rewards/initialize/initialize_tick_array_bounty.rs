@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, TickArrayBounty};
+
+#[event]
+pub struct TickArrayBountyInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array_bounty: Pubkey,
+    pub authority: Pubkey,
+    pub reward_per_tick_array: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTickArrayBounty<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TickArrayBounty::LEN,
+        seeds = [b"tick_array_bounty".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub tick_array_bounty: Account<'info, TickArrayBounty>,
+
+    /// The config authority or the creator of the pool, paying the rent of the bounty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the tick array bounty of a pool, paying whoever initializes a tick array next to the
+/// current price from the lamports funded with `fund_tick_array_bounty`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for creating the bounty.
+/// * `reward_per_tick_array` - The lamports paid for each tick array next to the current price.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTickArrayBountyAuthority` - If the signer is neither the config authority
+///   nor the creator of the pool.
+pub fn initialize_tick_array_bounty_handler(
+    ctx: Context<InitializeTickArrayBounty>,
+    reward_per_tick_array: u64,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    if authority != ctx.accounts.ai_dex_config.config_authority
+        && !ctx.accounts.ai_dex_pool.load()?.is_creator(&authority)
+    {
+        return Err(ErrorCode::InvalidTickArrayBountyAuthority.into());
+    }
+
+    ctx.accounts.tick_array_bounty.initialize(
+        ctx.accounts.ai_dex_pool.key(),
+        reward_per_tick_array,
+        ctx.bumps.tick_array_bounty,
+    );
+
+    emit!(TickArrayBountyInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        tick_array_bounty: ctx.accounts.tick_array_bounty.key(),
+        authority,
+        reward_per_tick_array,
+    });
+
+    Ok(())
+}
@@ -3,9 +3,31 @@ pub mod initialize_fee_tier;
 pub mod initialize_reward;
 pub mod initialize_swap_referral;
 pub mod initialize_reinvestments;
+pub mod initialize_position_metadata;
+pub mod initialize_pool_snapshot;
+pub mod initialize_pool_fee_analytics;
+pub mod initialize_position_index;
+pub mod create_market_instance;
+pub mod initialize_staking_rewards;
+pub mod initialize_tick_array_bounty;
+pub mod initialize_pool_revenue_snapshot;
+pub mod initialize_protocol_stats;
+pub mod initialize_referral_fee_escrow;
+pub mod initialize_position_grace_checkpoint;
 
 pub use initialize_config::*;
 pub use initialize_fee_tier::*;
 pub use initialize_reward::*;
 pub use initialize_swap_referral::*;
-pub use initialize_reinvestments::*;
\ No newline at end of file
+pub use initialize_reinvestments::*;
+pub use initialize_position_metadata::*;
+pub use initialize_pool_snapshot::*;
+pub use initialize_pool_fee_analytics::*;
+pub use initialize_position_index::*;
+pub use create_market_instance::*;
+pub use initialize_staking_rewards::*;
+pub use initialize_tick_array_bounty::*;
+pub use initialize_pool_revenue_snapshot::*;
+pub use initialize_protocol_stats::*;
+pub use initialize_referral_fee_escrow::*;
+pub use initialize_position_grace_checkpoint::*;
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub mod register_keeper;
+pub mod set_keeper_rate_limit;
+pub mod remove_keeper;
+
+pub use register_keeper::*;
+pub use set_keeper_rate_limit::*;
+pub use remove_keeper::*;
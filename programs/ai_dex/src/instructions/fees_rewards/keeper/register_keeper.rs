@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, Keeper};
+
+#[event]
+pub struct KeeperRegisteredEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub keeper: Pubkey,
+    pub keeper_authority: Pubkey,
+    pub max_actions_per_window: u32,
+    pub window_duration: u64,
+}
+
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the keeper authority only needs to sign the cranks it runs
+    pub keeper_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Keeper::LEN,
+        seeds = [
+            b"keeper".as_ref(),
+            ai_dex_config.key().as_ref(),
+            keeper_authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub keeper: Account<'info, Keeper>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers a keeper allowed to run reinvestments for the pools of the config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for registering the keeper.
+/// * `max_actions_per_window` - The maximum number of actions the keeper can run per window.
+/// * `window_duration` - The duration of a rate limit window in seconds.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the keeper is successfully registered,
+/// or an `Err` if the rate limit is invalid.
+pub fn register_keeper_handler(
+    ctx: Context<RegisterKeeper>,
+    max_actions_per_window: u32,
+    window_duration: u64,
+) -> Result<()> {
+    ctx.accounts.keeper.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.accounts.keeper_authority.key(),
+        max_actions_per_window,
+        window_duration,
+        ctx.bumps.keeper,
+    )?;
+
+    emit!(KeeperRegisteredEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        keeper: ctx.accounts.keeper.key(),
+        keeper_authority: ctx.accounts.keeper_authority.key(),
+        max_actions_per_window,
+        window_duration,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, Keeper};
+
+#[event]
+pub struct KeeperRemovedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub keeper: Pubkey,
+    pub keeper_authority: Pubkey,
+    pub total_action_count: u64,
+}
+
+#[derive(Accounts)]
+pub struct RemoveKeeper<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config, close = receiver)]
+    pub keeper: Account<'info, Keeper>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Removes a keeper from the registry of the config, refunding its rent to the receiver.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for removing the keeper.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the keeper is successfully removed.
+pub fn remove_keeper_handler(ctx: Context<RemoveKeeper>) -> Result<()> {
+    emit!(KeeperRemovedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        keeper: ctx.accounts.keeper.key(),
+        keeper_authority: ctx.accounts.keeper.authority,
+        total_action_count: ctx.accounts.keeper.total_action_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, Keeper};
+
+#[event]
+pub struct KeeperRateLimitSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub keeper: Pubkey,
+    pub old_max_actions_per_window: u32,
+    pub new_max_actions_per_window: u32,
+    pub old_window_duration: u64,
+    pub new_window_duration: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetKeeperRateLimit<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub keeper: Account<'info, Keeper>,
+}
+
+/// Sets the rate limit of a registered keeper.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the rate limit.
+/// * `max_actions_per_window` - The maximum number of actions the keeper can run per window.
+/// * `window_duration` - The duration of a rate limit window in seconds.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the rate limit is successfully updated,
+/// or an `Err` if the rate limit is invalid.
+pub fn set_keeper_rate_limit_handler(
+    ctx: Context<SetKeeperRateLimit>,
+    max_actions_per_window: u32,
+    window_duration: u64,
+) -> Result<()> {
+    let keeper = &mut ctx.accounts.keeper;
+    let old_max_actions_per_window = keeper.max_actions_per_window;
+    let old_window_duration = keeper.window_duration;
+
+    keeper.update_rate_limit(max_actions_per_window, window_duration)?;
+
+    emit!(KeeperRateLimitSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        keeper: keeper.key(),
+        old_max_actions_per_window,
+        new_max_actions_per_window: max_actions_per_window,
+        old_window_duration,
+        new_window_duration: window_duration,
+    });
+
+    Ok(())
+}
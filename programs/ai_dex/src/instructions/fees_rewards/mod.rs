@@ -3,9 +3,19 @@ pub mod initialize;
 pub mod update;
 pub mod set;
 pub mod admin;
+pub mod keeper;
+pub mod token_badge;
+pub mod swap_rate_limiter;
+pub mod blocklist;
+pub mod compute_unit_registry;
 
 pub use collect::*;
 pub use initialize::*;
 pub use update::*;
 pub use set::*;
 pub use admin::*;
+pub use keeper::*;
+pub use token_badge::*;
+pub use swap_rate_limiter::*;
+pub use blocklist::*;
+pub use compute_unit_registry::*;
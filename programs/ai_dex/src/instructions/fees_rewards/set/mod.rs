@@ -3,16 +3,44 @@ pub mod set_default_protocol_fee_rate;
 pub mod set_fee_authority;
 pub mod set_fee_rate;
 pub mod set_protocol_fee_rate;
+pub mod set_protocol_fee_rates_per_side;
+pub mod set_max_swap_liquidity_bps;
+pub mod set_dust_transfer_thresholds;
 pub mod set_default_swap_referral_reward_fee_rate;
 pub mod set_swap_referral_reward_fee_rate;
+pub mod set_pool_swap_referral_reward_fee_rates;
+pub mod set_dust_position_params;
+pub mod set_position_metadata;
+pub mod set_position_collection;
+pub mod set_max_integrator_fee_rate;
+pub mod set_protocol_fee_treasury;
+pub mod set_config_role;
+pub mod set_permanent_delegate_mint_policy;
+pub mod set_staking_reward_share;
+pub mod set_pool_metadata;
+pub mod set_tick_array_bounty_reward;
 
 pub use set_default_fee_rate::*;
 pub use set_default_protocol_fee_rate::*;
 pub use set_fee_authority::*;
 pub use set_fee_rate::*;
 pub use set_protocol_fee_rate::*;
+pub use set_protocol_fee_rates_per_side::*;
+pub use set_max_swap_liquidity_bps::*;
+pub use set_dust_transfer_thresholds::*;
 pub use set_default_swap_referral_reward_fee_rate::*;
 pub use set_swap_referral_reward_fee_rate::*;
+pub use set_pool_swap_referral_reward_fee_rates::*;
+pub use set_dust_position_params::*;
+pub use set_position_metadata::*;
+pub use set_position_collection::*;
+pub use set_max_integrator_fee_rate::*;
+pub use set_protocol_fee_treasury::*;
+pub use set_config_role::*;
+pub use set_permanent_delegate_mint_policy::*;
+pub use set_staking_reward_share::*;
+pub use set_pool_metadata::*;
+pub use set_tick_array_bounty_reward::*;
 
 pub mod oracle;
 pub use oracle::*;
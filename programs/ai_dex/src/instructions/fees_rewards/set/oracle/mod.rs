@@ -1,5 +1,15 @@
+pub mod set_default_oracle_maximum_age;
+pub mod set_default_oracle_price_feed;
 pub mod set_new_oracle_account;
+pub mod set_oracle_degraded_maximum_age;
 pub mod set_oracle_maximum_age;
+pub mod set_oracle_maximum_slot_lag;
+pub mod sync_oracle_params;
 
+pub use set_default_oracle_maximum_age::*;
+pub use set_default_oracle_price_feed::*;
 pub use set_new_oracle_account::*;
-pub use set_oracle_maximum_age::*;
\ No newline at end of file
+pub use set_oracle_degraded_maximum_age::*;
+pub use set_oracle_maximum_age::*;
+pub use set_oracle_maximum_slot_lag::*;
+pub use sync_oracle_params::*;
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, ConfigRole};
+use crate::util::emit_program_version_event;
+
+#[event]
+pub struct DefaultOracleMaxAgeSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_default_maximum_age: u64,
+    pub new_default_maximum_age: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetDefaultOracleMaxAge<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the default maximum age of oracle prices for the AI DEX configuration.
+///
+/// New oracle pools inherit this value when no maximum age is given at initialization,
+/// and existing oracle accounts can be brought in line with it through `sync_oracle_params`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the default maximum age.
+/// * `default_maximum_age` - The new default maximum age in seconds, or 0 to unset it.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the default maximum age is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_default_oracle_max_age_handler(
+    ctx: Context<SetDefaultOracleMaxAge>,
+    default_maximum_age: u64,
+) -> Result<()> {
+    let old_default_maximum_age = ctx.accounts.ai_dex_config.default_oracle_maximum_age;
+
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_default_oracle_maximum_age(default_maximum_age);
+
+    emit!(DefaultOracleMaxAgeSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_default_maximum_age,
+        new_default_maximum_age: default_maximum_age,
+    });
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
+
+    Ok(())
+}
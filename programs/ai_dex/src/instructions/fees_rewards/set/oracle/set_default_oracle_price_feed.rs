@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, ConfigRole, OraclePriceFeedDefault};
+use crate::util::is_canonical_token_mint_order;
+
+#[event]
+pub struct DefaultOraclePriceFeedSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_price_feed_id: String,
+    pub new_price_feed_id: String,
+}
+
+#[derive(Accounts)]
+pub struct SetDefaultOraclePriceFeed<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = config_authority,
+        space = OraclePriceFeedDefault::LEN,
+        seeds = [
+            b"oracle_price_feed_default".as_ref(),
+            ai_dex_config.key().as_ref(),
+            token_mint_a.key().as_ref(),
+            token_mint_b.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub oracle_price_feed_default: Account<'info, OraclePriceFeedDefault>,
+
+    /// The oracle manager of the config, paying for the default on creation
+    #[account(
+        mut,
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the default price feed of a token pair for the oracle pools of the AI DEX configuration.
+///
+/// Oracle pools of the pair inherit this price feed when no price feed id is given at
+/// initialization, and existing oracle accounts of the pair can be brought in line with it
+/// through `sync_oracle_params`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the default price feed.
+/// * `price_feed_id` - The hex id of the price feed of the pair.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the default price feed is successfully updated,
+/// or an `Err` if the token mints are not in canonical order or the price feed id is invalid.
+pub fn set_default_oracle_price_feed_handler(
+    ctx: Context<SetDefaultOraclePriceFeed>,
+    price_feed_id: String,
+) -> Result<()> {
+    let token_mint_a = ctx.accounts.token_mint_a.key();
+    let token_mint_b = ctx.accounts.token_mint_b.key();
+    if !is_canonical_token_mint_order(&token_mint_a, &token_mint_b) {
+        return Err(ErrorCode::InvalidTokenMintOrderError.into());
+    }
+
+    let oracle_price_feed_default = &mut ctx.accounts.oracle_price_feed_default;
+    if !oracle_price_feed_default.is_initialized() {
+        oracle_price_feed_default.initialize(
+            ctx.accounts.ai_dex_config.key(),
+            token_mint_a,
+            token_mint_b,
+            ctx.bumps.oracle_price_feed_default,
+        );
+    }
+    let old_price_feed_id = oracle_price_feed_default.price_feed_id.clone();
+    oracle_price_feed_default.update_price_feed_id(price_feed_id.clone())?;
+
+    emit!(DefaultOraclePriceFeedSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        token_mint_a,
+        token_mint_b,
+        config_authority: ctx.accounts.config_authority.key(),
+        old_price_feed_id,
+        new_price_feed_id: price_feed_id,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexPool, AiDexConfig};
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, AiDexConfig, ConfigRole};
 
 #[event]
 pub struct NewOracleAccountSetEvent {
+    pub event_version: u8,
     pub ai_dex_config: Pubkey,
     pub ai_dex_pool: Pubkey,
     pub config_authority: Pubkey,
@@ -17,7 +20,10 @@ pub struct SetNewOracleAccount<'info> {
     #[account(mut, has_one = ai_dex_config)]
     pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 
     /// CHECK: the account that will be new oracle can be arbitrary
@@ -34,6 +40,7 @@ pub fn set_new_oracle_handler(
         .update_oracle_account(ctx.accounts.new_oracle_account.key());
 
     emit!(NewOracleAccountSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         config_authority: ctx.accounts.config_authority.key(),
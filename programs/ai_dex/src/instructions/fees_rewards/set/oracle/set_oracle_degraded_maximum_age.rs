@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole};
+
+#[event]
+pub struct OracleDegradedMaximumAgeSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_degraded_maximum_age: u64,
+    pub new_degraded_maximum_age: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleDegradedMaximumAge<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the maximum age of the last oracle price of an oracle pool for swaps in degraded mode.
+///
+/// When the price update of a swap is missing or rejected, the swap executes at the last oracle
+/// price of the pool if it was updated at most this many seconds ago. Liquidity changes always
+/// require a valid price update.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the degraded maximum age.
+/// * `degraded_maximum_age` - The new degraded maximum age in seconds, or 0 to disable degraded mode.
+///
+/// # Errors
+///
+/// * `ErrorCode::NotOraclePool` - If the pool is not an oracle pool.
+pub fn set_oracle_degraded_maximum_age_handler(
+    ctx: Context<SetOracleDegradedMaximumAge>,
+    degraded_maximum_age: u64,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    if !ai_dex_pool.is_oracle_pool {
+        return Err(ErrorCode::NotOraclePool.into());
+    }
+
+    let old_degraded_maximum_age = ai_dex_pool.oracle_degraded_maximum_age;
+    ai_dex_pool.update_oracle_degraded_maximum_age(degraded_maximum_age);
+
+    emit!(OracleDegradedMaximumAgeSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_degraded_maximum_age,
+        new_degraded_maximum_age: degraded_maximum_age,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexConfig, OracleAccount};
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, OracleAccount, ConfigRole};
 
 #[event]
 pub struct NewOracleMaxAgeSetEvent {
+    pub event_version: u8,
     pub ai_dex_config: Pubkey,
     pub oracle_account: Pubkey,
     pub config_authority: Pubkey,
@@ -18,7 +21,10 @@ pub struct SetNewOracleMaxAgeAccount<'info> {
     #[account(mut)]
     pub oracle_account: Account<'info, OracleAccount>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 }
 
@@ -34,6 +40,7 @@ pub fn set_new_oracle_max_age_handler(
         .change_maximum_age(new_maximum_age)?;
     
     emit!(NewOracleMaxAgeSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         oracle_account: ctx.accounts.oracle_account.key(),
         config_authority: ctx.accounts.config_authority.key(),
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole};
+
+#[event]
+pub struct OracleMaximumSlotLagSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_maximum_slot_lag: u64,
+    pub new_maximum_slot_lag: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleMaximumSlotLag<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the maximum number of slots between the posting of an oracle price update and its use
+/// by an oracle pool.
+///
+/// Price updates can be within the maximum age of the oracle account and still lag behind the
+/// current slot during congestion. This bound is checked in addition to the maximum age.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the maximum slot lag.
+/// * `maximum_slot_lag` - The new maximum slot lag, or 0 to only check the maximum age.
+///
+/// # Errors
+///
+/// * `ErrorCode::NotOraclePool` - If the pool is not an oracle pool.
+pub fn set_oracle_maximum_slot_lag_handler(
+    ctx: Context<SetOracleMaximumSlotLag>,
+    maximum_slot_lag: u64,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    if !ai_dex_pool.is_oracle_pool {
+        return Err(ErrorCode::NotOraclePool.into());
+    }
+
+    let old_maximum_slot_lag = ai_dex_pool.oracle_maximum_slot_lag;
+    ai_dex_pool.update_oracle_maximum_slot_lag(maximum_slot_lag);
+
+    emit!(OracleMaximumSlotLagSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_maximum_slot_lag,
+        new_maximum_slot_lag: maximum_slot_lag,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole, OracleAccount, OraclePriceFeedDefault};
+
+#[event]
+pub struct OracleParamsSyncedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub oracle_account: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_maximum_age: u64,
+    pub new_maximum_age: u64,
+    pub old_price_feed_id: String,
+    pub new_price_feed_id: String,
+}
+
+#[derive(Accounts)]
+pub struct SyncOracleParams<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(constraint = ai_dex_pool.load()?.ai_dex_config == ai_dex_config.key() @ ErrorCode::InvalidAiDexConfig)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, address = ai_dex_pool.load()?.oracle_address)]
+    pub oracle_account: Account<'info, OracleAccount>,
+
+    /// Optional default price feed of the pair of the pool, synced along with the maximum age
+    #[account(
+        seeds = [
+            b"oracle_price_feed_default".as_ref(),
+            ai_dex_config.key().as_ref(),
+            oracle_account.mint_a.as_ref(),
+            oracle_account.mint_b.as_ref(),
+        ],
+        bump = oracle_price_feed_default.bump,
+    )]
+    pub oracle_price_feed_default: Option<Account<'info, OraclePriceFeedDefault>>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::OracleManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Applies the config-level oracle defaults to the oracle account of a pool.
+///
+/// Only the oracle manager of the config can sync a pool, since syncing overwrites the maximum
+/// age and price feed the pool was initialized or later configured with. The price feed is only
+/// synced when the default price feed of the pair is passed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for syncing the oracle parameters.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the oracle parameters are successfully synced,
+/// or an `Err` if the config has no default maximum age.
+pub fn sync_oracle_params_handler(ctx: Context<SyncOracleParams>) -> Result<()> {
+    let new_maximum_age = ctx
+        .accounts
+        .ai_dex_config
+        .get_default_oracle_maximum_age()
+        .ok_or(ErrorCode::DefaultOracleMaximumAgeNotSet)?;
+    let old_maximum_age = ctx.accounts.oracle_account.maximum_age;
+    let old_price_feed_id = ctx.accounts.oracle_account.price_feed_id.clone();
+
+    let oracle_account = &mut ctx.accounts.oracle_account;
+    oracle_account.change_maximum_age(new_maximum_age)?;
+    if let Some(oracle_price_feed_default) = &ctx.accounts.oracle_price_feed_default {
+        oracle_account.change_price_feed_id(oracle_price_feed_default.price_feed_id.clone());
+    }
+
+    emit!(OracleParamsSyncedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        oracle_account: ctx.accounts.oracle_account.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_maximum_age,
+        new_maximum_age,
+        old_price_feed_id,
+        new_price_feed_id: ctx.accounts.oracle_account.price_feed_id.clone(),
+    });
+
+    Ok(())
+}
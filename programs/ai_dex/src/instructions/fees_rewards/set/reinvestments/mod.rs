@@ -1,5 +1,7 @@
 pub mod set_default_reinvestment_fee_rate;
 pub mod set_new_reinvestments_authority;
+pub mod set_reinvestment_swap_max_deviation;
 
 pub use set_default_reinvestment_fee_rate::*;
-pub use set_new_reinvestments_authority::*;
\ No newline at end of file
+pub use set_new_reinvestments_authority::*;
+pub use set_reinvestment_swap_max_deviation::*;
\ No newline at end of file
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::AiDexReinvestments;
 
 #[event]
 pub struct DefaultReinvestmentFeeRateSetEvent {
+    pub event_version: u8,
     pub reinvestments_account: Pubkey,
     pub reinvestments_authority: Pubkey,
     pub new_default_reinvestment_fee_rate: u16,
@@ -28,6 +30,7 @@ pub fn set_default_reinvestment_fee_rate_handler(
         .update_default_reinvestment_fee_rate(new_default_reinvestment_fee_rate)?;
 
     emit!(DefaultReinvestmentFeeRateSetEvent {
+        event_version: EVENT_VERSION,
         reinvestments_account: ctx.accounts.reinvestments_account.key(),
         reinvestments_authority: ctx.accounts.reinvestments_authority.key(),
         new_default_reinvestment_fee_rate,
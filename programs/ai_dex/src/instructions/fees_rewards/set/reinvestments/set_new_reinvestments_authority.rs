@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::AiDexReinvestments;
 
 #[event]
 pub struct ReinvestmentNewAuthoritySetEvent {
+    pub event_version: u8,
     pub reinvestments_account: Pubkey,
     pub reinvestments_authority: Pubkey,
     pub new_reinvestments_authority: Pubkey,
@@ -28,6 +30,7 @@ pub fn set_new_reinvestments_authority_handler(
         .update_reinvestments_authority(new_reinvestments_authority)?;
 
     emit!(ReinvestmentNewAuthoritySetEvent {
+        event_version: EVENT_VERSION,
         reinvestments_account: ctx.accounts.reinvestments_account.key(),
         reinvestments_authority: ctx.accounts.reinvestments_authority.key(),
         new_reinvestments_authority,
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::Position;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct ReinvestmentSwapMaxDeviationSetEvent {
+    pub event_version: u8,
+    pub position: Pubkey,
+    pub position_authority: Pubkey,
+    pub old_max_deviation_bps: u16,
+    pub new_max_deviation_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetReinvestmentSwapMaxDeviation<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+}
+
+/// Sets how far the rate of a reinvestment swap of the fees of a position may deviate from the
+/// price of the pool snapshot.
+///
+/// Keepers choose the amount and limits of reinvestment swaps, so the owner of the position
+/// bounds the rate they can get for its fees. Reinvestment swaps are disabled while the maximum
+/// deviation is 0, which is the default.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the position and its authority.
+/// * `max_deviation_bps` - The largest deviation in basis points, or 0 to disable reinvestment swaps.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The signer is not the position authority.
+/// - The maximum deviation exceeds 10,000 basis points.
+pub fn set_reinvestment_swap_max_deviation_handler(
+    ctx: Context<SetReinvestmentSwapMaxDeviation>,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    let old_max_deviation_bps = position.reinvestment_swap_max_deviation_bps;
+    position.update_reinvestment_swap_max_deviation_bps(max_deviation_bps)?;
+
+    emit!(ReinvestmentSwapMaxDeviationSetEvent {
+        event_version: EVENT_VERSION,
+        position: position.key(),
+        position_authority: ctx.accounts.position_authority.key(),
+        old_max_deviation_bps,
+        new_max_deviation_bps: max_deviation_bps,
+    });
+
+    Ok(())
+}
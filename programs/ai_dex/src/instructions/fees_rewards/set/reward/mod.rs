@@ -1,7 +1,19 @@
 pub mod set_reward_authority;
 pub mod set_reward_authority_by_config_authority;
+pub mod set_reward_delegate;
 pub mod set_reward_emissions;
+pub mod set_reward_emissions_threshold;
+pub mod set_reward_full_range_boost;
+pub mod set_reward_out_of_range_grace;
+pub mod pause_reward;
+pub mod resume_reward;
 
 pub use set_reward_authority::*;
 pub use set_reward_authority_by_config_authority::*;
-pub use set_reward_emissions::*;
\ No newline at end of file
+pub use set_reward_delegate::*;
+pub use set_reward_emissions::*;
+pub use set_reward_emissions_threshold::*;
+pub use set_reward_full_range_boost::*;
+pub use set_reward_out_of_range_grace::*;
+pub use pause_reward::*;
+pub use resume_reward::*;
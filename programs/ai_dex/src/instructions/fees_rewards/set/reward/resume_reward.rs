@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
+use crate::state::{AiDexPool, RewardGraceEscrow};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct RewardResumedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub reward_authority: Pubkey,
+    pub emissions_per_second_x64: u128,
+    pub growth_global_x64: u128,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct ResumeReward<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub reward_authority: Signer<'info>,
+
+    /// The reward grace escrow of the pool, required while the reward has an out-of-range grace
+    #[account(
+        mut,
+        seeds = [b"reward_grace_escrow".as_ref(), ai_dex_pool.key().as_ref()],
+        bump = reward_grace_escrow.bump,
+    )]
+    pub reward_grace_escrow: Option<Account<'info, RewardGraceEscrow>>,
+}
+
+/// Resumes the emissions of a paused reward at its emissions rate.
+///
+/// Nothing accrues for the time the reward was paused, the growth of the reward restarts from the
+/// current timestamp.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for resuming the reward.
+/// * `reward_index` - The index of the reward to resume.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidRewardIndexError` - If the reward index is invalid or the reward is not initialized.
+/// * `ErrorCode::InvalidRewardAuthorityError` - If the signer is not the reward authority.
+/// * `ErrorCode::RewardNotPaused` - If the reward is not paused.
+/// * `ErrorCode::MissingRewardGraceEscrow` - If the reward has an out-of-range grace and its escrow is not passed.
+pub fn resume_reward_handler(ctx: Context<ResumeReward>, reward_index: u8) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    if reward_index as usize >= ai_dex_pool.reward_infos.len() {
+        return Err(ErrorCode::InvalidRewardIndexError.into());
+    }
+    if ctx.accounts.reward_authority.key() != ai_dex_pool.reward_infos[reward_index as usize].authority {
+        return Err(ErrorCode::InvalidRewardAuthorityError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if let Some(reward_grace_escrow) = &mut ctx.accounts.reward_grace_escrow {
+        reward_grace_escrow.accrue(&ai_dex_pool, timestamp);
+    } else if ai_dex_pool.out_of_range_grace_bps[reward_index as usize] != 0 {
+        return Err(ErrorCode::MissingRewardGraceEscrow.into());
+    }
+    let next_reward_infos = next_ai_dex_reward_infos(&ai_dex_pool, timestamp)?;
+    ai_dex_pool.update_reward_paused(reward_index as usize, next_reward_infos, timestamp, false)?;
+
+    let reward_info = ai_dex_pool.reward_infos[reward_index as usize];
+    emit!(RewardResumedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        reward_authority: ctx.accounts.reward_authority.key(),
+        emissions_per_second_x64: reward_info.emissions_per_second_x64,
+        growth_global_x64: reward_info.growth_global_x64,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::state::AiDexPool;
 
 #[event]
 pub struct RewardAuthorityUpdatedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub reward_index: u8,
     pub previous_reward_authority: Pubkey,
@@ -59,6 +61,7 @@ pub fn set_reward_authority_handler(
     )?;
 
     emit!(RewardAuthorityUpdatedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         reward_index,
         previous_reward_authority: ctx.accounts.reward_authority.key(),
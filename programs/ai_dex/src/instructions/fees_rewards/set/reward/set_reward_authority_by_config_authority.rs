@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexPool, AiDexConfig, NUM_REWARDS};
-use crate::errors::ErrorCode::InvalidRewardIndexError;
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexPool, AiDexConfig, NUM_REWARDS, ConfigRole};
+use crate::errors::ErrorCode::{self, InvalidRewardIndexError};
 
 #[event]
 pub struct RewardAuthoritySetEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub reward_index: u8,
     pub old_reward_authority: Pubkey,
@@ -20,7 +22,10 @@ pub struct SetRewardAuthorityByConfigAuthority<'info> {
     #[account(mut, has_one = ai_dex_config)]
     pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::RewardManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 
     /// CHECK: the account that will be new authority can be arbitrary
@@ -59,6 +64,7 @@ pub fn set_reward_authority_by_config_authority_handler(
     )?;
 
     emit!(RewardAuthoritySetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         reward_index,
         old_reward_authority,
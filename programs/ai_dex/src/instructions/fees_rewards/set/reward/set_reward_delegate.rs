@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::Position;
+use crate::util::verify_position_authority;
+
+#[event]
+pub struct RewardDelegateSetEvent {
+    pub event_version: u8,
+    pub position: Pubkey,
+    pub position_authority: Pubkey,
+    pub reward_delegate: Pubkey,
+    pub reward_recipient: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardDelegate<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+}
+
+/// Delegates the collection of the rewards of a position.
+///
+/// The delegate, such as a custodial platform or an auto-compounder program, can then call
+/// `collect_reward` in place of the position authority, to token accounts owned by the reward
+/// recipient only. The delegation is bound to the current owner of the position token account
+/// and lapses when the position is transferred. Setting the default pubkey as delegate clears it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the position and its authority.
+/// * `reward_delegate` - The delegate allowed to collect rewards, or the default pubkey to clear it.
+/// * `reward_recipient` - The owner of the token accounts the delegate collects rewards to.
+///
+/// # Errors
+///
+/// This function will return an error if the signer is not the position authority.
+pub fn set_reward_delegate_handler(
+    ctx: Context<SetRewardDelegate>,
+    reward_delegate: Pubkey,
+    reward_recipient: Pubkey,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    position.update_reward_delegate(
+        reward_delegate,
+        reward_recipient,
+        ctx.accounts.position_token_account.owner,
+    );
+
+    emit!(RewardDelegateSetEvent {
+        event_version: EVENT_VERSION,
+        position: position.key(),
+        position_authority: ctx.accounts.position_authority.key(),
+        reward_delegate: position.reward_delegate,
+        reward_recipient: position.reward_recipient,
+    });
+
+    Ok(())
+}
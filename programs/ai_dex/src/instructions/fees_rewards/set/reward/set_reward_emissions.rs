@@ -1,16 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::TokenAccount;
 
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
-use crate::math::checked_mul_shift_right;
-use crate::state::AiDexPool;
+use crate::math::{checked_mul_shift_right, get_reward_funded_duration};
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole, RewardEmissionsPolicy, RewardGraceEscrow};
 use crate::util::to_timestamp_u64;
 
 const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
 
 #[event]
 pub struct RewardEmissionsSetEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub reward_index: u8,
     pub reward_authority: Pubkey,
@@ -18,6 +20,7 @@ pub struct RewardEmissionsSetEvent {
     pub reward_vault_amount: u64,
     pub emissions_per_second_x64: u128,
     pub emissions_per_day: u64,
+    pub funded_duration: u64,
     pub timestamp: u64,
 }
 
@@ -34,6 +37,29 @@ pub struct SetRewardEmissions<'info> {
     // #[account(address = ai_dex_pool.reward_infos[reward_index as usize].vault)]
     #[account(mut)]
     pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: the emissions policy of the pool, deserialized in the handler once created by
+    /// `set_reward_emissions_threshold`
+    #[account(
+        mut,
+        seeds = [b"reward_emissions_policy".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub reward_emissions_policy: UncheckedAccount<'info>,
+
+    /// The config of the pool, required for emissions co-signed by the reward manager
+    pub ai_dex_config: Option<Account<'info, AiDexConfig>>,
+
+    /// The reward manager of the config, co-signing emissions beyond the threshold of the reward
+    pub reward_manager: Option<Signer<'info>>,
+
+    /// The reward grace escrow of the pool, required while the reward has an out-of-range grace
+    #[account(
+        mut,
+        seeds = [b"reward_grace_escrow".as_ref(), ai_dex_pool.key().as_ref()],
+        bump = reward_grace_escrow.bump,
+    )]
+    pub reward_grace_escrow: Option<Account<'info, RewardGraceEscrow>>,
 }
 
 /// Sets the reward emissions for the protocol.
@@ -43,6 +69,8 @@ pub struct SetRewardEmissions<'info> {
 /// * `ctx` - The context containing all the accounts and programs required for the operation.
 /// * `reward_index` - The index of the reward to set emissions for.
 /// * `emissions_per_second_x64` - The emissions rate per second, scaled by 2^64.
+/// * `min_funded_duration` - Optional number of seconds the reward vault must fund the emissions
+///   for. The vault always has to fund at least a day of emissions.
 ///
 /// # Returns
 ///
@@ -51,10 +79,14 @@ pub struct SetRewardEmissions<'info> {
 /// # Errors
 ///
 /// * `ErrorCode::InsufficientRewardVaultAmountError` - If the reward vault does not have enough tokens to cover the emissions for a day.
+/// * `ErrorCode::RewardEmissionsUnderfunded` - If the reward vault does not have enough tokens to cover the emissions for `min_funded_duration`.
+/// * `ErrorCode::EmissionsChangeRequiresCoSignature` - If the emissions are further from the baseline of the reward than its threshold and the reward manager of the config does not co-sign them.
+/// * `ErrorCode::MissingRewardGraceEscrow` - If the reward has an out-of-range grace and its escrow is not passed.
 pub fn set_reward_emissions_handler(
     ctx: Context<SetRewardEmissions>,
     reward_index: u8,
     emissions_per_second_x64: u128,
+    min_funded_duration: Option<u64>,
 ) -> Result<()> {
     let mut ai_dex_data = ctx.accounts.ai_dex_pool.load_mut()?;
 
@@ -72,6 +104,24 @@ pub fn set_reward_emissions_handler(
         return Err(ErrorCode::InvalidVault.into());
     }
 
+    let policy_info = ctx.accounts.reward_emissions_policy.to_account_info();
+    if policy_info.owner == &crate::ID {
+        let mut policy = RewardEmissionsPolicy::try_deserialize(&mut &policy_info.try_borrow_data()?[..])?;
+        let is_co_signed = match (&ctx.accounts.ai_dex_config, &ctx.accounts.reward_manager) {
+            (Some(ai_dex_config), Some(reward_manager)) => {
+                ai_dex_config.key() == ai_dex_data.ai_dex_config
+                    && ai_dex_config.is_role_authority(ConfigRole::RewardManager, reward_manager.key())
+            }
+            _ => false,
+        };
+        if is_co_signed {
+            policy.update_baseline(reward_index as usize, emissions_per_second_x64);
+            policy.try_serialize(&mut &mut policy_info.try_borrow_mut_data()?[..])?;
+        } else if policy.requires_co_signature(reward_index as usize, emissions_per_second_x64) {
+            return Err(ErrorCode::EmissionsChangeRequiresCoSignature.into());
+        }
+    }
+
     let reward_vault = &ctx.accounts.reward_vault;
 
     let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)?;
@@ -79,7 +129,26 @@ pub fn set_reward_emissions_handler(
         return Err(ErrorCode::InsufficientRewardVaultAmountError.into());
     }
 
+    // The vault balance also covers rewards owed but not collected yet, so the duration is an
+    // upper bound of how long the emissions are funded.
+    let funded_duration = get_reward_funded_duration(reward_vault.amount, emissions_per_second_x64);
+    if let Some(min_funded_duration) = min_funded_duration {
+        if funded_duration < min_funded_duration {
+            msg!(
+                "Reward vault funds {} seconds of emissions, {} required",
+                funded_duration,
+                min_funded_duration
+            );
+            return Err(ErrorCode::RewardEmissionsUnderfunded.into());
+        }
+    }
+
     let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if let Some(reward_grace_escrow) = &mut ctx.accounts.reward_grace_escrow {
+        reward_grace_escrow.accrue(&ai_dex_data, timestamp);
+    } else if ai_dex_data.out_of_range_grace_bps[reward_index as usize] != 0 {
+        return Err(ErrorCode::MissingRewardGraceEscrow.into());
+    }
     let next_reward_infos = next_ai_dex_reward_infos(&ai_dex_data, timestamp)?;
 
     ai_dex_data.update_emissions(
@@ -90,6 +159,7 @@ pub fn set_reward_emissions_handler(
     )?;
 
     emit!(RewardEmissionsSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         reward_index,
         reward_authority: ctx.accounts.reward_authority.key(),
@@ -97,9 +167,9 @@ pub fn set_reward_emissions_handler(
         reward_vault_amount: reward_vault.amount,
         emissions_per_second_x64,
         emissions_per_day,
+        funded_duration,
         timestamp,
     });
-    
 
     Ok(())
 }
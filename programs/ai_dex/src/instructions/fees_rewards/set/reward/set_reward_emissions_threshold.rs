@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole, RewardEmissionsPolicy};
+
+#[event]
+pub struct RewardEmissionsThresholdSetEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub previous_threshold_x64: u128,
+    pub new_threshold_x64: u128,
+    pub baseline_emissions_per_second_x64: u128,
+    pub reward_manager: Pubkey,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardEmissionsThreshold<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init_if_needed,
+        payer = reward_manager,
+        space = RewardEmissionsPolicy::LEN,
+        seeds = [b"reward_emissions_policy".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub reward_emissions_policy: Account<'info, RewardEmissionsPolicy>,
+
+    /// The reward manager of the config, paying for the policy on creation
+    #[account(
+        mut,
+        constraint = ai_dex_config.is_role_authority(ConfigRole::RewardManager, reward_manager.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub reward_manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets how far from its baseline the reward authority can set the emissions of a reward alone
+/// with `set_reward_emissions`. The current emissions of the reward become the baseline.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the threshold.
+/// * `reward_index` - The index of the reward for which the threshold is to be updated.
+/// * `threshold_x64` - The largest distance of `emissions_per_second_x64` from the baseline, or
+///   0 for no limit.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the threshold is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_reward_emissions_threshold_handler(
+    ctx: Context<SetRewardEmissionsThreshold>,
+    reward_index: u8,
+    threshold_x64: u128,
+) -> Result<()> {
+    let ai_dex_pool = ctx.accounts.ai_dex_pool.load()?;
+
+    if reward_index as usize >= ai_dex_pool.reward_infos.len() {
+        return Err(ErrorCode::InvalidRewardIndexError.into());
+    }
+    let emissions_per_second_x64 = ai_dex_pool.reward_infos[reward_index as usize].emissions_per_second_x64;
+
+    let reward_emissions_policy = &mut ctx.accounts.reward_emissions_policy;
+    if !reward_emissions_policy.is_initialized() {
+        reward_emissions_policy.initialize(
+            ctx.accounts.ai_dex_pool.key(),
+            ctx.bumps.reward_emissions_policy,
+        );
+    }
+    let previous_threshold_x64 = reward_emissions_policy.thresholds_x64[reward_index as usize];
+    reward_emissions_policy.update_threshold(reward_index as usize, threshold_x64, emissions_per_second_x64)?;
+
+    emit!(RewardEmissionsThresholdSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        previous_threshold_x64,
+        new_threshold_x64: threshold_x64,
+        baseline_emissions_per_second_x64: emissions_per_second_x64,
+        reward_manager: ctx.accounts.reward_manager.key(),
+    });
+
+    Ok(())
+}
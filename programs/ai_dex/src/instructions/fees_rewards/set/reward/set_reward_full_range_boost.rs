@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
+use crate::state::AiDexPool;
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct RewardFullRangeBoostUpdatedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub previous_boost_bps: u16,
+    pub new_boost_bps: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardFullRangeBoost<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub reward_authority: Signer<'info>,
+}
+
+/// Sets the extra weight of full-range positions in the emissions of a specific reward index.
+///
+/// The emissions are spread over the in-range liquidity weighted by the boost, and full-range
+/// positions earn the boost on top of the reward growth, so the boosted rewards stay within the
+/// emissions. The growth of the rewards is accrued up to the current timestamp at the previous
+/// boost first. The boost can only be raised before the reward accrues any growth.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the boost.
+/// * `reward_index` - The index of the reward for which the boost is to be updated.
+/// * `boost_bps` - The boost in basis points, up to `MAX_FULL_RANGE_REWARD_BOOST_BPS`.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the boost is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_reward_full_range_boost_handler(
+    ctx: Context<SetRewardFullRangeBoost>,
+    reward_index: u8,
+    boost_bps: u16,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    // Ensure the reward_index is valid
+    if reward_index as usize >= ai_dex_pool.reward_infos.len() {
+        return Err(ErrorCode::InvalidRewardIndexError.into());
+    }
+
+    // Check if the reward_authority matches the authority in reward_infos
+    if ctx.accounts.reward_authority.key() != ai_dex_pool.reward_infos[reward_index as usize].authority {
+        return Err(ErrorCode::InvalidRewardAuthorityError.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let next_reward_infos = next_ai_dex_reward_infos(&ai_dex_pool, timestamp)?;
+    ai_dex_pool.update_rewards(next_reward_infos, timestamp);
+
+    let previous_boost_bps = ai_dex_pool.full_range_reward_boost_bps[reward_index as usize];
+    ai_dex_pool.update_full_range_reward_boost(reward_index as usize, boost_bps)?;
+
+    emit!(RewardFullRangeBoostUpdatedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        previous_boost_bps,
+        new_boost_bps: boost_bps,
+    });
+
+    Ok(())
+}
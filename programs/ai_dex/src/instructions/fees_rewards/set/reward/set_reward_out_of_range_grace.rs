@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
+use crate::state::{AiDexPool, RewardGraceEscrow};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct RewardOutOfRangeGraceUpdatedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub reward_index: u8,
+    pub previous_grace_bps: u16,
+    pub new_grace_bps: u16,
+    pub previous_grace_period: u64,
+    pub new_grace_period: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardOutOfRangeGrace<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = reward_authority,
+        space = RewardGraceEscrow::LEN,
+        seeds = [b"reward_grace_escrow".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub reward_grace_escrow: Account<'info, RewardGraceEscrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the share of the emissions escrowed for positions that went out of range for a specific
+/// reward index, and for how long they are credited from it.
+///
+/// The reward growth and the reward grace escrow of the pool, created by the reward authority on
+/// first use, are accrued up to the current timestamp at the previous grace share first. Positions
+/// are only credited the grace share of the reward growth they miss once the grace is enabled.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the grace parameters.
+/// * `reward_index` - The index of the reward for which the grace parameters are to be updated.
+/// * `grace_bps` - The grace share in basis points, up to `MAX_OUT_OF_RANGE_GRACE_BPS`, or 0 to disable it.
+/// * `grace_period` - The grace period in seconds, up to `MAX_OUT_OF_RANGE_GRACE_PERIOD`.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the grace parameters are successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_reward_out_of_range_grace_handler(
+    ctx: Context<SetRewardOutOfRangeGrace>,
+    reward_index: u8,
+    grace_bps: u16,
+    grace_period: u64,
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    // Ensure the reward_index is valid
+    if reward_index as usize >= ai_dex_pool.reward_infos.len() {
+        return Err(ErrorCode::InvalidRewardIndexError.into());
+    }
+
+    // Check if the reward_authority matches the authority in reward_infos
+    if ctx.accounts.reward_authority.key() != ai_dex_pool.reward_infos[reward_index as usize].authority {
+        return Err(ErrorCode::InvalidRewardAuthorityError.into());
+    }
+
+    let previous_grace_bps = ai_dex_pool.out_of_range_grace_bps[reward_index as usize];
+    let previous_grace_period = ai_dex_pool.out_of_range_grace_periods[reward_index as usize];
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let reward_grace_escrow = &mut ctx.accounts.reward_grace_escrow;
+    if !reward_grace_escrow.is_initialized() {
+        reward_grace_escrow.initialize(
+            ctx.accounts.ai_dex_pool.key(),
+            timestamp,
+            ctx.bumps.reward_grace_escrow,
+        );
+    }
+    reward_grace_escrow.accrue(&ai_dex_pool, timestamp);
+    let next_reward_infos = next_ai_dex_reward_infos(&ai_dex_pool, timestamp)?;
+    ai_dex_pool.update_rewards(next_reward_infos, timestamp);
+
+    ai_dex_pool.update_out_of_range_grace(reward_index as usize, grace_bps, grace_period)?;
+    reward_grace_escrow.update_enabled_timestamp(reward_index as usize, previous_grace_bps, grace_bps, timestamp);
+
+    emit!(RewardOutOfRangeGraceUpdatedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        reward_index,
+        previous_grace_bps,
+        new_grace_bps: grace_bps,
+        previous_grace_period,
+        new_grace_period: grace_period,
+    });
+
+    Ok(())
+}
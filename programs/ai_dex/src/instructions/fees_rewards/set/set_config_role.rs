@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, ConfigRole};
+use crate::util::emit_program_version_event;
+
+#[event]
+pub struct ConfigRoleSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub role: ConfigRole,
+    pub old_role_holder: Pubkey,
+    pub new_role_holder: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetConfigRole<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: the account that will hold the role can be arbitrary
+    pub new_role_holder: UncheckedAccount<'info>,
+}
+
+/// Sets the holder of a config role.
+///
+/// Role holders can run the fee, oracle or reward instructions of the config without holding the
+/// config authority key. The config authority keeps access to all of them, and setting a role to
+/// the default pubkey revokes it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the role.
+/// * `role` - The role to set.
+pub fn set_config_role_handler(ctx: Context<SetConfigRole>, role: ConfigRole) -> Result<()> {
+    let old_role_holder = ctx.accounts.ai_dex_config.get_role(role);
+    let new_role_holder = ctx.accounts.new_role_holder.key();
+
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_role(role, new_role_holder);
+
+    emit!(ConfigRoleSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        role,
+        old_role_holder,
+        new_role_holder,
+    });
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{FeeTier, AiDexConfig};
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{FeeTier, AiDexConfig, ConfigRole};
 
 #[event]
 pub struct DefaultFeeRateSetEvent {
+    pub event_version: u8,
     pub ai_dex_config: Pubkey,
     pub fee_tier_key: Pubkey,
     pub config_authority: Pubkey,
@@ -18,7 +21,10 @@ pub struct SetDefaultFeeRate<'info> {
     #[account(mut, has_one = ai_dex_config)]
     pub fee_tier: Account<'info, FeeTier>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 }
 
@@ -43,6 +49,7 @@ pub fn set_default_fee_rate_handler(ctx: Context<SetDefaultFeeRate>, default_fee
         .update_default_fee_rate(default_fee_rate)?;
 
     emit!(DefaultFeeRateSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         fee_tier_key: ctx.accounts.fee_tier.key(),
         config_authority: ctx.accounts.config_authority.key(),
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::state::AiDexConfig;
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, ConfigRole};
+use crate::util::emit_program_version_event;
 
 #[event]
 pub struct DefaultProtocolFeeRateSetEvent {
+    pub event_version: u8,
     pub ai_dex_config: Pubkey,
     pub config_authority: Pubkey,
     pub new_default_protocol_fee_rate: u16,
@@ -14,7 +18,10 @@ pub struct SetDefaultProtocolFeeRate<'info> {
     #[account(mut)]
     pub ai_dex_config: Account<'info, AiDexConfig>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 }
 
@@ -41,10 +48,12 @@ pub fn set_default_protocol_fee_rate_handler(
         .update_default_protocol_fee_rate(default_protocol_fee_rate)?;
 
     emit!(DefaultProtocolFeeRateSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         config_authority: ctx.accounts.config_authority.key(),
         new_default_protocol_fee_rate: default_protocol_fee_rate,
     });        
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
 
     Ok(())
 }
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::AiDexConfig;
+use crate::util::emit_program_version_event;
+
+#[event]
+pub struct DustPositionParamsSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub dust_position_liquidity_threshold: u128,
+    pub dust_position_inactivity_period: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetDustPositionParams<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the parameters used to decide when a position can be liquidated as dust.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the parameters.
+/// * `dust_position_liquidity_threshold` - Positions with liquidity strictly below this value are considered dust.
+/// * `dust_position_inactivity_period` - The number of seconds a position must be inactive before it can be liquidated.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the parameters are successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_dust_position_params_handler(
+    ctx: Context<SetDustPositionParams>,
+    dust_position_liquidity_threshold: u128,
+    dust_position_inactivity_period: u64,
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_dust_position_params(dust_position_liquidity_threshold, dust_position_inactivity_period);
+
+    emit!(DustPositionParamsSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        dust_position_liquidity_threshold,
+        dust_position_inactivity_period,
+    });
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexPool, AiDexConfig};
+
+#[event]
+pub struct DustTransferThresholdsSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub dust_transfer_threshold_a: u32,
+    pub dust_transfer_threshold_b: u32,
+}
+
+#[derive(Accounts)]
+pub struct SetDustTransferThresholds<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the amounts below which the withdrawals of `decrease_liquidity` from a pool are added to
+/// the principal owed by the position rather than transferred.
+///
+/// Tiny liquidity decreases otherwise pay a transfer CPI, and its transfer fee, for a few raw
+/// units. Setting 0 transfers any amount.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the thresholds.
+/// * `dust_transfer_threshold_a` - The dust transfer threshold of token A, in raw token units.
+/// * `dust_transfer_threshold_b` - The dust transfer threshold of token B, in raw token units.
+pub fn set_dust_transfer_thresholds_handler(
+    ctx: Context<SetDustTransferThresholds>,
+    dust_transfer_threshold_a: u32,
+    dust_transfer_threshold_b: u32,
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_pool
+        .load_mut()?
+        .update_dust_transfer_thresholds(dust_transfer_threshold_a, dust_transfer_threshold_b);
+
+    emit!(DustTransferThresholdsSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        dust_transfer_threshold_a,
+        dust_transfer_threshold_b,
+    });
+
+    Ok(())
+}
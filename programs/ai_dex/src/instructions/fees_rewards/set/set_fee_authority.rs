@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::AiDexConfig;
+use crate::util::emit_program_version_event;
 
 #[event]
 pub struct FeeAuthorityUpdatedEvent {
+    pub event_version: u8,
     pub ai_dex_config: Pubkey,
     pub old_fee_authority: Pubkey,
     pub new_fee_authority: Pubkey,
@@ -43,10 +46,12 @@ pub fn set_fee_authority_handler(
         .update_config_authority(ctx.accounts.new_config_authority.key());
 
     emit!(FeeAuthorityUpdatedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         old_fee_authority: ctx.accounts.config_authority.key(),
         new_fee_authority: ctx.accounts.new_config_authority.key(),
     });        
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
 
     Ok(())
 }
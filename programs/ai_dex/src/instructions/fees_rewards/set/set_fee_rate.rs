@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexPool, AiDexConfig};
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, AiDexConfig, ConfigRole};
 
 #[event]
 pub struct FeeRateSetEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub ai_dex_config: Pubkey,
     pub config_authority: Pubkey,
@@ -18,7 +21,10 @@ pub struct SetFeeRate<'info> {
     #[account(mut, has_one = ai_dex_config)]
     pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 }
 
@@ -46,6 +52,7 @@ pub fn set_fee_rate_handler(
     ai_dex_pool.update_fee_rate(fee_rate)?;
 
     emit!(FeeRateSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         config_authority: ctx.accounts.config_authority.key(),
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, ConfigRole};
+use crate::util::emit_program_version_event;
+
+#[event]
+pub struct MaxIntegratorFeeRateSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_max_integrator_fee_rate: u16,
+    pub new_max_integrator_fee_rate: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxIntegratorFeeRate<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the maximum integrator fee rate that swaps of the config may charge.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the rate.
+/// * `max_integrator_fee_rate` - The new maximum integrator fee rate in basis points, or 0 to disable integrator fees.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the rate is successfully updated,
+/// or an `Err` if the rate exceeds the maximum integrator fee rate.
+pub fn set_max_integrator_fee_rate_handler(
+    ctx: Context<SetMaxIntegratorFeeRate>,
+    max_integrator_fee_rate: u16,
+) -> Result<()> {
+    let old_max_integrator_fee_rate = ctx.accounts.ai_dex_config.max_integrator_fee_rate;
+
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_max_integrator_fee_rate(max_integrator_fee_rate)?;
+
+    emit!(MaxIntegratorFeeRateSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_max_integrator_fee_rate,
+        new_max_integrator_fee_rate: max_integrator_fee_rate,
+    });
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexPool, AiDexConfig};
+
+#[event]
+pub struct MaxSwapLiquidityBpsSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub max_swap_liquidity_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxSwapLiquidityBps<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the maximum swap size of a pool as a share of its in-range liquidity.
+///
+/// Swaps whose input exceeds `max_swap_liquidity_bps` of the in-range reserve of the input token
+/// are rejected. This protects oracle-less pools whose price is read by other protocols from
+/// being moved by a single large swap. Setting 0 removes the limit.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the limit.
+/// * `max_swap_liquidity_bps` - The maximum swap input, in basis points of the in-range reserve.
+///
+/// # Errors
+///
+/// This function will return an error if the share exceeds `MAX_SWAP_LIQUIDITY_BPS`.
+pub fn set_max_swap_liquidity_bps_handler(
+    ctx: Context<SetMaxSwapLiquidityBps>,
+    max_swap_liquidity_bps: u16,
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_pool
+        .load_mut()?
+        .update_max_swap_liquidity_bps(max_swap_liquidity_bps)?;
+
+    emit!(MaxSwapLiquidityBpsSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        max_swap_liquidity_bps,
+    });
+
+    Ok(())
+}
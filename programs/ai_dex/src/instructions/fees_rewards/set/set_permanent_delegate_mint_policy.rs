@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::AiDexConfig;
+use crate::util::emit_program_version_event;
+
+#[event]
+pub struct PermanentDelegateMintPolicySetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub rejects_permanent_delegate_mints: bool,
+}
+
+#[derive(Accounts)]
+pub struct SetPermanentDelegateMintPolicy<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets whether new pools of the config reject token mints with a permanent delegate.
+///
+/// A permanent delegate can transfer or burn the tokens held by the vaults of a pool. Pools record
+/// whether one of their mints has one, and configs may refuse such pools altogether. Existing
+/// pools are not affected.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the policy.
+/// * `rejects_permanent_delegate_mints` - Whether pools with a permanent delegate mint are rejected.
+pub fn set_permanent_delegate_mint_policy_handler(
+    ctx: Context<SetPermanentDelegateMintPolicy>,
+    rejects_permanent_delegate_mints: bool,
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_permanent_delegate_mint_policy(rejects_permanent_delegate_mints);
+
+    emit!(PermanentDelegateMintPolicySetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        rejects_permanent_delegate_mints,
+    });
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, PoolMetadata};
+
+#[event]
+pub struct PoolMetadataSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub pool_metadata: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub uri: String,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolMetadata<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PoolMetadata::LEN,
+        seeds = [b"pool_metadata".as_ref(), ai_dex_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_metadata: Account<'info, PoolMetadata>,
+
+    /// The config authority or the creator of the pool, paying for the metadata on creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates the metadata of a pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the metadata.
+/// * `name` - The name of the pool, at most 32 bytes.
+/// * `tags` - The category tags of the pool, at most 4 tags of at most 16 bytes each.
+/// * `uri` - The uri of the project links of the pool, at most 200 bytes.
+///
+/// # Errors
+///
+/// This function will return an error if the signer is neither the config authority nor the
+/// creator of the pool, or if the metadata exceeds its maximum length.
+pub fn set_pool_metadata_handler(
+    ctx: Context<SetPoolMetadata>,
+    name: String,
+    tags: Vec<String>,
+    uri: String,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    if authority != ctx.accounts.ai_dex_config.config_authority
+        && !ctx.accounts.ai_dex_pool.load()?.is_creator(&authority)
+    {
+        return Err(ErrorCode::InvalidPoolMetadataAuthority.into());
+    }
+
+    let pool_metadata = &mut ctx.accounts.pool_metadata;
+    if !pool_metadata.is_initialized() {
+        pool_metadata.initialize(ctx.accounts.ai_dex_pool.key(), ctx.bumps.pool_metadata);
+    }
+    pool_metadata.update_metadata(name.clone(), tags.clone(), uri.clone())?;
+
+    emit!(PoolMetadataSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        pool_metadata: ctx.accounts.pool_metadata.key(),
+        authority,
+        name,
+        tags,
+        uri,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole};
+
+#[event]
+pub struct PoolSwapReferralRewardFeeRateSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub new_swap_referral_reward_fee_rate: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolSwapReferralRewardFeeRates<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+    // remaining accounts
+    // - the writable pools to update, of the same config and token pair
+}
+
+/// Sets the swap referral reward fee rate of the pools of a token pair.
+///
+/// The pools are passed as remaining accounts and must share the config and token mints, so the
+/// pools of a pair with distinct fee tiers can be kept in sync in a single instruction. Setting
+/// `USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE` makes the pools follow the default rate of
+/// the config again.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the config, its authority and the pools as remaining accounts.
+/// * `swap_referral_reward_fee_rate` - The new swap referral reward fee rate of the pools.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * No pool is provided, or the pools do not share the config and token pair.
+/// * The rate exceeds the maximum or is unchanged for one of the pools.
+pub fn set_pool_swap_referral_reward_fee_rates_handler<'a, 'b, 'info>(
+    ctx: Context<'a, 'b, 'info, 'info, SetPoolSwapReferralRewardFeeRates<'info>>,
+    swap_referral_reward_fee_rate: u16,
+) -> Result<()> {
+    if ctx.remaining_accounts.is_empty() {
+        return Err(ErrorCode::InvalidPoolBatch.into());
+    }
+
+    let mut token_pair: Option<(Pubkey, Pubkey)> = None;
+    for pool_info in ctx.remaining_accounts.iter() {
+        if !pool_info.is_writable {
+            return Err(ErrorCode::InvalidPoolBatch.into());
+        }
+        let ai_dex_pool = AccountLoader::<AiDexPool>::try_from(pool_info)?;
+        let mut ai_dex_pool_data = ai_dex_pool.load_mut()?;
+
+        if ai_dex_pool_data.ai_dex_config != ctx.accounts.ai_dex_config.key() {
+            return Err(ErrorCode::InvalidPoolBatch.into());
+        }
+        let pool_token_pair = (ai_dex_pool_data.token_mint_a, ai_dex_pool_data.token_mint_b);
+        if *token_pair.get_or_insert(pool_token_pair) != pool_token_pair {
+            return Err(ErrorCode::InvalidPoolBatch.into());
+        }
+
+        ai_dex_pool_data.update_swap_referral_reward_fee_rate(swap_referral_reward_fee_rate)?;
+
+        emit!(PoolSwapReferralRewardFeeRateSetEvent {
+            event_version: EVENT_VERSION,
+            ai_dex_config: ctx.accounts.ai_dex_config.key(),
+            ai_dex_pool: pool_info.key(),
+            config_authority: ctx.accounts.config_authority.key(),
+            new_swap_referral_reward_fee_rate: swap_referral_reward_fee_rate,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, PositionMetadata};
+
+#[event]
+pub struct PositionCollectionSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub position_metadata: Pubkey,
+    pub config_authority: Pubkey,
+    pub collection_mint: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetPositionCollection<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub position_metadata: Account<'info, PositionMetadata>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the Metaplex collection that position NFTs of a config are verified into.
+///
+/// The collection update authority must approve the `[b"position_collection_authority", ai_dex_config]`
+/// PDA as a collection authority so positions can be verified when they are opened.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the collection.
+/// * `collection_mint` - The mint of the collection NFT, `Pubkey::default()` to disable collections.
+pub fn set_position_collection_handler(
+    ctx: Context<SetPositionCollection>,
+    collection_mint: Pubkey,
+) -> Result<()> {
+    ctx
+        .accounts
+        .position_metadata
+        .update_collection_mint(collection_mint);
+
+    emit!(PositionCollectionSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        position_metadata: ctx.accounts.position_metadata.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        collection_mint,
+    });
+
+    Ok(())
+}
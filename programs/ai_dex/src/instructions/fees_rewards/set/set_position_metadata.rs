@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, PositionMetadata};
+
+#[event]
+pub struct PositionMetadataSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub position_metadata: Pubkey,
+    pub config_authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[derive(Accounts)]
+pub struct SetPositionMetadata<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub position_metadata: Account<'info, PositionMetadata>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+/// Updates the position NFT metadata used by all pools of a config.
+///
+/// Only positions opened after the update use the new metadata.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the metadata.
+/// * `name` - The name of the position NFTs, at most 32 bytes.
+/// * `symbol` - The symbol of the position NFTs, at most 10 bytes.
+/// * `uri` - The uri of the position NFTs, at most 200 bytes.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the metadata is successfully updated,
+/// or an `Err` if an error occurs.
+pub fn set_position_metadata_handler(
+    ctx: Context<SetPositionMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    ctx
+        .accounts
+        .position_metadata
+        .update_metadata(name.clone(), symbol.clone(), uri.clone())?;
+
+    emit!(PositionMetadataSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        position_metadata: ctx.accounts.position_metadata.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        name,
+        symbol,
+        uri,
+    });
+
+    Ok(())
+}
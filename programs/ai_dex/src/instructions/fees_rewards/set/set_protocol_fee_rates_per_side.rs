@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, AiDexConfig, ConfigRole};
+
+#[event]
+pub struct ProtocolFeeRatesPerSideSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub new_protocol_fee_rate_a: u16,
+    pub new_protocol_fee_rate_b: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFeeRatesPerSide<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets distinct protocol fee rates for the fees in token A and in token B of a pool.
+///
+/// Swap fees are paid in the input token, so this lets the protocol take its revenue primarily
+/// in one asset, such as the stable side of a stable/volatile pair. `set_protocol_fee_rate`
+/// returns the pool to a single rate for both tokens.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the protocol fee rates.
+/// * `protocol_fee_rate_a` - The protocol fee rate of the fees in token A.
+/// * `protocol_fee_rate_b` - The protocol fee rate of the fees in token B.
+///
+/// # Errors
+///
+/// This function will return an error if a protocol fee rate exceeds the maximum protocol fee rate.
+pub fn set_protocol_fee_rates_per_side_handler(
+    ctx: Context<SetProtocolFeeRatesPerSide>,
+    protocol_fee_rate_a: u16,
+    protocol_fee_rate_b: u16,
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_pool
+        .load_mut()?
+        .update_protocol_fee_rates_per_side(protocol_fee_rate_a, protocol_fee_rate_b)?;
+
+    emit!(ProtocolFeeRatesPerSideSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        new_protocol_fee_rate_a: protocol_fee_rate_a,
+        new_protocol_fee_rate_b: protocol_fee_rate_b,
+    });
+
+    Ok(())
+}
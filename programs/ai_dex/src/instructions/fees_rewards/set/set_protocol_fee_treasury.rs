@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, SuperAdmin};
+use crate::util::emit_program_version_event;
+use crate::instructions::record_super_admin_activity;
+
+#[event]
+pub struct ProtocolFeeTreasurySetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub super_admin_authority: Pubkey,
+    pub old_protocol_fee_treasury: Pubkey,
+    pub new_protocol_fee_treasury: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFeeTreasury<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, constraint = super_admin_account.super_admin == super_admin_authority.key())]
+    pub super_admin_account: Account<'info, SuperAdmin>,
+
+    pub super_admin_authority: Signer<'info>,
+
+    /// CHECK: the treasury owner can be arbitrary
+    pub new_protocol_fee_treasury: UncheckedAccount<'info>,
+}
+
+/// Sets the owner of the token accounts protocol fees can be collected to.
+///
+/// The treasury is set by the super admin rather than the config authority, so the config
+/// authority alone cannot redirect protocol fees. Setting it to the default pubkey allows any
+/// destination again.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the treasury.
+pub fn set_protocol_fee_treasury_handler(ctx: Context<SetProtocolFeeTreasury>) -> Result<()> {
+    record_super_admin_activity(&mut ctx.accounts.super_admin_account)?;
+
+    let old_protocol_fee_treasury = ctx.accounts.ai_dex_config.protocol_fee_treasury;
+    let new_protocol_fee_treasury = ctx.accounts.new_protocol_fee_treasury.key();
+
+    ctx
+        .accounts
+        .ai_dex_config
+        .update_protocol_fee_treasury(new_protocol_fee_treasury);
+
+    emit!(ProtocolFeeTreasurySetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        super_admin_authority: ctx.accounts.super_admin_authority.key(),
+        old_protocol_fee_treasury,
+        new_protocol_fee_treasury,
+    });
+    emit_program_version_event(&ctx.accounts.ai_dex_config);
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, ConfigRole, StakingRewards};
+
+#[event]
+pub struct StakingRewardShareSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub staking_reward_share_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct SetStakingRewardShare<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// The staking rewards of the config must exist before a pool owes them fees
+    #[account(
+        seeds = [b"staking_rewards".as_ref(), ai_dex_config.key().as_ref()],
+        bump = staking_rewards.bump,
+    )]
+    pub staking_rewards: Account<'info, StakingRewards>,
+
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
+    pub config_authority: Signer<'info>,
+}
+
+/// Sets the share of the protocol fees of swaps of a pool owed to the staking rewards of its
+/// config.
+///
+/// The share applies to the protocol fees accrued by later swaps. Protocol fees already owed stay
+/// with the protocol.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the share.
+/// * `staking_reward_share_bps` - The share of the protocol fees, in basis points.
+///
+/// # Errors
+///
+/// This function will return an error if the share exceeds 100%.
+pub fn set_staking_reward_share_handler(
+    ctx: Context<SetStakingRewardShare>,
+    staking_reward_share_bps: u16,
+) -> Result<()> {
+    ctx
+        .accounts
+        .ai_dex_pool
+        .load_mut()?
+        .update_staking_reward_share_bps(staking_reward_share_bps)?;
+
+    emit!(StakingRewardShareSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        staking_reward_share_bps,
+    });
+
+    Ok(())
+}
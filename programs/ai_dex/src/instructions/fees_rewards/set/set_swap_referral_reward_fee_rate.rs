@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AiDexConfig, SwapReferral};
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, SwapReferral, ConfigRole};
 
 #[event]
 pub struct SwapReferralRewardFeeRateSetEvent {
+    pub event_version: u8,
     pub ai_dex_config: Pubkey,
     pub swap_referral_account: Pubkey,
     pub config_authority: Pubkey,
@@ -17,7 +20,10 @@ pub struct SetSwapReferralRewardFeeRate<'info> {
     #[account(mut)]
     pub swap_referral_account: Account<'info, SwapReferral>,
 
-    #[account(address = ai_dex_config.config_authority)]
+    #[account(
+        constraint = ai_dex_config.is_role_authority(ConfigRole::FeeManager, config_authority.key())
+            @ ErrorCode::InvalidConfigRoleAuthority
+    )]
     pub config_authority: Signer<'info>,
 }
 
@@ -31,6 +37,7 @@ pub fn set_swap_referral_reward_fee_rate_handler(
         .update_swap_reward_fee_rate(swap_referral_reward_fee_rate)?;
 
     emit!(SwapReferralRewardFeeRateSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         swap_referral_account: ctx.accounts.swap_referral_account.key(),
         config_authority: ctx.accounts.config_authority.key(),
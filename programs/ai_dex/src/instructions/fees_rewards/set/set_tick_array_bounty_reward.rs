@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, TickArrayBounty};
+
+#[event]
+pub struct TickArrayBountyRewardSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array_bounty: Pubkey,
+    pub authority: Pubkey,
+    pub old_reward_per_tick_array: u64,
+    pub new_reward_per_tick_array: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetTickArrayBountyReward<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub tick_array_bounty: Account<'info, TickArrayBounty>,
+
+    /// The config authority or the creator of the pool
+    pub authority: Signer<'info>,
+}
+
+/// Sets the lamports paid by the tick array bounty of a pool for each tick array next to the
+/// current price.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the reward.
+/// * `reward_per_tick_array` - The new reward per tick array, or 0 to pause the bounty.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTickArrayBountyAuthority` - If the signer is neither the config authority
+///   nor the creator of the pool.
+pub fn set_tick_array_bounty_reward_handler(
+    ctx: Context<SetTickArrayBountyReward>,
+    reward_per_tick_array: u64,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    if authority != ctx.accounts.ai_dex_config.config_authority
+        && !ctx.accounts.ai_dex_pool.load()?.is_creator(&authority)
+    {
+        return Err(ErrorCode::InvalidTickArrayBountyAuthority.into());
+    }
+
+    let tick_array_bounty = &mut ctx.accounts.tick_array_bounty;
+    let old_reward_per_tick_array = tick_array_bounty.reward_per_tick_array;
+    tick_array_bounty.update_reward_per_tick_array(reward_per_tick_array);
+
+    emit!(TickArrayBountyRewardSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        tick_array_bounty: ctx.accounts.tick_array_bounty.key(),
+        authority,
+        old_reward_per_tick_array,
+        new_reward_per_tick_array: reward_per_tick_array,
+    });
+
+    Ok(())
+}
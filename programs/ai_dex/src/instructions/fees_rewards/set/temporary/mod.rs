@@ -2,8 +2,10 @@ pub mod set_start_timestamp_lp;
 pub mod set_end_timestamp_lp;
 pub mod set_start_timestamp_swap;
 pub mod set_end_timestamp_swap;
+pub mod set_early_exit_fee;
 
 pub use set_start_timestamp_lp::*;
 pub use set_end_timestamp_lp::*;
 pub use set_start_timestamp_swap::*;
-pub use set_end_timestamp_swap::*;
\ No newline at end of file
+pub use set_end_timestamp_swap::*;
+pub use set_early_exit_fee::*;
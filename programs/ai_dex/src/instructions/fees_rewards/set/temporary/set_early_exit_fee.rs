@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
+use crate::instructions::fees_rewards::set::set_start_timestamp_lp::SetTimestamp;
+
+#[event]
+pub struct EarlyExitFeeSetEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub ai_dex_config: Pubkey,
+    pub config_authority: Pubkey,
+    pub old_early_exit_fee_bps: u16,
+    pub new_early_exit_fee_bps: u16,
+}
+
+pub fn set_early_exit_fee_handler(
+    ctx: Context<SetTimestamp>,
+    early_exit_fee_bps: u16
+) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    let old_early_exit_fee_bps = ai_dex_pool.early_exit_fee_bps;
+
+    ai_dex_pool.update_early_exit_fee_bps(early_exit_fee_bps)?;
+
+    emit!(EarlyExitFeeSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        old_early_exit_fee_bps,
+        new_early_exit_fee_bps: early_exit_fee_bps,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::constants::EVENT_VERSION;
 use crate::instructions::fees_rewards::set::set_start_timestamp_lp::SetTimestamp;
 
 #[event]
 pub struct EndTimestampSwapSetEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub ai_dex_config: Pubkey,
     pub config_authority: Pubkey,
@@ -21,6 +23,7 @@ pub fn set_end_timestamp_swap_handler(
     ai_dex_pool.update_end_timestamp_swap(new_timestamp);
 
     emit!(EndTimestampSwapSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         config_authority: ctx.accounts.config_authority.key(),
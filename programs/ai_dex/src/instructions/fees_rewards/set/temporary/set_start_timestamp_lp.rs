@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::{AiDexPool, AiDexConfig};
 
 #[event]
 pub struct StartTimestampLpSetEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub ai_dex_config: Pubkey,
     pub config_authority: Pubkey,
@@ -33,6 +35,7 @@ pub fn set_start_timestamp_lp_handler(
     ai_dex_pool.update_start_timestamp_lp(new_timestamp);
 
     emit!(StartTimestampLpSetEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         ai_dex_config: ctx.accounts.ai_dex_config.key(),
         config_authority: ctx.accounts.config_authority.key(),
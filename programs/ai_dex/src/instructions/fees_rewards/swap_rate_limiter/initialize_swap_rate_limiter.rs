@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, SwapRateLimiter};
+
+#[event]
+pub struct SwapRateLimiterInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub swap_rate_limiter: Pubkey,
+    pub token_mint: Pubkey,
+    pub max_volume_per_window: u64,
+    pub window_duration: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSwapRateLimiter<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = SwapRateLimiter::LEN,
+        seeds = [
+            b"swap_rate_limiter".as_ref(),
+            ai_dex_config.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub swap_rate_limiter: Account<'info, SwapRateLimiter>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a swap rate limiter on the volume of a token, to be attached to pools of the config
+/// with `set_pool_swap_rate_limiter`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for creating the limiter.
+/// * `max_volume_per_window` - The maximum amount of the token swapped per window.
+/// * `window_duration` - The duration of the rolling window in seconds.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the limiter is successfully created,
+/// or an `Err` if the rate limit is invalid.
+pub fn initialize_swap_rate_limiter_handler(
+    ctx: Context<InitializeSwapRateLimiter>,
+    max_volume_per_window: u64,
+    window_duration: u64,
+) -> Result<()> {
+    ctx.accounts.swap_rate_limiter.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.accounts.token_mint.key(),
+        max_volume_per_window,
+        window_duration,
+        ctx.bumps.swap_rate_limiter,
+    )?;
+
+    emit!(SwapRateLimiterInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        swap_rate_limiter: ctx.accounts.swap_rate_limiter.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        max_volume_per_window,
+        window_duration,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod initialize_swap_rate_limiter;
+pub mod set_swap_rate_limit;
+pub mod set_pool_swap_rate_limiter;
+
+pub use initialize_swap_rate_limiter::*;
+pub use set_swap_rate_limit::*;
+pub use set_pool_swap_rate_limiter::*;
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, AiDexPool, SwapRateLimiter};
+
+#[event]
+pub struct PoolSwapRateLimiterSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub config_authority: Pubkey,
+    pub swap_rate_limiter: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolSwapRateLimiter<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(has_one = ai_dex_config)]
+    pub swap_rate_limiter: Option<Account<'info, SwapRateLimiter>>,
+}
+
+/// Attaches a swap rate limiter to a pool, or detaches the current one when no limiter is passed.
+///
+/// Once attached, every swap through the pool must pass the limiter in the `SwapRateLimiters`
+/// remaining accounts, and is rejected when the volume of the limiter's token over the window
+/// exceeds its limit.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the limiter.
+///
+/// # Errors
+///
+/// This function will return an error if the token of the limiter is not a token of the pool.
+pub fn set_pool_swap_rate_limiter_handler(ctx: Context<SetPoolSwapRateLimiter>) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+
+    let swap_rate_limiter = match &ctx.accounts.swap_rate_limiter {
+        Some(swap_rate_limiter) => {
+            if swap_rate_limiter.token_mint != ai_dex_pool.token_mint_a
+                && swap_rate_limiter.token_mint != ai_dex_pool.token_mint_b
+            {
+                return Err(ErrorCode::InvalidSwapRateLimiter.into());
+            }
+            swap_rate_limiter.key()
+        }
+        None => Pubkey::default(),
+    };
+    ai_dex_pool.swap_rate_limiter = swap_rate_limiter;
+
+    emit!(PoolSwapRateLimiterSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        config_authority: ctx.accounts.config_authority.key(),
+        swap_rate_limiter,
+    });
+
+    Ok(())
+}
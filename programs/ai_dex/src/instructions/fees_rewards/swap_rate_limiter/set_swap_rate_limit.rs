@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, SwapRateLimiter};
+
+#[event]
+pub struct SwapRateLimitSetEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub swap_rate_limiter: Pubkey,
+    pub old_max_volume_per_window: u64,
+    pub new_max_volume_per_window: u64,
+    pub old_window_duration: u64,
+    pub new_window_duration: u64,
+}
+
+#[derive(Accounts)]
+pub struct SetSwapRateLimit<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub swap_rate_limiter: Account<'info, SwapRateLimiter>,
+}
+
+/// Sets the rate limit of a swap rate limiter, and restarts its window.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for setting the rate limit.
+/// * `max_volume_per_window` - The maximum amount of the token swapped per window.
+/// * `window_duration` - The duration of the rolling window in seconds.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the rate limit is successfully updated,
+/// or an `Err` if the rate limit is invalid.
+pub fn set_swap_rate_limit_handler(
+    ctx: Context<SetSwapRateLimit>,
+    max_volume_per_window: u64,
+    window_duration: u64,
+) -> Result<()> {
+    let swap_rate_limiter = &mut ctx.accounts.swap_rate_limiter;
+    let old_max_volume_per_window = swap_rate_limiter.max_volume_per_window;
+    let old_window_duration = swap_rate_limiter.window_duration;
+
+    swap_rate_limiter.update_rate_limit(max_volume_per_window, window_duration)?;
+
+    emit!(SwapRateLimitSetEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        swap_rate_limiter: swap_rate_limiter.key(),
+        old_max_volume_per_window,
+        new_max_volume_per_window: max_volume_per_window,
+        old_window_duration,
+        new_window_duration: window_duration,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, TokenBadge};
+
+#[event]
+pub struct TokenBadgeDeletedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub token_badge: Pubkey,
+    pub token_mint: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct DeleteTokenBadge<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    #[account(mut, has_one = ai_dex_config, close = receiver)]
+    pub token_badge: Account<'info, TokenBadge>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Deletes a token badge, refunding its rent to the receiver.
+///
+/// Rewards already initialized with the mint keep working, the badge is only checked when a
+/// reward is initialized.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for deleting the token badge.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the token badge is successfully deleted.
+pub fn delete_token_badge_handler(ctx: Context<DeleteTokenBadge>) -> Result<()> {
+    emit!(TokenBadgeDeletedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        token_badge: ctx.accounts.token_badge.key(),
+        token_mint: ctx.accounts.token_badge.token_mint,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, TokenBadge};
+
+#[event]
+pub struct TokenBadgeInitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub token_badge: Pubkey,
+    pub token_mint: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTokenBadge<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = TokenBadge::LEN,
+        seeds = [
+            b"token_badge".as_ref(),
+            ai_dex_config.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub token_badge: Account<'info, TokenBadge>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Issues a token badge, approving a mint that needs one for use by the pools of the config.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for issuing the token badge.
+///
+/// # Returns
+///
+/// This function returns a `Result` which is `Ok` if the token badge is successfully issued.
+pub fn initialize_token_badge_handler(ctx: Context<InitializeTokenBadge>) -> Result<()> {
+    ctx.accounts.token_badge.initialize(
+        ctx.accounts.ai_dex_config.key(),
+        ctx.accounts.token_mint.key(),
+        ctx.bumps.token_badge,
+    );
+
+    emit!(TokenBadgeInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        token_badge: ctx.accounts.token_badge.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod initialize_token_badge;
+pub mod delete_token_badge;
+
+pub use initialize_token_badge::*;
+pub use delete_token_badge::*;
@@ -2,4 +2,16 @@ pub mod update_fees_and_rewards;
 pub use update_fees_and_rewards::*;
 
 pub mod update_reinvestments;
-pub use update_reinvestments::*;
\ No newline at end of file
+pub use update_reinvestments::*;
+pub mod reinvest_fees_with_swap;
+pub use reinvest_fees_with_swap::*;
+pub mod sync_pool_stats;
+pub use sync_pool_stats::*;
+pub mod sync_protocol_stats;
+pub use sync_protocol_stats::*;
+
+pub mod refresh_oracle_price;
+pub use refresh_oracle_price::*;
+
+pub mod refresh_pool_snapshot;
+pub use refresh_pool_snapshot::*;
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::EVENT_VERSION;
+use crate::{
+    errors::ErrorCode,
+    state::{AiDexPool, OracleAccount},
+    util::to_timestamp_u64,
+};
+
+#[event]
+pub struct OraclePriceRefreshedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub oracle_account: Pubkey,
+    pub refresher: Pubkey,
+    pub old_sqrt_price: u128,
+    pub new_sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+pub struct RefreshOraclePrice<'info> {
+    pub refresher: Signer<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_pool.load()?.oracle_address)]
+    pub oracle_account: Account<'info, OracleAccount>,
+
+    /// CHECK: Oracle Price Update Account, either a real PriceUpdateV2 or a MockPriceUpdate
+    pub price_update: UncheckedAccount<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+}
+
+/// Refreshes the price of an oracle pool from its price feed outside of a swap.
+///
+/// Anyone can call it, so that the price of an idle pool follows its feed instead of the first
+/// swap after a gap absorbing the whole correction. The price update must be within the maximum
+/// age of the oracle account.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The pool is not an oracle pool, or is locked.
+/// - The price update is invalid or stale.
+pub fn refresh_oracle_price_handler(ctx: Context<RefreshOraclePrice>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    if !ai_dex_pool.is_oracle_pool {
+        return Err(ErrorCode::NotOraclePool.into());
+    }
+    ai_dex_pool.check_not_locked()?;
+
+    let old_sqrt_price = ai_dex_pool.sqrt_price;
+    ctx.accounts.oracle_account.update_sqrt_price(
+        &mut ai_dex_pool,
+        &ctx.accounts.price_update,
+        ctx.accounts.token_mint_a.decimals,
+        ctx.accounts.token_mint_b.decimals,
+    )?;
+
+    emit!(OraclePriceRefreshedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        oracle_account: ctx.accounts.oracle_account.key(),
+        refresher: ctx.accounts.refresher.key(),
+        old_sqrt_price,
+        new_sqrt_price: ai_dex_pool.sqrt_price,
+        tick_current_index: ai_dex_pool.tick_current_index,
+        timestamp,
+    });
+
+    Ok(())
+}
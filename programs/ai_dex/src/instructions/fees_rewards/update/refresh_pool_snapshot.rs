@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexPool, PoolSnapshot};
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+pub struct RefreshPoolSnapshot<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub pool_snapshot: Account<'info, PoolSnapshot>,
+}
+
+/// Refreshes the snapshot of a pool from its current parameters.
+///
+/// Swaps and liquidity changes refresh the snapshots passed in their remaining accounts. Anyone
+/// can crank this instruction to refresh a snapshot after other changes, such as a new fee rate
+/// or a step of a liquidity bootstrapping fee schedule.
+pub fn refresh_pool_snapshot_handler(ctx: Context<RefreshPoolSnapshot>) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.pool_snapshot.refresh(
+        &*ctx.accounts.ai_dex_pool.load()?,
+        clock.slot,
+        to_timestamp_u64(clock.unix_timestamp)?,
+    );
+    Ok(())
+}
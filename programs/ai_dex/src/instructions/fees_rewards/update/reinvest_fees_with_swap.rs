@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::{
+    errors::ErrorCode,
+    math::is_rate_within_price_deviation,
+    orchestrator::swap_orchestrator::swap_with_fee_rate,
+    state::*,
+    util::{to_timestamp_u64, SwapTickSequence},
+};
+
+use super::update_reinvestments::*;
+
+#[event]
+pub struct ReinvestmentSwapEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub keeper: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub sqrt_price: u128,
+}
+
+#[derive(Accounts)]
+pub struct ReinvestFeesWithSwap<'info> {
+    pub reinvest_fees: ReinvestFees<'info>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_0)?.ai_dex_pool() == reinvest_fees.ai_dex_pool.key())]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_1)?.ai_dex_pool() == reinvest_fees.ai_dex_pool.key())]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_2)?.ai_dex_pool() == reinvest_fees.ai_dex_pool.key())]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// Snapshot of the pool giving the reference price the rate of the swap is bounded by
+    #[account(constraint = pool_snapshot.ai_dex_pool == reinvest_fees.ai_dex_pool.key())]
+    pub pool_snapshot: Account<'info, PoolSnapshot>,
+}
+
+/// Rebalances the fees owed to a position between token A and B with a fee-free swap through its
+/// pool, then reinvests them like `update_reinvestments`.
+///
+/// The swap is internal: the input is taken from the fees owed to the position and the output is
+/// added to them, so no tokens leave the vaults and no LP or protocol fee is charged. It is limited
+/// to the fees owed in the input token, and signed by a registered keeper.
+///
+/// The keeper chooses the amount and limits of the swap, so its rate is bounded by the maximum
+/// deviation set by the position owner from the price of the pool snapshot, which must have been
+/// refreshed in a recent earlier slot so the keeper cannot move it within the transaction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the reinvestment accounts and the tick arrays of the swap.
+/// * `amount` - The amount of fees owed in the input token to swap.
+/// * `other_amount_threshold` - The minimum amount of the output token to receive.
+/// * `sqrt_price_limit` - The square root price limit of the swap.
+/// * `a_to_b` - Whether token A is swapped for token B.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * Reinvestment is not enabled for the position.
+/// * The position owner has not set a maximum deviation for reinvestment swaps.
+/// * The pool snapshot was refreshed in the current slot or too long ago.
+/// * `amount` exceeds the fees owed to the position in the input token.
+/// * The pool is an oracle pool.
+/// * The output is below `other_amount_threshold`.
+/// * The rate of the swap deviates from the snapshot price by more than the maximum of the position.
+/// * The reinvestment fails.
+pub fn reinvest_fees_with_swap_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ReinvestFeesWithSwap<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+) -> Result<()> {
+    let reinvest_fees = &mut ctx.accounts.reinvest_fees;
+    let position = &mut reinvest_fees.position;
+    if !position.is_reinvestment_on {
+        return Err(ErrorCode::ReinvestmentNotEnabled.into());
+    }
+    if position.reinvestment_swap_max_deviation_bps == 0 {
+        return Err(ErrorCode::ReinvestmentSwapDisabled.into());
+    }
+    let pool_snapshot = &ctx.accounts.pool_snapshot;
+    if !pool_snapshot.is_reference_at(Clock::get()?.slot) {
+        return Err(ErrorCode::ReinvestmentSwapSnapshotStale.into());
+    }
+    let fee_owed_in = if a_to_b { position.fee_owed_a } else { position.fee_owed_b };
+    if amount > fee_owed_in {
+        return Err(ErrorCode::ReinvestmentSwapExceedsFeesOwed.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let mut ai_dex_pool = reinvest_fees.ai_dex_pool.load_mut()?;
+    ai_dex_pool.check_not_locked()?;
+    if ai_dex_pool.is_oracle_pool {
+        return Err(ErrorCode::OraclePoolNotSupported.into());
+    }
+
+    let mut swap_tick_sequence = SwapTickSequence::new(
+        load_tick_array_mut(&ctx.accounts.tick_array_0)?,
+        load_tick_array_mut(&ctx.accounts.tick_array_1).ok(),
+        load_tick_array_mut(&ctx.accounts.tick_array_2).ok(),
+    );
+    let swap_update = swap_with_fee_rate(
+        &ai_dex_pool,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        true,
+        a_to_b,
+        timestamp,
+        0,
+        0,
+    )?;
+    drop(swap_tick_sequence);
+
+    let (amount_in, amount_out) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    if amount_out < other_amount_threshold {
+        return Err(ErrorCode::AmountOutBelowMinimumError.into());
+    }
+    if !is_rate_within_price_deviation(
+        amount_in,
+        amount_out,
+        pool_snapshot.sqrt_price,
+        a_to_b,
+        position.reinvestment_swap_max_deviation_bps,
+    ) {
+        return Err(ErrorCode::ReinvestmentSwapPriceDeviationExceeded.into());
+    }
+
+    ai_dex_pool.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        a_to_b,
+        timestamp,
+    )?;
+    let sqrt_price = ai_dex_pool.sqrt_price;
+    drop(ai_dex_pool);
+
+    position.swap_fees_owed(amount_in, amount_out, a_to_b);
+
+    emit!(ReinvestmentSwapEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: reinvest_fees.ai_dex_pool.key(),
+        position: reinvest_fees.position.key(),
+        keeper: reinvest_fees.keeper.key(),
+        a_to_b,
+        amount_in,
+        amount_out,
+        sqrt_price,
+    });
+
+    reinvest_fees_handler(Context::new(
+        ctx.program_id,
+        &mut ctx.accounts.reinvest_fees,
+        ctx.remaining_accounts,
+        ctx.bumps.reinvest_fees,
+    ))
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::{AiDexConfig, AiDexPool};
+
+#[event]
+pub struct PoolStatsSyncedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub swap_count: u64,
+    pub reported_swap_count: u64,
+    pub total_swaps: u64,
+}
+
+#[derive(Accounts)]
+pub struct SyncPoolStats<'info> {
+    #[account(mut)]
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+}
+
+/// Adds the swaps of a pool not yet reported to the totals of its config.
+///
+/// Swaps only update the pool, so the config is not write-locked by every swap of the
+/// protocol. Anyone can crank this instruction to keep the config totals current.
+pub fn sync_pool_stats_handler(ctx: Context<SyncPoolStats>) -> Result<()> {
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    let reported_swap_count = ai_dex_pool.take_unreported_swap_count();
+    ctx.accounts.ai_dex_config.add_total_swaps(reported_swap_count);
+
+    emit!(PoolStatsSyncedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        swap_count: ai_dex_pool.swap_count,
+        reported_swap_count,
+        total_swaps: ctx.accounts.ai_dex_config.total_swaps,
+    });
+
+    Ok(())
+}
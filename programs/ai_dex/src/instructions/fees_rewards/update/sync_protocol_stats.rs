@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{AiDexConfig, PoolRevenueSnapshot, ProtocolStats};
+use crate::util::to_timestamp_u64;
+
+#[event]
+pub struct ProtocolStatsSyncedEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub protocol_stats: Pubkey,
+    pub pool_count: u64,
+    pub total_swaps: u64,
+    pub synced_pool_revenue_snapshots: u8,
+}
+
+#[derive(Accounts)]
+pub struct SyncProtocolStats<'info> {
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+}
+
+/// Adds the protocol fees collected from pools and not yet reported to the protocol stats of
+/// their config, and copies the pool and swap counts of the config.
+///
+/// The revenue snapshots of the pools are passed as writable remaining accounts. Collections
+/// only update the snapshot of their pool, so the protocol stats are not write-locked by every
+/// collection of the protocol. Anyone can crank this instruction to keep the stats current.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - A remaining account is not a writable revenue snapshot of a pool of the config.
+/// - The protocol stats would track more than `MAX_PROTOCOL_STATS_MINTS` mints.
+pub fn sync_protocol_stats_handler(ctx: Context<SyncProtocolStats>) -> Result<()> {
+    let protocol_stats = &mut ctx.accounts.protocol_stats;
+
+    for pool_revenue_snapshot_info in ctx.remaining_accounts {
+        if pool_revenue_snapshot_info.owner != &crate::ID || !pool_revenue_snapshot_info.is_writable {
+            return Err(ErrorCode::InvalidPoolRevenueSnapshot.into());
+        }
+        let mut pool_revenue_snapshot =
+            PoolRevenueSnapshot::try_deserialize(&mut &pool_revenue_snapshot_info.try_borrow_data()?[..])?;
+        if pool_revenue_snapshot.ai_dex_config != ctx.accounts.ai_dex_config.key() {
+            return Err(ErrorCode::InvalidPoolRevenueSnapshot.into());
+        }
+
+        let (protocol_fee_a, protocol_fee_b) = pool_revenue_snapshot.take_unreported_protocol_fees();
+        protocol_stats.add_protocol_fees(pool_revenue_snapshot.token_mint_a, protocol_fee_a)?;
+        protocol_stats.add_protocol_fees(pool_revenue_snapshot.token_mint_b, protocol_fee_b)?;
+        pool_revenue_snapshot.try_serialize(&mut &mut pool_revenue_snapshot_info.try_borrow_mut_data()?[..])?;
+    }
+
+    protocol_stats.update_counts(
+        ctx.accounts.ai_dex_config.total_pools,
+        ctx.accounts.ai_dex_config.total_swaps,
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    );
+
+    emit!(ProtocolStatsSyncedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
+        protocol_stats: protocol_stats.key(),
+        pool_count: protocol_stats.pool_count,
+        total_swaps: protocol_stats.total_swaps,
+        synced_pool_revenue_snapshots: ctx.remaining_accounts.len() as u8,
+    });
+
+    Ok(())
+}
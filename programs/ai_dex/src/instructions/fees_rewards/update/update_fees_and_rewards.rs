@@ -1,11 +1,19 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
 use crate::{
-    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths, state::*, util::to_timestamp_u64, UpdateTicksEvent,
+    orchestrator::{
+        liquidity_orchestrator::calculate_fee_and_reward_growths, position_orchestrator::next_position_grace_update,
+    },
+    state::*,
+    util::to_timestamp_u64,
+    UpdateTicksEvent,
 };
 
 #[event]
 pub struct FeesAndRewardsUpdatedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub position_key: Pubkey,
     pub position_update_info: PositionUpdate,
@@ -26,17 +34,38 @@ pub struct UpdateFeesAndRewards<'info> {
     #[account(mut, has_one = ai_dex_pool)]
     pub position: Account<'info, Position>,
 
-    #[account(has_one = ai_dex_pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(has_one = ai_dex_pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: checked by load_tick_array
+    #[account(constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// The grace checkpoint of the position, crediting it the out-of-range grace rewards it is owed
+    #[account(
+        mut,
+        seeds = [b"position_grace_checkpoint".as_ref(), position.key().as_ref()],
+        bump = position_grace_checkpoint.bump,
+    )]
+    pub position_grace_checkpoint: Option<Account<'info, PositionGraceCheckpoint>>,
+
+    /// The reward grace escrow of the pool, required with the grace checkpoint while any reward
+    /// has an out-of-range grace
+    #[account(
+        mut,
+        seeds = [b"reward_grace_escrow".as_ref(), ai_dex_pool.key().as_ref()],
+        bump = reward_grace_escrow.bump,
+    )]
+    pub reward_grace_escrow: Option<Account<'info, RewardGraceEscrow>>,
 }
 
 /// Updates the fees and rewards for a given position.
 ///
 /// This function handles the update of fees and rewards for a specific position in the AI DEX.
 /// It calculates the fee and reward growths based on the current state and updates the position
-/// and AI DEX accordingly.
+/// and AI DEX accordingly. With its grace checkpoint, the position is also credited from the
+/// reward grace escrow of the pool the grace share of the reward growth it missed while out of
+/// range since the checkpoint.
 ///
 /// # Arguments
 ///
@@ -48,11 +77,12 @@ pub struct UpdateFeesAndRewards<'info> {
 /// or an `Err` if an error occurs.
 pub fn update_fees_and_rewards_handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
     let ai_dex = &mut ctx.accounts.ai_dex_pool.load_mut()?;
+    ai_dex.check_not_locked()?;
     let position = &mut ctx.accounts.position;
     let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
     
     let (
-        position_update,
+        mut position_update,
         reward_infos,
         tick_lower_update,
         tick_upper_update
@@ -67,7 +97,41 @@ pub fn update_fees_and_rewards_handler(ctx: Context<UpdateFeesAndRewards>) -> Re
     ai_dex.update_rewards(reward_infos, timestamp);
     position.update(&position_update);
 
+    if let Some(position_grace_checkpoint) = &mut ctx.accounts.position_grace_checkpoint {
+        let grace_enabled_timestamps = match &mut ctx.accounts.reward_grace_escrow {
+            Some(reward_grace_escrow) => {
+                reward_grace_escrow.accrue(ai_dex, timestamp);
+                reward_grace_escrow.enabled_timestamps
+            }
+            None if { ai_dex.out_of_range_grace_bps }.iter().any(|grace_bps| *grace_bps != 0) => {
+                return Err(ErrorCode::MissingRewardGraceEscrow.into());
+            }
+            None => [0; NUM_REWARDS],
+        };
+
+        let grace_update = next_position_grace_update(
+            position_grace_checkpoint,
+            position,
+            ai_dex,
+            &grace_enabled_timestamps,
+            timestamp,
+        )?;
+        if let Some(reward_grace_escrow) = &mut ctx.accounts.reward_grace_escrow {
+            for (i, grace_reward) in grace_update.grace_rewards.iter().enumerate() {
+                // The grace rewards are capped by the grace share of the emissions escrowed so far.
+                let grace_reward = reward_grace_escrow.take(i, *grace_reward);
+                // Overflows allowed. Must collect rewards owed before overflow.
+                position_update.reward_infos[i].amount_owed =
+                    position_update.reward_infos[i].amount_owed.wrapping_add(grace_reward);
+            }
+            position.update(&position_update);
+        }
+        position_grace_checkpoint.update(&grace_update);
+    }
+    position.update_last_updated_timestamp(timestamp);
+
     emit!(UpdateTicksEvent {
+        event_version: EVENT_VERSION,
         tick_lower_index: position.tick_lower_index,
         tick_lower_update,
         tick_upper_index: position.tick_upper_index,
@@ -77,6 +141,7 @@ pub fn update_fees_and_rewards_handler(ctx: Context<UpdateFeesAndRewards>) -> Re
     });
 
     emit!(FeesAndRewardsUpdatedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         position_key: position.key(),
         position_update_info: position_update,
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token;
+use crate::constants::EVENT_VERSION;
 use crate::{
     errors::ErrorCode,
     math::convert_to_liquidity_delta,
@@ -18,14 +19,16 @@ use crate::{
 
 #[event]
 pub struct ReinvestFeesEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub position: Pubkey,
-    pub reinvestments_authority: Pubkey,
+    pub keeper: Pubkey,
+    pub keeper_authority: Pubkey,
     pub reinvested_amount_a: u64,
     pub reinvested_amount_b: u64,
     pub liquidity_delta_added: u128,
-    pub protocol_fee_added_a: u64,
-    pub protocol_fee_added_b: u64,
+    pub reinvestment_fee_added_a: u64,
+    pub reinvestment_fee_added_b: u64,
 }
 
 #[derive(Accounts)]
@@ -33,8 +36,15 @@ pub struct ReinvestFees<'info> {
     #[account(mut)]
     pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
     
-    #[account(address = reinvestments_account.reinvestments_authority)]
-    pub reinvestments_authority: Signer<'info>,
+    /// The keeper authority, paying for the reinvestment fee escrow on creation
+    #[account(mut, address = keeper.authority)]
+    pub keeper_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = keeper.ai_dex_config == ai_dex_pool.load()?.ai_dex_config @ ErrorCode::InvalidKeeper
+    )]
+    pub keeper: Account<'info, Keeper>,
 
     #[account(mut, has_one = ai_dex_pool)]
     pub position: Account<'info, Position>,
@@ -44,12 +54,33 @@ pub struct ReinvestFees<'info> {
     )]
     pub position_token_account: Box<Account<'info, token::TokenAccount>>,
 
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
 
+    #[account(
+        constraint = reinvestments_account.is_usable_by_config(&keeper.ai_dex_config)
+            @ ErrorCode::InvalidReinvestmentsAccount
+    )]
     pub reinvestments_account: Account<'info, AiDexReinvestments>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper_authority,
+        space = ReinvestmentFeeEscrow::LEN,
+        seeds = [
+            b"reinvestment_fee_escrow".as_ref(),
+            reinvestments_account.key().as_ref(),
+            ai_dex_pool.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub reinvestment_fee_escrow: Box<Account<'info, ReinvestmentFeeEscrow>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn reinvest_fees_handler(
@@ -59,8 +90,12 @@ pub fn reinvest_fees_handler(
         return Err(ErrorCode::ReinvestmentNotEnabled.into());
     }
 
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    ctx.accounts.keeper.record_action(timestamp)?;
+
     let position = &mut ctx.accounts.position;
     let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    ai_dex_pool.check_not_locked()?;
     
     // Calculate amounts based on current tick position
     let (amount_a, amount_b) = calculate_reinvestment_amounts(
@@ -76,16 +111,25 @@ pub fn reinvest_fees_handler(
         return Ok(());
     }
     
-    // Calculate protocol fees
-    let (protocol_fee_a, protocol_fee_b, reinvest_amount_a, reinvest_amount_b) = 
+    // Calculate reinvestment fees
+    let (reinvestment_fee_a, reinvestment_fee_b, reinvest_amount_a, reinvest_amount_b) = 
         calculate_reinvestment_fees(
             amount_a,
             amount_b,
             ctx.accounts.reinvestments_account.default_reinvestment_fee_rate,
         );
     
-    // Update protocol fees in pool
-    ai_dex_pool.add_protocol_fees_owed(protocol_fee_a, protocol_fee_b);
+    // Owe the reinvestment fees to the reinvestments account, apart from the protocol fees
+    let reinvestment_fee_escrow = &mut ctx.accounts.reinvestment_fee_escrow;
+    if !reinvestment_fee_escrow.is_initialized() {
+        reinvestment_fee_escrow.initialize(
+            ctx.accounts.reinvestments_account.key(),
+            ctx.accounts.ai_dex_pool.key(),
+            ctx.bumps.reinvestment_fee_escrow,
+        );
+    }
+    reinvestment_fee_escrow.accrue_fees(reinvestment_fee_a, reinvestment_fee_b);
+    ai_dex_pool.add_reinvestment_fees_owed(reinvestment_fee_a, reinvestment_fee_b);
 
     // Calculate liquidity based on the amounts
     let liquidity_delta = convert_to_liquidity_delta(
@@ -100,8 +144,6 @@ pub fn reinvest_fees_handler(
         true,
     )?;
 
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
-
     let update = calculate_modify_liquidity(
         &ai_dex_pool,
         position,
@@ -120,10 +162,15 @@ pub fn reinvest_fees_handler(
         timestamp,
     )?;
 
-    // Subtract the reinvested amounts from fees owed
-    position.subtract_fees_owed(reinvest_amount_a, reinvest_amount_b);
+    // Subtract the reinvested amounts and the reinvestment fees from fees owed
+    position.subtract_fees_owed(amount_a, amount_b);
+
+    // Lock the liquidity and fees of the position for the rest of the slot, so the owner cannot
+    // front-run the reinvestment with a modification in the same slot
+    position.record_reinvestment(Clock::get()?.slot);
 
     emit!(UpdateTicksEvent {
+        event_version: EVENT_VERSION,
         tick_lower_index: position.tick_lower_index,
         tick_lower_update: update.tick_lower_update,
         tick_upper_index: position.tick_upper_index,
@@ -133,14 +180,16 @@ pub fn reinvest_fees_handler(
     });
     
     emit!(ReinvestFeesEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         position: position.key(),
-        reinvestments_authority: ctx.accounts.reinvestments_authority.key(),
+        keeper: ctx.accounts.keeper.key(),
+        keeper_authority: ctx.accounts.keeper_authority.key(),
         reinvested_amount_a: reinvest_amount_a,
         reinvested_amount_b: reinvest_amount_b,
         liquidity_delta_added: liquidity_delta.abs() as u128,
-        protocol_fee_added_a: protocol_fee_a,
-        protocol_fee_added_b: protocol_fee_b,
+        reinvestment_fee_added_a: reinvestment_fee_a,
+        reinvestment_fee_added_b: reinvestment_fee_b,
     });
 
     Ok(())
@@ -3,17 +3,19 @@ use anchor_spl::token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::orchestrator::liquidity_orchestrator::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
 use crate::state::*;
-use crate::util::{calculate_transfer_fee_included_amount, parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
-use crate::util::{to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority};
+use crate::util::{check_blocklist, calculate_transfer_fee_included_amount, parse_remaining_accounts, refresh_pool_snapshots, AccountsType, RemainingAccountsInfo};
+use crate::util::{check_deadline, current_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority};
 
 #[event]
 pub struct IncreaseLiquidityEvent {
+    pub event_version: u8,
     pub liquidity_amount: u128,
     pub token_max_a: u64,
     pub token_max_b: u64,
@@ -34,11 +36,13 @@ pub struct IncreaseLiquidityEvent {
     pub new_liquidity_value: u128,
     pub update_position: PositionUpdate,
     pub referral_code: String,
+    pub lp_referral: Pubkey,
     pub timestamp: u64,
 }
 
 #[event]
 pub struct UpdateTicksEvent {
+    pub event_version: u8,
     pub tick_lower_index: i32,
     pub tick_lower_update: TickUpdate,
     pub tick_upper_index: i32,
@@ -94,10 +98,12 @@ pub struct ModifyLiquidity<'info> {
     #[account(mut)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -108,6 +114,11 @@ pub struct ModifyLiquidity<'info> {
     /// Oracle Price Update Account: Can be either a real PriceUpdateV2 or a MockPriceUpdate
     pub price_update: Option<AccountInfo<'info>>,
 
+    /// Optional LP referral attributed to the position on its first liquidity increase
+    #[account(
+        constraint = lp_referral.referrer_address != position_authority.key()
+    )]
+    pub lp_referral: Option<Account<'info, SwapReferral>>,
 }
 
 /// Handles the increase of liquidity in the protocol.
@@ -119,6 +130,12 @@ pub struct ModifyLiquidity<'info> {
 /// * `token_max_a` - The maximum amount of token A that can be transferred.
 /// * `token_max_b` - The maximum amount of token B that can be transferred.
 /// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the operation reverts.
+/// * `emit_tick_updates` - Optional flag, `false` skips the `UpdateTicksEvent` to keep the logs of
+///   the transaction short. Defaults to emitting it.
+///
+/// When an LP referral is provided and the position is not attributed yet, the position is
+/// attributed to it and the referrer earns a share of the protocol fees the position generates.
 ///
 /// # Returns
 ///
@@ -128,6 +145,7 @@ pub struct ModifyLiquidity<'info> {
 ///
 /// * `ErrorCode::ZeroLiquidityError` - If the liquidity amount is zero.
 /// * `ErrorCode::TokenLimitExceededError` - If the transfer amount exceeds the specified token limits.
+/// * `ErrorCode::DeadlineExceeded` - If the current timestamp is past `deadline_timestamp`.
 pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
@@ -135,11 +153,14 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
     token_max_b: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
     referral_code: Option<String>,
+    deadline_timestamp: Option<u64>,
+    emit_tick_updates: Option<bool>,
 ) -> Result<()> {
     verify_position_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
     )?;
+    ctx.accounts.position.check_not_reinvested_in_slot(Clock::get()?.slot)?;
 
     if liquidity_amount == 0 {
         return Err(ErrorCode::ZeroLiquidityError.into());
@@ -148,6 +169,8 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
     // Load AiDexPool as mut from the AccountLoader
     let mut ai_dex_pool_mut = ctx.accounts.ai_dex_pool.load_mut()?;  // Mutable borrow
 
+    ai_dex_pool_mut.lock()?;
+
     // Implementing the commented checks
     if ctx.accounts.token_mint_a.key() != ai_dex_pool_mut.token_mint_a {
         return Err(ErrorCode::InvalidInputTokenMint.into());
@@ -193,14 +216,32 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
         )?;
     }
 
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let timestamp = current_timestamp_u64(ctx.remaining_accounts)?;
+    check_deadline(timestamp, deadline_timestamp)?;
 
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB, AccountsType::PoolSnapshots, AccountsType::Blocklists],
+    )?;
+
+    check_blocklist(
+        &remaining_accounts.blocklists,
+        &ai_dex_pool_mut,
+        &[
+            ctx.accounts.position_authority.key(),
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
     )?;
 
+    if let Some(lp_referral) = &ctx.accounts.lp_referral {
+        ctx.accounts.position.attribute_lp_referral(
+            lp_referral.key(),
+            lp_referral.referral_reward_fee_rate,
+        );
+    }
+
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
 
     let update = calculate_modify_liquidity(
@@ -245,6 +286,11 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::TokenLimitExceededError.into());
     }
 
+    ctx.accounts.position.record_deposit(
+        transfer_fee_included_delta_a.amount,
+        transfer_fee_included_delta_b.amount,
+    );
+
     transfer_from_owner_to_vault(
         &ctx.accounts.position_authority,
         &ctx.accounts.token_mint_a,
@@ -267,16 +313,22 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
         transfer_fee_included_delta_b.amount,
     )?;
 
-    emit!(UpdateTicksEvent {
-        tick_lower_index: ctx.accounts.position.tick_lower_index,
-        tick_lower_update: update.tick_lower_update,
-        tick_upper_index: ctx.accounts.position.tick_upper_index,
-        tick_upper_update: update.tick_upper_update,
-        tick_array_lower: ctx.accounts.tick_array_lower.key(),
-        tick_array_upper: ctx.accounts.tick_array_upper.key(),
-    });
+    ai_dex_pool_mut.unlock();
+
+    if emit_tick_updates.unwrap_or(true) {
+        emit!(UpdateTicksEvent {
+            event_version: EVENT_VERSION,
+            tick_lower_index: ctx.accounts.position.tick_lower_index,
+            tick_lower_update: update.tick_lower_update,
+            tick_upper_index: ctx.accounts.position.tick_upper_index,
+            tick_upper_update: update.tick_upper_update,
+            tick_array_lower: ctx.accounts.tick_array_lower.key(),
+            tick_array_upper: ctx.accounts.tick_array_upper.key(),
+        });
+    }
 
     emit!(IncreaseLiquidityEvent {
+        event_version: EVENT_VERSION,
         liquidity_amount,
         token_max_a,
         token_max_b,
@@ -297,8 +349,12 @@ pub fn increase_liquidity_handler<'a, 'b, 'c, 'info>(
         new_liquidity_value: ai_dex_pool_mut.liquidity,
         update_position: update.position_update,
         referral_code: referral_code.unwrap_or_default(),
+        lp_referral: ctx.accounts.position.lp_referral,
         timestamp,
     });
 
+    drop(ai_dex_pool_mut);
+    refresh_pool_snapshots(&remaining_accounts.pool_snapshots, &[&ctx.accounts.ai_dex_pool])?;
+
     Ok(())
 }
@@ -2,15 +2,20 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
+use crate::constants::EVENT_VERSION;
 use crate::{
     errors::ErrorCode,
     math::FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD,
     state::*,
-    util::is_supported_token_mint,
+    util::{
+        canonicalize_token_mints, has_permanent_delegate, is_canonical_token_mint_order,
+        is_supported_token_mint,
+    },
 };
 
 #[event]
 pub struct PoolInitializedBasicEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub ai_dex_config: Pubkey,
     pub token_mint_a: Pubkey,
@@ -28,12 +33,27 @@ pub struct PoolInitializedBasicEvent {
     pub oracle_account: Pubkey,
     pub price_update: Pubkey,
     pub price_feed_id: String,
+    pub is_full_range_only: bool,
+    pub has_permanent_delegate_mint: bool,
+}
+
+/// Emitted before a pool initialization is rejected because its token mints are not in canonical
+/// order, so that clients can read the ordering to retry with from the transaction logs.
+#[event]
+pub struct ReversedTokenMintOrderEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub canonical_token_mint_a: Pubkey,
+    pub canonical_token_mint_b: Pubkey,
 }
 
 /// The `InitializePoolStep1` struct defines the accounts required for the first step of pool initialization.
 #[derive(Accounts)]
 #[instruction(tick_spacing: u16, is_oracle_pool: bool)]
 pub struct InitializePoolStep1<'info> {
+    #[account(mut)]
     pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
 
     pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
@@ -80,6 +100,18 @@ pub struct InitializePoolStep1<'info> {
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// Optional default price feed of the pair, used by Oracle Pools initialized without a price feed id
+    #[account(
+        seeds = [
+            b"oracle_price_feed_default".as_ref(),
+            ai_dex_config.key().as_ref(),
+            token_mint_a.key().as_ref(),
+            token_mint_b.key().as_ref(),
+        ],
+        bump = oracle_price_feed_default.bump,
+    )]
+    pub oracle_price_feed_default: Option<Box<Account<'info, OraclePriceFeedDefault>>>,
 }
 
 /// The `initialize_pool_step_1_handler` function performs the first step of pool initialization.
@@ -90,14 +122,37 @@ pub fn initialize_pool_step_1_handler(
     is_oracle_pool: bool,
     is_temporary_pool: bool,
     initial_sqrt_price: Option<u128>,  // Required for Classic and Temporary Pools
-    price_feed_id: Option<String>,     // Required for Oracle Pools
-    maximum_age: Option<u64>,          // Required for Oracle Pools
+    price_feed_id: Option<String>,     // Falls back to the default of the pair for Oracle Pools
+    maximum_age: Option<u64>,          // Falls back to the config default for Oracle Pools
+    is_full_range_only: Option<bool>,  // Restricts positions to the full range, defaults to false
 ) -> Result<()> {
+    let is_full_range_only = is_full_range_only.unwrap_or(false);
+    let token_mint_a = ctx.accounts.token_mint_a.key();
+    let token_mint_b = ctx.accounts.token_mint_b.key();
+
+    // Reject reversed pairs with the canonical ordering attached
+    if !is_canonical_token_mint_order(&token_mint_a, &token_mint_b) {
+        let (canonical_token_mint_a, canonical_token_mint_b, _) =
+            canonicalize_token_mints(token_mint_a, token_mint_b);
+        msg!(
+            "Token mints must be ordered as token_mint_a: {}, token_mint_b: {}",
+            canonical_token_mint_a,
+            canonical_token_mint_b
+        );
+        emit!(ReversedTokenMintOrderEvent {
+            event_version: EVENT_VERSION,
+            ai_dex_config: ctx.accounts.ai_dex_config.key(),
+            token_mint_a,
+            token_mint_b,
+            canonical_token_mint_a,
+            canonical_token_mint_b,
+        });
+        return Err(ErrorCode::InvalidTokenMintOrderError.into());
+    }
+
     let ai_dex_config = &ctx.accounts.ai_dex_config;
     let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_init()?;
 
-    let token_mint_a = ctx.accounts.token_mint_a.key();
-    let token_mint_b = ctx.accounts.token_mint_b.key();
     let default_fee_rate = ctx.accounts.fee_tier.default_fee_rate;
 
     // Validate token mints
@@ -109,6 +164,11 @@ pub fn initialize_pool_step_1_handler(
         return Err(ErrorCode::UnsupportedTokenMintError.into());
     }
 
+    // A permanent delegate can move tokens out of the vaults, the config may reject such mints
+    let has_permanent_delegate_mint = has_permanent_delegate(&ctx.accounts.token_mint_a)?
+        || has_permanent_delegate(&ctx.accounts.token_mint_b)?;
+    ai_dex_config.check_permanent_delegate_mint_allowed(has_permanent_delegate_mint)?;
+
     // For Oracle pools, ensure tick_spacing meets the threshold
     if is_oracle_pool && tick_spacing < FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD {
         return Err(ErrorCode::InvalidOraclePoolTickSpacing.into());
@@ -128,8 +188,18 @@ pub fn initialize_pool_step_1_handler(
             .as_mut()
             .ok_or(ErrorCode::MissingOracleAccount)?;
         
-        let price_feed_id = price_feed_id.clone().ok_or(ErrorCode::MissingOraclePriceFeedId)?;
-        let maximum_age = maximum_age.ok_or(ErrorCode::MissingMaxAge)?;
+        let price_feed_id = price_feed_id
+            .clone()
+            .or_else(|| {
+                ctx.accounts
+                    .oracle_price_feed_default
+                    .as_ref()
+                    .map(|oracle_price_feed_default| oracle_price_feed_default.price_feed_id.clone())
+            })
+            .ok_or(ErrorCode::MissingOraclePriceFeedId)?;
+        let maximum_age = maximum_age
+            .or_else(|| ai_dex_config.get_default_oracle_maximum_age())
+            .ok_or(ErrorCode::MissingMaxAge)?;
 
         oracle_account.initialize(
             price_feed_id.clone(),
@@ -166,11 +236,17 @@ pub fn initialize_pool_step_1_handler(
         token_mint_b,
         is_temporary_pool,
         is_oracle_pool,
+        is_full_range_only,
+        has_permanent_delegate_mint,
+        ctx.accounts.funder.key(),
     )?;
 
+    ctx.accounts.ai_dex_config.increment_total_pools();
+
     emit!(PoolInitializedBasicEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
-        ai_dex_config: ai_dex_config.key(),
+        ai_dex_config: ctx.accounts.ai_dex_config.key(),
         token_mint_a,
         token_mint_b,
         funder: ctx.accounts.funder.key(),
@@ -185,7 +261,9 @@ pub fn initialize_pool_step_1_handler(
         is_oracle_pool,
         oracle_account: ctx.accounts.oracle_account.as_ref().map(|a| a.key()).unwrap_or_default(),
         price_update: ctx.accounts.price_update.as_ref().map(|a| a.key()).unwrap_or_default(),
-        price_feed_id: price_feed_id.unwrap_or_default(),
+        price_feed_id: ctx.accounts.oracle_account.as_ref().map(|a| a.price_feed_id.clone()).unwrap_or_default(),
+        is_full_range_only,
+        has_permanent_delegate_mint,
     });
 
     Ok(())
@@ -2,6 +2,7 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint};
+use crate::constants::EVENT_VERSION;
 use crate::{
     errors::ErrorCode,
     state::*,
@@ -9,6 +10,7 @@ use crate::{
 
 #[event]
 pub struct PoolInitializedFinalEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub token_vault_a: Pubkey,
     pub token_vault_b: Pubkey,
@@ -21,6 +23,7 @@ pub struct PoolInitializedFinalEvent {
     pub start_timestamp_swap: u64,
     pub end_timestamp_swap: u64,
     pub tick_spacing: u16,
+    pub lbp_fee_schedule: Option<LbpFeeSchedule>,
 }
 
 /// The `InitializePoolStep2` struct defines the accounts required for the second step of pool initialization.
@@ -88,6 +91,7 @@ pub fn initialize_pool_step_2_handler(
     end_timestamp_lp: Option<u64>,
     start_timestamp_swap: Option<u64>,
     end_timestamp_swap: Option<u64>,
+    lbp_fee_schedule: Option<LbpFeeSchedule>,
 ) -> Result<()> {
     let ai_dex_pool = &mut ctx.accounts.ai_dex_pool.load_mut()?;
 
@@ -122,6 +126,11 @@ pub fn initialize_pool_step_2_handler(
                 ctx.accounts.token_vault_a.key(),
                 ctx.accounts.token_vault_b.key(),
             )?;
+
+            // Only temporary pools follow a liquidity bootstrapping fee schedule
+            if lbp_fee_schedule.is_some() {
+                return Err(ErrorCode::NotTemporaryPool.into());
+            }
         },
         (false, true) | (true, true) => {
             // Temporary Pool
@@ -141,11 +150,15 @@ pub fn initialize_pool_step_2_handler(
                 end_swap,
             )?;
 
+            if let Some(lbp_fee_schedule) = lbp_fee_schedule {
+                ai_dex_pool.initialize_lbp_fee_schedule(lbp_fee_schedule)?;
+            }
         },
     }
 
     // Emit PoolInitializedFinalEvent
     emit!(PoolInitializedFinalEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
         token_vault_a: ctx.accounts.token_vault_a.key(),
         token_vault_b: ctx.accounts.token_vault_b.key(),
@@ -158,6 +171,7 @@ pub fn initialize_pool_step_2_handler(
         start_timestamp_swap: start_timestamp_swap.unwrap_or(0),
         end_timestamp_swap: end_timestamp_swap.unwrap_or(0),
         tick_spacing,
+        lbp_fee_schedule,
     });
 
     Ok(())
@@ -0,0 +1,289 @@
+// initialize_pool_with_liquidity.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::instructions::{
+    open_position_with_liquidity_handler, OpenPositionWithLiquidity, OpenPositionWithLiquidityBumps,
+};
+use crate::state::*;
+use crate::util::{calculate_liquidity_from_amounts, RemainingAccountsInfo};
+
+use super::{initialize_pool_step_2_handler, InitializePoolStep2, InitializePoolStep2Bumps};
+
+/// The `InitializePoolWithLiquidity` struct defines the accounts required for the second step of
+/// pool initialization together with the initial position of the creator.
+#[derive(Accounts)]
+#[instruction(
+    tick_spacing: u16,
+    start_timestamp_lp: Option<u64>,
+    end_timestamp_lp: Option<u64>,
+    start_timestamp_swap: Option<u64>,
+    end_timestamp_swap: Option<u64>,
+    lbp_fee_schedule: Option<LbpFeeSchedule>,
+    position_seed: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+)]
+pub struct InitializePoolWithLiquidity<'info> {
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub token_mint_a: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(mut)]
+    pub token_mint_b: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    /// The creator of the pool, paying for the accounts and owning the initial position
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        token::token_program = token_program_a,
+        token::mint = token_mint_a,
+        token::authority = ai_dex_pool,
+        seeds = [
+            b"token_vault_a",
+            ai_dex_pool.key().as_ref(),
+            tick_spacing.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub token_vault_a: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init,
+        payer = funder,
+        token::token_program = token_program_b,
+        token::mint = token_mint_b,
+        token::authority = ai_dex_pool,
+        seeds = [
+            b"token_vault_b",
+            ai_dex_pool.key().as_ref(),
+            tick_spacing.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub token_vault_b: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        constraint = token_program_a.key() == token_mint_a.to_account_info().owner.clone()
+    )]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = token_program_b.key() == token_mint_b.to_account_info().owner.clone()
+    )]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        init,
+        payer = funder,
+        mint::authority = ai_dex_pool,
+        mint::decimals = 0,
+        seeds = [
+            b"position_mint",
+            ai_dex_pool.key().as_ref(),
+            funder.key().as_ref(),
+            position_seed.to_string().as_bytes(),
+            tick_lower_index.to_string().as_bytes(),
+            tick_upper_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub position_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = position_mint,
+        associated_token::authority = funder,
+    )]
+    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+
+    /// Page of the position index of the creator listing the initial position
+    #[account(mut, constraint = position_index.owner == funder.key())]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
+
+    #[account(mut)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = oracle_account.mint_a == token_mint_a.key() && oracle_account.mint_b == token_mint_b.key()
+    )]
+    pub oracle_account: Option<Box<Account<'info, OracleAccount>>>,
+
+    /// Oracle Price Update Account: Can be either a real PriceUpdateV2 or a MockPriceUpdate
+    pub price_update: Option<AccountInfo<'info>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub memo_program: Program<'info, Memo>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Performs the second step of pool initialization and deposits the initial liquidity of the
+/// creator in one instruction.
+///
+/// The pool becomes usable once its token vaults are initialized, so seeding the initial position
+/// in the same instruction leaves no window where others open positions or swap against a pool
+/// without liquidity. The tick arrays of the position are initialized after the first step.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts of the pool and of the initial position.
+/// * `tick_spacing` - The tick spacing of the pool.
+/// * `start_timestamp_lp` - The start of the liquidity period, required for temporary pools.
+/// * `end_timestamp_lp` - The end of the liquidity period, required for temporary pools.
+/// * `start_timestamp_swap` - The start of the swap period, required for temporary pools.
+/// * `end_timestamp_swap` - The end of the swap period, required for temporary pools.
+/// * `lbp_fee_schedule` - Optional liquidity bootstrapping fee schedule of temporary pools.
+/// * `position_seed` - The seed of the position mint, unique for the creator and range.
+/// * `tick_lower_index` - The lower tick index for the position.
+/// * `tick_upper_index` - The upper tick index for the position.
+/// * `is_reinvestment_on` - Whether the fees of the position are reinvested.
+/// * `token_amount_a` - The amount of token A to deposit at most, including transfer fees.
+/// * `token_amount_b` - The amount of token B to deposit at most, including transfer fees.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The pool cannot be finalized, see `initialize_pool_step_2_handler`.
+/// * The token amounts provide no liquidity in the range at the initial price of the pool.
+/// * The position cannot be opened or the deposit fails, see `open_position_with_liquidity_handler`.
+pub fn initialize_pool_with_liquidity_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, InitializePoolWithLiquidity<'info>>,
+    tick_spacing: u16,
+    start_timestamp_lp: Option<u64>,
+    end_timestamp_lp: Option<u64>,
+    start_timestamp_swap: Option<u64>,
+    end_timestamp_swap: Option<u64>,
+    lbp_fee_schedule: Option<LbpFeeSchedule>,
+    position_seed: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    is_reinvestment_on: bool,
+    token_amount_a: u64,
+    token_amount_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    let mut initialize_pool_step_2 = InitializePoolStep2 {
+        ai_dex_pool: accounts.ai_dex_pool.clone(),
+        token_mint_a: accounts.token_mint_a.clone(),
+        token_mint_b: accounts.token_mint_b.clone(),
+        funder: accounts.funder.clone(),
+        token_vault_a: accounts.token_vault_a.clone(),
+        token_vault_b: accounts.token_vault_b.clone(),
+        token_program_a: accounts.token_program_a.clone(),
+        token_program_b: accounts.token_program_b.clone(),
+        system_program: accounts.system_program.clone(),
+        rent: accounts.rent.clone(),
+    };
+    initialize_pool_step_2_handler(
+        Context::new(
+            ctx.program_id,
+            &mut initialize_pool_step_2,
+            ctx.remaining_accounts,
+            InitializePoolStep2Bumps::default(),
+        ),
+        tick_spacing,
+        start_timestamp_lp,
+        end_timestamp_lp,
+        start_timestamp_swap,
+        end_timestamp_swap,
+        lbp_fee_schedule,
+    )?;
+
+    let liquidity_amount = {
+        let ai_dex_pool = accounts.ai_dex_pool.load()?;
+        calculate_liquidity_from_amounts(
+            ai_dex_pool.tick_current_index,
+            ai_dex_pool.sqrt_price,
+            tick_lower_index,
+            tick_upper_index,
+            token_amount_a,
+            token_amount_b,
+        )?
+    };
+
+    let mut open_position_with_liquidity = OpenPositionWithLiquidity {
+        funder: accounts.funder.clone(),
+        owner: accounts.funder.clone(),
+        position: accounts.position.clone(),
+        position_mint: accounts.position_mint.clone(),
+        position_token_account: accounts.position_token_account.clone(),
+        ai_dex_pool: accounts.ai_dex_pool.clone(),
+        token_program: accounts.token_program.clone(),
+        system_program: accounts.system_program.clone(),
+        rent: accounts.rent.clone(),
+        associated_token_program: accounts.associated_token_program.clone(),
+        position_index: accounts.position_index.clone(),
+        token_program_a: accounts.token_program_a.clone(),
+        token_program_b: accounts.token_program_b.clone(),
+        memo_program: accounts.memo_program.clone(),
+        token_mint_a: accounts.token_mint_a.clone(),
+        token_mint_b: accounts.token_mint_b.clone(),
+        token_owner_account_a: accounts.token_owner_account_a.clone(),
+        token_owner_account_b: accounts.token_owner_account_b.clone(),
+        token_vault_a: accounts.token_vault_a.clone(),
+        token_vault_b: accounts.token_vault_b.clone(),
+        tick_array_lower: accounts.tick_array_lower.clone(),
+        tick_array_upper: accounts.tick_array_upper.clone(),
+        oracle_account: accounts.oracle_account.clone(),
+        price_update: accounts.price_update.clone(),
+        lp_referral: None,
+    };
+    open_position_with_liquidity_handler(
+        Context::new(
+            ctx.program_id,
+            &mut open_position_with_liquidity,
+            ctx.remaining_accounts,
+            OpenPositionWithLiquidityBumps::default(),
+        ),
+        position_seed,
+        tick_lower_index,
+        tick_upper_index,
+        is_reinvestment_on,
+        liquidity_amount,
+        token_amount_a,
+        token_amount_b,
+        remaining_accounts_info,
+        None,
+        None,
+        None,
+    )?;
+
+    // The deposit updated copies of the position, index and oracle, persisted when the instruction exits
+    accounts.position = open_position_with_liquidity.position;
+    accounts.position_index = open_position_with_liquidity.position_index;
+    accounts.oracle_account = open_position_with_liquidity.oracle_account;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 pub mod initialize_pool_step_1;
 pub mod initialize_pool_step_2;
+pub mod initialize_pool_with_liquidity;
 pub use initialize_pool_step_1::*;
-pub use initialize_pool_step_2::*;
\ No newline at end of file
+pub use initialize_pool_step_2::*;
+pub use initialize_pool_with_liquidity::*;
\ No newline at end of file
@@ -1,15 +1,28 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::EVENT_VERSION;
 use crate::state::*;
 
 #[event]
 pub struct TickArrayInitializedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub funder: Pubkey,
     pub tick_array: Pubkey,
     pub start_tick_index: i32,  // Assuming tick indices are 32-bit integers
 }
 
+#[event]
+pub struct TickArrayBountyClaimedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array_bounty: Pubkey,
+    pub tick_array: Pubkey,
+    pub funder: Pubkey,
+    pub start_tick_index: i32,
+    pub reward: u64,
+}
+
 #[derive(Accounts)]
 #[instruction(start_tick_index: i32)]
 pub struct InitializeTickArray<'info> {
@@ -28,6 +41,21 @@ pub struct InitializeTickArray<'info> {
     pub tick_array: AccountLoader<'info, TickArray>,
 
     pub system_program: Program<'info, System>,
+
+    /// Optional bounty of the pool, paying the funder for a tick array next to the current price
+    #[account(mut, has_one = ai_dex_pool)]
+    pub tick_array_bounty: Option<Account<'info, TickArrayBounty>>,
+
+    /// Optional record of the funder, letting the funder close the tick array once empty and far
+    /// from the current price to reclaim its rent
+    #[account(
+        init,
+        payer = funder,
+        space = TickArrayFunder::LEN,
+        seeds = [b"tick_array_funder", tick_array.key().as_ref()],
+        bump,
+    )]
+    pub tick_array_funder: Option<Account<'info, TickArrayFunder>>,
 }
 
 /// Initializes a tick array with the given starting tick index.
@@ -62,12 +90,31 @@ pub fn initialize_tick_array_handler(ctx: Context<InitializeTickArray>, start_ti
             // Emit a log event after successful initialization
             // Structured JSON logging
             emit!(TickArrayInitializedEvent {
+                event_version: EVENT_VERSION,
                 ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
                 funder: ctx.accounts.funder.key(),
                 tick_array: ctx.accounts.tick_array.key(),
                 start_tick_index,
             });
-            
+
+            if let Some(tick_array_funder) = &mut ctx.accounts.tick_array_funder {
+                tick_array_funder.initialize(
+                    ctx.accounts.tick_array.key(),
+                    ctx.accounts.funder.key(),
+                    ctx.bumps.tick_array_funder.unwrap(),
+                );
+            }
+
+            if let Some(tick_array_bounty) = &ctx.accounts.tick_array_bounty {
+                claim_tick_array_bounty(
+                    tick_array_bounty,
+                    &ctx.accounts.ai_dex_pool,
+                    ctx.accounts.tick_array.key(),
+                    start_tick_index,
+                    &ctx.accounts.funder,
+                )?;
+            }
+
             Ok(())
         },
         Err(e) => {
@@ -76,4 +123,54 @@ pub fn initialize_tick_array_handler(ctx: Context<InitializeTickArray>, start_ti
             Err(e)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Pays the bounty of the pool to the funder of a new tick array when the tick array contains or
+/// neighbours the tick array of the current price, and the bounty holds lamports above its rent.
+/// Does nothing otherwise, so clients can always pass the bounty.
+///
+/// # Arguments
+///
+/// * `tick_array_bounty` - The bounty of the pool of the tick array.
+/// * `ai_dex_pool` - The pool of the tick array.
+/// * `tick_array` - The address of the new tick array.
+/// * `start_tick_index` - The start tick index of the new tick array.
+/// * `funder` - The funder of the new tick array, receiving the reward.
+pub fn claim_tick_array_bounty<'info>(
+    tick_array_bounty: &Account<'info, TickArrayBounty>,
+    ai_dex_pool: &AccountLoader<'info, AiDexPool>,
+    tick_array: Pubkey,
+    start_tick_index: i32,
+    funder: &Signer<'info>,
+) -> Result<()> {
+    let (tick_current_index, tick_spacing) = {
+        let ai_dex_pool = ai_dex_pool.load()?;
+        (ai_dex_pool.tick_current_index, ai_dex_pool.tick_spacing)
+    };
+    if !TickArrayBounty::is_eligible_tick_array(start_tick_index, tick_current_index, tick_spacing) {
+        return Ok(());
+    }
+
+    let tick_array_bounty_info = tick_array_bounty.to_account_info();
+    let reward = tick_array_bounty.claimable_reward(
+        tick_array_bounty_info.lamports(),
+        Rent::get()?.minimum_balance(TickArrayBounty::LEN),
+    );
+    if reward == 0 {
+        return Ok(());
+    }
+    **tick_array_bounty_info.try_borrow_mut_lamports()? -= reward;
+    **funder.to_account_info().try_borrow_mut_lamports()? += reward;
+
+    emit!(TickArrayBountyClaimedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ai_dex_pool.key(),
+        tick_array_bounty: tick_array_bounty.key(),
+        tick_array,
+        funder: funder.key(),
+        start_tick_index,
+        reward,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+use super::{claim_tick_array_bounty, TickArrayInitializedEvent};
+
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeTickArrayIfNeeded<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: created by the handler when empty, checked by load_tick_array otherwise
+    #[account(
+        mut,
+        seeds = [b"tick_array", ai_dex_pool.key().as_ref(), start_tick_index.to_string().as_bytes()],
+        bump,
+    )]
+    pub tick_array: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional bounty of the pool, paying the funder when the handler creates the tick array
+    #[account(mut, has_one = ai_dex_pool)]
+    pub tick_array_bounty: Option<Account<'info, TickArrayBounty>>,
+}
+
+/// Initializes a tick array with the given starting tick index, unless it already exists.
+///
+/// Clients racing to initialize the same tick array all succeed: the first one creates it, and
+/// the others find a tick array of the pool, either `TickArray` or `TickArrayV2`, at its address.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for initialization.
+/// * `start_tick_index` - The starting index for the tick array.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The account at the tick array address exists but is not a tick array of this program.
+/// - The start tick index is not valid for the pool's tick spacing.
+pub fn initialize_tick_array_if_needed_handler(
+    ctx: Context<InitializeTickArrayIfNeeded>,
+    start_tick_index: i32,
+) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+
+    // The address is derived from the pool and the start tick index, so any tick array found
+    // there is the requested one.
+    if tick_array_info.owner != &system_program::ID || !tick_array_info.data_is_empty() {
+        load_tick_array(&tick_array_info)?;
+        return Ok(());
+    }
+
+    let ai_dex_pool_key = ctx.accounts.ai_dex_pool.key();
+    let start_tick_index_seed = start_tick_index.to_string();
+    let bump = [ctx.bumps.tick_array];
+    let signer_seeds: &[&[u8]] = &[
+        b"tick_array",
+        ai_dex_pool_key.as_ref(),
+        start_tick_index_seed.as_bytes(),
+        &bump,
+    ];
+    create_tick_array_account(
+        &ctx.accounts.funder,
+        &tick_array_info,
+        &ctx.accounts.system_program,
+        signer_seeds,
+    )?;
+
+    {
+        let mut data = tick_array_info.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(&TickArray::DISCRIMINATOR);
+        bytemuck::from_bytes_mut::<TickArray>(&mut data[8..TickArray::LEN])
+            .initialize(&ctx.accounts.ai_dex_pool, start_tick_index)?;
+    }
+
+    emit!(TickArrayInitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ai_dex_pool_key,
+        funder: ctx.accounts.funder.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index,
+    });
+
+    if let Some(tick_array_bounty) = &ctx.accounts.tick_array_bounty {
+        claim_tick_array_bounty(
+            tick_array_bounty,
+            &ctx.accounts.ai_dex_pool,
+            ctx.accounts.tick_array.key(),
+            start_tick_index,
+            &ctx.accounts.funder,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the tick array account, topping up the rent of an address that already holds lamports.
+fn create_tick_array_account<'info>(
+    funder: &Signer<'info>,
+    tick_array_info: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(TickArray::LEN);
+
+    if tick_array_info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: funder.to_account_info(),
+                    to: tick_array_info.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent_exempt,
+            TickArray::LEN as u64,
+            &crate::ID,
+        );
+    }
+
+    let rent_paid = rent_exempt.saturating_sub(tick_array_info.lamports());
+    if rent_paid > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: funder.to_account_info(),
+                    to: tick_array_info.clone(),
+                },
+            ),
+            rent_paid,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate {
+                account_to_allocate: tick_array_info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        TickArray::LEN as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign {
+                account_to_assign: tick_array_info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
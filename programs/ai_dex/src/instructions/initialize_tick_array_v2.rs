@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+#[event]
+pub struct TickArrayV2InitializedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub funder: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+}
+
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeTickArrayV2<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"tick_array", ai_dex_pool.key().as_ref(), start_tick_index.to_string().as_bytes()],
+        bump,
+        space = TickArrayV2::LEN
+    )]
+    pub tick_array: AccountLoader<'info, TickArrayV2>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional record of the funder, letting the funder close the tick array once empty and far
+    /// from the current price to reclaim its rent
+    #[account(
+        init,
+        payer = funder,
+        space = TickArrayFunder::LEN,
+        seeds = [b"tick_array_funder", tick_array.key().as_ref()],
+        bump,
+    )]
+    pub tick_array_funder: Option<Account<'info, TickArrayFunder>>,
+}
+
+/// Initializes a compact tick array with the given starting tick index.
+///
+/// The compact layout shares its address with `TickArray`, so a pool can only have one
+/// of the two layouts for a given start tick index.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The start tick index is not valid for the pool's tick spacing.
+pub fn initialize_tick_array_v2_handler(ctx: Context<InitializeTickArrayV2>, start_tick_index: i32) -> Result<()> {
+    let tick_spacing = ctx.accounts.ai_dex_pool.load()?.tick_spacing;
+
+    let mut tick_array = ctx.accounts.tick_array.load_init()?;
+    tick_array.initialize(ctx.accounts.ai_dex_pool.key(), tick_spacing, start_tick_index)?;
+
+    if let Some(tick_array_funder) = &mut ctx.accounts.tick_array_funder {
+        tick_array_funder.initialize(
+            ctx.accounts.tick_array.key(),
+            ctx.accounts.funder.key(),
+            ctx.bumps.tick_array_funder.unwrap(),
+        );
+    }
+
+    emit!(TickArrayV2InitializedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        funder: ctx.accounts.funder.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::memo::Memo;
+use anchor_spl::token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{transfer_memo, EVENT_VERSION};
+use crate::{
+    errors::ErrorCode,
+    math::convert_to_liquidity_delta,
+    orchestrator::liquidity_orchestrator::{
+        calculate_liquidity_token_deltas,
+        calculate_modify_liquidity,
+        sync_modify_liquidity_values,
+    },
+    state::*,
+    util::{parse_remaining_accounts, to_timestamp_u64, transfer_from_vault_to_owner, AccountsType, RemainingAccountsInfo},
+    UpdateTicksEvent,
+};
+
+#[event]
+pub struct DustPositionLiquidatedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub position_owner: Pubkey,
+    pub liquidator: Pubkey,
+    pub liquidity_removed: u128,
+    pub principal_returned_a: u64,
+    pub principal_returned_b: u64,
+    pub liquidator_rent: u64,
+    pub owner_rent: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+pub struct LiquidateDustPosition<'info> {
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+
+    pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    #[account(mut, has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut, address = position_token_account.owner)]
+    pub position_owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = ai_dex_pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = ai_dex_pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = ai_dex_pool.load()?.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token accounts of the position owner receiving the principal of the remaining liquidity
+    #[account(mut, token::mint = token_mint_a, token::authority = position_owner)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, token::mint = token_mint_b, token::authority = position_owner)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+
+    /// Page of a position index listing the liquidated position
+    #[account(mut)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
+}
+
+/// Closes an abandoned dust position.
+///
+/// This instruction is permissionless. A position can be liquidated when its liquidity is below
+/// the threshold of the config, it has nothing owed, and it has been inactive for at least the
+/// inactivity period of the config since its last recorded update. Any remaining dust liquidity is
+/// removed from the ticks and the tokens backing it are transferred to the token accounts of the
+/// position owner. The rent of the position account is split evenly between the liquidator and
+/// the position owner.
+///
+/// Burning the position NFT requires the signature of its owner, so the NFT is invalidated
+/// instead: the position account is derived from the NFT mint, which cannot be initialized again,
+/// so no position can be opened for the NFT once its account is closed. If a page of a position
+/// index listing the position is provided, the position is removed from it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the liquidation.
+/// * `remaining_accounts_info` - Optional information about remaining accounts, such as the
+///   transfer hook accounts of the pool tokens.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is successfully liquidated, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::PositionNotLiquidatable` - If the position is not a dust position.
+/// * `ErrorCode::PositionNotIndexed` - If the provided position index page does not list the position.
+pub fn liquidate_dust_position_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, LiquidateDustPosition<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let config = &ctx.accounts.ai_dex_config;
+    let position = &mut ctx.accounts.position;
+
+    if !position.is_dust_position(
+        config.dust_position_liquidity_threshold,
+        config.dust_position_inactivity_period,
+        timestamp,
+    ) {
+        return Err(ErrorCode::PositionNotLiquidatable.into());
+    }
+
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let liquidity_removed = position.liquidity;
+    let mut principal_returned_a = 0;
+    let mut principal_returned_b = 0;
+
+    if liquidity_removed > 0 {
+        let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+        ai_dex_pool.check_not_locked()?;
+
+        let liquidity_delta = convert_to_liquidity_delta(liquidity_removed, false)?;
+
+        let update = calculate_modify_liquidity(
+            &ai_dex_pool,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+
+        // Fees or rewards accrued since the last checkpoint still belong to the owner
+        let position_update = &update.position_update;
+        if position_update.fee_owed_a != 0
+            || position_update.fee_owed_b != 0
+            || position_update.reward_infos.iter().any(|reward| reward.amount_owed != 0)
+        {
+            return Err(ErrorCode::PositionNotLiquidatable.into());
+        }
+
+        sync_modify_liquidity_values(
+            &mut ai_dex_pool,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            update,
+            timestamp,
+        )?;
+
+        (principal_returned_a, principal_returned_b) = calculate_liquidity_token_deltas(
+            ai_dex_pool.tick_current_index,
+            ai_dex_pool.sqrt_price,
+            position,
+            liquidity_delta,
+        )?;
+
+        emit!(UpdateTicksEvent {
+            event_version: EVENT_VERSION,
+            tick_lower_index: position.tick_lower_index,
+            tick_lower_update: update.tick_lower_update,
+            tick_upper_index: position.tick_upper_index,
+            tick_upper_update: update.tick_upper_update,
+            tick_array_lower: ctx.accounts.tick_array_lower.key(),
+            tick_array_upper: ctx.accounts.tick_array_upper.key(),
+        });
+    }
+
+    // The principal of the remaining liquidity still belongs to the owner
+    if principal_returned_a > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            principal_returned_a,
+            transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+        )?;
+    }
+    if principal_returned_b > 0 {
+        transfer_from_vault_to_owner(
+            &ctx.accounts.ai_dex_pool,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            principal_returned_b,
+            transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+        )?;
+    }
+
+    if let Some(position_index) = &mut ctx.accounts.position_index {
+        position_index.remove_position(&position.key())?;
+    }
+
+    // Close the position account, splitting the rent between the liquidator and the owner
+    let position_info = position.to_account_info();
+    let rent = position_info.lamports();
+    let liquidator_rent = rent / 2;
+    let owner_rent = rent - liquidator_rent;
+
+    **position_info.try_borrow_mut_lamports()? = 0;
+    **ctx.accounts.liquidator.to_account_info().try_borrow_mut_lamports()? += liquidator_rent;
+    **ctx.accounts.position_owner.to_account_info().try_borrow_mut_lamports()? += owner_rent;
+
+    position_info.assign(&system_program::ID);
+    position_info.realloc(0, false)?;
+
+    emit!(DustPositionLiquidatedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: position.key(),
+        position_mint: position.position_mint,
+        position_owner: ctx.accounts.position_owner.key(),
+        liquidator: ctx.accounts.liquidator.key(),
+        liquidity_removed,
+        principal_returned_a,
+        principal_returned_b,
+        liquidator_rent,
+        owner_rent,
+        timestamp,
+    });
+
+    Ok(())
+}
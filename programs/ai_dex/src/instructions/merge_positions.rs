@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::constants::EVENT_VERSION;
+use crate::{
+    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths,
+    state::*,
+    util::{burn_and_close_user_position_token, to_timestamp_u64, verify_position_authority},
+};
+
+#[event]
+pub struct PositionsMergedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub source_position: Pubkey,
+    pub liquidity: u128,
+    pub receiver: Pubkey,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+pub struct MergePositions<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = ai_dex_pool,
+        close = receiver,
+        constraint = source_position.key() != position.key(),
+    )]
+    pub source_position: Box<Account<'info, Position>>,
+
+    #[account(mut, address = source_position.position_mint)]
+    pub source_position_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = source_position_token_account.mint == source_position.position_mint,
+        constraint = source_position_token_account.amount == 1
+    )]
+    pub source_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    /// Page of a position index listing the source position
+    #[account(mut)]
+    pub source_position_index: Option<Box<Account<'info, PositionIndex>>>,
+}
+
+/// Merges a source position into a position with the same range and closes the source position.
+///
+/// Fees and rewards of both positions are settled first, then the liquidity and the fees and
+/// rewards owed of the source position are moved over. Ticks and pool liquidity are unchanged.
+/// If a page of a position index listing the source position is provided, the source position
+/// is removed from it.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The signer is not the owner or delegate of both positions.
+/// - The pool is locked.
+/// - The positions do not share the same range.
+/// - The provided position index page does not list the source position.
+pub fn merge_positions_handler(ctx: Context<MergePositions>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+    verify_position_authority(
+        &ctx.accounts.source_position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+    let slot = Clock::get()?.slot;
+    ctx.accounts.position.check_not_reinvested_in_slot(slot)?;
+    ctx.accounts.source_position.check_not_reinvested_in_slot(slot)?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let position = &mut ctx.accounts.position;
+    let source_position = &mut ctx.accounts.source_position;
+
+    {
+        let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+        ai_dex.check_not_locked()?;
+
+        // Settle fees and rewards so both positions share the current checkpoints
+        for settled_position in [&mut **position, &mut **source_position] {
+            // Positions without liquidity accrue nothing and cannot be settled
+            if settled_position.liquidity == 0 {
+                continue;
+            }
+            let (position_update, reward_infos, _, _) = calculate_fee_and_reward_growths(
+                &ai_dex,
+                settled_position,
+                &ctx.accounts.tick_array_lower,
+                &ctx.accounts.tick_array_upper,
+                timestamp,
+            )?;
+            ai_dex.update_rewards(reward_infos, timestamp);
+            settled_position.update(&position_update);
+        }
+    }
+
+    let liquidity = source_position.liquidity;
+    position.merge_from(source_position)?;
+    position.update_last_updated_timestamp(timestamp);
+
+    if let Some(source_position_index) = &mut ctx.accounts.source_position_index {
+        source_position_index.remove_position(&source_position.key())?;
+    }
+
+    burn_and_close_user_position_token(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.receiver,
+        &ctx.accounts.source_position_mint,
+        &ctx.accounts.source_position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit!(PositionsMergedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: position.key(),
+        source_position: source_position.key(),
+        liquidity,
+        receiver: ctx.accounts.receiver.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[event]
+pub struct AccountMigratedEvent {
+    pub event_version: u8,
+    pub account: Pubkey,
+    pub payer: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_len: u64,
+    pub new_len: u64,
+    pub rent_paid: u64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: the discriminator is checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades an `AiDexConfig`, `AiDexPool`, `Position`, `SuperAdmin` or `AiDexReinvestments`
+/// account to its current layout in place.
+///
+/// New fields are appended to the end of these accounts, or take zeroed space from the reserved
+/// field of pools and positions, so a legacy account is upgraded by growing it to the current
+/// length with zeroed data if needed and stamping the current version. Accounts
+/// created before versioning was introduced are treated as version 0. Anyone can migrate an
+/// account; the payer covers the additional rent.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The account is not an `AiDexConfig`, `AiDexPool`, `Position`, `SuperAdmin` or
+///   `AiDexReinvestments`.
+/// - The account is already at the current version.
+pub fn migrate_account_handler(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account_info = ctx.accounts.account.to_account_info();
+
+    let (len, version_offset, current_version) = versioned_account_layout(&account_info)?;
+
+    let old_len = account_info.data_len();
+    let old_version = if old_len > version_offset {
+        account_info.try_borrow_data()?[version_offset]
+    } else {
+        0
+    };
+    if old_version >= current_version {
+        return Err(ErrorCode::AccountAlreadyMigrated.into());
+    }
+
+    let mut rent_paid = 0;
+    if old_len < len {
+        rent_paid = grow_account(&account_info, &ctx.accounts.payer, &ctx.accounts.system_program, len)?;
+    }
+
+    account_info.try_borrow_mut_data()?[version_offset] = current_version;
+
+    emit!(AccountMigratedEvent {
+        event_version: EVENT_VERSION,
+        account: ctx.accounts.account.key(),
+        payer: ctx.accounts.payer.key(),
+        old_version,
+        new_version: current_version,
+        old_len: old_len as u64,
+        new_len: account_info.data_len() as u64,
+        rent_paid,
+    });
+
+    Ok(())
+}
+
+/// Returns the current length, version offset and current version of a versioned account.
+///
+/// # Errors
+///
+/// This function will return an error if the account is not an `AiDexConfig`, `AiDexPool`,
+/// `Position`, `SuperAdmin` or `AiDexReinvestments`.
+pub(crate) fn versioned_account_layout(account_info: &AccountInfo) -> Result<(usize, usize, u8)> {
+    let data = account_info.try_borrow_data()?;
+    if data.len() < 8 {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+    let discriminator = &data[..8];
+    if discriminator == AiDexConfig::DISCRIMINATOR {
+        Ok((AiDexConfig::LEN, AiDexConfig::VERSION_OFFSET, AiDexConfig::CURRENT_VERSION))
+    } else if discriminator == AiDexPool::DISCRIMINATOR {
+        Ok((AiDexPool::LEN, AiDexPool::VERSION_OFFSET, AiDexPool::CURRENT_VERSION))
+    } else if discriminator == Position::DISCRIMINATOR {
+        Ok((Position::LEN, Position::VERSION_OFFSET, Position::CURRENT_VERSION))
+    } else if discriminator == SuperAdmin::DISCRIMINATOR {
+        Ok((SuperAdmin::LEN, SuperAdmin::VERSION_OFFSET, SuperAdmin::CURRENT_VERSION))
+    } else if discriminator == AiDexReinvestments::DISCRIMINATOR {
+        Ok((
+            AiDexReinvestments::LEN,
+            AiDexReinvestments::VERSION_OFFSET,
+            AiDexReinvestments::CURRENT_VERSION,
+        ))
+    } else {
+        Err(ErrorCode::UnsupportedAccountMigration.into())
+    }
+}
+
+/// Grows an account to the given length with zeroed data, the payer covering the additional rent.
+///
+/// # Returns
+///
+/// The rent paid by the payer.
+pub(crate) fn grow_account<'info>(
+    account_info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    len: usize,
+) -> Result<u64> {
+    let rent_exempt = Rent::get()?.minimum_balance(len);
+    let rent_paid = rent_exempt.saturating_sub(account_info.lamports());
+    if rent_paid > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            rent_paid,
+        )?;
+    }
+
+    account_info.realloc(len, true)?;
+    Ok(rent_paid)
+}
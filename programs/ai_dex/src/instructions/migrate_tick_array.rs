@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+#[event]
+pub struct TickArrayMigratedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+    pub receiver: Pubkey,
+    pub rent_refunded: u64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTickArray<'info> {
+    pub ai_dex_config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(has_one = ai_dex_config)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = ai_dex_config.config_authority)]
+    pub config_authority: Signer<'info>,
+
+    /// CHECK: checked by load_tick_array, must be a TickArray
+    #[account(mut, constraint = load_tick_array(&tick_array)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array: UncheckedAccount<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Migrates a `TickArray` to the compact `TickArrayV2` layout in place, refunding the freed rent.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The tick array already uses the compact layout.
+/// - The tick array has more initialized ticks than a `TickArrayV2` can hold.
+pub fn migrate_tick_array_handler(ctx: Context<MigrateTickArray>) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+
+    let tick_array_v2 = {
+        let data = tick_array_info.try_borrow_data()?;
+        if data[..8] != TickArray::DISCRIMINATOR {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        TickArrayV2::try_from_tick_array(bytemuck::from_bytes::<TickArray>(&data[8..TickArray::LEN]))?
+    };
+
+    {
+        let mut data = tick_array_info.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(&TickArrayV2::DISCRIMINATOR);
+        data[8..TickArrayV2::LEN].copy_from_slice(bytemuck::bytes_of(&tick_array_v2));
+    }
+    tick_array_info.realloc(TickArrayV2::LEN, false)?;
+
+    // Refund the rent that is no longer needed by the smaller account
+    let rent_exempt = Rent::get()?.minimum_balance(TickArrayV2::LEN);
+    let rent_refunded = tick_array_info.lamports().saturating_sub(rent_exempt);
+    **tick_array_info.try_borrow_mut_lamports()? -= rent_refunded;
+    **ctx.accounts.receiver.to_account_info().try_borrow_mut_lamports()? += rent_refunded;
+
+    emit!(TickArrayMigratedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        start_tick_index: tick_array_v2.start_tick_index,
+        receiver: ctx.accounts.receiver.key(),
+        rent_refunded,
+    });
+
+    Ok(())
+}
@@ -1,20 +1,68 @@
+pub mod cancel_swap_commitment;
+pub mod close_oracle_account;
 pub mod close_position;
+pub mod close_swap_referral;
+pub mod close_tick_array;
+pub mod commit_swap;
 pub mod decrease_liquidity;
+pub mod decrease_liquidity_by_bps;
+pub mod donate;
+pub mod expand_tick_array;
+pub mod extend_account;
 pub mod increase_liquidity;
 pub mod initialize_tick_array;
+pub mod initialize_tick_array_if_needed;
+pub mod initialize_tick_array_v2;
+pub mod liquidate_dust_position;
+pub mod merge_positions;
+pub mod migrate_account;
+pub mod migrate_tick_array;
 pub mod open_position;
 pub mod open_position_with_metadata;
+pub mod open_position_with_liquidity;
+pub mod quote_swap;
+pub mod reindex_position;
+pub mod reveal_swap;
+pub mod settle_expired_position;
+pub mod split_position;
 pub mod swap;
+pub mod swap_v2;
+pub mod transfer_swap_referral;
 pub mod two_hop_swap;
+pub mod two_hop_swap_compact;
 
+pub use cancel_swap_commitment::*;
+pub use close_oracle_account::*;
 pub use close_position::*;
+pub use close_swap_referral::*;
+pub use close_tick_array::*;
+pub use commit_swap::*;
 pub use decrease_liquidity::*;
+pub use decrease_liquidity_by_bps::*;
+pub use donate::*;
+pub use expand_tick_array::*;
+pub use extend_account::*;
 pub use increase_liquidity::*;
 pub use initialize_tick_array::*;
+pub use initialize_tick_array_if_needed::*;
+pub use initialize_tick_array_v2::*;
+pub use liquidate_dust_position::*;
+pub use merge_positions::*;
+pub use migrate_account::*;
+pub use migrate_tick_array::*;
 pub use open_position::*;
 pub use open_position_with_metadata::*;
+pub use open_position_with_liquidity::*;
+pub use quote_swap::*;
+pub use reindex_position::*;
+pub use reveal_swap::*;
+pub use settle_expired_position::*;
+pub use split_position::*;
 pub use swap::*;
+pub use swap_v2::*;
+pub use transfer_swap_referral::*;
 pub use two_hop_swap::*;
+pub use two_hop_swap_compact::*;
 
 pub mod trade_batch;
 pub use trade_batch::*;
@@ -2,10 +2,12 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
-use crate::{state::*, util::mint_position_token_and_remove_authority};
+use crate::constants::EVENT_VERSION;
+use crate::{state::*, util::{mint_position_token_and_remove_authority, to_timestamp_u64}};
 
 #[event]
 pub struct PositionOpenedEvent {
+    pub event_version: u8,
     pub funder: Pubkey,
     pub ai_dex_pool: Pubkey,
     pub position: Pubkey,
@@ -69,10 +71,16 @@ pub struct OpenPosition<'info> {
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Page of the position index of the owner listing the new position
+    #[account(mut, has_one = owner)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
 }
 
 /// Opens a position in the AI DEX.
 ///
+/// If a page of the position index of the owner is provided, the position is added to it.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing the accounts required for opening the position.
@@ -89,6 +97,7 @@ pub struct OpenPosition<'info> {
 /// This function will return an error if:
 /// - The position cannot be opened.
 /// - The position token cannot be minted or the authority cannot be removed.
+/// - The provided position index page is full.
 pub fn open_position_handler(
     ctx: Context<OpenPosition>,
     position_seed: u64,  // Add a unique position_seed
@@ -108,6 +117,11 @@ pub fn open_position_handler(
         tick_upper_index,
         is_reinvestment_on,
     )?;
+    position.update_last_updated_timestamp(to_timestamp_u64(Clock::get()?.unix_timestamp)?);
+
+    if let Some(position_index) = &mut ctx.accounts.position_index {
+        position_index.add_position(position.key())?;
+    }
 
     // Mint the position token and remove the authority
     mint_position_token_and_remove_authority(
@@ -118,6 +132,7 @@ pub fn open_position_handler(
     )?;
 
     emit!(PositionOpenedEvent {
+        event_version: EVENT_VERSION,
         funder: ctx.accounts.funder.key(),
         ai_dex_pool: ai_dex.key(),
         position: position.key(),
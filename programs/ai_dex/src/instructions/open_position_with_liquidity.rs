@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+use crate::util::{mint_position_token_and_remove_authority, to_timestamp_u64, RemainingAccountsInfo};
+
+use super::{increase_liquidity_handler, ModifyLiquidity, ModifyLiquidityBumps, PositionOpenedEvent};
+
+#[derive(Accounts)]
+#[instruction(position_seed: u64, tick_lower_index: i32, tick_upper_index: i32)]
+pub struct OpenPositionWithLiquidity<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// The owner of the position, depositing its initial liquidity
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        init,
+        payer = funder,
+        mint::authority = ai_dex_pool,
+        mint::decimals = 0,
+        seeds = [
+            b"position_mint",
+            ai_dex_pool.key().as_ref(),
+            owner.key().as_ref(),
+            position_seed.to_string().as_bytes(),
+            tick_lower_index.to_string().as_bytes(),
+            tick_upper_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Page of the position index of the owner listing the new position
+    #[account(mut, has_one = owner)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
+
+    #[account(
+        constraint = token_program_a.key() == token_mint_a.to_account_info().owner.clone()
+    )]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = token_program_b.key() == token_mint_b.to_account_info().owner.clone()
+    )]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(mut)]
+    pub token_mint_a: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(mut)]
+    pub token_mint_b: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(mut)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(mut)]
+    pub token_vault_a: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut)]
+    pub token_vault_b: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = oracle_account.mint_a == token_mint_a.key() && oracle_account.mint_b == token_mint_b.key()
+    )]
+    pub oracle_account: Option<Box<Account<'info, OracleAccount>>>,
+
+    /// Oracle Price Update Account: Can be either a real PriceUpdateV2 or a MockPriceUpdate
+    pub price_update: Option<AccountInfo<'info>>,
+
+    /// Optional LP referral attributed to the position
+    #[account(
+        constraint = lp_referral.referrer_address != owner.key()
+    )]
+    pub lp_referral: Option<Account<'info, SwapReferral>>,
+}
+
+/// Opens a position and deposits its initial liquidity in one instruction.
+///
+/// Combines `open_position` and `increase_liquidity`, so that the deposit cannot be front-run
+/// between the two, and so that liquidity withdrawn from a position on another CLMM earlier in
+/// the same transaction lands here atomically when migrating. Besides the token amount bounds,
+/// the deposit can be bounded by the price of the pool, which sets the ratio of the tokens.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts of the position and of the deposit.
+/// * `position_seed` - The seed of the position mint, unique for the owner, pool and range.
+/// * `tick_lower_index` - The lower tick index for the position.
+/// * `tick_upper_index` - The upper tick index for the position.
+/// * `is_reinvestment_on` - Whether the fees of the position are reinvested.
+/// * `liquidity_amount` - The liquidity to deposit.
+/// * `token_max_a` - The maximum amount of token A to deposit, including transfer fees.
+/// * `token_max_b` - The maximum amount of token B to deposit, including transfer fees.
+/// * `remaining_accounts_info` - Optional information about remaining accounts.
+/// * `deadline_timestamp` - Optional unix timestamp after which the operation reverts.
+/// * `min_sqrt_price` - Optional minimum square root price of the pool at the deposit.
+/// * `max_sqrt_price` - Optional maximum square root price of the pool at the deposit.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The position cannot be opened, see `open_position_handler`.
+/// * The deposit fails, see `increase_liquidity_handler`.
+/// * The price of the pool is below `min_sqrt_price` or above `max_sqrt_price`.
+pub fn open_position_with_liquidity_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithLiquidity<'info>>,
+    position_seed: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    is_reinvestment_on: bool,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<u64>,
+    min_sqrt_price: Option<u128>,
+    max_sqrt_price: Option<u128>,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    accounts.position.open_position(
+        &accounts.ai_dex_pool,
+        accounts.position_mint.key(),
+        tick_lower_index,
+        tick_upper_index,
+        is_reinvestment_on,
+    )?;
+    accounts.position.update_last_updated_timestamp(to_timestamp_u64(Clock::get()?.unix_timestamp)?);
+
+    if let Some(position_index) = &mut accounts.position_index {
+        position_index.add_position(accounts.position.key())?;
+    }
+
+    mint_position_token_and_remove_authority(
+        &accounts.ai_dex_pool,
+        &accounts.position_mint,
+        &accounts.position_token_account,
+        &accounts.token_program,
+    )?;
+    // The position token account must hold the minted token to authorize the deposit
+    accounts.position_token_account.reload()?;
+
+    emit!(PositionOpenedEvent {
+        event_version: EVENT_VERSION,
+        funder: accounts.funder.key(),
+        ai_dex_pool: accounts.ai_dex_pool.key(),
+        position: accounts.position.key(),
+        position_mint: accounts.position_mint.key(),
+        position_token_account: accounts.position_token_account.key(),
+        owner: accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+        token_program: accounts.token_program.key(),
+        position_seed,
+        is_reinvestment_on,
+    });
+
+    let mut modify_liquidity = ModifyLiquidity {
+        ai_dex_pool: accounts.ai_dex_pool.clone(),
+        token_program_a: accounts.token_program_a.clone(),
+        token_program_b: accounts.token_program_b.clone(),
+        memo_program: accounts.memo_program.clone(),
+        position_authority: accounts.owner.clone(),
+        position: (*accounts.position).clone(),
+        position_token_account: accounts.position_token_account.clone(),
+        token_mint_a: (*accounts.token_mint_a).clone(),
+        token_mint_b: (*accounts.token_mint_b).clone(),
+        token_owner_account_a: accounts.token_owner_account_a.clone(),
+        token_owner_account_b: accounts.token_owner_account_b.clone(),
+        token_vault_a: accounts.token_vault_a.clone(),
+        token_vault_b: accounts.token_vault_b.clone(),
+        tick_array_lower: accounts.tick_array_lower.clone(),
+        tick_array_upper: accounts.tick_array_upper.clone(),
+        oracle_account: accounts.oracle_account.as_deref().cloned(),
+        price_update: accounts.price_update.clone(),
+        lp_referral: accounts.lp_referral.clone(),
+    };
+    increase_liquidity_handler(
+        Context::new(
+            ctx.program_id,
+            &mut modify_liquidity,
+            ctx.remaining_accounts,
+            ModifyLiquidityBumps::default(),
+        ),
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+        remaining_accounts_info,
+        None,
+        deadline_timestamp,
+        None,
+    )?;
+    // Oracle pools refresh their price during the deposit, which does not move it otherwise
+    ctx
+        .accounts
+        .ai_dex_pool
+        .load()?
+        .check_sqrt_price_within(min_sqrt_price, max_sqrt_price)?;
+
+    // The deposit updated copies of the position and oracle, persisted when the instruction exits
+    *ctx.accounts.position = modify_liquidity.position;
+    ctx.accounts.oracle_account = modify_liquidity.oracle_account.map(Box::new);
+
+    Ok(())
+}
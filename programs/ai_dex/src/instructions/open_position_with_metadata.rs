@@ -3,12 +3,18 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::metadata::Metadata;
 
-use crate::{state::*, util::mint_position_token_with_metadata_and_remove_authority};
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::{
+    state::*,
+    util::{mint_position_token_with_metadata_and_remove_authority, to_timestamp_u64, verify_position_collection},
+};
 
 use crate::constants::nft::ai_dex_nft_update_auth::ID as AD_NFT_UPDATE_AUTH;
 
 #[event]
 pub struct PositionWithMetadataOpenedEvent {
+    pub event_version: u8,
     pub funder: Pubkey,
     pub ai_dex_pool: Pubkey,
     pub position: Pubkey,
@@ -82,10 +88,36 @@ pub struct OpenPositionWithMetadata<'info> {
     /// CHECK: checked via account constraints
     #[account(address = AD_NFT_UPDATE_AUTH)]
     pub metadata_update_auth: UncheckedAccount<'info>,
+
+    /// CHECK: the config-level metadata of the pool, used when initialized, the default metadata
+    /// is used otherwise
+    #[account(
+        seeds = [b"position_metadata".as_ref(), ai_dex_pool.load()?.ai_dex_config.as_ref()],
+        bump,
+    )]
+    pub position_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: checked in verify_position_collection, required when the position metadata has a collection
+    pub collection_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked via the Metadata CPI call
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in verify_position_collection
+    pub collection_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked via the Metadata CPI call
+    #[account(mut)]
+    pub collection_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked via the Metadata CPI call
+    pub collection_master_edition: Option<UncheckedAccount<'info>>,
+
+    /// Page of the position index of the owner listing the new position
+    #[account(mut, has_one = owner)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
 }
 
 /// Opens a position with metadata in the AI DEX.
 ///
+/// If a page of the position index of the owner is provided, the position is added to it.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing the accounts required for opening the position.
@@ -100,8 +132,11 @@ pub struct OpenPositionWithMetadata<'info> {
 /// # Errors
 ///
 /// This function will return an error if:
+/// - The position metadata of the config cannot be deserialized.
+/// - The position metadata has a collection and the collection accounts are missing or invalid.
 /// - The position cannot be opened.
 /// - The position token with metadata cannot be minted or the authority cannot be removed.
+/// - The provided position index page is full.
 pub fn open_position_with_metadata_handler(
     ctx: Context<OpenPositionWithMetadata>,
     position_seed: u64,
@@ -113,6 +148,14 @@ pub fn open_position_with_metadata_handler(
     let position_mint = &ctx.accounts.position_mint;
     let position = &mut ctx.accounts.position;
 
+    // The metadata of the config applies whenever it has been initialized
+    let position_metadata_info = ctx.accounts.position_metadata.to_account_info();
+    let position_metadata = if position_metadata_info.owner == &crate::ID {
+        Some(PositionMetadata::try_deserialize(&mut &position_metadata_info.try_borrow_data()?[..])?)
+    } else {
+        None
+    };
+
     // Open the position
     position.open_position(
         ai_dex,
@@ -121,6 +164,11 @@ pub fn open_position_with_metadata_handler(
         tick_upper_index,
         is_reinvestment_on,
     )?;
+    position.update_last_updated_timestamp(to_timestamp_u64(Clock::get()?.unix_timestamp)?);
+
+    if let Some(position_index) = &mut ctx.accounts.position_index {
+        position_index.add_position(position.key())?;
+    }
 
     // Mint the position token with metadata and remove the authority
     mint_position_token_with_metadata_and_remove_authority(
@@ -129,6 +177,7 @@ pub fn open_position_with_metadata_handler(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_metadata_account,
         &ctx.accounts.metadata_update_auth,
+        position_metadata.as_ref(),
         &ctx.accounts.funder,
         &ctx.accounts.metadata_program,
         &ctx.accounts.token_program,
@@ -136,7 +185,42 @@ pub fn open_position_with_metadata_handler(
         &ctx.accounts.rent,
     )?;
 
+    // Verify the position NFT into the config-level collection, if any
+    if let Some(position_metadata) = &position_metadata {
+        if let Some(collection_mint) = position_metadata.get_collection_mint() {
+            let (
+                Some(collection_authority),
+                Some(collection_authority_record),
+                Some(collection_mint_account),
+                Some(collection_metadata),
+                Some(collection_master_edition),
+            ) = (
+                &ctx.accounts.collection_authority,
+                &ctx.accounts.collection_authority_record,
+                &ctx.accounts.collection_mint,
+                &ctx.accounts.collection_metadata,
+                &ctx.accounts.collection_master_edition,
+            ) else {
+                return Err(ErrorCode::PositionCollectionAccountsMissing.into());
+            };
+
+            verify_position_collection(
+                &position_metadata.ai_dex_config,
+                &collection_mint,
+                &ctx.accounts.position_metadata_account,
+                collection_authority,
+                collection_authority_record,
+                collection_mint_account,
+                collection_metadata,
+                collection_master_edition,
+                &ctx.accounts.funder,
+                &ctx.accounts.metadata_program,
+            )?;
+        }
+    }
+
     emit!(PositionWithMetadataOpenedEvent {
+        event_version: EVENT_VERSION,
         funder: ctx.accounts.funder.key(),
         ai_dex_pool: ai_dex.key(),
         position: position.key(),
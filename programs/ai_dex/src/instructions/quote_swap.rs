@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    orchestrator::swap_orchestrator::quote_swap,
+    state::{load_tick_array, AiDexPool, TickArrayType},
+    util::{current_timestamp_u64, resolve_sqrt_price_limit},
+};
+
+/// Quote of a swap, set as the return data of the instruction so that routers can read it from
+/// a simulation or with `get_return_data` after the CPI.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuoteReturnData {
+    /// Amount of the input token the swap takes, including the swap fee.
+    pub amount_in: u64,
+    /// Amount of the output token the swap gives.
+    pub amount_out: u64,
+    /// Swap fee paid in the input token.
+    pub fee_amount: u64,
+    /// Square root price of the pool after the swap.
+    pub next_sqrt_price: u128,
+    /// Part of the amount the quote could not fill within the provided tick arrays, or before
+    /// the first initialized tick for a constant product quote.
+    pub amount_remaining: u64,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    /// CHECK: The tick array (v1 or v2) containing the current tick, which must be associated with the AI DEX
+    #[account(constraint = load_tick_array(&tick_array_0)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    /// CHECK: The next tick array (v1 or v2) in the direction of the swap, which must be associated with the AI DEX
+    #[account(constraint = load_tick_array(tick_array_1)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_1: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The tick array (v1 or v2) after `tick_array_1`, which must be associated with the AI DEX
+    #[account(constraint = load_tick_array(tick_array_2)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_2: Option<UncheckedAccount<'info>>,
+}
+
+pub fn quote_swap_handler(
+    ctx: Context<QuoteSwap>,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    tick_index_limit: Option<i32>,
+    constant_product: Option<bool>,
+) -> Result<SwapQuoteReturnData> {
+    let ai_dex_data = ctx.accounts.ai_dex_pool.load()?;
+    let timestamp = current_timestamp_u64(ctx.remaining_accounts)?;
+    let sqrt_price_limit = resolve_sqrt_price_limit(sqrt_price_limit, tick_index_limit, a_to_b)?;
+
+    let mut tick_arrays = vec![load_tick_array(&ctx.accounts.tick_array_0)?];
+    for tick_array in [&ctx.accounts.tick_array_1, &ctx.accounts.tick_array_2].into_iter().flatten() {
+        tick_arrays.push(load_tick_array(tick_array)?);
+    }
+    let tick_arrays: Vec<&dyn TickArrayType> = tick_arrays.iter().map(|tick_array| &**tick_array).collect();
+
+    let quote = quote_swap(
+        &ai_dex_data,
+        &tick_arrays,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        constant_product.unwrap_or(false),
+    )?;
+
+    Ok(SwapQuoteReturnData {
+        amount_in: quote.amount_in,
+        amount_out: quote.amount_out,
+        fee_amount: quote.fee_amount,
+        next_sqrt_price: quote.next_sqrt_price,
+        amount_remaining: quote.amount_remaining,
+    })
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+#[event]
+pub struct PositionReindexedEvent {
+    pub event_version: u8,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub previous_position_index: Pubkey,
+    pub position_index: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct ReindexPosition<'info> {
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Page of the position index of a previous owner still listing the position
+    #[account(mut, constraint = previous_position_index.owner != position_token_account.owner)]
+    pub previous_position_index: Option<Box<Account<'info, PositionIndex>>>,
+
+    /// Page of the position index of the current owner to list the position
+    #[account(mut, constraint = position_index.owner == position_token_account.owner)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
+}
+
+/// Moves a position whose token changed hands to the position index of its current owner.
+///
+/// Position tokens are transferred outside of the program, so the pages of the position index
+/// are not updated on transfers. The position is removed from the provided page of a previous
+/// owner, and added to the provided page of the owner of the position token. Anyone can reindex
+/// a position, since both only bring the index in line with the holder of the position token.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the accounts required for reindexing the position.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The page of the previous owner does not list the position.
+/// - The page of the current owner is full or already lists the position.
+pub fn reindex_position_handler(ctx: Context<ReindexPosition>) -> Result<()> {
+    let position = ctx.accounts.position.key();
+
+    if let Some(previous_position_index) = &mut ctx.accounts.previous_position_index {
+        previous_position_index.remove_position(&position)?;
+    }
+    if let Some(position_index) = &mut ctx.accounts.position_index {
+        position_index.add_position(position)?;
+    }
+
+    emit!(PositionReindexedEvent {
+        event_version: EVENT_VERSION,
+        position,
+        owner: ctx.accounts.position_token_account.owner,
+        previous_position_index: ctx.accounts.previous_position_index.as_ref().map(|a| a.key()).unwrap_or_default(),
+        position_index: ctx.accounts.position_index.as_ref().map(|a| a.key()).unwrap_or_default(),
+    });
+
+    Ok(())
+}
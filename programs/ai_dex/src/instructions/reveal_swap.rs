@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::SwapCommitment;
+use crate::util::RemainingAccountsInfo;
+
+use super::swap::*;
+
+#[event]
+pub struct SwapRevealedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub payer: Pubkey,
+    pub swap_commitment: Pubkey,
+    pub commit_slot: u64,
+    pub reveal_slot: u64,
+}
+
+#[derive(Accounts)]
+pub struct RevealSwap<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = payer,
+        close = payer,
+        constraint = swap_commitment.ai_dex_pool == swap.ai_dex_pool.key() @ ErrorCode::SwapCommitmentMismatch,
+    )]
+    pub swap_commitment: Account<'info, SwapCommitment>,
+}
+
+/// Executes a swap previously committed with `commit_swap`.
+///
+/// The swap parameters and salt are hashed together with the pool and the payer and must match
+/// the stored commitment. The reveal must land after the commit slot and within
+/// `MAX_SWAP_COMMITMENT_AGE_SLOTS`. The commitment account is closed and its rent refunded.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the swap accounts and the commitment.
+/// * `amount` - The amount to be swapped.
+/// * `other_amount_threshold` - The threshold for the other amount in the swap.
+/// * `sqrt_price_limit` - The square root price limit for the swap.
+/// * `amount_specified_is_input` - Whether the specified amount is the input amount.
+/// * `a_to_b` - The direction of the swap.
+/// * `salt` - The secret salt used when computing the commitment hash.
+/// * `remaining_accounts_info` - Optional remaining accounts information for the swap.
+///
+/// # Returns
+///
+/// This function returns the `SwapReturnData` of the swap if it is successful, or an error if it fails.
+pub fn reveal_swap_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RevealSwap<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    salt: [u8; 32],
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<SwapReturnData> {
+    let reveal_slot = Clock::get()?.slot;
+    let commitment_hash = SwapCommitment::compute_hash(
+        &ctx.accounts.swap.ai_dex_pool.key(),
+        &ctx.accounts.payer.key(),
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        &salt,
+    );
+    ctx.accounts.swap_commitment.verify_reveal(&commitment_hash, reveal_slot)?;
+
+    let swap_ctx = Context::new(
+        ctx.program_id,
+        &mut ctx.accounts.swap,
+        ctx.remaining_accounts,
+        SwapBumps::default(),
+    );
+    let swap_return_data = swap_handler(
+        swap_ctx,
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        remaining_accounts_info,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+
+    emit!(SwapRevealedEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.swap.ai_dex_pool.key(),
+        payer: ctx.accounts.payer.key(),
+        swap_commitment: ctx.accounts.swap_commitment.key(),
+        commit_slot: ctx.accounts.swap_commitment.commit_slot,
+        reveal_slot,
+    });
+
+    Ok(swap_return_data)
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::{
+    errors::ErrorCode,
+    math::convert_to_liquidity_delta,
+    orchestrator::liquidity_orchestrator::{
+        calculate_liquidity_token_deltas,
+        calculate_modify_liquidity,
+        sync_expired_modify_liquidity_values,
+    },
+    state::*,
+    util::to_timestamp_u64,
+    UpdateTicksEvent,
+};
+
+#[event]
+pub struct ExpiredPositionSettledEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub settler: Pubkey,
+    pub liquidity_removed: u128,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+pub struct SettleExpiredPosition<'info> {
+    pub settler: Signer<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+}
+
+/// Removes all the liquidity of a position of an expired temporary pool.
+///
+/// This instruction is permissionless, so launch pools can be wound down once their liquidity
+/// provision window has closed without waiting for every LP. The tokens backing the liquidity
+/// stay in the vaults and are added to the principal owed of the position, so its owner receives them
+/// with `collect_fees` along with the fees and rewards it earned. The pool price is not refreshed
+/// from the oracle of an oracle pool, as swaps have stopped with the pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for the settlement.
+///
+/// # Returns
+///
+/// * `Result<()>` - Returns an Ok result if the position is successfully settled, otherwise returns an error.
+///
+/// # Errors
+///
+/// * `ErrorCode::PoolNotExpired` - If the pool is not a temporary pool past `end_timestamp_lp`.
+/// * `ErrorCode::ZeroLiquidityError` - If the position has no liquidity left to settle.
+pub fn settle_expired_position_handler(ctx: Context<SettleExpiredPosition>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let position = &mut ctx.accounts.position;
+
+    let mut ai_dex_pool = ctx.accounts.ai_dex_pool.load_mut()?;
+    ai_dex_pool.check_not_locked()?;
+    if !ai_dex_pool.is_expired(timestamp) {
+        return Err(ErrorCode::PoolNotExpired.into());
+    }
+
+    let liquidity_removed = position.liquidity;
+    if liquidity_removed == 0 {
+        return Err(ErrorCode::ZeroLiquidityError.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_removed, false)?;
+
+    let update = calculate_modify_liquidity(
+        &ai_dex_pool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_expired_modify_liquidity_values(
+        &mut ai_dex_pool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    // No early-exit fee is charged past the liquidity provision window
+    let (amount_a, amount_b) = calculate_liquidity_token_deltas(
+        ai_dex_pool.tick_current_index,
+        ai_dex_pool.sqrt_price,
+        position,
+        liquidity_delta,
+    )?;
+    position.add_principal_owed(amount_a, amount_b);
+    position.record_withdrawal(amount_a, amount_b);
+
+    emit!(UpdateTicksEvent {
+        event_version: EVENT_VERSION,
+        tick_lower_index: position.tick_lower_index,
+        tick_lower_update: update.tick_lower_update,
+        tick_upper_index: position.tick_upper_index,
+        tick_upper_update: update.tick_upper_update,
+        tick_array_lower: ctx.accounts.tick_array_lower.key(),
+        tick_array_upper: ctx.accounts.tick_array_upper.key(),
+    });
+
+    emit!(ExpiredPositionSettledEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: position.key(),
+        settler: ctx.accounts.settler.key(),
+        liquidity_removed,
+        amount_a,
+        amount_b,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::constants::EVENT_VERSION;
+use crate::{
+    orchestrator::liquidity_orchestrator::calculate_fee_and_reward_growths,
+    state::*,
+    util::{mint_position_token_and_remove_authority, to_timestamp_u64, verify_position_authority},
+};
+
+#[event]
+pub struct PositionSplitEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub position: Pubkey,
+    pub new_position: Pubkey,
+    pub new_position_mint: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity: u128,
+    pub position_seed: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(position_seed: u64)]
+pub struct SplitPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be the owner of the new position can be arbitrary
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut, has_one = ai_dex_pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [b"position".as_ref(), new_position_mint.key().as_ref()],
+        bump,
+    )]
+    pub new_position: Box<Account<'info, Position>>,
+
+    #[account(
+        init,
+        payer = funder,
+        mint::authority = ai_dex_pool,
+        mint::decimals = 0,
+        seeds = [
+            b"position_mint",
+            ai_dex_pool.key().as_ref(),
+            owner.key().as_ref(),
+            position_seed.to_string().as_bytes(),
+            position.tick_lower_index.to_string().as_bytes(),
+            position.tick_upper_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub new_position_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = funder,
+        associated_token::mint = new_position_mint,
+        associated_token::authority = owner,
+    )]
+    pub new_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(constraint = load_tick_array(&tick_array_lower)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(constraint = load_tick_array(&tick_array_upper)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Page of the position index of the owner listing the new position
+    #[account(mut, has_one = owner)]
+    pub position_index: Option<Box<Account<'info, PositionIndex>>>,
+}
+
+/// Moves liquidity of a position into a new position with the same range.
+///
+/// Fees and rewards of the position are settled first, so the new position starts from the
+/// current checkpoints while the fees and rewards owed stay with the original position.
+/// Ticks and pool liquidity are unchanged. If a page of the position index of the owner of the
+/// new position is provided, the new position is added to it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for splitting the position.
+/// * `position_seed` - The seed of the new position mint.
+/// * `liquidity` - The liquidity moved into the new position.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The signer is not the owner or delegate of the position.
+/// - The pool is locked.
+/// - The liquidity is zero or exceeds the liquidity of the position.
+/// - The provided position index page is full.
+pub fn split_position_handler(
+    ctx: Context<SplitPosition>,
+    position_seed: u64,
+    liquidity: u128,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+    ctx.accounts.position.check_not_reinvested_in_slot(Clock::get()?.slot)?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let position = &mut ctx.accounts.position;
+    let new_position = &mut ctx.accounts.new_position;
+
+    {
+        let mut ai_dex = ctx.accounts.ai_dex_pool.load_mut()?;
+        ai_dex.check_not_locked()?;
+
+        // Settle fees and rewards so both positions continue from the current checkpoints
+        let (position_update, reward_infos, _, _) = calculate_fee_and_reward_growths(
+            &ai_dex,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+        ai_dex.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+        position.update_last_updated_timestamp(timestamp);
+    }
+
+    new_position.open_position(
+        &ctx.accounts.ai_dex_pool,
+        ctx.accounts.new_position_mint.key(),
+        position.tick_lower_index,
+        position.tick_upper_index,
+        position.is_reinvestment_on,
+    )?;
+    new_position.update_last_updated_timestamp(timestamp);
+    position.split_into(new_position, liquidity)?;
+
+    if let Some(position_index) = &mut ctx.accounts.position_index {
+        position_index.add_position(new_position.key())?;
+    }
+
+    mint_position_token_and_remove_authority(
+        &ctx.accounts.ai_dex_pool,
+        &ctx.accounts.new_position_mint,
+        &ctx.accounts.new_position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    emit!(PositionSplitEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool: ctx.accounts.ai_dex_pool.key(),
+        position: position.key(),
+        new_position: new_position.key(),
+        new_position_mint: ctx.accounts.new_position_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        liquidity,
+        position_seed,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -1,22 +1,25 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
+use crate::constants::EVENT_VERSION;
 use crate::state::{AiDexConfig, OracleAccount, SwapReferral};
 
 use crate::util::{
-    calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount, parse_remaining_accounts, transfer_referral_fee, AccountsType, RemainingAccountsInfo
+    check_blocklist, escrow_referral_fee, invoke_swap_callback, parse_remaining_accounts, record_swap_fee_analytics, record_swap_volume, refresh_pool_snapshots, transfer_from_vault_to_owner, transfer_referral_fee, AccountsType, MintTransferFee, RemainingAccountsInfo, SwapCallbackData
 };
 
 use crate::{
     errors::ErrorCode,
+    math::{get_effective_price_x64, get_integrator_fee_amount, get_integrator_fee_amount_for_output},
     orchestrator::swap_orchestrator::*,
-    state::{TickArray, AiDexPool},
-    util::{to_timestamp_u64, update_and_swap_ai_dex, SwapTickSequence},
+    state::{load_tick_array, load_tick_array_mut, AiDexPool},
+    util::{check_deadline, resolve_sqrt_price_limit, current_timestamp_u64, update_and_swap_ai_dex, SwapTickSequence},
     constants::transfer_memo,
 };
 
 #[event]
 pub struct SwapExecutedEvent {
+    pub event_version: u8,
     pub token_authority: Pubkey,
     pub ai_dex_pool: Pubkey,
     pub amount: u64,
@@ -41,6 +44,69 @@ pub struct SwapExecutedEvent {
     pub timestamp: u64,
     pub token_program_a: Pubkey,
     pub token_program_b: Pubkey,
+    pub integrator_fee_account: Pubkey,
+    pub integrator_fee_amount: u64,
+    /// Whether the swap was truncated at the end of the provided tick arrays. Routers should
+    /// retry the remainder with more tick arrays rather than treat the fill as final.
+    pub tick_arrays_exhausted: bool,
+    /// Square root price of the pool before the swap, after the refresh of its oracle price.
+    pub sqrt_price_before: u128,
+    /// Amount of the input token sent to the pool vault, including the transfer fee.
+    pub amount_in: u64,
+    /// Amount of the output token sent from the pool vault, including the transfer fee.
+    pub amount_out: u64,
+    /// Average execution price of the swap, see `get_effective_price_x64`. Monitoring compares it
+    /// with the pool price before and after the swap to detect abnormal executions.
+    pub effective_price_x64: u128,
+}
+
+/// Amounts and fees of a single pool swap, so that indexers do not have to reconstruct them from
+/// token balance changes.
+#[event]
+pub struct SwapBreakdownEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub a_to_b: bool,
+    /// Amount of the input token sent to the pool vault, including the transfer fee.
+    pub amount_in: u64,
+    /// Amount of the output token sent from the pool vault, including the transfer fee.
+    pub amount_out: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    pub transfer_fee_in: u64,
+    pub transfer_fee_out: u64,
+}
+
+/// Emitted by swaps of oracle pools executed in degraded mode, at the last oracle price of the
+/// pool because the price update was missing or rejected.
+#[event]
+pub struct DegradedOracleSwapEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub sqrt_price: u128,
+    pub last_updated_oracle_timestamp: u64,
+}
+
+pub fn emit_degraded_oracle_swap_event(ai_dex_pool: Pubkey, ai_dex: &AiDexPool) {
+    emit!(DegradedOracleSwapEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool,
+        sqrt_price: ai_dex.sqrt_price,
+        last_updated_oracle_timestamp: ai_dex.last_updated_oracle_timestamp,
+    });
+}
+
+/// Result of a swap, set as the return data of the instruction so that calling programs can read
+/// it with `get_return_data` after the CPI.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapReturnData {
+    /// Amount of the input token sent to the pool vault, including the transfer fee.
+    pub amount_in: u64,
+    /// Amount of the output token sent from the pool vault, including the transfer fee.
+    pub amount_out: u64,
+    /// Square root price of the pool after the swap.
+    pub next_sqrt_price: u128,
 }
 
 #[derive(Accounts)]
@@ -97,17 +163,17 @@ pub struct Swap<'info> {
     #[account(mut)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The first tick array, which is mutable and must be associated with the AI DEX
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_0: AccountLoader<'info, TickArray>,
+    /// CHECK: The first tick array (v1 or v2), which is mutable and must be associated with the AI DEX
+    #[account(mut, constraint = load_tick_array(&tick_array_0)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_0: UncheckedAccount<'info>,
 
-    /// The second tick array, which is mutable and must be associated with the AI DEX
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_1: AccountLoader<'info, TickArray>,
+    /// CHECK: The second tick array (v1 or v2), which is mutable and must be associated with the AI DEX
+    #[account(mut, constraint = load_tick_array(&tick_array_1)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_1: UncheckedAccount<'info>,
 
-    /// The third tick array, which is mutable and must be associated with the AI DEX
-    #[account(mut, has_one = ai_dex_pool)]
-    pub tick_array_2: AccountLoader<'info, TickArray>,
+    /// CHECK: The third tick array (v1 or v2), which is mutable and must be associated with the AI DEX
+    #[account(mut, constraint = load_tick_array(&tick_array_2)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_2: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -131,6 +197,10 @@ pub struct Swap<'info> {
     pub swap_referral_ata_b: Option<InterfaceAccount<'info, TokenAccount>>,
     
     pub ai_dex_config: Account<'info, AiDexConfig>,
+
+    /// The integrator token account receiving the integrator fee, which must hold the output token
+    #[account(mut)]
+    pub integrator_fee_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 pub fn swap_handler<'a, 'b, 'c, 'info>(
@@ -141,83 +211,249 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
     amount_specified_is_input: bool,
     a_to_b: bool, // Zero for one
     remaining_accounts_info: Option<RemainingAccountsInfo>,
-) -> Result<()> {
-    let ai_dex = &mut ctx.accounts.ai_dex_pool;
+    deadline_timestamp: Option<u64>,
+    tick_index_limit: Option<i32>,
+    integrator_fee_rate: Option<u16>,
+    stop_at_tick_array_end: Option<bool>,
+    oracle_maximum_age: Option<u64>,
+) -> Result<SwapReturnData> {
+    let integrator_fee_rate = integrator_fee_rate.unwrap_or(0);
+    if integrator_fee_rate > ctx.accounts.ai_dex_config.max_integrator_fee_rate {
+        return Err(ErrorCode::IntegratorFeeRateExceededError.into());
+    }
+
+    let accounts = &mut *ctx.accounts;
+    execute_swap(
+        SwapAccounts {
+            token_program_a: &accounts.token_program_a,
+            token_program_b: &accounts.token_program_b,
+            memo_program: &accounts.memo_program,
+            token_authority: &accounts.token_authority,
+            ai_dex_pool: &mut accounts.ai_dex_pool,
+            token_mint_a: &accounts.token_mint_a,
+            token_mint_b: &accounts.token_mint_b,
+            token_owner_account_a: &accounts.token_owner_account_a,
+            token_vault_a: &accounts.token_vault_a,
+            token_owner_account_b: &accounts.token_owner_account_b,
+            token_vault_b: &accounts.token_vault_b,
+            tick_array_0: &accounts.tick_array_0,
+            tick_array_1: &accounts.tick_array_1,
+            tick_array_2: &accounts.tick_array_2,
+            oracle_account: accounts.oracle_account.as_mut(),
+            price_update: accounts.price_update.as_ref(),
+            swap_referral: accounts.swap_referral.as_ref(),
+            swap_referral_ata_a: accounts.swap_referral_ata_a.as_ref(),
+            swap_referral_ata_b: accounts.swap_referral_ata_b.as_ref(),
+            ai_dex_config: Some(&accounts.ai_dex_config),
+            integrator_fee_account: accounts.integrator_fee_account.as_deref(),
+        },
+        ctx.remaining_accounts,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::PoolSnapshots,
+            AccountsType::SwapRateLimiters,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::SwapCallback,
+            AccountsType::Blocklists,
+            AccountsType::PoolFeeAnalytics,
+            AccountsType::ReferralFeeEscrows,
+        ],
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        remaining_accounts_info,
+        deadline_timestamp,
+        tick_index_limit,
+        integrator_fee_rate,
+        stop_at_tick_array_end.unwrap_or(false),
+        oracle_maximum_age,
+    )
+}
+
+/// Accounts of a single pool swap, borrowed from the accounts of `swap` or `swap_v2`.
+///
+/// The oracle, referral and integrator accounts are optional since `swap_v2` does not take them.
+pub struct SwapAccounts<'a, 'info> {
+    pub token_program_a: &'a Interface<'info, TokenInterface>,
+    pub token_program_b: &'a Interface<'info, TokenInterface>,
+    pub memo_program: &'a Program<'info, Memo>,
+    pub token_authority: &'a Signer<'info>,
+    pub ai_dex_pool: &'a mut AccountLoader<'info, AiDexPool>,
+    pub token_mint_a: &'a InterfaceAccount<'info, Mint>,
+    pub token_mint_b: &'a InterfaceAccount<'info, Mint>,
+    pub token_owner_account_a: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_vault_a: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_owner_account_b: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_vault_b: &'a InterfaceAccount<'info, TokenAccount>,
+    pub tick_array_0: &'a UncheckedAccount<'info>,
+    pub tick_array_1: &'a UncheckedAccount<'info>,
+    pub tick_array_2: &'a UncheckedAccount<'info>,
+    pub oracle_account: Option<&'a mut Account<'info, OracleAccount>>,
+    pub price_update: Option<&'a AccountInfo<'info>>,
+    pub swap_referral: Option<&'a Account<'info, SwapReferral>>,
+    pub swap_referral_ata_a: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub swap_referral_ata_b: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub ai_dex_config: Option<&'a Account<'info, AiDexConfig>>,
+    pub integrator_fee_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Executes a single pool swap, shared by `swap` and `swap_v2`.
+///
+/// # Parameters
+/// - `accounts`: The accounts of the swap.
+/// - `remaining_accounts`: The remaining accounts of the instruction.
+/// - `valid_accounts_type_list`: The remaining accounts types accepted by the instruction.
+/// - `integrator_fee_rate`: The integrator fee rate, already checked against the config maximum.
+///
+/// The other parameters are the arguments of `swap`.
+///
+/// # Returns
+/// - `Result<SwapReturnData>`: The amounts of the swap and the pool price after it.
+pub fn execute_swap<'info>(
+    accounts: SwapAccounts<'_, 'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    valid_accounts_type_list: &[AccountsType],
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<u64>,
+    tick_index_limit: Option<i32>,
+    integrator_fee_rate: u16,
+    stop_at_tick_array_end: bool,
+    oracle_maximum_age: Option<u64>,
+) -> Result<SwapReturnData> {
+    let SwapAccounts {
+        token_program_a,
+        token_program_b,
+        memo_program,
+        token_authority,
+        ai_dex_pool: ai_dex,
+        token_mint_a,
+        token_mint_b,
+        token_owner_account_a,
+        token_vault_a,
+        token_owner_account_b,
+        token_vault_b,
+        tick_array_0,
+        tick_array_1,
+        tick_array_2,
+        oracle_account,
+        price_update,
+        swap_referral,
+        swap_referral_ata_a,
+        swap_referral_ata_b,
+        ai_dex_config,
+        integrator_fee_account,
+    } = accounts;
     let mut ai_dex_data = ai_dex.load_mut()?; // Load ai_dex data once
 
+    ai_dex_data.lock()?;
+
     // Verify that token_mint_a matches the AiDexPool's token_mint_a
-    if ctx.accounts.token_mint_a.key() != ai_dex_data.token_mint_a {
+    if token_mint_a.key() != ai_dex_data.token_mint_a {
         return Err(ErrorCode::InvalidInputTokenMint.into());
     }
 
     // Verify that token_mint_b matches the AiDexPool's token_mint_b
-    if ctx.accounts.token_mint_b.key() != ai_dex_data.token_mint_b {
+    if token_mint_b.key() != ai_dex_data.token_mint_b {
         return Err(ErrorCode::InvalidOutputTokenMint.into());
     }
 
     // Verify that the token owner account for token mint A matches the token_mint_a
-    if ctx.accounts.token_owner_account_a.mint != ai_dex_data.token_mint_a {
+    if token_owner_account_a.mint != ai_dex_data.token_mint_a {
         return Err(ErrorCode::InvalidTokenOwner.into());
     }
 
     // Verify that the token owner account for token mint B matches the token_mint_b
-    if ctx.accounts.token_owner_account_b.mint != ai_dex_data.token_mint_b {
+    if token_owner_account_b.mint != ai_dex_data.token_mint_b {
         return Err(ErrorCode::InvalidTokenOwner.into());
     }
 
     // Verify that the token vault account for token mint A matches the AiDexPool's token_vault_a
-    if ctx.accounts.token_vault_a.key() != ai_dex_data.token_vault_a {
+    if token_vault_a.key() != ai_dex_data.token_vault_a {
         return Err(ErrorCode::InvalidVault.into());
     }
 
     // Verify that the token vault account for token mint B matches the AiDexPool's token_vault_b
-    if ctx.accounts.token_vault_b.key() != ai_dex_data.token_vault_b {
+    if token_vault_b.key() != ai_dex_data.token_vault_b {
         return Err(ErrorCode::InvalidVault.into());
     }
 
     // Update the global reward growth which increases as a function of time.
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let timestamp = current_timestamp_u64(remaining_accounts)?;
+    check_deadline(timestamp, deadline_timestamp)?;
+
+    let sqrt_price_limit = resolve_sqrt_price_limit(sqrt_price_limit, tick_index_limit, a_to_b)?;
+
+    // Read the transfer fees once for the quote, the slippage check, the event and the transfers
+    let transfer_fee_a = MintTransferFee::load(token_mint_a)?;
+    let transfer_fee_b = MintTransferFee::load(token_mint_b)?;
+
+    // The integrator fee is taken from the output token, so the fee account must hold it
+    if integrator_fee_rate > 0 {
+        let output_token_mint = if a_to_b {
+            ai_dex_data.token_mint_b
+        } else {
+            ai_dex_data.token_mint_a
+        };
+        match integrator_fee_account {
+            Some(integrator_fee_account) if integrator_fee_account.mint == output_token_mint => {}
+            _ => return Err(ErrorCode::InvalidIntegratorFeeAccount.into()),
+        }
+    }
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
-        &ctx.remaining_accounts,
+        remaining_accounts,
         &remaining_accounts_info,
+        valid_accounts_type_list,
+    )?;
+
+    check_blocklist(
+        &remaining_accounts.blocklists,
+        &ai_dex_data,
         &[
-            AccountsType::TransferHookA,
-            AccountsType::TransferHookB,
+            token_authority.key(),
+            token_owner_account_a.owner,
+            token_owner_account_b.owner,
         ],
     )?;
 
     let mut swap_tick_sequence = SwapTickSequence::new(
-        ctx.accounts.tick_array_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_1.load_mut().ok(),
-        ctx.accounts.tick_array_2.load_mut().ok(),
+        load_tick_array_mut(tick_array_0)?,
+        load_tick_array_mut(tick_array_1).ok(),
+        load_tick_array_mut(tick_array_2).ok(),
     );
+    swap_tick_sequence.push_supplemental_tick_arrays(&remaining_accounts.supplemental_tick_arrays, ai_dex.key())?;
+    swap_tick_sequence.set_stop_at_end(stop_at_tick_array_end);
 
     if ai_dex_data.is_oracle_pool {
-        let oracle_account = ctx
-            .accounts
-            .oracle_account
-            .as_mut()
-            .ok_or(ErrorCode::MissingOracleAccount)?;
-        let price_update_account_info = ctx
-            .accounts
-            .price_update
-            .as_ref()
-            .ok_or(ErrorCode::MissingPriceUpdate)?;
-
-        oracle_account.update_sqrt_price(
+        let oracle_account = oracle_account.ok_or(ErrorCode::MissingOracleAccount)?;
+
+        let is_oracle_degraded = oracle_account.update_sqrt_price_or_degrade(
             &mut *ai_dex_data,
-            price_update_account_info,
-            ctx.accounts.token_mint_a.decimals,
-            ctx.accounts.token_mint_b.decimals,
+            price_update,
+            token_mint_a.decimals,
+            token_mint_b.decimals,
+            oracle_maximum_age,
         )?;
+        if is_oracle_degraded {
+            emit_degraded_oracle_swap_event(ai_dex.key(), &ai_dex_data);
+        }
     }
 
     let config_referral_reward_fee_rate;
     let referral_account_reward_fee_rate;
-    if let Some(referral_account) = &ctx.accounts.swap_referral {
-        config_referral_reward_fee_rate = ctx.accounts.ai_dex_config.default_swap_referral_reward_fee_rate;
+    if let Some(referral_account) = swap_referral {
+        config_referral_reward_fee_rate = ai_dex_data.resolve_swap_referral_reward_fee_rate(
+            ai_dex_config.map_or(0, |ai_dex_config| ai_dex_config.default_swap_referral_reward_fee_rate),
+        );
         referral_account_reward_fee_rate = referral_account.referral_reward_fee_rate;
     } else {
         config_referral_reward_fee_rate = 0;
@@ -230,12 +466,23 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
         referral_account_reward_fee_rate,
     );
 
-    let swap_update = swap_with_transfer_fee_extension(
+    // An exact output is grossed up by the integrator fee, so the owner still receives `amount`
+    let exact_output_integrator_fee_amount = if amount_specified_is_input {
+        0
+    } else {
+        get_integrator_fee_amount_for_output(amount, integrator_fee_rate)?
+    };
+    let swap_amount = amount
+        .checked_add(exact_output_integrator_fee_amount)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
+    let sqrt_price_before = ai_dex_data.sqrt_price;
+    let mut swap_update = swap_with_transfer_fee_extension(
         &ai_dex_data, // Use the already loaded AiDex data
-        &ctx.accounts.token_mint_a,
-        &ctx.accounts.token_mint_b,
+        &transfer_fee_a,
+        &transfer_fee_b,
         &mut swap_tick_sequence,
-        amount,
+        swap_amount,
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
@@ -245,19 +492,43 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
 
     drop(ai_dex_data);
 
+    record_swap_volume(
+        &remaining_accounts.swap_rate_limiters,
+        ai_dex,
+        swap_update.amount_a,
+        swap_update.amount_b,
+        timestamp,
+    )?;
+    record_swap_fee_analytics(
+        &remaining_accounts.pool_fee_analytics,
+        ai_dex,
+        &swap_update,
+        a_to_b,
+        timestamp,
+    )?;
+
+    // The integrator fee is skimmed from the swap output, and paid from the output vault
+    let (output_amount, output_transfer_fee) = if a_to_b {
+        (swap_update.amount_b, &transfer_fee_b)
+    } else {
+        (swap_update.amount_a, &transfer_fee_a)
+    };
+    let integrator_fee_amount = if amount_specified_is_input {
+        get_integrator_fee_amount(
+            output_transfer_fee.calculate_transfer_fee_excluded_amount(output_amount)?.amount,
+            integrator_fee_rate,
+        )
+    } else {
+        exact_output_integrator_fee_amount
+    };
+    let owner_output_amount = output_amount
+        .checked_sub(integrator_fee_amount)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+
     if amount_specified_is_input {
-        let transfer_fee_excluded_output_amount = if a_to_b {
-            calculate_transfer_fee_excluded_amount(
-                &ctx.accounts.token_mint_b,
-                swap_update.amount_b
-            )?.amount
-        } else {
-            calculate_transfer_fee_excluded_amount(
-                &ctx.accounts.token_mint_a,
-                swap_update.amount_a
-            )?.amount
-        };
-        if transfer_fee_excluded_output_amount < other_amount_threshold {
+        let transfer_fee_excluded_owner_output_amount =
+            output_transfer_fee.calculate_transfer_fee_excluded_amount(owner_output_amount)?.amount;
+        if transfer_fee_excluded_owner_output_amount < other_amount_threshold {
             return Err(ErrorCode::AmountOutBelowMinimumError.into());
         }
     } else {
@@ -272,84 +543,206 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
     }
 
     if swap_update.next_referral_fee > 0 {
-        if let Some(referral_account) = &ctx.accounts.swap_referral {
-            transfer_referral_fee(
+        if let Some(referral_account) = swap_referral {
+            let is_escrowed = escrow_referral_fee(
+                &remaining_accounts.referral_fee_escrows,
                 referral_account,
-                ctx.accounts.swap_referral_ata_a.as_ref(),
-                ctx.accounts.swap_referral_ata_b.as_ref(),
-                &ctx.accounts.token_mint_a,
-                &ctx.accounts.token_mint_b,
-                &*ctx.accounts.token_vault_a,
-                &*ctx.accounts.token_vault_b,
-                &ctx.accounts.token_program_a,
-                &ctx.accounts.token_program_b,
-                &ctx.accounts.memo_program,
-                &remaining_accounts.transfer_hook_a,
-                &remaining_accounts.transfer_hook_b,
-                &ai_dex,
+                ai_dex,
                 swap_update.next_referral_fee,
                 a_to_b,
             )?;
+            if !is_escrowed {
+                transfer_referral_fee(
+                    referral_account,
+                    swap_referral_ata_a,
+                    swap_referral_ata_b,
+                    token_mint_a,
+                    token_mint_b,
+                    token_vault_a,
+                    token_vault_b,
+                    token_program_a,
+                    token_program_b,
+                    memo_program,
+                    &remaining_accounts.transfer_hook_a,
+                    &remaining_accounts.transfer_hook_b,
+                    ai_dex,
+                    swap_update.next_referral_fee,
+                    a_to_b,
+                )?;
+            }
         } else {
             return Err(ErrorCode::MissingSwapReferralAccount.into());
         }
     }
+    let tick_arrays_exhausted = swap_update.tick_arrays_exhausted;
+    let swap_breakdown_event = if a_to_b {
+        build_swap_breakdown_event(ai_dex.key(), &swap_update, a_to_b, &transfer_fee_a, &transfer_fee_b)?
+    } else {
+        build_swap_breakdown_event(ai_dex.key(), &swap_update, a_to_b, &transfer_fee_b, &transfer_fee_a)?
+    };
+
+    // The owner receives the swap output less the integrator fee
+    if a_to_b {
+        swap_update.amount_b = owner_output_amount;
+    } else {
+        swap_update.amount_a = owner_output_amount;
+    }
+
     update_and_swap_ai_dex(
         ai_dex,
-        &ctx.accounts.token_authority,
-        &ctx.accounts.token_mint_a,
-        &ctx.accounts.token_mint_b,
-        &ctx.accounts.token_owner_account_a,
-        &ctx.accounts.token_owner_account_b,
-        &ctx.accounts.token_vault_a,
-        &ctx.accounts.token_vault_b,
+        token_authority,
+        token_mint_a,
+        token_mint_b,
+        &transfer_fee_a,
+        &transfer_fee_b,
+        token_owner_account_a,
+        token_owner_account_b,
+        token_vault_a,
+        token_vault_b,
         &remaining_accounts.transfer_hook_a,
         &remaining_accounts.transfer_hook_b,
-        &ctx.accounts.token_program_a,
-        &ctx.accounts.token_program_b,
-        &ctx.accounts.memo_program,
+        token_program_a,
+        token_program_b,
+        memo_program,
         swap_update,
         a_to_b,
         timestamp,
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
+    let integrator_fee_account_key = match integrator_fee_account {
+        Some(integrator_fee_account) if integrator_fee_amount > 0 => {
+            let (output_token_mint, output_token_vault, output_token_program, output_transfer_hook) = if a_to_b {
+                (token_mint_b, token_vault_b, token_program_b, &remaining_accounts.transfer_hook_b)
+            } else {
+                (token_mint_a, token_vault_a, token_program_a, &remaining_accounts.transfer_hook_a)
+            };
+            transfer_from_vault_to_owner(
+                ai_dex,
+                output_token_mint,
+                output_token_vault,
+                integrator_fee_account,
+                output_token_program,
+                memo_program,
+                output_transfer_hook,
+                integrator_fee_amount,
+                transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+            )?;
+            integrator_fee_account.key()
+        }
+        _ => Pubkey::default(),
+    };
+
+    ai_dex.load_mut()?.unlock();
+
     emit!(SwapExecutedEvent {
-        token_authority: ctx.accounts.token_authority.key(),
+        event_version: EVENT_VERSION,
+        token_authority: token_authority.key(),
         ai_dex_pool: ai_dex.key(),
         amount,
         other_amount_threshold,
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
-        token_mint_a: ctx.accounts.token_mint_a.key(),
-        token_mint_b: ctx.accounts.token_mint_b.key(),
-        token_owner_account_a: ctx.accounts.token_owner_account_a.key(),
-        token_owner_account_b: ctx.accounts.token_owner_account_b.key(),
-        token_vault_a: ctx.accounts.token_vault_a.key(),
-        token_vault_b: ctx.accounts.token_vault_b.key(),
-        tick_array_0: ctx.accounts.tick_array_0.key(),
-        tick_array_1: ctx.accounts.tick_array_1.key(),
-        tick_array_2: ctx.accounts.tick_array_2.key(),
+        token_mint_a: token_mint_a.key(),
+        token_mint_b: token_mint_b.key(),
+        token_owner_account_a: token_owner_account_a.key(),
+        token_owner_account_b: token_owner_account_b.key(),
+        token_vault_a: token_vault_a.key(),
+        token_vault_b: token_vault_b.key(),
+        tick_array_0: tick_array_0.key(),
+        tick_array_1: tick_array_1.key(),
+        tick_array_2: tick_array_2.key(),
         sqrt_price: ai_dex.load()?.sqrt_price,
         liquidity: ai_dex.load()?.liquidity,
         current_tick: ai_dex.load()?.tick_current_index,
         fee_growth_global_a: ai_dex.load()?.fee_growth_global_a,
         fee_growth_global_b: ai_dex.load()?.fee_growth_global_b,
         timestamp,
-        token_program_a: ctx.accounts.token_program_a.key(),
-        token_program_b: ctx.accounts.token_program_b.key(),
+        token_program_a: token_program_a.key(),
+        token_program_b: token_program_b.key(),
+        integrator_fee_account: integrator_fee_account_key,
+        integrator_fee_amount,
+        tick_arrays_exhausted,
+        sqrt_price_before,
+        amount_in: swap_breakdown_event.amount_in,
+        amount_out: swap_breakdown_event.amount_out,
+        effective_price_x64: get_effective_price_x64(swap_breakdown_event.amount_in, swap_breakdown_event.amount_out),
     });
+    emit!(swap_breakdown_event);
+
+    refresh_pool_snapshots(&remaining_accounts.pool_snapshots, &[ai_dex])?;
+
+    invoke_swap_callback(
+        &remaining_accounts.swap_callback,
+        &SwapCallbackData {
+            ai_dex_pool: ai_dex.key(),
+            token_authority: token_authority.key(),
+            a_to_b,
+            amount_in: swap_breakdown_event.amount_in,
+            amount_out: swap_breakdown_event.amount_out,
+            sqrt_price: ai_dex.load()?.sqrt_price,
+            timestamp,
+        },
+    )?;
+
+    Ok(SwapReturnData {
+        amount_in: swap_breakdown_event.amount_in,
+        amount_out: swap_breakdown_event.amount_out,
+        next_sqrt_price: ai_dex.load()?.sqrt_price,
+    })
+}
 
-    Ok(())
+/// Builds the `SwapBreakdownEvent` of a swap from its `PostSwapUpdate`.
+///
+/// The swap fee is split into the protocol fee, the referral fee and the remainder credited to
+/// liquidity providers. All of them are in the input token.
+///
+/// # Parameters
+/// - `ai_dex_pool`: The key of the pool the swap was executed on.
+/// - `swap_update`: The result of `swap_with_transfer_fee_extension`.
+/// - `a_to_b`: Boolean indicating the direction of the swap.
+/// - `input_transfer_fee`: The transfer fee of the input token mint.
+/// - `output_transfer_fee`: The transfer fee of the output token mint.
+///
+/// # Returns
+/// - `Result<SwapBreakdownEvent>`: The event to emit once the swap is executed.
+pub fn build_swap_breakdown_event(
+    ai_dex_pool: Pubkey,
+    swap_update: &PostSwapUpdate,
+    a_to_b: bool,
+    input_transfer_fee: &MintTransferFee,
+    output_transfer_fee: &MintTransferFee,
+) -> Result<SwapBreakdownEvent> {
+    let (amount_in, amount_out) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+
+    Ok(SwapBreakdownEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_pool,
+        a_to_b,
+        amount_in,
+        amount_out,
+        lp_fee: swap_update
+            .fee_amount
+            .saturating_sub(swap_update.next_protocol_fee)
+            .saturating_sub(swap_update.next_referral_fee),
+        protocol_fee: swap_update.next_protocol_fee,
+        referral_fee: swap_update.next_referral_fee,
+        transfer_fee_in: input_transfer_fee.calculate_transfer_fee_excluded_amount(amount_in)?.transfer_fee,
+        transfer_fee_out: output_transfer_fee.calculate_transfer_fee_excluded_amount(amount_out)?.transfer_fee,
+    })
 }
 
 /// Performs a token swap with transfer fee extension.
 ///
 /// # Parameters
 /// - `ai_dex`: Reference to the AiDex instance.
-/// - `token_mint_a`: Interface account for the first token mint.
-/// - `token_mint_b`: Interface account for the second token mint.
+/// - `transfer_fee_a`: Transfer fee of the first token mint, read once by the instruction.
+/// - `transfer_fee_b`: Transfer fee of the second token mint, read once by the instruction.
 /// - `swap_tick_sequence`: Mutable reference to the swap tick sequence.
 /// - `amount`: The amount to be swapped.
 /// - `sqrt_price_limit`: The square root price limit for the swap.
@@ -359,10 +752,10 @@ pub fn swap_handler<'a, 'b, 'c, 'info>(
 ///
 /// # Returns
 /// - `Result<PostSwapUpdate>`: The result containing the post-swap update or an error.
-pub fn swap_with_transfer_fee_extension<'info>(
+pub fn swap_with_transfer_fee_extension(
     ai_dex: &AiDexPool,
-    token_mint_a: &InterfaceAccount<'info, Mint>,
-    token_mint_b: &InterfaceAccount<'info, Mint>,
+    transfer_fee_a: &MintTransferFee,
+    transfer_fee_b: &MintTransferFee,
     swap_tick_sequence: &mut SwapTickSequence,
     amount: u64,
     sqrt_price_limit: u128,
@@ -371,17 +764,17 @@ pub fn swap_with_transfer_fee_extension<'info>(
     timestamp: u64,
     referrer_swap_fee_rate: u16,
 ) -> Result<PostSwapUpdate> {
-    let (input_token_mint, output_token_mint) = if a_to_b {
-        (token_mint_a, token_mint_b)
+    let (input_transfer_fee, output_transfer_fee) = if a_to_b {
+        (transfer_fee_a, transfer_fee_b)
     } else {
-        (token_mint_b, token_mint_a)
+        (transfer_fee_b, transfer_fee_a)
     };
 
     let (transfer_fee_included_amount, transfer_fee_excluded_amount) = if amount_specified_is_input {
-        let transfer_fee_excluded_input = calculate_transfer_fee_excluded_amount(input_token_mint, amount)?.amount;
+        let transfer_fee_excluded_input = input_transfer_fee.calculate_transfer_fee_excluded_amount(amount)?.amount;
         (amount, transfer_fee_excluded_input)
     } else {
-        let transfer_fee_included_output = calculate_transfer_fee_included_amount(output_token_mint, amount)?.amount;
+        let transfer_fee_included_output = output_transfer_fee.calculate_transfer_fee_included_amount(amount)?.amount;
         (transfer_fee_included_output, amount)
     };
 
@@ -406,7 +799,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
         if swap_update_amount_input == transfer_fee_excluded_amount {
             transfer_fee_included_amount
         } else {
-            calculate_transfer_fee_included_amount(input_token_mint, swap_update_amount_input)?.amount
+            input_transfer_fee.calculate_transfer_fee_included_amount(swap_update_amount_input)?.amount
         }
     } else {
         swap_update_amount_output
@@ -428,5 +821,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
         next_reward_infos: swap_update.next_reward_infos,
         next_protocol_fee: swap_update.next_protocol_fee,
         next_referral_fee: swap_update.next_referral_fee,
+        fee_amount: swap_update.fee_amount,
+        tick_arrays_exhausted: swap_update.tick_arrays_exhausted,
     })
 }
\ No newline at end of file
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::memo::Memo;
+
+use crate::util::{AccountsType, RemainingAccountsInfo};
+
+use crate::{
+    errors::ErrorCode,
+    state::{load_tick_array, AiDexPool},
+};
+
+use super::swap::{execute_swap, SwapAccounts, SwapReturnData};
+
+/// Trimmed account list of `Swap` for CPI callers, without the oracle, referral and config accounts.
+#[derive(Accounts)]
+pub struct SwapV2<'info> {
+    #[account(constraint = token_program_a.key() == *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+
+    #[account(constraint = token_program_b.key() == *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_0)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_0: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_1)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_1: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_2)?.ai_dex_pool() == ai_dex_pool.key())]
+    pub tick_array_2: UncheckedAccount<'info>,
+}
+
+/// Executes a swap without oracle or referral support.
+///
+/// Oracle pools are rejected since their price must be refreshed from the oracle accounts
+/// `swap` takes. No referral or integrator fee is taken. The swap itself is executed by
+/// `execute_swap`, shared with `swap`.
+///
+/// The `SwapReturnData` of the swap is set as the return data of the instruction.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The pool is an oracle pool.
+/// - The mints, owner accounts or vaults do not match the pool.
+/// - The deadline has passed or the output/input amount thresholds are not met.
+pub fn swap_v2_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<u64>,
+) -> Result<SwapReturnData> {
+    if ctx.accounts.ai_dex_pool.load()?.is_oracle_pool {
+        return Err(ErrorCode::OraclePoolNotSupported.into());
+    }
+
+    let accounts = &mut *ctx.accounts;
+    execute_swap(
+        SwapAccounts {
+            token_program_a: &accounts.token_program_a,
+            token_program_b: &accounts.token_program_b,
+            memo_program: &accounts.memo_program,
+            token_authority: &accounts.token_authority,
+            ai_dex_pool: &mut accounts.ai_dex_pool,
+            token_mint_a: &accounts.token_mint_a,
+            token_mint_b: &accounts.token_mint_b,
+            token_owner_account_a: &accounts.token_owner_account_a,
+            token_vault_a: &accounts.token_vault_a,
+            token_owner_account_b: &accounts.token_owner_account_b,
+            token_vault_b: &accounts.token_vault_b,
+            tick_array_0: &accounts.tick_array_0,
+            tick_array_1: &accounts.tick_array_1,
+            tick_array_2: &accounts.tick_array_2,
+            oracle_account: None,
+            price_update: None,
+            swap_referral: None,
+            swap_referral_ata_a: None,
+            swap_referral_ata_b: None,
+            ai_dex_config: None,
+            integrator_fee_account: None,
+        },
+        ctx.remaining_accounts,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::PoolSnapshots,
+            AccountsType::SwapRateLimiters,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::SwapCallback,
+            AccountsType::Blocklists,
+            AccountsType::PoolFeeAnalytics,
+        ],
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        remaining_accounts_info,
+        deadline_timestamp,
+        None,
+        0,
+        false,
+        None,
+    )
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AiDexConfig, MockClock};
+
+#[derive(Accounts)]
+pub struct InitializeMockClock<'info> {
+    pub config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"mock_clock"],
+        bump,
+        space = MockClock::LEN,
+    )]
+    pub mock_clock: Account<'info, MockClock>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = config.config_authority)]
+    pub config_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMockClock<'info> {
+    pub config: Box<Account<'info, AiDexConfig>>,
+
+    #[account(mut, seeds = [b"mock_clock"], bump = mock_clock.bump)]
+    pub mock_clock: Account<'info, MockClock>,
+
+    #[account(address = config.config_authority)]
+    pub config_authority: Signer<'info>,
+}
+
+pub fn initialize_mock_clock_handler(
+    ctx: Context<InitializeMockClock>,
+    unix_timestamp: i64,
+) -> Result<()> {
+    let mock_clock = &mut ctx.accounts.mock_clock;
+
+    mock_clock.initialize(unix_timestamp, ctx.bumps.mock_clock)?;
+
+    Ok(())
+}
+
+pub fn set_mock_clock_handler(
+    ctx: Context<SetMockClock>,
+    unix_timestamp: i64,
+) -> Result<()> {
+    ctx.accounts.mock_clock.update_unix_timestamp(unix_timestamp)
+}
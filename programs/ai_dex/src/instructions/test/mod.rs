@@ -1,2 +1,5 @@
 pub mod mock_oracle_price_update;
-pub use mock_oracle_price_update::*;
\ No newline at end of file
+pub use mock_oracle_price_update::*;
+
+pub mod mock_clock;
+pub use mock_clock::*;
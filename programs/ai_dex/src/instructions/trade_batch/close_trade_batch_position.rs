@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::{state::*, util::verify_position_trade_batch_authority};
 
 #[event]
 pub struct TradeBatchPositionClosedEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub trade_batch_index: u16,
     pub position_trade_batch: Pubkey,
@@ -84,6 +86,7 @@ pub fn close_trade_batch_position_handler(ctx: Context<CloseTradeBatchPosition>,
     // Anchor will close the Position account
 
     emit!(TradeBatchPositionClosedEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ctx.accounts.trade_batch_position.ai_dex_pool.key(),
         trade_batch_index,
         position_trade_batch: position_trade_batch.key(),
@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use crate::state::*;
 use crate::util::burn_and_close_position_trade_batch_token;
 
 #[event]
 pub struct PositionTradeBatchDeletedEvent {
+    pub event_version: u8,
     pub position_trade_batch: Pubkey,
     pub position_trade_batch_mint: Pubkey,
     pub position_trade_batch_token_account_key: Pubkey,
@@ -77,6 +79,7 @@ pub fn delete_trade_batch_position_handler(ctx: Context<DeletePositionTradeBatch
     )?;
 
     emit!(PositionTradeBatchDeletedEvent {
+        event_version: EVENT_VERSION,
         position_trade_batch: ctx.accounts.position_trade_batch.key(),
         position_trade_batch_mint: ctx.accounts.position_trade_batch_mint.key(),
         position_trade_batch_token_account_key: ctx.accounts.position_trade_batch_token_account.key(),
@@ -2,10 +2,12 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
+use crate::constants::EVENT_VERSION;
 use crate::{state::*, util::mint_position_trade_batch_token_and_remove_authority};
 
 #[event]
 pub struct InitializeTradeBatchPositionEvent {
+    pub event_version: u8,
     pub position_trade_batch: Pubkey,
     pub position_trade_batch_mint: Pubkey,
     pub position_trade_batch_token_account_key: Pubkey,
@@ -99,6 +101,7 @@ pub fn initialize_trade_batch_position_handler(
     )?;
 
     emit!(InitializeTradeBatchPositionEvent {
+        event_version: EVENT_VERSION,
         position_trade_batch: ctx.accounts.position_trade_batch.key(),
         position_trade_batch_mint: position_trade_batch_mint.key(),
         position_trade_batch_token_account_key: ctx.accounts.position_trade_batch_token_account.key(),
@@ -3,11 +3,13 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use anchor_spl::metadata::Metadata;
 
+use crate::constants::EVENT_VERSION;
 use crate::constants::nft::ai_dex_nft_update_auth::ID as ADB_NFT_UPDATE_AUTH;
 use crate::{state::*, util::mint_position_trade_batch_token_with_metadata_and_remove_authority};
 
 #[event]
 pub struct InitializePositionTradeBatchWithMetadataEvent {
+    pub event_version: u8,
     pub funder: Pubkey,
     pub position_trade_batch: Pubkey,
     pub position_trade_batch_mint: Pubkey,
@@ -119,6 +121,7 @@ pub fn initialize_trade_batch_position_with_metadata_handler(
     )?;
 
     emit!(InitializePositionTradeBatchWithMetadataEvent {
+        event_version: EVENT_VERSION,
         funder: ctx.accounts.funder.key(),
         position_trade_batch: ctx.accounts.position_trade_batch.key(),
         position_trade_batch_mint: ctx.accounts.position_trade_batch_mint.key(),
@@ -3,6 +3,7 @@ pub mod delete_trade_batch_position;
 pub mod initialize_trade_batch_position;
 pub mod initialize_trade_batch_position_with_metadata;
 pub mod open_trade_batch_position;
+pub mod open_trade_batch_positions_multi;
 
 pub use close_trade_batch_position::*;
 // pub use delete_trade_batch_position::delete_trade_batch_position_handler;
@@ -14,4 +15,5 @@ pub use close_trade_batch_position::*;
 pub use delete_trade_batch_position::*;
 pub use initialize_trade_batch_position::*;
 pub use initialize_trade_batch_position_with_metadata::*;
-pub use open_trade_batch_position::*;
\ No newline at end of file
+pub use open_trade_batch_position::*;
+pub use open_trade_batch_positions_multi::*;
\ No newline at end of file
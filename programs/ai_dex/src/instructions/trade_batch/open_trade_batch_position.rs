@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
-use crate::{state::*, util::verify_position_trade_batch_authority};
+use crate::constants::EVENT_VERSION;
+use crate::{state::*, util::{to_timestamp_u64, verify_position_trade_batch_authority}};
 
 #[event]
 pub struct TradeBatchPositionOpenedEvent {
+    pub event_version: u8,
     pub trade_batch_index: u16,
     pub position_trade_batch_key: Pubkey,
     pub position_trade_batch_mint: Pubkey,
@@ -97,8 +99,10 @@ pub fn open_trade_batch_position_handler(
         tick_upper_index,
         is_reinvestment_on,
     )?;
+    position.update_last_updated_timestamp(to_timestamp_u64(Clock::get()?.unix_timestamp)?);
 
     emit!(TradeBatchPositionOpenedEvent {
+        event_version: EVENT_VERSION,
         trade_batch_index,
         position_trade_batch_key: position_trade_batch.key(),
         position_trade_batch_mint: position_trade_batch.position_trade_batch_mint,
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::TokenAccount;
+
+use crate::constants::EVENT_VERSION;
+use crate::errors::ErrorCode;
+use crate::{state::*, util::{to_timestamp_u64, verify_position_trade_batch_authority}};
+
+use super::TradeBatchPositionOpenedEvent;
+
+/// The maximum number of trade batch positions that can be opened in a single instruction.
+pub const MAX_TRADE_BATCH_POSITIONS_PER_INSTRUCTION: usize = 16;
+
+/// The tick range of a trade batch position opened by `open_trade_batch_positions_multi`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TradeBatchPositionRange {
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+#[derive(Accounts)]
+pub struct OpenTradeBatchPositionsMulti<'info> {
+    #[account(mut)]
+    pub position_trade_batch: Box<Account<'info, PositionTradeBatch>>,
+
+    #[account(
+        constraint = position_trade_batch_token_account.mint == position_trade_batch.position_trade_batch_mint,
+        constraint = position_trade_batch_token_account.amount == 1
+    )]
+    pub position_trade_batch_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub position_trade_batch_authority: Signer<'info>,
+
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens several trade batch positions in the same pool in one instruction.
+///
+/// The positions use the contiguous trade batch indices starting at `start_trade_batch_index`,
+/// one per entry of `tick_ranges`. The uninitialized trade batch position accounts are passed as
+/// remaining accounts in the same order and are created by this instruction.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all the accounts required for opening the trade batch positions.
+/// * `start_trade_batch_index` - The index of the first trade batch position to be opened.
+/// * `tick_ranges` - The tick range of each position to open.
+/// * `is_reinvestment_on` - Whether reinvestment is enabled on the opened positions.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidTradeBatchPositionCount` - If no range or more than
+///   `MAX_TRADE_BATCH_POSITIONS_PER_INSTRUCTION` ranges are given, or if the number of remaining
+///   accounts does not match the number of ranges.
+/// * `ErrorCode::InvalidTradeBatchPositionAccount` - If a remaining account is not the expected
+///   trade batch position address.
+pub fn open_trade_batch_positions_multi_handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, OpenTradeBatchPositionsMulti<'info>>,
+    start_trade_batch_index: u16,
+    tick_ranges: Vec<TradeBatchPositionRange>,
+    is_reinvestment_on: bool,
+) -> Result<()> {
+    if tick_ranges.is_empty()
+        || tick_ranges.len() > MAX_TRADE_BATCH_POSITIONS_PER_INSTRUCTION
+        || ctx.remaining_accounts.len() != tick_ranges.len()
+    {
+        return Err(ErrorCode::InvalidTradeBatchPositionCount.into());
+    }
+
+    // Allow delegation
+    verify_position_trade_batch_authority(
+        &ctx.accounts.position_trade_batch_token_account,
+        &ctx.accounts.position_trade_batch_authority,
+    )?;
+
+    let ai_dex = &ctx.accounts.ai_dex_pool;
+    let position_trade_batch = &mut ctx.accounts.position_trade_batch;
+    let position_trade_batch_mint = position_trade_batch.position_trade_batch_mint;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let rent_exempt = Rent::get()?.minimum_balance(Position::LEN);
+
+    for (offset, (tick_range, position_info)) in tick_ranges.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+        let trade_batch_index = start_trade_batch_index
+            .checked_add(offset as u16)
+            .ok_or(ErrorCode::InvalidTradeBatchIndexError)?;
+        let trade_batch_index_seed = trade_batch_index.to_string();
+
+        let (expected_position, bump) = Pubkey::find_program_address(
+            &[
+                b"trade_batch_position".as_ref(),
+                position_trade_batch_mint.as_ref(),
+                trade_batch_index_seed.as_bytes(),
+            ],
+            ctx.program_id,
+        );
+        if position_info.key() != expected_position {
+            return Err(ErrorCode::InvalidTradeBatchPositionAccount.into());
+        }
+
+        position_trade_batch.open_trade_batch_position(trade_batch_index)?;
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: position_info.clone(),
+                },
+                &[&[
+                    b"trade_batch_position".as_ref(),
+                    position_trade_batch_mint.as_ref(),
+                    trade_batch_index_seed.as_bytes(),
+                    &[bump],
+                ]],
+            ),
+            rent_exempt,
+            Position::LEN as u64,
+            ctx.program_id,
+        )?;
+
+        let mut position = Position::default();
+        position.open_position(
+            ai_dex,
+            position_trade_batch_mint,
+            tick_range.tick_lower_index,
+            tick_range.tick_upper_index,
+            is_reinvestment_on,
+        )?;
+        position.update_last_updated_timestamp(timestamp);
+
+        let mut data = position_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        position.try_serialize(&mut writer)?;
+
+        emit!(TradeBatchPositionOpenedEvent {
+            event_version: EVENT_VERSION,
+            trade_batch_index,
+            position_trade_batch_key: position_trade_batch.key(),
+            position_trade_batch_mint,
+            trade_batch_position_tick_lower_index: tick_range.tick_lower_index,
+            trade_batch_position_tick_upper_index: tick_range.tick_upper_index,
+            position_trade_batch_authority: ctx.accounts.position_trade_batch_authority.key(),
+            position_trade_batch_token_account_key: ctx.accounts.position_trade_batch_token_account.key(),
+            position_trade_batch_token_account_mint: ctx.accounts.position_trade_batch_token_account.mint,
+            position_trade_batch_token_account_amount: ctx.accounts.position_trade_batch_token_account.amount,
+            ai_dex_pool: ai_dex.key(),
+            funder: ctx.accounts.funder.key(),
+            is_reinvestment_on,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::*;
+
+#[event]
+pub struct SwapReferralTransferredEvent {
+    pub event_version: u8,
+    pub swap_referral: Pubkey,
+    pub referral_code: String,
+    pub old_referrer_address: Pubkey,
+    pub new_referrer_address: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct TransferSwapReferral<'info> {
+    #[account(
+        mut,
+        has_one = referrer_address,
+        realloc = SwapReferral::LEN,
+        realloc::payer = referrer_address,
+        realloc::zero = false,
+    )]
+    pub swap_referral: Account<'info, SwapReferral>,
+
+    #[account(mut)]
+    pub referrer_address: Signer<'info>,
+
+    pub new_referrer_address: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfers a swap referral to a new referrer.
+///
+/// The referral keeps its address, referral code and token accounts, so swaps that pass it keep
+/// paying referral fees to the same token accounts, and the fees accrued in them become
+/// collectable by the new referrer only. The new referrer signs to prove control of the key.
+/// Referrals created before ownership could be transferred are grown to their current length,
+/// with the current referrer paying the additional rent.
+pub fn transfer_swap_referral_handler(ctx: Context<TransferSwapReferral>) -> Result<()> {
+    let old_referrer_address = ctx.accounts.referrer_address.key();
+    let new_referrer_address = ctx.accounts.new_referrer_address.key();
+
+    ctx
+        .accounts
+        .swap_referral
+        .transfer_ownership(new_referrer_address);
+
+    emit!(SwapReferralTransferredEvent {
+        event_version: EVENT_VERSION,
+        swap_referral: ctx.accounts.swap_referral.key(),
+        referral_code: ctx.accounts.swap_referral.referral_code.clone(),
+        old_referrer_address,
+        new_referrer_address,
+    });
+
+    Ok(())
+}
@@ -1,21 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
+use crate::constants::EVENT_VERSION;
 use crate::orchestrator::swap_orchestrator::PostSwapUpdate;
 use crate::state::{AiDexConfig, OracleAccount, SwapReferral};
-use crate::swap_with_transfer_fee_extension;
+use crate::{build_swap_breakdown_event, emit_degraded_oracle_swap_event, swap_with_transfer_fee_extension};
 use crate::util::{
-    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, transfer_referral_fee, update_and_two_hop_swap_ai_dex, AccountsType, RemainingAccountsInfo
+    check_blocklist, escrow_referral_fee, parse_remaining_accounts, record_swap_fee_analytics, record_swap_volume, refresh_pool_snapshots, transfer_referral_fee, update_and_two_hop_swap_ai_dex, AccountsType, MintTransferFee, RemainingAccountsInfo
 };
 use crate::{
     errors::ErrorCode,
-    state::{TickArray, AiDexPool},
-    util::{to_timestamp_u64, SwapTickSequence},
+    math::{check_composite_oracle_price, get_effective_price_x64},
+    state::{load_tick_array, load_tick_array_mut, AiDexPool},
+    util::{check_deadline, resolve_sqrt_price_limit, current_timestamp_u64, SwapTickSequence},
     constants::transfer_memo,
 };
 
 #[event]
 pub struct TwoHopSwapEvent {
+    pub event_version: u8,
     pub ai_dex_one: Pubkey,
     pub ai_dex_two: Pubkey,
     pub amount: u64,
@@ -43,6 +46,31 @@ pub struct TwoHopSwapEvent {
     pub tick_array_two_0: Pubkey,
     pub tick_array_two_1: Pubkey,
     pub tick_array_two_2: Pubkey,
+    /// Square root price of the first pool before the swap, after the refresh of its oracle price.
+    pub sqrt_price_before_one: u128,
+    /// Square root price of the second pool before the swap, after the refresh of its oracle price.
+    pub sqrt_price_before_two: u128,
+    /// Amount of the input token sent to the vault of the first pool, including the transfer fee.
+    pub amount_in: u64,
+    /// Amount of the output token sent from the vault of the second pool, including the transfer fee.
+    pub amount_out: u64,
+    /// Average execution price of the whole route, in output token units per input token unit,
+    /// see `get_effective_price_x64`.
+    pub effective_price_x64: u128,
+}
+
+/// Result of a two-hop swap, set as the return data of the instruction so that calling programs
+/// can read it with `get_return_data` after the CPI.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TwoHopSwapReturnData {
+    /// Amount of the input token sent to the vault of the first pool, including the transfer fee.
+    pub amount_in: u64,
+    /// Amount of the output token sent from the vault of the second pool, including the transfer fee.
+    pub amount_out: u64,
+    /// Square root price of the first pool after the swap.
+    pub next_sqrt_price_one: u128,
+    /// Square root price of the second pool after the swap.
+    pub next_sqrt_price_two: u128,
 }
 
 #[derive(Accounts)]
@@ -98,19 +126,25 @@ pub struct TwoHopSwap<'info> {
 
     pub token_authority: Signer<'info>,
 
-    #[account(mut, constraint = tick_array_one_0.load()?.ai_dex_pool == ai_dex_one.key())]
-    pub tick_array_one_0: AccountLoader<'info, TickArray>,
-    #[account(mut, constraint = tick_array_one_1.load()?.ai_dex_pool == ai_dex_one.key())]
-    pub tick_array_one_1: AccountLoader<'info, TickArray>,
-    #[account(mut, constraint = tick_array_one_2.load()?.ai_dex_pool == ai_dex_one.key())]
-    pub tick_array_one_2: AccountLoader<'info, TickArray>,
-
-    #[account(mut, constraint = tick_array_two_0.load()?.ai_dex_pool == ai_dex_two.key())]
-    pub tick_array_two_0: AccountLoader<'info, TickArray>,
-    #[account(mut, constraint = tick_array_two_1.load()?.ai_dex_pool == ai_dex_two.key())]
-    pub tick_array_two_1: AccountLoader<'info, TickArray>,
-    #[account(mut, constraint = tick_array_two_2.load()?.ai_dex_pool == ai_dex_two.key())]
-    pub tick_array_two_2: AccountLoader<'info, TickArray>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_one_0)?.ai_dex_pool() == ai_dex_one.key())]
+    pub tick_array_one_0: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_one_1)?.ai_dex_pool() == ai_dex_one.key())]
+    pub tick_array_one_1: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_one_2)?.ai_dex_pool() == ai_dex_one.key())]
+    pub tick_array_one_2: UncheckedAccount<'info>,
+
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_two_0)?.ai_dex_pool() == ai_dex_two.key())]
+    pub tick_array_two_0: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_two_1)?.ai_dex_pool() == ai_dex_two.key())]
+    pub tick_array_two_1: UncheckedAccount<'info>,
+    /// CHECK: checked by load_tick_array
+    #[account(mut, constraint = load_tick_array(&tick_array_two_2)?.ai_dex_pool() == ai_dex_two.key())]
+    pub tick_array_two_2: UncheckedAccount<'info>,
 
     pub memo_program: Program<'info, Memo>,
 
@@ -141,6 +175,13 @@ pub struct TwoHopSwap<'info> {
     )]
     pub swap_referral_two: Option<Account<'info, SwapReferral>>,
 
+    /// Swap referral applied to both hops, instead of `swap_referral_one` and `swap_referral_two`
+    #[account(
+        mut,
+        constraint = swap_referral.referrer_address != token_authority.key()
+    )]
+    pub swap_referral: Option<Account<'info, SwapReferral>>,
+
     #[account(mut, constraint = swap_referral_ata_input.mint == token_mint_input.key())]
     pub swap_referral_ata_input: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(mut, constraint = swap_referral_ata_intermediate.mint == token_mint_intermediate.key())]
@@ -164,15 +205,32 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
     amount_specified_is_input: bool,
     a_to_b_one: bool,
     a_to_b_two: bool,
-    sqrt_price_limit_one_bytes: [u8; 16],
-    sqrt_price_limit_two_bytes: [u8; 16],
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
-) -> Result<()> {
-    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    deadline_timestamp: Option<u64>,
+    tick_index_limit_one: Option<i32>,
+    tick_index_limit_two: Option<i32>,
+    max_composite_price_deviation_bps: Option<u16>,
+    oracle_maximum_age: Option<u64>,
+) -> Result<TwoHopSwapReturnData> {
+    let timestamp = current_timestamp_u64(ctx.remaining_accounts)?;
+    check_deadline(timestamp, deadline_timestamp)?;
+
+    let sqrt_price_limit_one = resolve_sqrt_price_limit(sqrt_price_limit_one, tick_index_limit_one, a_to_b_one)?;
+    let sqrt_price_limit_two = resolve_sqrt_price_limit(sqrt_price_limit_two, tick_index_limit_two, a_to_b_two)?;
+
+    // Read the transfer fees once for the quotes, the slippage check, the events and the transfers
+    let transfer_fee_input = MintTransferFee::load(&ctx.accounts.token_mint_input)?;
+    let transfer_fee_intermediate = MintTransferFee::load(&ctx.accounts.token_mint_intermediate)?;
+    let transfer_fee_output = MintTransferFee::load(&ctx.accounts.token_mint_output)?;
 
     let mut ai_dex_one_data = ctx.accounts.ai_dex_one.load_mut()?;
     let mut ai_dex_two_data = ctx.accounts.ai_dex_two.load_mut()?;
 
+    ai_dex_one_data.lock()?;
+    ai_dex_two_data.lock()?;
+
     validate_inputs(
         &ctx,
         &mut *ai_dex_one_data,
@@ -188,9 +246,25 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             AccountsType::TransferHookInput,
             AccountsType::TransferHookIntermediate,
             AccountsType::TransferHookOutput,
+            AccountsType::PoolSnapshots,
+            AccountsType::SwapRateLimiters,
+            AccountsType::SupplementalTickArraysOne,
+            AccountsType::SupplementalTickArraysTwo,
+            AccountsType::OracleUpdates,
+            AccountsType::Blocklists,
+            AccountsType::PoolFeeAnalytics,
+            AccountsType::ReferralFeeEscrows,
         ],
     )?;
 
+    let swap_addresses = [
+        ctx.accounts.token_authority.key(),
+        ctx.accounts.token_owner_account_input.owner,
+        ctx.accounts.token_owner_account_output.owner,
+    ];
+    check_blocklist(&remaining_accounts.blocklists, &ai_dex_one_data, &swap_addresses)?;
+    check_blocklist(&remaining_accounts.blocklists, &ai_dex_two_data, &swap_addresses)?;
+
     // Update oracles if needed
     if ai_dex_one_data.is_oracle_pool {
         // Get mutable reference to Account<'info, OracleAccount>
@@ -199,17 +273,16 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             .oracle_account_a
             .as_mut()
             .ok_or(ErrorCode::MissingOracleAccount)?;
-        let price_update_account_info = ctx
-            .accounts
-            .price_update
-            .as_ref()
-            .ok_or(ErrorCode::MissingPriceUpdate)?;
-        oracle_account_a.update_sqrt_price(
+        let is_oracle_degraded = oracle_account_a.update_sqrt_price_or_degrade(
             &mut *ai_dex_one_data,
-            price_update_account_info,
+            ctx.accounts.price_update.as_ref(),
             ctx.accounts.token_mint_input.decimals,
             ctx.accounts.token_mint_intermediate.decimals,
+            oracle_maximum_age,
         )?;
+        if is_oracle_degraded {
+            emit_degraded_oracle_swap_event(ctx.accounts.ai_dex_one.key(), &ai_dex_one_data);
+        }
     }
 
     if ai_dex_two_data.is_oracle_pool {
@@ -219,42 +292,72 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             .oracle_account_b
             .as_mut()
             .ok_or(ErrorCode::MissingOracleAccount)?;
-        let price_update_account_info = ctx
-            .accounts
-            .price_update
-            .as_ref()
-            .ok_or(ErrorCode::MissingPriceUpdate)?;
+        // The second hop reads its own price update when passed in the `OracleUpdates` slice
+        let price_update_account_info = match &remaining_accounts.oracle_updates {
+            Some(oracle_updates) => oracle_updates.first(),
+            None => ctx.accounts.price_update.as_ref(),
+        };
 
-        oracle_account_b.update_sqrt_price(
+        let is_oracle_degraded = oracle_account_b.update_sqrt_price_or_degrade(
             &mut *ai_dex_two_data, // &mut AiDexPool
             price_update_account_info,
             ctx.accounts.token_mint_intermediate.decimals,
             ctx.accounts.token_mint_output.decimals,
+            oracle_maximum_age,
         )?;
+        if is_oracle_degraded {
+            emit_degraded_oracle_swap_event(ctx.accounts.ai_dex_two.key(), &ai_dex_two_data);
+        }
     }
 
+    // The oracle prices of both pools, read before the swaps move the pool prices
+    let oracle_sqrt_prices = match max_composite_price_deviation_bps {
+        Some(_) if ai_dex_one_data.is_oracle_pool && ai_dex_two_data.is_oracle_pool => {
+            Some((ai_dex_one_data.sqrt_price, ai_dex_two_data.sqrt_price))
+        }
+        Some(_) => return Err(ErrorCode::CompositePriceCheckRequiresOraclePools.into()),
+        None => None,
+    };
+
     // Grab referral fee rates
+    let (swap_referral_one, swap_referral_two) = resolve_hop_swap_referrals(
+        ctx.accounts.swap_referral.as_ref(),
+        ctx.accounts.swap_referral_one.as_ref(),
+        ctx.accounts.swap_referral_two.as_ref(),
+    )?;
     let (referrer_swap_fee_rate_one, referrer_swap_fee_rate_two) = compute_referral_fee_rates(
-        &ctx.accounts.swap_referral_one,
-        &ctx.accounts.swap_referral_two,
-        ctx.accounts.ai_dex_config_one.default_swap_referral_reward_fee_rate,
-        ctx.accounts.ai_dex_config_two.default_swap_referral_reward_fee_rate,
+        swap_referral_one,
+        swap_referral_two,
+        ai_dex_one_data
+            .resolve_swap_referral_reward_fee_rate(ctx.accounts.ai_dex_config_one.default_swap_referral_reward_fee_rate),
+        ai_dex_two_data
+            .resolve_swap_referral_reward_fee_rate(ctx.accounts.ai_dex_config_two.default_swap_referral_reward_fee_rate),
     );
 
     // Create tick sequences
     let mut swap_tick_sequence_one = SwapTickSequence::new(
-        ctx.accounts.tick_array_one_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_one_1.load_mut().ok(),
-        ctx.accounts.tick_array_one_2.load_mut().ok(),
+        load_tick_array_mut(&ctx.accounts.tick_array_one_0)?,
+        load_tick_array_mut(&ctx.accounts.tick_array_one_1).ok(),
+        load_tick_array_mut(&ctx.accounts.tick_array_one_2).ok(),
     );
+    swap_tick_sequence_one.push_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays_one,
+        ctx.accounts.ai_dex_one.key(),
+    )?;
     let mut swap_tick_sequence_two = SwapTickSequence::new(
-        ctx.accounts.tick_array_two_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_two_1.load_mut().ok(),
-        ctx.accounts.tick_array_two_2.load_mut().ok(),
+        load_tick_array_mut(&ctx.accounts.tick_array_two_0)?,
+        load_tick_array_mut(&ctx.accounts.tick_array_two_1).ok(),
+        load_tick_array_mut(&ctx.accounts.tick_array_two_2).ok(),
     );
+    swap_tick_sequence_two.push_supplemental_tick_arrays(
+        &remaining_accounts.supplemental_tick_arrays_two,
+        ctx.accounts.ai_dex_two.key(),
+    )?;
 
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
     // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
+    let sqrt_price_before_one = ai_dex_one_data.sqrt_price;
+    let sqrt_price_before_two = ai_dex_two_data.sqrt_price;
     let (swap_update_one, swap_update_two) = match amount_specified_is_input {
         true => {
             // If the amount specified is input, this means we are doing exact-in
@@ -262,11 +365,11 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             // and the swaps occur from Swap 1 => Swap 2
             let swap_calc_one = swap_with_transfer_fee_extension(
                 &ai_dex_one_data,
-                if a_to_b_one { &ctx.accounts.token_mint_input } else { &ctx.accounts.token_mint_intermediate },
-                if a_to_b_one { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_input },
+                if a_to_b_one { &transfer_fee_input } else { &transfer_fee_intermediate },
+                if a_to_b_one { &transfer_fee_intermediate } else { &transfer_fee_input },
                 &mut swap_tick_sequence_one,
                 amount,
-                u128::from_le_bytes(sqrt_price_limit_one_bytes),
+                sqrt_price_limit_one,
                 true,
                 a_to_b_one,
                 timestamp,
@@ -280,11 +383,11 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             };
             let swap_calc_two = swap_with_transfer_fee_extension(
                 &ai_dex_two_data,
-                if a_to_b_two { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_output },
-                if a_to_b_two { &ctx.accounts.token_mint_output } else { &ctx.accounts.token_mint_intermediate },
+                if a_to_b_two { &transfer_fee_intermediate } else { &transfer_fee_output },
+                if a_to_b_two { &transfer_fee_output } else { &transfer_fee_intermediate },
                 &mut swap_tick_sequence_two,
                 swap_two_input_amount,
-                u128::from_le_bytes(sqrt_price_limit_two_bytes),
+                sqrt_price_limit_two,
                 true,
                 a_to_b_two,
                 timestamp,
@@ -298,11 +401,11 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             // but the actual swaps occur from Swap 1 => Swap 2 (to ensure that the intermediate token exists in the account)
             let swap_calc_two = swap_with_transfer_fee_extension(
                 &ai_dex_two_data,
-                if a_to_b_two { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_output },
-                if a_to_b_two { &ctx.accounts.token_mint_output } else { &ctx.accounts.token_mint_intermediate },
+                if a_to_b_two { &transfer_fee_intermediate } else { &transfer_fee_output },
+                if a_to_b_two { &transfer_fee_output } else { &transfer_fee_intermediate },
                 &mut swap_tick_sequence_two,
                 amount,
-                u128::from_le_bytes(sqrt_price_limit_two_bytes),
+                sqrt_price_limit_two,
                 false,
                 a_to_b_two,
                 timestamp,
@@ -310,23 +413,17 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
             )?;
             // The output of swap 1 is input of swap_calc_two
             let swap_one_output_amount = match a_to_b_two {
-                true => calculate_transfer_fee_excluded_amount(
-                    &ctx.accounts.token_mint_intermediate,
-                    swap_calc_two.amount_a
-                )?.amount,
-                false => calculate_transfer_fee_excluded_amount(
-                    &ctx.accounts.token_mint_intermediate,
-                    swap_calc_two.amount_b
-                )?.amount,
+                true => transfer_fee_intermediate.calculate_transfer_fee_excluded_amount(swap_calc_two.amount_a)?.amount,
+                false => transfer_fee_intermediate.calculate_transfer_fee_excluded_amount(swap_calc_two.amount_b)?.amount,
             };
 
             let swap_calc_one = swap_with_transfer_fee_extension(
                 &ai_dex_one_data,
-                if a_to_b_one { &ctx.accounts.token_mint_input } else { &ctx.accounts.token_mint_intermediate },
-                if a_to_b_one { &ctx.accounts.token_mint_intermediate } else { &ctx.accounts.token_mint_input },
+                if a_to_b_one { &transfer_fee_input } else { &transfer_fee_intermediate },
+                if a_to_b_one { &transfer_fee_intermediate } else { &transfer_fee_input },
                 &mut swap_tick_sequence_one,
                 swap_one_output_amount,
-                u128::from_le_bytes(sqrt_price_limit_one_bytes),
+                sqrt_price_limit_one,
                 false,
                 a_to_b_one,
                 timestamp,
@@ -339,40 +436,96 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
     check_swap_mismatch(&swap_update_one, &swap_update_two, a_to_b_one, a_to_b_two)?;
 
     check_slippage(
-        &ctx,
         &swap_update_one,
         &swap_update_two,
+        &transfer_fee_output,
         amount_specified_is_input,
         other_amount_threshold,
         a_to_b_one,
         a_to_b_two,
     )?;
 
+    if let (Some(max_deviation_bps), Some((oracle_sqrt_price_one, oracle_sqrt_price_two))) =
+        (max_composite_price_deviation_bps, oracle_sqrt_prices)
+    {
+        let input_amount = if a_to_b_one { swap_update_one.amount_a } else { swap_update_one.amount_b };
+        let output_amount = transfer_fee_output.calculate_transfer_fee_excluded_amount(
+            if a_to_b_two { swap_update_two.amount_b } else { swap_update_two.amount_a },
+        )?.amount;
+        check_composite_oracle_price(
+            input_amount,
+            output_amount,
+            oracle_sqrt_price_one,
+            a_to_b_one,
+            oracle_sqrt_price_two,
+            a_to_b_two,
+            max_deviation_bps,
+        )?;
+    }
+
     drop(ai_dex_one_data);
     drop(ai_dex_two_data);
 
+    record_swap_volume(
+        &remaining_accounts.swap_rate_limiters,
+        &ctx.accounts.ai_dex_one,
+        swap_update_one.amount_a,
+        swap_update_one.amount_b,
+        timestamp,
+    )?;
+    record_swap_volume(
+        &remaining_accounts.swap_rate_limiters,
+        &ctx.accounts.ai_dex_two,
+        swap_update_two.amount_a,
+        swap_update_two.amount_b,
+        timestamp,
+    )?;
+    record_swap_fee_analytics(
+        &remaining_accounts.pool_fee_analytics,
+        &ctx.accounts.ai_dex_one,
+        &swap_update_one,
+        a_to_b_one,
+        timestamp,
+    )?;
+    record_swap_fee_analytics(
+        &remaining_accounts.pool_fee_analytics,
+        &ctx.accounts.ai_dex_two,
+        &swap_update_two,
+        a_to_b_two,
+        timestamp,
+    )?;
+
     // If the first hop produced a referral fee:
     if swap_update_one.next_referral_fee > 0 {
-        if let Some(referral_account_one) = &ctx.accounts.swap_referral_one {
-            // For the first hop, the referral tokens are likely from input/intermediate tokens
-            // Adjust if your logic differs
-            transfer_referral_fee(
+        if let Some(referral_account_one) = swap_referral_one {
+            let is_escrowed = escrow_referral_fee(
+                &remaining_accounts.referral_fee_escrows,
                 referral_account_one,
-                ctx.accounts.swap_referral_ata_input.as_ref(),        // Referral ATA for the input token
-                ctx.accounts.swap_referral_ata_intermediate.as_ref(), // Referral ATA for the intermediate token
-                &ctx.accounts.token_mint_input,
-                &ctx.accounts.token_mint_intermediate,
-                &*ctx.accounts.token_vault_one_input,
-                &*ctx.accounts.token_vault_one_intermediate,
-                &ctx.accounts.token_program_input,
-                &ctx.accounts.token_program_intermediate,
-                &ctx.accounts.memo_program,
-                &remaining_accounts.transfer_hook_input,
-                &remaining_accounts.transfer_hook_intermediate,
-                &mut ctx.accounts.ai_dex_one, // AiDexPool reference from the first hop
+                &ctx.accounts.ai_dex_one,
                 swap_update_one.next_referral_fee,
                 a_to_b_one,
             )?;
+            if !is_escrowed {
+                // For the first hop, the referral tokens are likely from input/intermediate tokens
+                // Adjust if your logic differs
+                transfer_referral_fee(
+                    referral_account_one,
+                    ctx.accounts.swap_referral_ata_input.as_ref(),        // Referral ATA for the input token
+                    ctx.accounts.swap_referral_ata_intermediate.as_ref(), // Referral ATA for the intermediate token
+                    &ctx.accounts.token_mint_input,
+                    &ctx.accounts.token_mint_intermediate,
+                    &*ctx.accounts.token_vault_one_input,
+                    &*ctx.accounts.token_vault_one_intermediate,
+                    &ctx.accounts.token_program_input,
+                    &ctx.accounts.token_program_intermediate,
+                    &ctx.accounts.memo_program,
+                    &remaining_accounts.transfer_hook_input,
+                    &remaining_accounts.transfer_hook_intermediate,
+                    &mut ctx.accounts.ai_dex_one, // AiDexPool reference from the first hop
+                    swap_update_one.next_referral_fee,
+                    a_to_b_one,
+                )?;
+            }
         } else {
             return Err(ErrorCode::MissingSwapReferralAccount.into());
         }
@@ -380,30 +533,56 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
 
     // If the second hop produced a referral fee:
     if swap_update_two.next_referral_fee > 0 {
-        if let Some(referral_account_two) = &ctx.accounts.swap_referral_two {
-            // For the second hop, the referral tokens are likely from intermediate/output tokens
-            transfer_referral_fee(
+        if let Some(referral_account_two) = swap_referral_two {
+            let is_escrowed = escrow_referral_fee(
+                &remaining_accounts.referral_fee_escrows,
                 referral_account_two,
-                ctx.accounts.swap_referral_ata_intermediate.as_ref(), // Referral ATA for intermediate token
-                ctx.accounts.swap_referral_ata_output.as_ref(),       // Referral ATA for output token
-                &ctx.accounts.token_mint_intermediate,
-                &ctx.accounts.token_mint_output,
-                &*ctx.accounts.token_vault_two_intermediate,
-                &*ctx.accounts.token_vault_two_output,
-                &ctx.accounts.token_program_intermediate,
-                &ctx.accounts.token_program_output,
-                &ctx.accounts.memo_program,
-                &remaining_accounts.transfer_hook_intermediate,
-                &remaining_accounts.transfer_hook_output,
-                &mut ctx.accounts.ai_dex_two, // AiDexPool reference from the second hop
+                &ctx.accounts.ai_dex_two,
                 swap_update_two.next_referral_fee,
                 a_to_b_two,
             )?;
+            if !is_escrowed {
+                // For the second hop, the referral tokens are likely from intermediate/output tokens
+                transfer_referral_fee(
+                    referral_account_two,
+                    ctx.accounts.swap_referral_ata_intermediate.as_ref(), // Referral ATA for intermediate token
+                    ctx.accounts.swap_referral_ata_output.as_ref(),       // Referral ATA for output token
+                    &ctx.accounts.token_mint_intermediate,
+                    &ctx.accounts.token_mint_output,
+                    &*ctx.accounts.token_vault_two_intermediate,
+                    &*ctx.accounts.token_vault_two_output,
+                    &ctx.accounts.token_program_intermediate,
+                    &ctx.accounts.token_program_output,
+                    &ctx.accounts.memo_program,
+                    &remaining_accounts.transfer_hook_intermediate,
+                    &remaining_accounts.transfer_hook_output,
+                    &mut ctx.accounts.ai_dex_two, // AiDexPool reference from the second hop
+                    swap_update_two.next_referral_fee,
+                    a_to_b_two,
+                )?;
+            }
         } else {
             return Err(ErrorCode::MissingSwapReferralAccount.into());
         }
     }
 
+    let swap_breakdown_event_one = build_swap_breakdown_event(
+        ctx.accounts.ai_dex_one.key(),
+        &swap_update_one,
+        a_to_b_one,
+        &transfer_fee_input,
+        &transfer_fee_intermediate,
+    )?;
+    let mut swap_breakdown_event_two = build_swap_breakdown_event(
+        ctx.accounts.ai_dex_two.key(),
+        &swap_update_two,
+        a_to_b_two,
+        &transfer_fee_intermediate,
+        &transfer_fee_output,
+    )?;
+    // The intermediate token moves vault to vault once, its transfer fee is reported on hop one
+    swap_breakdown_event_two.transfer_fee_in = 0;
+
     update_and_two_hop_swap_ai_dex(
         swap_update_one,
         swap_update_two,
@@ -414,6 +593,9 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.token_mint_input,
         &ctx.accounts.token_mint_intermediate,
         &ctx.accounts.token_mint_output,
+        &transfer_fee_input,
+        &transfer_fee_intermediate,
+        &transfer_fee_output,
         &ctx.accounts.token_program_input,
         &ctx.accounts.token_program_intermediate,
         &ctx.accounts.token_program_output,
@@ -432,7 +614,11 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
+    ctx.accounts.ai_dex_one.load_mut()?.unlock();
+    ctx.accounts.ai_dex_two.load_mut()?.unlock();
+
     emit!(TwoHopSwapEvent {
+        event_version: EVENT_VERSION,
         ai_dex_one: ctx.accounts.ai_dex_one.key(),
         ai_dex_two: ctx.accounts.ai_dex_two.key(),
         amount,
@@ -440,8 +626,8 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         amount_specified_is_input,
         a_to_b_one,
         a_to_b_two,
-        sqrt_price_limit_one: u128::from_le_bytes(sqrt_price_limit_one_bytes),
-        sqrt_price_limit_two: u128::from_le_bytes(sqrt_price_limit_two_bytes),
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
         sqrt_price_one: ctx.accounts.ai_dex_one.load()?.sqrt_price,
         sqrt_price_two: ctx.accounts.ai_dex_two.load()?.sqrt_price,
         current_tick_one: ctx.accounts.ai_dex_one.load()?.tick_current_index,
@@ -460,9 +646,29 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
         tick_array_two_0: ctx.accounts.tick_array_two_0.key(),
         tick_array_two_1: ctx.accounts.tick_array_two_1.key(),
         tick_array_two_2: ctx.accounts.tick_array_two_2.key(),
+        sqrt_price_before_one,
+        sqrt_price_before_two,
+        amount_in: swap_breakdown_event_one.amount_in,
+        amount_out: swap_breakdown_event_two.amount_out,
+        effective_price_x64: get_effective_price_x64(
+            swap_breakdown_event_one.amount_in,
+            swap_breakdown_event_two.amount_out,
+        ),
     });
+    emit!(swap_breakdown_event_one);
+    emit!(swap_breakdown_event_two);
 
-    Ok(())
+    refresh_pool_snapshots(
+        &remaining_accounts.pool_snapshots,
+        &[&ctx.accounts.ai_dex_one, &ctx.accounts.ai_dex_two],
+    )?;
+
+    Ok(TwoHopSwapReturnData {
+        amount_in: swap_breakdown_event_one.amount_in,
+        amount_out: swap_breakdown_event_two.amount_out,
+        next_sqrt_price_one: ctx.accounts.ai_dex_one.load()?.sqrt_price,
+        next_sqrt_price_two: ctx.accounts.ai_dex_two.load()?.sqrt_price,
+    })
 }
 
 //
@@ -471,17 +677,36 @@ pub fn two_hop_swap_handler<'a, 'b, 'c, 'info>(
 // ────────────────────────────────────────────────────────────────────────────────
 //
 
+/// Resolves the swap referral of each hop.
+///
+/// A single `swap_referral` is the referral of both hops, each hop then pays it the referral fee
+/// of that hop in its input token. Otherwise each hop uses its own optional referral account.
+#[inline(never)]
+fn resolve_hop_swap_referrals<'a, 'info>(
+    swap_referral: Option<&'a Account<'info, SwapReferral>>,
+    swap_referral_one: Option<&'a Account<'info, SwapReferral>>,
+    swap_referral_two: Option<&'a Account<'info, SwapReferral>>,
+) -> Result<(Option<&'a Account<'info, SwapReferral>>, Option<&'a Account<'info, SwapReferral>>)> {
+    match swap_referral {
+        Some(_) if swap_referral_one.is_some() || swap_referral_two.is_some() => {
+            Err(ErrorCode::AmbiguousSwapReferral.into())
+        }
+        Some(swap_referral) => Ok((Some(swap_referral), Some(swap_referral))),
+        None => Ok((swap_referral_one, swap_referral_two)),
+    }
+}
+
 #[inline(never)]
 fn compute_referral_fee_rates<'info>(
-    swap_referral_one: &Option<Account<'info, SwapReferral>>,
-    swap_referral_two: &Option<Account<'info, SwapReferral>>,
-    default_swap_referral_reward_fee_rate_config_one: u16,
-    default_swap_referral_reward_fee_rate_config_two: u16,
+    swap_referral_one: Option<&Account<'info, SwapReferral>>,
+    swap_referral_two: Option<&Account<'info, SwapReferral>>,
+    swap_referral_reward_fee_rate_pool_one: u16,
+    swap_referral_reward_fee_rate_pool_two: u16,
 
 ) -> (u16, u16) {
     let (config_referral_fee_rate_one, referral_account_fee_rate_one) = if let Some(referral_account) = swap_referral_one {
         (
-            default_swap_referral_reward_fee_rate_config_one,
+            swap_referral_reward_fee_rate_pool_one,
             referral_account.referral_reward_fee_rate
         )
     } else {
@@ -490,7 +715,7 @@ fn compute_referral_fee_rates<'info>(
 
     let (config_referral_fee_rate_two, referral_account_fee_rate_two) = if let Some(referral_account) = swap_referral_two {
         (
-            default_swap_referral_reward_fee_rate_config_two,
+            swap_referral_reward_fee_rate_pool_two,
             referral_account.referral_reward_fee_rate
         )
     } else {
@@ -504,7 +729,7 @@ fn compute_referral_fee_rates<'info>(
 }
 
 #[inline(never)]
-fn check_swap_mismatch(
+pub(crate) fn check_swap_mismatch(
     swap_update_one: &PostSwapUpdate,
     swap_update_two: &PostSwapUpdate,
     a_to_b_one: bool,
@@ -528,10 +753,10 @@ fn check_swap_mismatch(
 }
 
 #[inline(never)]
-fn check_slippage<'info>(
-    ctx: &Context<TwoHopSwap<'info>>,
+pub(crate) fn check_slippage(
     swap_update_one: &PostSwapUpdate,
     swap_update_two: &PostSwapUpdate,
+    transfer_fee_output: &MintTransferFee,
     amount_specified_is_input: bool,
     other_amount_threshold: u64,
     a_to_b_one: bool,
@@ -540,15 +765,9 @@ fn check_slippage<'info>(
     if amount_specified_is_input {
         let output_amount = {
             if a_to_b_two {
-                calculate_transfer_fee_excluded_amount(
-                    &ctx.accounts.token_mint_output,
-                    swap_update_two.amount_b
-                )?.amount
+                transfer_fee_output.calculate_transfer_fee_excluded_amount(swap_update_two.amount_b)?.amount
             } else {
-                calculate_transfer_fee_excluded_amount(
-                    &ctx.accounts.token_mint_output,
-                    swap_update_two.amount_a
-                )?.amount
+                transfer_fee_output.calculate_transfer_fee_excluded_amount(swap_update_two.amount_a)?.amount
             }
         };
         if output_amount < other_amount_threshold {
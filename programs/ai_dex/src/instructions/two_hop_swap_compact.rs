@@ -0,0 +1,443 @@
+use crate::constants::EVENT_VERSION;
+use crate::instructions::two_hop_swap::{
+    check_slippage, check_swap_mismatch, TwoHopSwapEvent, TwoHopSwapReturnData,
+};
+use crate::util::{
+    check_blocklist, parse_remaining_accounts, record_swap_fee_analytics, record_swap_volume,
+    refresh_pool_snapshots, remaining_accounts_slice, update_and_two_hop_swap_ai_dex, AccountsType,
+    HopAccounts, MintTransferFee, RemainingAccountsInfo,
+};
+use crate::{build_swap_breakdown_event, swap_with_transfer_fee_extension};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    math::get_effective_price_x64,
+    util::{check_deadline, current_timestamp_u64, resolve_sqrt_price_limit, SwapTickSequence},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+/// A two-hop swap taking the accounts of each hop from the remaining accounts.
+pub struct TwoHopSwapCompact<'info> {
+    #[account(mut)]
+    pub token_mint_input: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub token_mint_intermediate: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub token_mint_output: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        constraint = token_program_input.key() == token_mint_input.to_account_info().owner.clone()
+    )]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = token_program_intermediate.key() == token_mint_intermediate.to_account_info().owner.clone()
+    )]
+    pub token_program_intermediate: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = token_program_output.key() == token_mint_output.to_account_info().owner.clone()
+    )]
+    pub token_program_output: Interface<'info, TokenInterface>,
+
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_output.mint == token_mint_output.key())]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_authority: Signer<'info>,
+
+    pub memo_program: Program<'info, Memo>,
+    // Remaining accounts:
+    // - `HopAccountsOne` and `HopAccountsTwo`: the pool, vaults and tick arrays of each hop
+    // - Accounts for transfer hook programs of the input, intermediate and output mints
+}
+
+/// Swaps through two pools, like `two_hop_swap`, with the accounts of each hop passed as a
+/// typed group of the remaining accounts.
+///
+/// Each hop passes only its pool, its two vaults and the tick arrays it crosses, so the
+/// instruction leaves room in the transaction for the accounts of transfer hooks. Oracle pools
+/// and swap referrals need the accounts of `two_hop_swap`.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * A hop accounts slice is missing or invalid.
+/// * Both hops use the same pool, or a pool is an oracle pool.
+/// * The mints do not chain from the input to the output token through both pools.
+/// * The swap fails, or the amounts exceed `other_amount_threshold`.
+pub fn two_hop_swap_compact_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TwoHopSwapCompact<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    deadline_timestamp: Option<u64>,
+    tick_index_limit_one: Option<i32>,
+    tick_index_limit_two: Option<i32>,
+) -> Result<TwoHopSwapReturnData> {
+    let timestamp = current_timestamp_u64(ctx.remaining_accounts)?;
+    check_deadline(timestamp, deadline_timestamp)?;
+
+    let sqrt_price_limit_one =
+        resolve_sqrt_price_limit(sqrt_price_limit_one, tick_index_limit_one, a_to_b_one)?;
+    let sqrt_price_limit_two =
+        resolve_sqrt_price_limit(sqrt_price_limit_two, tick_index_limit_two, a_to_b_two)?;
+
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookInput,
+            AccountsType::TransferHookIntermediate,
+            AccountsType::TransferHookOutput,
+            AccountsType::PoolSnapshots,
+            AccountsType::SwapRateLimiters,
+            AccountsType::Blocklists,
+            AccountsType::PoolFeeAnalytics,
+            AccountsType::HopAccountsOne,
+            AccountsType::HopAccountsTwo,
+        ],
+    )?;
+    let mut hop_one = HopAccounts::load(
+        remaining_accounts_slice(
+            ctx.remaining_accounts,
+            &remaining_accounts_info,
+            AccountsType::HopAccountsOne,
+        ),
+        a_to_b_one,
+    )?;
+    let mut hop_two = HopAccounts::load(
+        remaining_accounts_slice(
+            ctx.remaining_accounts,
+            &remaining_accounts_info,
+            AccountsType::HopAccountsTwo,
+        ),
+        a_to_b_two,
+    )?;
+    if hop_one.ai_dex_pool.key() == hop_two.ai_dex_pool.key() {
+        return Err(ErrorCode::DuplicateTwoHopPoolError.into());
+    }
+
+    // Read the transfer fees once for the quotes, the slippage check, the events and the transfers
+    let transfer_fee_input = MintTransferFee::load(&ctx.accounts.token_mint_input)?;
+    let transfer_fee_intermediate = MintTransferFee::load(&ctx.accounts.token_mint_intermediate)?;
+    let transfer_fee_output = MintTransferFee::load(&ctx.accounts.token_mint_output)?;
+
+    let mut ai_dex_one_data = hop_one.ai_dex_pool.load_mut()?;
+    let mut ai_dex_two_data = hop_two.ai_dex_pool.load_mut()?;
+
+    ai_dex_one_data.lock()?;
+    ai_dex_two_data.lock()?;
+
+    // Oracle pools refresh their price from the oracle accounts of `two_hop_swap`
+    if ai_dex_one_data.is_oracle_pool || ai_dex_two_data.is_oracle_pool {
+        return Err(ErrorCode::MissingOracleAccount.into());
+    }
+    if ctx.accounts.token_mint_input.key() != ai_dex_one_data.input_token_mint(a_to_b_one) {
+        return Err(ErrorCode::InvalidInputTokenMint.into());
+    }
+    if ctx.accounts.token_mint_intermediate.key() != ai_dex_one_data.output_token_mint(a_to_b_one) {
+        return Err(ErrorCode::InvalidIntermediateTokenMint.into());
+    }
+    if ctx.accounts.token_mint_intermediate.key() != ai_dex_two_data.input_token_mint(a_to_b_two) {
+        return Err(ErrorCode::InvalidIntermediaryMintError.into());
+    }
+    if ctx.accounts.token_mint_output.key() != ai_dex_two_data.output_token_mint(a_to_b_two) {
+        return Err(ErrorCode::InvalidOutputTokenMint.into());
+    }
+
+    let swap_addresses = [
+        ctx.accounts.token_authority.key(),
+        ctx.accounts.token_owner_account_input.owner,
+        ctx.accounts.token_owner_account_output.owner,
+    ];
+    check_blocklist(
+        &remaining_accounts.blocklists,
+        &ai_dex_one_data,
+        &swap_addresses,
+    )?;
+    check_blocklist(
+        &remaining_accounts.blocklists,
+        &ai_dex_two_data,
+        &swap_addresses,
+    )?;
+
+    let mut swap_tick_sequence_one = SwapTickSequence::from_tick_arrays(&hop_one.tick_arrays)?;
+    let mut swap_tick_sequence_two = SwapTickSequence::from_tick_arrays(&hop_two.tick_arrays)?;
+
+    let sqrt_price_before_one = ai_dex_one_data.sqrt_price;
+    let sqrt_price_before_two = ai_dex_two_data.sqrt_price;
+    let (swap_update_one, swap_update_two) = match amount_specified_is_input {
+        true => {
+            // Exact-in: quote Swap 1 => Swap 2, the output of swap one is the input of swap two
+            let swap_calc_one = swap_with_transfer_fee_extension(
+                &ai_dex_one_data,
+                if a_to_b_one {
+                    &transfer_fee_input
+                } else {
+                    &transfer_fee_intermediate
+                },
+                if a_to_b_one {
+                    &transfer_fee_intermediate
+                } else {
+                    &transfer_fee_input
+                },
+                &mut swap_tick_sequence_one,
+                amount,
+                sqrt_price_limit_one,
+                true,
+                a_to_b_one,
+                timestamp,
+                0,
+            )?;
+            let swap_two_input_amount = match a_to_b_one {
+                true => swap_calc_one.amount_b,
+                false => swap_calc_one.amount_a,
+            };
+            let swap_calc_two = swap_with_transfer_fee_extension(
+                &ai_dex_two_data,
+                if a_to_b_two {
+                    &transfer_fee_intermediate
+                } else {
+                    &transfer_fee_output
+                },
+                if a_to_b_two {
+                    &transfer_fee_output
+                } else {
+                    &transfer_fee_intermediate
+                },
+                &mut swap_tick_sequence_two,
+                swap_two_input_amount,
+                sqrt_price_limit_two,
+                true,
+                a_to_b_two,
+                timestamp,
+                0,
+            )?;
+            (swap_calc_one, swap_calc_two)
+        }
+        false => {
+            // Exact-out: quote Swap 2 => Swap 1, the swaps still occur from Swap 1 => Swap 2
+            let swap_calc_two = swap_with_transfer_fee_extension(
+                &ai_dex_two_data,
+                if a_to_b_two {
+                    &transfer_fee_intermediate
+                } else {
+                    &transfer_fee_output
+                },
+                if a_to_b_two {
+                    &transfer_fee_output
+                } else {
+                    &transfer_fee_intermediate
+                },
+                &mut swap_tick_sequence_two,
+                amount,
+                sqrt_price_limit_two,
+                false,
+                a_to_b_two,
+                timestamp,
+                0,
+            )?;
+            let swap_one_output_amount = match a_to_b_two {
+                true => {
+                    transfer_fee_intermediate
+                        .calculate_transfer_fee_excluded_amount(swap_calc_two.amount_a)?
+                        .amount
+                }
+                false => {
+                    transfer_fee_intermediate
+                        .calculate_transfer_fee_excluded_amount(swap_calc_two.amount_b)?
+                        .amount
+                }
+            };
+            let swap_calc_one = swap_with_transfer_fee_extension(
+                &ai_dex_one_data,
+                if a_to_b_one {
+                    &transfer_fee_input
+                } else {
+                    &transfer_fee_intermediate
+                },
+                if a_to_b_one {
+                    &transfer_fee_intermediate
+                } else {
+                    &transfer_fee_input
+                },
+                &mut swap_tick_sequence_one,
+                swap_one_output_amount,
+                sqrt_price_limit_one,
+                false,
+                a_to_b_one,
+                timestamp,
+                0,
+            )?;
+            (swap_calc_one, swap_calc_two)
+        }
+    };
+
+    check_swap_mismatch(&swap_update_one, &swap_update_two, a_to_b_one, a_to_b_two)?;
+
+    check_slippage(
+        &swap_update_one,
+        &swap_update_two,
+        &transfer_fee_output,
+        amount_specified_is_input,
+        other_amount_threshold,
+        a_to_b_one,
+        a_to_b_two,
+    )?;
+
+    drop(ai_dex_one_data);
+    drop(ai_dex_two_data);
+
+    record_swap_volume(
+        &remaining_accounts.swap_rate_limiters,
+        &hop_one.ai_dex_pool,
+        swap_update_one.amount_a,
+        swap_update_one.amount_b,
+        timestamp,
+    )?;
+    record_swap_volume(
+        &remaining_accounts.swap_rate_limiters,
+        &hop_two.ai_dex_pool,
+        swap_update_two.amount_a,
+        swap_update_two.amount_b,
+        timestamp,
+    )?;
+    record_swap_fee_analytics(
+        &remaining_accounts.pool_fee_analytics,
+        &hop_one.ai_dex_pool,
+        &swap_update_one,
+        a_to_b_one,
+        timestamp,
+    )?;
+    record_swap_fee_analytics(
+        &remaining_accounts.pool_fee_analytics,
+        &hop_two.ai_dex_pool,
+        &swap_update_two,
+        a_to_b_two,
+        timestamp,
+    )?;
+
+    let swap_breakdown_event_one = build_swap_breakdown_event(
+        hop_one.ai_dex_pool.key(),
+        &swap_update_one,
+        a_to_b_one,
+        &transfer_fee_input,
+        &transfer_fee_intermediate,
+    )?;
+    let mut swap_breakdown_event_two = build_swap_breakdown_event(
+        hop_two.ai_dex_pool.key(),
+        &swap_update_two,
+        a_to_b_two,
+        &transfer_fee_intermediate,
+        &transfer_fee_output,
+    )?;
+    // The intermediate token moves vault to vault once, its transfer fee is reported on hop one
+    swap_breakdown_event_two.transfer_fee_in = 0;
+
+    update_and_two_hop_swap_ai_dex(
+        swap_update_one,
+        swap_update_two,
+        &mut hop_one.ai_dex_pool,
+        &mut hop_two.ai_dex_pool,
+        a_to_b_one,
+        a_to_b_two,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_mint_intermediate,
+        &ctx.accounts.token_mint_output,
+        &transfer_fee_input,
+        &transfer_fee_intermediate,
+        &transfer_fee_output,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.token_program_intermediate,
+        &ctx.accounts.token_program_output,
+        &ctx.accounts.token_owner_account_input,
+        &hop_one.token_vault_input,
+        &hop_one.token_vault_output,
+        &hop_two.token_vault_input,
+        &hop_two.token_vault_output,
+        &ctx.accounts.token_owner_account_output,
+        &remaining_accounts.transfer_hook_input,
+        &remaining_accounts.transfer_hook_intermediate,
+        &remaining_accounts.transfer_hook_output,
+        &ctx.accounts.token_authority,
+        &ctx.accounts.memo_program,
+        timestamp,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    hop_one.ai_dex_pool.load_mut()?.unlock();
+    hop_two.ai_dex_pool.load_mut()?.unlock();
+
+    let ai_dex_one_data = hop_one.ai_dex_pool.load()?;
+    let ai_dex_two_data = hop_two.ai_dex_pool.load()?;
+    let tick_array_key = |hop: &HopAccounts, index: usize| {
+        hop.tick_arrays
+            .get(index)
+            .map(|tick_array| tick_array.key())
+            .unwrap_or_default()
+    };
+    emit!(TwoHopSwapEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_one: hop_one.ai_dex_pool.key(),
+        ai_dex_two: hop_two.ai_dex_pool.key(),
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        sqrt_price_one: ai_dex_one_data.sqrt_price,
+        sqrt_price_two: ai_dex_two_data.sqrt_price,
+        current_tick_one: ai_dex_one_data.tick_current_index,
+        current_tick_two: ai_dex_two_data.tick_current_index,
+        fee_growth_global_a_one: ai_dex_one_data.fee_growth_global_a,
+        fee_growth_global_b_one: ai_dex_one_data.fee_growth_global_b,
+        fee_growth_global_a_two: ai_dex_two_data.fee_growth_global_a,
+        fee_growth_global_b_two: ai_dex_two_data.fee_growth_global_b,
+        timestamp,
+        token_owner_account_input: ctx.accounts.token_owner_account_input.key(),
+        token_owner_account_output: ctx.accounts.token_owner_account_output.key(),
+        token_authority: ctx.accounts.token_authority.key(),
+        tick_array_one_0: tick_array_key(&hop_one, 0),
+        tick_array_one_1: tick_array_key(&hop_one, 1),
+        tick_array_one_2: tick_array_key(&hop_one, 2),
+        tick_array_two_0: tick_array_key(&hop_two, 0),
+        tick_array_two_1: tick_array_key(&hop_two, 1),
+        tick_array_two_2: tick_array_key(&hop_two, 2),
+        sqrt_price_before_one,
+        sqrt_price_before_two,
+        amount_in: swap_breakdown_event_one.amount_in,
+        amount_out: swap_breakdown_event_two.amount_out,
+        effective_price_x64: get_effective_price_x64(
+            swap_breakdown_event_one.amount_in,
+            swap_breakdown_event_two.amount_out,
+        ),
+    });
+    emit!(swap_breakdown_event_one);
+    emit!(swap_breakdown_event_two);
+
+    let return_data = TwoHopSwapReturnData {
+        amount_in: swap_breakdown_event_one.amount_in,
+        amount_out: swap_breakdown_event_two.amount_out,
+        next_sqrt_price_one: ai_dex_one_data.sqrt_price,
+        next_sqrt_price_two: ai_dex_two_data.sqrt_price,
+    };
+    drop(ai_dex_one_data);
+    drop(ai_dex_two_data);
+
+    refresh_pool_snapshots(
+        &remaining_accounts.pool_snapshots,
+        &[&hop_one.ai_dex_pool, &hop_two.ai_dex_pool],
+    )?;
+
+    Ok(return_data)
+}
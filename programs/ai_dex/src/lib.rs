@@ -20,6 +20,7 @@ pub mod util;
 #[doc(hidden)]
 pub mod security;
 
+use crate::state::{ConfigRole, Feature, LbpFeeSchedule};
 use crate::util::RemainingAccountsInfo;
 use instructions::*;
 
@@ -64,6 +65,79 @@ pub mod ai_dex {
         return instructions::update_super_admin::update_super_admin_handler(ctx);
     }
 
+    /// Sets the key allowed to recover the SuperAdmin account after a period of inactivity.
+    ///
+    /// Recovery takes two steps: once the super admin has not signed any admin instruction for
+    /// `recovery_inactivity_timeout` seconds, the recovery authority requests the role, and claims
+    /// it after a fixed delay during which any admin instruction of the super admin cancels it.
+    /// The default pubkey with a zero timeout disables recovery.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context containing all the required accounts.
+    /// * `recovery_authority` - The key allowed to recover the super admin role.
+    /// * `recovery_inactivity_timeout` - The inactivity period in seconds after which recovery is allowed.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the update is successful,
+    /// or an `Err` if an error occurs.
+    pub fn set_super_admin_recovery(
+        ctx: Context<SetSuperAdminRecovery>,
+        recovery_authority: Pubkey,
+        recovery_inactivity_timeout: u64,
+    ) -> Result<()> {
+        return instructions::set_super_admin_recovery::set_super_admin_recovery_handler(
+            ctx,
+            recovery_authority,
+            recovery_inactivity_timeout,
+        );
+    }
+
+    /// Creates the feature gate of the program, with every feature disabled.
+    pub fn initialize_feature_gate(ctx: Context<InitializeFeatureGate>) -> Result<()> {
+        return instructions::initialize_feature_gate::initialize_feature_gate_handler(ctx);
+    }
+
+    /// Enables or disables a feature gated by the feature gate, signed by the super admin.
+    pub fn set_feature_enabled(
+        ctx: Context<SetFeatureEnabled>,
+        feature: Feature,
+        enabled: bool,
+    ) -> Result<()> {
+        return instructions::set_feature_enabled::set_feature_enabled_handler(ctx, feature, enabled);
+    }
+
+    /// Requests the super admin role for the recovery authority once the super admin has been
+    /// inactive for the recovery inactivity timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context containing all the required accounts.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the request is successful,
+    /// or an `Err` if an error occurs.
+    pub fn request_super_admin_recovery(ctx: Context<RequestSuperAdminRecovery>) -> Result<()> {
+        return instructions::request_super_admin_recovery::request_super_admin_recovery_handler(ctx);
+    }
+
+    /// Hands the super admin role to the recovery authority once the claim delay of its request
+    /// has passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context containing all the required accounts.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the claim is successful,
+    /// or an `Err` if an error occurs.
+    pub fn claim_super_admin_recovery(ctx: Context<ClaimSuperAdminRecovery>) -> Result<()> {
+        return instructions::claim_super_admin_recovery::claim_super_admin_recovery_handler(ctx);
+    }
+
     /// Initializes the configuration for the ai dex.
     ///
     /// This function sets up the initial configuration parameters for the protocol,
@@ -94,10 +168,31 @@ pub mod ai_dex {
         );
     }
 
+    /// Creates an independent market instance: a config with its own authority and fee rates,
+    /// and a reinvestments account that only serves the config's pools.
+    pub fn create_market_instance(
+        ctx: Context<CreateMarketInstance>,
+        config_authority: Pubkey,
+        default_protocol_fee_rate: u16,
+        default_referral_reward_fee_rate: u16,
+        reinvestments_authority: Pubkey,
+        default_reinvestment_fee_rate: u16,
+    ) -> Result<()> {
+        return instructions::create_market_instance::create_market_instance_handler(
+            ctx,
+            config_authority,
+            default_protocol_fee_rate,
+            default_referral_reward_fee_rate,
+            reinvestments_authority,
+            default_reinvestment_fee_rate,
+        );
+    }
+
     /// Initializes a new tick array with the given start tick index.
     ///
     /// This function sets up a new tick array starting at the specified tick index.
-    /// It uses the provided context to initialize the tick array.
+    /// It uses the provided context to initialize the tick array. With the optional tick array
+    /// funder record, the funder can later reclaim its rent with `close_tick_array`.
     ///
     /// # Arguments
     ///
@@ -115,6 +210,73 @@ pub mod ai_dex {
         return instructions::initialize_tick_array::initialize_tick_array_handler(ctx, start_tick_index);
     }
 
+    /// Initializes a tick array with the given start tick index, succeeding without changes when
+    /// it already exists.
+    pub fn initialize_tick_array_if_needed(
+        ctx: Context<InitializeTickArrayIfNeeded>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        return instructions::initialize_tick_array_if_needed::initialize_tick_array_if_needed_handler(
+            ctx,
+            start_tick_index,
+        );
+    }
+
+    /// Initializes a compact tick array holding up to `TICK_ARRAY_V2_CAPACITY` initialized ticks.
+    /// With the optional tick array funder record, the funder can later reclaim its rent with
+    /// `close_tick_array`.
+    pub fn initialize_tick_array_v2(
+        ctx: Context<InitializeTickArrayV2>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        return instructions::initialize_tick_array_v2::initialize_tick_array_v2_handler(ctx, start_tick_index);
+    }
+
+    /// Migrates a tick array to the compact layout, refunding the freed rent to the receiver.
+    pub fn migrate_tick_array(ctx: Context<MigrateTickArray>) -> Result<()> {
+        return instructions::migrate_tick_array::migrate_tick_array_handler(ctx);
+    }
+
+    /// Expands a compact tick array back to the full layout, with the funder paying the rent.
+    pub fn expand_tick_array(ctx: Context<ExpandTickArray>) -> Result<()> {
+        return instructions::expand_tick_array::expand_tick_array_handler(ctx);
+    }
+
+    /// Closes an empty tick array away from the current price, refunding its rent to the funder
+    /// recorded when it was initialized.
+    pub fn close_tick_array(ctx: Context<CloseTickArray>) -> Result<()> {
+        return instructions::close_tick_array::close_tick_array_handler(ctx);
+    }
+
+    /// Closes the oracle account of a pool that no longer reads it, refunding its rent to the receiver.
+    pub fn close_oracle_account(ctx: Context<CloseOracleAccount>) -> Result<()> {
+        return instructions::close_oracle_account::close_oracle_account_handler(ctx);
+    }
+
+    /// Closes a swap referral whose token accounts, passed as remaining accounts, are empty,
+    /// refunding its rent to the receiver.
+    pub fn close_swap_referral<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CloseSwapReferral<'info>>,
+    ) -> Result<()> {
+        return instructions::close_swap_referral::close_swap_referral_handler(ctx);
+    }
+
+    /// Transfers a swap referral, with its accrued fees, to a new referrer.
+    pub fn transfer_swap_referral(ctx: Context<TransferSwapReferral>) -> Result<()> {
+        return instructions::transfer_swap_referral::transfer_swap_referral_handler(ctx);
+    }
+
+    /// Upgrades a config, pool or position account to its current layout, with the payer paying the rent.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        return instructions::migrate_account::migrate_account_handler(ctx);
+    }
+
+    /// Grows a config, pool or position account to its current length without changing its version,
+    /// with the payer paying the rent.
+    pub fn extend_account(ctx: Context<ExtendAccount>) -> Result<()> {
+        return instructions::extend_account::extend_account_handler(ctx);
+    }
+
     /// Initializes a new fee tier with the given parameters.
     ///
     /// This function sets up a new fee tier with the specified tick spacing and default fee rate.
@@ -174,6 +336,39 @@ pub mod ai_dex {
         );
     }
 
+    /// Opens a position and deposits its initial liquidity in one instruction, so that the deposit
+    /// cannot be front-run and migrations from other CLMMs land in a single transaction. The
+    /// deposit is bounded by token amounts and optionally by the price of the pool.
+    pub fn open_position_with_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenPositionWithLiquidity<'info>>,
+        position_seed: u64,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        is_reinvestment_on: bool,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<u64>,
+        min_sqrt_price: Option<u128>,
+        max_sqrt_price: Option<u128>,
+    ) -> Result<()> {
+        return instructions::open_position_with_liquidity::open_position_with_liquidity_handler(
+            ctx,
+            position_seed,
+            tick_lower_index,
+            tick_upper_index,
+            is_reinvestment_on,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+            remaining_accounts_info,
+            deadline_timestamp,
+            min_sqrt_price,
+            max_sqrt_price,
+        );
+    }
+
     /// Opens a new position with metadata within the specified tick range.
     /// NFT will be minted to represent the position.
     ///
@@ -242,6 +437,52 @@ pub mod ai_dex {
         return instructions::close_position::close_position_handler(ctx);
     }
 
+    /// Moves liquidity of a position into a new position with the same range.
+    pub fn split_position(
+        ctx: Context<SplitPosition>,
+        position_seed: u64,
+        liquidity: u128,
+    ) -> Result<()> {
+        return instructions::split_position::split_position_handler(ctx, position_seed, liquidity);
+    }
+
+    /// Merges a source position into a position with the same range and closes the source position.
+    pub fn merge_positions(ctx: Context<MergePositions>) -> Result<()> {
+        return instructions::merge_positions::merge_positions_handler(ctx);
+    }
+
+    /// Closes an abandoned dust position in the ai dex pool.
+    ///
+    /// This function is permissionless. It closes a position whose liquidity is below the config
+    /// threshold, has nothing owed, and has been inactive for the config inactivity period.
+    /// The principal of the remaining liquidity is transferred to the position owner, and the rent
+    /// of the position account is split between the caller and the position owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `LiquidateDustPosition` instruction.
+    /// * `remaining_accounts_info` - Optional information about remaining accounts.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the position is successfully liquidated,
+    /// or an error if it fails.
+    pub fn liquidate_dust_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, LiquidateDustPosition<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::liquidate_dust_position::liquidate_dust_position_handler(ctx, remaining_accounts_info);
+    }
+
+    /// Removes all the liquidity of a position of a temporary pool past the end of its liquidity
+    /// provision window, owing the withdrawn tokens to the position.
+    ///
+    /// This instruction is permissionless. The owner receives the settled tokens with
+    /// `collect_fees`.
+    pub fn settle_expired_position(ctx: Context<SettleExpiredPosition>) -> Result<()> {
+        return instructions::settle_expired_position::settle_expired_position_handler(ctx);
+    }
+
     /// Sets the default fee rate for the fee tier.
     ///
     /// It uses the provided context (fee authority) and fee rate to update the default fee rate.
@@ -322,6 +563,45 @@ pub mod ai_dex {
         return instructions::set_protocol_fee_rate::set_protocol_fee_rate_handler(ctx, protocol_fee_rate);
     }
 
+    /// Sets distinct protocol fee rates for the fees in token A and in token B of a pool.
+    pub fn set_protocol_fee_rates_per_side(
+        ctx: Context<SetProtocolFeeRatesPerSide>,
+        protocol_fee_rate_a: u16,
+        protocol_fee_rate_b: u16,
+    ) -> Result<()> {
+        return instructions::set_protocol_fee_rates_per_side::set_protocol_fee_rates_per_side_handler(
+            ctx,
+            protocol_fee_rate_a,
+            protocol_fee_rate_b,
+        );
+    }
+
+    /// Sets the maximum swap size of a pool, in basis points of its in-range liquidity, or 0 to
+    /// not limit swaps.
+    pub fn set_max_swap_liquidity_bps(
+        ctx: Context<SetMaxSwapLiquidityBps>,
+        max_swap_liquidity_bps: u16,
+    ) -> Result<()> {
+        return instructions::set_max_swap_liquidity_bps::set_max_swap_liquidity_bps_handler(
+            ctx,
+            max_swap_liquidity_bps,
+        );
+    }
+
+    /// Sets the amounts below which the liquidity withdrawals of a pool are owed to the position
+    /// rather than transferred, or 0 to transfer any amount.
+    pub fn set_dust_transfer_thresholds(
+        ctx: Context<SetDustTransferThresholds>,
+        dust_transfer_threshold_a: u32,
+        dust_transfer_threshold_b: u32,
+    ) -> Result<()> {
+        return instructions::set_dust_transfer_thresholds::set_dust_transfer_thresholds_handler(
+            ctx,
+            dust_transfer_threshold_a,
+            dust_transfer_threshold_b,
+        );
+    }
+
     /// Sets the fee authority for an ai dex config.
     /// The fee authority can set the fee and protocol fee rate for individual pools or
     /// set the default fee rate for newly minted pools.
@@ -466,6 +746,31 @@ pub mod ai_dex {
         );
     }
 
+    /// Opens several trade batch positions with contiguous indices in the same pool.
+    ///
+    /// The trade batch position accounts are passed as remaining accounts, one per tick range.
+    /// Each position will start off with 0 liquidity.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `OpenTradeBatchPositionsMulti` instruction.
+    /// * `start_trade_batch_index` - The index of the first trade batch position, represented as a `u16`.
+    /// * `tick_ranges` - The tick range of each position to open.
+    /// * `is_reinvestment_on` - Whether reinvestment is enabled on the opened positions.
+    pub fn open_trade_batch_positions_multi<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, OpenTradeBatchPositionsMulti<'info>>,
+        start_trade_batch_index: u16,
+        tick_ranges: Vec<TradeBatchPositionRange>,
+        is_reinvestment_on: bool,
+    ) -> Result<()> {
+        return instructions::open_trade_batch_positions_multi::open_trade_batch_positions_multi_handler(
+            ctx,
+            start_trade_batch_index,
+            tick_ranges,
+            is_reinvestment_on,
+        );
+    }
+
     /// Closes a trade batch position in the ai dex pool.
     ///
     /// This function closes a trade batch position using the provided context and trade batch index.
@@ -490,12 +795,15 @@ pub mod ai_dex {
     /// Collects fees of the protocol.
     ///
     /// This function collects fees using the provided context and optional remaining accounts information.
-    /// It handles the fee collection process of the protocol.
+    /// It handles the fee collection process of the protocol. Missing associated token accounts of
+    /// the position authority are created when the optional programs are provided.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The context for the `CollectFees` instruction.
     /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `to_owned_token_accounts` - Whether the fees can be collected to any token accounts owned by the
+    ///   position authority instead of its associated token accounts.
     ///
     /// # Returns
     ///
@@ -504,8 +812,13 @@ pub mod ai_dex {
     pub fn collect_fees<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CollectFees<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
+        to_owned_token_accounts: Option<bool>,
     ) -> Result<()> {
-        return instructions::collect_fees::collect_fees_handler(ctx, remaining_accounts_info);
+        return instructions::collect_fees::collect_fees_handler(
+            ctx,
+            remaining_accounts_info,
+            to_owned_token_accounts,
+        );
     }
 
     /// Collects protocol fees for ai dex of the protocol.
@@ -517,6 +830,8 @@ pub mod ai_dex {
     ///
     /// * `ctx` - The context for the `CollectProtocolFees` instruction.
     /// * `remaining_accounts_info` - Optional information about remaining accounts, represented as `Option<RemainingAccountsInfo>`.
+    /// * `amount_a` - Optional maximum amount of token A to collect, all of it when not set.
+    /// * `amount_b` - Optional maximum amount of token B to collect, all of it when not set.
     ///
     /// # Returns
     ///
@@ -525,14 +840,61 @@ pub mod ai_dex {
     pub fn collect_protocol_fees<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFees<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
+        amount_a: Option<u64>,
+        amount_b: Option<u64>,
+    ) -> Result<()> {
+        return instructions::collect_protocol_fees::collect_protocol_fees_handler(
+            ctx,
+            remaining_accounts_info,
+            amount_a,
+            amount_b,
+        );
+    }
+
+    /// Collects the reinvestment fees owed by a pool to a reinvestments account, signed by its
+    /// reinvestments authority.
+    pub fn collect_reinvestment_fees<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectReinvestmentFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::collect_reinvestment_fees::collect_reinvestment_fees_handler(
+            ctx,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Claims the protocol fees a pool owes to the staking rewards of its config, signed by the
+    /// staking authority. The claimed amounts are set as the return data of the instruction.
+    pub fn claim_staking_rewards<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClaimStakingRewards<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<ClaimStakingRewardsReturnData> {
+        return instructions::claim_staking_rewards::claim_staking_rewards_handler(
+            ctx,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Routes a share of the protocol fees owed into the vault of a reward with the same mint.
+    pub fn fund_rewards_from_protocol_fees<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FundRewardsFromProtocolFees<'info>>,
+        reward_index: u8,
+        share_bps: u16,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
-        return instructions::collect_protocol_fees::collect_protocol_fees_handler(ctx, remaining_accounts_info);
+        return instructions::fund_rewards_from_protocol_fees::fund_rewards_from_protocol_fees_handler(
+            ctx,
+            reward_index,
+            share_bps,
+            remaining_accounts_info,
+        );
     }
 
     /// Collects rewards for the position.
     ///
     /// This function collects rewards using the provided context, reward index, and optional remaining accounts information.
-    /// It handles the reward collection process of the protocol.
+    /// It handles the reward collection process of the protocol. A missing associated token account
+    /// of the position authority is created when the optional programs are provided.
     ///
     /// # Arguments
     ///
@@ -552,6 +914,20 @@ pub mod ai_dex {
         return instructions::collect_reward::collect_reward_handler(ctx, reward_index, remaining_accounts_info);
     }
 
+    /// Delegates the collection of the rewards of a position to a delegate, collecting to the
+    /// token accounts of a recipient.
+    pub fn set_reward_delegate(
+        ctx: Context<SetRewardDelegate>,
+        reward_delegate: Pubkey,
+        reward_recipient: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_reward_delegate::set_reward_delegate_handler(
+            ctx,
+            reward_delegate,
+            reward_recipient,
+        );
+    }
+
     /// Decreases the liquidity for a position in the ai dex pool with additional account information.
     ///
     /// This function reduces the liquidity for the specified position, ensuring that the minimum
@@ -565,6 +941,11 @@ pub mod ai_dex {
     /// * `token_min_a` - The minimum amount of token A to be received, represented as a `u64`.
     /// * `token_min_b` - The minimum amount of token B to be received, represented as a `u64`.
     /// * `remaining_accounts_info` - Optional additional account information for the operation.
+    /// * `referral_code` - Optional referral code to attribute the operation to.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the instruction reverts.
+    /// * `unwrap_sol` - Optional flag to close the position authority's wrapped SOL account into
+    ///   native lamports after the withdrawal.
+    /// * `emit_tick_updates` - Optional flag, `false` skips the `UpdateTicksEvent`.
     ///
     /// # Returns
     ///
@@ -577,6 +958,9 @@ pub mod ai_dex {
         token_min_b: u64,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
         referral_code: Option<String>,
+        deadline_timestamp: Option<u64>,
+        unwrap_sol: Option<bool>,
+        emit_tick_updates: Option<bool>,
     ) -> Result<()> {
         return instructions::decrease_liquidity::decrease_liquidity_handler(
             ctx,
@@ -585,6 +969,56 @@ pub mod ai_dex {
             token_min_b,
             remaining_accounts_info,
             referral_code,
+            deadline_timestamp,
+            unwrap_sol,
+            emit_tick_updates,
+        );
+    }
+
+    /// Decreases the liquidity of a position by a share of its current liquidity.
+    ///
+    /// The liquidity withdrawn is computed on-chain from the position, so that a client does not
+    /// have to read the liquidity of the position beforehand, which may change before execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required for modifying liquidity.
+    /// * `liquidity_bps` - The share of the liquidity of the position to withdraw, in basis points.
+    ///   10,000 withdraws all the liquidity.
+    /// * `token_min_a` - The minimum amount of token A to be received.
+    /// * `token_min_b` - The minimum amount of token B to be received.
+    /// * `remaining_accounts_info` - Optional additional account information for the operation.
+    /// * `referral_code` - Optional referral code to attribute the operation to.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the instruction reverts.
+    /// * `unwrap_sol` - Optional flag to close the position authority's wrapped SOL account into
+    ///   native lamports after the withdrawal.
+    /// * `emit_tick_updates` - Optional flag, `false` skips the `UpdateTicksEvent`.
+    ///
+    /// # Returns
+    ///
+    /// This function returns a `Result` which is `Ok` if the liquidity decrease is successful,
+    /// or an error if it fails.
+    pub fn decrease_liquidity_by_bps<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidity<'info>>,
+        liquidity_bps: u16,
+        token_min_a: u64,
+        token_min_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        referral_code: Option<String>,
+        deadline_timestamp: Option<u64>,
+        unwrap_sol: Option<bool>,
+        emit_tick_updates: Option<bool>,
+    ) -> Result<()> {
+        return instructions::decrease_liquidity_by_bps::decrease_liquidity_by_bps_handler(
+            ctx,
+            liquidity_bps,
+            token_min_a,
+            token_min_b,
+            remaining_accounts_info,
+            referral_code,
+            deadline_timestamp,
+            unwrap_sol,
+            emit_tick_updates,
         );
     }
 
@@ -600,6 +1034,10 @@ pub mod ai_dex {
     /// * `token_max_a` - The maximum amount of token A to use, represented as a `u64`.
     /// * `token_max_b` - The maximum amount of token B to use, represented as a `u64`.
     /// * `remaining_accounts_info` - Optional additional account information.
+    /// * `referral_code` - Optional referral code to attribute the operation to.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the instruction reverts.
+    /// * `emit_tick_updates` - Optional flag, `false` skips the `UpdateTicksEvent` so that the logs
+    ///   of the transaction stay within limits, for instance for Token-2022 pools with memos.
     ///
     /// # Returns
     ///
@@ -612,6 +1050,8 @@ pub mod ai_dex {
         token_max_b: u64,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
         referral_code: Option<String>,
+        deadline_timestamp: Option<u64>,
+        emit_tick_updates: Option<bool>,
     ) -> Result<()> {
         return instructions::increase_liquidity::increase_liquidity_handler(
             ctx,
@@ -620,9 +1060,21 @@ pub mod ai_dex {
             token_max_b,
             remaining_accounts_info,
             referral_code,
+            deadline_timestamp,
+            emit_tick_updates,
         );
     }
 
+    /// Donates tokens to the in-range liquidity providers of a pool through the fee growths.
+    pub fn donate<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Donate<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::donate::donate_handler(ctx, amount_a, amount_b, remaining_accounts_info);
+    }
+
     /// The `initialize_pool_step_1` function is the first step in initializing a pool in the AiDex protocol.
     ///
     /// # Parameters
@@ -631,8 +1083,11 @@ pub mod ai_dex {
     /// - `is_oracle_pool`: A boolean flag indicating if the pool is an oracle pool.
     /// - `is_temporary_pool`: A boolean flag indicating if the pool is a temporary pool.
     /// - `initial_sqrt_price`: The initial square root price of the pool. Optional, since if oracle, the price feed will be used.
-    /// - `price_feed_id`: The price feed id for the oracle pool. Optional, since if classic, it will be ignored.
-    /// - `maximum_age`: The maximum age of the oracle price feed. Optional, since if classic, it will be ignored.
+    /// - `price_feed_id`: The price feed id for the oracle pool. Optional, since if classic, it will be ignored, and oracle pools
+    ///   fall back to the default price feed of the pair.
+    /// - `maximum_age`: The maximum age of the oracle price feed. Optional, since if classic, it will be ignored, and oracle pools fall back to the config default.
+    /// - `is_full_range_only`: Whether positions must span the full tick range at any tick spacing, pricing the pool like a
+    ///   constant-product pool. Optional, defaults to false.
     ///
     /// # Returns
     /// - `Result<()>`: Returns an empty result on success, or an error if the initialization fails.
@@ -649,6 +1104,7 @@ pub mod ai_dex {
         initial_sqrt_price: Option<u128>,
         price_feed_id: Option<String>,
         maximum_age: Option<u64>,
+        is_full_range_only: Option<bool>,
     ) -> Result<()> {
         return instructions::initialize_pool::initialize_pool_step_1_handler(
             ctx,
@@ -658,6 +1114,7 @@ pub mod ai_dex {
             initial_sqrt_price,
             price_feed_id,
             maximum_age,
+            is_full_range_only,
         );
     }
 
@@ -670,6 +1127,8 @@ pub mod ai_dex {
     /// - `end_timestamp_lp`: The end timestamp for liquidity provision. Optional.
     /// - `start_timestamp_swap`: The start timestamp for swapping. Optional.
     /// - `end_timestamp_swap`: The end timestamp for swapping. Optional.
+    /// - `lbp_fee_schedule`: The liquidity bootstrapping fee schedule of a temporary pool, moving the fee rate
+    ///   from a start rate to an end rate once swaps open. Optional.
     ///
     /// # Returns
     /// - `Result<()>`: Returns an empty result on success, or an error if the initialization fails.
@@ -684,6 +1143,7 @@ pub mod ai_dex {
         end_timestamp_lp: Option<u64>,
         start_timestamp_swap: Option<u64>,
         end_timestamp_swap: Option<u64>,
+        lbp_fee_schedule: Option<LbpFeeSchedule>,
     ) -> Result<()> {
         return instructions::initialize_pool::initialize_pool_step_2_handler(
             ctx,
@@ -692,6 +1152,44 @@ pub mod ai_dex {
             end_timestamp_lp,
             start_timestamp_swap,
             end_timestamp_swap,
+            lbp_fee_schedule,
+        );
+    }
+
+    /// Performs the second step of pool initialization and deposits the initial liquidity of the
+    /// creator in the same instruction, so that the pool has tradable liquidity as soon as it is
+    /// usable. The liquidity is the most the token amounts provide in the range at the initial price.
+    pub fn initialize_pool_with_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, InitializePoolWithLiquidity<'info>>,
+        tick_spacing: u16,
+        start_timestamp_lp: Option<u64>,
+        end_timestamp_lp: Option<u64>,
+        start_timestamp_swap: Option<u64>,
+        end_timestamp_swap: Option<u64>,
+        lbp_fee_schedule: Option<LbpFeeSchedule>,
+        position_seed: u64,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        is_reinvestment_on: bool,
+        token_amount_a: u64,
+        token_amount_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::initialize_pool::initialize_pool_with_liquidity_handler(
+            ctx,
+            tick_spacing,
+            start_timestamp_lp,
+            end_timestamp_lp,
+            start_timestamp_swap,
+            end_timestamp_swap,
+            lbp_fee_schedule,
+            position_seed,
+            tick_lower_index,
+            tick_upper_index,
+            is_reinvestment_on,
+            token_amount_a,
+            token_amount_b,
+            remaining_accounts_info,
         );
     }
 
@@ -718,13 +1216,16 @@ pub mod ai_dex {
     /// Sets the reward emissions rate for a specific reward in the ai dex pool (version 2).
     ///
     /// This function updates the emissions rate for the specified reward index in the pool.
-    /// It uses the provided context to set the emissions rate.
+    /// It uses the provided context to set the emissions rate. Rates further from the baseline of
+    /// the reward than its threshold are co-signed by the reward manager of the config.
     ///
     /// # Arguments
     ///
     /// * `ctx` - The context for the `SetRewardEmissions` instruction.
     /// * `reward_index` - The index of the reward to update, represented as a `u8`.
     /// * `emissions_per_second_x64` - The emissions rate per second for the reward, represented as a `u128`.
+    /// * `min_funded_duration` - Optional number of seconds the reward vault must fund the new emissions
+    ///   for, on top of the day always required.
     ///
     /// # Returns
     ///
@@ -734,41 +1235,113 @@ pub mod ai_dex {
         ctx: Context<SetRewardEmissions>,
         reward_index: u8,
         emissions_per_second_x64: u128,
+        min_funded_duration: Option<u64>,
     ) -> Result<()> {
         return instructions::set_reward_emissions::set_reward_emissions_handler(
             ctx,
             reward_index,
             emissions_per_second_x64,
+            min_funded_duration,
         );
     }
 
-    /// Executes a swap operation in the AI DEX protocol.
-    ///
-    /// This function performs a swap operation with the specified parameters. It uses the provided context
-    /// and additional parameters to execute the swap.
-    ///
-    /// # Arguments
-    ///
-    /// * `ctx` - The context for the `Swap` instruction.
-    /// * `amount` - The amount to be swapped, represented as a `u64`.
-    /// * `other_amount_threshold` - The threshold for the other amount in the swap, represented as a `u64`.
-    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`.
-    /// * `amount_specified_is_input` - A boolean indicating whether the specified amount is the input amount.
-    /// * `a_to_b` - A boolean indicating the direction of the swap (true for A to B, false for B to A).
-    /// * `remaining_accounts_info` - Optional remaining accounts information for the swap.
-    ///
-    /// # Returns
-    ///
-    /// This function returns a `Result` which is `Ok` if the swap is successful, or an error if it fails.
-    pub fn swap<'a, 'b, 'c, 'info>(
-        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
+    /// Sets how far from its current emissions the reward authority can set the emissions of a
+    /// reward alone, by the reward manager of the config. Further rates are co-signed by the
+    /// reward manager.
+    pub fn set_reward_emissions_threshold(
+        ctx: Context<SetRewardEmissionsThreshold>,
+        reward_index: u8,
+        threshold_x64: u128,
+    ) -> Result<()> {
+        return instructions::set_reward_emissions_threshold::set_reward_emissions_threshold_handler(
+            ctx,
+            reward_index,
+            threshold_x64,
+        );
+    }
+
+    /// Pauses the emissions of a reward, keeping its emissions rate for when it resumes.
+    pub fn pause_reward(ctx: Context<PauseReward>, reward_index: u8) -> Result<()> {
+        return instructions::pause_reward::pause_reward_handler(ctx, reward_index);
+    }
+
+    /// Resumes the emissions of a paused reward at its emissions rate.
+    pub fn resume_reward(ctx: Context<ResumeReward>, reward_index: u8) -> Result<()> {
+        return instructions::resume_reward::resume_reward_handler(ctx, reward_index);
+    }
+
+    /// Sets the extra weight, in basis points, of full-range positions in the reward emissions.
+    pub fn set_reward_full_range_boost(
+        ctx: Context<SetRewardFullRangeBoost>,
+        reward_index: u8,
+        boost_bps: u16,
+    ) -> Result<()> {
+        return instructions::set_reward_full_range_boost::set_reward_full_range_boost_handler(
+            ctx,
+            reward_index,
+            boost_bps,
+        );
+    }
+
+    /// Sets the share of the reward emissions escrowed for positions that went out of range.
+    pub fn set_reward_out_of_range_grace(
+        ctx: Context<SetRewardOutOfRangeGrace>,
+        reward_index: u8,
+        grace_bps: u16,
+        grace_period: u64,
+    ) -> Result<()> {
+        return instructions::set_reward_out_of_range_grace::set_reward_out_of_range_grace_handler(
+            ctx,
+            reward_index,
+            grace_bps,
+            grace_period,
+        );
+    }
+
+    /// Executes a swap operation in the AI DEX protocol.
+    ///
+    /// This function performs a swap operation with the specified parameters. It uses the provided context
+    /// and additional parameters to execute the swap.
+    ///
+    /// Integrators can pass a callback program and its accounts in the `SwapCallback` remaining
+    /// accounts slice. It is invoked with the `SwapCallbackData` of the swap once it is complete.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `Swap` instruction.
+    /// * `amount` - The amount to be swapped, represented as a `u64`.
+    /// * `other_amount_threshold` - The threshold for the other amount in the swap, represented as a `u64`.
+    /// * `sqrt_price_limit` - The square root price limit for the swap, represented as a `u128`.
+    /// * `amount_specified_is_input` - A boolean indicating whether the specified amount is the input amount.
+    /// * `a_to_b` - A boolean indicating the direction of the swap (true for A to B, false for B to A).
+    /// * `remaining_accounts_info` - Optional remaining accounts information for the swap.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the swap reverts.
+    /// * `tick_index_limit` - Optional tick index limit for the swap, used instead of `sqrt_price_limit` when set.
+    /// * `integrator_fee_rate` - Optional integrator fee rate taken from the output amount and paid
+    ///   from the output vault. An exact output is grossed up so the owner still receives `amount`.
+    /// * `stop_at_tick_array_end` - Optional flag to stop the swap at the end of the provided tick arrays
+    ///   instead of failing. A truncated swap sets `tick_arrays_exhausted` in its `SwapExecutedEvent`.
+    /// * `oracle_maximum_age` - Optional maximum age in seconds of the oracle price of an oracle pool,
+    ///   applied when stricter than the maximum age of the pool's oracle.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `SwapReturnData` of the swap, also set as the return data of the
+    /// instruction for calling programs, or an error if it fails.
+    pub fn swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, Swap<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         sqrt_price_limit: u128,
         amount_specified_is_input: bool,
         a_to_b: bool,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
+        deadline_timestamp: Option<u64>,
+        tick_index_limit: Option<i32>,
+        integrator_fee_rate: Option<u16>,
+        stop_at_tick_array_end: Option<bool>,
+        oracle_maximum_age: Option<u64>,
+    ) -> Result<SwapReturnData> {
         return instructions::swap::swap_handler(
             ctx,
             amount,
@@ -777,15 +1350,121 @@ pub mod ai_dex {
             amount_specified_is_input,
             a_to_b,
             remaining_accounts_info,
+            deadline_timestamp,
+            tick_index_limit,
+            integrator_fee_rate,
+            stop_at_tick_array_end,
+            oracle_maximum_age,
+        );
+    }
+
+    /// Quotes a swap without executing it, for routers to price the pool in a simulation or a CPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `QuoteSwap` instruction.
+    /// * `amount` - The amount of the swap.
+    /// * `sqrt_price_limit` - The square root price limit for the swap.
+    /// * `amount_specified_is_input` - A boolean indicating if the amount specified is the input amount.
+    /// * `a_to_b` - A boolean indicating the direction of the swap (true for A to B, false for B to A).
+    /// * `tick_index_limit` - Optional tick index limit for the swap, used instead of `sqrt_price_limit` when set.
+    /// * `constant_product` - Optional flag to price the swap against the current liquidity only,
+    ///   stopping at the first initialized tick. Routers pass the tick array containing the current
+    ///   tick alone for pools concentrated in it; a quote with an `amount_remaining` needs the full quote.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `SwapQuoteReturnData` of the swap, also set as the return data of
+    /// the instruction.
+    pub fn quote_swap(
+        ctx: Context<QuoteSwap>,
+        amount: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        tick_index_limit: Option<i32>,
+        constant_product: Option<bool>,
+    ) -> Result<SwapQuoteReturnData> {
+        return instructions::quote_swap::quote_swap_handler(
+            ctx,
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            tick_index_limit,
+            constant_product,
+        );
+    }
+
+    /// Executes a swap with a trimmed account list for CPI callers, without oracle or referral support.
+    pub fn swap_v2<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<u64>,
+    ) -> Result<SwapReturnData> {
+        return instructions::swap_v2::swap_v2_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            remaining_accounts_info,
+            deadline_timestamp,
         );
     }
 
+    /// Commits to the hash of a swap to be executed by `reveal_swap` in a later slot.
+    pub fn commit_swap(ctx: Context<CommitSwap>, commitment_hash: [u8; 32]) -> Result<()> {
+        return instructions::commit_swap::commit_swap_handler(ctx, commitment_hash);
+    }
+
+    /// Executes a previously committed swap after verifying its parameters against the commitment.
+    pub fn reveal_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RevealSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        salt: [u8; 32],
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<SwapReturnData> {
+        return instructions::reveal_swap::reveal_swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            salt,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Closes an unrevealed swap commitment and refunds its rent.
+    pub fn cancel_swap_commitment(ctx: Context<CancelSwapCommitment>) -> Result<()> {
+        return instructions::cancel_swap_commitment::cancel_swap_commitment_handler(ctx);
+    }
+
     /// Executes a two-hop swap with the given parameters.
     ///
     /// This function performs a two-hop swap operation, which involves swapping tokens
     /// through two different pools. It uses the provided context and parameters to execute
     /// the swap.
     ///
+    /// A single `swap_referral` account can be the referral of both hops, in place of
+    /// `swap_referral_one` and `swap_referral_two`.
+    ///
+    /// The `OracleUpdates` remaining accounts slice can pass the price update of the second hop
+    /// when it differs from `price_update`, and the `SupplementalTickArraysOne` and
+    /// `SupplementalTickArraysTwo` slices extra tick arrays for each hop.
+    ///
     /// # Arguments
     ///
     /// * `ctx` - The context for the `TwoHopSwap` instruction.
@@ -794,14 +1473,21 @@ pub mod ai_dex {
     /// * `amount_specified_is_input` - A boolean indicating if the specified amount is the input amount.
     /// * `a_to_b_one` - A boolean indicating the direction of the first swap (A to B).
     /// * `a_to_b_two` - A boolean indicating the direction of the second swap (A to B).
-    /// * `sqrt_price_limit_one` - The square root price limit for the first swap.
-    /// * `sqrt_price_limit_two` - The square root price limit for the second swap.
+    /// * `sqrt_price_limit_one` - The square root price limit for the first swap, as little-endian bytes.
+    /// * `sqrt_price_limit_two` - The square root price limit for the second swap, as little-endian bytes.
     /// * `remaining_accounts_info` - Optional remaining accounts information.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the swap reverts.
+    /// * `tick_index_limit_one` - Optional tick index limit for the first swap, used instead of `sqrt_price_limit_one` when set.
+    /// * `tick_index_limit_two` - Optional tick index limit for the second swap, used instead of `sqrt_price_limit_two` when set.
+    /// * `max_composite_price_deviation_bps` - Optional maximum deviation, in basis points, of the rate of the swap
+    ///   from the rate implied by the oracle prices of both pools. Requires both pools to be oracle pools.
+    /// * `oracle_maximum_age` - Optional maximum age in seconds of the oracle prices of oracle pools,
+    ///   applied to each pool when stricter than the maximum age of its oracle.
     ///
     /// # Returns
     ///
-    /// This function returns a `Result` which is `Ok` if the swap is successful,
-    /// or an error if it fails.
+    /// This function returns the `TwoHopSwapReturnData` of the swap, also set as the return data
+    /// of the instruction for calling programs, or an error if it fails.
     pub fn two_hop_swap<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, TwoHopSwap<'info>>,
         amount: u64,
@@ -812,7 +1498,53 @@ pub mod ai_dex {
         sqrt_price_limit_one: [u8; 16],
         sqrt_price_limit_two: [u8; 16],
         remaining_accounts_info: Option<RemainingAccountsInfo>,
-    ) -> Result<()> {
+        deadline_timestamp: Option<u64>,
+        tick_index_limit_one: Option<i32>,
+        tick_index_limit_two: Option<i32>,
+        max_composite_price_deviation_bps: Option<u16>,
+        oracle_maximum_age: Option<u64>,
+    ) -> Result<TwoHopSwapReturnData> {
+        return instructions::two_hop_swap::two_hop_swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            u128::from_le_bytes(sqrt_price_limit_one),
+            u128::from_le_bytes(sqrt_price_limit_two),
+            remaining_accounts_info,
+            deadline_timestamp,
+            tick_index_limit_one,
+            tick_index_limit_two,
+            max_composite_price_deviation_bps,
+            oracle_maximum_age,
+        );
+    }
+
+    /// Executes a two-hop swap like `two_hop_swap`, taking the square root price limits as `u128`
+    /// like the other swap instructions instead of little-endian bytes.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `TwoHopSwapReturnData` of the swap, also set as the return data
+    /// of the instruction for calling programs, or an error if it fails.
+    pub fn two_hop_swap_v2<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, TwoHopSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<u64>,
+        tick_index_limit_one: Option<i32>,
+        tick_index_limit_two: Option<i32>,
+        max_composite_price_deviation_bps: Option<u16>,
+        oracle_maximum_age: Option<u64>,
+    ) -> Result<TwoHopSwapReturnData> {
         return instructions::two_hop_swap::two_hop_swap_handler(
             ctx,
             amount,
@@ -823,6 +1555,66 @@ pub mod ai_dex {
             sqrt_price_limit_one,
             sqrt_price_limit_two,
             remaining_accounts_info,
+            deadline_timestamp,
+            tick_index_limit_one,
+            tick_index_limit_two,
+            max_composite_price_deviation_bps,
+            oracle_maximum_age,
+        );
+    }
+
+    /// Swaps through two pools, taking the pool, vaults and tick arrays of each hop from the
+    /// `HopAccountsOne` and `HopAccountsTwo` remaining accounts slices.
+    ///
+    /// Each hop passes only the tick arrays it crosses, leaving room in the transaction for the
+    /// accounts of transfer hooks. Oracle pools and swap referrals are not supported, they go
+    /// through `two_hop_swap`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for the `TwoHopSwapCompact` instruction.
+    /// * `amount` - The amount of tokens to swap.
+    /// * `other_amount_threshold` - The threshold for the other amount in the swap.
+    /// * `amount_specified_is_input` - A boolean indicating if the specified amount is the input amount.
+    /// * `a_to_b_one` - A boolean indicating the direction of the first swap (A to B).
+    /// * `a_to_b_two` - A boolean indicating the direction of the second swap (A to B).
+    /// * `sqrt_price_limit_one` - The square root price limit for the first swap.
+    /// * `sqrt_price_limit_two` - The square root price limit for the second swap.
+    /// * `remaining_accounts_info` - Remaining accounts information, with the slices of both hops.
+    /// * `deadline_timestamp` - Optional unix timestamp after which the swap reverts.
+    /// * `tick_index_limit_one` - Optional tick index limit for the first swap, used instead of `sqrt_price_limit_one` when set.
+    /// * `tick_index_limit_two` - Optional tick index limit for the second swap, used instead of `sqrt_price_limit_two` when set.
+    ///
+    /// # Returns
+    ///
+    /// This function returns the `TwoHopSwapReturnData` of the swap, or an error if it fails.
+    pub fn two_hop_swap_compact<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TwoHopSwapCompact<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+        deadline_timestamp: Option<u64>,
+        tick_index_limit_one: Option<i32>,
+        tick_index_limit_two: Option<i32>,
+    ) -> Result<TwoHopSwapReturnData> {
+        return instructions::two_hop_swap_compact::two_hop_swap_compact_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            remaining_accounts_info,
+            deadline_timestamp,
+            tick_index_limit_one,
+            tick_index_limit_two,
         );
     }
 
@@ -846,16 +1638,52 @@ pub mod ai_dex {
         return instructions::set_end_timestamp_swap::set_end_timestamp_swap_handler(ctx, end_timestamp_swap);
     }
 
+    /// Sets the decaying early-exit fee charged on withdrawals from a temporary pool.
+    pub fn set_early_exit_fee(ctx: Context<SetTimestamp>, early_exit_fee_bps: u16) -> Result<()> {
+        return instructions::set_early_exit_fee::set_early_exit_fee_handler(ctx, early_exit_fee_bps);
+    }
+
     /// Sets the new max age for the oracle.
     pub fn set_new_oracle_max_age(ctx: Context<SetNewOracleMaxAgeAccount>, new_max_age: u64) -> Result<()> {
         return instructions::set_oracle_maximum_age::set_new_oracle_max_age_handler(ctx, new_max_age);
     }
+
+    /// Sets the maximum number of slots between the posting of an oracle price update and its use by a pool.
+    pub fn set_oracle_maximum_slot_lag(ctx: Context<SetOracleMaximumSlotLag>, maximum_slot_lag: u64) -> Result<()> {
+        return instructions::set_oracle_maximum_slot_lag::set_oracle_maximum_slot_lag_handler(ctx, maximum_slot_lag);
+    }
+
+    /// Sets the maximum age of the last oracle price of a pool for swaps in degraded mode.
+    pub fn set_oracle_degraded_maximum_age(ctx: Context<SetOracleDegradedMaximumAge>, degraded_maximum_age: u64) -> Result<()> {
+        return instructions::set_oracle_degraded_maximum_age::set_oracle_degraded_maximum_age_handler(ctx, degraded_maximum_age);
+    }
     
     /// Sets the new oracle account.
     pub fn set_new_oracle_account(ctx: Context<SetNewOracleAccount>) -> Result<()> {
         return instructions::set_new_oracle_account::set_new_oracle_handler(ctx);
     }
 
+    /// Sets the default max age inherited by oracle pools of the config.
+    pub fn set_default_oracle_max_age(ctx: Context<SetDefaultOracleMaxAge>, default_max_age: u64) -> Result<()> {
+        return instructions::set_default_oracle_maximum_age::set_default_oracle_max_age_handler(ctx, default_max_age);
+    }
+
+    /// Sets the default price feed inherited by oracle pools of a token pair of the config.
+    pub fn set_default_oracle_price_feed(ctx: Context<SetDefaultOraclePriceFeed>, price_feed_id: String) -> Result<()> {
+        return instructions::set_default_oracle_price_feed::set_default_oracle_price_feed_handler(ctx, price_feed_id);
+    }
+
+    /// Applies the config-level oracle defaults to the oracle account of a pool, by the oracle
+    /// manager of the config.
+    pub fn sync_oracle_params(ctx: Context<SyncOracleParams>) -> Result<()> {
+        return instructions::sync_oracle_params::sync_oracle_params_handler(ctx);
+    }
+
+    /// Refreshes the price of an oracle pool from its price feed. Anyone can call it.
+    pub fn refresh_oracle_price(ctx: Context<RefreshOraclePrice>) -> Result<()> {
+        return instructions::refresh_oracle_price::refresh_oracle_price_handler(ctx);
+    }
+
     pub fn initialize_swap_referral(
         ctx: Context<InitializeSwapReferral>,
         referral_code: String,
@@ -888,7 +1716,153 @@ pub mod ai_dex {
         );
     }
 
-    /// Collects the referral reward fee for the swap.
+    /// Sets the swap referral reward fee rate of the pools of a token pair, passed as remaining accounts.
+    pub fn set_pool_swap_referral_reward_fee_rates<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, SetPoolSwapReferralRewardFeeRates<'info>>,
+        swap_referral_reward_fee_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_pool_swap_referral_reward_fee_rates::set_pool_swap_referral_reward_fee_rates_handler(
+            ctx,
+            swap_referral_reward_fee_rate,
+        );
+    }
+
+    /// Sets the maximum integrator fee rate that swaps of the ai dex config may charge.
+    pub fn set_max_integrator_fee_rate(
+        ctx: Context<SetMaxIntegratorFeeRate>,
+        max_integrator_fee_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_max_integrator_fee_rate::set_max_integrator_fee_rate_handler(
+            ctx,
+            max_integrator_fee_rate,
+        );
+    }
+
+    /// Sets the owner of the token accounts protocol fees of the ai dex config can be collected to.
+    pub fn set_protocol_fee_treasury(ctx: Context<SetProtocolFeeTreasury>) -> Result<()> {
+        return instructions::set_protocol_fee_treasury::set_protocol_fee_treasury_handler(ctx);
+    }
+
+    /// Sets the holder of a fee, oracle or reward role of the ai dex config.
+    pub fn set_config_role(ctx: Context<SetConfigRole>, role: ConfigRole) -> Result<()> {
+        return instructions::set_config_role::set_config_role_handler(ctx, role);
+    }
+
+    /// Sets whether new pools of the ai dex config reject token mints with a permanent delegate.
+    pub fn set_permanent_delegate_mint_policy(
+        ctx: Context<SetPermanentDelegateMintPolicy>,
+        rejects_permanent_delegate_mints: bool,
+    ) -> Result<()> {
+        return instructions::set_permanent_delegate_mint_policy::set_permanent_delegate_mint_policy_handler(
+            ctx,
+            rejects_permanent_delegate_mints,
+        );
+    }
+
+    /// Sets the share of the protocol fees of swaps of a pool owed to the staking rewards of its
+    /// config, in basis points.
+    pub fn set_staking_reward_share(
+        ctx: Context<SetStakingRewardShare>,
+        staking_reward_share_bps: u16,
+    ) -> Result<()> {
+        return instructions::set_staking_reward_share::set_staking_reward_share_handler(
+            ctx,
+            staking_reward_share_bps,
+        );
+    }
+
+    /// Initializes the position NFT metadata used by all pools of an ai dex config.
+    pub fn initialize_position_metadata(
+        ctx: Context<InitializePositionMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        return instructions::initialize_position_metadata::initialize_position_metadata_handler(
+            ctx,
+            name,
+            symbol,
+            uri,
+        );
+    }
+
+    /// Sets the position NFT metadata used by all pools of an ai dex config.
+    pub fn set_position_metadata(
+        ctx: Context<SetPositionMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        return instructions::set_position_metadata::set_position_metadata_handler(
+            ctx,
+            name,
+            symbol,
+            uri,
+        );
+    }
+
+    /// Creates or updates the name, category tags and uri of a pool, by the config authority or
+    /// the creator of the pool.
+    pub fn set_pool_metadata(
+        ctx: Context<SetPoolMetadata>,
+        name: String,
+        tags: Vec<String>,
+        uri: String,
+    ) -> Result<()> {
+        return instructions::set_pool_metadata::set_pool_metadata_handler(ctx, name, tags, uri);
+    }
+
+    /// Creates the tick array bounty of a pool, by the config authority or the creator of the
+    /// pool. The bounty pays whoever initializes a tick array next to the current price.
+    pub fn initialize_tick_array_bounty(
+        ctx: Context<InitializeTickArrayBounty>,
+        reward_per_tick_array: u64,
+    ) -> Result<()> {
+        return instructions::initialize_tick_array_bounty::initialize_tick_array_bounty_handler(
+            ctx,
+            reward_per_tick_array,
+        );
+    }
+
+    /// Sets the reward per tick array of the tick array bounty of a pool.
+    pub fn set_tick_array_bounty_reward(
+        ctx: Context<SetTickArrayBountyReward>,
+        reward_per_tick_array: u64,
+    ) -> Result<()> {
+        return instructions::set_tick_array_bounty_reward::set_tick_array_bounty_reward_handler(
+            ctx,
+            reward_per_tick_array,
+        );
+    }
+
+    /// Transfers lamports to the tick array bounty of a pool.
+    pub fn fund_tick_array_bounty(ctx: Context<FundTickArrayBounty>, lamports: u64) -> Result<()> {
+        return instructions::fund_tick_array_bounty::fund_tick_array_bounty_handler(ctx, lamports);
+    }
+
+    /// Sets the Metaplex collection that position NFTs of an ai dex config are verified into.
+    pub fn set_position_collection(
+        ctx: Context<SetPositionCollection>,
+        collection_mint: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_position_collection::set_position_collection_handler(ctx, collection_mint);
+    }
+
+    /// Sets the liquidity threshold and inactivity period used for dust position liquidation.
+    pub fn set_dust_position_params(
+        ctx: Context<SetDustPositionParams>,
+        dust_position_liquidity_threshold: u128,
+        dust_position_inactivity_period: u64,
+    ) -> Result<()> {
+        return instructions::set_dust_position_params::set_dust_position_params_handler(
+            ctx,
+            dust_position_liquidity_threshold,
+            dust_position_inactivity_period,
+        );
+    }
+
+    /// Collects the referral reward fee for the swap, along with the fees owed by a referral fee
+    /// escrow when passed.
     pub fn collect_referral_reward_fee<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CollectReferralFees<'info>>,
         amount: u64,
@@ -901,11 +1875,108 @@ pub mod ai_dex {
         );
     }
 
-    /// Add liquidity from the fee as reinvestment for an ai dex.
+    /// Adds the swaps of a pool not yet reported to the totals of its config.
+    pub fn sync_pool_stats(ctx: Context<SyncPoolStats>) -> Result<()> {
+        return instructions::sync_pool_stats::sync_pool_stats_handler(ctx);
+    }
+
+    /// Creates the snapshot account of a pool, fetched in bulk by routers.
+    pub fn initialize_pool_snapshot(ctx: Context<InitializePoolSnapshot>) -> Result<()> {
+        return instructions::initialize_pool_snapshot::initialize_pool_snapshot_handler(ctx);
+    }
+
+    /// Creates the fee analytics account of an oracle pool, recording the oracle-priced value of
+    /// the fees of every swap of the pool.
+    pub fn initialize_pool_fee_analytics(ctx: Context<InitializePoolFeeAnalytics>) -> Result<()> {
+        return instructions::initialize_pool_fee_analytics::initialize_pool_fee_analytics_handler(ctx);
+    }
+
+    /// Creates the revenue snapshot account of a pool, recording the protocol fees collected from
+    /// the pool until they are synced to the protocol stats.
+    pub fn initialize_pool_revenue_snapshot(ctx: Context<InitializePoolRevenueSnapshot>) -> Result<()> {
+        return instructions::initialize_pool_revenue_snapshot::initialize_pool_revenue_snapshot_handler(ctx);
+    }
+
+    /// Creates the protocol stats account of an ai dex config.
+    pub fn initialize_protocol_stats(ctx: Context<InitializeProtocolStats>) -> Result<()> {
+        return instructions::initialize_protocol_stats::initialize_protocol_stats_handler(ctx);
+    }
+
+    /// Adds the protocol fees recorded by the revenue snapshots passed in the remaining accounts
+    /// to the protocol stats of their config, and copies the pool and swap counts of the config.
+    pub fn sync_protocol_stats(ctx: Context<SyncProtocolStats>) -> Result<()> {
+        return instructions::sync_protocol_stats::sync_protocol_stats_handler(ctx);
+    }
+
+    /// Creates the staking rewards of an ai dex config, claimable by the given staking authority.
+    pub fn initialize_staking_rewards(
+        ctx: Context<InitializeStakingRewards>,
+        staking_authority: Pubkey,
+    ) -> Result<()> {
+        return instructions::initialize_staking_rewards::initialize_staking_rewards_handler(
+            ctx,
+            staking_authority,
+        );
+    }
+
+    /// Creates a page of the position index of an owner, which the instructions opening and
+    /// closing positions add positions to and remove positions from.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context containing the accounts required for the initialization.
+    /// * `page` - The number of the page, starting from 0.
+    pub fn initialize_position_index(ctx: Context<InitializePositionIndex>, page: u16) -> Result<()> {
+        return instructions::initialize_position_index::initialize_position_index_handler(ctx, page);
+    }
+
+    /// Moves a transferred position from the position index of a previous owner to the position
+    /// index of its current owner. Anyone can call it.
+    pub fn reindex_position(ctx: Context<ReindexPosition>) -> Result<()> {
+        return instructions::reindex_position::reindex_position_handler(ctx);
+    }
+
+    /// Refreshes the snapshot of a pool from its current parameters.
+    pub fn refresh_pool_snapshot(ctx: Context<RefreshPoolSnapshot>) -> Result<()> {
+        return instructions::refresh_pool_snapshot::refresh_pool_snapshot_handler(ctx);
+    }
+
+    /// Add liquidity from the fee as reinvestment for an ai dex, signed by a registered keeper.
     pub fn update_reinvestments(ctx: Context<ReinvestFees>) -> Result<()> {
         return instructions::update_reinvestments::reinvest_fees_handler(ctx);
     }
 
+    /// Rebalances the fees of a position between its tokens with a fee-free internal swap, then
+    /// reinvests them, signed by a registered keeper. The rate of the swap is bounded by the
+    /// maximum deviation of the position from the pool snapshot price.
+    pub fn update_reinvestments_with_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ReinvestFeesWithSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        a_to_b: bool,
+    ) -> Result<()> {
+        return instructions::reinvest_fees_with_swap::reinvest_fees_with_swap_handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            a_to_b,
+        );
+    }
+
+    /// Sets how far the rate of reinvestment swaps of the fees of a position may deviate from the
+    /// pool snapshot price, signed by the position authority. 0 disables reinvestment swaps.
+    pub fn set_reinvestment_swap_max_deviation(
+        ctx: Context<SetReinvestmentSwapMaxDeviation>,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        return instructions::set_reinvestment_swap_max_deviation::set_reinvestment_swap_max_deviation_handler(
+            ctx,
+            max_deviation_bps,
+        );
+    }
+
     /// REINVESTMENTS ENDPOINTS <START>
     
     pub fn initialize_reinvestments(
@@ -942,6 +2013,144 @@ pub mod ai_dex {
         );
     }
 
+    /// Registers a keeper allowed to run reinvestments for the config.
+    pub fn register_keeper(
+        ctx: Context<RegisterKeeper>,
+        max_actions_per_window: u32,
+        window_duration: u64,
+    ) -> Result<()> {
+        return instructions::register_keeper::register_keeper_handler(
+            ctx,
+            max_actions_per_window,
+            window_duration,
+        );
+    }
+
+    /// Sets the rate limit of a registered keeper.
+    pub fn set_keeper_rate_limit(
+        ctx: Context<SetKeeperRateLimit>,
+        max_actions_per_window: u32,
+        window_duration: u64,
+    ) -> Result<()> {
+        return instructions::set_keeper_rate_limit::set_keeper_rate_limit_handler(
+            ctx,
+            max_actions_per_window,
+            window_duration,
+        );
+    }
+
+    /// Removes a keeper from the registry of the config.
+    pub fn remove_keeper(ctx: Context<RemoveKeeper>) -> Result<()> {
+        return instructions::remove_keeper::remove_keeper_handler(ctx);
+    }
+
+    /// Issues a token badge approving a mint, such as a reward mint with a freeze authority, for
+    /// the pools of the config.
+    pub fn initialize_token_badge(ctx: Context<InitializeTokenBadge>) -> Result<()> {
+        return instructions::initialize_token_badge::initialize_token_badge_handler(ctx);
+    }
+
+    /// Deletes a token badge of the config.
+    pub fn delete_token_badge(ctx: Context<DeleteTokenBadge>) -> Result<()> {
+        return instructions::delete_token_badge::delete_token_badge_handler(ctx);
+    }
+
+    /// Creates a swap rate limiter on the volume of a token swapped through the pools it is
+    /// attached to.
+    pub fn initialize_swap_rate_limiter(
+        ctx: Context<InitializeSwapRateLimiter>,
+        max_volume_per_window: u64,
+        window_duration: u64,
+    ) -> Result<()> {
+        return instructions::initialize_swap_rate_limiter::initialize_swap_rate_limiter_handler(
+            ctx,
+            max_volume_per_window,
+            window_duration,
+        );
+    }
+
+    /// Sets the maximum volume and the window duration of a swap rate limiter.
+    pub fn set_swap_rate_limit(
+        ctx: Context<SetSwapRateLimit>,
+        max_volume_per_window: u64,
+        window_duration: u64,
+    ) -> Result<()> {
+        return instructions::set_swap_rate_limit::set_swap_rate_limit_handler(
+            ctx,
+            max_volume_per_window,
+            window_duration,
+        );
+    }
+
+    /// Attaches a swap rate limiter to a pool, or detaches the current one when no limiter is
+    /// passed.
+    pub fn set_pool_swap_rate_limiter(ctx: Context<SetPoolSwapRateLimiter>) -> Result<()> {
+        return instructions::set_pool_swap_rate_limiter::set_pool_swap_rate_limiter_handler(ctx);
+    }
+
+    /// Creates the blocklist of a config.
+    pub fn initialize_blocklist(ctx: Context<InitializeBlocklist>) -> Result<()> {
+        return instructions::initialize_blocklist::initialize_blocklist_handler(ctx);
+    }
+
+    /// Adds an address to the blocklist of a config.
+    pub fn add_blocklist_address(ctx: Context<ModifyBlocklist>, address: Pubkey) -> Result<()> {
+        return instructions::add_blocklist_address::add_blocklist_address_handler(ctx, address);
+    }
+
+    /// Removes an address from the blocklist of a config.
+    pub fn remove_blocklist_address(ctx: Context<ModifyBlocklist>, address: Pubkey) -> Result<()> {
+        return instructions::remove_blocklist_address::remove_blocklist_address_handler(ctx, address);
+    }
+
+    /// Enables or disables the blocklist of the config on a pool, rejecting swaps and liquidity
+    /// changes of blocked addresses while enabled.
+    pub fn set_pool_blocklist_enabled(
+        ctx: Context<SetPoolBlocklistEnabled>,
+        is_blocklist_enabled: bool,
+    ) -> Result<()> {
+        return instructions::set_pool_blocklist_enabled::set_pool_blocklist_enabled_handler(
+            ctx,
+            is_blocklist_enabled,
+        );
+    }
+
+    /// Creates the referral fee escrow of a swap referral on a pool. Swaps passing it as a
+    /// remaining account accrue the referral fee to it, collected with
+    /// `collect_referral_reward_fee`, instead of transferring it.
+    pub fn initialize_referral_fee_escrow(ctx: Context<InitializeReferralFeeEscrow>) -> Result<()> {
+        return instructions::initialize_referral_fee_escrow::initialize_referral_fee_escrow_handler(ctx);
+    }
+
+    /// Creates the grace checkpoint of a position. Passed to `update_fees_and_rewards` with the
+    /// reward grace escrow of the pool, it credits the position the grace share of the reward
+    /// growth it missed while out of range.
+    pub fn initialize_position_grace_checkpoint(ctx: Context<InitializePositionGraceCheckpoint>) -> Result<()> {
+        return instructions::initialize_position_grace_checkpoint::initialize_position_grace_checkpoint_handler(ctx);
+    }
+
+    /// Creates the compute unit registry of a config, from which clients read the compute units
+    /// of the instructions to set their compute budgets.
+    pub fn initialize_compute_unit_registry(ctx: Context<InitializeComputeUnitRegistry>) -> Result<()> {
+        return instructions::initialize_compute_unit_registry::initialize_compute_unit_registry_handler(ctx);
+    }
+
+    /// Sets the compute units measured for a variant of an instruction in the compute unit
+    /// registry, or removes the variant when 0 compute units are passed.
+    pub fn set_instruction_compute_units(
+        ctx: Context<SetInstructionComputeUnits>,
+        instruction_discriminator: [u8; 8],
+        variant: u8,
+        compute_units: u32,
+    ) -> Result<()> {
+        return instructions::set_instruction_compute_units::set_instruction_compute_units_handler(
+            ctx,
+            instruction_discriminator,
+            variant,
+            compute_units,
+        );
+    }
+
     /// Create mocked oracle account, just for testing.
     pub fn test_initialize_mock_price(
         ctx: Context<InitializeMockPrice>,
@@ -958,4 +2167,23 @@ pub mod ai_dex {
             publish_time,
         );
     }
+
+    /// Create the mocked clock account, just for testing.
+    ///
+    /// Programs built with the `mock-clock` feature read the current timestamp from this account
+    /// when it is passed as the last remaining account of swaps and liquidity instructions.
+    pub fn test_initialize_mock_clock(
+        ctx: Context<InitializeMockClock>,
+        unix_timestamp: i64,
+    ) -> Result<()> {
+        return instructions::test::initialize_mock_clock_handler(ctx, unix_timestamp);
+    }
+
+    /// Set the timestamp of the mocked clock account, just for testing.
+    pub fn test_set_mock_clock(
+        ctx: Context<SetMockClock>,
+        unix_timestamp: i64,
+    ) -> Result<()> {
+        return instructions::test::set_mock_clock_handler(ctx, unix_timestamp);
+    }
 }
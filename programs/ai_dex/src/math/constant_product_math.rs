@@ -0,0 +1,129 @@
+use crate::errors::ErrorCode;
+use crate::math::*;
+
+/// Swap quoted against the liquidity of a pool between two initialized ticks.
+#[derive(PartialEq, Debug)]
+pub struct ConstantProductQuote {
+    /// Amount of the input token paid, including the fee.
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+    pub next_sqrt_price: u128,
+    /// Part of the specified amount left unswapped because the price reached its bound.
+    pub amount_remaining: u64,
+}
+
+/// Quotes a swap that does not move the price past `sqrt_price_bound`.
+///
+/// The liquidity of a pool is constant between two initialized ticks, where the pool trades as a
+/// constant product pool of virtual reserves `liquidity / sqrt_price` of token A and
+/// `liquidity * sqrt_price` of token B. Bounding the price by the next initialized tick makes
+/// the quote exact without loading the ticks beyond it.
+///
+/// # Parameters
+/// - `amount` - The amount of the input token if `amount_specified_is_input`, of the output token otherwise
+/// - `fee_rate` - The fee rate of the pool
+/// - `liquidity` - The liquidity of the pool at the current price
+/// - `sqrt_price` - The current square root price of the pool
+/// - `sqrt_price_bound` - The square root price the swap stops at, in the direction of the swap
+/// - `amount_specified_is_input` - Whether `amount` is the input amount
+/// - `a_to_b` - The direction of the swap
+///
+/// # Errors
+/// - `AmountRemainingOverflowError`: - The swap step overflowed the specified amount.
+pub fn get_constant_product_quote(
+    amount: u64,
+    fee_rate: u16,
+    liquidity: u128,
+    sqrt_price: u128,
+    sqrt_price_bound: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Result<ConstantProductQuote, ErrorCode> {
+    let step = compute_swap(
+        amount,
+        fee_rate,
+        liquidity,
+        sqrt_price,
+        sqrt_price_bound,
+        amount_specified_is_input,
+        a_to_b,
+    )?;
+
+    let amount_in = step
+        .amount_in
+        .checked_add(step.fee_amount)
+        .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+    let amount_swapped = if amount_specified_is_input {
+        amount_in
+    } else {
+        step.amount_out
+    };
+    let amount_remaining = amount
+        .checked_sub(amount_swapped)
+        .ok_or(ErrorCode::AmountRemainingOverflowError)?;
+
+    Ok(ConstantProductQuote {
+        amount_in,
+        amount_out: step.amount_out,
+        fee_amount: step.fee_amount,
+        next_sqrt_price: step.next_price,
+        amount_remaining,
+    })
+}
+
+#[cfg(test)]
+mod constant_product_math_tests {
+    use super::*;
+
+    const LIQUIDITY: u128 = 1_000_000_000;
+    const SQRT_PRICE_1: u128 = 1 << 64;
+
+    #[test]
+    fn test_quote_within_bound() {
+        let sqrt_price_bound = sqrt_price_from_tick_index(-1000);
+        let quote =
+            get_constant_product_quote(1_000, 3000, LIQUIDITY, SQRT_PRICE_1, sqrt_price_bound, true, true).unwrap();
+        let step = compute_swap(1_000, 3000, LIQUIDITY, SQRT_PRICE_1, sqrt_price_bound, true, true).unwrap();
+
+        assert_eq!(quote.amount_in, 1_000);
+        assert_eq!(quote.amount_out, step.amount_out);
+        assert_eq!(quote.fee_amount, step.fee_amount);
+        assert_eq!(quote.next_sqrt_price, step.next_price);
+        assert_eq!(quote.amount_remaining, 0);
+        assert!(quote.next_sqrt_price > sqrt_price_bound);
+    }
+
+    #[test]
+    fn test_quote_stops_at_bound() {
+        let sqrt_price_bound = sqrt_price_from_tick_index(10);
+        let quote =
+            get_constant_product_quote(u64::MAX, 3000, LIQUIDITY, SQRT_PRICE_1, sqrt_price_bound, true, false).unwrap();
+
+        assert_eq!(quote.next_sqrt_price, sqrt_price_bound);
+        assert!(quote.amount_remaining > 0);
+        assert_eq!(quote.amount_in + quote.amount_remaining, u64::MAX);
+    }
+
+    #[test]
+    fn test_quote_exact_output() {
+        let sqrt_price_bound = sqrt_price_from_tick_index(1000);
+        let quote =
+            get_constant_product_quote(1_000, 3000, LIQUIDITY, SQRT_PRICE_1, sqrt_price_bound, false, false).unwrap();
+
+        assert_eq!(quote.amount_out, 1_000);
+        assert_eq!(quote.amount_remaining, 0);
+        assert!(quote.amount_in > quote.amount_out);
+    }
+
+    #[test]
+    fn test_quote_without_liquidity() {
+        // The price moves through a range without liquidity without trading anything
+        let sqrt_price_bound = sqrt_price_from_tick_index(-10);
+        let quote = get_constant_product_quote(1_000, 3000, 0, SQRT_PRICE_1, sqrt_price_bound, true, true).unwrap();
+
+        assert_eq!(quote.next_sqrt_price, sqrt_price_bound);
+        assert_eq!(quote.amount_in, 0);
+        assert_eq!(quote.amount_remaining, 1_000);
+    }
+}
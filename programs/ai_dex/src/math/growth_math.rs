@@ -0,0 +1,124 @@
+use crate::errors::ErrorCode;
+
+use super::{checked_mul_div, checked_mul_shift_right, mul_u256, U256Muldiv, Q64_RESOLUTION};
+
+/// Computes the amount accrued by `liquidity` over a Q64.64 fee or reward growth delta.
+///
+/// With the `u256-growth-math` feature, the product is computed in u256 and an amount that does
+/// not fit in a u64 is an error. Otherwise the overflowing amount is forfeited and 0 is returned,
+/// so that the position can still be modified.
+pub fn growth_amount(liquidity: u128, growth_delta: u128) -> Result<u64, ErrorCode> {
+    if cfg!(feature = "u256-growth-math") {
+        checked_growth_amount_u256(liquidity, growth_delta)
+    } else {
+        Ok(checked_mul_shift_right(liquidity, growth_delta).unwrap_or(0))
+    }
+}
+
+/// Computes the Q64.64 reward growth of `emissions_per_second_x64` emitted over `time_delta`
+/// seconds and spread over `liquidity`.
+///
+/// With the `u256-growth-math` feature, the emissions are computed in u256, so a growth is only
+/// an error when it does not fit in a u128 itself. Otherwise emissions overflowing a u128 halt the
+/// reward and 0 is returned.
+pub fn reward_growth_delta(
+    time_delta: u128,
+    emissions_per_second_x64: u128,
+    liquidity: u128,
+) -> Result<u128, ErrorCode> {
+    if cfg!(feature = "u256-growth-math") {
+        checked_reward_growth_delta_u256(time_delta, emissions_per_second_x64, liquidity)
+    } else {
+        Ok(checked_mul_div(time_delta, emissions_per_second_x64, liquidity).unwrap_or(0))
+    }
+}
+
+/// Computes the amount accrued by `liquidity` over a Q64.64 growth delta in u256.
+///
+/// # Errors
+///
+/// Returns `ErrorCode::GrowthOverflowError` if the amount does not fit in a u64.
+pub fn checked_growth_amount_u256(liquidity: u128, growth_delta: u128) -> Result<u64, ErrorCode> {
+    let amount = mul_u256(liquidity, growth_delta)
+        .shift_right(Q64_RESOLUTION as u32)
+        .try_into_u128()
+        .map_err(|_| ErrorCode::GrowthOverflowError)?;
+    u64::try_from(amount).map_err(|_| ErrorCode::GrowthOverflowError)
+}
+
+/// Computes the Q64.64 reward growth of the emissions over `time_delta` seconds in u256.
+///
+/// # Errors
+///
+/// * `ErrorCode::DivisionByZeroError` - If the liquidity is zero.
+/// * `ErrorCode::GrowthOverflowError` - If the growth does not fit in a u128.
+pub fn checked_reward_growth_delta_u256(
+    time_delta: u128,
+    emissions_per_second_x64: u128,
+    liquidity: u128,
+) -> Result<u128, ErrorCode> {
+    if liquidity == 0 {
+        return Err(ErrorCode::DivisionByZeroError);
+    }
+    let (growth_delta, _) = mul_u256(time_delta, emissions_per_second_x64)
+        .div(U256Muldiv::new(0, liquidity), false);
+    growth_delta.try_into_u128().map_err(|_| ErrorCode::GrowthOverflowError)
+}
+
+#[cfg(test)]
+mod growth_math_tests {
+    use super::*;
+    use crate::math::TO_Q64;
+
+    #[test]
+    fn test_checked_growth_amount_u256_matches_u128() {
+        for (liquidity, growth_delta) in [
+            (0, TO_Q64),
+            (1_000_000, TO_Q64 * 3),
+            (u64::MAX as u128, TO_Q64 - 1),
+            (TO_Q64, u64::MAX as u128),
+        ] {
+            assert_eq!(
+                checked_growth_amount_u256(liquidity, growth_delta).unwrap(),
+                checked_mul_shift_right(liquidity, growth_delta).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_growth_amount_u256_overflow() {
+        assert_eq!(
+            checked_growth_amount_u256(TO_Q64, TO_Q64 << 1),
+            Err(ErrorCode::GrowthOverflowError)
+        );
+        assert_eq!(
+            checked_growth_amount_u256(u128::MAX, u128::MAX),
+            Err(ErrorCode::GrowthOverflowError)
+        );
+    }
+
+    #[test]
+    fn test_checked_reward_growth_delta_u256_beyond_u128_product() {
+        // 2^64 tokens per second for 2^32 seconds overflow a u128 product before the division
+        let time_delta = 1u128 << 32;
+        let emissions_per_second_x64 = u64::MAX as u128 * TO_Q64;
+        let liquidity = 1u128 << 40;
+        assert_eq!(checked_mul_div(time_delta, emissions_per_second_x64, liquidity), Err(ErrorCode::MulDivOverflowError));
+        assert_eq!(
+            checked_reward_growth_delta_u256(time_delta, emissions_per_second_x64, liquidity).unwrap(),
+            u64::MAX as u128 * (1u128 << 56)
+        );
+    }
+
+    #[test]
+    fn test_checked_reward_growth_delta_u256_overflow() {
+        assert_eq!(
+            checked_reward_growth_delta_u256(u128::MAX, u128::MAX, 1),
+            Err(ErrorCode::GrowthOverflowError)
+        );
+        assert_eq!(
+            checked_reward_growth_delta_u256(1, 1, 0),
+            Err(ErrorCode::DivisionByZeroError)
+        );
+    }
+}
@@ -6,6 +6,8 @@ pub mod tick_math;
 pub mod token_math;
 pub mod u256_math;
 pub mod oracle_math;
+pub mod growth_math;
+pub mod constant_product_math;
 
 pub use bit_math::*;
 pub use bn::*;
@@ -15,3 +17,5 @@ pub use tick_math::*;
 pub use token_math::*;
 pub use u256_math::*;
 pub use oracle_math::*;
+pub use growth_math::*;
+pub use constant_product_math::*;
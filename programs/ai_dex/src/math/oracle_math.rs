@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 use pyth_solana_receiver_sdk::price_update::Price;
 use crate::errors::ErrorCode;
+use crate::math::{mul_u256, U256Muldiv};
+
+const BPS_DENOMINATOR: u128 = 10_000;
 
 /// Calculates the initial sqrt price from Pyth Oracle price data.
 ///
@@ -123,6 +126,102 @@ fn integer_sqrt(n: u128) -> u128 {
     res
 }
 
+/// Converts an amount through a pool at the given sqrt price, without fees or price impact.
+///
+/// # Parameters
+/// - amount: The amount of the input token.
+/// - sqrt_price: The sqrt price of the pool in Q64.64 fixed-point format.
+/// - a_to_b: The direction of the conversion.
+///
+/// # Returns
+/// - u128: The amount of the output token, saturated at `u128::MAX`.
+fn convert_amount_at_sqrt_price(amount: u128, sqrt_price: u128, a_to_b: bool) -> u128 {
+    let sqrt_price_u256 = U256Muldiv::new(0, sqrt_price);
+    let converted = if a_to_b {
+        // amount * sqrt_price^2 / 2^128
+        mul_u256(amount, sqrt_price).shift_right(64).mul(sqrt_price_u256).shift_right(64)
+    } else {
+        // amount * 2^128 / sqrt_price^2
+        U256Muldiv::new(0, amount)
+            .shift_left(64)
+            .div(sqrt_price_u256, false)
+            .0
+            .shift_left(64)
+            .div(sqrt_price_u256, false)
+            .0
+    };
+    converted.try_into_u128().unwrap_or(u128::MAX)
+}
+
+/// Returns whether the rate of a swap is within a maximum deviation from a reference price.
+///
+/// Only rates worse than the reference price count as a deviation.
+///
+/// # Parameters
+/// - amount_in: The amount of the input token of the swap.
+/// - amount_out: The amount of the output token of the swap.
+/// - reference_sqrt_price: The reference sqrt price of the pool in Q64.64 fixed-point format.
+/// - a_to_b: The direction of the swap.
+/// - max_deviation_bps: The maximum deviation from the reference price, in basis points, at most 10,000.
+pub fn is_rate_within_price_deviation(
+    amount_in: u64,
+    amount_out: u64,
+    reference_sqrt_price: u128,
+    a_to_b: bool,
+    max_deviation_bps: u16,
+) -> bool {
+    let expected_amount_out = convert_amount_at_sqrt_price(amount_in as u128, reference_sqrt_price, a_to_b);
+
+    // amount_out / expected_amount_out >= 1 - max_deviation_bps / 10,000
+    let scaled_amount_out = mul_u256(amount_out as u128, BPS_DENOMINATOR);
+    let scaled_minimum_amount_out =
+        mul_u256(expected_amount_out, BPS_DENOMINATOR.saturating_sub(max_deviation_bps as u128));
+    !scaled_amount_out.lt(scaled_minimum_amount_out)
+}
+
+/// Checks the end-to-end rate of a two-hop swap against the rate implied by the oracle prices of
+/// both pools, so that a manipulated intermediate pool cannot give a catastrophic overall rate
+/// even when each hop stays within its own price limit.
+///
+/// Only rates worse than the composite oracle price count as a deviation.
+///
+/// # Parameters
+/// - amount_in: The amount of the input token paid by the swapper.
+/// - amount_out: The amount of the output token received by the swapper.
+/// - oracle_sqrt_price_one: The oracle sqrt price of the first pool.
+/// - a_to_b_one: The direction of the first hop.
+/// - oracle_sqrt_price_two: The oracle sqrt price of the second pool.
+/// - a_to_b_two: The direction of the second hop.
+/// - max_deviation_bps: The maximum deviation from the composite oracle price, in basis points.
+///
+/// # Errors
+/// - ErrorCode::InvalidCompositePriceDeviation: If the maximum deviation exceeds 10,000 basis points.
+/// - ErrorCode::CompositePriceDeviationExceeded: If the rate of the swap deviates by more than the maximum.
+pub fn check_composite_oracle_price(
+    amount_in: u64,
+    amount_out: u64,
+    oracle_sqrt_price_one: u128,
+    a_to_b_one: bool,
+    oracle_sqrt_price_two: u128,
+    a_to_b_two: bool,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    if max_deviation_bps as u128 > BPS_DENOMINATOR {
+        return Err(ErrorCode::InvalidCompositePriceDeviation.into());
+    }
+
+    let intermediate_amount = convert_amount_at_sqrt_price(amount_in as u128, oracle_sqrt_price_one, a_to_b_one);
+    let expected_amount_out = convert_amount_at_sqrt_price(intermediate_amount, oracle_sqrt_price_two, a_to_b_two);
+
+    // amount_out / expected_amount_out >= 1 - max_deviation_bps / 10,000
+    let scaled_amount_out = mul_u256(amount_out as u128, BPS_DENOMINATOR);
+    let scaled_minimum_amount_out = mul_u256(expected_amount_out, BPS_DENOMINATOR - max_deviation_bps as u128);
+    if scaled_amount_out.lt(scaled_minimum_amount_out) {
+        return Err(ErrorCode::CompositePriceDeviationExceeded.into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,4 +444,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_check_composite_oracle_price() {
+        // 1 A = 4 B in pool one and 1 B = 0.25 C in pool two, so 1 A = 1 C
+        let sqrt_price_unit = 1u128 << 64;
+        let sqrt_price_one = 2u128 << 64;
+        let sqrt_price_quarter = 1u128 << 63;
+
+        assert!(check_composite_oracle_price(1_000, 1_000, sqrt_price_one, true, sqrt_price_quarter, true, 0).is_ok());
+        assert!(check_composite_oracle_price(1_000, 990, sqrt_price_one, true, sqrt_price_quarter, true, 100).is_ok());
+        assert!(check_composite_oracle_price(1_000, 989, sqrt_price_one, true, sqrt_price_quarter, true, 100).is_err());
+        // Better rates than the oracle price pass
+        assert!(check_composite_oracle_price(1_000, 2_000, sqrt_price_one, true, sqrt_price_quarter, true, 0).is_ok());
+
+        // Reverse route: C -> B -> A
+        assert!(check_composite_oracle_price(1_000, 1_000, sqrt_price_quarter, false, sqrt_price_one, false, 0).is_ok());
+        assert!(check_composite_oracle_price(1_000, 500, sqrt_price_quarter, false, sqrt_price_one, false, 4_000).is_err());
+
+        // Price of one on both pools
+        assert!(check_composite_oracle_price(u64::MAX, u64::MAX, sqrt_price_unit, true, sqrt_price_unit, false, 0).is_ok());
+        assert!(check_composite_oracle_price(1_000, 1_000, sqrt_price_unit, true, sqrt_price_unit, true, 10_001).is_err());
+    }
+
+    #[test]
+    fn test_is_rate_within_price_deviation() {
+        // 1 A = 4 B
+        let sqrt_price = 2u128 << 64;
+
+        assert!(is_rate_within_price_deviation(1_000, 4_000, sqrt_price, true, 0));
+        assert!(is_rate_within_price_deviation(1_000, 3_960, sqrt_price, true, 100));
+        assert!(!is_rate_within_price_deviation(1_000, 3_959, sqrt_price, true, 100));
+        // Better rates than the reference price pass
+        assert!(is_rate_within_price_deviation(1_000, 5_000, sqrt_price, true, 0));
+
+        assert!(is_rate_within_price_deviation(4_000, 990, sqrt_price, false, 100));
+        assert!(!is_rate_within_price_deviation(4_000, 989, sqrt_price, false, 100));
+    }
+
+    #[test]
+    fn test_convert_amount_at_extreme_sqrt_prices() {
+        use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+        assert!(convert_amount_at_sqrt_price(u64::MAX as u128, MAX_SQRT_PRICE_X64, true) > u64::MAX as u128);
+        assert_eq!(convert_amount_at_sqrt_price(u128::MAX, MAX_SQRT_PRICE_X64, true), u128::MAX);
+        assert_eq!(convert_amount_at_sqrt_price(u128::MAX, MIN_SQRT_PRICE_X64, false), u128::MAX);
+        assert_eq!(convert_amount_at_sqrt_price(1, MAX_SQRT_PRICE_X64, false), 0);
+    }
 }
@@ -38,6 +38,12 @@ pub const MAX_REINVESTMENT_PROTOCOL_FEE_RATE: u16 = 1_500;
 
 pub const REINVESTMENT_PROTOCOL_FEE_RATE_MUL_VALUE: u128 = 10_000;
 
+// Integrator fee rate is represented as a basis point of the swap output.
+// Max integrator fee rate supported is 10% of the output.
+pub const MAX_INTEGRATOR_FEE_RATE: u16 = 1_000;
+
+pub const INTEGRATOR_FEE_RATE_MUL_VALUE: u128 = 10_000;
+
 //
 // Get change in token_a corresponding to a change in price
 //
@@ -265,6 +271,56 @@ pub fn get_next_sqrt_price(
     }
 }
 
+/// Returns the integrator fee taken from a swap output, rounded down.
+///
+/// # Parameters
+/// - `amount`: The swap output amount the fee is taken from.
+/// - `integrator_fee_rate`: The integrator fee rate in basis points.
+pub fn get_integrator_fee_amount(amount: u64, integrator_fee_rate: u16) -> u64 {
+    (amount as u128 * integrator_fee_rate as u128 / INTEGRATOR_FEE_RATE_MUL_VALUE) as u64
+}
+
+/// Returns the integrator fee to add on top of an exact swap output, rounded up, so that the fee
+/// is its rate of the grossed-up output and the owner still receives `amount_out`.
+///
+/// # Parameters
+/// - `amount_out`: The swap output amount requested by the owner.
+/// - `integrator_fee_rate`: The integrator fee rate in basis points, below 100%.
+pub fn get_integrator_fee_amount_for_output(amount_out: u64, integrator_fee_rate: u16) -> Result<u64, ErrorCode> {
+    let numerator = amount_out as u128 * integrator_fee_rate as u128;
+    let denominator = INTEGRATOR_FEE_RATE_MUL_VALUE
+        .checked_sub(integrator_fee_rate as u128)
+        .filter(|denominator| *denominator > 0)
+        .ok_or(ErrorCode::IntegratorFeeRateExceededError)?;
+    u64::try_from(numerator.div_ceil(denominator)).map_err(|_| ErrorCode::AmountCalculationOverflowError)
+}
+
+// Number of seconds a reward vault holding `vault_amount` funds emissions for, rounded down, or
+// u64::MAX if nothing is emitted.
+pub fn get_reward_funded_duration(vault_amount: u64, emissions_per_second_x64: u128) -> u64 {
+    if emissions_per_second_x64 == 0 {
+        return u64::MAX;
+    }
+    let funded_duration = (u128::from(vault_amount) << Q64_RESOLUTION) / emissions_per_second_x64;
+    u64::try_from(funded_duration).unwrap_or(u64::MAX)
+}
+
+/// Returns the effective price of a swap, in output token units per input token unit, as a Q64.64
+/// number rounded down, or 0 if nothing was swapped.
+///
+/// Like the price of a pool, the square of its Q64.64 square root price, the effective price is
+/// in raw token units, so that the two compare without the decimals of the mints.
+///
+/// # Parameters
+/// - `amount_in`: The input amount of the swap.
+/// - `amount_out`: The output amount of the swap.
+pub fn get_effective_price_x64(amount_in: u64, amount_out: u64) -> u128 {
+    if amount_in == 0 {
+        return 0;
+    }
+    (u128::from(amount_out) << Q64_RESOLUTION) / u128::from(amount_in)
+}
+
 #[cfg(test)]
 mod fuzz_tests {
     use super::*;
@@ -495,3 +551,69 @@ mod test_get_amount_delta {
         assert!(get_amount_delta_a(1 << 64, 2 << 64, u64::MAX as u128, true).is_ok());
     }
 }
+
+#[cfg(test)]
+mod test_get_integrator_fee_amount {
+    use super::{get_integrator_fee_amount, get_integrator_fee_amount_for_output, MAX_INTEGRATOR_FEE_RATE};
+
+    #[test]
+    fn test_get_integrator_fee_amount() {
+        assert_eq!(get_integrator_fee_amount(10_000, 0), 0);
+        assert_eq!(get_integrator_fee_amount(10_000, 25), 25);
+        assert_eq!(get_integrator_fee_amount(399, 25), 0);
+        assert_eq!(get_integrator_fee_amount(u64::MAX, MAX_INTEGRATOR_FEE_RATE), u64::MAX / 10);
+    }
+
+    #[test]
+    fn test_get_integrator_fee_amount_for_output() {
+        assert_eq!(get_integrator_fee_amount_for_output(10_000, 0).unwrap(), 0);
+        assert_eq!(get_integrator_fee_amount_for_output(9_975, 25).unwrap(), 25);
+        assert_eq!(get_integrator_fee_amount_for_output(1, 25).unwrap(), 1);
+        assert_eq!(get_integrator_fee_amount_for_output(9_000, MAX_INTEGRATOR_FEE_RATE).unwrap(), 1_000);
+        assert!(get_integrator_fee_amount_for_output(1, 10_000).is_err());
+
+        // The fee is the rate of the grossed-up output
+        for amount_out in [1, 399, 10_000, 123_456_789] {
+            let fee = get_integrator_fee_amount_for_output(amount_out, 25).unwrap();
+            assert!(get_integrator_fee_amount(amount_out + fee, 25) <= fee);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_get_reward_funded_duration {
+    use super::*;
+
+    #[test]
+    fn test_no_emissions() {
+        assert_eq!(get_reward_funded_duration(0, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_funded_duration() {
+        // 10 tokens per second
+        assert_eq!(get_reward_funded_duration(864_000, 10 << 64), 86_400);
+        assert_eq!(get_reward_funded_duration(864_009, 10 << 64), 86_400);
+        assert_eq!(get_reward_funded_duration(0, 10 << 64), 0);
+    }
+
+    #[test]
+    fn test_saturates() {
+        assert_eq!(get_reward_funded_duration(u64::MAX, 1), u64::MAX);
+    }
+}
+
+#[cfg(test)]
+mod test_get_effective_price_x64 {
+    use super::*;
+
+    #[test]
+    fn test_get_effective_price_x64() {
+        assert_eq!(get_effective_price_x64(0, 1_000), 0);
+        assert_eq!(get_effective_price_x64(1_000, 0), 0);
+        assert_eq!(get_effective_price_x64(1_000, 1_000), 1 << 64);
+        assert_eq!(get_effective_price_x64(1_000, 2_500), 5 << 63);
+        assert_eq!(get_effective_price_x64(3, 1), (1u128 << 64) / 3);
+        assert_eq!(get_effective_price_x64(1, u64::MAX), u128::from(u64::MAX) << 64);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::math::{add_liquidity_delta, checked_mul_div};
+use crate::math::{add_liquidity_delta, reward_growth_delta};
 use crate::state::*;
 
 // Calculates the next global reward growth variables based on the given timestamp.
@@ -25,20 +25,22 @@ pub fn next_ai_dex_reward_infos(
     let time_delta = u128::from(next_timestamp - curr_timestamp);
 
     // Iterate through each reward info and calculate the new reward growth
-    for reward_info in &mut next_reward_infos {
+    for (i, reward_info) in next_reward_infos.iter_mut().enumerate() {
         if !reward_info.initialized() {
             continue;
         }
 
-        // Calculate the new reward growth delta.
-        // If the calculation overflows, set the delta value to zero.
-        // This will halt reward distributions for this reward.
-        let reward_growth_delta = checked_mul_div(
+        // Calculate the new reward growth delta, none while the reward is paused. The emissions
+        // without the out-of-range grace share, escrowed separately, are spread over the
+        // liquidity weighted by the full-range boost, which full-range positions earn on top of
+        // the growth.
+        // If the calculation overflows, set the delta value to zero unless `u256-growth-math` is
+        // enabled. This will halt reward distributions for this reward.
+        let reward_growth_delta = reward_growth_delta(
             time_delta,
-            reward_info.emissions_per_second_x64,
-            ai_dex.liquidity,
-        )
-        .unwrap_or(0);
+            ai_dex.growth_emissions_per_second_x64(i),
+            ai_dex.reward_growth_liquidity(i),
+        )?;
 
         // Add the reward growth delta to the global reward growth.
         reward_info.growth_global_x64 = reward_info.growth_global_x64.wrapping_add(reward_growth_delta);
@@ -186,6 +188,7 @@ mod ai_dex_orchestrator_tests {
     }
 
     #[test]
+    #[cfg(not(feature = "u256-growth-math"))]
     fn test_next_ai_dex_reward_infos_delta_zero_on_overflow() {
         let ai_dex = &AiDexBuilder::new()
             .liquidity(100)
@@ -226,4 +229,59 @@ mod ai_dex_orchestrator_tests {
             0b1001011011 << (Q64_RESOLUTION - 1) // 301.5
         );
     }
+
+    #[test]
+    fn test_next_ai_dex_reward_infos_paused_reward() {
+        let mut ai_dex = init_test_ai_dex(100, 1577854800);
+        ai_dex.paused_rewards = 0b010;
+
+        let new_timestamp = 1577854800 + 300;
+        let result = next_ai_dex_reward_infos(&ai_dex, new_timestamp).unwrap();
+        assert_eq!(
+            AiDexRewardInfo::to_reward_growths(&result),
+            [
+                130 << Q64_RESOLUTION,
+                200 << Q64_RESOLUTION,
+                0b1001011011 << (Q64_RESOLUTION - 1) // 301.5
+            ]
+        );
+        // The emissions rate is kept for when the reward resumes
+        assert_eq!({ result[1].emissions_per_second_x64 }, 0b11 << (Q64_RESOLUTION - 1));
+    }
+
+    #[test]
+    fn test_next_ai_dex_reward_infos_out_of_range_grace() {
+        let mut ai_dex = init_test_ai_dex(100, 1577854800);
+        ai_dex.out_of_range_grace_bps = [2_500, 0, 10_000];
+
+        let new_timestamp = 1577854800 + 300;
+        let result = next_ai_dex_reward_infos(&ai_dex, new_timestamp).unwrap();
+        // The grace share of the emissions is escrowed rather than added to the growth
+        assert_eq!(
+            AiDexRewardInfo::to_reward_growths(&result),
+            [
+                0b11110101 << (Q64_RESOLUTION - 1), // 122.5
+                0b110011001 << (Q64_RESOLUTION - 1), // 204.5
+                300 << Q64_RESOLUTION,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_ai_dex_reward_infos_full_range_boost() {
+        let mut ai_dex = init_test_ai_dex(100, 1577854800);
+        ai_dex.full_range_reward_boost_bps = [10_000, 0, 5_000];
+
+        let new_timestamp = 1577854800 + 300;
+        let result = next_ai_dex_reward_infos(&ai_dex, new_timestamp).unwrap();
+        // The emissions are spread over the liquidity weighted by the boost
+        assert_eq!(
+            AiDexRewardInfo::to_reward_growths(&result),
+            [
+                115 << Q64_RESOLUTION,
+                0b110011001 << (Q64_RESOLUTION - 1), // 204.5
+                301 << Q64_RESOLUTION,
+            ]
+        );
+    }
 }
@@ -0,0 +1,162 @@
+use crate::state::{AiDexPool, Position, Tick, TickArray, NUM_REWARDS};
+
+/// An invariant of the pool, tick and position state that every sequence of swaps and liquidity
+/// changes must preserve, found violated.
+///
+/// The checks operate on plain state so that unit tests, property tests and external fuzzers can
+/// run them after each simulated instruction. They are compiled for tests and with the
+/// `invariant-checks` feature only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// The `liquidity_net` of the initialized ticks does not sum to zero.
+    LiquidityNetSum(i128),
+    /// The `liquidity_net` of the initialized ticks overflows when summed.
+    LiquidityNetOverflow,
+    /// An initialized tick has no gross liquidity, or an uninitialized tick has some.
+    TickInitialization(i32),
+    /// The net liquidity of a tick exceeds its gross liquidity.
+    LiquidityNetExceedsGross(i32),
+    /// The liquidity of the pool differs from the net liquidity of the ticks at or below the
+    /// current tick.
+    ActiveLiquidity { expected: u128, actual: u128 },
+    /// The fees owed to a position decreased.
+    FeesOwedDecreased,
+    /// The reward owed to a position for the given reward index decreased.
+    RewardOwedDecreased(usize),
+}
+
+/// Iterates over the ticks of a tick array with their tick index.
+pub fn tick_array_ticks(
+    tick_array: &TickArray,
+    tick_spacing: u16,
+) -> impl Iterator<Item = (i32, &Tick)> {
+    let start_tick_index = tick_array.start_tick_index;
+    tick_array
+        .ticks
+        .iter()
+        .enumerate()
+        .map(move |(offset, tick)| (start_tick_index + offset as i32 * tick_spacing as i32, tick))
+}
+
+/// Checks that the ticks are consistently initialized and that the `liquidity_net` of the
+/// initialized ticks sums to zero, since every position adds its liquidity at its lower tick and
+/// removes it at its upper tick.
+///
+/// All initialized ticks of the pool must be given.
+pub fn check_tick_liquidity<'a>(
+    ticks: impl IntoIterator<Item = (i32, &'a Tick)>,
+) -> Result<(), InvariantViolation> {
+    let mut liquidity_net_sum: i128 = 0;
+    for (tick_index, tick) in ticks {
+        let liquidity_gross = tick.liquidity_gross;
+        let liquidity_net = tick.liquidity_net;
+        if tick.initialized != (liquidity_gross > 0) {
+            return Err(InvariantViolation::TickInitialization(tick_index));
+        }
+        if liquidity_net.unsigned_abs() > liquidity_gross {
+            return Err(InvariantViolation::LiquidityNetExceedsGross(tick_index));
+        }
+        if tick.initialized {
+            liquidity_net_sum = liquidity_net_sum
+                .checked_add(liquidity_net)
+                .ok_or(InvariantViolation::LiquidityNetOverflow)?;
+        }
+    }
+
+    if liquidity_net_sum != 0 {
+        return Err(InvariantViolation::LiquidityNetSum(liquidity_net_sum));
+    }
+    Ok(())
+}
+
+/// Checks that the liquidity of the pool equals the net liquidity of the initialized ticks at or
+/// below the current tick, i.e. the liquidity of the positions in range.
+///
+/// All initialized ticks of the pool must be given.
+pub fn check_active_liquidity<'a>(
+    ai_dex: &AiDexPool,
+    ticks: impl IntoIterator<Item = (i32, &'a Tick)>,
+) -> Result<(), InvariantViolation> {
+    let tick_current_index = ai_dex.tick_current_index;
+    let mut active_liquidity: i128 = 0;
+    for (tick_index, tick) in ticks {
+        if tick.initialized && tick_index <= tick_current_index {
+            active_liquidity = active_liquidity
+                .checked_add(tick.liquidity_net)
+                .ok_or(InvariantViolation::LiquidityNetOverflow)?;
+        }
+    }
+
+    let actual = ai_dex.liquidity;
+    if active_liquidity < 0 || active_liquidity as u128 != actual {
+        return Err(InvariantViolation::ActiveLiquidity {
+            expected: active_liquidity.max(0) as u128,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the fees and rewards owed to a position did not decrease between two states in
+/// which nothing was collected.
+pub fn check_owed_amounts_monotonic(
+    before: &Position,
+    after: &Position,
+) -> Result<(), InvariantViolation> {
+    if after.fee_owed_a < before.fee_owed_a || after.fee_owed_b < before.fee_owed_b {
+        return Err(InvariantViolation::FeesOwedDecreased);
+    }
+    for reward_index in 0..NUM_REWARDS {
+        if after.reward_infos[reward_index].amount_owed
+            < before.reward_infos[reward_index].amount_owed
+        {
+            return Err(InvariantViolation::RewardOwedDecreased(reward_index));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod invariants_tests {
+    use super::*;
+    use crate::state::tick_builder::TickBuilder;
+
+    fn tick(liquidity_net: i128, liquidity_gross: u128) -> Tick {
+        TickBuilder::default()
+            .initialized(liquidity_gross > 0)
+            .liquidity_net(liquidity_net)
+            .liquidity_gross(liquidity_gross)
+            .build()
+    }
+
+    #[test]
+    fn test_check_tick_liquidity() {
+        let lower = tick(100, 100);
+        let upper = tick(-100, 100);
+        assert_eq!(check_tick_liquidity([(-10, &lower), (10, &upper)]), Ok(()));
+        assert_eq!(
+            check_tick_liquidity([(-10, &lower)]),
+            Err(InvariantViolation::LiquidityNetSum(100))
+        );
+
+        let uninitialized_with_liquidity = TickBuilder::default().liquidity_gross(1).build();
+        assert_eq!(
+            check_tick_liquidity([(0, &uninitialized_with_liquidity)]),
+            Err(InvariantViolation::TickInitialization(0))
+        );
+    }
+
+    #[test]
+    fn test_check_active_liquidity() {
+        let lower = tick(100, 100);
+        let upper = tick(-100, 100);
+        let mut ai_dex = AiDexPool { tick_current_index: 0, liquidity: 100, ..Default::default() };
+        assert_eq!(check_active_liquidity(&ai_dex, [(-10, &lower), (10, &upper)]), Ok(()));
+
+        ai_dex.tick_current_index = 10;
+        assert_eq!(
+            check_active_liquidity(&ai_dex, [(-10, &lower), (10, &upper)]),
+            Err(InvariantViolation::ActiveLiquidity { expected: 0, actual: 100 })
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use super::{
-    position_orchestrator::next_position_modify_liquidity_update,
+    position_orchestrator::{
+        apply_full_range_reward_boost, next_position_modify_liquidity_update, track_position_out_of_range,
+    },
     tick_orchestrator::{
         next_fee_growths_inside, next_reward_growths_inside, next_tick_modify_liquidity_update,
     },
@@ -10,7 +12,7 @@ use crate::{
     math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
     state::*,
 };
-use anchor_lang::prelude::{AccountLoader, *};
+use anchor_lang::prelude::*;
 
 #[derive(Debug, Copy, Clone)]
 pub struct ModifyLiquidityUpdate {
@@ -27,20 +29,20 @@ pub struct ModifyLiquidityUpdate {
 pub fn calculate_modify_liquidity<'info>(
     ai_dex: &AiDexPool,
     position: &Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
     liquidity_delta: i128,
     timestamp: u64,
 ) -> Result<ModifyLiquidityUpdate> {
     // Load the tick array for the lower tick index
-    let tick_array_lower = tick_array_lower.load()?;
+    let tick_array_lower = load_tick_array(tick_array_lower)?;
     // Get the tick at the lower tick index
     // The lower tick value obtained from the `tick_array_lower` at the specified index.
     let tick_lower =
         tick_array_lower.get_tick(position.tick_lower_index, ai_dex.tick_spacing)?;
 
     // Load the tick array for the upper tick index
-    let tick_array_upper = tick_array_upper.load()?;
+    let tick_array_upper = load_tick_array(tick_array_upper)?;
     // Get the tick at the upper tick index
     let tick_upper =
         tick_array_upper.get_tick(position.tick_upper_index, ai_dex.tick_spacing)?;
@@ -78,15 +80,15 @@ pub fn calculate_modify_liquidity<'info>(
 pub fn calculate_fee_and_reward_growths<'info>(
     ai_dex: &AiDexPool,
     position: &Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
     timestamp: u64,
 ) -> Result<(PositionUpdate, [AiDexRewardInfo; NUM_REWARDS], TickUpdate, TickUpdate)> {
-    let tick_array_lower = tick_array_lower.load()?;
+    let tick_array_lower = load_tick_array(tick_array_lower)?;
     let tick_lower =
         tick_array_lower.get_tick(position.tick_lower_index, ai_dex.tick_spacing)?;
 
-    let tick_array_upper = tick_array_upper.load()?;
+    let tick_array_upper = load_tick_array(tick_array_upper)?;
     let tick_upper =
         tick_array_upper.get_tick(position.tick_upper_index, ai_dex.tick_spacing)?;
 
@@ -178,7 +180,7 @@ fn _calculate_modify_liquidity(
     );
 
     // Calculate the position update
-    let position_update = next_position_modify_liquidity_update(
+    let mut position_update = next_position_modify_liquidity_update(
         position,
         liquidity_delta,
         fee_growth_inside_a,
@@ -186,6 +188,17 @@ fn _calculate_modify_liquidity(
         &reward_growths_inside,
     )?;
 
+    // Boost the rewards accrued by full-range positions
+    apply_full_range_reward_boost(
+        position,
+        &mut position_update,
+        ai_dex.tick_spacing,
+        &{ ai_dex.full_range_reward_boost_bps },
+    );
+
+    // Track since when the position is out of range for its out-of-range grace
+    track_position_out_of_range(position, &mut position_update, ai_dex, timestamp);
+
     Ok(ModifyLiquidityUpdate {
         ai_dex_liquidity: next_global_liquidity,
         reward_infos: next_reward_infos,
@@ -271,16 +284,89 @@ pub fn calculate_liquidity_token_deltas(
 pub fn sync_modify_liquidity_values<'info>(
     ai_dex: &mut AiDexPool,
     position: &mut Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
+    modify_liquidity_update: ModifyLiquidityUpdate,
+    reward_last_updated_timestamp: u64,
+) -> Result<()> {
+    sync_position_and_ticks(
+        ai_dex,
+        position,
+        tick_array_lower,
+        tick_array_upper,
+        &modify_liquidity_update,
+        reward_last_updated_timestamp,
+    )?;
+
+    // Update the rewards and liquidity in the AiDex instance
+    ai_dex.update_rewards_and_liquidity(
+        modify_liquidity_update.reward_infos,
+        modify_liquidity_update.ai_dex_liquidity,
+        reward_last_updated_timestamp,
+    )?;
+
+    Ok(())
+}
+
+/// Synchronizes the modify liquidity values of a position settled after the liquidity provision
+/// window of its expired temporary pool closed, which `sync_modify_liquidity_values` rejects.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The mutable reference to the AiDex instance.
+/// * `position` - The mutable reference to the Position instance.
+/// * `tick_array_lower` - The lower tick array.
+/// * `tick_array_upper` - The upper tick array.
+/// * `modify_liquidity_update` - The ModifyLiquidityUpdate struct containing the updated values.
+/// * `reward_last_updated_timestamp` - The timestamp when the rewards were last updated.
+///
+/// # Returns
+///
+/// A Result indicating success or failure, failing if the pool is not expired.
+pub fn sync_expired_modify_liquidity_values<'info>(
+    ai_dex: &mut AiDexPool,
+    position: &mut Position,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
     modify_liquidity_update: ModifyLiquidityUpdate,
     reward_last_updated_timestamp: u64,
+) -> Result<()> {
+    if !ai_dex.is_expired(reward_last_updated_timestamp) {
+        return Err(ErrorCode::PoolNotExpired.into());
+    }
+
+    sync_position_and_ticks(
+        ai_dex,
+        position,
+        tick_array_lower,
+        tick_array_upper,
+        &modify_liquidity_update,
+        reward_last_updated_timestamp,
+    )?;
+
+    ai_dex.update_rewards_and_liquidity_after_expiry(
+        modify_liquidity_update.reward_infos,
+        modify_liquidity_update.ai_dex_liquidity,
+        reward_last_updated_timestamp,
+    )?;
+
+    Ok(())
+}
+
+fn sync_position_and_ticks<'info>(
+    ai_dex: &AiDexPool,
+    position: &mut Position,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
+    modify_liquidity_update: &ModifyLiquidityUpdate,
+    reward_last_updated_timestamp: u64,
 ) -> Result<()> {
     // Update the position with the new values
     position.update(&modify_liquidity_update.position_update);
+    position.update_last_updated_timestamp(reward_last_updated_timestamp);
 
     // Update the lower tick in the tick array
-    let mut tick_array_lower_mut = tick_array_lower.load_mut()?;
+    let mut tick_array_lower_mut = load_tick_array_mut(tick_array_lower)?;
     tick_array_lower_mut.update_tick(
         position.tick_lower_index,
         ai_dex.tick_spacing,
@@ -288,20 +374,13 @@ pub fn sync_modify_liquidity_values<'info>(
     )?;
 
     // Update the upper tick in the tick array
-    let mut tick_array_upper_mut = tick_array_upper.load_mut()?;
+    let mut tick_array_upper_mut = load_tick_array_mut(tick_array_upper)?;
     tick_array_upper_mut.update_tick(
         position.tick_upper_index,
         ai_dex.tick_spacing,
         &modify_liquidity_update.tick_upper_update,
     )?;
 
-    // Update the rewards and liquidity in the AiDex instance
-    ai_dex.update_rewards_and_liquidity(
-        modify_liquidity_update.reward_infos,
-        modify_liquidity_update.ai_dex_liquidity,
-        reward_last_updated_timestamp,
-    )?;
-
     Ok(())
 }
 
@@ -1340,6 +1419,7 @@ mod calculate_modify_liquidity_unit_tests {
                                 // 8 = 0.83 * 10
                                 8,
                             ),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -1697,6 +1777,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(50),
                             fee_owed_b: 500,
                             reward_infos: create_position_reward_infos(to_x64(20), 200),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -1760,6 +1841,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 200,
                             reward_infos: create_position_reward_infos(to_x64(3), 30),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate::default(),
                         tick_upper_update: TickUpdate::default(),
@@ -1803,6 +1885,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 200,
                             reward_infos: create_position_reward_infos(to_x64(3), 30),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -2002,6 +2085,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(20),
                         fee_owed_b: 200,
                         reward_infos: create_position_reward_infos(to_x64(3), 30),
+                        ..Default::default()
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2154,6 +2238,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(20),
                         fee_owed_b: 200,
                         reward_infos: create_position_reward_infos(to_x64(3), 30),
+                        ..Default::default()
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2306,6 +2391,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(20),
                         fee_owed_b: 200,
                         reward_infos: create_position_reward_infos(to_x64(3), 30),
+                        ..Default::default()
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2455,6 +2541,7 @@ mod calculate_modify_liquidity_unit_tests {
                     fee_growth_checkpoint_b: to_x64(198), // 220 - 20 - 2
                     fee_owed_b: 2000,
                     reward_infos: create_position_reward_infos(317116300394406928234, 9),
+                    ..Default::default()
                 }
             );
             test.apply_update(&update, 200);
@@ -2486,6 +2573,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(218), // 240 - 20 - 2
                         fee_owed_b: 5000,
                         reward_infos: create_position_reward_infos(318720365096468628374, 22),
+                        ..Default::default()
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2623,6 +2711,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -2751,6 +2840,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -2890,6 +2980,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3015,6 +3106,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(90),
                             fee_owed_b: 90000,
                             reward_infos: create_position_reward_infos(67079069358943824058, 3636),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3135,6 +3227,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(110),
                             fee_owed_b: 110000,
                             reward_infos: create_position_reward_infos(to_x64(5), 5000),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3269,6 +3362,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(90),
                             fee_owed_b: 90000,
                             reward_infos: create_position_reward_infos(to_x64(4), 4000),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3408,6 +3502,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_owed_b: 20000,
                             // 0.909 = 0.909 - (-3) - (0.909 - -2.0909)
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3539,6 +3634,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3683,6 +3779,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_owed_b: 20000,
                             // 0.909 = 0.909 - (-4) - (0.909 - (-3.0909))
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            ..Default::default()
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3713,3 +3810,219 @@ mod calculate_modify_liquidity_unit_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod modify_liquidity_invariant_proptests {
+    use super::_calculate_modify_liquidity;
+    use crate::orchestrator::ai_dex_orchestrator::next_ai_dex_reward_infos;
+    use crate::orchestrator::invariants::{
+        check_active_liquidity, check_owed_amounts_monotonic, check_tick_liquidity,
+    };
+    use crate::orchestrator::tick_orchestrator::next_tick_cross_update;
+    use crate::math::{add_liquidity_delta, Q64_RESOLUTION};
+    use crate::state::{
+        ai_dex_builder::AiDexBuilder, position_builder::PositionBuilder, AiDexPool, AiDexRewardInfo,
+        Position, Tick,
+    };
+    use anchor_lang::prelude::Pubkey;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    const TICK_BOUND: i32 = 64;
+    const NUM_POSITIONS: usize = 4;
+    const MAX_LIQUIDITY_DELTA: i128 = 1_000_000_000_000;
+    const MAX_FEE_GROWTH_DELTA: u128 = 1 << 70;
+    const SECONDS_PER_OP: u64 = 10;
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        ModifyLiquidity { position_index: usize, liquidity_delta: i128 },
+        Swap { target_tick_index: i32, fee_growth_delta_a: u128, fee_growth_delta_b: u128 },
+    }
+
+    fn op_strategy() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0..NUM_POSITIONS, -MAX_LIQUIDITY_DELTA..=MAX_LIQUIDITY_DELTA).prop_map(
+                |(position_index, liquidity_delta)| Op::ModifyLiquidity {
+                    position_index,
+                    liquidity_delta,
+                }
+            ),
+            (
+                -2 * TICK_BOUND..=2 * TICK_BOUND,
+                0..MAX_FEE_GROWTH_DELTA,
+                0..MAX_FEE_GROWTH_DELTA,
+            )
+                .prop_map(|(target_tick_index, fee_growth_delta_a, fee_growth_delta_b)| Op::Swap {
+                    target_tick_index,
+                    fee_growth_delta_a,
+                    fee_growth_delta_b,
+                }),
+        ]
+    }
+
+    /// A pool with its initialized ticks and positions, updated by the orchestrator the same way
+    /// the instructions update the accounts.
+    struct Simulation {
+        ai_dex: AiDexPool,
+        ticks: BTreeMap<i32, Tick>,
+        positions: Vec<Position>,
+        timestamp: u64,
+    }
+
+    impl Simulation {
+        fn new(ranges: &[(i32, i32)]) -> Self {
+            let reward_info = AiDexRewardInfo {
+                mint: Pubkey::new_unique(),
+                emissions_per_second_x64: 1 << Q64_RESOLUTION,
+                ..Default::default()
+            };
+            Simulation {
+                ai_dex: AiDexBuilder::new().tick_spacing(1).reward_info(0, reward_info).build(),
+                ticks: BTreeMap::new(),
+                positions: ranges
+                    .iter()
+                    .map(|&(lower, upper)| PositionBuilder::new(lower, upper).build())
+                    .collect(),
+                timestamp: 0,
+            }
+        }
+
+        fn modify_liquidity(&mut self, position_index: usize, liquidity_delta: i128) {
+            let position = &self.positions[position_index];
+            let (tick_lower_index, tick_upper_index) =
+                (position.tick_lower_index, position.tick_upper_index);
+            let tick_lower = self.ticks.get(&tick_lower_index).copied().unwrap_or_default();
+            let tick_upper = self.ticks.get(&tick_upper_index).copied().unwrap_or_default();
+
+            // Invalid changes, such as removing more liquidity than the position has, fail
+            // without modifying any state.
+            let Ok(update) = _calculate_modify_liquidity(
+                &self.ai_dex,
+                position,
+                &tick_lower,
+                &tick_upper,
+                tick_lower_index,
+                tick_upper_index,
+                liquidity_delta,
+                self.timestamp,
+            ) else {
+                return;
+            };
+
+            self.positions[position_index].update(&update.position_update);
+            self.set_tick(tick_lower_index, &update.tick_lower_update);
+            self.set_tick(tick_upper_index, &update.tick_upper_update);
+            self.ai_dex.reward_infos = update.reward_infos;
+            self.ai_dex.reward_last_updated_timestamp = self.timestamp;
+            self.ai_dex.liquidity = update.ai_dex_liquidity;
+        }
+
+        fn set_tick(&mut self, tick_index: i32, update: &crate::state::TickUpdate) {
+            let mut tick = Tick::default();
+            tick.update(update);
+            if tick.initialized {
+                self.ticks.insert(tick_index, tick);
+            } else {
+                self.ticks.remove(&tick_index);
+            }
+        }
+
+        /// Accrues swap fees to the liquidity in range, then moves the price to the target tick,
+        /// crossing the initialized ticks in between.
+        fn swap(&mut self, target_tick_index: i32, fee_growth_delta_a: u128, fee_growth_delta_b: u128) {
+            self.ai_dex.reward_infos = next_ai_dex_reward_infos(&self.ai_dex, self.timestamp).unwrap();
+            self.ai_dex.reward_last_updated_timestamp = self.timestamp;
+            if self.ai_dex.liquidity > 0 {
+                self.ai_dex.fee_growth_global_a =
+                    self.ai_dex.fee_growth_global_a.wrapping_add(fee_growth_delta_a);
+                self.ai_dex.fee_growth_global_b =
+                    self.ai_dex.fee_growth_global_b.wrapping_add(fee_growth_delta_b);
+            }
+
+            let tick_current_index = self.ai_dex.tick_current_index;
+            let crossed: Vec<i32> = if target_tick_index == tick_current_index {
+                Vec::new()
+            } else if target_tick_index > tick_current_index {
+                self.ticks
+                    .range(tick_current_index + 1..=target_tick_index)
+                    .map(|(&tick_index, _)| tick_index)
+                    .collect()
+            } else {
+                self.ticks
+                    .range(target_tick_index + 1..=tick_current_index)
+                    .rev()
+                    .map(|(&tick_index, _)| tick_index)
+                    .collect()
+            };
+            for tick_index in crossed {
+                let tick = self.ticks.get_mut(&tick_index).unwrap();
+                let update = next_tick_cross_update(
+                    tick,
+                    self.ai_dex.fee_growth_global_a,
+                    self.ai_dex.fee_growth_global_b,
+                    &self.ai_dex.reward_infos,
+                )
+                .unwrap();
+                tick.update(&update);
+                let liquidity_net = tick.liquidity_net;
+                let liquidity_delta =
+                    if target_tick_index > tick_current_index { liquidity_net } else { -liquidity_net };
+                self.ai_dex.liquidity =
+                    add_liquidity_delta(self.ai_dex.liquidity, liquidity_delta).unwrap();
+            }
+            self.ai_dex.tick_current_index = target_tick_index;
+        }
+
+        /// Updates the fees and rewards owed to every position with liquidity, as
+        /// `update_fees_and_rewards` does.
+        fn update_fees_and_rewards(&mut self) {
+            for position_index in 0..self.positions.len() {
+                if self.positions[position_index].liquidity > 0 {
+                    self.modify_liquidity(position_index, 0);
+                }
+            }
+        }
+
+        fn check_invariants(&self) -> std::result::Result<(), TestCaseError> {
+            let ticks = || self.ticks.iter().map(|(&tick_index, tick)| (tick_index, tick));
+            prop_assert_eq!(check_tick_liquidity(ticks()), Ok(()));
+            prop_assert_eq!(check_active_liquidity(&self.ai_dex, ticks()), Ok(()));
+            Ok(())
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_random_swap_and_modify_liquidity_sequences(
+            ranges in prop::collection::vec(
+                (-TICK_BOUND..TICK_BOUND, 1..TICK_BOUND)
+                    .prop_map(|(lower, width)| (lower, lower + width)),
+                NUM_POSITIONS,
+            ),
+            ops in prop::collection::vec(op_strategy(), 1..64),
+        ) {
+            let mut simulation = Simulation::new(&ranges);
+
+            for op in ops {
+                simulation.timestamp += SECONDS_PER_OP;
+                let positions_before = simulation.positions.clone();
+
+                match op {
+                    Op::ModifyLiquidity { position_index, liquidity_delta } => {
+                        simulation.modify_liquidity(position_index, liquidity_delta)
+                    }
+                    Op::Swap { target_tick_index, fee_growth_delta_a, fee_growth_delta_b } => {
+                        simulation.swap(target_tick_index, fee_growth_delta_a, fee_growth_delta_b)
+                    }
+                }
+                simulation.update_fees_and_rewards();
+
+                simulation.check_invariants()?;
+                for (before, after) in positions_before.iter().zip(simulation.positions.iter()) {
+                    prop_assert_eq!(check_owed_amounts_monotonic(before, after), Ok(()));
+                }
+            }
+        }
+    }
+}
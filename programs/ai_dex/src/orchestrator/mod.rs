@@ -3,3 +3,6 @@ pub mod position_orchestrator;
 pub mod swap_orchestrator;
 pub mod tick_orchestrator;
 pub mod ai_dex_orchestrator;
+
+#[cfg(any(test, feature = "invariant-checks"))]
+pub mod invariants;
@@ -1,9 +1,11 @@
 use crate::{
     errors::ErrorCode,
-    math::{add_liquidity_delta, checked_mul_shift_right},
-    state::{Position, PositionUpdate, NUM_REWARDS},
+    math::{add_liquidity_delta, checked_mul_div, growth_amount},
+    state::{AiDexPool, Position, PositionGraceCheckpoint, PositionGraceUpdate, PositionUpdate, NUM_REWARDS},
 };
 
+const BPS_DENOMINATOR: u128 = 10_000;
+
 /// Calculates the position update after modifying the liquidity of a position.
 /// 
 /// This function takes a `Position` struct representing the current state of the position,
@@ -32,13 +34,14 @@ pub fn next_position_modify_liquidity_update(
     let mut update = PositionUpdate::default();
 
     // Calculate fee deltas.
-    // If fee deltas overflow, default to a zero value. This means the position loses
-    // all fees earned since the last time the position was modified or fees collected.
+    // If fee deltas overflow, default to a zero value unless `u256-growth-math` is enabled. This
+    // means the position loses all fees earned since the last time the position was modified or
+    // fees collected.
     let growth_delta_a = fee_growth_inside_a.wrapping_sub(position.fee_growth_checkpoint_a);
-    let fee_delta_a = checked_mul_shift_right(position.liquidity, growth_delta_a).unwrap_or(0);
+    let fee_delta_a = growth_amount(position.liquidity, growth_delta_a)?;
 
     let growth_delta_b = fee_growth_inside_b.wrapping_sub(position.fee_growth_checkpoint_b);
-    let fee_delta_b = checked_mul_shift_right(position.liquidity, growth_delta_b).unwrap_or(0);
+    let fee_delta_b = growth_amount(position.liquidity, growth_delta_b)?;
 
     update.fee_growth_checkpoint_a = fee_growth_inside_a;
     update.fee_growth_checkpoint_b = fee_growth_inside_b;
@@ -52,12 +55,12 @@ pub fn next_position_modify_liquidity_update(
         let curr_reward_info = position.reward_infos[i];
 
         // Calculate reward delta.
-        // If reward delta overflows, default to a zero value. This means the position loses all
-        // rewards earned since the last time the position was modified or rewards were collected.
+        // If reward delta overflows, default to a zero value unless `u256-growth-math` is enabled.
+        // This means the position loses all rewards earned since the last time the position was
+        // modified or rewards were collected.
         let reward_growth_delta =
             reward_growth_inside.wrapping_sub(curr_reward_info.growth_inside_checkpoint);
-        let amount_owed_delta =
-            checked_mul_shift_right(position.liquidity, reward_growth_delta).unwrap_or(0);
+        let amount_owed_delta = growth_amount(position.liquidity, reward_growth_delta)?;
 
         update.reward_infos[i].growth_inside_checkpoint = reward_growth_inside;
 
@@ -71,14 +74,176 @@ pub fn next_position_modify_liquidity_update(
     Ok(update)
 }
 
+/// Applies the full-range reward boost to the rewards accrued by a position update.
+///
+/// Only the rewards earned since the previous checkpoint are boosted, so calling this once per
+/// update never compounds the boost on rewards that were already owed. The reward growth is
+/// spread over the liquidity weighted by the boost, see `AiDexPool::reward_growth_liquidity`, so
+/// the boost is paid out of the emissions rather than on top of them.
+///
+/// # Arguments
+///
+/// * `position` - The position before the update.
+/// * `update` - The position update whose owed rewards are boosted in place.
+/// * `tick_spacing` - The tick spacing of the pool, used to detect full-range positions.
+/// * `boosts_bps` - The boost for each reward, in basis points.
+pub fn apply_full_range_reward_boost(
+    position: &Position,
+    update: &mut PositionUpdate,
+    tick_spacing: u16,
+    boosts_bps: &[u16; NUM_REWARDS],
+) {
+    if boosts_bps.iter().all(|boost_bps| *boost_bps == 0) || !position.is_full_range(tick_spacing) {
+        return;
+    }
+
+    for (i, boost_bps) in boosts_bps.iter().enumerate() {
+        if *boost_bps == 0 {
+            continue;
+        }
+
+        let amount_owed_delta = update.reward_infos[i]
+            .amount_owed
+            .wrapping_sub(position.reward_infos[i].amount_owed);
+        // The boost is capped at 100%, so the bonus never exceeds the accrued delta and fits in a u64.
+        let bonus = (amount_owed_delta as u128 * *boost_bps as u128 / BPS_DENOMINATOR) as u64;
+
+        // Overflows allowed. Must collect rewards owed before overflow.
+        update.reward_infos[i].amount_owed = update.reward_infos[i].amount_owed.wrapping_add(bonus);
+    }
+}
+
+/// Tracks since when a position is out of range and advances its grace checkpoint.
+///
+/// The position is observed out of range from its previous checkpoint at the latest, so the
+/// out-of-range grace period of the position starts there unless it was already out of range.
+/// Nothing is tracked while no reward of the pool has an out-of-range grace.
+///
+/// # Arguments
+///
+/// * `position` - The position before the update.
+/// * `update` - The position update whose grace checkpoints are updated in place.
+/// * `ai_dex` - The pool of the position, providing the current tick and grace parameters.
+/// * `timestamp` - The timestamp of the update.
+pub fn track_position_out_of_range(
+    position: &Position,
+    update: &mut PositionUpdate,
+    ai_dex: &AiDexPool,
+    timestamp: u64,
+) {
+    if { ai_dex.out_of_range_grace_bps }.iter().all(|grace_bps| *grace_bps == 0) {
+        update.out_of_range_since = 0;
+        update.grace_checkpoint = 0;
+        return;
+    }
+
+    let tick_current_index = ai_dex.tick_current_index;
+    let in_range = position.tick_lower_index <= tick_current_index
+        && tick_current_index < position.tick_upper_index;
+
+    update.grace_checkpoint = timestamp;
+    update.out_of_range_since = if in_range {
+        0
+    } else if position.out_of_range_since != 0 {
+        position.out_of_range_since
+    } else if position.grace_checkpoint != 0 {
+        position.grace_checkpoint
+    } else {
+        timestamp
+    };
+}
+
+/// Calculates the next grace checkpoint of a position and the grace rewards it is owed.
+///
+/// The change of the reward growth outside the range of the position since its checkpoint is the
+/// reward growth it missed while out of range. The part of it within the grace period of each
+/// reward, pro rata over the time since the checkpoint, earns the grace share of the amount it
+/// would have paid the liquidity of the position, which is capped by its liquidity at the
+/// checkpoint. Positions with no checkpoint yet only take their first snapshot.
+///
+/// # Arguments
+///
+/// * `checkpoint` - The grace checkpoint of the position.
+/// * `position` - The position after its fees and rewards are updated.
+/// * `ai_dex` - The pool of the position after its rewards are updated.
+/// * `grace_enabled_timestamps` - The timestamps at which the grace of each reward was enabled.
+/// * `timestamp` - The timestamp of the update.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the `PositionGraceUpdate`, or an `ErrorCode` if an amount overflows.
+pub fn next_position_grace_update(
+    checkpoint: &PositionGraceCheckpoint,
+    position: &Position,
+    ai_dex: &AiDexPool,
+    grace_enabled_timestamps: &[u64; NUM_REWARDS],
+    timestamp: u64,
+) -> Result<PositionGraceUpdate, ErrorCode> {
+    let reward_infos = { ai_dex.reward_infos };
+    let mut update = PositionGraceUpdate {
+        checkpoint_timestamp: timestamp,
+        liquidity: position.liquidity,
+        ..Default::default()
+    };
+    for i in 0..NUM_REWARDS {
+        update.reward_growths_outside_x64[i] = reward_infos[i]
+            .growth_global_x64
+            .wrapping_sub(position.reward_infos[i].growth_inside_checkpoint);
+    }
+
+    let checkpoint_timestamp = checkpoint.checkpoint_timestamp;
+    if checkpoint_timestamp == 0 || timestamp <= checkpoint_timestamp {
+        return Ok(update);
+    }
+    let elapsed = u128::from(timestamp - checkpoint_timestamp);
+    let liquidity = checkpoint.liquidity.min(position.liquidity);
+    let grace_start = if position.out_of_range_since != 0 {
+        position.out_of_range_since
+    } else {
+        checkpoint_timestamp
+    };
+
+    let grace_bps = { ai_dex.out_of_range_grace_bps };
+    let grace_periods = { ai_dex.out_of_range_grace_periods };
+    for i in 0..NUM_REWARDS {
+        if grace_bps[i] == 0 || !reward_infos[i].initialized() {
+            continue;
+        }
+
+        let start = checkpoint_timestamp.max(grace_enabled_timestamps[i]);
+        let end = grace_start.saturating_add(grace_periods[i]).min(timestamp);
+        if end <= start {
+            continue;
+        }
+
+        // Growth is allowed to wrap around, as in the growth inside.
+        let missed_growth = update.reward_growths_outside_x64[i]
+            .wrapping_sub(checkpoint.reward_growths_outside_x64[i]);
+        // If the calculation overflows, the position does not earn any grace reward.
+        let grace_growth = checked_mul_div(missed_growth, u128::from(end - start), elapsed).unwrap_or(0);
+        let amount = growth_amount(liquidity, grace_growth)?;
+        // The grace share is capped at 100%, so the reward never exceeds the amount and fits in a u64.
+        update.grace_rewards[i] = (amount as u128 * grace_bps[i] as u128 / BPS_DENOMINATOR) as u64;
+    }
+
+    Ok(update)
+}
+
 #[cfg(test)]
 mod position_orchestrator_unit_tests {
     use crate::{
         math::{add_liquidity_delta, Q64_RESOLUTION},
-        state::{position_builder::PositionBuilder, Position, PositionRewardInfo, NUM_REWARDS},
+        state::{
+            position_builder::PositionBuilder, AiDexPool, AiDexRewardInfo, Position, PositionGraceCheckpoint,
+            PositionGraceUpdate, PositionRewardInfo, Tick, NUM_REWARDS,
+        },
     };
+    use anchor_lang::prelude::Pubkey;
 
-    use super::next_position_modify_liquidity_update;
+    use super::{
+        apply_full_range_reward_boost, next_position_grace_update, next_position_modify_liquidity_update,
+        track_position_out_of_range,
+    };
 
     #[test]
     fn ok_positive_liquidity_delta_fee_growth() {
@@ -153,6 +318,7 @@ mod position_orchestrator_unit_tests {
     }
 
     #[test]
+    #[cfg(not(feature = "u256-growth-math"))]
     fn fee_delta_overflow_defaults_zero() {
         let position = PositionBuilder::new(-10, 10)
             .liquidity(i64::MAX as u128)
@@ -173,6 +339,16 @@ mod position_orchestrator_unit_tests {
         assert_eq!(update.fee_owed_b, 20);
     }
 
+    #[test]
+    #[cfg(feature = "u256-growth-math")]
+    fn fee_delta_overflow_errors() {
+        let position = PositionBuilder::new(-10, 10).liquidity(i64::MAX as u128).build();
+        assert_eq!(
+            next_position_modify_liquidity_update(&position, 0, u128::MAX, 0, &[0, 0, 0]).unwrap_err(),
+            crate::errors::ErrorCode::GrowthOverflowError
+        );
+    }
+
     #[test]
     fn ok_reward_growth() {
         struct Test<'a> {
@@ -226,6 +402,7 @@ mod position_orchestrator_unit_tests {
                     },
                 ],
             },
+            #[cfg(not(feature = "u256-growth-math"))]
             Test {
                 name: "reward delta overflow defaults to zero",
                 position: &PositionBuilder::new(-10, 10)
@@ -293,6 +470,7 @@ mod position_orchestrator_unit_tests {
     }
 
     #[test]
+    #[cfg(not(feature = "u256-growth-math"))]
     fn reward_delta_overflow_defaults_zero() {
         let position = PositionBuilder::new(-10, 10)
             .liquidity(i64::MAX as u128)
@@ -337,4 +515,159 @@ mod position_orchestrator_unit_tests {
             ]
         )
     }
+
+    #[test]
+    fn ok_full_range_reward_boost() {
+        let (lower, upper) = Tick::full_range_indexes(64);
+        let position = PositionBuilder::new(lower, upper)
+            .liquidity(1000)
+            .reward_info(0, PositionRewardInfo { growth_inside_checkpoint: 0, amount_owed: 100 })
+            .build();
+        let mut update = next_position_modify_liquidity_update(
+            &position,
+            0,
+            0,
+            0,
+            &[1 << Q64_RESOLUTION, 1 << Q64_RESOLUTION, 0],
+        )
+        .unwrap();
+        apply_full_range_reward_boost(&position, &mut update, 64, &[5_000, 0, 10_000]);
+
+        assert_eq!(update.reward_infos[0].amount_owed, 100 + 1000 + 500);
+        assert_eq!(update.reward_infos[1].amount_owed, 1000);
+        assert_eq!(update.reward_infos[2].amount_owed, 0);
+    }
+
+    #[test]
+    fn ok_full_range_reward_boost_ignores_concentrated_position() {
+        let position = PositionBuilder::new(-64, 64).liquidity(1000).build();
+        let mut update = next_position_modify_liquidity_update(
+            &position,
+            0,
+            0,
+            0,
+            &[1 << Q64_RESOLUTION, 0, 0],
+        )
+        .unwrap();
+        apply_full_range_reward_boost(&position, &mut update, 64, &[10_000, 10_000, 10_000]);
+
+        assert_eq!(update.reward_infos[0].amount_owed, 1000);
+    }
+
+    fn grace_test_ai_dex(growth_global_x64: u128) -> AiDexPool {
+        let mut reward_infos = [AiDexRewardInfo::default(); NUM_REWARDS];
+        reward_infos[0] = AiDexRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64: 10 << Q64_RESOLUTION,
+            growth_global_x64,
+            ..Default::default()
+        };
+        reward_infos[1] = reward_infos[0];
+        AiDexPool {
+            reward_infos,
+            out_of_range_grace_bps: [5_000, 0, 10_000],
+            out_of_range_grace_periods: [100, 100, 100],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ok_track_position_out_of_range() {
+        let mut ai_dex = grace_test_ai_dex(0);
+        ai_dex.tick_current_index = 100;
+        let mut position = PositionBuilder::new(-64, 64).build();
+        let mut update = next_position_modify_liquidity_update(&position, 0, 0, 0, &[0; NUM_REWARDS]).unwrap();
+        track_position_out_of_range(&position, &mut update, &ai_dex, 1_000);
+        assert_eq!(update.out_of_range_since, 1_000);
+        assert_eq!(update.grace_checkpoint, 1_000);
+
+        // Out of range since the previous checkpoint at the latest
+        position.grace_checkpoint = 900;
+        track_position_out_of_range(&position, &mut update, &ai_dex, 1_000);
+        assert_eq!(update.out_of_range_since, 900);
+
+        position.out_of_range_since = 800;
+        ai_dex.tick_current_index = -100;
+        track_position_out_of_range(&position, &mut update, &ai_dex, 1_000);
+        assert_eq!(update.out_of_range_since, 800);
+
+        ai_dex.tick_current_index = 0;
+        track_position_out_of_range(&position, &mut update, &ai_dex, 1_050);
+        assert_eq!(update.out_of_range_since, 0);
+        assert_eq!(update.grace_checkpoint, 1_050);
+
+        // Nothing is tracked without any grace
+        ai_dex.tick_current_index = 100;
+        ai_dex.out_of_range_grace_bps = [0; NUM_REWARDS];
+        track_position_out_of_range(&position, &mut update, &ai_dex, 1_100);
+        assert_eq!(update.out_of_range_since, 0);
+        assert_eq!(update.grace_checkpoint, 0);
+    }
+
+    #[test]
+    fn ok_position_grace_update_first_snapshot() {
+        let ai_dex = grace_test_ai_dex(10 << Q64_RESOLUTION);
+        let position = PositionBuilder::new(-64, 64)
+            .liquidity(1000)
+            .reward_info(0, PositionRewardInfo { growth_inside_checkpoint: 4 << Q64_RESOLUTION, amount_owed: 0 })
+            .build();
+        let checkpoint = PositionGraceCheckpoint::default();
+
+        let update = next_position_grace_update(&checkpoint, &position, &ai_dex, &[0; NUM_REWARDS], 1_000).unwrap();
+        assert_eq!(
+            update,
+            PositionGraceUpdate {
+                checkpoint_timestamp: 1_000,
+                liquidity: 1000,
+                reward_growths_outside_x64: [6 << Q64_RESOLUTION, 10 << Q64_RESOLUTION, 0],
+                grace_rewards: [0; NUM_REWARDS],
+            }
+        );
+    }
+
+    #[test]
+    fn ok_position_grace_update_missed_growth() {
+        // 2 of reward growth missed while out of range over 200 seconds
+        let ai_dex = grace_test_ai_dex(3 << Q64_RESOLUTION);
+        let mut position = PositionBuilder::new(-64, 64).liquidity(1000).build();
+        position.out_of_range_since = 1_000;
+        let checkpoint = PositionGraceCheckpoint {
+            checkpoint_timestamp: 1_000,
+            liquidity: 2000,
+            reward_growths_outside_x64: [1 << Q64_RESOLUTION; NUM_REWARDS],
+            ..Default::default()
+        };
+
+        // Only the first half is within the grace period, at the lower liquidity, and at 50%.
+        // Reward 1 has no grace and reward 2 is not initialized.
+        let update = next_position_grace_update(&checkpoint, &position, &ai_dex, &[0; NUM_REWARDS], 1_200).unwrap();
+        assert_eq!(update.grace_rewards, [500, 0, 0]);
+        assert_eq!(update.liquidity, 1000);
+        assert_eq!(update.reward_growths_outside_x64[0], 3 << Q64_RESOLUTION);
+
+        // No grace reward before the grace was enabled
+        let update = next_position_grace_update(&checkpoint, &position, &ai_dex, &[1_050, 0, 0], 1_200).unwrap();
+        assert_eq!(update.grace_rewards, [250, 0, 0]);
+
+        // Nothing missed, nothing credited
+        let checkpoint = PositionGraceCheckpoint { reward_growths_outside_x64: [3 << Q64_RESOLUTION; NUM_REWARDS], ..checkpoint };
+        let update = next_position_grace_update(&checkpoint, &position, &ai_dex, &[0; NUM_REWARDS], 1_200).unwrap();
+        assert_eq!(update.grace_rewards, [0; NUM_REWARDS]);
+    }
+
+    #[test]
+    fn ok_position_grace_update_after_grace_period() {
+        let ai_dex = grace_test_ai_dex(3 << Q64_RESOLUTION);
+        let mut position = PositionBuilder::new(-64, 64).liquidity(1000).build();
+        position.out_of_range_since = 500;
+        let checkpoint = PositionGraceCheckpoint {
+            checkpoint_timestamp: 1_000,
+            liquidity: 1000,
+            reward_growths_outside_x64: [1 << Q64_RESOLUTION; NUM_REWARDS],
+            ..Default::default()
+        };
+
+        let update = next_position_grace_update(&checkpoint, &position, &ai_dex, &[0; NUM_REWARDS], 1_200).unwrap();
+        assert_eq!(update.grace_rewards, [0; NUM_REWARDS]);
+    }
 }
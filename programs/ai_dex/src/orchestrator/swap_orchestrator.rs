@@ -1,5 +1,6 @@
 use solana_program::msg;
 
+use crate::constants::EVENT_VERSION;
 use crate::{
     errors::ErrorCode,
     orchestrator::{
@@ -23,10 +24,16 @@ pub struct PostSwapUpdate {
     pub next_reward_infos: [AiDexRewardInfo; NUM_REWARDS],
     pub next_protocol_fee: u64,
     pub next_referral_fee: u64,
+    /// Total swap fee paid in the input token, including the protocol and referral shares.
+    pub fee_amount: u64,
+    /// Whether the swap stopped because it crossed every provided tick array rather than
+    /// filling the amount or reaching the price limit.
+    pub tick_arrays_exhausted: bool,
 }
 
 #[event]
 pub struct SwapTickUpdate {
+    pub event_version: u8,
     pub tick_array_start_index: i32,
     pub tick_index: i32,
     pub tick_update: TickUpdate,
@@ -56,11 +63,55 @@ pub fn swap(
     a_to_b: bool,
     timestamp: u64,
     referrer_swap_fee_rate: u16,
+) -> Result<PostSwapUpdate> {
+    swap_with_fee_rate(
+        ai_dex,
+        swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        ai_dex.fee_rate_at(timestamp),
+        referrer_swap_fee_rate,
+    )
+}
+
+/// Performs a swap operation on the AiDex pool at the given fee rate instead of the fee rate of
+/// the pool, such as a fee-free internal swap of the reinvestment engine.
+///
+/// Without liquidity in range, the swap moves the price to the price limit without trading, as
+/// long as the limit is within the tick arrays. It fails with `ZeroTradableLiquidity` if it
+/// would run past the tick arrays, or stop at their end, without liquidity or anything traded.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex instance representing the pool.
+/// * `swap_tick_sequence` - The mutable reference to the SwapTickSequence.
+/// * `amount` - The amount to swap.
+/// * `sqrt_price_limit` - The square root price limit.
+/// * `amount_specified_is_input` - Indicates whether the specified amount is the input amount.
+/// * `a_to_b` - Indicates the direction of the swap.
+/// * `timestamp` - The timestamp of the swap.
+/// * `fee_rate` - The fee rate charged on the swap, in hundredths of a basis point.
+/// * `referrer_swap_fee_rate` - The share of the protocol fee paid to the swap referral.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the `PostSwapUpdate` struct if the swap is successful, or an `ErrorCode` if there is an error.
+pub fn swap_with_fee_rate(
+    ai_dex: &AiDexPool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+    fee_rate: u16,
+    referrer_swap_fee_rate: u16,
 ) -> Result<PostSwapUpdate> {
     // Check if the square root price limit is within the valid range
     if sqrt_price_limit < MIN_SQRT_PRICE_X64 || sqrt_price_limit > MAX_SQRT_PRICE_X64 {
-        msg!("it is here, in the orchestrator");
-        msg!("sqrt_price_limit: {}", sqrt_price_limit);
         return Err(ErrorCode::SqrtPriceOutOfBoundsError.into());
     }
 
@@ -76,10 +127,9 @@ pub fn swap(
         return Err(ErrorCode::NoTradableAmountError.into());
     }
 
-    // Get the tick spacing and fee rate from the AiDex instance
+    // Get the tick spacing from the AiDex instance
     let tick_spacing = ai_dex.tick_spacing;
-    let fee_rate = ai_dex.fee_rate;
-    let protocol_fee_rate = ai_dex.protocol_fee_rate;
+    let protocol_fee_rate = ai_dex.protocol_fee_rate_for_side(a_to_b);
 
     // Get the next reward infos
     let next_reward_infos = next_ai_dex_reward_infos(ai_dex, timestamp)?;
@@ -92,7 +142,9 @@ pub fn swap(
     let mut curr_liquidity = ai_dex.liquidity;
     let mut curr_protocol_fee: u64 = 0;
     let mut curr_referral_fee: u64 = 0;
+    let mut curr_fee_amount: u64 = 0;
     let mut curr_array_index: usize = 0;
+    let mut tick_arrays_exhausted = false;
     let mut curr_fee_growth_global_input = if a_to_b {
         ai_dex.fee_growth_global_a
     } else {
@@ -101,9 +153,20 @@ pub fn swap(
 
     // Loop until the amount is fully swapped or the square root price limit is reached
     while amount_remaining > 0 && sqrt_price_limit != curr_sqrt_price {
-        // Get the next initialized tick index and array index
-        let (next_array_index, next_tick_index) = swap_tick_sequence
-            .get_next_initialized_tick_index(curr_tick_index, tick_spacing, a_to_b, curr_array_index)?;
+        // Stop at the boundary of the last tick array if the sequence allows a partial fill
+        if swap_tick_sequence.is_exhausted(curr_array_index) {
+            tick_arrays_exhausted = true;
+            break;
+        }
+
+        // Get the next initialized tick index and array index. Without liquidity, a swap that
+        // runs past the tick arrays could not have traded in the arrays it is missing either.
+        let (next_array_index, next_tick_index) = match swap_tick_sequence
+            .get_next_initialized_tick_index(curr_tick_index, tick_spacing, a_to_b, curr_array_index)
+        {
+            Err(_) if curr_liquidity == 0 => return Err(ErrorCode::ZeroTradableLiquidity.into()),
+            next => next?,
+        };
 
         // Get the next tick's square root price and the target square root price
         let (next_tick_sqrt_price, sqrt_price_target) =
@@ -158,6 +221,9 @@ pub fn swap(
 
         curr_protocol_fee = next_protocol_fee;
         curr_referral_fee = next_referral_fee;
+        curr_fee_amount = curr_fee_amount
+            .checked_add(swap_computation.fee_amount)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
         curr_fee_growth_global_input = next_fee_growth_global_input;
 
         // Update the tick and liquidity if the next tick is initialized
@@ -183,6 +249,7 @@ pub fn swap(
                 )?;
 
                 emit!(SwapTickUpdate {
+                    event_version: EVENT_VERSION,
                     tick_array_start_index: swap_tick_sequence.get_start_tick_index(next_array_index),
                     tick_index: next_tick_index,
                     tick_update: update,
@@ -228,6 +295,11 @@ pub fn swap(
         curr_sqrt_price = swap_computation.next_price;
     }
 
+    // Only a swap stopping at its price limit may move the price without trading anything
+    if tick_arrays_exhausted && curr_liquidity == 0 && amount_remaining == amount {
+        return Err(ErrorCode::ZeroTradableLiquidity.into());
+    }
+
     // Calculate the amounts of token A and token B swapped
     let (amount_a, amount_b) = if a_to_b == amount_specified_is_input {
         (amount - amount_remaining, amount_calculated)
@@ -235,6 +307,9 @@ pub fn swap(
         (amount_calculated, amount - amount_remaining)
     };
 
+    // Reject swaps larger than the allowed share of the liquidity of the pool
+    check_max_swap_liquidity_share(ai_dex, if a_to_b { amount_a } else { amount_b }, a_to_b)?;
+
     // Calculate the fee growth based on the swap direction
     let fee_growth = if a_to_b {
         curr_fee_growth_global_input - ai_dex.fee_growth_global_a
@@ -255,9 +330,50 @@ pub fn swap(
         next_reward_infos,
         next_protocol_fee: curr_protocol_fee,
         next_referral_fee: curr_referral_fee,
+        fee_amount: curr_fee_amount,
+        tick_arrays_exhausted,
     })
 }
 
+/// Checks the input of a swap against the maximum swap size of the pool.
+///
+/// The limit is a share, `max_swap_liquidity_bps`, of the in-range reserve of the input token at
+/// the price and liquidity of the pool before the swap. It guards oracle-less pools whose price is
+/// read by other protocols against single-transaction manipulation.
+///
+/// # Arguments
+///
+/// * `ai_dex` - The AiDex pool before the swap.
+/// * `amount_in` - The input amount of the swap, fees included.
+/// * `a_to_b` - Indicates the direction of the swap.
+///
+/// # Errors
+///
+/// Returns `SwapExceedsMaxLiquidityShare` if the input exceeds the limit.
+fn check_max_swap_liquidity_share(ai_dex: &AiDexPool, amount_in: u64, a_to_b: bool) -> Result<()> {
+    let max_swap_liquidity_bps = ai_dex.max_swap_liquidity_bps;
+    if max_swap_liquidity_bps == 0 {
+        return Ok(());
+    }
+
+    // Virtual reserves of the current price range: L / sqrt(P) of token A, L * sqrt(P) of token B.
+    // Reserves too large to be represented cannot be exceeded by a u64 input.
+    let reserve_in = if a_to_b {
+        checked_mul_div(ai_dex.liquidity, TO_Q64, ai_dex.sqrt_price)
+    } else {
+        checked_mul_div(ai_dex.liquidity, ai_dex.sqrt_price, TO_Q64)
+    };
+    let Ok(reserve_in) = reserve_in else {
+        return Ok(());
+    };
+
+    let max_amount_in = reserve_in.saturating_mul(max_swap_liquidity_bps as u128) / MAX_SWAP_LIQUIDITY_BPS as u128;
+    if amount_in as u128 > max_amount_in {
+        return Err(ErrorCode::SwapExceedsMaxLiquidityShare.into());
+    }
+    Ok(())
+}
+
 /// Calculates the fees for the swap operation.
 ///
 /// # Arguments
@@ -398,6 +514,134 @@ fn get_next_sqrt_prices(
     (next_tick_price, next_sqrt_price_limit)
 }
 
+/// Quotes a swap on the AiDex pool without updating the pool or its tick arrays.
+///
+/// The quote traverses the initialized ticks of `tick_arrays`, in the direction of the swap, and
+/// stops at the end of the last one with the rest of the amount in `amount_remaining`. With
+/// `constant_product`, the quote instead stops at the first initialized tick, pricing the swap
+/// against the current liquidity only. This is exact and cheaper for the common case of a swap
+/// that does not cross a tick, and routers fall back to the full quote when `amount_remaining`
+/// is not 0.
+///
+/// The amounts exclude the transfer fees of the tokens and the integrator fee.
+///
+/// # Errors
+/// - `SqrtPriceOutOfBoundsError`: - The square root price limit is out of bounds.
+/// - `InvalidSqrtPriceLimitDirectionError`: - The square root price limit is on the wrong side of the price.
+/// - `NoTradableAmountError`: - The amount is 0.
+/// - `ZeroTradableLiquidity`: - The constant product quote has no liquidity to price the swap against.
+/// - `InvalidTickArraySequenceError`: - The tick arrays do not follow the price in the direction of the swap.
+pub fn quote_swap(
+    ai_dex: &AiDexPool,
+    tick_arrays: &[&dyn TickArrayType],
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+    constant_product: bool,
+) -> Result<ConstantProductQuote> {
+    if sqrt_price_limit < MIN_SQRT_PRICE_X64 || sqrt_price_limit > MAX_SQRT_PRICE_X64 {
+        return Err(ErrorCode::SqrtPriceOutOfBoundsError.into());
+    }
+    if (a_to_b && sqrt_price_limit > ai_dex.sqrt_price)
+        || (!a_to_b && sqrt_price_limit < ai_dex.sqrt_price)
+    {
+        return Err(ErrorCode::InvalidSqrtPriceLimitDirectionError.into());
+    }
+    if amount == 0 {
+        return Err(ErrorCode::NoTradableAmountError.into());
+    }
+    if constant_product && ai_dex.liquidity == 0 {
+        return Err(ErrorCode::ZeroTradableLiquidity.into());
+    }
+
+    let tick_spacing = ai_dex.tick_spacing;
+    let fee_rate = ai_dex.fee_rate_at(timestamp);
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+
+    let mut quote = ConstantProductQuote {
+        amount_in: 0,
+        amount_out: 0,
+        fee_amount: 0,
+        next_sqrt_price: ai_dex.sqrt_price,
+        amount_remaining: amount,
+    };
+    let mut curr_tick_index = ai_dex.tick_current_index;
+    let mut curr_liquidity = ai_dex.liquidity;
+    let mut curr_array_index: usize = 0;
+
+    while quote.amount_remaining > 0 && quote.next_sqrt_price != sqrt_price_limit {
+        let tick_array = match tick_arrays.get(curr_array_index) {
+            Some(tick_array) => *tick_array,
+            None => break,
+        };
+
+        // Without an initialized tick left in the array, the step ends at the end of the array
+        let next_tick_index = match tick_array.get_next_init_tick_index(curr_tick_index, tick_spacing, a_to_b)? {
+            Some(next_tick_index) => next_tick_index,
+            None if a_to_b => tick_array.start_tick_index().max(MIN_TICK_INDEX),
+            None => (tick_array.start_tick_index() + ticks_in_array - 1).min(MAX_TICK_INDEX),
+        };
+        let (next_tick_sqrt_price, sqrt_price_target) =
+            get_next_sqrt_prices(next_tick_index, sqrt_price_limit, a_to_b);
+
+        let step = get_constant_product_quote(
+            quote.amount_remaining,
+            fee_rate,
+            curr_liquidity,
+            quote.next_sqrt_price,
+            sqrt_price_target,
+            amount_specified_is_input,
+            a_to_b,
+        )?;
+        quote.amount_in = quote
+            .amount_in
+            .checked_add(step.amount_in)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        quote.amount_out = quote
+            .amount_out
+            .checked_add(step.amount_out)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        quote.fee_amount = quote
+            .fee_amount
+            .checked_add(step.fee_amount)
+            .ok_or(ErrorCode::AmountCalculationOverflowError)?;
+        quote.amount_remaining = step.amount_remaining;
+
+        if step.next_sqrt_price == next_tick_sqrt_price {
+            let next_tick = tick_array.get_tick(next_tick_index, tick_spacing).ok();
+            if let Some(next_tick) = next_tick.filter(|tick| tick.initialized) {
+                if constant_product {
+                    quote.next_sqrt_price = step.next_sqrt_price;
+                    break;
+                }
+                let signed_liquidity_net = if a_to_b {
+                    -next_tick.liquidity_net
+                } else {
+                    next_tick.liquidity_net
+                };
+                curr_liquidity = add_liquidity_delta(curr_liquidity, signed_liquidity_net)?;
+            }
+
+            let tick_offset = tick_array.tick_offset(next_tick_index, tick_spacing)?;
+            if (a_to_b && tick_offset == 0) || (!a_to_b && tick_offset == TICK_ARRAY_SIZE as isize - 1) {
+                curr_array_index += 1;
+            }
+            curr_tick_index = if a_to_b {
+                next_tick_index - 1
+            } else {
+                next_tick_index
+            };
+        } else if step.next_sqrt_price != quote.next_sqrt_price {
+            curr_tick_index = tick_index_from_sqrt_price(&step.next_sqrt_price);
+        }
+        quote.next_sqrt_price = step.next_sqrt_price;
+    }
+
+    Ok(quote)
+}
+
 #[cfg(test)]
 mod swap_liquidity_tests {
     use super::*;
@@ -1478,6 +1722,73 @@ mod swap_liquidity_tests {
         );
     }
 
+    #[test]
+    /// A swap that moves across towards the right on all tick-arrays, with a tick sequence set to
+    /// stop at its end.
+    /// |c1_____________|_________________|_________________|...limit
+    ///
+    /// Expectation:
+    /// The swap stops at the last tick of the last array and reports that the tick arrays were
+    /// exhausted, with part of the amount left unfilled.
+    fn sqrt_price_exceeds_tick_range_b_to_a_stop_at_end() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 500_000,
+            curr_tick_index: -322176, // c1
+            start_tick_index: -322176,
+            trade_amount: 100_000_000_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: false,
+            a_to_b: false,
+            fee_growth_global_a: 100,
+            fee_growth_global_b: 100,
+            reward_infos: create_ai_dex_reward_infos(100, 10),
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        tick_sequence.set_stop_at_end(true);
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        assert!(post_swap.tick_arrays_exhausted);
+        assert_eq!(post_swap.next_tick_index, -288385);
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_from_tick_index(-288385));
+        assert!(post_swap.amount_a < 100_000_000_000_000);
+        assert_eq!(post_swap.next_liquidity, 500_000);
+    }
+
+    #[test]
+    /// A swap that stops at the price limit within the tick arrays of a tick sequence set to stop
+    /// at its end.
+    ///
+    /// Expectation:
+    /// The swap does not report that the tick arrays were exhausted.
+    fn price_limit_within_tick_range_stop_at_end() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 500_000,
+            curr_tick_index: -322176,
+            start_tick_index: -322176,
+            trade_amount: 100_000_000_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(-300000),
+            amount_specified_is_input: false,
+            a_to_b: false,
+            reward_infos: create_ai_dex_reward_infos(100, 10),
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        tick_sequence.set_stop_at_end(true);
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        assert!(!post_swap.tick_arrays_exhausted);
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_from_tick_index(-300000));
+    }
+
     #[test]
     /// A swap that moves across towards the left on all tick-arrays
     /// with no initialized-ticks in the tick-range, but has the liquidity to support it,
@@ -2509,6 +2820,101 @@ mod swap_error_tests {
         swap_test_info.run(&mut tick_sequence, 100);
     }
 
+    #[test]
+    #[should_panic(expected = "ZeroTradableLiquidity")]
+    /// A swap on a pool without liquidity and without initialized ticks, with a price limit
+    /// outside of the tick-range.
+    /// limit...|____________|_________________|____c1__________|
+    ///
+    /// Expectation:
+    /// Fail on ZeroTradableLiquidity rather than on the tick sequence, as no tick arrays could
+    /// fill the swap.
+    fn zero_liquidity_price_limit_outside_tick_range_a_to_b() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 0,
+            curr_tick_index: 0, // c1
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: MIN_SQRT_PRICE_X64, // limit
+            amount_specified_is_input: true,
+            a_to_b: true,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        swap_test_info.run(&mut tick_sequence, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "ZeroTradableLiquidity")]
+    /// A swap on a pool without liquidity, with a tick sequence set to stop at its end.
+    /// |__c1__________|_________________|______________|...limit
+    ///
+    /// Expectation:
+    /// Fail on ZeroTradableLiquidity rather than moving the price to the end of the tick-range
+    /// without trading.
+    fn zero_liquidity_stop_at_end_b_to_a() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 0,
+            curr_tick_index: 0, // c1
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: MAX_SQRT_PRICE_X64, // limit
+            amount_specified_is_input: true,
+            a_to_b: false,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        tick_sequence.set_stop_at_end(true);
+        swap_test_info.run(&mut tick_sequence, 100);
+    }
+
+    #[test]
+    /// A swap on a pool without liquidity, with a price limit within the tick-range.
+    /// |__c1_____limit,c2____|_________________|______________|
+    ///
+    /// Expectation:
+    /// The swap moves the price to the limit without trading anything.
+    fn zero_liquidity_price_limit_within_tick_range_b_to_a() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 0,
+            curr_tick_index: 0, // c1
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(576), // limit
+            amount_specified_is_input: true,
+            a_to_b: false,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        assert_swap(
+            &post_swap,
+            &SwapTestExpectation {
+                traded_amount_a: 0,
+                traded_amount_b: 0,
+                end_tick_index: 576,
+                end_liquidity: 0,
+                end_reward_growths: [0, 0, 0],
+            },
+        );
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_from_tick_index(576));
+    }
+
     #[test]
     /// A swap with the pool's current tick index at sqrt-price 0.
     ///
@@ -2667,3 +3073,155 @@ mod swap_error_tests {
         swap_test_info.run(&mut tick_sequence, 100);
     }
 }
+
+#[cfg(test)]
+mod swap_max_liquidity_share_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    fn run_swap(trade_amount: u64, a_to_b: bool, max_swap_liquidity_bps: u16) -> Result<PostSwapUpdate> {
+        let mut swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 5_000_000_000,
+            curr_tick_index: 0,
+            start_tick_index: 0,
+            trade_amount,
+            sqrt_price_limit: sqrt_price_from_tick_index(if a_to_b { -20_000 } else { 20_000 }),
+            amount_specified_is_input: true,
+            a_to_b,
+            ..Default::default()
+        });
+        swap_test_info.ai_dex.max_swap_liquidity_bps = max_swap_liquidity_bps;
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        swap(
+            &swap_test_info.ai_dex,
+            &mut tick_sequence,
+            swap_test_info.trade_amount,
+            swap_test_info.sqrt_price_limit,
+            swap_test_info.amount_specified_is_input,
+            swap_test_info.a_to_b,
+            100,
+            0,
+        )
+    }
+
+    #[test]
+    /// At sqrt-price 1, both in-range reserves equal the liquidity, so 1% of them is 50_000_000.
+    fn swap_within_max_liquidity_share() {
+        assert!(run_swap(50_000_000, true, 100).is_ok());
+        assert!(run_swap(50_000_000, false, 100).is_ok());
+    }
+
+    #[test]
+    fn swap_exceeds_max_liquidity_share() {
+        assert_eq!(
+            run_swap(50_000_001, true, 100).unwrap_err(),
+            ErrorCode::SwapExceedsMaxLiquidityShare.into()
+        );
+        assert_eq!(
+            run_swap(50_000_001, false, 100).unwrap_err(),
+            ErrorCode::SwapExceedsMaxLiquidityShare.into()
+        );
+    }
+
+    #[test]
+    fn swap_without_max_liquidity_share() {
+        assert!(run_swap(1_000_000_000, true, 0).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod swap_protocol_fee_per_side_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    fn run_swap(a_to_b: bool) -> PostSwapUpdate {
+        let mut swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 5_000_000_000,
+            curr_tick_index: 0,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(if a_to_b { -20_000 } else { 20_000 }),
+            amount_specified_is_input: true,
+            a_to_b,
+            fee_rate: 10_000,
+            protocol_fee_rate: 1_000,
+            ..Default::default()
+        });
+        swap_test_info.ai_dex.update_protocol_fee_rates_per_side(0, 2_000).unwrap();
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        swap_test_info.run(&mut tick_sequence, 100)
+    }
+
+    #[test]
+    /// Fees of 1% on an input of 1_000_000 are 10_000, of which the protocol takes none in
+    /// token A and 20% in token B.
+    fn swap_takes_protocol_fee_of_input_side() {
+        assert_eq!(run_swap(true).next_protocol_fee, 0);
+        assert_eq!(run_swap(false).next_protocol_fee, 2_000);
+    }
+
+    #[test]
+    /// The total fee reported by the swap includes the protocol share.
+    fn swap_reports_total_fee_amount() {
+        let post_swap = run_swap(false);
+        assert_eq!(post_swap.fee_amount, 10_000);
+        assert_eq!(post_swap.fee_amount - post_swap.next_protocol_fee, 8_000);
+    }
+}
+
+#[cfg(test)]
+mod swap_with_fee_rate_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    #[test]
+    /// A swap at a zero fee rate overrides the fee rate of the pool and charges no LP or
+    /// protocol fee.
+    fn swap_with_zero_fee_rate_charges_no_fee() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 5_000_000_000,
+            curr_tick_index: 0,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(-20_000),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            fee_rate: 10_000,
+            protocol_fee_rate: 1_000,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = swap_with_fee_rate(
+            &swap_test_info.ai_dex,
+            &mut tick_sequence,
+            swap_test_info.trade_amount,
+            swap_test_info.sqrt_price_limit,
+            true,
+            true,
+            100,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(post_swap.amount_a, 1_000_000);
+        assert_eq!(post_swap.fee_amount, 0);
+        assert_eq!(post_swap.next_protocol_fee, 0);
+        assert_eq!(post_swap.next_fee_growth_global, { swap_test_info.ai_dex.fee_growth_global_a });
+    }
+}
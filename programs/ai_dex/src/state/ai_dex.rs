@@ -1,8 +1,9 @@
 use crate::{
     errors::ErrorCode,
     math::{
-        tick_index_from_sqrt_price, MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_SQRT_PRICE_X64,
-        MIN_SQRT_PRICE_X64,
+        checked_mul_div, checked_mul_div_round_up, tick_index_from_sqrt_price, FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_SQRT_PRICE_X64,
+        MAX_REFERRAL_REWARD_FEE_RATE, MIN_SQRT_PRICE_X64, PROTOCOL_FEE_RATE_MUL_VALUE,
+        Q64_RESOLUTION, REFERRAL_REWARD_FEE_RATE_MUL_VALUE,
     },
     util::to_timestamp_u64,
 };
@@ -84,11 +85,157 @@ pub struct AiDexPool {
 
     /// The reward information for each reward.
     pub reward_infos: [AiDexRewardInfo; NUM_REWARDS], // 384
+
+    /// Extra weight, in basis points, of full-range positions in the emissions of each reward.
+    pub full_range_reward_boost_bps: [u16; NUM_REWARDS], // 6
+
+    /// Fee, in basis points, charged on liquidity withdrawn from a temporary pool at the start of
+    /// the liquidity provision window. It decays linearly to zero at `end_timestamp_lp`.
+    pub early_exit_fee_bps: u16, // 2
+
+    /// Number of swaps executed against the pool.
+    pub swap_count: u64, // 8
+
+    /// Part of `swap_count` already added to the config totals by `sync_pool_stats`.
+    pub reported_swap_count: u64, // 8
+
+    /// Layout version of the pool account, upgraded by `migrate_account`.
+    pub version: u8, // 1
+
+    /// Share of the emissions, in basis points, escrowed for each reward for positions that went
+    /// out of range, and share of the reward growth they missed that they are credited from it.
+    pub out_of_range_grace_bps: [u16; NUM_REWARDS], // 6
+
+    /// Number of seconds after going out of range during which positions are credited the grace share.
+    pub out_of_range_grace_periods: [u64; NUM_REWARDS], // 24
+
+    /// Accumulated liquidity-seconds, used to compute the time-weighted average liquidity
+    /// between two observations. Overflows allowed, compared with wrapping arithmetic.
+    pub liquidity_cumulative: u128, // 16
+
+    /// Timestamp of the last liquidity observation.
+    pub liquidity_observation_timestamp: u64, // 8
+
+    /// Maximum number of slots between the posting of an oracle price update and its use, or 0
+    /// to only rely on the maximum age of the oracle account.
+    pub oracle_maximum_slot_lag: u64, // 8
+
+    /// Slot at which the last oracle price update used by the pool was posted.
+    pub last_oracle_update_slot: u64, // 8
+
+    /// Swap referral reward fee rate of the pool, or
+    /// `USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE` to follow the default of the config.
+    pub swap_referral_reward_fee_rate: u16, // 2
+
+    /// Maximum input of a swap, in basis points of the in-range reserve of the input token at the
+    /// price and liquidity before the swap, or 0 to not limit swaps.
+    pub max_swap_liquidity_bps: u16, // 2
+
+    /// 1 if the protocol fees are taken at `protocol_fee_rate_a` on fees in token A and
+    /// `protocol_fee_rate_b` on fees in token B, 0 if at `protocol_fee_rate` on both. A `u8`
+    /// rather than a `bool`, which the compiler moves ahead of the other fields of the packed
+    /// struct.
+    pub is_protocol_fee_rate_per_side: u8, // 1
+    pub protocol_fee_rate_a: u16, // 2
+    pub protocol_fee_rate_b: u16, // 2
+
+    /// Fee rate of a temporary pool at `start_timestamp_swap` when it follows a liquidity
+    /// bootstrapping fee schedule.
+    pub lbp_start_fee_rate: u16, // 2
+    /// Fee rate of a temporary pool once its liquidity bootstrapping fee schedule is over.
+    pub lbp_end_fee_rate: u16, // 2
+    /// Number of seconds over which the fee rate moves linearly from `lbp_start_fee_rate` to
+    /// `lbp_end_fee_rate`, or 0 if the pool charges `fee_rate`.
+    pub lbp_fee_schedule_duration: u32, // 4
+
+    /// Swap rate limiter attached to the pool, or the default pubkey if swaps are not rate
+    /// limited.
+    pub swap_rate_limiter: Pubkey, // 32
+
+    /// 1 if positions must span the full tick range whatever the tick spacing, so that the pool
+    /// prices like a constant-product pool, 0 otherwise.
+    pub is_full_range_only: u8, // 1
+
+    /// 1 if swaps and liquidity changes of the pool are checked against the blocklist of the
+    /// config, 0 otherwise.
+    pub is_blocklist_enabled: u8, // 1
+
+    /// 1 if swaps must record their fees in the fee analytics account of the pool, 0 otherwise.
+    pub is_fee_analytics_enabled: u8, // 1
+
+    /// Reinvestment fees of the positions of the pool, in total over the reinvestments accounts.
+    /// Each account is owed its share in its `ReinvestmentFeeEscrow` for the pool.
+    pub reinvestment_fee_owed_a: u64, // 8
+    pub reinvestment_fee_owed_b: u64, // 8
+
+    /// 1 if either token mint has a permanent delegate, which can move tokens out of the vaults
+    /// of the pool, 0 otherwise. Recorded at initialization so front-ends can warn LPs.
+    pub has_permanent_delegate_mint: u8, // 1
+
+    /// Share of the protocol fees of swaps owed to the staking rewards of the config, in basis
+    /// points.
+    pub staking_reward_share_bps: u16, // 2
+
+    /// Protocol fees of swaps owed to the staking rewards of the config.
+    pub staking_reward_owed_a: u64, // 8
+    pub staking_reward_owed_b: u64, // 8
+
+    /// Funder of the pool initialization, allowed to set the pool metadata. Default for pools
+    /// initialized before it was recorded.
+    pub creator: Pubkey, // 32
+
+    /// Maximum age, in seconds, of the last oracle price of the pool that swaps fall back to when
+    /// the price update is missing or rejected, or 0 to disable this degraded mode.
+    pub oracle_degraded_maximum_age: u64, // 8
+
+    /// 1 if protocol fee collections must record the collected amounts in the revenue snapshot
+    /// of the pool, 0 otherwise.
+    pub is_revenue_reporting_enabled: u8, // 1
+
+    /// Amounts, in raw token units, below which the withdrawals of `decrease_liquidity` are added
+    /// to the principal owed by the position rather than transferred, or 0 to transfer any amount.
+    pub dust_transfer_threshold_a: u32, // 4
+    pub dust_transfer_threshold_b: u32, // 4
+
+    /// Bit `i` is set while the emissions of reward `i` are paused by its reward authority. The
+    /// reward keeps its `emissions_per_second_x64` for when it resumes.
+    pub paused_rewards: u8, // 1
+
+    /// 1 while a handler holds the pool across its token transfers, 0 otherwise. The runtime
+    /// already rejects reentrant calls into the program, so this is a consistency check only.
+    pub is_locked: u8, // 1
+
+    /// Space reserved for future fields, so they can be added without growing the account.
+    pub reserved: [u64; 16], // 128
 }
 
 // Number of rewards supported by AiDex
 pub const NUM_REWARDS: usize = 3;
 
+// Maximum full-range reward boost, doubling the weight of full-range positions in the rewards
+pub const MAX_FULL_RANGE_REWARD_BOOST_BPS: u16 = 10_000;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+// Maximum out-of-range grace share, escrowing all the emissions of a reward
+pub const MAX_OUT_OF_RANGE_GRACE_BPS: u16 = 10_000;
+
+// Maximum out-of-range grace period (7 days)
+pub const MAX_OUT_OF_RANGE_GRACE_PERIOD: u64 = 604_800;
+
+// Share of the protocol fees owed that can be routed at once (100%)
+pub const MAX_PROTOCOL_FEE_SHARE_BPS: u16 = 10_000;
+
+// Maximum early-exit fee of temporary pools (10%)
+pub const MAX_EARLY_EXIT_FEE_BPS: u16 = 1_000;
+
+// Swap referral reward fee rate of pools following the default of their config. Zero, so pools
+// upgraded by `migrate_account` keep following the default.
+pub const USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE: u16 = 0;
+
+// Maximum swap size share, a swap consuming the whole in-range reserve of the input token
+pub const MAX_SWAP_LIQUIDITY_BPS: u16 = 10_000;
+
 /// The AiDex struct represents the state of the AiDex program.
 impl AiDexPool {
     /// The total length of the AiDex struct.
@@ -99,7 +246,50 @@ impl AiDexPool {
     + 1 // is_temporary_pool
     + 32 // oracle address
     + 8 // last_updated_oracle_timestamp
-    + 1; // is_oracle_pool
+    + 1 // is_oracle_pool
+    + 6 // full_range_reward_boost_bps
+    + 2 // early_exit_fee_bps
+    + 8 // swap_count
+    + 8 // reported_swap_count
+    + 1 // version
+    + 6 // out_of_range_grace_bps
+    + 24 // out_of_range_grace_periods
+    + 16 // liquidity_cumulative
+    + 8 // liquidity_observation_timestamp
+    + 8 // oracle_maximum_slot_lag
+    + 8 // last_oracle_update_slot
+    + 2 // swap_referral_reward_fee_rate
+    + 2 // max_swap_liquidity_bps
+    + 1 // is_protocol_fee_rate_per_side
+    + 2 // protocol_fee_rate_a
+    + 2 // protocol_fee_rate_b
+    + 2 // lbp_start_fee_rate
+    + 2 // lbp_end_fee_rate
+    + 4 // lbp_fee_schedule_duration
+    + 32 // swap_rate_limiter
+    + 1 // is_full_range_only
+    + 1 // is_blocklist_enabled
+    + 1 // is_fee_analytics_enabled
+    + 8 // reinvestment_fee_owed_a
+    + 8 // reinvestment_fee_owed_b
+    + 1 // has_permanent_delegate_mint
+    + 2 // staking_reward_share_bps
+    + 8 // staking_reward_owed_a
+    + 8 // staking_reward_owed_b
+    + 32 // creator
+    + 8 // oracle_degraded_maximum_age
+    + 1 // is_revenue_reporting_enabled
+    + 4 // dust_transfer_threshold_a
+    + 4 // dust_transfer_threshold_b
+    + 1 // paused_rewards
+    + 1 // is_locked
+    + 128; // reserved
+
+    /// The current layout version of a pool.
+    pub const CURRENT_VERSION: u8 = 9;
+
+    /// The offset of the version field in the account data.
+    pub const VERSION_OFFSET: usize = 8 + std::mem::offset_of!(AiDexPool, version);
 
     /// Returns an array of references to the seeds used for program address generation.
     pub fn seeds(&self) -> [&[u8]; 6] {
@@ -184,6 +374,9 @@ impl AiDexPool {
         token_mint_b: Pubkey,
         is_temporary_pool: bool,
         is_oracle_pool: bool,
+        is_full_range_only: bool,
+        has_permanent_delegate_mint: bool,
+        creator: Pubkey,
     ) -> Result<()> {
         if token_mint_a.ge(&token_mint_b) {
             return Err(ErrorCode::InvalidTokenMintOrderError.into());
@@ -212,7 +405,46 @@ impl AiDexPool {
         self.token_mint_b = token_mint_b;
         self.is_temporary_pool = is_temporary_pool;
         self.is_oracle_pool = is_oracle_pool;
+        self.is_full_range_only = u8::from(is_full_range_only);
+        self.has_permanent_delegate_mint = u8::from(has_permanent_delegate_mint);
+        self.creator = creator;
+        self.swap_referral_reward_fee_rate = USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE;
+        self.version = Self::CURRENT_VERSION;
+
+        Ok(())
+    }
+
+    /// Checks if positions of the pool must span the full tick range, because the pool was
+    /// created full-range only or its tick spacing is at least
+    /// `FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD`.
+    pub fn requires_full_range_positions(&self) -> bool {
+        self.is_full_range_only != 0 || self.tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD
+    }
+
+    /// Checks if `key` funded the pool initialization. Never true for pools initialized before
+    /// the creator was recorded.
+    pub fn is_creator(&self, key: &Pubkey) -> bool {
+        self.creator != Pubkey::default() && self.creator == *key
+    }
 
+    /// Checks that the price of the pool is within the bounds expected by a deposit, so that a
+    /// deposit quoted at one price does not land at another.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PoolPriceOutsideDepositBounds` if the square root price is below `min_sqrt_price`
+    /// or above `max_sqrt_price`.
+    pub fn check_sqrt_price_within(
+        &self,
+        min_sqrt_price: Option<u128>,
+        max_sqrt_price: Option<u128>,
+    ) -> Result<()> {
+        let sqrt_price = self.sqrt_price;
+        if min_sqrt_price.is_some_and(|min_sqrt_price| sqrt_price < min_sqrt_price)
+            || max_sqrt_price.is_some_and(|max_sqrt_price| sqrt_price > max_sqrt_price)
+        {
+            return Err(ErrorCode::PoolPriceOutsideDepositBounds.into());
+        }
         Ok(())
     }
 
@@ -295,12 +527,45 @@ impl AiDexPool {
             }
         }
         // Update rewards and liquidity
+        self.update_liquidity_cumulative(curr_timestamp);
         self.update_rewards(reward_infos, curr_timestamp);
         self.liquidity = liquidity;
     
         Ok(())
     }
 
+    /// Returns whether the pool is a temporary pool past the end of its liquidity provision
+    /// window, whose positions can be settled by anyone.
+    pub fn is_expired(&self, timestamp: u64) -> bool {
+        self.is_temporary_pool && timestamp > self.end_timestamp_lp
+    }
+
+    /// Updates the rewards and liquidity values of an expired temporary pool, when the liquidity
+    /// of one of its positions is settled after its liquidity provision window closed.
+    ///
+    /// # Parameters
+    /// - `reward_infos` - An array of all updated ai_dex rewards
+    /// - `liquidity` - The updated liquidity value
+    /// - `curr_timestamp` - The timestamp when the rewards were last updated
+    ///
+    /// # Errors
+    /// This function returns an error if the pool is not expired.
+    pub fn update_rewards_and_liquidity_after_expiry(
+        &mut self,
+        reward_infos: [AiDexRewardInfo; NUM_REWARDS],
+        liquidity: u128,
+        curr_timestamp: u64,
+    ) -> Result<()> {
+        if !self.is_expired(curr_timestamp) {
+            return Err(ErrorCode::PoolNotExpired.into());
+        }
+        self.update_liquidity_cumulative(curr_timestamp);
+        self.update_rewards(reward_infos, curr_timestamp);
+        self.liquidity = liquidity;
+
+        Ok(())
+    }
+
     /// Update the reward authority at the specified AiDex reward index.
     ///
     /// # Parameters
@@ -318,6 +583,56 @@ impl AiDexPool {
         Ok(())
     }
 
+    /// Update the full-range reward boost at the specified AiDex reward index.
+    ///
+    /// The growth a full-range position accrued before the change is boosted at the new rate
+    /// when the position is next updated, so the boost can only be raised while the reward has
+    /// not accrued any growth. The rewards must be updated up to the current timestamp first.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward to update.
+    /// - `boost_bps` - The extra weight, in basis points, of full-range positions in the emissions.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid, the boost exceeds the
+    /// maximum, or it is raised after the reward accrued growth.
+    pub fn update_full_range_reward_boost(&mut self, index: usize, boost_bps: u16) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        if boost_bps > MAX_FULL_RANGE_REWARD_BOOST_BPS {
+            return Err(ErrorCode::FullRangeRewardBoostExceedsMaximum.into());
+        }
+        if boost_bps > self.full_range_reward_boost_bps[index] && self.reward_infos[index].growth_global_x64 != 0 {
+            return Err(ErrorCode::FullRangeRewardBoostRaisedAfterGrowth.into());
+        }
+        self.full_range_reward_boost_bps[index] = boost_bps;
+
+        Ok(())
+    }
+
+    /// Update the out-of-range grace parameters at the specified AiDex reward index.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward to update.
+    /// - `grace_bps` - The share of the emissions, in basis points, escrowed for out-of-range positions.
+    /// - `grace_period` - The number of seconds after going out of range during which the share is accrued.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid or the parameters exceed their maximum.
+    pub fn update_out_of_range_grace(&mut self, index: usize, grace_bps: u16, grace_period: u64) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        if grace_bps > MAX_OUT_OF_RANGE_GRACE_BPS || grace_period > MAX_OUT_OF_RANGE_GRACE_PERIOD {
+            return Err(ErrorCode::OutOfRangeGraceExceedsMaximum.into());
+        }
+        self.out_of_range_grace_bps[index] = grace_bps;
+        self.out_of_range_grace_periods[index] = grace_period;
+
+        Ok(())
+    }
+
     /// Update the emissions for the specified AiDex reward index.
     ///
     /// # Parameters
@@ -344,6 +659,82 @@ impl AiDexPool {
         Ok(())
     }
 
+    /// Checks if the emissions of the reward at the specified index are paused.
+    pub fn is_reward_paused(&self, index: usize) -> bool {
+        self.paused_rewards & (1 << index) != 0
+    }
+
+    /// Returns the emissions per second the reward at the specified index currently accrues at,
+    /// zero while the reward is paused.
+    pub fn effective_emissions_per_second_x64(&self, index: usize) -> u128 {
+        if self.is_reward_paused(index) {
+            0
+        } else {
+            self.reward_infos[index].emissions_per_second_x64
+        }
+    }
+
+    /// Returns the part of the emissions per second of the reward at the specified index escrowed
+    /// for the out-of-range grace of positions, zero while the reward is paused.
+    pub fn grace_emissions_per_second_x64(&self, index: usize) -> u128 {
+        let grace_bps = u128::from(self.out_of_range_grace_bps[index]);
+        // If the calculation overflows, no emissions are escrowed.
+        checked_mul_div(self.effective_emissions_per_second_x64(index), grace_bps, BPS_DENOMINATOR).unwrap_or(0)
+    }
+
+    /// Returns the emissions per second of the reward at the specified index added to the reward
+    /// growth, that is the current emissions without the part escrowed for the out-of-range grace.
+    pub fn growth_emissions_per_second_x64(&self, index: usize) -> u128 {
+        self.effective_emissions_per_second_x64(index) - self.grace_emissions_per_second_x64(index)
+    }
+
+    /// Returns the liquidity the emissions of the reward at the specified index are spread over:
+    /// the in-range liquidity weighted by the full-range boost of the reward, rounded up.
+    ///
+    /// Full-range positions earn the boost on top of the reward growth, so weighting every unit
+    /// of liquidity by it keeps the boosted rewards within the emissions.
+    pub fn reward_growth_liquidity(&self, index: usize) -> u128 {
+        let boost_bps = u128::from(self.full_range_reward_boost_bps[index]);
+        let boost_liquidity =
+            checked_mul_div_round_up(self.liquidity, boost_bps, BPS_DENOMINATOR).unwrap_or(u128::MAX);
+        self.liquidity.saturating_add(boost_liquidity)
+    }
+
+    /// Pauses or resumes the emissions of the reward at the specified index, after accruing its
+    /// growth up to `timestamp` at the rate before the change.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the reward to pause or resume.
+    /// - `reward_infos` - An array of all updated ai_dex rewards.
+    /// - `timestamp` - The timestamp when the rewards were last updated.
+    /// - `is_paused` - Whether the emissions are paused from `timestamp` on.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid, the reward is not
+    /// initialized, or it is already in the requested state.
+    pub fn update_reward_paused(
+        &mut self,
+        index: usize,
+        reward_infos: [AiDexRewardInfo; NUM_REWARDS],
+        timestamp: u64,
+        is_paused: bool,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS || !self.reward_infos[index].initialized() {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        if self.is_reward_paused(index) == is_paused {
+            return Err(if is_paused {
+                ErrorCode::RewardAlreadyPaused.into()
+            } else {
+                ErrorCode::RewardNotPaused.into()
+            });
+        }
+        self.update_rewards(reward_infos, timestamp);
+        self.paused_rewards ^= 1 << index;
+
+        Ok(())
+    }
+
     /// Initializes the reward at the specified AiDex reward index.
     ///
     /// # Parameters
@@ -399,23 +790,54 @@ impl AiDexPool {
                 return Err(ErrorCode::SwapWindowClosed.into());
             }
         }
+        self.update_liquidity_cumulative(curr_timestamp);
         self.tick_current_index = tick_index;
         self.sqrt_price = sqrt_price;
         self.liquidity = liquidity;
         self.reward_infos = reward_infos;
         self.reward_last_updated_timestamp = curr_timestamp;
+        // Part of the protocol fee is owed to the staking rewards of the config
+        let staking_reward = self.staking_reward_share_of(protocol_fee);
         if is_token_fee_in_a {
             // Add fees taken via a
             self.fee_growth_global_a = fee_growth_global;
-            self.protocol_fee_owed_a += protocol_fee;
+            self.protocol_fee_owed_a += protocol_fee - staking_reward;
+            self.staking_reward_owed_a = self.staking_reward_owed_a.wrapping_add(staking_reward);
         } else {
             // Add fees taken via b
             self.fee_growth_global_b = fee_growth_global;
-            self.protocol_fee_owed_b += protocol_fee;
+            self.protocol_fee_owed_b += protocol_fee - staking_reward;
+            self.staking_reward_owed_b = self.staking_reward_owed_b.wrapping_add(staking_reward);
         }
+        self.swap_count = self.swap_count.wrapping_add(1);
         Ok(())
     }
 
+    /// Records a liquidity observation, accumulating the current liquidity over the time elapsed
+    /// since the previous observation. Must be called before the liquidity changes.
+    ///
+    /// # Parameters
+    /// - `timestamp` - The timestamp of the observation.
+    pub fn update_liquidity_cumulative(&mut self, timestamp: u64) {
+        if self.liquidity_observation_timestamp != 0 && timestamp > self.liquidity_observation_timestamp {
+            let elapsed = timestamp - self.liquidity_observation_timestamp;
+            // Overflows allowed, consumers take the difference of two observations.
+            self.liquidity_cumulative = self
+                .liquidity_cumulative
+                .wrapping_add(self.liquidity.wrapping_mul(elapsed as u128));
+        }
+        if timestamp > self.liquidity_observation_timestamp {
+            self.liquidity_observation_timestamp = timestamp;
+        }
+    }
+
+    /// Returns the swaps not yet added to the config totals and marks them as reported.
+    pub fn take_unreported_swap_count(&mut self) -> u64 {
+        let unreported_swap_count = self.swap_count.wrapping_sub(self.reported_swap_count);
+        self.reported_swap_count = self.swap_count;
+        unreported_swap_count
+    }
+
     /// Update the fee rate for the AiDex.
     ///
     /// # Parameters
@@ -444,22 +866,160 @@ impl AiDexPool {
     /// # Errors
     /// This function returns an error if the protocol fee rate exceeds the maximum protocol fee rate.
     /// This function returns an error if the protocol fee rate is unchanged.
+    ///
+    /// The rate applies to both tokens again if per-side protocol fee rates were set.
     pub fn update_protocol_fee_rate(&mut self, protocol_fee_rate: u16) -> Result<()> {
         if protocol_fee_rate > MAX_PROTOCOL_FEE_RATE {
             return Err(ErrorCode::ProtocolFeeRateExceededError.into());
         }
-        if protocol_fee_rate == self.protocol_fee_rate {
+        if protocol_fee_rate == self.protocol_fee_rate && self.is_protocol_fee_rate_per_side == 0 {
             return Err(ErrorCode::FeeRateUnchanged.into());
         }
         self.protocol_fee_rate = protocol_fee_rate;
+        self.is_protocol_fee_rate_per_side = 0;
 
         Ok(())
     }
 
-    /// Reset the protocol fees owed by the AiDex.
-    pub fn reset_protocol_fees_owed(&mut self) {
-        self.protocol_fee_owed_a = 0;
-        self.protocol_fee_owed_b = 0;
+    /// Update the protocol fee rates of the AiDex to distinct rates for each token.
+    ///
+    /// # Parameters
+    /// - `protocol_fee_rate_a` - The protocol fee rate of the fees in token A.
+    /// - `protocol_fee_rate_b` - The protocol fee rate of the fees in token B.
+    ///
+    /// # Errors
+    /// This function returns an error if a protocol fee rate exceeds the maximum protocol fee rate.
+    pub fn update_protocol_fee_rates_per_side(
+        &mut self,
+        protocol_fee_rate_a: u16,
+        protocol_fee_rate_b: u16,
+    ) -> Result<()> {
+        if protocol_fee_rate_a > MAX_PROTOCOL_FEE_RATE || protocol_fee_rate_b > MAX_PROTOCOL_FEE_RATE {
+            return Err(ErrorCode::ProtocolFeeRateExceededError.into());
+        }
+        self.protocol_fee_rate_a = protocol_fee_rate_a;
+        self.protocol_fee_rate_b = protocol_fee_rate_b;
+        self.is_protocol_fee_rate_per_side = 1;
+
+        Ok(())
+    }
+
+    /// Returns the protocol fee rate applied to the fees in token A or token B.
+    ///
+    /// # Parameters
+    /// - `is_token_fee_in_a` - Whether the fees are in token A, the input token of a_to_b swaps.
+    pub fn protocol_fee_rate_for_side(&self, is_token_fee_in_a: bool) -> u16 {
+        match (self.is_protocol_fee_rate_per_side != 0, is_token_fee_in_a) {
+            (false, _) => self.protocol_fee_rate,
+            (true, true) => self.protocol_fee_rate_a,
+            (true, false) => self.protocol_fee_rate_b,
+        }
+    }
+
+    /// Update the swap referral reward fee rate of the pool.
+    ///
+    /// # Parameters
+    /// - `swap_referral_reward_fee_rate` - The new rate, or
+    ///   `USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE` to follow the default of the config.
+    ///
+    /// # Errors
+    /// This function returns an error if the rate exceeds the maximum referral reward fee rate.
+    /// This function returns an error if the rate is unchanged.
+    pub fn update_swap_referral_reward_fee_rate(&mut self, swap_referral_reward_fee_rate: u16) -> Result<()> {
+        if swap_referral_reward_fee_rate > MAX_REFERRAL_REWARD_FEE_RATE {
+            return Err(ErrorCode::ReferralRewardFeeRateExceededError.into());
+        }
+        if swap_referral_reward_fee_rate == self.swap_referral_reward_fee_rate {
+            return Err(ErrorCode::FeeRateUnchanged.into());
+        }
+        self.swap_referral_reward_fee_rate = swap_referral_reward_fee_rate;
+
+        Ok(())
+    }
+
+    /// Returns the swap referral reward fee rate of the pool, resolving the config default.
+    ///
+    /// # Parameters
+    /// - `default_swap_referral_reward_fee_rate` - The default rate of the config of the pool.
+    pub fn resolve_swap_referral_reward_fee_rate(&self, default_swap_referral_reward_fee_rate: u16) -> u16 {
+        if self.swap_referral_reward_fee_rate == USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE {
+            default_swap_referral_reward_fee_rate
+        } else {
+            self.swap_referral_reward_fee_rate
+        }
+    }
+
+    /// Takes the share of the protocol fees earned by the LP referral of a position.
+    ///
+    /// The protocol fees generated by the position are derived from the fees collected by the
+    /// position and the protocol fee rate. The referral share of them is no longer owed as
+    /// protocol fees and can be moved out of the vault.
+    ///
+    /// # Parameters
+    /// - `fee_a` - The fees in token A collected by the position.
+    /// - `fee_b` - The fees in token B collected by the position.
+    /// - `lp_referral_fee_rate` - The share of the protocol fees earned by the referral, in basis points.
+    ///
+    /// # Returns
+    /// The referral fees in token A and token B.
+    pub fn take_lp_referral_fees(&mut self, fee_a: u64, fee_b: u64, lp_referral_fee_rate: u16) -> (u64, u64) {
+        let referral_fee = |fee: u64, protocol_fee_rate: u16, protocol_fee_owed: u64| -> u64 {
+            let lp_fee_rate = PROTOCOL_FEE_RATE_MUL_VALUE - protocol_fee_rate as u128;
+            let protocol_fee = fee as u128 * protocol_fee_rate as u128 / lp_fee_rate;
+            let referral_fee = protocol_fee * lp_referral_fee_rate as u128 / REFERRAL_REWARD_FEE_RATE_MUL_VALUE;
+            std::cmp::min(referral_fee, protocol_fee_owed as u128) as u64
+        };
+        let referral_fee_a = referral_fee(fee_a, self.protocol_fee_rate_for_side(true), self.protocol_fee_owed_a);
+        let referral_fee_b = referral_fee(fee_b, self.protocol_fee_rate_for_side(false), self.protocol_fee_owed_b);
+        self.protocol_fee_owed_a -= referral_fee_a;
+        self.protocol_fee_owed_b -= referral_fee_b;
+        (referral_fee_a, referral_fee_b)
+    }
+
+    /// Takes the protocol fees owed by the AiDex, up to the given amounts.
+    ///
+    /// The returned amounts are no longer owed as protocol fees and can be moved out of the vaults.
+    ///
+    /// # Parameters
+    /// - `max_amount_a` - The maximum amount of token A to take, or `None` for all of it.
+    /// - `max_amount_b` - The maximum amount of token B to take, or `None` for all of it.
+    pub fn take_protocol_fees_owed(&mut self, max_amount_a: Option<u64>, max_amount_b: Option<u64>) -> (u64, u64) {
+        let amount_a = max_amount_a.map_or(self.protocol_fee_owed_a, |max_amount| max_amount.min(self.protocol_fee_owed_a));
+        let amount_b = max_amount_b.map_or(self.protocol_fee_owed_b, |max_amount| max_amount.min(self.protocol_fee_owed_b));
+        self.protocol_fee_owed_a -= amount_a;
+        self.protocol_fee_owed_b -= amount_b;
+        (amount_a, amount_b)
+    }
+
+    /// Takes a share of the protocol fees owed for one of the pool tokens.
+    ///
+    /// The returned amount is no longer owed as protocol fees and can be moved out of the vault.
+    ///
+    /// # Parameters
+    /// - `is_token_a` - Whether the share is taken from the protocol fees owed in token A.
+    /// - `share_bps` - The share of the protocol fees owed, in basis points.
+    ///
+    /// # Errors
+    /// This function returns an error if the share exceeds 100%.
+    pub fn take_protocol_fee_share(&mut self, is_token_a: bool, share_bps: u16) -> Result<u64> {
+        if share_bps > MAX_PROTOCOL_FEE_SHARE_BPS {
+            return Err(ErrorCode::ProtocolFeeShareExceedsMaximum.into());
+        }
+
+        let protocol_fee_owed = if is_token_a {
+            self.protocol_fee_owed_a
+        } else {
+            self.protocol_fee_owed_b
+        };
+        let amount = (protocol_fee_owed as u128 * share_bps as u128 / MAX_PROTOCOL_FEE_SHARE_BPS as u128) as u64;
+
+        if is_token_a {
+            self.protocol_fee_owed_a = protocol_fee_owed - amount;
+        } else {
+            self.protocol_fee_owed_b = protocol_fee_owed - amount;
+        }
+
+        Ok(amount)
     }
 
     /// Add protocol fees owed by the AiDex.
@@ -468,6 +1028,80 @@ impl AiDexPool {
         self.protocol_fee_owed_b = self.protocol_fee_owed_b.wrapping_add(protocol_fee_b);
     }
 
+    /// Adds the reinvestment fees taken from the fees of a position.
+    ///
+    /// # Parameters
+    /// - `reinvestment_fee_a` - The reinvestment fee in token A.
+    /// - `reinvestment_fee_b` - The reinvestment fee in token B.
+    pub fn add_reinvestment_fees_owed(&mut self, reinvestment_fee_a: u64, reinvestment_fee_b: u64) {
+        self.reinvestment_fee_owed_a = self.reinvestment_fee_owed_a.wrapping_add(reinvestment_fee_a);
+        self.reinvestment_fee_owed_b = self.reinvestment_fee_owed_b.wrapping_add(reinvestment_fee_b);
+    }
+
+    /// Removes reinvestment fees collected from the escrow of a reinvestments account.
+    ///
+    /// The removed amounts are no longer owed and can be moved out of the vaults.
+    ///
+    /// # Parameters
+    /// - `reinvestment_fee_a` - The collected reinvestment fee in token A.
+    /// - `reinvestment_fee_b` - The collected reinvestment fee in token B.
+    pub fn remove_reinvestment_fees_owed(&mut self, reinvestment_fee_a: u64, reinvestment_fee_b: u64) {
+        self.reinvestment_fee_owed_a = self.reinvestment_fee_owed_a.saturating_sub(reinvestment_fee_a);
+        self.reinvestment_fee_owed_b = self.reinvestment_fee_owed_b.saturating_sub(reinvestment_fee_b);
+    }
+
+    /// Updates the share of the protocol fees of swaps owed to the staking rewards of the config.
+    ///
+    /// # Parameters
+    /// - `staking_reward_share_bps` - The share of the protocol fees, in basis points.
+    ///
+    /// # Errors
+    /// This function returns an error if the share exceeds 100%.
+    pub fn update_staking_reward_share_bps(&mut self, staking_reward_share_bps: u16) -> Result<()> {
+        if staking_reward_share_bps > MAX_PROTOCOL_FEE_SHARE_BPS {
+            return Err(ErrorCode::ProtocolFeeShareExceedsMaximum.into());
+        }
+        self.staking_reward_share_bps = staking_reward_share_bps;
+        Ok(())
+    }
+
+    /// Returns the part of a protocol fee owed to the staking rewards of the config.
+    fn staking_reward_share_of(&self, protocol_fee: u64) -> u64 {
+        (protocol_fee as u128 * self.staking_reward_share_bps as u128 / MAX_PROTOCOL_FEE_SHARE_BPS as u128) as u64
+    }
+
+    /// Takes all the protocol fees owed to the staking rewards of the config.
+    ///
+    /// The returned amounts are no longer owed and can be moved out of the vaults.
+    pub fn take_staking_rewards_owed(&mut self) -> (u64, u64) {
+        let amounts = (self.staking_reward_owed_a, self.staking_reward_owed_b);
+        self.staking_reward_owed_a = 0;
+        self.staking_reward_owed_b = 0;
+        amounts
+    }
+
+    /// Distributes donated amounts pro-rata to the in-range liquidity by growing the global fee growths.
+    ///
+    /// # Parameters
+    /// - `amount_a` - The amount of token A donated to the vault.
+    /// - `amount_b` - The amount of token B donated to the vault.
+    ///
+    /// # Errors
+    /// This function returns an error if the AiDex has no in-range liquidity to distribute to.
+    pub fn add_donation(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        if self.liquidity == 0 {
+            return Err(ErrorCode::DonationWithoutLiquidity.into());
+        }
+        // Overflows allowed, fee growths are compared with wrapping arithmetic.
+        self.fee_growth_global_a = self
+            .fee_growth_global_a
+            .wrapping_add(((amount_a as u128) << Q64_RESOLUTION) / self.liquidity);
+        self.fee_growth_global_b = self
+            .fee_growth_global_b
+            .wrapping_add(((amount_b as u128) << Q64_RESOLUTION) / self.liquidity);
+        Ok(())
+    }
+
     /// update the start timestamp for the liquidity provider
     pub fn update_start_timestamp_lp(&mut self, start_timestamp_lp: u64) {
         self.start_timestamp_lp = start_timestamp_lp;
@@ -476,6 +1110,116 @@ impl AiDexPool {
     pub fn update_end_timestamp_lp(&mut self, end_timestamp_lp: u64) {
         self.end_timestamp_lp = end_timestamp_lp;
     }
+
+    /// Updates the maximum swap size of the pool as a share of its in-range liquidity.
+    ///
+    /// # Errors
+    /// This function returns an error if the share exceeds `MAX_SWAP_LIQUIDITY_BPS`.
+    pub fn update_max_swap_liquidity_bps(&mut self, max_swap_liquidity_bps: u16) -> Result<()> {
+        if max_swap_liquidity_bps > MAX_SWAP_LIQUIDITY_BPS {
+            return Err(ErrorCode::MaxSwapLiquidityBpsExceedsMaximum.into());
+        }
+        self.max_swap_liquidity_bps = max_swap_liquidity_bps;
+
+        Ok(())
+    }
+
+    pub fn update_dust_transfer_thresholds(&mut self, dust_transfer_threshold_a: u32, dust_transfer_threshold_b: u32) {
+        self.dust_transfer_threshold_a = dust_transfer_threshold_a;
+        self.dust_transfer_threshold_b = dust_transfer_threshold_b;
+    }
+
+    /// Returns the parts of the withdrawn amounts below the dust transfer thresholds of the pool,
+    /// which are owed to the position rather than transferred.
+    pub fn dust_withdrawals(&self, withdrawn_a: u64, withdrawn_b: u64) -> (u64, u64) {
+        let dust_a = if withdrawn_a < self.dust_transfer_threshold_a as u64 { withdrawn_a } else { 0 };
+        let dust_b = if withdrawn_b < self.dust_transfer_threshold_b as u64 { withdrawn_b } else { 0 };
+        (dust_a, dust_b)
+    }
+
+    /// Updates the early-exit fee of the temporary pool.
+    ///
+    /// # Errors
+    /// This function returns an error if the pool is not temporary or the fee exceeds the maximum.
+    pub fn update_early_exit_fee_bps(&mut self, early_exit_fee_bps: u16) -> Result<()> {
+        if !self.is_temporary_pool {
+            return Err(ErrorCode::NotTemporaryPool.into());
+        }
+        if early_exit_fee_bps > MAX_EARLY_EXIT_FEE_BPS {
+            return Err(ErrorCode::EarlyExitFeeExceedsMaximum.into());
+        }
+        self.early_exit_fee_bps = early_exit_fee_bps;
+
+        Ok(())
+    }
+
+    /// Returns the early-exit fee rate in basis points at the given timestamp.
+    ///
+    /// The full rate applies up to `start_timestamp_lp` and decays linearly to zero at
+    /// `end_timestamp_lp`. Pools that are not temporary never charge an early-exit fee.
+    pub fn early_exit_fee_rate_at(&self, timestamp: u64) -> u16 {
+        let start_timestamp_lp = self.start_timestamp_lp;
+        let end_timestamp_lp = self.end_timestamp_lp;
+        if !self.is_temporary_pool || self.early_exit_fee_bps == 0 || timestamp >= end_timestamp_lp {
+            return 0;
+        }
+        if timestamp <= start_timestamp_lp {
+            return self.early_exit_fee_bps;
+        }
+
+        let remaining = (end_timestamp_lp - timestamp) as u128;
+        let duration = (end_timestamp_lp - start_timestamp_lp) as u128;
+        (self.early_exit_fee_bps as u128 * remaining / duration) as u16
+    }
+
+    /// Returns the early-exit fee charged on the given withdrawn amount at the given timestamp.
+    pub fn early_exit_fee_at(&self, amount: u64, timestamp: u64) -> u64 {
+        let fee_rate = self.early_exit_fee_rate_at(timestamp) as u128;
+        (amount as u128 * fee_rate / 10_000) as u64
+    }
+
+    /// Sets the liquidity bootstrapping fee schedule of the temporary pool.
+    ///
+    /// # Errors
+    /// This function returns an error if the pool is not temporary, a fee rate exceeds the
+    /// maximum, or the schedule has no duration.
+    pub fn initialize_lbp_fee_schedule(&mut self, lbp_fee_schedule: LbpFeeSchedule) -> Result<()> {
+        if !self.is_temporary_pool {
+            return Err(ErrorCode::NotTemporaryPool.into());
+        }
+        if lbp_fee_schedule.start_fee_rate > MAX_FEE_RATE || lbp_fee_schedule.end_fee_rate > MAX_FEE_RATE {
+            return Err(ErrorCode::FeeRateExceededError.into());
+        }
+        if lbp_fee_schedule.duration == 0 {
+            return Err(ErrorCode::InvalidLbpFeeSchedule.into());
+        }
+        self.lbp_start_fee_rate = lbp_fee_schedule.start_fee_rate;
+        self.lbp_end_fee_rate = lbp_fee_schedule.end_fee_rate;
+        self.lbp_fee_schedule_duration = lbp_fee_schedule.duration;
+
+        Ok(())
+    }
+
+    /// Returns the fee rate charged by swaps at the given timestamp.
+    ///
+    /// Pools following a liquidity bootstrapping fee schedule charge `lbp_start_fee_rate` up to
+    /// `start_timestamp_swap`, then a rate moving linearly to `lbp_end_fee_rate` over
+    /// `lbp_fee_schedule_duration` seconds. Other pools charge `fee_rate`.
+    pub fn fee_rate_at(&self, timestamp: u64) -> u16 {
+        let duration = self.lbp_fee_schedule_duration as u64;
+        if duration == 0 {
+            return self.fee_rate;
+        }
+        let elapsed = timestamp.saturating_sub(self.start_timestamp_swap);
+        if elapsed >= duration {
+            return self.lbp_end_fee_rate;
+        }
+
+        let start_fee_rate = self.lbp_start_fee_rate as i128;
+        let end_fee_rate = self.lbp_end_fee_rate as i128;
+        (start_fee_rate + (end_fee_rate - start_fee_rate) * elapsed as i128 / duration as i128) as u16
+    }
+
     /// update the start timestamp for the swap
     pub fn update_start_timestamp_swap(&mut self, start_timestamp_swap: u64) {
         self.start_timestamp_swap = start_timestamp_swap;
@@ -489,6 +1233,42 @@ impl AiDexPool {
         self.oracle_address = oracle_address;
     }
 
+    pub fn update_oracle_maximum_slot_lag(&mut self, oracle_maximum_slot_lag: u64) {
+        self.oracle_maximum_slot_lag = oracle_maximum_slot_lag;
+    }
+
+    pub fn update_oracle_degraded_maximum_age(&mut self, oracle_degraded_maximum_age: u64) {
+        self.oracle_degraded_maximum_age = oracle_degraded_maximum_age;
+    }
+
+    /// Checks if a swap can fall back to the last oracle price of the pool, because the degraded
+    /// mode is enabled and the price was refreshed at most `oracle_degraded_maximum_age` seconds
+    /// before `timestamp`.
+    pub fn can_swap_at_last_oracle_price(&self, timestamp: u64) -> bool {
+        self.oracle_degraded_maximum_age != 0
+            && timestamp.saturating_sub(self.last_updated_oracle_timestamp)
+                <= self.oracle_degraded_maximum_age
+    }
+
+    /// Records the slot of an oracle price update used by the pool.
+    ///
+    /// # Parameters
+    /// - `posted_slot` - The slot at which the price update was posted.
+    /// - `current_slot` - The current slot.
+    ///
+    /// # Errors
+    /// This function returns an error if the price update was posted more than
+    /// `oracle_maximum_slot_lag` slots ago.
+    pub fn record_oracle_update_slot(&mut self, posted_slot: u64, current_slot: u64) -> Result<()> {
+        if self.oracle_maximum_slot_lag != 0
+            && current_slot.saturating_sub(posted_slot) > self.oracle_maximum_slot_lag
+        {
+            return Err(ErrorCode::OraclePriceSlotLagExceeded.into());
+        }
+        self.last_oracle_update_slot = posted_slot;
+        Ok(())
+    }
+
     pub fn update_sqrt_price(&mut self, sqrt_price: u128) {
         let old_sqrt_price = self.sqrt_price;
         msg!("Old sqrt price: {}", old_sqrt_price);
@@ -510,6 +1290,34 @@ impl AiDexPool {
         Ok(oracle_account.price_feed_id.clone())
     }
 
+    /// Acquires the lock of the pool.
+    ///
+    /// # Errors
+    /// This function returns an error if the pool is already locked.
+    pub fn lock(&mut self) -> Result<()> {
+        self.check_not_locked()?;
+        self.is_locked = 1;
+
+        Ok(())
+    }
+
+    /// Releases the lock of the pool.
+    pub fn unlock(&mut self) {
+        self.is_locked = 0;
+    }
+
+    /// Checks that no handler currently holds the lock of the pool.
+    ///
+    /// # Errors
+    /// This function returns an error if the pool is locked.
+    pub fn check_not_locked(&self) -> Result<()> {
+        if self.is_locked != 0 {
+            return Err(ErrorCode::PoolReentrancyLocked.into());
+        }
+
+        Ok(())
+    }
+
     // TODO: add del method of the pool if it is temporary
 }
 
@@ -561,6 +1369,17 @@ impl AiDexRewardInfo {
     }
 }
 
+/// Liquidity bootstrapping fee schedule of a temporary pool, set at initialization.
+///
+/// The fee rate starts at `start_fee_rate` when swaps open and moves linearly to `end_fee_rate`
+/// over `duration` seconds, for instance from 10% down to 1% over the sale window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy, Debug, PartialEq)]
+pub struct LbpFeeSchedule {
+    pub start_fee_rate: u16,
+    pub end_fee_rate: u16,
+    pub duration: u32,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
 pub struct AiDexBumps {
     pub ai_dex_bump: u8,
@@ -579,6 +1398,514 @@ fn test_ai_dex_reward_info_initialized() {
     assert_eq!(reward_info.initialized(), true);
 }
 
+#[test]
+fn test_ai_dex_lock_and_unlock() {
+    let ai_dex = &mut AiDexPool::default();
+    assert!(ai_dex.check_not_locked().is_ok());
+
+    ai_dex.lock().unwrap();
+    assert_eq!(ai_dex.check_not_locked().unwrap_err(), ErrorCode::PoolReentrancyLocked.into());
+    assert_eq!(ai_dex.lock().unwrap_err(), ErrorCode::PoolReentrancyLocked.into());
+
+    ai_dex.unlock();
+    assert!(ai_dex.check_not_locked().is_ok());
+    assert!(ai_dex.lock().is_ok());
+}
+
+#[test]
+fn test_ai_dex_take_protocol_fee_share() {
+    let ai_dex = &mut AiDexPool {
+        protocol_fee_owed_a: 1_000,
+        protocol_fee_owed_b: 3,
+        ..Default::default()
+    };
+    assert_eq!(ai_dex.take_protocol_fee_share(true, 2_500).unwrap(), 250);
+    assert_eq!({ ai_dex.protocol_fee_owed_a }, 750);
+    assert_eq!(ai_dex.take_protocol_fee_share(false, 5_000).unwrap(), 1);
+    assert_eq!({ ai_dex.protocol_fee_owed_b }, 2);
+    assert_eq!(ai_dex.take_protocol_fee_share(false, 10_000).unwrap(), 2);
+    assert_eq!({ ai_dex.protocol_fee_owed_b }, 0);
+
+    assert_eq!(
+        ai_dex.take_protocol_fee_share(true, MAX_PROTOCOL_FEE_SHARE_BPS + 1).unwrap_err(),
+        ErrorCode::ProtocolFeeShareExceedsMaximum.into()
+    );
+}
+
+#[test]
+fn test_ai_dex_take_protocol_fees_owed() {
+    let ai_dex = &mut AiDexPool {
+        protocol_fee_owed_a: 1_000,
+        protocol_fee_owed_b: 3,
+        ..Default::default()
+    };
+    assert_eq!(ai_dex.take_protocol_fees_owed(Some(400), Some(10)), (400, 3));
+    assert_eq!({ ai_dex.protocol_fee_owed_a }, 600);
+    assert_eq!({ ai_dex.protocol_fee_owed_b }, 0);
+    assert_eq!(ai_dex.take_protocol_fees_owed(None, Some(0)), (600, 0));
+    assert_eq!({ ai_dex.protocol_fee_owed_a }, 0);
+}
+
+#[test]
+fn test_ai_dex_take_unreported_swap_count() {
+    let ai_dex = &mut AiDexPool {
+        swap_count: 5,
+        ..Default::default()
+    };
+    assert_eq!(ai_dex.take_unreported_swap_count(), 5);
+    assert_eq!(ai_dex.take_unreported_swap_count(), 0);
+
+    ai_dex.swap_count = 7;
+    assert_eq!(ai_dex.take_unreported_swap_count(), 2);
+    assert_eq!({ ai_dex.reported_swap_count }, 7);
+}
+
+#[test]
+fn test_ai_dex_early_exit_fee() {
+    let ai_dex = &mut AiDexPool {
+        start_timestamp_lp: 1_000,
+        end_timestamp_lp: 2_000,
+        ..Default::default()
+    };
+    assert_eq!(ai_dex.update_early_exit_fee_bps(500).unwrap_err(), ErrorCode::NotTemporaryPool.into());
+
+    ai_dex.is_temporary_pool = true;
+    assert_eq!(
+        ai_dex.update_early_exit_fee_bps(MAX_EARLY_EXIT_FEE_BPS + 1).unwrap_err(),
+        ErrorCode::EarlyExitFeeExceedsMaximum.into()
+    );
+    ai_dex.update_early_exit_fee_bps(500).unwrap();
+
+    assert_eq!(ai_dex.early_exit_fee_rate_at(500), 500);
+    assert_eq!(ai_dex.early_exit_fee_rate_at(1_000), 500);
+    assert_eq!(ai_dex.early_exit_fee_rate_at(1_500), 250);
+    assert_eq!(ai_dex.early_exit_fee_rate_at(2_000), 0);
+    assert_eq!(ai_dex.early_exit_fee_at(1_000_000, 1_500), 25_000);
+    assert_eq!(ai_dex.early_exit_fee_at(1_000_000, 2_500), 0);
+}
+
+#[test]
+fn test_ai_dex_lbp_fee_schedule() {
+    let ai_dex = &mut AiDexPool {
+        fee_rate: 3_000,
+        start_timestamp_swap: 1_000,
+        ..Default::default()
+    };
+    let lbp_fee_schedule = LbpFeeSchedule { start_fee_rate: 30_000, end_fee_rate: 3_000, duration: 1_000 };
+    assert_eq!(
+        ai_dex.initialize_lbp_fee_schedule(lbp_fee_schedule).unwrap_err(),
+        ErrorCode::NotTemporaryPool.into()
+    );
+    assert_eq!(ai_dex.fee_rate_at(1_500), 3_000);
+
+    ai_dex.is_temporary_pool = true;
+    assert_eq!(
+        ai_dex.initialize_lbp_fee_schedule(LbpFeeSchedule { duration: 0, ..lbp_fee_schedule }).unwrap_err(),
+        ErrorCode::InvalidLbpFeeSchedule.into()
+    );
+    assert_eq!(
+        ai_dex
+            .initialize_lbp_fee_schedule(LbpFeeSchedule { start_fee_rate: MAX_FEE_RATE + 1, ..lbp_fee_schedule })
+            .unwrap_err(),
+        ErrorCode::FeeRateExceededError.into()
+    );
+    ai_dex.initialize_lbp_fee_schedule(lbp_fee_schedule).unwrap();
+
+    assert_eq!(ai_dex.fee_rate_at(500), 30_000);
+    assert_eq!(ai_dex.fee_rate_at(1_000), 30_000);
+    assert_eq!(ai_dex.fee_rate_at(1_500), 16_500);
+    assert_eq!(ai_dex.fee_rate_at(2_000), 3_000);
+    assert_eq!(ai_dex.fee_rate_at(5_000), 3_000);
+}
+
+#[test]
+fn test_ai_dex_update_liquidity_cumulative() {
+    let ai_dex = &mut AiDexPool {
+        liquidity: 1_000,
+        ..Default::default()
+    };
+
+    // The first observation only records the timestamp
+    ai_dex.update_liquidity_cumulative(100);
+    assert_eq!({ ai_dex.liquidity_cumulative }, 0);
+    assert_eq!({ ai_dex.liquidity_observation_timestamp }, 100);
+
+    ai_dex.update_liquidity_cumulative(110);
+    assert_eq!({ ai_dex.liquidity_cumulative }, 10_000);
+
+    ai_dex.update_rewards_and_liquidity([AiDexRewardInfo::default(); NUM_REWARDS], 3_000, 115).unwrap();
+    assert_eq!({ ai_dex.liquidity_cumulative }, 15_000);
+
+    ai_dex.update_liquidity_cumulative(125);
+    assert_eq!({ ai_dex.liquidity_cumulative }, 45_000);
+    assert_eq!({ ai_dex.liquidity_observation_timestamp }, 125);
+
+    // Observations in the same second do not accrue
+    ai_dex.update_liquidity_cumulative(125);
+    assert_eq!({ ai_dex.liquidity_cumulative }, 45_000);
+}
+
+#[test]
+fn test_ai_dex_resolve_swap_referral_reward_fee_rate() {
+    let ai_dex = &mut AiDexPool::default();
+
+    // Pools follow the default of the config until a rate is set
+    assert_eq!(ai_dex.resolve_swap_referral_reward_fee_rate(500), 500);
+
+    ai_dex.update_swap_referral_reward_fee_rate(800).unwrap();
+    assert_eq!(ai_dex.resolve_swap_referral_reward_fee_rate(500), 800);
+
+    ai_dex.update_swap_referral_reward_fee_rate(USE_CONFIG_DEFAULT_SWAP_REFERRAL_REWARD_FEE_RATE).unwrap();
+    assert_eq!(ai_dex.resolve_swap_referral_reward_fee_rate(300), 300);
+
+    assert_eq!(
+        ai_dex.update_swap_referral_reward_fee_rate(MAX_REFERRAL_REWARD_FEE_RATE + 1).unwrap_err(),
+        ErrorCode::ReferralRewardFeeRateExceededError.into()
+    );
+}
+
+#[test]
+fn test_ai_dex_update_max_swap_liquidity_bps() {
+    let ai_dex = &mut AiDexPool::default();
+    ai_dex.update_max_swap_liquidity_bps(MAX_SWAP_LIQUIDITY_BPS).unwrap();
+    assert_eq!({ ai_dex.max_swap_liquidity_bps }, MAX_SWAP_LIQUIDITY_BPS);
+    assert_eq!(
+        ai_dex.update_max_swap_liquidity_bps(MAX_SWAP_LIQUIDITY_BPS + 1).unwrap_err(),
+        ErrorCode::MaxSwapLiquidityBpsExceedsMaximum.into()
+    );
+}
+
+#[test]
+fn test_ai_dex_protocol_fee_rates_per_side() {
+    let ai_dex = &mut AiDexPool {
+        protocol_fee_rate: 300,
+        ..Default::default()
+    };
+    assert_eq!(ai_dex.protocol_fee_rate_for_side(true), 300);
+    assert_eq!(ai_dex.protocol_fee_rate_for_side(false), 300);
+
+    // Take the protocol fees in token B only
+    ai_dex.update_protocol_fee_rates_per_side(0, 2_000).unwrap();
+    assert_eq!(ai_dex.protocol_fee_rate_for_side(true), 0);
+    assert_eq!(ai_dex.protocol_fee_rate_for_side(false), 2_000);
+
+    assert_eq!(
+        ai_dex.update_protocol_fee_rates_per_side(0, MAX_PROTOCOL_FEE_RATE + 1).unwrap_err(),
+        ErrorCode::ProtocolFeeRateExceededError.into()
+    );
+
+    // Setting the same single rate again leaves the per-side rates
+    ai_dex.update_protocol_fee_rate(300).unwrap();
+    assert_eq!(ai_dex.protocol_fee_rate_for_side(true), 300);
+    assert_eq!(ai_dex.protocol_fee_rate_for_side(false), 300);
+}
+
+#[test]
+fn test_ai_dex_requires_full_range_positions() {
+    let mut ai_dex = AiDexPool { tick_spacing: 64, ..Default::default() };
+    assert!(!ai_dex.requires_full_range_positions());
+
+    ai_dex.is_full_range_only = 1;
+    assert!(ai_dex.requires_full_range_positions());
+
+    let ai_dex = AiDexPool { tick_spacing: FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, ..Default::default() };
+    assert!(ai_dex.requires_full_range_positions());
+}
+
+#[test]
+fn test_ai_dex_check_sqrt_price_within() {
+    let ai_dex = AiDexPool { sqrt_price: 1 << 64, ..Default::default() };
+    ai_dex.check_sqrt_price_within(None, None).unwrap();
+    ai_dex.check_sqrt_price_within(Some(1 << 64), Some(1 << 64)).unwrap();
+    assert_eq!(
+        ai_dex.check_sqrt_price_within(Some((1 << 64) + 1), None).unwrap_err(),
+        ErrorCode::PoolPriceOutsideDepositBounds.into()
+    );
+    assert_eq!(
+        ai_dex.check_sqrt_price_within(None, Some((1 << 64) - 1)).unwrap_err(),
+        ErrorCode::PoolPriceOutsideDepositBounds.into()
+    );
+}
+
+#[test]
+fn test_ai_dex_len_matches_layout() {
+    // The pool is `repr(packed)` without `repr(C)`, so the compiler may move fields, and does
+    // move `bool` fields ahead. New fields must keep the version and the reserved space in place.
+    assert_eq!(AiDexPool::LEN, 8 + std::mem::size_of::<AiDexPool>());
+    // Pools are migrated by reading their version at this offset, so it must never move.
+    assert_eq!(AiDexPool::VERSION_OFFSET, 751);
+    assert_eq!(AiDexPool::LEN, 8 + std::mem::offset_of!(AiDexPool, reserved) + 128);
+}
+
+#[test]
+fn test_ai_dex_baseline_field_offsets() {
+    // Offsets of the fields of pools created before any layout change, with the two `bool`
+    // fields moved ahead by the compiler. New fields, `bool` ones included, must not move them.
+    assert_eq!(std::mem::offset_of!(AiDexPool, is_temporary_pool), 0);
+    assert_eq!(std::mem::offset_of!(AiDexPool, is_oracle_pool), 1);
+    assert_eq!(std::mem::offset_of!(AiDexPool, ai_dex_config), 2);
+    assert_eq!(std::mem::offset_of!(AiDexPool, ai_dex_bump), 34);
+    assert_eq!(std::mem::offset_of!(AiDexPool, tick_spacing), 35);
+    assert_eq!(std::mem::offset_of!(AiDexPool, tick_spacing_seed), 37);
+    assert_eq!(std::mem::offset_of!(AiDexPool, fee_rate), 39);
+    assert_eq!(std::mem::offset_of!(AiDexPool, protocol_fee_rate), 41);
+    assert_eq!(std::mem::offset_of!(AiDexPool, liquidity), 43);
+    assert_eq!(std::mem::offset_of!(AiDexPool, sqrt_price), 59);
+    assert_eq!(std::mem::offset_of!(AiDexPool, tick_current_index), 75);
+    assert_eq!(std::mem::offset_of!(AiDexPool, protocol_fee_owed_a), 79);
+    assert_eq!(std::mem::offset_of!(AiDexPool, protocol_fee_owed_b), 87);
+    assert_eq!(std::mem::offset_of!(AiDexPool, token_mint_a), 95);
+    assert_eq!(std::mem::offset_of!(AiDexPool, token_mint_b), 127);
+    assert_eq!(std::mem::offset_of!(AiDexPool, token_vault_a), 159);
+    assert_eq!(std::mem::offset_of!(AiDexPool, token_vault_b), 191);
+    assert_eq!(std::mem::offset_of!(AiDexPool, fee_growth_global_a), 223);
+    assert_eq!(std::mem::offset_of!(AiDexPool, fee_growth_global_b), 239);
+    assert_eq!(std::mem::offset_of!(AiDexPool, reward_last_updated_timestamp), 255);
+    assert_eq!(std::mem::offset_of!(AiDexPool, start_timestamp_lp), 263);
+    assert_eq!(std::mem::offset_of!(AiDexPool, start_timestamp_swap), 271);
+    assert_eq!(std::mem::offset_of!(AiDexPool, end_timestamp_swap), 279);
+    assert_eq!(std::mem::offset_of!(AiDexPool, end_timestamp_lp), 287);
+    assert_eq!(std::mem::offset_of!(AiDexPool, oracle_address), 295);
+    assert_eq!(std::mem::offset_of!(AiDexPool, last_updated_oracle_timestamp), 327);
+    assert_eq!(std::mem::offset_of!(AiDexPool, reward_infos), 335);
+    assert_eq!(std::mem::offset_of!(AiDexPool, full_range_reward_boost_bps), 719);
+}
+
+#[test]
+fn test_ai_dex_dust_withdrawals() {
+    let mut ai_dex = AiDexPool::default();
+    assert_eq!(ai_dex.dust_withdrawals(1, 0), (0, 0));
+
+    ai_dex.update_dust_transfer_thresholds(100, 10);
+    assert_eq!(ai_dex.dust_withdrawals(99, 10), (99, 0));
+    assert_eq!(ai_dex.dust_withdrawals(100, 9), (0, 9));
+    assert_eq!(ai_dex.dust_withdrawals(0, u64::MAX), (0, 0));
+}
+
+#[test]
+fn test_ai_dex_update_rewards_and_liquidity_after_expiry() {
+    let mut ai_dex = AiDexPool {
+        is_temporary_pool: true,
+        start_timestamp_lp: 100,
+        end_timestamp_lp: 200,
+        liquidity: 1_000,
+        ..Default::default()
+    };
+    let reward_infos = ai_dex.reward_infos;
+
+    assert!(!ai_dex.is_expired(200));
+    assert_eq!(
+        ai_dex.update_rewards_and_liquidity_after_expiry(reward_infos, 0, 200).unwrap_err(),
+        ErrorCode::PoolNotExpired.into()
+    );
+    assert_eq!(
+        ai_dex.update_rewards_and_liquidity(reward_infos, 0, 201).unwrap_err(),
+        ErrorCode::LiquidityProvisionWindowClosed.into()
+    );
+
+    assert!(ai_dex.is_expired(201));
+    ai_dex.update_rewards_and_liquidity_after_expiry(reward_infos, 0, 201).unwrap();
+    assert_eq!({ ai_dex.liquidity }, 0);
+    assert_eq!({ ai_dex.reward_last_updated_timestamp }, 201);
+
+    // Pools that are not temporary never expire
+    ai_dex.is_temporary_pool = false;
+    assert!(!ai_dex.is_expired(u64::MAX));
+}
+
+#[test]
+fn test_ai_dex_update_reward_paused() {
+    let mut ai_dex = AiDexPool::default();
+    ai_dex.reward_infos[0].mint = Pubkey::new_unique();
+    ai_dex.reward_infos[0].emissions_per_second_x64 = 10;
+    let reward_infos = ai_dex.reward_infos;
+
+    assert_eq!(
+        ai_dex.update_reward_paused(1, reward_infos, 1, true).unwrap_err(),
+        ErrorCode::InvalidRewardIndexError.into()
+    );
+    assert_eq!(
+        ai_dex.update_reward_paused(0, reward_infos, 1, false).unwrap_err(),
+        ErrorCode::RewardNotPaused.into()
+    );
+
+    ai_dex.update_reward_paused(0, reward_infos, 100, true).unwrap();
+    assert!(ai_dex.is_reward_paused(0));
+    assert_eq!({ ai_dex.reward_last_updated_timestamp }, 100);
+    assert_eq!(ai_dex.effective_emissions_per_second_x64(0), 0);
+    assert_eq!(
+        ai_dex.update_reward_paused(0, reward_infos, 101, true).unwrap_err(),
+        ErrorCode::RewardAlreadyPaused.into()
+    );
+
+    ai_dex.update_reward_paused(0, reward_infos, 200, false).unwrap();
+    assert!(!ai_dex.is_reward_paused(0));
+    assert_eq!(ai_dex.effective_emissions_per_second_x64(0), 10);
+}
+
+#[test]
+fn test_ai_dex_reinvestment_fees_owed_apart_from_protocol_fees() {
+    let mut ai_dex = AiDexPool { protocol_fee_owed_a: 7, ..Default::default() };
+    ai_dex.add_reinvestment_fees_owed(100, 200);
+    ai_dex.add_reinvestment_fees_owed(1, 2);
+    assert_eq!({ ai_dex.protocol_fee_owed_a }, 7);
+
+    ai_dex.remove_reinvestment_fees_owed(100, 200);
+    assert_eq!({ ai_dex.reinvestment_fee_owed_a }, 1);
+    assert_eq!({ ai_dex.reinvestment_fee_owed_b }, 2);
+    ai_dex.remove_reinvestment_fees_owed(1, 2);
+    assert_eq!({ ai_dex.reinvestment_fee_owed_a }, 0);
+    assert_eq!({ ai_dex.reinvestment_fee_owed_b }, 0);
+}
+
+#[test]
+fn test_ai_dex_swap_protocol_fees_owed_to_staking_rewards() {
+    let mut ai_dex = AiDexPool::default();
+    ai_dex.update_staking_reward_share_bps(2_500).unwrap();
+    ai_dex
+        .update_after_swap(0, 0, 1 << 64, 0, ai_dex.reward_infos, 1_001, true, 0)
+        .unwrap();
+    ai_dex
+        .update_after_swap(0, 0, 1 << 64, 0, ai_dex.reward_infos, 400, false, 0)
+        .unwrap();
+
+    // The share rounds down, in favor of the protocol fees
+    assert_eq!({ ai_dex.protocol_fee_owed_a }, 751);
+    assert_eq!({ ai_dex.protocol_fee_owed_b }, 300);
+    assert_eq!(ai_dex.take_staking_rewards_owed(), (250, 100));
+    assert_eq!(ai_dex.take_staking_rewards_owed(), (0, 0));
+
+    assert_eq!(
+        ai_dex.update_staking_reward_share_bps(MAX_PROTOCOL_FEE_SHARE_BPS + 1).unwrap_err(),
+        ErrorCode::ProtocolFeeShareExceedsMaximum.into()
+    );
+}
+
+#[test]
+fn test_ai_dex_take_lp_referral_fees() {
+    let ai_dex = &mut AiDexPool {
+        protocol_fee_rate: 2_000,
+        protocol_fee_owed_a: 1_000,
+        protocol_fee_owed_b: 10,
+        ..Default::default()
+    };
+
+    // 8_000 of LP fees imply 2_000 of protocol fees, of which the referral earns 10%
+    let (referral_fee_a, referral_fee_b) = ai_dex.take_lp_referral_fees(8_000, 8_000, 1_000);
+    assert_eq!(referral_fee_a, 200);
+    // Capped to the protocol fees owed
+    assert_eq!(referral_fee_b, 10);
+    assert_eq!({ ai_dex.protocol_fee_owed_a }, 800);
+    assert_eq!({ ai_dex.protocol_fee_owed_b }, 0);
+}
+
+#[test]
+fn test_ai_dex_record_oracle_update_slot() {
+    let ai_dex = &mut AiDexPool::default();
+
+    // Without a maximum slot lag, only the maximum age of the oracle applies
+    ai_dex.record_oracle_update_slot(10, 1_000).unwrap();
+    assert_eq!({ ai_dex.last_oracle_update_slot }, 10);
+
+    ai_dex.update_oracle_maximum_slot_lag(5);
+    ai_dex.record_oracle_update_slot(995, 1_000).unwrap();
+    assert_eq!({ ai_dex.last_oracle_update_slot }, 995);
+    assert_eq!(
+        ai_dex.record_oracle_update_slot(994, 1_000).unwrap_err(),
+        ErrorCode::OraclePriceSlotLagExceeded.into()
+    );
+    assert_eq!({ ai_dex.last_oracle_update_slot }, 995);
+}
+
+#[test]
+fn test_ai_dex_can_swap_at_last_oracle_price() {
+    let ai_dex = &mut AiDexPool { last_updated_oracle_timestamp: 1_000, ..Default::default() };
+    assert!(!ai_dex.can_swap_at_last_oracle_price(1_000));
+
+    ai_dex.update_oracle_degraded_maximum_age(60);
+    assert!(ai_dex.can_swap_at_last_oracle_price(1_000));
+    assert!(ai_dex.can_swap_at_last_oracle_price(1_060));
+    assert!(!ai_dex.can_swap_at_last_oracle_price(1_061));
+}
+
+#[test]
+fn test_ai_dex_add_donation() {
+    let ai_dex = &mut AiDexPool {
+        fee_growth_global_a: 5,
+        ..Default::default()
+    };
+    assert_eq!(
+        ai_dex.add_donation(1_000, 0).unwrap_err(),
+        ErrorCode::DonationWithoutLiquidity.into()
+    );
+
+    ai_dex.liquidity = 4_000;
+    ai_dex.add_donation(1_000, 2_000).unwrap();
+    assert_eq!({ ai_dex.fee_growth_global_a }, 5 + (1 << 62));
+    assert_eq!({ ai_dex.fee_growth_global_b }, 1 << 63);
+}
+
+#[test]
+fn test_ai_dex_update_out_of_range_grace() {
+    let ai_dex = &mut AiDexPool::default();
+    ai_dex.update_out_of_range_grace(2, 2_500, 3_600).unwrap();
+    assert_eq!({ ai_dex.out_of_range_grace_bps }, [0, 0, 2_500]);
+    assert_eq!({ ai_dex.out_of_range_grace_periods }, [0, 0, 3_600]);
+
+    assert_eq!(
+        ai_dex.update_out_of_range_grace(NUM_REWARDS, 2_500, 3_600).unwrap_err(),
+        ErrorCode::InvalidRewardIndexError.into()
+    );
+    assert_eq!(
+        ai_dex.update_out_of_range_grace(0, MAX_OUT_OF_RANGE_GRACE_BPS + 1, 3_600).unwrap_err(),
+        ErrorCode::OutOfRangeGraceExceedsMaximum.into()
+    );
+    assert_eq!(
+        ai_dex.update_out_of_range_grace(0, 2_500, MAX_OUT_OF_RANGE_GRACE_PERIOD + 1).unwrap_err(),
+        ErrorCode::OutOfRangeGraceExceedsMaximum.into()
+    );
+}
+
+#[test]
+fn test_ai_dex_update_full_range_reward_boost() {
+    let ai_dex = &mut AiDexPool::default();
+    ai_dex.update_full_range_reward_boost(1, 2_500).unwrap();
+    assert_eq!({ ai_dex.full_range_reward_boost_bps }, [0, 2_500, 0]);
+
+    assert_eq!(
+        ai_dex.update_full_range_reward_boost(NUM_REWARDS, 2_500).unwrap_err(),
+        ErrorCode::InvalidRewardIndexError.into()
+    );
+    assert_eq!(
+        ai_dex.update_full_range_reward_boost(0, MAX_FULL_RANGE_REWARD_BOOST_BPS + 1).unwrap_err(),
+        ErrorCode::FullRangeRewardBoostExceedsMaximum.into()
+    );
+
+    // Once the reward accrued growth, the boost can be lowered but not raised
+    ai_dex.reward_infos[1].growth_global_x64 = 1;
+    assert_eq!(
+        ai_dex.update_full_range_reward_boost(1, 2_501).unwrap_err(),
+        ErrorCode::FullRangeRewardBoostRaisedAfterGrowth.into()
+    );
+    ai_dex.update_full_range_reward_boost(1, 1_000).unwrap();
+    assert_eq!({ ai_dex.full_range_reward_boost_bps }, [0, 1_000, 0]);
+}
+
+#[test]
+fn test_ai_dex_reward_growth_liquidity() {
+    let ai_dex = &mut AiDexPool::default();
+    ai_dex.liquidity = 10_001;
+    assert_eq!(ai_dex.reward_growth_liquidity(0), 10_001);
+
+    ai_dex.full_range_reward_boost_bps = [5_000, 10_000, 1];
+    assert_eq!(ai_dex.reward_growth_liquidity(0), 15_002);
+    assert_eq!(ai_dex.reward_growth_liquidity(1), 20_002);
+    assert_eq!(ai_dex.reward_growth_liquidity(2), 10_003);
+
+    ai_dex.liquidity = u128::MAX;
+    assert_eq!(ai_dex.reward_growth_liquidity(0), u128::MAX);
+}
+
 #[cfg(test)]
 pub mod ai_dex_builder {
     use super::{AiDexPool, AiDexRewardInfo, NUM_REWARDS};
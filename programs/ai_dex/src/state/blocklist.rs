@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Maximum number of addresses on a blocklist.
+pub const MAX_BLOCKLIST_ADDRESSES: usize = 128;
+
+/// Addresses blocked by the config authority from swapping through, or changing the liquidity of,
+/// the pools of a config that have the blocklist enabled.
+///
+/// There is a single blocklist per config, so a sanctioned address only has to be added once to
+/// be blocked on every flagged pool.
+#[account]
+#[derive(Default)]
+pub struct Blocklist {
+    pub ai_dex_config: Pubkey, // 32
+    pub addresses: Vec<Pubkey>, // 4 + 32 * MAX_BLOCKLIST_ADDRESSES
+    pub bump: u8, // 1
+}
+
+impl Blocklist {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 4 + 32 * MAX_BLOCKLIST_ADDRESSES // addresses
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, bump: u8) {
+        self.ai_dex_config = ai_dex_config;
+        self.addresses = Vec::new();
+        self.bump = bump;
+    }
+
+    /// Adds an address to the blocklist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is already blocked or the blocklist is full.
+    pub fn add_address(&mut self, address: Pubkey) -> Result<()> {
+        if self.is_blocked(&address) {
+            return Err(ErrorCode::AddressAlreadyBlocked.into());
+        }
+        if self.addresses.len() >= MAX_BLOCKLIST_ADDRESSES {
+            return Err(ErrorCode::BlocklistFull.into());
+        }
+        self.addresses.push(address);
+        Ok(())
+    }
+
+    /// Removes an address from the blocklist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is not blocked.
+    pub fn remove_address(&mut self, address: Pubkey) -> Result<()> {
+        let index = self
+            .addresses
+            .iter()
+            .position(|blocked_address| *blocked_address == address)
+            .ok_or(ErrorCode::AddressNotBlocked)?;
+        self.addresses.swap_remove(index);
+        Ok(())
+    }
+
+    /// Checks if an address is on the blocklist.
+    pub fn is_blocked(&self, address: &Pubkey) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
+#[cfg(test)]
+mod blocklist_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_address() {
+        let mut blocklist = Blocklist::default();
+        let address = Pubkey::new_unique();
+        blocklist.add_address(address).unwrap();
+        assert!(blocklist.is_blocked(&address));
+        assert_eq!(
+            blocklist.add_address(address).unwrap_err(),
+            ErrorCode::AddressAlreadyBlocked.into()
+        );
+
+        blocklist.remove_address(address).unwrap();
+        assert!(!blocklist.is_blocked(&address));
+        assert_eq!(
+            blocklist.remove_address(address).unwrap_err(),
+            ErrorCode::AddressNotBlocked.into()
+        );
+    }
+
+    #[test]
+    fn test_add_address_full() {
+        let mut blocklist = Blocklist::default();
+        for _ in 0..MAX_BLOCKLIST_ADDRESSES {
+            blocklist.add_address(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            blocklist.add_address(Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::BlocklistFull.into()
+        );
+    }
+
+    #[test]
+    fn test_len_fits_full_blocklist() {
+        let blocklist = Blocklist {
+            addresses: vec![Pubkey::default(); MAX_BLOCKLIST_ADDRESSES],
+            ..Default::default()
+        };
+        assert_eq!(8 + blocklist.try_to_vec().unwrap().len(), Blocklist::LEN);
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Maximum number of instruction variants whose compute units are registered.
+pub const MAX_COMPUTE_UNIT_ENTRIES: usize = 32;
+
+/// Compute units measured for a variant of an instruction of the program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct InstructionComputeUnits {
+    /// The Anchor discriminator of the instruction.
+    pub instruction_discriminator: [u8; 8], // 8
+    /// The path of the instruction measured, for example the number of tick arrays a swap
+    /// crosses. 0 is the base path.
+    pub variant: u8, // 1
+    pub compute_units: u32, // 4
+}
+
+impl InstructionComputeUnits {
+    pub const LEN: usize = 8 + 1 + 4;
+}
+
+/// Compute units of the instructions of the program, measured by benchmarking each release.
+///
+/// Clients read the registry to set the compute unit limit of their transactions, and so the
+/// priority fee they pay per compute unit, without simulating them first. The config authority
+/// updates it with `set_instruction_compute_units`.
+#[account]
+#[derive(Default)]
+pub struct ComputeUnitRegistry {
+    pub ai_dex_config: Pubkey, // 32
+    pub entries: Vec<InstructionComputeUnits>, // 4 + 13 * MAX_COMPUTE_UNIT_ENTRIES
+    pub last_update_timestamp: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl ComputeUnitRegistry {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 4 + InstructionComputeUnits::LEN * MAX_COMPUTE_UNIT_ENTRIES // entries
+    + 8 // last_update_timestamp
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, bump: u8) {
+        self.ai_dex_config = ai_dex_config;
+        self.entries = Vec::new();
+        self.bump = bump;
+    }
+
+    /// Returns the compute units registered for a variant of an instruction, if any.
+    pub fn compute_units(&self, instruction_discriminator: [u8; 8], variant: u8) -> Option<u32> {
+        self.entries
+            .iter()
+            .find(|entry| entry.instruction_discriminator == instruction_discriminator && entry.variant == variant)
+            .map(|entry| entry.compute_units)
+    }
+
+    /// Sets the compute units of a variant of an instruction. Setting 0 removes the variant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the variant is not registered yet and `MAX_COMPUTE_UNIT_ENTRIES`
+    /// variants already are.
+    pub fn set_compute_units(
+        &mut self,
+        instruction_discriminator: [u8; 8],
+        variant: u8,
+        compute_units: u32,
+        timestamp: u64,
+    ) -> Result<()> {
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.instruction_discriminator == instruction_discriminator && entry.variant == variant);
+        match (position, compute_units) {
+            (Some(index), 0) => {
+                self.entries.remove(index);
+            }
+            (Some(index), _) => {
+                self.entries[index].compute_units = compute_units;
+            }
+            (None, 0) => {}
+            (None, _) => {
+                if self.entries.len() >= MAX_COMPUTE_UNIT_ENTRIES {
+                    return Err(ErrorCode::ComputeUnitRegistryFull.into());
+                }
+                self.entries.push(InstructionComputeUnits {
+                    instruction_discriminator,
+                    variant,
+                    compute_units,
+                });
+            }
+        }
+        self.last_update_timestamp = timestamp;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod compute_unit_registry_tests {
+    use super::*;
+
+    #[test]
+    fn test_set_compute_units() {
+        let mut registry = ComputeUnitRegistry::default();
+        let swap = [1; 8];
+
+        registry.set_compute_units(swap, 0, 80_000, 10).unwrap();
+        registry.set_compute_units(swap, 1, 120_000, 11).unwrap();
+        registry.set_compute_units(swap, 0, 85_000, 12).unwrap();
+        assert_eq!(registry.compute_units(swap, 0), Some(85_000));
+        assert_eq!(registry.compute_units(swap, 1), Some(120_000));
+        assert_eq!(registry.compute_units([2; 8], 0), None);
+        assert_eq!(registry.last_update_timestamp, 12);
+
+        registry.set_compute_units(swap, 0, 0, 13).unwrap();
+        registry.set_compute_units([2; 8], 0, 0, 14).unwrap();
+        assert_eq!(registry.compute_units(swap, 0), None);
+        assert_eq!(registry.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_set_compute_units_bounds_entries() {
+        let mut registry = ComputeUnitRegistry::default();
+        for variant in 0..MAX_COMPUTE_UNIT_ENTRIES {
+            registry.set_compute_units([1; 8], variant as u8, 1, 0).unwrap();
+        }
+
+        assert_eq!(
+            registry.set_compute_units([2; 8], 0, 1, 0).unwrap_err(),
+            ErrorCode::ComputeUnitRegistryFull.into()
+        );
+        // Registered variants can still be updated
+        registry.set_compute_units([1; 8], 0, 2, 0).unwrap();
+
+        let mut data = Vec::new();
+        registry.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ComputeUnitRegistry::LEN);
+    }
+}
@@ -1,18 +1,48 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, math::{MAX_PROTOCOL_FEE_RATE, MAX_REFERRAL_REWARD_FEE_RATE}};
+use crate::{errors::ErrorCode, math::{MAX_INTEGRATOR_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_REFERRAL_REWARD_FEE_RATE}};
 
 #[account]
 pub struct AiDexConfig {
     pub config_authority: Pubkey,
     pub default_protocol_fee_rate: u16,
     pub default_swap_referral_reward_fee_rate: u16,
+    pub dust_position_liquidity_threshold: u128,
+    pub dust_position_inactivity_period: u64,
+    pub default_oracle_maximum_age: u64,
+    pub total_pools: u64,
+    pub total_swaps: u64,
+    pub max_integrator_fee_rate: u16,
+    pub version: u8,
+    pub protocol_fee_treasury: Pubkey,
+    pub fee_manager: Pubkey,
+    pub oracle_manager: Pubkey,
+    pub reward_manager: Pubkey,
+    /// Whether pools of the config reject token mints with a permanent delegate.
+    pub rejects_permanent_delegate_mints: bool,
+}
+
+/// Operational roles of a config, each allowed to run one group of config authority instructions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigRole {
+    /// Sets fee rates of the config, its fee tiers, pools and swap referrals.
+    FeeManager,
+    /// Sets oracle accounts and oracle price parameters.
+    OracleManager,
+    /// Sets reward authorities of pools.
+    RewardManager,
 }
 
 /// Implementation of the AiDexConfig struct.
 impl AiDexConfig {
     /// Length of the AiDexConfig struct.
-    pub const LEN: usize = 8 + 32 + 2 + 2;
+    pub const LEN: usize = 8 + 32 + 2 + 2 + 16 + 8 + 8 + 8 + 8 + 2 + 1 + 32 + 32 * 3 + 1;
+
+    /// The current layout version of a config.
+    pub const CURRENT_VERSION: u8 = 4;
+
+    /// The offset of the version field in the account data.
+    pub const VERSION_OFFSET: usize = Self::LEN - 1 - 32 * 3 - 32 - 1;
 
     /// Updates the fee authority.
     ///
@@ -40,6 +70,7 @@ impl AiDexConfig {
         default_swap_referral_reward_fee_rate: u16,
     ) -> Result<()> {
         self.config_authority = config_authority;
+        self.version = Self::CURRENT_VERSION;
         self.update_default_protocol_fee_rate(default_protocol_fee_rate)?;
         self.update_default_swap_referral_reward_fee_rate(default_swap_referral_reward_fee_rate)?;
         Ok(())
@@ -84,4 +115,203 @@ impl AiDexConfig {
         Ok(())
     }
 
+    /// Updates the maximum integrator fee rate that swaps may charge on top of their output.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_integrator_fee_rate` - The new maximum integrator fee rate in basis points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rate exceeds the maximum integrator fee rate.
+    pub fn update_max_integrator_fee_rate(&mut self, max_integrator_fee_rate: u16) -> Result<()> {
+        if max_integrator_fee_rate > MAX_INTEGRATOR_FEE_RATE {
+            return Err(ErrorCode::IntegratorFeeRateExceededError.into());
+        }
+        self.max_integrator_fee_rate = max_integrator_fee_rate;
+        Ok(())
+    }
+
+    /// Updates the parameters used to decide when a position can be liquidated as dust.
+    ///
+    /// # Arguments
+    ///
+    /// * `dust_position_liquidity_threshold` - Positions with liquidity strictly below this value are considered dust.
+    /// * `dust_position_inactivity_period` - The number of seconds a position must be inactive before it can be liquidated.
+    pub fn update_dust_position_params(
+        &mut self,
+        dust_position_liquidity_threshold: u128,
+        dust_position_inactivity_period: u64,
+    ) {
+        self.dust_position_liquidity_threshold = dust_position_liquidity_threshold;
+        self.dust_position_inactivity_period = dust_position_inactivity_period;
+    }
+
+    /// Updates the default maximum age of oracle prices inherited by oracle pools.
+    ///
+    /// # Arguments
+    ///
+    /// * `default_oracle_maximum_age` - The new default maximum age in seconds, or 0 to unset it.
+    pub fn update_default_oracle_maximum_age(&mut self, default_oracle_maximum_age: u64) {
+        self.default_oracle_maximum_age = default_oracle_maximum_age;
+    }
+
+    /// Returns the default maximum age of oracle prices, if one is set.
+    pub fn get_default_oracle_maximum_age(&self) -> Option<u64> {
+        if self.default_oracle_maximum_age == 0 {
+            return None;
+        }
+        Some(self.default_oracle_maximum_age)
+    }
+
+    /// Updates the owner of the token accounts protocol fees can be collected to.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol_fee_treasury` - The new treasury owner, or the default pubkey to allow any destination.
+    pub fn update_protocol_fee_treasury(&mut self, protocol_fee_treasury: Pubkey) {
+        self.protocol_fee_treasury = protocol_fee_treasury;
+    }
+
+    /// Checks that a protocol fee destination is owned by the treasury, if one is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a treasury is set and does not own the destination.
+    pub fn check_protocol_fee_destination(&self, destination_owner: Pubkey) -> Result<()> {
+        if self.protocol_fee_treasury != Pubkey::default() && destination_owner != self.protocol_fee_treasury {
+            return Err(ErrorCode::InvalidProtocolFeeDestination.into());
+        }
+        Ok(())
+    }
+
+    /// Updates the holder of a config role.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role to update.
+    /// * `holder` - The new holder of the role, or the default pubkey to leave it to the config authority alone.
+    pub fn update_role(&mut self, role: ConfigRole, holder: Pubkey) {
+        match role {
+            ConfigRole::FeeManager => self.fee_manager = holder,
+            ConfigRole::OracleManager => self.oracle_manager = holder,
+            ConfigRole::RewardManager => self.reward_manager = holder,
+        }
+    }
+
+    /// Returns the holder of a config role, or the default pubkey if the role is unset.
+    pub fn get_role(&self, role: ConfigRole) -> Pubkey {
+        match role {
+            ConfigRole::FeeManager => self.fee_manager,
+            ConfigRole::OracleManager => self.oracle_manager,
+            ConfigRole::RewardManager => self.reward_manager,
+        }
+    }
+
+    /// Returns whether a key may run the instructions of a config role. The config authority
+    /// keeps every role, so configs without role holders behave as before.
+    pub fn is_role_authority(&self, role: ConfigRole, key: Pubkey) -> bool {
+        let holder = self.get_role(role);
+        key == self.config_authority || (holder != Pubkey::default() && key == holder)
+    }
+
+    /// Updates whether pools of the config reject token mints with a permanent delegate.
+    pub fn update_permanent_delegate_mint_policy(&mut self, rejects_permanent_delegate_mints: bool) {
+        self.rejects_permanent_delegate_mints = rejects_permanent_delegate_mints;
+    }
+
+    /// Checks that a pool of the config may be initialized with a token mint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mint has a permanent delegate and the config rejects such mints.
+    pub fn check_permanent_delegate_mint_allowed(&self, has_permanent_delegate: bool) -> Result<()> {
+        if has_permanent_delegate && self.rejects_permanent_delegate_mints {
+            return Err(ErrorCode::PermanentDelegateMintRejected.into());
+        }
+        Ok(())
+    }
+
+    /// Counts a pool initialized under this config.
+    pub fn increment_total_pools(&mut self) {
+        self.total_pools = self.total_pools.wrapping_add(1);
+    }
+
+    /// Adds swaps reported by a pool of this config.
+    pub fn add_total_swaps(&mut self, swap_count: u64) {
+        self.total_swaps = self.total_swaps.wrapping_add(swap_count);
+    }
+}
+
+#[cfg(test)]
+mod config_role_tests {
+    use super::*;
+
+    fn test_config(config_authority: Pubkey) -> AiDexConfig {
+        AiDexConfig {
+            config_authority,
+            default_protocol_fee_rate: 0,
+            default_swap_referral_reward_fee_rate: 0,
+            dust_position_liquidity_threshold: 0,
+            dust_position_inactivity_period: 0,
+            default_oracle_maximum_age: 0,
+            total_pools: 0,
+            total_swaps: 0,
+            max_integrator_fee_rate: 0,
+            version: AiDexConfig::CURRENT_VERSION,
+            protocol_fee_treasury: Pubkey::default(),
+            fee_manager: Pubkey::default(),
+            oracle_manager: Pubkey::default(),
+            reward_manager: Pubkey::default(),
+            rejects_permanent_delegate_mints: false,
+        }
+    }
+
+    #[test]
+    fn test_unset_role_is_config_authority_only() {
+        let config_authority = Pubkey::new_unique();
+        let config = test_config(config_authority);
+        assert!(config.is_role_authority(ConfigRole::FeeManager, config_authority));
+        assert!(!config.is_role_authority(ConfigRole::FeeManager, Pubkey::new_unique()));
+        assert!(!config.is_role_authority(ConfigRole::FeeManager, Pubkey::default()));
+    }
+
+    #[test]
+    fn test_role_holder_is_scoped_to_its_role() {
+        let config_authority = Pubkey::new_unique();
+        let fee_manager = Pubkey::new_unique();
+        let mut config = test_config(config_authority);
+        config.update_role(ConfigRole::FeeManager, fee_manager);
+
+        assert_eq!(config.get_role(ConfigRole::FeeManager), fee_manager);
+        assert!(config.is_role_authority(ConfigRole::FeeManager, fee_manager));
+        assert!(config.is_role_authority(ConfigRole::FeeManager, config_authority));
+        assert!(!config.is_role_authority(ConfigRole::OracleManager, fee_manager));
+        assert!(!config.is_role_authority(ConfigRole::RewardManager, fee_manager));
+
+        config.update_role(ConfigRole::FeeManager, Pubkey::default());
+        assert!(!config.is_role_authority(ConfigRole::FeeManager, fee_manager));
+    }
+
+    #[test]
+    fn test_permanent_delegate_mint_policy() {
+        let mut config = test_config(Pubkey::new_unique());
+        assert!(config.check_permanent_delegate_mint_allowed(true).is_ok());
+
+        config.update_permanent_delegate_mint_policy(true);
+        assert!(config.check_permanent_delegate_mint_allowed(false).is_ok());
+        assert_eq!(
+            config.check_permanent_delegate_mint_allowed(true).unwrap_err(),
+            ErrorCode::PermanentDelegateMintRejected.into()
+        );
+    }
+
+    #[test]
+    fn test_version_offset() {
+        let config = test_config(Pubkey::new_unique());
+        let mut data = vec![];
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), AiDexConfig::LEN);
+        assert_eq!(data[AiDexConfig::VERSION_OFFSET], AiDexConfig::CURRENT_VERSION);
+    }
 }
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Program-level switches of features that ship disabled, set by the super admin.
+///
+/// A gated feature stays off until the super admin enables it on a deployment, so it can be
+/// released with the program and turned on, or back off, without an upgrade.
+#[account]
+#[derive(Default)]
+pub struct FeatureGate {
+    /// Bit `n` is set if the feature with index `n` is enabled.
+    pub enabled_features: u64, // 8
+    pub bump: u8, // 1
+}
+
+/// Features switched by the feature gate.
+///
+/// Variants are stored by index, so new features must be appended.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    /// Flash swaps, paying the output before the input is received.
+    FlashSwap,
+    /// Fee rates that follow the volatility of the pool.
+    DynamicFees,
+}
+
+impl Feature {
+    fn mask(self) -> u64 {
+        1 << self as u8
+    }
+}
+
+impl FeatureGate {
+    pub const LEN: usize = 8 // discriminator
+    + 8 // enabled_features
+    + 1; // bump
+
+    pub fn initialize(&mut self, bump: u8) {
+        self.enabled_features = 0;
+        self.bump = bump;
+    }
+
+    /// Enables or disables a feature.
+    pub fn set_feature_enabled(&mut self, feature: Feature, enabled: bool) {
+        if enabled {
+            self.enabled_features |= feature.mask();
+        } else {
+            self.enabled_features &= !feature.mask();
+        }
+    }
+
+    /// Returns whether a feature is enabled.
+    pub fn is_feature_enabled(&self, feature: Feature) -> bool {
+        self.enabled_features & feature.mask() != 0
+    }
+
+    /// Checks that a feature is enabled, for the handlers of the feature to consult.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the feature is disabled.
+    pub fn require_feature_enabled(&self, feature: Feature) -> Result<()> {
+        if !self.is_feature_enabled(feature) {
+            return Err(ErrorCode::FeatureDisabled.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod feature_gate_tests {
+    use super::*;
+
+    #[test]
+    fn test_features_are_switched_independently() {
+        let mut feature_gate = FeatureGate::default();
+        feature_gate.initialize(255);
+        assert!(!feature_gate.is_feature_enabled(Feature::FlashSwap));
+        assert!(feature_gate.require_feature_enabled(Feature::DynamicFees).is_err());
+
+        feature_gate.set_feature_enabled(Feature::DynamicFees, true);
+        assert!(feature_gate.is_feature_enabled(Feature::DynamicFees));
+        assert!(!feature_gate.is_feature_enabled(Feature::FlashSwap));
+        assert!(feature_gate.require_feature_enabled(Feature::DynamicFees).is_ok());
+
+        feature_gate.set_feature_enabled(Feature::FlashSwap, true);
+        feature_gate.set_feature_enabled(Feature::DynamicFees, false);
+        assert_eq!(feature_gate.enabled_features, 0b01);
+    }
+}
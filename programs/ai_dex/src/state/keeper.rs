@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Registration of a keeper allowed to run permissioned cranks, such as reinvestments, for the
+/// pools of a config. Each keeper is rate limited to a number of actions per window.
+#[account]
+pub struct Keeper {
+    pub ai_dex_config: Pubkey, // 32
+    pub authority: Pubkey, // 32
+    pub max_actions_per_window: u32, // 4
+    pub window_duration: u64, // 8
+    pub window_start_timestamp: u64, // 8
+    pub window_action_count: u32, // 4
+    pub total_action_count: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl Keeper {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 32 // authority
+    + 4 // max_actions_per_window
+    + 8 // window_duration
+    + 8 // window_start_timestamp
+    + 4 // window_action_count
+    + 8 // total_action_count
+    + 1; // bump
+
+    pub fn initialize(
+        &mut self,
+        ai_dex_config: Pubkey,
+        authority: Pubkey,
+        max_actions_per_window: u32,
+        window_duration: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.ai_dex_config = ai_dex_config;
+        self.authority = authority;
+        self.bump = bump;
+        self.update_rate_limit(max_actions_per_window, window_duration)
+    }
+
+    /// Updates the rate limit of the keeper.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_actions_per_window` - The maximum number of actions the keeper can run per window.
+    /// * `window_duration` - The duration of a window in seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either value is zero.
+    pub fn update_rate_limit(&mut self, max_actions_per_window: u32, window_duration: u64) -> Result<()> {
+        if max_actions_per_window == 0 || window_duration == 0 {
+            return Err(ErrorCode::InvalidKeeperRateLimit.into());
+        }
+        self.max_actions_per_window = max_actions_per_window;
+        self.window_duration = window_duration;
+        Ok(())
+    }
+
+    /// Records an action run by the keeper, starting a new window when the current one is over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keeper already ran the maximum number of actions in the current window.
+    pub fn record_action(&mut self, timestamp: u64) -> Result<()> {
+        if timestamp >= self.window_start_timestamp.saturating_add(self.window_duration) {
+            self.window_start_timestamp = timestamp;
+            self.window_action_count = 0;
+        }
+        if self.window_action_count >= self.max_actions_per_window {
+            return Err(ErrorCode::KeeperRateLimitExceeded.into());
+        }
+        self.window_action_count += 1;
+        self.total_action_count = self.total_action_count.wrapping_add(1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod keeper_tests {
+    use super::*;
+
+    fn new_keeper(max_actions_per_window: u32, window_duration: u64) -> Keeper {
+        Keeper {
+            ai_dex_config: Pubkey::default(),
+            authority: Pubkey::default(),
+            max_actions_per_window,
+            window_duration,
+            window_start_timestamp: 0,
+            window_action_count: 0,
+            total_action_count: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_record_action_rate_limited() {
+        let mut keeper = new_keeper(2, 60);
+        keeper.record_action(1_000).unwrap();
+        keeper.record_action(1_030).unwrap();
+        assert_eq!(
+            keeper.record_action(1_059).unwrap_err(),
+            ErrorCode::KeeperRateLimitExceeded.into()
+        );
+
+        // A new window starts once the previous one is over
+        keeper.record_action(1_060).unwrap();
+        assert_eq!(keeper.window_start_timestamp, 1_060);
+        assert_eq!(keeper.window_action_count, 1);
+        assert_eq!(keeper.total_action_count, 3);
+    }
+
+    #[test]
+    fn test_update_rate_limit_zero() {
+        let mut keeper = new_keeper(2, 60);
+        assert_eq!(
+            keeper.update_rate_limit(0, 60).unwrap_err(),
+            ErrorCode::InvalidKeeperRateLimit.into()
+        );
+        assert_eq!(
+            keeper.update_rate_limit(2, 0).unwrap_err(),
+            ErrorCode::InvalidKeeperRateLimit.into()
+        );
+    }
+}
@@ -8,6 +8,30 @@ pub mod super_admin;
 pub mod oracle;
 pub mod swap_referral;
 pub mod reinvestments;
+pub mod position_metadata;
+pub mod swap_commitment;
+pub mod keeper;
+pub mod position_data;
+pub mod pool_snapshot;
+pub mod token_badge;
+pub mod swap_rate_limiter;
+pub mod position_index;
+pub mod blocklist;
+pub mod pool_fee_analytics;
+pub mod feature_gate;
+pub mod staking_rewards;
+pub mod pool_metadata;
+pub mod tick_array_bounty;
+pub mod tick_array_funder;
+pub mod pool_revenue_snapshot;
+pub mod protocol_stats;
+pub mod compute_unit_registry;
+pub mod referral_fee_escrow;
+pub mod reward_emissions_policy;
+pub mod reinvestment_fee_escrow;
+pub mod oracle_price_feed_default;
+pub mod reward_grace_escrow;
+pub mod position_grace_checkpoint;
 
 pub use self::ai_dex::*;
 pub use ai_dex::NUM_REWARDS;
@@ -20,6 +44,30 @@ pub use super_admin::*;
 pub use oracle::*;
 pub use swap_referral::*;
 pub use reinvestments::*;
+pub use position_metadata::*;
+pub use swap_commitment::*;
+pub use keeper::*;
+pub use position_data::*;
+pub use pool_snapshot::*;
+pub use token_badge::*;
+pub use swap_rate_limiter::*;
+pub use position_index::*;
+pub use blocklist::*;
+pub use pool_fee_analytics::*;
+pub use feature_gate::*;
+pub use staking_rewards::*;
+pub use pool_metadata::*;
+pub use tick_array_bounty::*;
+pub use tick_array_funder::*;
+pub use pool_revenue_snapshot::*;
+pub use protocol_stats::*;
+pub use compute_unit_registry::*;
+pub use referral_fee_escrow::*;
+pub use reward_emissions_policy::*;
+pub use reinvestment_fee_escrow::*;
+pub use oracle_price_feed_default::*;
+pub use reward_grace_escrow::*;
+pub use position_grace_checkpoint::*;
 
 pub mod test;
 pub use test::*;
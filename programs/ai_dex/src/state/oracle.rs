@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, Price, PriceUpdateV2};
 use crate::{
-    errors::ErrorCode, math::calculate_initial_sqrt_price, state::MockPriceUpdate
+    errors::ErrorCode, math::calculate_initial_sqrt_price, state::MockPriceUpdate, util::to_timestamp_u64
 };
 use super::AiDexPool;
 
@@ -45,31 +45,60 @@ impl OracleAccount {
         token_decimals_a: u8,
         token_decimals_b: u8,
     ) -> Result<u128> {
+        let (sqrt_price, _) = self.get_new_sqrt_price_and_posted_slot(
+            price_update_account_info,
+            token_decimals_a,
+            token_decimals_b,
+            self.maximum_age,
+        )?;
+        Ok(sqrt_price)
+    }
+
+    /// Returns the maximum age of the prices an instruction accepts. Callers may ask for a
+    /// stricter maximum age than the one of the oracle, never a looser one.
+    pub fn resolve_maximum_age(&self, maximum_age_override: Option<u64>) -> u64 {
+        match maximum_age_override {
+            Some(maximum_age_override) => maximum_age_override.min(self.maximum_age),
+            None => self.maximum_age,
+        }
+    }
+
+    /// Reads the sqrt price from the price update account along with the slot the price update
+    /// was posted at. Mock price updates have no posted slot and report the current slot.
+    pub fn get_new_sqrt_price_and_posted_slot(
+        &mut self,
+        price_update_account_info: &AccountInfo,
+        token_decimals_a: u8,
+        token_decimals_b: u8,
+        maximum_age: u64,
+    ) -> Result<(u128, u64)> {
         let feed_id: [u8; 32] = get_feed_id_from_hex(&self.price_feed_id)?;
     
         // Determine which account type we're dealing with based on the owner
-        let price_data = if price_update_account_info.owner == &pyth_solana_receiver_sdk::ID {
+        let (price_data, posted_slot) = if price_update_account_info.owner == &pyth_solana_receiver_sdk::ID {
             // Deserialize as PriceUpdateV2
             let mut data = &price_update_account_info.data.borrow()[..];
             let price_update = PriceUpdateV2::try_deserialize(&mut data)
                 .map_err(|_| ErrorCode::InvalidPriceUpdateAccount)?;
-            price_update.get_price_no_older_than(
+            let price = price_update.get_price_no_older_than(
                 &Clock::get()?,
-                self.maximum_age,
+                maximum_age,
                 &feed_id,
-            )?
+            )?;
+            (price, price_update.posted_slot)
         } else if price_update_account_info.owner == &crate::ID {
             // Deserialize as MockPriceUpdate
             let mut data = &price_update_account_info.data.borrow()[..];
             let mock_price_update = MockPriceUpdate::try_deserialize(&mut data)
                 .map_err(|_| ErrorCode::InvalidPriceUpdateAccount)?;
             // Construct the Price struct
-            Price {
+            let price = Price {
                 price: mock_price_update.price,
                 conf: mock_price_update.conf,
                 exponent: mock_price_update.exponent,
                 publish_time: mock_price_update.publish_time,
-            }
+            };
+            (price, Clock::get()?.slot)
         } else {
             // Invalid owner
             return Err(ErrorCode::InvalidPriceUpdateAccount.into());
@@ -82,11 +111,12 @@ impl OracleAccount {
             price_data.exponent
         );
     
-        Ok(calculate_initial_sqrt_price(
+        let sqrt_price = calculate_initial_sqrt_price(
             &price_data,
             token_decimals_a,
             token_decimals_b,
-        )?)
+        )?;
+        Ok((sqrt_price, posted_slot))
     }
 
     pub fn update_sqrt_price(
@@ -96,19 +126,99 @@ impl OracleAccount {
         token_decimals_a: u8,
         token_decimals_b: u8,
     ) -> Result<()> {
-        let new_sqrt_price = self.get_new_sqrt_price(
+        self.update_sqrt_price_no_older_than(
+            ai_dex,
+            price_update_account_info,
+            token_decimals_a,
+            token_decimals_b,
+            None,
+        )
+    }
+
+    /// Updates the price of the pool like `update_sqrt_price`, rejecting prices older than the
+    /// stricter of the maximum age of the oracle and `maximum_age_override`.
+    pub fn update_sqrt_price_no_older_than(
+        &mut self,
+        ai_dex: &mut AiDexPool,
+        price_update_account_info: &AccountInfo,
+        token_decimals_a: u8,
+        token_decimals_b: u8,
+        maximum_age_override: Option<u64>,
+    ) -> Result<()> {
+        let (new_sqrt_price, posted_slot) = self.get_new_sqrt_price_and_posted_slot(
             price_update_account_info,
             token_decimals_a,
             token_decimals_b,
+            self.resolve_maximum_age(maximum_age_override),
         )?;
+        ai_dex.record_oracle_update_slot(posted_slot, Clock::get()?.slot)?;
         ai_dex.update_sqrt_price(new_sqrt_price);
         ai_dex.update_tick_current_index_by_sqrt_price(new_sqrt_price);
+        ai_dex.last_updated_oracle_timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
         Ok(())
     }
 
+    /// Updates the price of the pool for a swap like `update_sqrt_price_no_older_than`, falling
+    /// back to the last oracle price of the pool when the price update is missing or rejected and
+    /// the degraded mode of the pool allows it. Liquidity changes never fall back.
+    ///
+    /// Returns whether the swap runs in degraded mode, at the last oracle price.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the price update if the swap cannot fall back to the last price.
+    pub fn update_sqrt_price_or_degrade(
+        &mut self,
+        ai_dex: &mut AiDexPool,
+        price_update_account_info: Option<&AccountInfo>,
+        token_decimals_a: u8,
+        token_decimals_b: u8,
+        maximum_age_override: Option<u64>,
+    ) -> Result<bool> {
+        let update = match price_update_account_info {
+            Some(price_update_account_info) => self.update_sqrt_price_no_older_than(
+                ai_dex,
+                price_update_account_info,
+                token_decimals_a,
+                token_decimals_b,
+                maximum_age_override,
+            ),
+            None => Err(ErrorCode::MissingPriceUpdate.into()),
+        };
+        match update {
+            Ok(()) => Ok(false),
+            Err(_) if ai_dex.can_swap_at_last_oracle_price(to_timestamp_u64(Clock::get()?.unix_timestamp)?) => {
+                msg!("Oracle price update unavailable, swapping at the last oracle price");
+                Ok(true)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     pub fn change_maximum_age(&mut self, new_maximum_age: u64) -> Result<()> {
         self.maximum_age = new_maximum_age;
         Ok(())
     }
 
+    pub fn change_price_feed_id(&mut self, new_price_feed_id: String) {
+        self.price_feed_id = new_price_feed_id;
+    }
+}
+
+#[cfg(test)]
+mod maximum_age_override_tests {
+    use super::*;
+
+    #[test]
+    fn test_maximum_age_override_is_never_looser() {
+        let oracle_account = OracleAccount {
+            price_feed_id: String::new(),
+            maximum_age: 30,
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+        };
+        assert_eq!(oracle_account.resolve_maximum_age(None), 30);
+        assert_eq!(oracle_account.resolve_maximum_age(Some(2)), 2);
+        assert_eq!(oracle_account.resolve_maximum_age(Some(60)), 30);
+    }
 }
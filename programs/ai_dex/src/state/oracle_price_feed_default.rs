@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+/// Default price feed of a token pair for the oracle pools of a config, managed by the oracle
+/// manager of the config.
+///
+/// Oracle pools of the pair initialized without a price feed id inherit this one, and existing
+/// oracle accounts of the pair can be brought in line with it through `sync_oracle_params`.
+#[account]
+#[derive(Default)]
+pub struct OraclePriceFeedDefault {
+    pub ai_dex_config: Pubkey, // 32
+    pub mint_a: Pubkey, // 32
+    pub mint_b: Pubkey, // 32
+    pub price_feed_id: String, // 70
+    pub bump: u8, // 1
+}
+
+impl OraclePriceFeedDefault {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 32 // mint_a
+    + 32 // mint_b
+    + 70 // price_feed_id
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, mint_a: Pubkey, mint_b: Pubkey, bump: u8) {
+        self.ai_dex_config = ai_dex_config;
+        self.mint_a = mint_a;
+        self.mint_b = mint_b;
+        self.bump = bump;
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.ai_dex_config != Pubkey::default()
+    }
+
+    /// Sets the default price feed id of the pair.
+    ///
+    /// # Errors
+    /// This function returns an error if the price feed id is not a valid hex feed id.
+    pub fn update_price_feed_id(&mut self, price_feed_id: String) -> Result<()> {
+        get_feed_id_from_hex(&price_feed_id)?;
+        self.price_feed_id = price_feed_id;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod oracle_price_feed_default_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_price_feed_id() {
+        let mut price_feed_default = OraclePriceFeedDefault::default();
+        let price_feed_id = format!("0x{}", "ef".repeat(32));
+        price_feed_default.update_price_feed_id(price_feed_id.clone()).unwrap();
+        assert_eq!(price_feed_default.price_feed_id, price_feed_id);
+
+        assert!(price_feed_default.update_price_feed_id("0x1234".to_string()).is_err());
+        assert_eq!(price_feed_default.price_feed_id, price_feed_id);
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::math::{mul_u256, Q64_RESOLUTION};
+
+/// Swap fees of an oracle pool, valued at the oracle price of each swap.
+///
+/// Swaps of a pool with fee analytics enabled must pass this account in their remaining accounts,
+/// so incentives based on the fee revenue of a pool can be computed on-chain from the difference
+/// of two readings, without trusting an indexer.
+#[account]
+#[derive(Default)]
+pub struct PoolFeeAnalytics {
+    pub ai_dex_pool: Pubkey, // 32
+    /// Swap fees paid in token A, in raw token units.
+    pub total_fee_a: u128, // 16
+    /// Swap fees paid in token B, in raw token units.
+    pub total_fee_b: u128, // 16
+    /// Swap fees of both tokens valued in raw units of token B, the quote token of the oracle
+    /// price, at the oracle price of each swap.
+    pub total_fee_value: u128, // 16
+    pub swap_count: u64, // 8
+    pub last_update_timestamp: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl PoolFeeAnalytics {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 16 // total_fee_a
+    + 16 // total_fee_b
+    + 16 // total_fee_value
+    + 8 // swap_count
+    + 8 // last_update_timestamp
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, bump: u8) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.bump = bump;
+    }
+
+    /// Adds the fee of a swap. Totals saturate rather than fail the swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `fee_amount` - The swap fee paid in the input token, including the protocol and referral shares.
+    /// * `a_to_b` - Whether the fee was paid in token A.
+    /// * `oracle_sqrt_price` - The oracle price of the pool at the swap, as a Q64.64 square root price.
+    /// * `timestamp` - The current timestamp.
+    pub fn record_swap_fee(&mut self, fee_amount: u64, a_to_b: bool, oracle_sqrt_price: u128, timestamp: u64) {
+        let fee_value = if a_to_b {
+            self.total_fee_a = self.total_fee_a.saturating_add(fee_amount as u128);
+            fee_value_in_b(fee_amount, oracle_sqrt_price)
+        } else {
+            self.total_fee_b = self.total_fee_b.saturating_add(fee_amount as u128);
+            fee_amount as u128
+        };
+        self.total_fee_value = self.total_fee_value.saturating_add(fee_value);
+        self.swap_count = self.swap_count.wrapping_add(1);
+        self.last_update_timestamp = timestamp;
+    }
+}
+
+/// Values an amount of token A in raw units of token B at a Q64.64 square root price, rounding down.
+fn fee_value_in_b(amount_a: u64, sqrt_price: u128) -> u128 {
+    // Multiplying by the square root price twice keeps each product within 256 bits
+    let value = mul_u256(amount_a as u128, sqrt_price)
+        .shift_right(Q64_RESOLUTION as u32)
+        .try_into_u128()
+        .unwrap_or(u128::MAX);
+    mul_u256(value, sqrt_price)
+        .shift_right(Q64_RESOLUTION as u32)
+        .try_into_u128()
+        .unwrap_or(u128::MAX)
+}
+
+#[cfg(test)]
+mod pool_fee_analytics_tests {
+    use super::*;
+    use crate::math::{sqrt_price_from_tick_index, MAX_SQRT_PRICE_X64};
+
+    #[test]
+    fn test_record_swap_fee_values_token_a_at_the_oracle_price() {
+        let mut pool_fee_analytics = PoolFeeAnalytics::default();
+        // A price of 4 token B per token A
+        let sqrt_price = 2u128 << 64;
+        pool_fee_analytics.record_swap_fee(1_000, true, sqrt_price, 10);
+        pool_fee_analytics.record_swap_fee(500, false, sqrt_price, 20);
+
+        assert_eq!(pool_fee_analytics.total_fee_a, 1_000);
+        assert_eq!(pool_fee_analytics.total_fee_b, 500);
+        assert_eq!(pool_fee_analytics.total_fee_value, 4_500);
+        assert_eq!(pool_fee_analytics.swap_count, 2);
+        assert_eq!(pool_fee_analytics.last_update_timestamp, 20);
+    }
+
+    #[test]
+    fn test_fee_value_in_b_rounds_down() {
+        // A price slightly above 1 token B per token A
+        assert_eq!(fee_value_in_b(1_000_000, sqrt_price_from_tick_index(1)), 1_000_099);
+        assert_eq!(fee_value_in_b(0, MAX_SQRT_PRICE_X64), 0);
+        assert!(fee_value_in_b(u64::MAX, MAX_SQRT_PRICE_X64) > u64::MAX as u128);
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_POOL_METADATA_NAME_LEN: usize = 32;
+pub const MAX_POOL_METADATA_TAGS: usize = 4;
+pub const MAX_POOL_METADATA_TAG_LEN: usize = 16;
+pub const MAX_POOL_METADATA_URI_LEN: usize = 200;
+
+/// Human-readable metadata of a pool, such as its name, category tags (e.g. `launch`) and a uri
+/// to project links, so front-ends can read them on-chain.
+#[account]
+pub struct PoolMetadata {
+    pub ai_dex_pool: Pubkey, // 32
+    pub name: String, // 4 + 32
+    pub tags: Vec<String>, // 4 + 4 * (4 + 16)
+    pub uri: String, // 4 + 200
+    pub bump: u8, // 1
+}
+
+impl PoolMetadata {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 4 + MAX_POOL_METADATA_NAME_LEN // name
+    + 4 + MAX_POOL_METADATA_TAGS * (4 + MAX_POOL_METADATA_TAG_LEN) // tags
+    + 4 + MAX_POOL_METADATA_URI_LEN // uri
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, bump: u8) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.bump = bump;
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.ai_dex_pool != Pubkey::default()
+    }
+
+    /// Updates the name, tags and uri of the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name, uri, any tag or the number of tags exceeds its maximum.
+    pub fn update_metadata(&mut self, name: String, tags: Vec<String>, uri: String) -> Result<()> {
+        if name.len() > MAX_POOL_METADATA_NAME_LEN
+            || tags.len() > MAX_POOL_METADATA_TAGS
+            || tags.iter().any(|tag| tag.len() > MAX_POOL_METADATA_TAG_LEN)
+            || uri.len() > MAX_POOL_METADATA_URI_LEN
+        {
+            return Err(ErrorCode::PoolMetadataTooLong.into());
+        }
+        self.name = name;
+        self.tags = tags;
+        self.uri = uri;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod pool_metadata_tests {
+    use super::*;
+
+    fn new_pool_metadata() -> PoolMetadata {
+        PoolMetadata {
+            ai_dex_pool: Pubkey::default(),
+            name: String::new(),
+            tags: Vec::new(),
+            uri: String::new(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_update_metadata_max_lengths() {
+        let mut metadata = new_pool_metadata();
+        let name = "n".repeat(MAX_POOL_METADATA_NAME_LEN);
+        let tags = vec!["t".repeat(MAX_POOL_METADATA_TAG_LEN); MAX_POOL_METADATA_TAGS];
+        let uri = "u".repeat(MAX_POOL_METADATA_URI_LEN);
+        metadata.update_metadata(name.clone(), tags.clone(), uri.clone()).unwrap();
+        assert_eq!(metadata.name, name);
+        assert_eq!(metadata.tags, tags);
+        assert_eq!(metadata.uri, uri);
+
+        // The account fits the metadata at maximum length
+        assert_eq!(PoolMetadata::LEN, 8 + metadata.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn test_update_metadata_too_long() {
+        let mut metadata = new_pool_metadata();
+        let too_long = [
+            ("n".repeat(MAX_POOL_METADATA_NAME_LEN + 1), vec![], String::new()),
+            (String::new(), vec![String::new(); MAX_POOL_METADATA_TAGS + 1], String::new()),
+            (String::new(), vec!["t".repeat(MAX_POOL_METADATA_TAG_LEN + 1)], String::new()),
+            (String::new(), vec![], "u".repeat(MAX_POOL_METADATA_URI_LEN + 1)),
+        ];
+        for (name, tags, uri) in too_long {
+            assert_eq!(
+                metadata.update_metadata(name, tags, uri).unwrap_err(),
+                ErrorCode::PoolMetadataTooLong.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+/// Protocol fees collected from a pool and not yet reported to the protocol stats of its config.
+///
+/// Protocol fee collections of a pool with revenue reporting enabled must pass this account in
+/// their remaining accounts, so `sync_protocol_stats` can aggregate the revenue of every pool
+/// without write-locking the protocol stats in each collection.
+#[account]
+#[derive(Default)]
+pub struct PoolRevenueSnapshot {
+    pub ai_dex_config: Pubkey, // 32
+    pub ai_dex_pool: Pubkey, // 32
+    pub token_mint_a: Pubkey, // 32
+    pub token_mint_b: Pubkey, // 32
+    /// Protocol fees collected in token A since the last sync, in raw token units.
+    pub unreported_protocol_fee_a: u64, // 8
+    /// Protocol fees collected in token B since the last sync, in raw token units.
+    pub unreported_protocol_fee_b: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl PoolRevenueSnapshot {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 32 // ai_dex_pool
+    + 32 // token_mint_a
+    + 32 // token_mint_b
+    + 8 // unreported_protocol_fee_a
+    + 8 // unreported_protocol_fee_b
+    + 1; // bump
+
+    pub fn initialize(
+        &mut self,
+        ai_dex_config: Pubkey,
+        ai_dex_pool: Pubkey,
+        token_mint_a: Pubkey,
+        token_mint_b: Pubkey,
+        bump: u8,
+    ) {
+        self.ai_dex_config = ai_dex_config;
+        self.ai_dex_pool = ai_dex_pool;
+        self.token_mint_a = token_mint_a;
+        self.token_mint_b = token_mint_b;
+        self.bump = bump;
+    }
+
+    /// Adds collected protocol fees. Totals saturate rather than fail the collection.
+    pub fn record_collected_protocol_fees(&mut self, amount_a: u64, amount_b: u64) {
+        self.unreported_protocol_fee_a = self.unreported_protocol_fee_a.saturating_add(amount_a);
+        self.unreported_protocol_fee_b = self.unreported_protocol_fee_b.saturating_add(amount_b);
+    }
+
+    /// Resets the unreported protocol fees, returning them.
+    pub fn take_unreported_protocol_fees(&mut self) -> (u64, u64) {
+        let unreported_protocol_fees = (self.unreported_protocol_fee_a, self.unreported_protocol_fee_b);
+        self.unreported_protocol_fee_a = 0;
+        self.unreported_protocol_fee_b = 0;
+        unreported_protocol_fees
+    }
+}
+
+#[cfg(test)]
+mod pool_revenue_snapshot_tests {
+    use super::*;
+
+    #[test]
+    fn test_take_unreported_protocol_fees() {
+        let mut snapshot = PoolRevenueSnapshot::default();
+        snapshot.record_collected_protocol_fees(100, 0);
+        snapshot.record_collected_protocol_fees(20, 7);
+        assert_eq!(snapshot.take_unreported_protocol_fees(), (120, 7));
+        assert_eq!(snapshot.take_unreported_protocol_fees(), (0, 0));
+
+        snapshot.record_collected_protocol_fees(u64::MAX, 1);
+        snapshot.record_collected_protocol_fees(1, 1);
+        assert_eq!(snapshot.take_unreported_protocol_fees(), (u64::MAX, 2));
+    }
+
+    #[test]
+    fn test_len_matches_serialized_size() {
+        let mut data = Vec::new();
+        PoolRevenueSnapshot::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PoolRevenueSnapshot::LEN);
+    }
+}
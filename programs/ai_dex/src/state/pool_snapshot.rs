@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use super::AiDexPool;
+
+// Maximum number of slots between the refresh of a snapshot and its use as a reference price (~1 minute)
+pub const MAX_REFERENCE_SNAPSHOT_AGE_SLOTS: u64 = 150;
+
+/// Compact copy of the parameters of a pool that routers need to quote a swap.
+///
+/// Routers fetch the snapshots of many pools at once with `getMultipleAccounts` instead of
+/// deserializing each `AiDexPool`. Swaps and liquidity changes refresh the snapshots passed in
+/// their remaining accounts, and anyone can refresh a snapshot with `refresh_pool_snapshot`.
+#[account]
+#[derive(Default)]
+pub struct PoolSnapshot {
+    pub ai_dex_pool: Pubkey, // 32
+    /// Fee rate charged by swaps at the last refresh, following the fee schedule of the pool.
+    pub fee_rate: u16, // 2
+    pub tick_spacing: u16, // 2
+    pub sqrt_price: u128, // 16
+    pub liquidity: u128, // 16
+    pub tick_current_index: i32, // 4
+    pub last_update_slot: u64, // 8
+    pub bump: u8, // 1
+    /// Whether a token mint of the pool has a permanent delegate, which can move tokens out of
+    /// the vaults of the pool.
+    pub has_permanent_delegate_mint: bool, // 1
+}
+
+impl PoolSnapshot {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 2 // fee_rate
+    + 2 // tick_spacing
+    + 16 // sqrt_price
+    + 16 // liquidity
+    + 4 // tick_current_index
+    + 8 // last_update_slot
+    + 1 // bump
+    + 1; // has_permanent_delegate_mint
+
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool_key: Pubkey,
+        ai_dex_pool: &AiDexPool,
+        slot: u64,
+        timestamp: u64,
+        bump: u8,
+    ) {
+        self.ai_dex_pool = ai_dex_pool_key;
+        self.bump = bump;
+        self.refresh(ai_dex_pool, slot, timestamp);
+    }
+
+    /// Copies the current parameters of the pool into the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `ai_dex_pool` - The pool of the snapshot.
+    /// * `slot` - The current slot.
+    /// * `timestamp` - The current timestamp.
+    pub fn refresh(&mut self, ai_dex_pool: &AiDexPool, slot: u64, timestamp: u64) {
+        self.fee_rate = ai_dex_pool.fee_rate_at(timestamp);
+        self.tick_spacing = ai_dex_pool.tick_spacing;
+        self.sqrt_price = ai_dex_pool.sqrt_price;
+        self.liquidity = ai_dex_pool.liquidity;
+        self.tick_current_index = ai_dex_pool.tick_current_index;
+        self.last_update_slot = slot;
+        self.has_permanent_delegate_mint = ai_dex_pool.has_permanent_delegate_mint != 0;
+    }
+
+    /// Checks if the snapshot can serve as the reference price of an operation in the given slot.
+    ///
+    /// The snapshot must have been refreshed in an earlier slot, so the price cannot be moved
+    /// within the same transaction, and recently enough to reflect the market.
+    pub fn is_reference_at(&self, slot: u64) -> bool {
+        self.last_update_slot < slot && slot - self.last_update_slot <= MAX_REFERENCE_SNAPSHOT_AGE_SLOTS
+    }
+}
+
+#[cfg(test)]
+mod pool_snapshot_tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_copies_pool_parameters() {
+        let ai_dex_pool = AiDexPool {
+            fee_rate: 3_000,
+            tick_spacing: 64,
+            sqrt_price: 1 << 64,
+            liquidity: 5_000_000,
+            tick_current_index: -12,
+            has_permanent_delegate_mint: 1,
+            ..Default::default()
+        };
+        let ai_dex_pool_key = Pubkey::new_unique();
+
+        let mut snapshot = PoolSnapshot::default();
+        snapshot.initialize(ai_dex_pool_key, &ai_dex_pool, 10, 1_000, 255);
+
+        assert_eq!(snapshot.ai_dex_pool, ai_dex_pool_key);
+        assert_eq!(snapshot.fee_rate, 3_000);
+        assert_eq!(snapshot.tick_spacing, 64);
+        assert_eq!(snapshot.sqrt_price, 1 << 64);
+        assert_eq!(snapshot.liquidity, 5_000_000);
+        assert_eq!(snapshot.tick_current_index, -12);
+        assert_eq!(snapshot.last_update_slot, 10);
+        assert_eq!(snapshot.bump, 255);
+        assert!(snapshot.has_permanent_delegate_mint);
+    }
+
+    #[test]
+    fn test_is_reference_at() {
+        let snapshot = PoolSnapshot { last_update_slot: 100, ..Default::default() };
+        assert!(!snapshot.is_reference_at(100));
+        assert!(snapshot.is_reference_at(101));
+        assert!(snapshot.is_reference_at(100 + MAX_REFERENCE_SNAPSHOT_AGE_SLOTS));
+        assert!(!snapshot.is_reference_at(101 + MAX_REFERENCE_SNAPSHOT_AGE_SLOTS));
+    }
+
+    #[test]
+    fn test_len_matches_serialized_size() {
+        let mut data = Vec::new();
+        PoolSnapshot::default().try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PoolSnapshot::LEN);
+    }
+}
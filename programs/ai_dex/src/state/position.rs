@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, math::FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, state::NUM_REWARDS};
+use crate::{errors::ErrorCode, state::NUM_REWARDS};
 
 use super::{Tick, AiDexPool};
 
@@ -15,6 +15,12 @@ pub struct OpenPositionWithMetadataBumps {
     pub metadata_bump: u8,
 }
 
+// Share of the liquidity of a position withdrawing all of it (100%)
+pub const MAX_LIQUIDITY_BPS: u16 = 10_000;
+
+// Largest deviation of the rate of a reinvestment swap from the pool snapshot price (100%)
+pub const MAX_REINVESTMENT_SWAP_DEVIATION_BPS: u16 = 10_000;
+
 #[account]
 #[derive(Default)]
 pub struct Position {
@@ -34,12 +40,60 @@ pub struct Position {
     pub reward_infos: [PositionRewardInfo; NUM_REWARDS], // 72
 
     pub is_reinvestment_on: bool, // 1
+
+    pub last_updated_timestamp: u64, // 8
+
+    pub version: u8, // 1
+
+    pub out_of_range_since: u64, // 8
+    pub grace_checkpoint: u64,   // 8
+
+    pub lp_referral: Pubkey,       // 32
+    pub lp_referral_fee_rate: u16, // 2
+
+    /// Standing delegate allowed to collect the rewards of the position to the token accounts
+    /// of `reward_recipient`, or the default pubkey if rewards are not delegated.
+    pub reward_delegate: Pubkey,  // 32
+    pub reward_recipient: Pubkey, // 32
+
+    /// Cumulative token amounts paid into and received out of the liquidity of the position by
+    /// its owners, so that its realized profit and loss can be computed without replaying events.
+    pub deposited_a: u64, // 8
+    pub deposited_b: u64, // 8
+    pub withdrawn_a: u64, // 8
+    pub withdrawn_b: u64, // 8
+
+    /// Slot of the last reinvestment of the fees of the position. Its liquidity and fees cannot
+    /// be modified by the owner in that slot, so a reinvestment cannot be front-run.
+    pub last_reinvestment_slot: u64, // 8
+
+    /// Holder of the position token when the reward delegate was set. The delegation lapses
+    /// once the position is transferred to another owner.
+    pub reward_delegator: Pubkey, // 32
+
+    /// Liquidity withdrawn but not transferred yet, such as withdrawals below the dust transfer
+    /// thresholds of the pool. Paid with the fees by `collect_fees`, but not counted as fees.
+    pub principal_owed_a: u64, // 8
+    pub principal_owed_b: u64, // 8
+
+    /// Largest deviation, in basis points, of the rate of a reinvestment swap of the fees of the
+    /// position from the price of the pool snapshot, or 0 to disable reinvestment swaps.
+    pub reinvestment_swap_max_deviation_bps: u16, // 2
+
+    /// Space reserved for future fields, so they can be added without growing the account.
+    pub reserved: [u8; 6], // 6
 }
 
 /// Represents a position in the AiDex program.
 impl Position {
     /// The length of a position in bytes.
-    pub const LEN: usize = 8 + 136 + 72 + 1;
+    pub const LEN: usize = 8 + 136 + 72 + 1 + 8 + 1 + 16 + 34 + 64 + 32 + 32 + 32;
+
+    /// The current layout version of a position.
+    pub const CURRENT_VERSION: u8 = 6;
+
+    /// The offset of the version field in the account data.
+    pub const VERSION_OFFSET: usize = Self::LEN - 32 - 32 - 32 - 64 - 34 - 16 - 1;
 
     /// Checks if a position is empty.
     ///
@@ -56,11 +110,108 @@ impl Position {
         position.liquidity == 0 && 
         position.fee_owed_a == 0 && 
         position.fee_owed_b == 0 && 
+        position.principal_owed_a == 0 &&
+        position.principal_owed_b == 0 &&
         position.reward_infos.iter().all(
             |reward| reward.amount_owed == 0
         )
     }
 
+    /// Checks if a position can be liquidated as dust.
+    ///
+    /// A position is considered dust if its liquidity is below the threshold, it has no fees,
+    /// principal or rewards owed, and it has not been updated for at least the inactivity period.
+    /// Positions without a recorded update are never dust, as their inactivity is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `liquidity_threshold` - Positions with liquidity strictly below this value are considered dust.
+    /// * `inactivity_period` - The number of seconds the position must have been inactive.
+    /// * `timestamp` - The current timestamp.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the position can be liquidated, `false` otherwise.
+    pub fn is_dust_position(
+        &self,
+        liquidity_threshold: u128,
+        inactivity_period: u64,
+        timestamp: u64,
+    ) -> bool {
+        self.liquidity < liquidity_threshold &&
+        self.fee_owed_a == 0 &&
+        self.fee_owed_b == 0 &&
+        self.principal_owed_a == 0 &&
+        self.principal_owed_b == 0 &&
+        self.reward_infos.iter().all(
+            |reward| reward.amount_owed == 0
+        ) &&
+        self.last_updated_timestamp != 0 &&
+        timestamp >= self.last_updated_timestamp.saturating_add(inactivity_period)
+    }
+
+    /// Records a reinvestment of the fees of the position in the given slot.
+    pub fn record_reinvestment(&mut self, slot: u64) {
+        self.last_reinvestment_slot = slot;
+    }
+
+    /// Checks that the fees of the position were not reinvested in the given slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PositionReinvestedInSlot` if the position was reinvested in the slot. Slot 0 is
+    /// never locked, as it is the value of positions that were never reinvested.
+    pub fn check_not_reinvested_in_slot(&self, slot: u64) -> Result<()> {
+        if slot != 0 && self.last_reinvestment_slot == slot {
+            return Err(ErrorCode::PositionReinvestedInSlot.into());
+        }
+        Ok(())
+    }
+
+    /// Checks if the position spans the full tick range of a pool with the given tick spacing.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick_spacing` - The tick spacing of the pool.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the position is full-range, `false` otherwise.
+    pub fn is_full_range(&self, tick_spacing: u16) -> bool {
+        let (full_range_lower_index, full_range_upper_index) = Tick::full_range_indexes(tick_spacing);
+        self.tick_lower_index == full_range_lower_index
+            && self.tick_upper_index == full_range_upper_index
+    }
+
+    /// Returns the liquidity of the position for a share in basis points, rounded down, the whole
+    /// liquidity for 10,000 basis points.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the share is zero or above 10,000 basis points.
+    pub fn liquidity_share(&self, liquidity_bps: u16) -> Result<u128> {
+        if liquidity_bps == 0 || liquidity_bps > MAX_LIQUIDITY_BPS {
+            return Err(ErrorCode::InvalidLiquidityBps.into());
+        }
+        if liquidity_bps == MAX_LIQUIDITY_BPS {
+            return Ok(self.liquidity);
+        }
+        // liquidity_bps < 10,000, so the share is below the liquidity; split the product to
+        // avoid overflowing u128
+        let bps = u128::from(liquidity_bps);
+        let denominator = u128::from(MAX_LIQUIDITY_BPS);
+        Ok(self.liquidity / denominator * bps + self.liquidity % denominator * bps / denominator)
+    }
+
+    /// Updates the timestamp of the last activity on the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The current timestamp.
+    pub fn update_last_updated_timestamp(&mut self, timestamp: u64) {
+        self.last_updated_timestamp = timestamp;
+    }
+
     /// Updates the position with the given position update.
     ///
     /// # Arguments
@@ -73,6 +224,8 @@ impl Position {
         self.fee_owed_a = update.fee_owed_a;
         self.fee_owed_b = update.fee_owed_b;
         self.reward_infos = update.reward_infos;
+        self.out_of_range_since = update.out_of_range_since;
+        self.grace_checkpoint = update.grace_checkpoint;
     }
 
     /// Opens a position in the AiDex program.
@@ -105,8 +258,8 @@ impl Position {
             return Err(ErrorCode::InvalidTickIndexError.into());
         }
 
-        // On tick spacing >= 2^15, should only be able to open full range positions
-        if ai_dex_data.tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD {
+        // On full-range only pools and tick spacing >= 2^15, should only be able to open full range positions
+        if ai_dex_data.requires_full_range_positions() {
             let (full_range_lower_index, full_range_upper_index) = Tick::full_range_indexes(ai_dex_data.tick_spacing);
             if tick_lower_index != full_range_lower_index
                 || tick_upper_index != full_range_upper_index
@@ -122,20 +275,138 @@ impl Position {
         self.tick_upper_index = tick_upper_index;
 
         self.is_reinvestment_on = is_reinvestment_on;
+        self.version = Self::CURRENT_VERSION;
+        Ok(())
+    }
+
+    /// Attributes the position to an LP referral, which then earns a share of the protocol fees
+    /// generated by the position. The first attribution is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `lp_referral` - The swap referral account of the referrer.
+    /// * `lp_referral_fee_rate` - The share of the protocol fees earned by the referrer, in basis points.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the position was attributed to the referral, `false` if it already was attributed.
+    pub fn attribute_lp_referral(&mut self, lp_referral: Pubkey, lp_referral_fee_rate: u16) -> bool {
+        if self.lp_referral != Pubkey::default() {
+            return false;
+        }
+        self.lp_referral = lp_referral;
+        self.lp_referral_fee_rate = lp_referral_fee_rate;
+        true
+    }
+
+    /// Delegates the collection of the rewards of the position.
+    ///
+    /// The delegation is bound to the current holder of the position token, and lapses when the
+    /// position is transferred to another owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `reward_delegate` - The delegate allowed to collect rewards, or the default pubkey to clear it.
+    /// * `reward_recipient` - The owner of the token accounts the delegate collects rewards to.
+    /// * `reward_delegator` - The owner of the position token account delegating the rewards.
+    pub fn update_reward_delegate(
+        &mut self,
+        reward_delegate: Pubkey,
+        reward_recipient: Pubkey,
+        reward_delegator: Pubkey,
+    ) {
+        self.reward_delegate = reward_delegate;
+        (self.reward_recipient, self.reward_delegator) = if reward_delegate == Pubkey::default() {
+            (Pubkey::default(), Pubkey::default())
+        } else {
+            (reward_recipient, reward_delegator)
+        };
+    }
+
+    /// Sets the largest deviation of the rate of reinvestment swaps from the pool snapshot price.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deviation exceeds 10,000 basis points.
+    pub fn update_reinvestment_swap_max_deviation_bps(&mut self, max_deviation_bps: u16) -> Result<()> {
+        if max_deviation_bps > MAX_REINVESTMENT_SWAP_DEVIATION_BPS {
+            return Err(ErrorCode::InvalidReinvestmentSwapMaxDeviation.into());
+        }
+        self.reinvestment_swap_max_deviation_bps = max_deviation_bps;
         Ok(())
     }
 
+    /// Checks if the given signer is the reward delegate of the position, delegated by the
+    /// current owner of the position token.
+    pub fn is_reward_delegate(&self, signer: &Pubkey, position_token_owner: &Pubkey) -> bool {
+        self.reward_delegate != Pubkey::default()
+            && self.reward_delegate == *signer
+            && self.reward_delegator == *position_token_owner
+    }
+
+    /// Adds the token amounts paid into the liquidity of the position.
+    pub fn record_deposit(&mut self, amount_a: u64, amount_b: u64) {
+        self.deposited_a = self.deposited_a.saturating_add(amount_a);
+        self.deposited_b = self.deposited_b.saturating_add(amount_b);
+    }
+
+    /// Adds the token amounts received out of the liquidity of the position.
+    pub fn record_withdrawal(&mut self, amount_a: u64, amount_b: u64) {
+        self.withdrawn_a = self.withdrawn_a.saturating_add(amount_a);
+        self.withdrawn_b = self.withdrawn_b.saturating_add(amount_b);
+    }
+
+    /// Returns the realized profit and loss of the position in each token, the amounts withdrawn
+    /// minus the amounts deposited.
+    pub fn realized_pnl(&self) -> (i128, i128) {
+        (
+            i128::from(self.withdrawn_a) - i128::from(self.deposited_a),
+            i128::from(self.withdrawn_b) - i128::from(self.deposited_b),
+        )
+    }
+
     /// Resets the fees owed by the position to zero.
     pub fn reset_fees_owed(&mut self) {
         self.fee_owed_a = 0;
         self.fee_owed_b = 0;
     }
 
+    /// Adds liquidity withdrawn but kept in the vaults to the principal owed by the position, to
+    /// be transferred by the next fee collection.
+    pub fn add_principal_owed(&mut self, principal_owed_a: u64, principal_owed_b: u64) {
+        self.principal_owed_a = self.principal_owed_a.saturating_add(principal_owed_a);
+        self.principal_owed_b = self.principal_owed_b.saturating_add(principal_owed_b);
+    }
+
+    /// Resets the principal owed by the position to zero.
+    pub fn reset_principal_owed(&mut self) {
+        self.principal_owed_a = 0;
+        self.principal_owed_b = 0;
+    }
+
     pub fn subtract_fees_owed(&mut self, fee_owed_a: u64, fee_owed_b: u64) {
         self.fee_owed_a = self.fee_owed_a.saturating_sub(fee_owed_a);
         self.fee_owed_b = self.fee_owed_b.saturating_sub(fee_owed_b);
     }
 
+    /// Exchanges fees owed in one token for fees owed in the other, after an internal swap of the
+    /// reinvestment engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_in` - The fees owed swapped out of the input token.
+    /// * `amount_out` - The fees owed received in the output token.
+    /// * `a_to_b` - Whether token A is swapped for token B.
+    pub fn swap_fees_owed(&mut self, amount_in: u64, amount_out: u64, a_to_b: bool) {
+        if a_to_b {
+            self.fee_owed_a = self.fee_owed_a.saturating_sub(amount_in);
+            self.fee_owed_b = self.fee_owed_b.saturating_add(amount_out);
+        } else {
+            self.fee_owed_b = self.fee_owed_b.saturating_sub(amount_in);
+            self.fee_owed_a = self.fee_owed_a.saturating_add(amount_out);
+        }
+    }
+
     /// Updates the amount owed for a specific reward in the position.
     ///
     /// # Arguments
@@ -145,6 +416,109 @@ impl Position {
     pub fn update_reward_owed(&mut self, index: usize, amount_owed: u64) {
         self.reward_infos[index].amount_owed = amount_owed;
     }
+
+    /// Moves liquidity into a new position of the same range.
+    ///
+    /// The new position starts from the fee and reward checkpoints of this position, which must
+    /// be settled beforehand. Fees and rewards owed stay with this position, while the deposited
+    /// and withdrawn amounts are split in proportion to the liquidity moved. The LP referral is
+    /// carried over, so the referrer keeps its share of the fees earned by the moved liquidity.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_position` - The opened position receiving the liquidity.
+    /// * `liquidity` - The liquidity to move.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the liquidity is zero or exceeds the liquidity of this position.
+    pub fn split_into(&mut self, new_position: &mut Position, liquidity: u128) -> Result<()> {
+        if liquidity == 0 || liquidity > self.liquidity {
+            return Err(ErrorCode::InvalidSplitLiquidity.into());
+        }
+
+        new_position.liquidity = liquidity;
+        new_position.lp_referral = self.lp_referral;
+        new_position.lp_referral_fee_rate = self.lp_referral_fee_rate;
+        new_position.reinvestment_swap_max_deviation_bps = self.reinvestment_swap_max_deviation_bps;
+        new_position.fee_growth_checkpoint_a = self.fee_growth_checkpoint_a;
+        new_position.fee_growth_checkpoint_b = self.fee_growth_checkpoint_b;
+        for (new_reward_info, reward_info) in new_position.reward_infos.iter_mut().zip(self.reward_infos.iter()) {
+            new_reward_info.growth_inside_checkpoint = reward_info.growth_inside_checkpoint;
+        }
+
+        let moved_share = |amount: &mut u64| -> u64 {
+            // liquidity <= self.liquidity, so the share fits in u64
+            let share = (u128::from(*amount) * liquidity / self.liquidity) as u64;
+            *amount -= share;
+            share
+        };
+        new_position.deposited_a = moved_share(&mut self.deposited_a);
+        new_position.deposited_b = moved_share(&mut self.deposited_b);
+        new_position.withdrawn_a = moved_share(&mut self.withdrawn_a);
+        new_position.withdrawn_b = moved_share(&mut self.withdrawn_b);
+
+        self.liquidity -= liquidity;
+        Ok(())
+    }
+
+    /// Moves the liquidity, fees and rewards owed of another position of the same range into this position.
+    ///
+    /// Both positions must be settled beforehand so their checkpoints match. The other position is
+    /// left empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The position merged into this position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the positions belong to different pools or ranges, are attributed to
+    /// different LP referrals, or the liquidity overflows.
+    pub fn merge_from(&mut self, other: &mut Position) -> Result<()> {
+        if self.ai_dex_pool != other.ai_dex_pool
+            || self.tick_lower_index != other.tick_lower_index
+            || self.tick_upper_index != other.tick_upper_index
+        {
+            return Err(ErrorCode::PositionRangeMismatch.into());
+        }
+        if self.lp_referral != other.lp_referral || self.lp_referral_fee_rate != other.lp_referral_fee_rate {
+            return Err(ErrorCode::PositionReferralMismatch.into());
+        }
+
+        // A position without liquidity is not settled, so it takes the checkpoints of the other position
+        if self.liquidity == 0 {
+            self.fee_growth_checkpoint_a = other.fee_growth_checkpoint_a;
+            self.fee_growth_checkpoint_b = other.fee_growth_checkpoint_b;
+            for (reward_info, other_reward_info) in self.reward_infos.iter_mut().zip(other.reward_infos.iter()) {
+                reward_info.growth_inside_checkpoint = other_reward_info.growth_inside_checkpoint;
+            }
+        }
+
+        self.liquidity = self
+            .liquidity
+            .checked_add(other.liquidity)
+            .ok_or(ErrorCode::LiquidityOverflowError)?;
+        self.fee_owed_a = self.fee_owed_a.wrapping_add(other.fee_owed_a);
+        self.fee_owed_b = self.fee_owed_b.wrapping_add(other.fee_owed_b);
+        self.add_principal_owed(other.principal_owed_a, other.principal_owed_b);
+        for (reward_info, other_reward_info) in self.reward_infos.iter_mut().zip(other.reward_infos.iter_mut()) {
+            reward_info.amount_owed = reward_info.amount_owed.wrapping_add(other_reward_info.amount_owed);
+            other_reward_info.amount_owed = 0;
+        }
+
+        self.record_deposit(other.deposited_a, other.deposited_b);
+        self.record_withdrawal(other.withdrawn_a, other.withdrawn_b);
+        other.deposited_a = 0;
+        other.deposited_b = 0;
+        other.withdrawn_a = 0;
+        other.withdrawn_b = 0;
+
+        other.liquidity = 0;
+        other.reset_fees_owed();
+        other.reset_principal_owed();
+        Ok(())
+    }
 }
 
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
@@ -162,6 +536,8 @@ pub struct PositionUpdate {
     pub fee_growth_checkpoint_b: u128,
     pub fee_owed_b: u64,
     pub reward_infos: [PositionRewardInfo; NUM_REWARDS],
+    pub out_of_range_since: u64,
+    pub grace_checkpoint: u64,
 }
 
 #[cfg(test)]
@@ -202,6 +578,24 @@ mod is_position_empty_tests {
                 },
             ],
             is_reinvestment_on: false,
+            last_updated_timestamp: 0,
+            version: Position::CURRENT_VERSION,
+            out_of_range_since: 0,
+            grace_checkpoint: 0,
+            lp_referral: Pubkey::default(),
+            lp_referral_fee_rate: 0,
+            reward_delegate: Pubkey::default(),
+            reward_recipient: Pubkey::default(),
+            deposited_a: 0,
+            deposited_b: 0,
+            withdrawn_a: 0,
+            withdrawn_b: 0,
+            last_reinvestment_slot: 0,
+            reward_delegator: Pubkey::default(),
+            principal_owed_a: 0,
+            principal_owed_b: 0,
+            reinvestment_swap_max_deviation_bps: 0,
+            reserved: [0; 6],
         }
     }
 
@@ -248,6 +642,349 @@ mod is_position_empty_tests {
     }
 }
 
+#[cfg(test)]
+mod version_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_version_offset_matches_layout() {
+        let mut position = PositionBuilder::new(-10, 10).build();
+        position.version = Position::CURRENT_VERSION;
+
+        let mut data = Vec::new();
+        position.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Position::LEN);
+        assert_eq!(data[Position::VERSION_OFFSET], Position::CURRENT_VERSION);
+    }
+}
+
+#[cfg(test)]
+mod lp_referral_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_attribute_lp_referral_keeps_first() {
+        let mut position = PositionBuilder::new(-10, 10).build();
+        let first = Pubkey::new_unique();
+
+        assert!(position.attribute_lp_referral(first, 1_000));
+        assert!(!position.attribute_lp_referral(Pubkey::new_unique(), 1_500));
+        assert_eq!(position.lp_referral, first);
+        assert_eq!(position.lp_referral_fee_rate, 1_000);
+    }
+}
+
+#[cfg(test)]
+mod swap_fees_owed_tests {
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_swap_fees_owed() {
+        let mut position = PositionBuilder::new(-10, 10).fee_owed_a(1_000).fee_owed_b(50).build();
+
+        position.swap_fees_owed(400, 390, true);
+        assert_eq!((position.fee_owed_a, position.fee_owed_b), (600, 440));
+
+        position.swap_fees_owed(440, 450, false);
+        assert_eq!((position.fee_owed_a, position.fee_owed_b), (1_050, 0));
+    }
+}
+
+#[cfg(test)]
+mod reinvestment_slot_tests {
+    use crate::errors::ErrorCode;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_position_is_locked_in_slot_of_reinvestment() {
+        let mut position = PositionBuilder::new(-10, 10).build();
+        assert!(position.check_not_reinvested_in_slot(100).is_ok());
+
+        position.record_reinvestment(100);
+        assert_eq!(
+            position.check_not_reinvested_in_slot(100).unwrap_err(),
+            ErrorCode::PositionReinvestedInSlot.into()
+        );
+        assert!(position.check_not_reinvested_in_slot(101).is_ok());
+    }
+
+    #[test]
+    fn test_never_reinvested_position_is_not_locked() {
+        let position = PositionBuilder::new(-10, 10).build();
+        assert!(position.check_not_reinvested_in_slot(0).is_ok());
+    }
+
+    #[test]
+    fn test_update_reinvestment_swap_max_deviation_bps() {
+        let mut position = PositionBuilder::new(-10, 10).build();
+        assert_eq!(position.reinvestment_swap_max_deviation_bps, 0);
+
+        position.update_reinvestment_swap_max_deviation_bps(10_000).unwrap();
+        assert_eq!(position.reinvestment_swap_max_deviation_bps, 10_000);
+        assert_eq!(
+            position.update_reinvestment_swap_max_deviation_bps(10_001).unwrap_err(),
+            ErrorCode::InvalidReinvestmentSwapMaxDeviation.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod liquidity_share_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_liquidity_share() {
+        let position = PositionBuilder::new(-10, 10).liquidity(1_001).build();
+        assert_eq!(position.liquidity_share(5_000).unwrap(), 500);
+        assert_eq!(position.liquidity_share(1).unwrap(), 0);
+        assert_eq!(position.liquidity_share(MAX_LIQUIDITY_BPS).unwrap(), 1_001);
+    }
+
+    #[test]
+    fn test_liquidity_share_does_not_overflow() {
+        let position = PositionBuilder::new(-10, 10).liquidity(u128::MAX).build();
+        assert_eq!(position.liquidity_share(5_000).unwrap(), u128::MAX / 2);
+        assert_eq!(position.liquidity_share(MAX_LIQUIDITY_BPS).unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn test_invalid_liquidity_share() {
+        let position = PositionBuilder::new(-10, 10).liquidity(1_000).build();
+        for liquidity_bps in [0, MAX_LIQUIDITY_BPS + 1] {
+            assert_eq!(
+                position.liquidity_share(liquidity_bps).unwrap_err(),
+                ErrorCode::InvalidLiquidityBps.into()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod realized_pnl_tests {
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_realized_pnl() {
+        let mut position = PositionBuilder::new(-10, 10).build();
+        position.record_deposit(1_000, 2_000);
+        position.record_withdrawal(1_200, 500);
+        position.record_withdrawal(0, 1_000);
+        assert_eq!(position.realized_pnl(), (200, -500));
+
+        position.record_deposit(u64::MAX, 0);
+        assert_eq!(position.deposited_a, u64::MAX);
+    }
+}
+
+#[cfg(test)]
+mod reward_delegate_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_update_reward_delegate() {
+        let mut position = PositionBuilder::new(-10, 10).build();
+        let reward_delegate = Pubkey::new_unique();
+        let reward_recipient = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        assert!(!position.is_reward_delegate(&Pubkey::default(), &Pubkey::default()));
+
+        position.update_reward_delegate(reward_delegate, reward_recipient, owner);
+        assert!(position.is_reward_delegate(&reward_delegate, &owner));
+        assert_eq!(position.reward_recipient, reward_recipient);
+
+        // The delegation lapses once the position token has another owner
+        assert!(!position.is_reward_delegate(&reward_delegate, &Pubkey::new_unique()));
+
+        // Clearing the delegate clears the recipient and the delegator
+        position.update_reward_delegate(Pubkey::default(), reward_recipient, owner);
+        assert!(!position.is_reward_delegate(&reward_delegate, &owner));
+        assert_eq!(position.reward_recipient, Pubkey::default());
+        assert_eq!(position.reward_delegator, Pubkey::default());
+    }
+}
+
+#[cfg(test)]
+mod is_dust_position_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_dust_position() {
+        let mut pos = PositionBuilder::new(-10, 10).liquidity(99).build();
+        pos.update_last_updated_timestamp(1_000);
+        assert!(pos.is_dust_position(100, 500, 1_500));
+    }
+
+    #[test]
+    fn test_liquidity_at_threshold() {
+        let mut pos = PositionBuilder::new(-10, 10).liquidity(100).build();
+        pos.update_last_updated_timestamp(1_000);
+        assert!(!pos.is_dust_position(100, 500, 1_500));
+    }
+
+    #[test]
+    fn test_recently_updated() {
+        let mut pos = PositionBuilder::new(-10, 10).liquidity(0).build();
+        pos.update_last_updated_timestamp(1_000);
+        assert!(!pos.is_dust_position(100, 500, 1_499));
+    }
+
+    #[test]
+    fn test_never_updated() {
+        let pos = PositionBuilder::new(-10, 10).liquidity(0).build();
+        assert!(!pos.is_dust_position(100, 500, 1_500));
+    }
+
+    #[test]
+    fn test_fees_owed() {
+        let mut pos = PositionBuilder::new(-10, 10).fee_owed_b(1).build();
+        pos.update_last_updated_timestamp(1);
+        assert!(!pos.is_dust_position(100, 0, 1));
+    }
+
+    #[test]
+    fn test_principal_owed() {
+        let mut pos = PositionBuilder::new(-10, 10).build();
+        pos.update_last_updated_timestamp(1);
+        pos.add_principal_owed(0, 1);
+        assert!(!pos.is_dust_position(100, 0, 1));
+    }
+
+    #[test]
+    fn test_reward_owed() {
+        let mut pos = PositionBuilder::new(-10, 10)
+            .reward_info(2, PositionRewardInfo { amount_owed: 1, ..Default::default() })
+            .build();
+        pos.update_last_updated_timestamp(1);
+        assert!(!pos.is_dust_position(100, 0, 1));
+    }
+
+    #[test]
+    fn test_inactivity_period_overflow() {
+        let mut pos = PositionBuilder::new(-10, 10).build();
+        pos.update_last_updated_timestamp(1_000);
+        assert!(!pos.is_dust_position(100, u64::MAX, u64::MAX - 1));
+    }
+}
+
+#[cfg(test)]
+mod split_and_merge_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    fn reward_info(growth_inside_checkpoint: u128, amount_owed: u64) -> PositionRewardInfo {
+        PositionRewardInfo { growth_inside_checkpoint, amount_owed }
+    }
+
+    #[test]
+    fn test_split_into() {
+        let mut pos = PositionBuilder::new(-10, 10)
+            .liquidity(1_000)
+            .fee_growth_checkpoint_a(5)
+            .fee_growth_checkpoint_b(6)
+            .fee_owed_a(7)
+            .reward_info(1, reward_info(8, 9))
+            .build();
+        pos.record_deposit(1_000, 333);
+        pos.attribute_lp_referral(Pubkey::new_unique(), 2_000);
+        pos.update_reinvestment_swap_max_deviation_bps(50).unwrap();
+        let mut new_pos = PositionBuilder::new(-10, 10).build();
+
+        pos.split_into(&mut new_pos, 400).unwrap();
+        assert_eq!((new_pos.lp_referral, new_pos.lp_referral_fee_rate), (pos.lp_referral, 2_000));
+        assert_eq!(new_pos.reinvestment_swap_max_deviation_bps, 50);
+        assert_eq!((pos.deposited_a, new_pos.deposited_a), (600, 400));
+        assert_eq!((pos.deposited_b, new_pos.deposited_b), (200, 133));
+        assert_eq!(pos.liquidity, 600);
+        assert_eq!(pos.fee_owed_a, 7);
+        assert_eq!(new_pos.liquidity, 400);
+        assert_eq!(new_pos.fee_growth_checkpoint_a, 5);
+        assert_eq!(new_pos.fee_growth_checkpoint_b, 6);
+        assert_eq!(new_pos.fee_owed_a, 0);
+        assert_eq!(new_pos.reward_infos[1], reward_info(8, 0));
+    }
+
+    #[test]
+    fn test_split_into_invalid_liquidity() {
+        let mut pos = PositionBuilder::new(-10, 10).liquidity(1_000).build();
+        let mut new_pos = PositionBuilder::new(-10, 10).build();
+        for liquidity in [0, 1_001] {
+            assert_eq!(
+                pos.split_into(&mut new_pos, liquidity).unwrap_err(),
+                ErrorCode::InvalidSplitLiquidity.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_from() {
+        let mut pos = PositionBuilder::new(-10, 10)
+            .liquidity(600)
+            .fee_growth_checkpoint_a(5)
+            .fee_owed_a(7)
+            .reward_info(2, reward_info(8, 1))
+            .build();
+        let mut other = PositionBuilder::new(-10, 10)
+            .liquidity(400)
+            .fee_growth_checkpoint_a(5)
+            .fee_owed_a(3)
+            .fee_owed_b(2)
+            .reward_info(2, reward_info(8, 4))
+            .build();
+        other.ai_dex_pool = pos.ai_dex_pool;
+        pos.record_deposit(60, 0);
+        other.record_deposit(40, 10);
+        other.record_withdrawal(5, 0);
+
+        pos.merge_from(&mut other).unwrap();
+        assert_eq!((pos.deposited_a, pos.deposited_b, pos.withdrawn_a), (100, 10, 5));
+        assert_eq!((other.deposited_a, other.withdrawn_a), (0, 0));
+        assert_eq!(pos.liquidity, 1_000);
+        assert_eq!(pos.fee_owed_a, 10);
+        assert_eq!(pos.fee_owed_b, 2);
+        assert_eq!(pos.reward_infos[2], reward_info(8, 5));
+        assert!(Position::is_position_empty(&other));
+    }
+
+    #[test]
+    fn test_merge_into_position_without_liquidity() {
+        let mut pos = PositionBuilder::new(-10, 10).fee_growth_checkpoint_a(1).build();
+        let mut other = PositionBuilder::new(-10, 10)
+            .liquidity(400)
+            .fee_growth_checkpoint_a(5)
+            .reward_info(0, reward_info(8, 0))
+            .build();
+        other.ai_dex_pool = pos.ai_dex_pool;
+
+        pos.merge_from(&mut other).unwrap();
+        assert_eq!(pos.liquidity, 400);
+        assert_eq!(pos.fee_growth_checkpoint_a, 5);
+        assert_eq!(pos.reward_infos[0], reward_info(8, 0));
+    }
+
+    #[test]
+    fn test_merge_from_range_mismatch() {
+        let mut pos = PositionBuilder::new(-10, 10).build();
+        let mut other = PositionBuilder::new(-20, 10).build();
+        other.ai_dex_pool = pos.ai_dex_pool;
+        assert_eq!(pos.merge_from(&mut other).unwrap_err(), ErrorCode::PositionRangeMismatch.into());
+    }
+
+    #[test]
+    fn test_merge_from_referral_mismatch() {
+        let mut pos = PositionBuilder::new(-10, 10).build();
+        let mut other = PositionBuilder::new(-10, 10).liquidity(400).build();
+        other.ai_dex_pool = pos.ai_dex_pool;
+        other.attribute_lp_referral(Pubkey::new_unique(), 2_000);
+        assert_eq!(pos.merge_from(&mut other).unwrap_err(), ErrorCode::PositionReferralMismatch.into());
+        assert_eq!(other.liquidity, 400);
+    }
+}
+
 #[cfg(test)]
 pub mod position_builder {
     use anchor_lang::prelude::Pubkey;
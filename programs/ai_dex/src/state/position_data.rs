@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cell::{Ref, RefMut};
+
+use crate::errors::ErrorCode;
+use crate::state::NUM_REWARDS;
+
+use super::Position;
+
+/// Zero-copy view of the data of a `Position` account.
+///
+/// `Position` only has fixed-size fields, so its borsh encoding has the layout of this packed
+/// struct. Instructions and CPI callers touching many positions can read and update them through
+/// this view instead of deserializing and serializing each `Position`.
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct PositionData {
+    pub ai_dex_pool: Pubkey,     // 32
+    pub position_mint: Pubkey, // 32
+    pub liquidity: u128,       // 16
+    pub tick_lower_index: i32, // 4
+    pub tick_upper_index: i32, // 4
+
+    // Q64.64
+    pub fee_growth_checkpoint_a: u128, // 16
+    pub fee_owed_a: u64,               // 8
+    // Q64.64
+    pub fee_growth_checkpoint_b: u128, // 16
+    pub fee_owed_b: u64,               // 8
+
+    pub reward_infos: [PositionRewardInfoData; NUM_REWARDS], // 72
+
+    /// Borsh encodes `bool` as a single byte, 0 or 1.
+    pub is_reinvestment_on: u8, // 1
+
+    pub last_updated_timestamp: u64, // 8
+
+    pub version: u8, // 1
+
+    pub out_of_range_since: u64, // 8
+    pub grace_checkpoint: u64,   // 8
+
+    pub lp_referral: Pubkey,       // 32
+    pub lp_referral_fee_rate: u16, // 2
+
+    pub reward_delegate: Pubkey,  // 32
+    pub reward_recipient: Pubkey, // 32
+
+    pub deposited_a: u64, // 8
+    pub deposited_b: u64, // 8
+    pub withdrawn_a: u64, // 8
+    pub withdrawn_b: u64, // 8
+
+    pub last_reinvestment_slot: u64, // 8
+
+    pub reward_delegator: Pubkey, // 32
+
+    pub principal_owed_a: u64, // 8
+    pub principal_owed_b: u64, // 8
+
+    pub reinvestment_swap_max_deviation_bps: u16, // 2
+
+    pub reserved: [u8; 6], // 6
+}
+
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct PositionRewardInfoData {
+    // Q64.64
+    pub growth_inside_checkpoint: u128,
+    pub amount_owed: u64,
+}
+
+// SAFETY: the struct is packed and only made of integers and pubkeys, so it has no padding and
+// any bit pattern is valid. `is_reinvestment_on` is a `u8` rather than a `bool` for this reason.
+unsafe impl bytemuck::Zeroable for PositionData {}
+unsafe impl bytemuck::Pod for PositionData {}
+
+impl PositionData {
+    pub fn is_reinvestment_on(&self) -> bool {
+        self.is_reinvestment_on != 0
+    }
+}
+
+/// Loads a `Position` account for reading without deserializing it.
+///
+/// # Errors
+/// - `AccountOwnedByWrongProgram`: - The account is not owned by this program.
+/// - `AccountDiscriminatorMismatch`: - The account is not a Position.
+/// - `PositionNotMigrated`: - The position is not at the current layout version.
+pub fn load_position<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, PositionData>> {
+    let data = borrow_position_data(account_info)?;
+    Ok(Ref::map(data, |data| bytemuck::from_bytes::<PositionData>(&data[8..Position::LEN])))
+}
+
+/// Loads a `Position` account for writing without deserializing it.
+///
+/// # Errors
+/// - `AccountNotMutable`: - The account is not writable.
+/// - `AccountOwnedByWrongProgram`: - The account is not owned by this program.
+/// - `AccountDiscriminatorMismatch`: - The account is not a Position.
+/// - `PositionNotMigrated`: - The position is not at the current layout version.
+pub fn load_position_mut<'a>(account_info: &'a AccountInfo) -> Result<RefMut<'a, PositionData>> {
+    if !account_info.is_writable {
+        return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+    }
+    drop(borrow_position_data(account_info)?);
+
+    let data = account_info.try_borrow_mut_data()?;
+    Ok(RefMut::map(data, |data| bytemuck::from_bytes_mut::<PositionData>(&mut data[8..Position::LEN])))
+}
+
+/// Loads a batch of `Position` accounts for reading, such as the remaining accounts of a keeper
+/// instruction.
+///
+/// # Errors
+/// Returns the error of the first account that cannot be loaded with `load_position`.
+pub fn load_positions<'a>(account_infos: &'a [AccountInfo]) -> Result<Vec<Ref<'a, PositionData>>> {
+    account_infos.iter().map(load_position).collect()
+}
+
+fn borrow_position_data<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, &'a mut [u8]>> {
+    if account_info.owner != &crate::ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let data = account_info.try_borrow_data()?;
+    if data.len() < 8 || data[..8] != Position::DISCRIMINATOR {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    // Positions created before the current layout are shorter, `migrate_account` upgrades them
+    if data.len() < Position::LEN || data[Position::VERSION_OFFSET] != Position::CURRENT_VERSION {
+        return Err(ErrorCode::PositionNotMigrated.into());
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod position_data_tests {
+    use super::*;
+    use crate::state::PositionRewardInfo;
+
+    fn test_position() -> Position {
+        let mut position = Position {
+            ai_dex_pool: Pubkey::new_unique(),
+            position_mint: Pubkey::new_unique(),
+            liquidity: 1_000_000,
+            tick_lower_index: -128,
+            tick_upper_index: 256,
+            fee_growth_checkpoint_a: 1 << 64,
+            fee_owed_a: 10,
+            fee_growth_checkpoint_b: 2 << 64,
+            fee_owed_b: 20,
+            is_reinvestment_on: true,
+            last_updated_timestamp: 1_700_000_000,
+            version: Position::CURRENT_VERSION,
+            out_of_range_since: 1_600_000_000,
+            grace_checkpoint: 5,
+            lp_referral: Pubkey::new_unique(),
+            lp_referral_fee_rate: 1_000,
+            reward_delegate: Pubkey::new_unique(),
+            reward_recipient: Pubkey::new_unique(),
+            withdrawn_b: 40,
+            last_reinvestment_slot: 50,
+            ..Default::default()
+        };
+        position.reward_infos[2] = PositionRewardInfo { growth_inside_checkpoint: 3 << 64, amount_owed: 30 };
+        position
+    }
+
+    fn position_account_data(position: &Position) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Position::LEN);
+        position.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_position_data_matches_borsh_layout() {
+        assert_eq!(std::mem::size_of::<PositionData>(), Position::LEN - 8);
+
+        let position = test_position();
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = position_account_data(&position);
+        let account_info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+
+        let position_data = load_position(&account_info).unwrap();
+        assert_eq!({ position_data.ai_dex_pool }, position.ai_dex_pool);
+        assert_eq!({ position_data.liquidity }, position.liquidity);
+        assert_eq!({ position_data.tick_lower_index }, position.tick_lower_index);
+        assert_eq!({ position_data.tick_upper_index }, position.tick_upper_index);
+        assert_eq!({ position_data.fee_owed_b }, position.fee_owed_b);
+        assert_eq!({ position_data.reward_infos[2].amount_owed }, 30);
+        assert!(position_data.is_reinvestment_on());
+        assert_eq!({ position_data.last_updated_timestamp }, position.last_updated_timestamp);
+        assert_eq!({ position_data.lp_referral }, position.lp_referral);
+        assert_eq!({ position_data.lp_referral_fee_rate }, position.lp_referral_fee_rate);
+        assert_eq!({ position_data.reward_recipient }, position.reward_recipient);
+        assert_eq!({ position_data.withdrawn_b }, position.withdrawn_b);
+        assert_eq!({ position_data.last_reinvestment_slot }, position.last_reinvestment_slot);
+    }
+
+    #[test]
+    fn test_load_position_mut_updates_account() {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = position_account_data(&test_position());
+        let account_info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+
+        load_position_mut(&account_info).unwrap().fee_owed_a = 0;
+
+        let position = Position::try_deserialize(&mut &account_info.try_borrow_data().unwrap()[..]).unwrap();
+        assert_eq!(position.fee_owed_a, 0);
+        assert_eq!(position.fee_owed_b, 20);
+    }
+
+    #[test]
+    fn test_load_positions_rejects_legacy_position() {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = position_account_data(&test_position());
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        let mut legacy_lamports = 0;
+        let mut legacy_data = position_account_data(&test_position());
+        legacy_data.truncate(Position::VERSION_OFFSET);
+        let legacy_account_info =
+            AccountInfo::new(&key, false, false, &mut legacy_lamports, &mut legacy_data, &owner, false, 0);
+
+        assert_eq!(load_positions(std::slice::from_ref(&account_info)).unwrap().len(), 1);
+        assert_eq!(
+            load_positions(&[account_info, legacy_account_info]).unwrap_err(),
+            ErrorCode::PositionNotMigrated.into()
+        );
+    }
+}
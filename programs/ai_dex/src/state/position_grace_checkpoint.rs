@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::state::NUM_REWARDS;
+
+/// Reward growth outside the range of a position at its last out-of-range grace update.
+///
+/// The reward growth outside the range of a position only grows while the position is out of
+/// range, so its change since the checkpoint is the reward growth the position missed, as
+/// recorded by the pool. `update_fees_and_rewards` credits the position the grace share of it
+/// from the `RewardGraceEscrow` of the pool when the checkpoint is passed.
+#[account]
+#[derive(Default)]
+pub struct PositionGraceCheckpoint {
+    pub position: Pubkey, // 32
+    /// Timestamp of the last grace update, or 0 before the first one.
+    pub checkpoint_timestamp: u64, // 8
+    /// Liquidity of the position at the last grace update.
+    pub liquidity: u128, // 16
+    /// Reward growth outside the range of the position at the last grace update, Q64.64.
+    pub reward_growths_outside_x64: [u128; NUM_REWARDS], // 48
+    pub bump: u8, // 1
+}
+
+impl PositionGraceCheckpoint {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // position
+    + 8 // checkpoint_timestamp
+    + 16 // liquidity
+    + 16 * NUM_REWARDS // reward_growths_outside_x64
+    + 1; // bump
+
+    pub fn initialize(&mut self, position: Pubkey, bump: u8) {
+        self.position = position;
+        self.bump = bump;
+    }
+
+    pub fn update(&mut self, update: &PositionGraceUpdate) {
+        self.checkpoint_timestamp = update.checkpoint_timestamp;
+        self.liquidity = update.liquidity;
+        self.reward_growths_outside_x64 = update.reward_growths_outside_x64;
+    }
+}
+
+/// Next grace checkpoint of a position, and the grace rewards it is owed before they are taken
+/// from the escrow of the pool.
+#[derive(Default, Debug, PartialEq, Copy, Clone)]
+pub struct PositionGraceUpdate {
+    pub checkpoint_timestamp: u64,
+    pub liquidity: u128,
+    pub reward_growths_outside_x64: [u128; NUM_REWARDS],
+    pub grace_rewards: [u64; NUM_REWARDS],
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Maximum number of positions listed by a page of a position index.
+pub const POSITION_INDEX_PAGE_SIZE: usize = 32;
+
+/// Page of the list of positions of an owner.
+///
+/// Wallets list the positions of an owner by fetching the pages of its index, numbered from 0,
+/// instead of scanning all program accounts by position token ownership. The instructions opening
+/// and closing positions add and remove positions of the pages passed to them. Position tokens
+/// are transferred outside of the program, so `reindex_position` moves a transferred position
+/// from the page of its previous owner to a page of its current owner.
+#[account]
+#[derive(Default)]
+pub struct PositionIndex {
+    pub owner: Pubkey, // 32
+    pub page: u16, // 2
+    pub bump: u8, // 1
+    pub positions: Vec<Pubkey>, // 4 + 32 * POSITION_INDEX_PAGE_SIZE
+}
+
+impl PositionIndex {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // owner
+    + 2 // page
+    + 1 // bump
+    + 4 + 32 * POSITION_INDEX_PAGE_SIZE; // positions
+
+    pub fn initialize(&mut self, owner: Pubkey, page: u16, bump: u8) {
+        self.owner = owner;
+        self.page = page;
+        self.bump = bump;
+    }
+
+    /// Adds a position to the page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page is full or already lists the position.
+    pub fn add_position(&mut self, position: Pubkey) -> Result<()> {
+        if self.positions.len() >= POSITION_INDEX_PAGE_SIZE {
+            return Err(ErrorCode::PositionIndexFull.into());
+        }
+        if self.positions.contains(&position) {
+            return Err(ErrorCode::PositionAlreadyIndexed.into());
+        }
+        self.positions.push(position);
+        Ok(())
+    }
+
+    /// Removes a position from the page, moving the last position of the page into its slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page does not list the position.
+    pub fn remove_position(&mut self, position: &Pubkey) -> Result<()> {
+        let index = self
+            .positions
+            .iter()
+            .position(|listed| listed == position)
+            .ok_or(ErrorCode::PositionNotIndexed)?;
+        self.positions.swap_remove(index);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod position_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_positions() {
+        let mut position_index = PositionIndex::default();
+        let positions: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for position in &positions {
+            position_index.add_position(*position).unwrap();
+        }
+        assert_eq!(
+            position_index.add_position(positions[1]).unwrap_err(),
+            ErrorCode::PositionAlreadyIndexed.into()
+        );
+
+        position_index.remove_position(&positions[0]).unwrap();
+        assert_eq!(position_index.positions, vec![positions[2], positions[1]]);
+        assert_eq!(
+            position_index.remove_position(&positions[0]).unwrap_err(),
+            ErrorCode::PositionNotIndexed.into()
+        );
+    }
+
+    #[test]
+    fn test_page_is_bounded() {
+        let mut position_index = PositionIndex::default();
+        for _ in 0..POSITION_INDEX_PAGE_SIZE {
+            position_index.add_position(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            position_index.add_position(Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::PositionIndexFull.into()
+        );
+
+        let mut data = Vec::new();
+        position_index.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PositionIndex::LEN);
+    }
+}
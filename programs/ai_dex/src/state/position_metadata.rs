@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::nft::{MAX_METADATA_NAME_LEN, MAX_METADATA_SYMBOL_LEN, MAX_METADATA_URI_LEN},
+    errors::ErrorCode,
+};
+
+/// Position NFT metadata used by all pools of a config instead of the default constants.
+#[account]
+pub struct PositionMetadata {
+    pub ai_dex_config: Pubkey, // 32
+    pub name: String, // 4 + 32
+    pub symbol: String, // 4 + 10
+    pub uri: String, // 4 + 200
+    pub collection_mint: Pubkey, // 32, default when position NFTs have no collection
+}
+
+impl PositionMetadata {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 4 + MAX_METADATA_NAME_LEN // name
+    + 4 + MAX_METADATA_SYMBOL_LEN // symbol
+    + 4 + MAX_METADATA_URI_LEN // uri
+    + 32; // collection_mint
+
+    pub fn initialize(
+        &mut self,
+        ai_dex_config: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        self.ai_dex_config = ai_dex_config;
+        self.update_metadata(name, symbol, uri)
+    }
+
+    /// Updates the metadata used when minting position NFTs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name, symbol or uri exceeds the Metaplex maximum length.
+    pub fn update_metadata(&mut self, name: String, symbol: String, uri: String) -> Result<()> {
+        if name.len() > MAX_METADATA_NAME_LEN
+            || symbol.len() > MAX_METADATA_SYMBOL_LEN
+            || uri.len() > MAX_METADATA_URI_LEN
+        {
+            return Err(ErrorCode::PositionMetadataTooLong.into());
+        }
+        self.name = name;
+        self.symbol = symbol;
+        self.uri = uri;
+        Ok(())
+    }
+
+    /// Sets the Metaplex collection position NFTs are verified into, `Pubkey::default()` to disable.
+    pub fn update_collection_mint(&mut self, collection_mint: Pubkey) {
+        self.collection_mint = collection_mint;
+    }
+
+    pub fn get_collection_mint(&self) -> Option<Pubkey> {
+        if self.collection_mint == Pubkey::default() {
+            None
+        } else {
+            Some(self.collection_mint)
+        }
+    }
+}
+
+#[cfg(test)]
+mod position_metadata_tests {
+    use super::*;
+
+    fn new_position_metadata() -> PositionMetadata {
+        PositionMetadata {
+            ai_dex_config: Pubkey::default(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            collection_mint: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_update_metadata() {
+        let mut metadata = new_position_metadata();
+        metadata
+            .update_metadata("Name".to_string(), "SYM".to_string(), "https://uri".to_string())
+            .unwrap();
+        assert_eq!(metadata.name, "Name");
+        assert_eq!(metadata.symbol, "SYM");
+        assert_eq!(metadata.uri, "https://uri");
+    }
+
+    #[test]
+    fn test_update_metadata_max_lengths() {
+        let mut metadata = new_position_metadata();
+        assert!(metadata
+            .update_metadata(
+                "n".repeat(MAX_METADATA_NAME_LEN),
+                "s".repeat(MAX_METADATA_SYMBOL_LEN),
+                "u".repeat(MAX_METADATA_URI_LEN),
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_update_metadata_too_long() {
+        let mut metadata = new_position_metadata();
+        let too_long = [
+            ("n".repeat(MAX_METADATA_NAME_LEN + 1), String::new(), String::new()),
+            (String::new(), "s".repeat(MAX_METADATA_SYMBOL_LEN + 1), String::new()),
+            (String::new(), String::new(), "u".repeat(MAX_METADATA_URI_LEN + 1)),
+        ];
+        for (name, symbol, uri) in too_long {
+            assert_eq!(
+                metadata.update_metadata(name, symbol, uri).unwrap_err(),
+                ErrorCode::PositionMetadataTooLong.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_collection_mint() {
+        let mut metadata = new_position_metadata();
+        assert_eq!(metadata.get_collection_mint(), None);
+
+        let collection_mint = Pubkey::new_unique();
+        metadata.update_collection_mint(collection_mint);
+        assert_eq!(metadata.get_collection_mint(), Some(collection_mint));
+
+        metadata.update_collection_mint(Pubkey::default());
+        assert_eq!(metadata.get_collection_mint(), None);
+    }
+}
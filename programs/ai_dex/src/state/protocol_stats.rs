@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Maximum number of mints whose collected protocol fees are tracked by the protocol stats.
+pub const MAX_PROTOCOL_STATS_MINTS: usize = 16;
+
+/// Protocol fees collected in one token mint across the pools of a config.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct MintProtocolFees {
+    pub mint: Pubkey, // 32
+    /// Protocol fees collected in the mint, in raw token units.
+    pub collected_amount: u128, // 16
+}
+
+impl MintProtocolFees {
+    pub const LEN: usize = 32 + 16;
+}
+
+/// Totals of the pools of a config, for on-chain revenue reporting to the DAO.
+///
+/// Anyone can crank `sync_protocol_stats` with the revenue snapshots of the pools, which adds
+/// the protocol fees collected since the previous sync and copies the pool and swap counts of
+/// the config.
+#[account]
+#[derive(Default)]
+pub struct ProtocolStats {
+    pub ai_dex_config: Pubkey, // 32
+    pub pool_count: u64, // 8
+    pub total_swaps: u64, // 8
+    pub protocol_fees: Vec<MintProtocolFees>, // 4 + 48 * MAX_PROTOCOL_STATS_MINTS
+    pub last_update_timestamp: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl ProtocolStats {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 8 // pool_count
+    + 8 // total_swaps
+    + 4 + MintProtocolFees::LEN * MAX_PROTOCOL_STATS_MINTS // protocol_fees
+    + 8 // last_update_timestamp
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, bump: u8) {
+        self.ai_dex_config = ai_dex_config;
+        self.protocol_fees = Vec::new();
+        self.bump = bump;
+    }
+
+    /// Adds protocol fees collected in a mint. Totals saturate rather than fail the sync.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mint is not tracked yet and `MAX_PROTOCOL_STATS_MINTS` mints
+    /// already are.
+    pub fn add_protocol_fees(&mut self, mint: Pubkey, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        match self.protocol_fees.iter_mut().find(|mint_protocol_fees| mint_protocol_fees.mint == mint) {
+            Some(mint_protocol_fees) => {
+                mint_protocol_fees.collected_amount =
+                    mint_protocol_fees.collected_amount.saturating_add(amount as u128);
+            }
+            None => {
+                if self.protocol_fees.len() >= MAX_PROTOCOL_STATS_MINTS {
+                    return Err(ErrorCode::ProtocolStatsMintLimitReached.into());
+                }
+                self.protocol_fees.push(MintProtocolFees { mint, collected_amount: amount as u128 });
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies the pool and swap counts of the config and records the time of the sync.
+    pub fn update_counts(&mut self, pool_count: u64, total_swaps: u64, timestamp: u64) {
+        self.pool_count = pool_count;
+        self.total_swaps = total_swaps;
+        self.last_update_timestamp = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod protocol_stats_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_protocol_fees() {
+        let mut protocol_stats = ProtocolStats::default();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+
+        protocol_stats.add_protocol_fees(mint_a, 100).unwrap();
+        protocol_stats.add_protocol_fees(mint_b, 0).unwrap();
+        protocol_stats.add_protocol_fees(mint_a, u64::MAX).unwrap();
+        protocol_stats.add_protocol_fees(mint_b, 5).unwrap();
+
+        assert_eq!(
+            protocol_stats.protocol_fees,
+            vec![
+                MintProtocolFees { mint: mint_a, collected_amount: u64::MAX as u128 + 100 },
+                MintProtocolFees { mint: mint_b, collected_amount: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_protocol_fees_bounds_mints() {
+        let mut protocol_stats = ProtocolStats::default();
+        let mints: Vec<Pubkey> = (0..MAX_PROTOCOL_STATS_MINTS).map(|_| Pubkey::new_unique()).collect();
+        for mint in &mints {
+            protocol_stats.add_protocol_fees(*mint, 1).unwrap();
+        }
+
+        assert_eq!(
+            protocol_stats.add_protocol_fees(Pubkey::new_unique(), 1).unwrap_err(),
+            ErrorCode::ProtocolStatsMintLimitReached.into()
+        );
+        // Tracked mints keep accumulating, and an empty amount never needs a slot
+        protocol_stats.add_protocol_fees(mints[0], 1).unwrap();
+        protocol_stats.add_protocol_fees(Pubkey::new_unique(), 0).unwrap();
+        assert_eq!(protocol_stats.protocol_fees[0].collected_amount, 2);
+    }
+
+    #[test]
+    fn test_len_fits_max_mints() {
+        let mut protocol_stats = ProtocolStats::default();
+        for _ in 0..MAX_PROTOCOL_STATS_MINTS {
+            protocol_stats.add_protocol_fees(Pubkey::new_unique(), 1).unwrap();
+        }
+        let mut data = Vec::new();
+        protocol_stats.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ProtocolStats::LEN);
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+/// Referral fees of a swap referral owed by the vaults of a pool.
+///
+/// Swaps passing the escrow in their remaining accounts accrue the referral fee here instead of
+/// transferring it to the referral token accounts, saving the transfer and not requiring the
+/// token accounts to exist. The referrer collects the fees owed with `collect_referral_reward_fee`.
+#[account]
+#[derive(Default)]
+pub struct ReferralFeeEscrow {
+    pub swap_referral: Pubkey, // 32
+    pub ai_dex_pool: Pubkey, // 32
+    pub fee_owed_a: u64, // 8
+    pub fee_owed_b: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl ReferralFeeEscrow {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // swap_referral
+    + 32 // ai_dex_pool
+    + 8 // fee_owed_a
+    + 8 // fee_owed_b
+    + 1; // bump
+
+    pub fn initialize(&mut self, swap_referral: Pubkey, ai_dex_pool: Pubkey, bump: u8) {
+        self.swap_referral = swap_referral;
+        self.ai_dex_pool = ai_dex_pool;
+        self.bump = bump;
+    }
+
+    /// Adds the referral fee of a swap, paid in token A if `a_to_b`. Saturates rather than fail
+    /// the swap.
+    pub fn accrue_fee(&mut self, amount: u64, a_to_b: bool) {
+        if a_to_b {
+            self.fee_owed_a = self.fee_owed_a.saturating_add(amount);
+        } else {
+            self.fee_owed_b = self.fee_owed_b.saturating_add(amount);
+        }
+    }
+
+    /// Resets the fees owed in token A, or in token B, and returns them.
+    pub fn take_fee_owed(&mut self, is_token_a: bool) -> u64 {
+        if is_token_a {
+            std::mem::take(&mut self.fee_owed_a)
+        } else {
+            std::mem::take(&mut self.fee_owed_b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod referral_fee_escrow_tests {
+    use super::*;
+
+    #[test]
+    fn test_accrue_and_take_fee_owed() {
+        let mut escrow = ReferralFeeEscrow::default();
+        escrow.accrue_fee(100, true);
+        escrow.accrue_fee(50, true);
+        escrow.accrue_fee(7, false);
+        escrow.accrue_fee(u64::MAX, false);
+
+        assert_eq!(escrow.take_fee_owed(true), 150);
+        assert_eq!(escrow.take_fee_owed(true), 0);
+        assert_eq!(escrow.fee_owed_b, u64::MAX);
+        assert_eq!(escrow.take_fee_owed(false), u64::MAX);
+        assert_eq!(escrow.fee_owed_b, 0);
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Reinvestment fees of a pool owed to a reinvestments account.
+///
+/// Reinvestments through a reinvestments account accrue their fee here, and only the authority
+/// of that account can collect it with `collect_reinvestment_fees`. The pool keeps the total owed
+/// to all the reinvestments accounts in its own `reinvestment_fee_owed_a/b`.
+#[account]
+#[derive(Default)]
+pub struct ReinvestmentFeeEscrow {
+    pub reinvestments_account: Pubkey, // 32
+    pub ai_dex_pool: Pubkey, // 32
+    pub fee_owed_a: u64, // 8
+    pub fee_owed_b: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl ReinvestmentFeeEscrow {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // reinvestments_account
+    + 32 // ai_dex_pool
+    + 8 // fee_owed_a
+    + 8 // fee_owed_b
+    + 1; // bump
+
+    pub fn initialize(&mut self, reinvestments_account: Pubkey, ai_dex_pool: Pubkey, bump: u8) {
+        self.reinvestments_account = reinvestments_account;
+        self.ai_dex_pool = ai_dex_pool;
+        self.bump = bump;
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.ai_dex_pool != Pubkey::default()
+    }
+
+    /// Adds the reinvestment fees taken from the fees of a position.
+    pub fn accrue_fees(&mut self, fee_a: u64, fee_b: u64) {
+        self.fee_owed_a = self.fee_owed_a.wrapping_add(fee_a);
+        self.fee_owed_b = self.fee_owed_b.wrapping_add(fee_b);
+    }
+
+    /// Resets the fees owed and returns them.
+    pub fn take_fees_owed(&mut self) -> (u64, u64) {
+        (std::mem::take(&mut self.fee_owed_a), std::mem::take(&mut self.fee_owed_b))
+    }
+}
+
+#[cfg(test)]
+mod reinvestment_fee_escrow_tests {
+    use super::*;
+
+    #[test]
+    fn test_accrue_and_take_fees_owed() {
+        let mut escrow = ReinvestmentFeeEscrow::default();
+        escrow.accrue_fees(100, 200);
+        escrow.accrue_fees(1, 2);
+        assert_eq!(escrow.take_fees_owed(), (101, 202));
+        assert_eq!(escrow.take_fees_owed(), (0, 0));
+    }
+}
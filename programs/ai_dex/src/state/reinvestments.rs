@@ -5,10 +5,21 @@ use crate::{errors::ErrorCode, math::MAX_REINVESTMENT_PROTOCOL_FEE_RATE};
 pub struct AiDexReinvestments {
     pub reinvestments_authority: Pubkey,
     pub default_reinvestment_fee_rate: u16,
+    pub version: u8,
+
+    /// Config whose pools this account reinvests for, or the default pubkey for global accounts
+    /// created by `initialize_reinvestments`, which any config may use.
+    pub ai_dex_config: Pubkey,
 }
 
 impl AiDexReinvestments {
-    pub const LEN: usize = 8 + 32 + 2;
+    pub const LEN: usize = 8 + 32 + 2 + 1 + 32;
+
+    /// The current layout version of the reinvestments account.
+    pub const CURRENT_VERSION: u8 = 1;
+
+    /// The offset of the version field in the account data.
+    pub const VERSION_OFFSET: usize = 8 + 32 + 2;
 
     pub fn initialize(
         &mut self,
@@ -16,6 +27,7 @@ impl AiDexReinvestments {
         default_reinvestment_fee_rate: u16
     ) -> Result<()> {
         self.reinvestments_authority = reinvestments_authority;
+        self.version = Self::CURRENT_VERSION;
         self.update_default_reinvestment_fee_rate(default_reinvestment_fee_rate)?;
         Ok(())
     }
@@ -35,5 +47,37 @@ impl AiDexReinvestments {
         self.reinvestments_authority = reinvestments_authority;
         Ok(())
     }
-    
+
+    /// Scopes the account to a single config, so it can only reinvest fees of that config's pools.
+    pub fn scope_to_config(&mut self, ai_dex_config: Pubkey) {
+        self.ai_dex_config = ai_dex_config;
+    }
+
+    /// Returns whether the account may reinvest fees of pools belonging to the given config.
+    pub fn is_usable_by_config(&self, ai_dex_config: &Pubkey) -> bool {
+        self.ai_dex_config == Pubkey::default() || self.ai_dex_config == *ai_dex_config
+    }
+}
+
+#[cfg(test)]
+mod reinvestments_config_scope_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_usable_by_config() {
+        let mut reinvestments = AiDexReinvestments {
+            reinvestments_authority: Pubkey::new_unique(),
+            default_reinvestment_fee_rate: 0,
+            version: 0,
+            ai_dex_config: Pubkey::default(),
+        };
+        let config = Pubkey::new_unique();
+        let other_config = Pubkey::new_unique();
+        assert!(reinvestments.is_usable_by_config(&config));
+        assert!(reinvestments.is_usable_by_config(&other_config));
+
+        reinvestments.scope_to_config(config);
+        assert!(reinvestments.is_usable_by_config(&config));
+        assert!(!reinvestments.is_usable_by_config(&other_config));
+    }
 }
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::NUM_REWARDS;
+
+/// Limits on the emissions the reward authorities of a pool set alone with
+/// `set_reward_emissions`, managed by the reward manager of the config.
+///
+/// A reward authority can set the emissions of a reward within its threshold of the baseline
+/// rate. Rates further from the baseline are co-signed by the reward manager and become the new
+/// baseline, so repeated changes cannot ramp the emissions past the threshold.
+#[account]
+#[derive(Default)]
+pub struct RewardEmissionsPolicy {
+    pub ai_dex_pool: Pubkey, // 32
+    /// Largest distance from the baseline of `emissions_per_second_x64` of each reward, or 0 for
+    /// no limit.
+    pub thresholds_x64: [u128; NUM_REWARDS], // 48
+    /// Emissions per second of each reward last approved by the reward manager.
+    pub baseline_emissions_per_second_x64: [u128; NUM_REWARDS], // 48
+    pub bump: u8, // 1
+}
+
+impl RewardEmissionsPolicy {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 16 * NUM_REWARDS // thresholds_x64
+    + 16 * NUM_REWARDS // baseline_emissions_per_second_x64
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, bump: u8) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.bump = bump;
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.ai_dex_pool != Pubkey::default()
+    }
+
+    /// Sets the threshold of the reward at the specified index, taking its current emissions as
+    /// the baseline.
+    ///
+    /// # Errors
+    /// This function returns an error if the reward index is invalid.
+    pub fn update_threshold(
+        &mut self,
+        index: usize,
+        threshold_x64: u128,
+        emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndexError.into());
+        }
+        self.thresholds_x64[index] = threshold_x64;
+        self.baseline_emissions_per_second_x64[index] = emissions_per_second_x64;
+
+        Ok(())
+    }
+
+    /// Checks if setting the emissions of the reward at the specified index to
+    /// `emissions_per_second_x64` requires the co-signature of the reward manager.
+    pub fn requires_co_signature(&self, index: usize, emissions_per_second_x64: u128) -> bool {
+        let threshold_x64 = self.thresholds_x64[index];
+        threshold_x64 != 0
+            && self.baseline_emissions_per_second_x64[index].abs_diff(emissions_per_second_x64) > threshold_x64
+    }
+
+    /// Records emissions co-signed by the reward manager as the baseline of the reward at the
+    /// specified index.
+    pub fn update_baseline(&mut self, index: usize, emissions_per_second_x64: u128) {
+        self.baseline_emissions_per_second_x64[index] = emissions_per_second_x64;
+    }
+}
+
+#[cfg(test)]
+mod reward_emissions_policy_tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_co_signature() {
+        let mut policy = RewardEmissionsPolicy::default();
+        assert!(!policy.requires_co_signature(0, u128::MAX));
+
+        policy.update_threshold(0, 100, 1_000).unwrap();
+        assert!(!policy.requires_co_signature(0, 1_100));
+        assert!(!policy.requires_co_signature(0, 900));
+        assert!(policy.requires_co_signature(0, 1_101));
+        assert!(policy.requires_co_signature(0, 0));
+        assert!(!policy.requires_co_signature(1, u128::MAX));
+
+        assert_eq!(
+            policy.update_threshold(NUM_REWARDS, 100, 0).unwrap_err(),
+            ErrorCode::InvalidRewardIndexError.into()
+        );
+    }
+
+    #[test]
+    fn test_repeated_changes_stay_within_threshold_of_baseline() {
+        let mut policy = RewardEmissionsPolicy::default();
+        policy.update_threshold(0, 100, 1_000).unwrap();
+
+        // Stepping up from the last rate set alone does not move the baseline.
+        assert!(!policy.requires_co_signature(0, 1_100));
+        assert!(policy.requires_co_signature(0, 1_200));
+
+        policy.update_baseline(0, 1_200);
+        assert!(!policy.requires_co_signature(0, 1_300));
+        assert!(policy.requires_co_signature(0, 1_000));
+    }
+}
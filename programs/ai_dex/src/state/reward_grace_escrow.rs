@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+
+use crate::math::checked_mul_shift_right;
+use crate::state::{AiDexPool, NUM_REWARDS};
+
+/// Out-of-range grace share of the reward emissions of a pool, escrowed for the positions in
+/// their grace period.
+///
+/// The grace share of the emissions of a reward is not added to the reward growth of the pool. It
+/// accrues here instead, and `update_fees_and_rewards` credits it to positions for the reward
+/// growth they missed while out of range, so the grace rewards never exceed that share.
+#[account]
+#[derive(Default)]
+pub struct RewardGraceEscrow {
+    pub ai_dex_pool: Pubkey, // 32
+    /// Grace rewards of each reward escrowed but not credited to positions yet.
+    pub amounts: [u64; NUM_REWARDS], // 24
+    /// Timestamp up to which the grace rewards are accrued.
+    pub accrued_timestamp: u64, // 8
+    /// Timestamp at which the grace of each reward was last enabled, before which positions are
+    /// not credited any grace reward.
+    pub enabled_timestamps: [u64; NUM_REWARDS], // 24
+    pub bump: u8, // 1
+}
+
+impl RewardGraceEscrow {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 8 * NUM_REWARDS // amounts
+    + 8 // accrued_timestamp
+    + 8 * NUM_REWARDS // enabled_timestamps
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, timestamp: u64, bump: u8) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.accrued_timestamp = timestamp;
+        self.bump = bump;
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.ai_dex_pool != Pubkey::default()
+    }
+
+    /// Accrues the grace share of the emissions of each reward from the last accrual up to
+    /// `timestamp`, at the current grace parameters and emissions of the pool. Must be called
+    /// before either changes.
+    pub fn accrue(&mut self, ai_dex: &AiDexPool, timestamp: u64) {
+        if timestamp <= self.accrued_timestamp {
+            return;
+        }
+        let time_delta = u128::from(timestamp - self.accrued_timestamp);
+
+        for i in 0..NUM_REWARDS {
+            // If the calculation overflows, the grace share of the period is not escrowed.
+            let amount =
+                checked_mul_shift_right(time_delta, ai_dex.grace_emissions_per_second_x64(i)).unwrap_or(0);
+            self.amounts[i] = self.amounts[i].saturating_add(amount);
+        }
+        self.accrued_timestamp = timestamp;
+    }
+
+    /// Records the timestamp at which the grace of the reward at the specified index is enabled,
+    /// when it changes from `previous_grace_bps` to `grace_bps`.
+    pub fn update_enabled_timestamp(&mut self, index: usize, previous_grace_bps: u16, grace_bps: u16, timestamp: u64) {
+        if previous_grace_bps == 0 && grace_bps != 0 {
+            self.enabled_timestamps[index] = timestamp;
+        }
+    }
+
+    /// Takes up to `amount` of the grace rewards escrowed for the reward at the specified index,
+    /// and returns the amount taken.
+    pub fn take(&mut self, index: usize, amount: u64) -> u64 {
+        let taken = amount.min(self.amounts[index]);
+        self.amounts[index] -= taken;
+        taken
+    }
+}
+
+#[cfg(test)]
+mod reward_grace_escrow_tests {
+    use super::*;
+    use crate::math::Q64_RESOLUTION;
+    use crate::state::AiDexRewardInfo;
+
+    fn test_pool() -> AiDexPool {
+        let mut ai_dex = AiDexPool::default();
+        ai_dex.reward_infos[0] = AiDexRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64: 100 << Q64_RESOLUTION,
+            ..Default::default()
+        };
+        ai_dex.reward_infos[1] = ai_dex.reward_infos[0];
+        ai_dex.out_of_range_grace_bps = [2_500, 0, 10_000];
+        ai_dex
+    }
+
+    #[test]
+    fn test_accrue() {
+        let mut ai_dex = test_pool();
+        let mut escrow = RewardGraceEscrow::default();
+        escrow.initialize(Pubkey::new_unique(), 1_000, 255);
+
+        escrow.accrue(&ai_dex, 1_010);
+        // Reward 2 has no emissions, and reward 1 no grace
+        assert_eq!(escrow.amounts, [250, 0, 0]);
+        assert_eq!(escrow.accrued_timestamp, 1_010);
+
+        // Earlier timestamps accrue nothing
+        escrow.accrue(&ai_dex, 1_005);
+        assert_eq!(escrow.amounts, [250, 0, 0]);
+        assert_eq!(escrow.accrued_timestamp, 1_010);
+
+        // Nothing accrues while the reward is paused
+        ai_dex.paused_rewards = 0b001;
+        escrow.accrue(&ai_dex, 1_020);
+        assert_eq!(escrow.amounts, [250, 0, 0]);
+        assert_eq!(escrow.accrued_timestamp, 1_020);
+    }
+
+    #[test]
+    fn test_take() {
+        let mut escrow = RewardGraceEscrow { amounts: [100, 0, 5], ..Default::default() };
+        assert_eq!(escrow.take(0, 60), 60);
+        assert_eq!(escrow.take(0, 60), 40);
+        assert_eq!(escrow.take(0, 60), 0);
+        assert_eq!(escrow.take(2, 0), 0);
+        assert_eq!(escrow.amounts, [0, 0, 5]);
+    }
+
+    #[test]
+    fn test_update_enabled_timestamp() {
+        let mut escrow = RewardGraceEscrow::default();
+        escrow.update_enabled_timestamp(1, 0, 2_500, 1_000);
+        assert_eq!(escrow.enabled_timestamps, [0, 1_000, 0]);
+
+        // Changing or disabling an enabled grace keeps its timestamp
+        escrow.update_enabled_timestamp(1, 2_500, 5_000, 2_000);
+        escrow.update_enabled_timestamp(1, 5_000, 0, 3_000);
+        assert_eq!(escrow.enabled_timestamps, [0, 1_000, 0]);
+
+        escrow.update_enabled_timestamp(1, 0, 5_000, 4_000);
+        assert_eq!(escrow.enabled_timestamps, [0, 4_000, 0]);
+    }
+}
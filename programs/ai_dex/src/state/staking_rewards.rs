@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Staking rewards of a config, funded by a share of the protocol fees of swaps.
+///
+/// Each pool of the config owes its share of the protocol fees to the staking rewards as swaps
+/// accrue them, and keeps the tokens in its vaults until the staking authority claims them.
+/// The staking authority is expected to be a PDA of the staking program, so that only the
+/// staking program can claim, through CPI.
+#[account]
+#[derive(Default)]
+pub struct StakingRewards {
+    pub ai_dex_config: Pubkey, // 32
+    pub staking_authority: Pubkey, // 32
+    pub bump: u8, // 1
+}
+
+impl StakingRewards {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 32 // staking_authority
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, staking_authority: Pubkey, bump: u8) {
+        self.ai_dex_config = ai_dex_config;
+        self.staking_authority = staking_authority;
+        self.bump = bump;
+    }
+}
@@ -1,20 +1,178 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
+/// The delay between a recovery request and the recovery authority claiming the super admin
+/// role, during which any admin instruction signed by the super admin cancels the request.
+pub const SUPER_ADMIN_RECOVERY_CLAIM_DELAY: u64 = 3 * 24 * 60 * 60;
+
 /// The SuperAdmin account which holds the super admin's public key.
 #[account]
 pub struct SuperAdmin {
     pub super_admin: Pubkey, // Storing the super admin's public key
+    pub version: u8,
+
+    /// Key allowed to claim the super admin role once the super admin has been inactive for
+    /// `recovery_inactivity_timeout` seconds, or the default pubkey if recovery is disabled.
+    pub recovery_authority: Pubkey,
+    pub recovery_inactivity_timeout: u64,
+    /// Timestamp of the last admin instruction signed by the super admin.
+    pub last_active_timestamp: u64,
+    /// Timestamp of the pending recovery request, or 0 if no recovery is requested.
+    pub recovery_requested_timestamp: u64,
 }
 
 impl SuperAdmin {
-    // Define the length of the account (32 bytes for `Pubkey` + 8 for discriminator).
-    pub const LEN: usize = 32 + 8;
+    // Define the length of the account (8 for discriminator + 32 for `Pubkey` + 1 for the
+    // version + 32 + 8 + 8 + 8 for the recovery fields).
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 8 + 8 + 8;
+
+    /// The current layout version of the super admin account.
+    pub const CURRENT_VERSION: u8 = 1;
 
-    pub fn initialize(&mut self, super_admin: Pubkey) {
+    /// The offset of the version field in the account data.
+    pub const VERSION_OFFSET: usize = 8 + 32;
+
+    pub fn initialize(&mut self, super_admin: Pubkey, timestamp: u64) {
         self.super_admin = super_admin;
+        self.version = Self::CURRENT_VERSION;
+        self.last_active_timestamp = timestamp;
     }
 
     pub fn update_super_admin(&mut self, super_admin: Pubkey) {
         self.super_admin = super_admin;
     }
+
+    /// Records an admin instruction signed by the super admin, cancelling any pending recovery.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a pending recovery request was cancelled, `false` otherwise.
+    pub fn record_activity(&mut self, timestamp: u64) -> bool {
+        self.last_active_timestamp = timestamp;
+        let cancelled = self.recovery_requested_timestamp != 0;
+        self.recovery_requested_timestamp = 0;
+        cancelled
+    }
+
+    /// Sets the recovery authority and the inactivity timeout after which it can claim the role.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a recovery authority is set with a zero timeout, or a timeout is set
+    /// without a recovery authority.
+    pub fn update_recovery(
+        &mut self,
+        recovery_authority: Pubkey,
+        recovery_inactivity_timeout: u64,
+    ) -> Result<()> {
+        if (recovery_authority == Pubkey::default()) != (recovery_inactivity_timeout == 0) {
+            return Err(ErrorCode::InvalidSuperAdminRecovery.into());
+        }
+        self.recovery_authority = recovery_authority;
+        self.recovery_inactivity_timeout = recovery_inactivity_timeout;
+        self.recovery_requested_timestamp = 0;
+        Ok(())
+    }
+
+    /// Starts the recovery of the super admin role by the recovery authority.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if recovery is disabled, already requested, or the super admin has not
+    /// been inactive for the recovery timeout.
+    pub fn request_recovery(&mut self, timestamp: u64) -> Result<()> {
+        if self.recovery_authority == Pubkey::default() || self.recovery_requested_timestamp != 0 {
+            return Err(ErrorCode::SuperAdminRecoveryUnavailable.into());
+        }
+        let recoverable_at = self
+            .last_active_timestamp
+            .saturating_add(self.recovery_inactivity_timeout);
+        if timestamp < recoverable_at {
+            return Err(ErrorCode::SuperAdminRecoveryUnavailable.into());
+        }
+        self.recovery_requested_timestamp = timestamp;
+        Ok(())
+    }
+
+    /// Hands the super admin role to the recovery authority once the claim delay has passed.
+    /// Recovery is disabled afterwards until the new super admin sets it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no recovery is requested or the claim delay has not passed.
+    pub fn claim_recovery(&mut self, timestamp: u64) -> Result<()> {
+        if self.recovery_requested_timestamp == 0
+            || timestamp
+                < self
+                    .recovery_requested_timestamp
+                    .saturating_add(SUPER_ADMIN_RECOVERY_CLAIM_DELAY)
+        {
+            return Err(ErrorCode::SuperAdminRecoveryNotClaimable.into());
+        }
+        self.super_admin = self.recovery_authority;
+        self.recovery_authority = Pubkey::default();
+        self.recovery_inactivity_timeout = 0;
+        self.recovery_requested_timestamp = 0;
+        self.last_active_timestamp = timestamp;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod super_admin_recovery_tests {
+    use super::*;
+
+    const TIMEOUT: u64 = 1_000;
+
+    fn super_admin_with_recovery(recovery_authority: Pubkey) -> SuperAdmin {
+        let mut super_admin = SuperAdmin {
+            super_admin: Pubkey::new_unique(),
+            version: 0,
+            recovery_authority: Pubkey::default(),
+            recovery_inactivity_timeout: 0,
+            last_active_timestamp: 0,
+            recovery_requested_timestamp: 0,
+        };
+        super_admin.initialize(super_admin.super_admin, 100);
+        super_admin.update_recovery(recovery_authority, TIMEOUT).unwrap();
+        super_admin
+    }
+
+    #[test]
+    fn test_update_recovery_requires_timeout_with_authority() {
+        let mut super_admin = super_admin_with_recovery(Pubkey::new_unique());
+        assert!(super_admin.update_recovery(Pubkey::new_unique(), 0).is_err());
+        assert!(super_admin.update_recovery(Pubkey::default(), TIMEOUT).is_err());
+        assert!(super_admin.update_recovery(Pubkey::default(), 0).is_ok());
+        assert!(super_admin.request_recovery(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_recovery_after_inactivity() {
+        let recovery_authority = Pubkey::new_unique();
+        let mut super_admin = super_admin_with_recovery(recovery_authority);
+
+        assert!(super_admin.request_recovery(100 + TIMEOUT - 1).is_err());
+        super_admin.request_recovery(100 + TIMEOUT).unwrap();
+        assert!(super_admin.request_recovery(100 + TIMEOUT).is_err());
+
+        let claimable_at = 100 + TIMEOUT + SUPER_ADMIN_RECOVERY_CLAIM_DELAY;
+        assert!(super_admin.claim_recovery(claimable_at - 1).is_err());
+        super_admin.claim_recovery(claimable_at).unwrap();
+        assert_eq!(super_admin.super_admin, recovery_authority);
+        assert_eq!(super_admin.recovery_authority, Pubkey::default());
+        assert_eq!(super_admin.last_active_timestamp, claimable_at);
+    }
+
+    #[test]
+    fn test_activity_cancels_recovery() {
+        let mut super_admin = super_admin_with_recovery(Pubkey::new_unique());
+        super_admin.request_recovery(100 + TIMEOUT).unwrap();
+
+        assert!(super_admin.record_activity(200 + TIMEOUT));
+        assert!(!super_admin.record_activity(200 + TIMEOUT));
+        assert!(super_admin.claim_recovery(u64::MAX).is_err());
+        assert!(super_admin.request_recovery(200 + 2 * TIMEOUT - 1).is_err());
+    }
 }
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::errors::ErrorCode;
+
+// Maximum number of slots between a swap commitment and its reveal (~1 minute)
+pub const MAX_SWAP_COMMITMENT_AGE_SLOTS: u64 = 150;
+
+/// Hash of the parameters of a swap committed ahead of its execution, so the swap cannot be
+/// observed and sandwiched before it lands.
+#[account]
+pub struct SwapCommitment {
+    pub ai_dex_pool: Pubkey, // 32
+    pub payer: Pubkey, // 32
+    pub commitment_hash: [u8; 32], // 32
+    pub commit_slot: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl SwapCommitment {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 32 // payer
+    + 32 // commitment_hash
+    + 8 // commit_slot
+    + 1; // bump
+
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool: Pubkey,
+        payer: Pubkey,
+        commitment_hash: [u8; 32],
+        commit_slot: u64,
+        bump: u8,
+    ) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.payer = payer;
+        self.commitment_hash = commitment_hash;
+        self.commit_slot = commit_slot;
+        self.bump = bump;
+    }
+
+    /// Computes the commitment hash of the swap parameters, bound to the pool and the payer.
+    pub fn compute_hash(
+        ai_dex_pool: &Pubkey,
+        payer: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        hashv(&[
+            ai_dex_pool.as_ref(),
+            payer.as_ref(),
+            &amount.to_le_bytes(),
+            &other_amount_threshold.to_le_bytes(),
+            &sqrt_price_limit.to_le_bytes(),
+            &[amount_specified_is_input as u8, a_to_b as u8],
+            salt,
+        ])
+        .to_bytes()
+    }
+
+    /// Verifies that the commitment can be revealed with the given hash at the given slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reveal happens in the commit slot, after the maximum age,
+    /// or if the hash does not match the commitment.
+    pub fn verify_reveal(&self, commitment_hash: &[u8; 32], slot: u64) -> Result<()> {
+        if slot <= self.commit_slot {
+            return Err(ErrorCode::SwapCommitmentNotMatured.into());
+        }
+        if slot > self.commit_slot.saturating_add(MAX_SWAP_COMMITMENT_AGE_SLOTS) {
+            return Err(ErrorCode::SwapCommitmentExpired.into());
+        }
+        if *commitment_hash != self.commitment_hash {
+            return Err(ErrorCode::SwapCommitmentMismatch.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod swap_commitment_tests {
+    use super::*;
+
+    fn hash_with_amount(amount: u64) -> [u8; 32] {
+        SwapCommitment::compute_hash(
+            &Pubkey::default(),
+            &Pubkey::default(),
+            amount,
+            0,
+            0,
+            true,
+            true,
+            &[7u8; 32],
+        )
+    }
+
+    fn new_commitment() -> SwapCommitment {
+        SwapCommitment {
+            ai_dex_pool: Pubkey::default(),
+            payer: Pubkey::default(),
+            commitment_hash: hash_with_amount(100),
+            commit_slot: 1_000,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_verify_reveal_ok() {
+        let commitment = new_commitment();
+        assert!(commitment.verify_reveal(&hash_with_amount(100), 1_001).is_ok());
+        assert!(commitment
+            .verify_reveal(&hash_with_amount(100), 1_000 + MAX_SWAP_COMMITMENT_AGE_SLOTS)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_verify_reveal_same_slot() {
+        let commitment = new_commitment();
+        assert_eq!(
+            commitment.verify_reveal(&hash_with_amount(100), 1_000).unwrap_err(),
+            ErrorCode::SwapCommitmentNotMatured.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_reveal_expired() {
+        let commitment = new_commitment();
+        assert_eq!(
+            commitment
+                .verify_reveal(&hash_with_amount(100), 1_001 + MAX_SWAP_COMMITMENT_AGE_SLOTS)
+                .unwrap_err(),
+            ErrorCode::SwapCommitmentExpired.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_reveal_mismatch() {
+        let commitment = new_commitment();
+        assert_eq!(
+            commitment.verify_reveal(&hash_with_amount(101), 1_001).unwrap_err(),
+            ErrorCode::SwapCommitmentMismatch.into()
+        );
+    }
+}
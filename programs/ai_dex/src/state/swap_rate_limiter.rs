@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Number of buckets of the rolling window of a swap rate limiter.
+pub const SWAP_RATE_LIMIT_BUCKETS: usize = 6;
+
+/// Limit set by the config authority on the volume of a token swapped through the pools the
+/// limiter is attached to, over a rolling window.
+///
+/// A limiter attached to a single pool limits that pool. Attached to every pool of a token, it
+/// limits the volume of the token across the config, for example while the token is being
+/// exploited elsewhere and toxic flow drains the LPs.
+///
+/// The window is split into `SWAP_RATE_LIMIT_BUCKETS` buckets of `window_duration /
+/// SWAP_RATE_LIMIT_BUCKETS` seconds, kept in a ring buffer indexed by `bucket_index`.
+#[account]
+#[derive(Default)]
+pub struct SwapRateLimiter {
+    pub ai_dex_config: Pubkey, // 32
+    pub token_mint: Pubkey, // 32
+    pub max_volume_per_window: u64, // 8
+    pub window_duration: u64, // 8
+    pub bucket_index: u64, // 8
+    pub bucket_volumes: [u64; SWAP_RATE_LIMIT_BUCKETS], // 48
+    pub bump: u8, // 1
+}
+
+impl SwapRateLimiter {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 32 // token_mint
+    + 8 // max_volume_per_window
+    + 8 // window_duration
+    + 8 // bucket_index
+    + 48 // bucket_volumes
+    + 1; // bump
+
+    pub fn initialize(
+        &mut self,
+        ai_dex_config: Pubkey,
+        token_mint: Pubkey,
+        max_volume_per_window: u64,
+        window_duration: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.ai_dex_config = ai_dex_config;
+        self.token_mint = token_mint;
+        self.bump = bump;
+        self.update_rate_limit(max_volume_per_window, window_duration)
+    }
+
+    /// Updates the rate limit, clearing the volume recorded in the current window.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_volume_per_window` - The maximum amount of the token swapped per window.
+    /// * `window_duration` - The duration of the rolling window in seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the window is shorter than `SWAP_RATE_LIMIT_BUCKETS` seconds.
+    pub fn update_rate_limit(&mut self, max_volume_per_window: u64, window_duration: u64) -> Result<()> {
+        if window_duration < SWAP_RATE_LIMIT_BUCKETS as u64 {
+            return Err(ErrorCode::InvalidSwapRateLimit.into());
+        }
+        self.max_volume_per_window = max_volume_per_window;
+        self.window_duration = window_duration;
+        self.bucket_index = 0;
+        self.bucket_volumes = [0; SWAP_RATE_LIMIT_BUCKETS];
+        Ok(())
+    }
+
+    /// Returns the volume swapped over the rolling window ending at `timestamp`.
+    pub fn window_volume(&self, timestamp: u64) -> u64 {
+        let bucket_index = self.bucket_index_at(timestamp);
+        let elapsed = bucket_index.saturating_sub(self.bucket_index);
+        (0..SWAP_RATE_LIMIT_BUCKETS as u64)
+            .filter(|age| *age + elapsed < SWAP_RATE_LIMIT_BUCKETS as u64)
+            .map(|age| self.bucket_volumes[(self.bucket_index.wrapping_sub(age) % SWAP_RATE_LIMIT_BUCKETS as u64) as usize])
+            .fold(0u64, |window_volume, volume| window_volume.saturating_add(volume))
+    }
+
+    /// Records a swap of `volume` tokens, clearing the buckets that left the window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the volume of the window would exceed `max_volume_per_window`.
+    pub fn record_volume(&mut self, volume: u64, timestamp: u64) -> Result<()> {
+        if self.window_volume(timestamp).saturating_add(volume) > self.max_volume_per_window {
+            return Err(ErrorCode::SwapRateLimitExceeded.into());
+        }
+
+        let bucket_index = self.bucket_index_at(timestamp);
+        let elapsed = bucket_index.saturating_sub(self.bucket_index);
+        for age in 0..elapsed.min(SWAP_RATE_LIMIT_BUCKETS as u64) {
+            self.bucket_volumes[((bucket_index - age) % SWAP_RATE_LIMIT_BUCKETS as u64) as usize] = 0;
+        }
+        self.bucket_index = self.bucket_index.max(bucket_index);
+
+        let bucket = &mut self.bucket_volumes[(self.bucket_index % SWAP_RATE_LIMIT_BUCKETS as u64) as usize];
+        *bucket = bucket.saturating_add(volume);
+        Ok(())
+    }
+
+    fn bucket_index_at(&self, timestamp: u64) -> u64 {
+        timestamp / (self.window_duration / SWAP_RATE_LIMIT_BUCKETS as u64)
+    }
+}
+
+#[cfg(test)]
+mod swap_rate_limiter_tests {
+    use super::*;
+
+    fn new_limiter(max_volume_per_window: u64, window_duration: u64) -> SwapRateLimiter {
+        let mut limiter = SwapRateLimiter::default();
+        limiter
+            .initialize(Pubkey::default(), Pubkey::default(), max_volume_per_window, window_duration, 255)
+            .unwrap();
+        limiter
+    }
+
+    #[test]
+    fn test_record_volume_rate_limited() {
+        // Buckets of 10 seconds
+        let mut limiter = new_limiter(1_000, 60);
+        limiter.record_volume(600, 1_000).unwrap();
+        limiter.record_volume(300, 1_025).unwrap();
+        assert_eq!(limiter.window_volume(1_025), 900);
+        assert_eq!(
+            limiter.record_volume(101, 1_059).unwrap_err(),
+            ErrorCode::SwapRateLimitExceeded.into()
+        );
+
+        // The first bucket leaves the window after 60 seconds, the second one stays
+        assert_eq!(limiter.window_volume(1_060), 300);
+        limiter.record_volume(700, 1_060).unwrap();
+        assert_eq!(
+            limiter.record_volume(1, 1_079).unwrap_err(),
+            ErrorCode::SwapRateLimitExceeded.into()
+        );
+        assert_eq!(limiter.window_volume(1_080), 700);
+    }
+
+    #[test]
+    fn test_record_volume_after_idle_window() {
+        let mut limiter = new_limiter(1_000, 60);
+        limiter.record_volume(1_000, 1_000).unwrap();
+        assert_eq!(limiter.window_volume(5_000), 0);
+        limiter.record_volume(1_000, 5_000).unwrap();
+        assert_eq!(limiter.bucket_volumes.iter().sum::<u64>(), 1_000);
+    }
+
+    #[test]
+    fn test_update_rate_limit_invalid_window() {
+        let mut limiter = new_limiter(1_000, 60);
+        assert_eq!(
+            limiter.update_rate_limit(1_000, SWAP_RATE_LIMIT_BUCKETS as u64 - 1).unwrap_err(),
+            ErrorCode::InvalidSwapRateLimit.into()
+        );
+    }
+}
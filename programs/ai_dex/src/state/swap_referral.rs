@@ -8,7 +8,8 @@ pub struct SwapReferral {
     pub referrer_address: Pubkey, // 32 bytes
     pub referral_reward_fee_rate: u16, // 2 bytes
     pub referral_code: String, // 11 bytes
-    pub referral_bump: [u8; 1] // 1 byte
+    pub referral_bump: [u8; 1], // 1 byte
+    pub original_referrer_address: Option<Pubkey>, // 33 bytes
 }
 
 impl SwapReferral {
@@ -18,13 +19,17 @@ impl SwapReferral {
     + 32 // referred_user_address
     + 2  // referral_reward_fee_rate
     + 11 // referral_code
-    + 1; // referral_bump
+    + 1 // referral_bump
+    + 33; // original_referrer_address
 
     /// Returns an array of references to the seeds used for program address generation.
+    ///
+    /// The address is derived from the referrer that initialized the referral, which differs from
+    /// `referrer_address` once ownership has been transferred.
     pub fn seeds(&self) -> [&[u8]; 4] {
         [
             &b"swap-referral"[..],
-            self.referrer_address.as_ref(),
+            self.original_referrer_address.as_ref().unwrap_or(&self.referrer_address).as_ref(),
             self.referral_code.as_ref(),
             self.referral_bump.as_ref(),
         ]
@@ -40,9 +45,23 @@ impl SwapReferral {
         self.referrer_address = referrer_address;
         self.referral_reward_fee_rate = 0;
         self.referral_code = referral_code.to_string();
+        self.original_referrer_address = None;
         Ok(())
     }
 
+    /// Transfers the ownership of the referral to a new referrer, who can then collect its fees
+    /// and close it. The referral keeps its address, token accounts and accrued fees.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_referrer_address` - The new referrer of the referral.
+    pub fn transfer_ownership(&mut self, new_referrer_address: Pubkey) {
+        if self.original_referrer_address.is_none() {
+            self.original_referrer_address = Some(self.referrer_address);
+        }
+        self.referrer_address = new_referrer_address;
+    }
+
     pub fn update_swap_reward_fee_rate(
         &mut self,
         swap_referral_reward_fee_rate: u16,
@@ -57,4 +76,56 @@ impl SwapReferral {
         Ok(())
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod swap_referral_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn test_transfer_ownership_keeps_seeds() {
+        let referrer_address = Pubkey::new_unique();
+        let referral_code = "code".to_string();
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"swap-referral", referrer_address.as_ref(), referral_code.as_ref()],
+            &crate::ID,
+        );
+        let mut swap_referral = SwapReferral {
+            referrer_address: Pubkey::default(),
+            referral_reward_fee_rate: 0,
+            referral_code: String::new(),
+            referral_bump: [0],
+            original_referrer_address: None,
+        };
+        swap_referral.initialize_swap_referral(bump, referrer_address, &referral_code).unwrap();
+
+        let first_referrer_address = Pubkey::new_unique();
+        swap_referral.transfer_ownership(first_referrer_address);
+        let second_referrer_address = Pubkey::new_unique();
+        swap_referral.transfer_ownership(second_referrer_address);
+
+        assert_eq!(swap_referral.referrer_address, second_referrer_address);
+        assert_eq!(swap_referral.original_referrer_address, Some(referrer_address));
+        assert_eq!(
+            Pubkey::create_program_address(&swap_referral.seeds(), &crate::ID).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_legacy_layout_deserializes() {
+        // Referrals initialized before ownership transfers have no trailing option, only zeroed space
+        let mut data = SwapReferral::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"code");
+        data.push(255);
+        data.resize(86, 0);
+
+        let swap_referral = SwapReferral::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(swap_referral.referral_code, "code");
+        assert_eq!(swap_referral.original_referrer_address, None);
+    }
+}
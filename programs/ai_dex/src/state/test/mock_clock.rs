@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::util::to_timestamp_u64;
+
+/// Clock override used by tests to exercise time-dependent logic, such as the windows of temporary
+/// pools, without warping the validator clock.
+///
+/// The program cannot hold global state, so the override is an account: when the program is built
+/// with the `mock-clock` feature, a `MockClock` passed as the last remaining account of an
+/// instruction replaces the cluster clock.
+#[account]
+pub struct MockClock {
+    pub unix_timestamp: i64, // 8
+    pub bump: u8, // 1
+}
+
+impl MockClock {
+    pub const LEN: usize = 8 // discriminator
+    + 8 // unix_timestamp
+    + 1; // bump
+
+    pub fn initialize(&mut self, unix_timestamp: i64, bump: u8) -> Result<()> {
+        self.bump = bump;
+        self.update_unix_timestamp(unix_timestamp)
+    }
+
+    pub fn update_unix_timestamp(&mut self, unix_timestamp: i64) -> Result<()> {
+        // Reject timestamps the instructions could not convert anyway
+        to_timestamp_u64(unix_timestamp)?;
+        self.unix_timestamp = unix_timestamp;
+        Ok(())
+    }
+
+    /// Reads the overridden timestamp from the last of the remaining accounts, if it is a
+    /// `MockClock` owned by the program.
+    pub fn read_override(remaining_accounts: &[AccountInfo]) -> Result<Option<i64>> {
+        let Some(account_info) = remaining_accounts.last() else {
+            return Ok(None);
+        };
+        if account_info.owner != &crate::ID {
+            return Ok(None);
+        }
+        let data = account_info.try_borrow_data()?;
+        if data.len() < MockClock::LEN || data[..8] != MockClock::DISCRIMINATOR {
+            return Ok(None);
+        }
+        let mock_clock = MockClock::try_deserialize(&mut &data[..])?;
+        Ok(Some(mock_clock.unix_timestamp))
+    }
+}
+
+#[cfg(test)]
+mod mock_clock_tests {
+    use super::*;
+
+    fn mock_clock_data(unix_timestamp: i64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(MockClock::LEN);
+        MockClock { unix_timestamp, bump: 255 }.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_read_override() {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = mock_clock_data(1_700_000_000);
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert_eq!(MockClock::read_override(&[account_info]).unwrap(), Some(1_700_000_000));
+        assert_eq!(MockClock::read_override(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_override_ignores_other_accounts() {
+        let key = Pubkey::new_unique();
+        let other_owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = mock_clock_data(1_700_000_000);
+        let foreign = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &other_owner, false, 0);
+        assert_eq!(MockClock::read_override(&[foreign]).unwrap(), None);
+
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = vec![0u8; MockClock::LEN];
+        let not_a_clock = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(MockClock::read_override(&[not_a_clock]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_update_unix_timestamp_negative() {
+        let mut mock_clock = MockClock { unix_timestamp: 0, bump: 255 };
+        assert!(mock_clock.update_unix_timestamp(-1).is_err());
+        mock_clock.update_unix_timestamp(42).unwrap();
+        assert_eq!(mock_clock.unix_timestamp, 42);
+    }
+}
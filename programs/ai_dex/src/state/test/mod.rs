@@ -1,2 +1,5 @@
 pub mod mocked_price_update_state;
-pub use mocked_price_update_state::*;
\ No newline at end of file
+pub use mocked_price_update_state::*;
+
+pub mod mock_clock;
+pub use mock_clock::*;
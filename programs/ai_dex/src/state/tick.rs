@@ -1,6 +1,8 @@
 use crate::errors::ErrorCode;
 use crate::state::NUM_REWARDS;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cell::{Ref, RefMut};
 
 use super::AiDexPool;
 
@@ -161,58 +163,6 @@ impl Default for TickArray {
 impl TickArray {
     pub const LEN: usize = 8 + 36 + (Tick::LEN * TICK_ARRAY_SIZE_USIZE);
 
-    /// Search for the next initialized tick in this array.
-    ///
-    /// # Parameters
-    /// - `tick_index` - A i32 integer representing the tick index to start searching for
-    /// - `tick_spacing` - A u8 integer of the tick spacing for this ai_dex
-    /// - `a_to_b` - If the trade is from a_to_b, the search will move to the left and the starting search tick is inclusive.
-    ///              If the trade is from b_to_a, the search will move to the right and the starting search tick is not inclusive.
-    ///
-    /// # Returns
-    /// - `Some(i32)`: The next initialized tick index of this array
-    /// - `None`: An initialized tick index was not found in this array
-    /// - `InvalidTickArraySequenceError` - error if `tick_index` is not a valid search tick for the array
-    /// - `UnsupportedTickSpacing` - error if the provided tick spacing is 0
-    pub fn get_next_init_tick_index(
-        &self,
-        tick_index: i32,
-        tick_spacing: u16,
-        a_to_b: bool,
-    ) -> Result<Option<i32>> {
-        if !self.in_search_range(tick_index, tick_spacing, !a_to_b) {
-            return Err(ErrorCode::InvalidTickArraySequenceError.into());
-        }
-
-        let mut curr_offset = match self.tick_offset(tick_index, tick_spacing) {
-            Ok(value) => value as i32,
-            Err(e) => return Err(e),
-        };
-
-        // For a_to_b searches, the search moves to the left. The next possible init-tick can be the 1st tick in the current offset
-        // For b_to_a searches, the search moves to the right. The next possible init-tick cannot be within the current offset
-        if !a_to_b {
-            curr_offset += 1;
-        }
-
-        while curr_offset >= 0 && curr_offset < TICK_ARRAY_SIZE {
-            let curr_tick = self.ticks[curr_offset as usize];
-            if curr_tick.initialized {
-                return Ok(Some(
-                    (curr_offset * tick_spacing as i32) + self.start_tick_index,
-                ));
-            }
-
-            curr_offset = if a_to_b {
-                curr_offset - 1
-            } else {
-                curr_offset + 1
-            };
-        }
-
-        Ok(None)
-    }
-
     /// Initialize the TickArray object
     ///
     /// # Parameters
@@ -237,6 +187,20 @@ impl TickArray {
         self.start_tick_index = start_tick_index;
         Ok(())
     }
+}
+
+impl TickArrayType for TickArray {
+    fn start_tick_index(&self) -> i32 {
+        self.start_tick_index
+    }
+
+    fn ai_dex_pool(&self) -> Pubkey {
+        self.ai_dex_pool
+    }
+
+    fn is_initialized_at_offset(&self, offset: usize) -> bool {
+        self.ticks[offset].initialized
+    }
 
     /// Get the Tick object at the given tick-index & tick-spacing
     ///
@@ -247,17 +211,9 @@ impl TickArray {
     /// # Returns
     /// - `&Tick`: A reference to the desired Tick object
     /// - `TickNotFoundError`: - The provided tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
-    pub fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
-        if !self.check_in_array_bounds(tick_index, tick_spacing)
-            || !Tick::check_is_usable_tick(tick_index, tick_spacing)
-        {
-            return Err(ErrorCode::TickNotFoundError.into());
-        }
-        let offset = self.tick_offset(tick_index, tick_spacing)?;
-        if offset < 0 {
-            return Err(ErrorCode::TickNotFoundError.into());
-        }
-        Ok(&self.ticks[offset as usize])
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        let offset = self.checked_tick_offset(tick_index, tick_spacing)?;
+        Ok(&self.ticks[offset])
     }
 
     /// Updates the Tick object at the given tick-index & tick-spacing
@@ -269,25 +225,265 @@ impl TickArray {
     ///
     /// # Errors
     /// - `TickNotFoundError`: - The provided tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
-    pub fn update_tick(
+    fn update_tick(
         &mut self,
         tick_index: i32,
         tick_spacing: u16,
         update: &TickUpdate,
     ) -> Result<()> {
-        if !self.check_in_array_bounds(tick_index, tick_spacing)
-            || !Tick::check_is_usable_tick(tick_index, tick_spacing)
-        {
-            return Err(ErrorCode::TickNotFoundError.into());
+        let offset = self.checked_tick_offset(tick_index, tick_spacing)?;
+        self.ticks.get_mut(offset).unwrap().update(update);
+        Ok(())
+    }
+}
+
+// Number of initialized ticks a TickArrayV2 can hold
+pub const TICK_ARRAY_V2_CAPACITY: usize = 8;
+
+// Uninitialized ticks of a TickArrayV2 are read as this value
+static UNINITIALIZED_TICK: Tick = Tick {
+    initialized: false,
+    liquidity_net: 0,
+    liquidity_gross: 0,
+    fee_growth_outside_a: 0,
+    fee_growth_outside_b: 0,
+    reward_growths_outside: [0; NUM_REWARDS],
+};
+
+/// Compact tick array covering the same range and address as a `TickArray`, but only storing
+/// up to `TICK_ARRAY_V2_CAPACITY` initialized ticks.
+///
+/// Wide tick spacing and full-range pools rarely initialize more than a couple of ticks per
+/// array, so this cuts the rent of an array by roughly 90%. An array that runs out of slots
+/// can be expanded back to a `TickArray` in place.
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+pub struct TickArrayV2 {
+    pub start_tick_index: i32,
+    pub ai_dex_pool: Pubkey,
+    /// Offset + 1 of the tick stored in each slot, 0 for an empty slot.
+    pub tick_slots: [u8; TICK_ARRAY_V2_CAPACITY],
+    pub ticks: [Tick; TICK_ARRAY_V2_CAPACITY],
+}
+
+impl Default for TickArrayV2 {
+    #[inline]
+    fn default() -> TickArrayV2 {
+        TickArrayV2 {
+            start_tick_index: 0,
+            ai_dex_pool: Pubkey::default(),
+            tick_slots: [0; TICK_ARRAY_V2_CAPACITY],
+            ticks: [Tick::default(); TICK_ARRAY_V2_CAPACITY],
         }
-        let offset = self.tick_offset(tick_index, tick_spacing)?;
-        if offset < 0 {
-            return Err(ErrorCode::TickNotFoundError.into());
+    }
+}
+
+impl TickArrayV2 {
+    pub const LEN: usize = 8 + 36 + TICK_ARRAY_V2_CAPACITY + (Tick::LEN * TICK_ARRAY_V2_CAPACITY);
+
+    /// Initialize the TickArrayV2 object
+    ///
+    /// # Parameters
+    /// - `ai_dex_pool` - The pool this tick array belongs to
+    /// - `tick_spacing` - The tick spacing of the pool
+    /// - `start_tick_index` - The first tick index covered by this tick array
+    ///
+    /// # Errors
+    /// - `InvalidStartTickIndex`: - The provided start-tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
+    pub fn initialize(
+        &mut self,
+        ai_dex_pool: Pubkey,
+        tick_spacing: u16,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        if !Tick::check_is_valid_start_tick(start_tick_index, tick_spacing) {
+            msg!("Tick index: {}", start_tick_index);
+            return Err(ErrorCode::InvalidStartTickIndex.into());
         }
-        self.ticks.get_mut(offset as usize).unwrap().update(update);
+
+        self.ai_dex_pool = ai_dex_pool;
+        self.start_tick_index = start_tick_index;
         Ok(())
     }
 
+    /// Builds a compact copy of a TickArray.
+    ///
+    /// # Errors
+    /// - `TickArrayV2Full`: - The TickArray has more initialized ticks than a TickArrayV2 can hold.
+    pub fn try_from_tick_array(tick_array: &TickArray) -> Result<TickArrayV2> {
+        let mut tick_array_v2 = TickArrayV2 {
+            start_tick_index: tick_array.start_tick_index,
+            ai_dex_pool: tick_array.ai_dex_pool,
+            ..Default::default()
+        };
+
+        let mut slot = 0;
+        for (offset, tick) in tick_array.ticks.iter().enumerate() {
+            if !tick.initialized {
+                continue;
+            }
+            if slot == TICK_ARRAY_V2_CAPACITY {
+                return Err(ErrorCode::TickArrayV2Full.into());
+            }
+            tick_array_v2.tick_slots[slot] = offset as u8 + 1;
+            tick_array_v2.ticks[slot] = *tick;
+            slot += 1;
+        }
+
+        Ok(tick_array_v2)
+    }
+
+    /// Builds a full TickArray holding the same ticks.
+    pub fn to_tick_array(&self) -> TickArray {
+        let mut tick_array = TickArray {
+            start_tick_index: self.start_tick_index,
+            ai_dex_pool: self.ai_dex_pool,
+            ..Default::default()
+        };
+        for (slot, tick_slot) in self.tick_slots.iter().enumerate() {
+            if *tick_slot != 0 {
+                tick_array.ticks[*tick_slot as usize - 1] = self.ticks[slot];
+            }
+        }
+        tick_array
+    }
+
+    fn find_slot(&self, offset: usize) -> Option<usize> {
+        self.tick_slots.iter().position(|tick_slot| *tick_slot as usize == offset + 1)
+    }
+}
+
+impl TickArrayType for TickArrayV2 {
+    fn start_tick_index(&self) -> i32 {
+        self.start_tick_index
+    }
+
+    fn ai_dex_pool(&self) -> Pubkey {
+        self.ai_dex_pool
+    }
+
+    fn is_initialized_at_offset(&self, offset: usize) -> bool {
+        self.find_slot(offset).is_some()
+    }
+
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        let offset = self.checked_tick_offset(tick_index, tick_spacing)?;
+        Ok(self
+            .find_slot(offset)
+            .map_or(&UNINITIALIZED_TICK, |slot| &self.ticks[slot]))
+    }
+
+    /// Updates the Tick object at the given tick-index & tick-spacing.
+    ///
+    /// Initializing a tick takes an empty slot and uninitializing a tick frees its slot.
+    ///
+    /// # Errors
+    /// - `TickNotFoundError`: - The provided tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
+    /// - `TickArrayV2Full`: - All slots are taken and the tick array must be upgraded to a TickArray.
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()> {
+        let offset = self.checked_tick_offset(tick_index, tick_spacing)?;
+        let slot = self.find_slot(offset);
+
+        if !update.initialized {
+            // An uninitialized tick carries no state, free its slot
+            if let Some(slot) = slot {
+                self.tick_slots[slot] = 0;
+                self.ticks[slot] = Tick::default();
+            }
+            return Ok(());
+        }
+
+        let slot = match slot {
+            Some(slot) => slot,
+            None => {
+                let slot = self
+                    .tick_slots
+                    .iter()
+                    .position(|tick_slot| *tick_slot == 0)
+                    .ok_or(ErrorCode::TickArrayV2Full)?;
+                self.tick_slots[slot] = offset as u8 + 1;
+                slot
+            }
+        };
+        self.ticks[slot].update(update);
+        Ok(())
+    }
+}
+
+/// Common interface of the `TickArray` and `TickArrayV2` layouts.
+pub trait TickArrayType {
+    fn start_tick_index(&self) -> i32;
+
+    fn ai_dex_pool(&self) -> Pubkey;
+
+    /// Returns true if the tick at the given offset is initialized.
+    fn is_initialized_at_offset(&self, offset: usize) -> bool;
+
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick>;
+
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()>;
+
+    /// Search for the next initialized tick in this array.
+    ///
+    /// # Parameters
+    /// - `tick_index` - A i32 integer representing the tick index to start searching for
+    /// - `tick_spacing` - A u8 integer of the tick spacing for this ai_dex
+    /// - `a_to_b` - If the trade is from a_to_b, the search will move to the left and the starting search tick is inclusive.
+    ///              If the trade is from b_to_a, the search will move to the right and the starting search tick is not inclusive.
+    ///
+    /// # Returns
+    /// - `Some(i32)`: The next initialized tick index of this array
+    /// - `None`: An initialized tick index was not found in this array
+    /// - `InvalidTickArraySequenceError` - error if `tick_index` is not a valid search tick for the array
+    /// - `UnsupportedTickSpacing` - error if the provided tick spacing is 0
+    fn get_next_init_tick_index(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        if !self.in_search_range(tick_index, tick_spacing, !a_to_b) {
+            return Err(ErrorCode::InvalidTickArraySequenceError.into());
+        }
+
+        let mut curr_offset = match self.tick_offset(tick_index, tick_spacing) {
+            Ok(value) => value as i32,
+            Err(e) => return Err(e),
+        };
+
+        // For a_to_b searches, the search moves to the left. The next possible init-tick can be the 1st tick in the current offset
+        // For b_to_a searches, the search moves to the right. The next possible init-tick cannot be within the current offset
+        if !a_to_b {
+            curr_offset += 1;
+        }
+
+        while curr_offset >= 0 && curr_offset < TICK_ARRAY_SIZE {
+            if self.is_initialized_at_offset(curr_offset as usize) {
+                return Ok(Some(
+                    (curr_offset * tick_spacing as i32) + self.start_tick_index(),
+                ));
+            }
+
+            curr_offset = if a_to_b {
+                curr_offset - 1
+            } else {
+                curr_offset + 1
+            };
+        }
+
+        Ok(None)
+    }
+
     /// Checks that this array holds the next tick index for the current tick index, given the pool's tick spacing & search direction.
     ///
     /// unshifted checks on [start, start + TICK_ARRAY_SIZE * tick_spacing)
@@ -300,9 +496,9 @@ impl TickArray {
     ///
     /// For b_to_a swaps, this tick-array's left-most ticks can be the 'next' usable tick-index of the previous tick-array.
     /// The right-most ticks also points towards the next tick-array. The search range is therefore shifted by 1 tick-spacing.
-    pub fn in_search_range(&self, tick_index: i32, tick_spacing: u16, shifted: bool) -> bool {
-        let mut lower = self.start_tick_index;
-        let mut upper = self.start_tick_index + TICK_ARRAY_SIZE * tick_spacing as i32;
+    fn in_search_range(&self, tick_index: i32, tick_spacing: u16, shifted: bool) -> bool {
+        let mut lower = self.start_tick_index();
+        let mut upper = self.start_tick_index() + TICK_ARRAY_SIZE * tick_spacing as i32;
         if shifted {
             lower = lower - tick_spacing as i32;
             upper = upper - tick_spacing as i32;
@@ -310,26 +506,92 @@ impl TickArray {
         tick_index >= lower && tick_index < upper
     }
 
-    pub fn check_in_array_bounds(&self, tick_index: i32, tick_spacing: u16) -> bool {
+    fn check_in_array_bounds(&self, tick_index: i32, tick_spacing: u16) -> bool {
         self.in_search_range(tick_index, tick_spacing, false)
     }
 
-    pub fn is_min_tick_array(&self) -> bool {
-        self.start_tick_index <= MIN_TICK_INDEX
+    fn is_min_tick_array(&self) -> bool {
+        self.start_tick_index() <= MIN_TICK_INDEX
     }
 
-    pub fn is_max_tick_array(&self, tick_spacing: u16) -> bool {
-        self.start_tick_index + TICK_ARRAY_SIZE * (tick_spacing as i32) > MAX_TICK_INDEX
+    fn is_max_tick_array(&self, tick_spacing: u16) -> bool {
+        self.start_tick_index() + TICK_ARRAY_SIZE * (tick_spacing as i32) > MAX_TICK_INDEX
     }
 
     // Calculates an offset from a tick index that can be used to access the tick data
-    pub fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<isize> {
+    fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<isize> {
         if tick_spacing == 0 {
             return Err(ErrorCode::UnsupportedTickSpacing.into());
         }
 
-        Ok(get_offset(tick_index, self.start_tick_index, tick_spacing))
+        Ok(get_offset(tick_index, self.start_tick_index(), tick_spacing))
+    }
+
+    /// Returns the offset of a usable tick index within the bounds of this array.
+    ///
+    /// # Errors
+    /// - `TickNotFoundError`: - The provided tick-index is not an initializable tick index in this AiDex w/ this tick-spacing.
+    fn checked_tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        if !self.check_in_array_bounds(tick_index, tick_spacing)
+            || !Tick::check_is_usable_tick(tick_index, tick_spacing)
+        {
+            return Err(ErrorCode::TickNotFoundError.into());
+        }
+        let offset = self.tick_offset(tick_index, tick_spacing)?;
+        if offset < 0 {
+            return Err(ErrorCode::TickNotFoundError.into());
+        }
+        Ok(offset as usize)
+    }
+}
+
+/// Loads a `TickArray` or `TickArrayV2` account for reading.
+///
+/// # Errors
+/// - `AccountOwnedByWrongProgram`: - The account is not owned by this program.
+/// - `AccountDiscriminatorMismatch`: - The account is neither a TickArray nor a TickArrayV2.
+pub fn load_tick_array<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, dyn TickArrayType>> {
+    let data = borrow_tick_array_data(account_info)?;
+    let tick_array: Ref<'a, dyn TickArrayType> = if data[..8] == TickArray::DISCRIMINATOR {
+        Ref::map(data, |data| bytemuck::from_bytes::<TickArray>(&data[8..TickArray::LEN]))
+    } else {
+        Ref::map(data, |data| bytemuck::from_bytes::<TickArrayV2>(&data[8..TickArrayV2::LEN]))
+    };
+    Ok(tick_array)
+}
+
+/// Loads a `TickArray` or `TickArrayV2` account for writing.
+///
+/// # Errors
+/// - `AccountNotMutable`: - The account is not writable.
+/// - `AccountOwnedByWrongProgram`: - The account is not owned by this program.
+/// - `AccountDiscriminatorMismatch`: - The account is neither a TickArray nor a TickArrayV2.
+pub fn load_tick_array_mut<'a>(account_info: &'a AccountInfo) -> Result<RefMut<'a, dyn TickArrayType>> {
+    if !account_info.is_writable {
+        return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+    }
+    let is_tick_array_v1 = borrow_tick_array_data(account_info)?[..8] == TickArray::DISCRIMINATOR;
+
+    let data = account_info.try_borrow_mut_data()?;
+    let tick_array: RefMut<'a, dyn TickArrayType> = if is_tick_array_v1 {
+        RefMut::map(data, |data| bytemuck::from_bytes_mut::<TickArray>(&mut data[8..TickArray::LEN]))
+    } else {
+        RefMut::map(data, |data| bytemuck::from_bytes_mut::<TickArrayV2>(&mut data[8..TickArrayV2::LEN]))
+    };
+    Ok(tick_array)
+}
+
+fn borrow_tick_array_data<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, &'a mut [u8]>> {
+    if account_info.owner != &crate::ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let data = account_info.try_borrow_data()?;
+    let is_tick_array = data.len() >= TickArray::LEN && data[..8] == TickArray::DISCRIMINATOR;
+    let is_tick_array_v2 = data.len() >= TickArrayV2::LEN && data[..8] == TickArrayV2::DISCRIMINATOR;
+    if !is_tick_array && !is_tick_array_v2 {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
     }
+    Ok(data)
 }
 
 fn get_offset(tick_index: i32, start_tick_index: i32, tick_spacing: u16) -> isize {
@@ -665,3 +927,76 @@ mod array_update_tests {
         assert_eq!(*result, expected);
     }
 }
+
+#[cfg(test)]
+mod tick_array_v2_tests {
+    use super::*;
+
+    fn initialized_update(liquidity: u128) -> TickUpdate {
+        TickUpdate {
+            initialized: true,
+            liquidity_net: liquidity as i128,
+            liquidity_gross: liquidity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn uninitialized_tick_reads_as_default() {
+        let array = TickArrayV2::default();
+        let tick = array.get_tick(16, 8).unwrap();
+        assert_eq!(*tick, Tick::default());
+        assert_eq!(array.get_next_init_tick_index(0, 8, false).unwrap(), None);
+    }
+
+    #[test]
+    fn update_takes_and_frees_slots() {
+        let mut array = TickArrayV2::default();
+        array.update_tick(16, 8, &initialized_update(100)).unwrap();
+        assert_eq!({ array.tick_slots }[0], 3);
+        assert_eq!({ array.get_tick(16, 8).unwrap().liquidity_gross }, 100);
+        assert_eq!(array.get_next_init_tick_index(0, 8, false).unwrap(), Some(16));
+
+        array.update_tick(16, 8, &TickUpdate::default()).unwrap();
+        assert_eq!({ array.tick_slots }[0], 0);
+        assert_eq!(*array.get_tick(16, 8).unwrap(), Tick::default());
+    }
+
+    #[test]
+    fn update_fails_when_full() {
+        let mut array = TickArrayV2::default();
+        for i in 0..TICK_ARRAY_V2_CAPACITY as i32 {
+            array.update_tick(i * 8, 8, &initialized_update(1)).unwrap();
+        }
+        let result = array.update_tick(TICK_ARRAY_V2_CAPACITY as i32 * 8, 8, &initialized_update(1));
+        assert_eq!(result.unwrap_err(), ErrorCode::TickArrayV2Full.into());
+
+        // Updating a tick that already holds a slot still succeeds
+        array.update_tick(0, 8, &initialized_update(2)).unwrap();
+        assert_eq!({ array.get_tick(0, 8).unwrap().liquidity_gross }, 2);
+    }
+
+    #[test]
+    fn converts_to_and_from_tick_array() {
+        let mut tick_array = TickArray::default();
+        tick_array.update_tick(8, 8, &initialized_update(5)).unwrap();
+        tick_array.update_tick(696, 8, &initialized_update(7)).unwrap();
+
+        let tick_array_v2 = TickArrayV2::try_from_tick_array(&tick_array).unwrap();
+        assert_eq!({ tick_array_v2.get_tick(8, 8).unwrap().liquidity_gross }, 5);
+        assert_eq!({ tick_array_v2.get_tick(696, 8).unwrap().liquidity_gross }, 7);
+
+        let round_trip = tick_array_v2.to_tick_array();
+        assert_eq!({ round_trip.ticks }, { tick_array.ticks });
+    }
+
+    #[test]
+    fn conversion_fails_with_too_many_ticks() {
+        let mut tick_array = TickArray::default();
+        for i in 0..=TICK_ARRAY_V2_CAPACITY as i32 {
+            tick_array.update_tick(i * 8, 8, &initialized_update(1)).unwrap();
+        }
+        let result = TickArrayV2::try_from_tick_array(&tick_array);
+        assert_eq!(result.err(), Some(ErrorCode::TickArrayV2Full.into()));
+    }
+}
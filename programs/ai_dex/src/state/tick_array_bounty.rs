@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use super::TICK_ARRAY_SIZE;
+
+/// Lamport escrow of a pool paying whoever initializes the tick arrays next to the current price.
+///
+/// Swaps fail when a tick array they cross is missing. Instead of running a crank, the pool
+/// creator or the config authority sets a reward per tick array, anyone funds the escrow, and
+/// `initialize_tick_array` pays the reward to its funder when the new tick array contains or
+/// neighbours the tick array of the current price.
+#[account]
+#[derive(Default)]
+pub struct TickArrayBounty {
+    pub ai_dex_pool: Pubkey, // 32
+    /// Lamports paid for each tick array initialized next to the current price.
+    pub reward_per_tick_array: u64, // 8
+    pub bump: u8, // 1
+}
+
+impl TickArrayBounty {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_pool
+    + 8 // reward_per_tick_array
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_pool: Pubkey, reward_per_tick_array: u64, bump: u8) {
+        self.ai_dex_pool = ai_dex_pool;
+        self.reward_per_tick_array = reward_per_tick_array;
+        self.bump = bump;
+    }
+
+    pub fn update_reward_per_tick_array(&mut self, reward_per_tick_array: u64) {
+        self.reward_per_tick_array = reward_per_tick_array;
+    }
+
+    /// Returns whether the tick array starting at `start_tick_index` contains or neighbours the
+    /// tick array of the current tick of the pool.
+    pub fn is_eligible_tick_array(start_tick_index: i32, tick_current_index: i32, tick_spacing: u16) -> bool {
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        let current_start_tick_index = tick_current_index.div_euclid(ticks_in_array) * ticks_in_array;
+        (start_tick_index as i64 - current_start_tick_index as i64).abs() <= ticks_in_array as i64
+    }
+
+    /// Returns the reward paid for a tick array, capped by the lamports of the escrow above its
+    /// rent-exempt minimum.
+    ///
+    /// # Arguments
+    ///
+    /// * `lamports` - The lamports of the escrow account.
+    /// * `minimum_balance` - The rent-exempt minimum of the escrow account.
+    pub fn claimable_reward(&self, lamports: u64, minimum_balance: u64) -> u64 {
+        self.reward_per_tick_array
+            .min(lamports.saturating_sub(minimum_balance))
+    }
+}
+
+#[cfg(test)]
+mod tick_array_bounty_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_eligible_tick_array() {
+        let tick_spacing = 64;
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        for tick_current_index in [0, ticks_in_array - 1] {
+            assert!(TickArrayBounty::is_eligible_tick_array(0, tick_current_index, tick_spacing));
+            assert!(TickArrayBounty::is_eligible_tick_array(-ticks_in_array, tick_current_index, tick_spacing));
+            assert!(TickArrayBounty::is_eligible_tick_array(ticks_in_array, tick_current_index, tick_spacing));
+            assert!(!TickArrayBounty::is_eligible_tick_array(-2 * ticks_in_array, tick_current_index, tick_spacing));
+            assert!(!TickArrayBounty::is_eligible_tick_array(2 * ticks_in_array, tick_current_index, tick_spacing));
+        }
+        // The current tick array of a negative tick starts below it
+        assert!(TickArrayBounty::is_eligible_tick_array(-2 * ticks_in_array, -1, tick_spacing));
+        assert!(!TickArrayBounty::is_eligible_tick_array(ticks_in_array, -1, tick_spacing));
+    }
+
+    #[test]
+    fn test_claimable_reward() {
+        let mut bounty = TickArrayBounty::default();
+        bounty.initialize(Pubkey::new_unique(), 1_000, 255);
+        assert_eq!(bounty.claimable_reward(10_000, 2_000), 1_000);
+        assert_eq!(bounty.claimable_reward(2_500, 2_000), 500);
+        assert_eq!(bounty.claimable_reward(1_500, 2_000), 0);
+
+        bounty.update_reward_per_tick_array(0);
+        assert_eq!(bounty.claimable_reward(10_000, 2_000), 0);
+    }
+}
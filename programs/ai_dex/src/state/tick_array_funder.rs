@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Record of the funder of a tick array, created with the tick array when its funder opts in to
+/// reclaiming its rent.
+///
+/// `close_tick_array` refunds the rent of an empty tick array and of this record to the recorded
+/// funder only, so a tick array can never be closed to the benefit of anyone else.
+#[account]
+#[derive(Default)]
+pub struct TickArrayFunder {
+    pub tick_array: Pubkey, // 32
+    pub funder: Pubkey, // 32
+    pub bump: u8, // 1
+}
+
+impl TickArrayFunder {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // tick_array
+    + 32 // funder
+    + 1; // bump
+
+    pub fn initialize(&mut self, tick_array: Pubkey, funder: Pubkey, bump: u8) {
+        self.tick_array = tick_array;
+        self.funder = funder;
+        self.bump = bump;
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Approval by the config authority of a mint that can only be used by the pools of a config once
+/// vetted, such as a reward mint with a freeze authority or a transfer hook.
+#[account]
+#[derive(Default)]
+pub struct TokenBadge {
+    pub ai_dex_config: Pubkey, // 32
+    pub token_mint: Pubkey, // 32
+    pub bump: u8, // 1
+}
+
+impl TokenBadge {
+    pub const LEN: usize = 8 // discriminator
+    + 32 // ai_dex_config
+    + 32 // token_mint
+    + 1; // bump
+
+    pub fn initialize(&mut self, ai_dex_config: Pubkey, token_mint: Pubkey, bump: u8) {
+        self.ai_dex_config = ai_dex_config;
+        self.token_mint = token_mint;
+        self.bump = bump;
+    }
+}
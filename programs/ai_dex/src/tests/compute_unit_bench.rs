@@ -0,0 +1,116 @@
+//! Compute unit ceilings of the swap and liquidity paths, to catch compute unit regressions.
+//!
+//! The program test runs the program natively everywhere else, which does not meter compute
+//! units, so these tests load the SBF build and are ignored by default. Run them with
+//! `cargo test-sbf --test program_integration_tests -- --ignored compute_unit_bench`.
+//!
+//! The measured values are what the config authority registers with
+//! `set_instruction_compute_units` after benchmarking a release.
+
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::signer::Signer;
+
+use super::*;
+
+const SWAP_COMPUTE_UNIT_CEILING: u64 = 150_000;
+const SWAP_CROSSING_TICK_COMPUTE_UNIT_CEILING: u64 = 200_000;
+const INCREASE_LIQUIDITY_COMPUTE_UNIT_CEILING: u64 = 120_000;
+const DECREASE_LIQUIDITY_COMPUTE_UNIT_CEILING: u64 = 120_000;
+
+struct BenchFixture {
+    context: ProgramTestContext,
+    pool: PoolFixture,
+    position: PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+}
+
+/// A pool with a position around the current price inside a wider position, so a large enough
+/// swap crosses an initialized tick and keeps liquidity.
+async fn start_bench() -> BenchFixture {
+    let mut context = start_sbf_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    // Also initializes the tick arrays a swap from A to B crosses
+    let wide_position = open_position(&mut context, &pool, -11_264, 11_200).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+    increase_liquidity(&mut context, &pool, &wide_position, token_owner_account_a, token_owner_account_b, LIQUIDITY)
+        .await;
+
+    BenchFixture { context, pool, position, token_owner_account_a, token_owner_account_b }
+}
+
+async fn assert_swap_compute_units(amount: u64, ceiling: u64) {
+    let mut bench = start_bench().await;
+    let instruction = swap_exact_input_instruction(
+        &mut bench.context,
+        &bench.pool,
+        bench.token_owner_account_a,
+        bench.token_owner_account_b,
+        amount,
+        true,
+    )
+    .await;
+
+    let compute_units = simulate_compute_units(&mut bench.context, &[instruction], &[]).await;
+    assert!(compute_units <= ceiling, "swap of {amount} consumed {compute_units} compute units, over {ceiling}");
+}
+
+#[tokio::test]
+#[ignore = "requires the SBF build of the program"]
+async fn test_swap_compute_units() {
+    assert_swap_compute_units(1_000_000, SWAP_COMPUTE_UNIT_CEILING).await;
+}
+
+#[tokio::test]
+#[ignore = "requires the SBF build of the program"]
+async fn test_swap_crossing_tick_compute_units() {
+    // Exceeds the token A needed to move the price below the lower tick of the narrow position
+    assert_swap_compute_units(200_000_000, SWAP_CROSSING_TICK_COMPUTE_UNIT_CEILING).await;
+}
+
+#[tokio::test]
+#[ignore = "requires the SBF build of the program"]
+async fn test_increase_liquidity_compute_units() {
+    let mut bench = start_bench().await;
+    let instruction = increase_liquidity_instruction(
+        &bench.context,
+        &bench.pool,
+        &bench.position,
+        bench.token_owner_account_a,
+        bench.token_owner_account_b,
+        LIQUIDITY,
+    );
+
+    let compute_units = simulate_compute_units(&mut bench.context, &[instruction], &[]).await;
+    assert!(
+        compute_units <= INCREASE_LIQUIDITY_COMPUTE_UNIT_CEILING,
+        "increase_liquidity consumed {compute_units} compute units"
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires the SBF build of the program"]
+async fn test_decrease_liquidity_compute_units() {
+    let mut bench = start_bench().await;
+    let instruction = decrease_liquidity_instruction(
+        &bench.context,
+        &bench.pool,
+        &bench.position,
+        bench.token_owner_account_a,
+        bench.token_owner_account_b,
+        LIQUIDITY,
+    );
+
+    let compute_units = simulate_compute_units(&mut bench.context, &[instruction], &[]).await;
+    assert!(
+        compute_units <= DECREASE_LIQUIDITY_COMPUTE_UNIT_CEILING,
+        "decrease_liquidity consumed {compute_units} compute units"
+    );
+}
@@ -0,0 +1,203 @@
+//! Registry of the events of the program, pinning their discriminators and layouts so that a
+//! change breaking indexers fails here first. A layout change must bump `EVENT_VERSION` and
+//! update the registry.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::EVENT_VERSION;
+use crate::instructions::*;
+use crate::orchestrator::swap_orchestrator::SwapTickUpdate;
+use crate::state::{PositionUpdate, TickUpdate};
+use crate::util::*;
+
+/// Length of the encoding of `T` with every field zeroed and every string, vector and option
+/// empty, which changes with any field added, removed or resized.
+fn zeroed_len<T: AnchorDeserialize>() -> Option<usize> {
+    (0..4_096).find(|len| T::try_from_slice(&vec![0; *len]).is_ok())
+}
+
+macro_rules! event_registry {
+    ($(($event:ident, $discriminator:expr, $zeroed_len:expr)),* $(,)?) => {
+        #[test]
+        fn test_event_discriminators_are_stable() {
+            $(assert_eq!($event::DISCRIMINATOR, $discriminator, stringify!($event));)*
+        }
+
+        #[test]
+        fn test_event_layouts_are_stable() {
+            $(assert_eq!(zeroed_len::<$event>(), Some($zeroed_len), stringify!($event));)*
+        }
+
+        #[test]
+        fn test_events_start_with_event_version() {
+            $(
+                let mut data = vec![0; $zeroed_len];
+                data[0] = EVENT_VERSION;
+                assert_eq!(
+                    $event::try_from_slice(&data).unwrap().event_version,
+                    EVENT_VERSION,
+                    stringify!($event)
+                );
+            )*
+        }
+    };
+}
+
+event_registry! {
+        (AccountExtendedEvent, [175, 221, 223, 215, 209, 93, 222, 189], 89),
+        (AccountMigratedEvent, [109, 3, 25, 119, 155, 108, 69, 61], 91),
+        (BlocklistAddressAddedEvent, [177, 154, 148, 119, 157, 239, 170, 190], 129),
+        (BlocklistAddressRemovedEvent, [113, 202, 215, 28, 35, 124, 75, 9], 129),
+        (BlocklistInitializedEvent, [166, 37, 166, 64, 133, 247, 107, 237], 65),
+        (CollectEscrowedReferralFeesEvent, [31, 208, 233, 106, 139, 237, 55, 10], 169),
+        (CollectProtocolFeesEvent, [138, 239, 63, 34, 151, 127, 184, 58], 273),
+        (CollectReferralFeesEvent, [15, 111, 89, 198, 148, 148, 248, 177], 161),
+        (CollectReinvestmentFeesEvent, [68, 98, 150, 230, 31, 74, 202, 88], 177),
+        (ComputeUnitRegistryInitializedEvent, [13, 239, 69, 230, 8, 157, 238, 90], 97),
+        (ConfigInitializedEvent, [22, 167, 192, 50, 220, 20, 10, 71], 101),
+        (ConfigRoleSetEvent, [164, 182, 201, 6, 61, 148, 97, 146], 130),
+        (DecreaseLiquidityEvent, [58, 222, 86, 58, 68, 50, 85, 56], 566),
+        (DefaultFeeRateSetEvent, [137, 49, 126, 44, 94, 200, 210, 27], 101),
+        (DefaultOracleMaxAgeSetEvent, [220, 186, 181, 93, 181, 194, 1, 231], 81),
+        (DefaultOraclePriceFeedSetEvent, [217, 113, 227, 156, 227, 195, 8, 191], 137),
+        (DefaultProtocolFeeRateSetEvent, [50, 31, 15, 7, 138, 43, 136, 205], 67),
+        (DefaultReinvestmentFeeRateSetEvent, [241, 1, 1, 224, 150, 140, 158, 93], 67),
+        (DefaultSwapReferralRewardFeeRateSetEvent, [76, 237, 18, 213, 31, 114, 65, 144], 67),
+        (DegradedOracleSwapEvent, [253, 31, 69, 15, 118, 117, 239, 119], 57),
+        (DonationEvent, [43, 125, 2, 48, 193, 140, 25, 191], 153),
+        (DustPositionLiquidatedEvent, [130, 250, 185, 157, 227, 143, 189, 217], 217),
+        (DustPositionParamsSetEvent, [201, 246, 213, 114, 143, 232, 24, 18], 89),
+        (DustTransferThresholdsSetEvent, [26, 81, 233, 46, 140, 246, 161, 12], 105),
+        (DustWithdrawalOwedEvent, [247, 20, 48, 101, 108, 141, 143, 7], 81),
+        (EarlyExitFeeSetEvent, [30, 174, 113, 207, 0, 9, 114, 244], 101),
+        (EndTimestampLpSetEvent, [151, 67, 196, 243, 61, 123, 141, 154], 113),
+        (EndTimestampSwapSetEvent, [212, 237, 117, 171, 19, 232, 10, 246], 113),
+        (ExpiredPositionSettledEvent, [133, 32, 242, 143, 171, 42, 37, 232], 137),
+        (FeatureEnabledSetEvent, [225, 16, 147, 245, 91, 201, 26, 224], 43),
+        (FeatureGateInitializedEvent, [74, 112, 40, 45, 200, 175, 29, 101], 65),
+        (FeeAuthorityUpdatedEvent, [98, 203, 209, 221, 216, 67, 219, 242], 97),
+        (FeeRateSetEvent, [228, 235, 2, 221, 236, 57, 177, 128], 101),
+        (FeeTierInitializedEvent, [96, 98, 251, 97, 122, 107, 16, 59], 101),
+        (FeesAndRewardsUpdatedEvent, [2, 116, 10, 74, 158, 135, 247, 165], 225),
+        (FeesCollectedEvent, [228, 238, 55, 219, 37, 85, 82, 54], 402),
+        (FrozenTokenAccountEvent, [227, 167, 241, 17, 152, 205, 70, 44], 65),
+        (IncreaseLiquidityEvent, [49, 79, 105, 212, 32, 34, 30, 84], 581),
+        (InitializePositionTradeBatchWithMetadataEvent, [91, 213, 37, 223, 8, 180, 124, 168], 361),
+        (InitializeTradeBatchPositionEvent, [225, 186, 254, 155, 148, 142, 213, 3], 201),
+        (InstructionComputeUnitsSetEvent, [213, 240, 5, 175, 236, 90, 244, 22], 78),
+        (IntermediateTransferEvent, [209, 29, 181, 54, 30, 13, 165, 127], 185),
+        (KeeperRateLimitSetEvent, [211, 124, 8, 165, 36, 217, 30, 18], 89),
+        (KeeperRegisteredEvent, [224, 191, 29, 228, 40, 178, 126, 26], 109),
+        (KeeperRemovedEvent, [119, 18, 177, 187, 146, 70, 152, 144], 105),
+        (MarketInstanceCreatedEvent, [240, 241, 32, 226, 14, 152, 251, 235], 167),
+        (MaxIntegratorFeeRateSetEvent, [123, 62, 141, 236, 169, 124, 133, 75], 69),
+        (MaxSwapLiquidityBpsSetEvent, [144, 172, 208, 40, 183, 39, 49, 95], 99),
+        (NewOracleAccountSetEvent, [53, 120, 171, 176, 68, 59, 159, 142], 129),
+        (NewOracleMaxAgeSetEvent, [232, 192, 21, 149, 214, 40, 194, 57], 113),
+        (OracleAccountClosedEvent, [81, 160, 70, 50, 182, 179, 52, 239], 105),
+        (OracleDegradedMaximumAgeSetEvent, [175, 113, 124, 166, 83, 250, 243, 165], 113),
+        (OracleMaximumSlotLagSetEvent, [193, 185, 41, 30, 100, 210, 164, 76], 113),
+        (OracleParamsSyncedEvent, [255, 71, 240, 179, 127, 93, 176, 208], 153),
+        (OraclePriceRefreshedEvent, [201, 15, 58, 238, 108, 214, 19, 108], 141),
+        (PermanentDelegateMintPolicySetEvent, [13, 0, 202, 122, 16, 8, 240, 232], 66),
+        (PoolBlocklistEnabledSetEvent, [9, 73, 145, 25, 110, 29, 149, 180], 98),
+        (PoolFeeAnalyticsInitializedEvent, [131, 110, 195, 160, 72, 68, 70, 239], 97),
+        (PoolInitializedBasicEvent, [86, 93, 176, 18, 69, 45, 149, 162], 305),
+        (PoolInitializedFinalEvent, [141, 253, 192, 197, 213, 5, 189, 70], 228),
+        (PoolMetadataSetEvent, [108, 29, 54, 186, 202, 36, 249, 251], 141),
+        (PoolRevenueSnapshotInitializedEvent, [214, 143, 248, 229, 77, 38, 254, 184], 97),
+        (PoolSnapshotInitializedEvent, [199, 68, 30, 253, 191, 155, 11, 205], 97),
+        (PoolStatsSyncedEvent, [253, 211, 177, 76, 154, 41, 22, 124], 89),
+        (PoolSwapRateLimiterSetEvent, [146, 246, 151, 10, 113, 54, 252, 196], 129),
+        (PoolSwapReferralRewardFeeRateSetEvent, [4, 190, 69, 143, 44, 241, 169, 71], 99),
+        (PositionClosedEvent, [76, 129, 10, 225, 238, 51, 158, 126], 297),
+        (PositionCollectionSetEvent, [47, 46, 40, 163, 225, 117, 159, 224], 129),
+        (PositionGraceCheckpointInitializedEvent, [226, 9, 130, 182, 225, 48, 123, 97], 65),
+        (PositionIndexInitializedEvent, [184, 158, 118, 76, 170, 136, 239, 96], 99),
+        (PositionMetadataInitializedEvent, [172, 43, 22, 191, 250, 0, 239, 174], 109),
+        (PositionMetadataSetEvent, [110, 242, 240, 233, 129, 132, 155, 27], 109),
+        (PositionOpenedEvent, [163, 1, 92, 149, 138, 188, 177, 23], 242),
+        (PositionReindexedEvent, [237, 70, 159, 238, 9, 215, 74, 84], 129),
+        (PositionSplitEvent, [254, 220, 229, 38, 136, 161, 91, 177], 193),
+        (PositionTradeBatchDeletedEvent, [49, 6, 45, 15, 230, 215, 23, 183], 233),
+        (PositionWithMetadataOpenedEvent, [199, 94, 211, 129, 26, 89, 219, 251], 274),
+        (PositionsMergedEvent, [130, 254, 201, 187, 159, 245, 158, 130], 153),
+        (ProgramVersionEvent, [184, 92, 131, 235, 29, 151, 215, 189], 38),
+        (ProtocolFeeRateSetEvent, [7, 120, 190, 225, 80, 146, 142, 50], 99),
+        (ProtocolFeeRatesPerSideSetEvent, [150, 3, 169, 75, 236, 235, 124, 235], 101),
+        (ProtocolFeeTreasurySetEvent, [194, 50, 243, 17, 90, 68, 182, 67], 129),
+        (ProtocolStatsInitializedEvent, [222, 147, 182, 127, 127, 163, 121, 55], 97),
+        (ProtocolStatsSyncedEvent, [180, 122, 48, 147, 248, 201, 98, 61], 82),
+        (ReferralFeeEscrowInitializedEvent, [166, 155, 100, 207, 212, 125, 198, 232], 97),
+        (ReferralFeeEscrowedEvent, [131, 95, 61, 113, 176, 247, 229, 200], 106),
+        (ReinvestFeesEvent, [174, 101, 133, 6, 172, 128, 208, 97], 177),
+        (ReinvestmentNewAuthoritySetEvent, [11, 17, 177, 222, 226, 217, 74, 104], 97),
+        (ReinvestmentSwapEvent, [190, 115, 157, 43, 246, 44, 47, 2], 130),
+        (ReinvestmentSwapMaxDeviationSetEvent, [17, 66, 51, 161, 113, 182, 3, 240], 69),
+        (ReinvestmentsAuthorityInitializedEvent, [205, 78, 187, 184, 236, 105, 168, 65], 99),
+        (ReversedTokenMintOrderEvent, [93, 253, 79, 233, 147, 240, 117, 168], 161),
+        (RewardAuthoritySetEvent, [68, 231, 197, 58, 26, 35, 174, 249], 130),
+        (RewardAuthorityUpdatedEvent, [70, 148, 191, 89, 224, 75, 50, 6], 98),
+        (RewardCollectedEvent, [241, 95, 182, 205, 45, 202, 7, 79], 210),
+        (RewardDelegateSetEvent, [91, 142, 138, 93, 40, 0, 176, 83], 129),
+        (RewardEmissionsSetEvent, [215, 34, 141, 201, 114, 152, 164, 116], 146),
+        (RewardEmissionsThresholdSetEvent, [109, 224, 164, 89, 252, 153, 79, 19], 114),
+        (RewardFullRangeBoostUpdatedEvent, [4, 157, 20, 106, 128, 15, 55, 27], 38),
+        (RewardInitializedEvent, [219, 146, 100, 186, 124, 253, 237, 33], 162),
+        (RewardOutOfRangeGraceUpdatedEvent, [174, 172, 208, 105, 23, 101, 36, 40], 54),
+        (RewardPausedEvent, [219, 132, 19, 1, 118, 163, 34, 208], 106),
+        (RewardResumedEvent, [217, 72, 97, 58, 139, 252, 191, 189], 106),
+        (RewardsFundedFromProtocolFeesEvent, [168, 125, 35, 219, 8, 156, 187, 170], 140),
+        (StakingRewardShareSetEvent, [95, 167, 3, 239, 30, 33, 208, 62], 99),
+        (StakingRewardsClaimedEvent, [12, 129, 221, 129, 39, 249, 105, 163], 177),
+        (StakingRewardsInitializedEvent, [195, 201, 57, 48, 51, 16, 21, 72], 129),
+        (StartTimestampLpSetEvent, [217, 205, 169, 217, 103, 130, 65, 33], 113),
+        (StartTimestampSwapSetEvent, [170, 109, 162, 106, 135, 201, 80, 25], 113),
+        (SuperAdminRecoveryCancelledEvent, [34, 165, 172, 220, 198, 44, 141, 188], 65),
+        (SuperAdminRecoveryClaimedEvent, [198, 173, 157, 78, 238, 8, 207, 54], 65),
+        (SuperAdminRecoveryRequestedEvent, [103, 31, 42, 129, 60, 196, 71, 82], 81),
+        (SuperAdminRecoverySetEvent, [255, 186, 83, 128, 8, 224, 62, 250], 73),
+        (SwapBreakdownEvent, [222, 82, 101, 251, 84, 218, 238, 196], 90),
+        (SwapCommitmentCancelledEvent, [3, 128, 35, 136, 162, 214, 247, 2], 97),
+        (SwapCommittedEvent, [205, 133, 138, 152, 181, 117, 188, 230], 137),
+        (SwapExecutedEvent, [183, 28, 219, 210, 164, 184, 62, 12], 616),
+        (SwapRateLimitSetEvent, [204, 128, 88, 37, 21, 129, 233, 55], 97),
+        (SwapRateLimiterInitializedEvent, [141, 224, 185, 70, 236, 1, 33, 3], 113),
+        (SwapReferralClosedEvent, [89, 82, 17, 111, 123, 10, 6, 126], 109),
+        (SwapReferralInitialized, [191, 60, 210, 248, 35, 209, 153, 101], 101),
+        (SwapReferralRewardFeeRateSetEvent, [234, 108, 197, 176, 160, 40, 31, 16], 99),
+        (SwapReferralTransferredEvent, [236, 180, 155, 194, 12, 208, 44, 108], 101),
+        (SwapRevealedEvent, [21, 114, 183, 201, 195, 52, 192, 84], 113),
+        (SwapTickUpdate, [120, 92, 71, 210, 114, 233, 110, 172], 122),
+        (TickArrayBountyClaimedEvent, [197, 103, 140, 177, 56, 243, 100, 57], 141),
+        (TickArrayBountyFundedEvent, [94, 247, 224, 41, 168, 125, 93, 73], 105),
+        (TickArrayBountyInitializedEvent, [122, 217, 141, 72, 190, 178, 250, 31], 137),
+        (TickArrayBountyRewardSetEvent, [49, 68, 202, 52, 120, 219, 7, 117], 145),
+        (TickArrayClosedEvent, [226, 63, 68, 129, 213, 128, 13, 105], 109),
+        (TickArrayExpandedEvent, [104, 84, 174, 46, 74, 172, 51, 155], 109),
+        (TickArrayInitializedEvent, [31, 59, 13, 94, 35, 225, 138, 193], 101),
+        (TickArrayMigratedEvent, [81, 152, 226, 14, 151, 180, 187, 86], 109),
+        (TickArrayV2InitializedEvent, [120, 144, 245, 79, 134, 144, 13, 2], 101),
+        (TokenBadgeDeletedEvent, [233, 26, 81, 234, 59, 98, 156, 16], 97),
+        (TokenBadgeInitializedEvent, [129, 104, 149, 162, 66, 78, 72, 117], 97),
+        (TradeBatchPositionClosedEvent, [234, 91, 224, 128, 115, 171, 73, 67], 195),
+        (TradeBatchPositionOpenedEvent, [107, 145, 106, 54, 157, 11, 59, 217], 244),
+        (TransferReferralFeeEvent, [164, 24, 156, 203, 17, 194, 229, 118], 202),
+        (TwoHopSwapEvent, [49, 34, 179, 94, 211, 238, 242, 243], 580),
+        (UpdateTicksEvent, [218, 47, 200, 227, 120, 114, 99, 79], 299),
+}
+
+#[test]
+fn test_embedded_struct_layouts_are_stable() {
+    // Embedded in `IncreaseLiquidityEvent`, `DecreaseLiquidityEvent` and `UpdateTicksEvent`
+    assert_eq!(PositionUpdate::default().try_to_vec().unwrap().len(), 152);
+    assert_eq!(TickUpdate::default().try_to_vec().unwrap().len(), 113);
+
+    let position_update = PositionUpdate { liquidity: 1, ..Default::default() };
+    assert_eq!(position_update.try_to_vec().unwrap()[..16], 1u128.to_le_bytes());
+    let tick_update = TickUpdate { initialized: true, liquidity_net: -1, ..Default::default() };
+    let tick_update_data = tick_update.try_to_vec().unwrap();
+    assert_eq!(tick_update_data[0], 1);
+    assert_eq!(tick_update_data[1..17], (-1i128).to_le_bytes());
+}
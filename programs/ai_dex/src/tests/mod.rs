@@ -1,5 +1,7 @@
 #[cfg(test)]
 mod swap_integration_tests;
+#[cfg(test)]
+mod event_registry_tests;
 
 // #[cfg(test)]
 // pub use swap_integration_tests::*;
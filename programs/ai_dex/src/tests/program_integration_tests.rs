@@ -0,0 +1,1469 @@
+use anchor_lang::prelude::{AccountMeta, AnchorDeserialize, Clock, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+use ai_dex::errors::ErrorCode;
+use ai_dex::math::{
+    get_amount_delta_a, get_amount_delta_b, get_integrator_fee_amount_for_output, sqrt_price_from_tick_index,
+    MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+};
+use ai_dex::state::{PositionIndex, TickArrayType, TICK_ARRAY_SIZE};
+use ai_dex::util::{calculate_liquidity_from_amounts, AccountsType, RemainingAccountsInfo, RemainingAccountsSlice};
+use program_test_fixture::*;
+
+mod compute_unit_bench;
+mod program_test_fixture;
+
+const LIQUIDITY: u128 = 1_000_000_000;
+const TOKEN_BALANCE: u64 = 1_000_000_000_000;
+
+fn increase_liquidity_instruction(
+    context: &ProgramTestContext,
+    pool: &PoolFixture,
+    position: &PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    liquidity_amount: u128,
+) -> Instruction {
+    ai_dex_instruction(
+        modify_liquidity_accounts(context, pool, position, token_owner_account_a, token_owner_account_b),
+        ai_dex::instruction::IncreaseLiquidity {
+            liquidity_amount,
+            token_max_a: u64::MAX,
+            token_max_b: u64::MAX,
+            remaining_accounts_info: None,
+            referral_code: None,
+            deadline_timestamp: None,
+            emit_tick_updates: None,
+        },
+    )
+}
+
+async fn increase_liquidity(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    position: &PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    liquidity_amount: u128,
+) {
+    let instruction = increase_liquidity_instruction(
+        context,
+        pool,
+        position,
+        token_owner_account_a,
+        token_owner_account_b,
+        liquidity_amount,
+    );
+    process_instructions(context, &[instruction], &[]).await.unwrap();
+}
+
+fn decrease_liquidity_instruction(
+    context: &ProgramTestContext,
+    pool: &PoolFixture,
+    position: &PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    liquidity_amount: u128,
+) -> Instruction {
+    ai_dex_instruction(
+        modify_liquidity_accounts(context, pool, position, token_owner_account_a, token_owner_account_b),
+        ai_dex::instruction::DecreaseLiquidity {
+            liquidity_amount,
+            token_min_a: 0,
+            token_min_b: 0,
+            remaining_accounts_info: None,
+            referral_code: None,
+            deadline_timestamp: None,
+            unwrap_sol: None,
+            emit_tick_updates: None,
+        },
+    )
+}
+
+async fn decrease_liquidity(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    position: &PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    liquidity_amount: u128,
+) {
+    let instruction = decrease_liquidity_instruction(
+        context,
+        pool,
+        position,
+        token_owner_account_a,
+        token_owner_account_b,
+        liquidity_amount,
+    );
+    process_instructions(context, &[instruction], &[]).await.unwrap();
+}
+
+async fn swap_exact_input_instruction(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    amount: u64,
+    a_to_b: bool,
+) -> Instruction {
+    let tick_current_index = get_pool(context, pool).await.tick_current_index;
+    ai_dex_instruction(
+        swap_accounts(
+            pool,
+            context.payer.pubkey(),
+            token_owner_account_a,
+            token_owner_account_b,
+            tick_current_index,
+            a_to_b,
+        ),
+        ai_dex::instruction::Swap {
+            amount,
+            other_amount_threshold: 0,
+            sqrt_price_limit: if a_to_b { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 },
+            amount_specified_is_input: true,
+            a_to_b,
+            remaining_accounts_info: None,
+            deadline_timestamp: None,
+            tick_index_limit: None,
+            integrator_fee_rate: None,
+            stop_at_tick_array_end: None,
+            oracle_maximum_age: None,
+        },
+    )
+}
+
+async fn swap_exact_input(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    amount: u64,
+    a_to_b: bool,
+) {
+    let instruction =
+        swap_exact_input_instruction(context, pool, token_owner_account_a, token_owner_account_b, amount, a_to_b).await;
+    process_instructions(context, &[instruction], &[]).await.unwrap();
+}
+
+async fn collect_fees(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    position: &PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+) {
+    let instruction = ai_dex_instruction(
+        ai_dex::accounts::CollectFees {
+            ai_dex_pool: pool.ai_dex_pool,
+            position_authority: context.payer.pubkey(),
+            position: position.position,
+            position_token_account: position.position_token_account,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            token_owner_account_a,
+            token_vault_a: pool.token_vault_a,
+            token_owner_account_b,
+            token_vault_b: pool.token_vault_b,
+            token_program_a: pool.token_program_a,
+            token_program_b: pool.token_program_b,
+            memo_program: anchor_spl::memo::ID,
+            lp_referral_ata_a: None,
+            lp_referral_ata_b: None,
+            associated_token_program: None,
+            system_program: None,
+        },
+        ai_dex::instruction::CollectFees {
+            remaining_accounts_info: None,
+            to_owned_token_accounts: None,
+        },
+    );
+    process_instructions(context, &[instruction], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_liquidity_and_swap_flow() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+
+    // A position around the current price, with the tick arrays a swap crosses in both directions
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264, 5_632, 11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    let sqrt_price = sqrt_price_from_tick_index(0);
+    let expected_amount_a =
+        get_amount_delta_a(sqrt_price, sqrt_price_from_tick_index(1280), LIQUIDITY, true).unwrap();
+    let expected_amount_b =
+        get_amount_delta_b(sqrt_price_from_tick_index(-1280), sqrt_price, LIQUIDITY, true).unwrap();
+    assert_eq!(get_token_balance(&mut context, pool.token_vault_a).await, expected_amount_a);
+    assert_eq!(get_token_balance(&mut context, pool.token_vault_b).await, expected_amount_b);
+    assert_eq!({ get_pool(&mut context, &pool).await.liquidity }, LIQUIDITY);
+    assert_eq!(get_position(&mut context, &position).await.liquidity, LIQUIDITY);
+    let tick_array_lower = get_tick_array(&mut context, &pool, tick_array_start_index(-1280)).await;
+    let tick_lower = tick_array_lower.get_tick(-1280, TICK_SPACING).unwrap();
+    assert_eq!({ tick_lower.liquidity_net }, LIQUIDITY as i128);
+    assert_eq!({ tick_lower.liquidity_gross }, LIQUIDITY);
+
+    // Swap A to B, then back
+    let amount_in = 1_000_000;
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, amount_in, true).await;
+    let ai_dex_pool = get_pool(&mut context, &pool).await;
+    assert!(ai_dex_pool.sqrt_price < sqrt_price);
+    assert!(ai_dex_pool.fee_growth_global_a > 0);
+    assert!(ai_dex_pool.protocol_fee_owed_a > 0);
+    assert_eq!(
+        get_token_balance(&mut context, token_owner_account_a).await,
+        TOKEN_BALANCE - expected_amount_a - amount_in
+    );
+    assert_eq!(
+        get_token_balance(&mut context, pool.token_vault_a).await,
+        expected_amount_a + amount_in
+    );
+
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, amount_in, false).await;
+    let ai_dex_pool = get_pool(&mut context, &pool).await;
+    assert!(ai_dex_pool.fee_growth_global_b > 0);
+
+    // Withdraw the liquidity and the fees earned on both swaps
+    decrease_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+    let position_data = get_position(&mut context, &position).await;
+    assert_eq!(position_data.liquidity, 0);
+    assert!(position_data.fee_owed_a > 0);
+    assert!(position_data.fee_owed_b > 0);
+
+    let balance_a = get_token_balance(&mut context, token_owner_account_a).await;
+    let balance_b = get_token_balance(&mut context, token_owner_account_b).await;
+    collect_fees(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b).await;
+    assert_eq!(
+        get_token_balance(&mut context, token_owner_account_a).await,
+        balance_a + position_data.fee_owed_a
+    );
+    assert_eq!(
+        get_token_balance(&mut context, token_owner_account_b).await,
+        balance_b + position_data.fee_owed_b
+    );
+
+    // Only the protocol fees and a few units of rounding in favor of the pool remain in the vaults
+    let ai_dex_pool = get_pool(&mut context, &pool).await;
+    assert_eq!({ ai_dex_pool.liquidity }, 0);
+    assert!(get_token_balance(&mut context, pool.token_vault_a).await >= ai_dex_pool.protocol_fee_owed_a);
+    assert!(get_token_balance(&mut context, pool.token_vault_b).await >= ai_dex_pool.protocol_fee_owed_b);
+    assert!(get_token_balance(&mut context, pool.token_vault_a).await <= ai_dex_pool.protocol_fee_owed_a + 5);
+    assert!(get_token_balance(&mut context, pool.token_vault_b).await <= ai_dex_pool.protocol_fee_owed_b + 5);
+}
+
+#[tokio::test]
+async fn test_swap_token_2022_with_transfer_fee_and_transfer_hook() {
+    let mut context = start_program_test().await;
+    // 1% transfer fee on token A, and a transfer hook extension without a hook program on token B
+    let transfer_fee_bps = 100;
+    let mint_config_b = MintConfig { has_transfer_hook: true, ..MintConfig::token_2022() };
+    let pool = create_pool(
+        &mut context,
+        MintConfig::token_2022_with_transfer_fee(transfer_fee_bps, u64::MAX),
+        mint_config_b,
+        0,
+    )
+    .await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    // The deposit includes the transfer fee, so the vault receives the liquidity amount exactly
+    let expected_amount_a =
+        get_amount_delta_a(sqrt_price_from_tick_index(0), sqrt_price_from_tick_index(1280), LIQUIDITY, true).unwrap();
+    assert_eq!(get_token_balance(&mut context, pool.token_vault_a).await, expected_amount_a);
+    let deposit_fee_a = get_withheld_transfer_fee(&mut context, pool.token_vault_a).await;
+    assert!(deposit_fee_a > 0);
+
+    // The input amount includes the transfer fee withheld in the vault
+    let amount_in = 1_000_000;
+    let vault_b_before = get_token_balance(&mut context, pool.token_vault_b).await;
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, amount_in, true).await;
+
+    let swap_fee_a = amount_in * transfer_fee_bps as u64 / 10_000;
+    assert_eq!(
+        get_withheld_transfer_fee(&mut context, pool.token_vault_a).await,
+        deposit_fee_a + swap_fee_a
+    );
+    assert_eq!(
+        get_token_balance(&mut context, pool.token_vault_a).await,
+        expected_amount_a + amount_in - swap_fee_a
+    );
+    let amount_out = vault_b_before - get_token_balance(&mut context, pool.token_vault_b).await;
+    assert!(amount_out > 0);
+    assert_eq!(
+        get_token_balance(&mut context, token_owner_account_b).await,
+        TOKEN_BALANCE - get_amount_delta_b(sqrt_price_from_tick_index(-1280), sqrt_price_from_tick_index(0), LIQUIDITY, true).unwrap()
+            + amount_out
+    );
+}
+
+#[tokio::test]
+async fn test_swap_exact_output_with_integrator_fee() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let integrator_fee_account =
+        create_token_account(&mut context, Pubkey::new_unique(), pool.token_mint_b, pool.token_program_b, 0).await;
+
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    let integrator_fee_rate = 25;
+    let set_max_integrator_fee_rate = ai_dex_instruction(
+        ai_dex::accounts::SetMaxIntegratorFeeRate { ai_dex_config: pool.ai_dex_config, config_authority: owner },
+        ai_dex::instruction::SetMaxIntegratorFeeRate { max_integrator_fee_rate: integrator_fee_rate },
+    );
+    process_instructions(&mut context, &[set_max_integrator_fee_rate], &[]).await.unwrap();
+
+    // The output is grossed up by the fee, which the integrator receives from the vault
+    let amount_out = 100_000;
+    let balance_b = get_token_balance(&mut context, token_owner_account_b).await;
+    let vault_b_before = get_token_balance(&mut context, pool.token_vault_b).await;
+    let tick_current_index = get_pool(&mut context, &pool).await.tick_current_index;
+    let swap = ai_dex_instruction(
+        ai_dex::accounts::Swap {
+            integrator_fee_account: Some(integrator_fee_account),
+            ..swap_accounts(&pool, owner, token_owner_account_a, token_owner_account_b, tick_current_index, true)
+        },
+        ai_dex::instruction::Swap {
+            amount: amount_out,
+            other_amount_threshold: u64::MAX,
+            sqrt_price_limit: MIN_SQRT_PRICE_X64,
+            amount_specified_is_input: false,
+            a_to_b: true,
+            remaining_accounts_info: None,
+            deadline_timestamp: None,
+            tick_index_limit: None,
+            integrator_fee_rate: Some(integrator_fee_rate),
+            stop_at_tick_array_end: None,
+            oracle_maximum_age: None,
+        },
+    );
+    process_instructions(&mut context, &[swap], &[]).await.unwrap();
+
+    let integrator_fee_amount = get_integrator_fee_amount_for_output(amount_out, integrator_fee_rate).unwrap();
+    assert!(integrator_fee_amount > 0);
+    assert_eq!(get_token_balance(&mut context, token_owner_account_b).await, balance_b + amount_out);
+    assert_eq!(get_token_balance(&mut context, integrator_fee_account).await, integrator_fee_amount);
+    assert_eq!(
+        get_token_balance(&mut context, pool.token_vault_b).await,
+        vault_b_before - amount_out - integrator_fee_amount
+    );
+}
+
+/// The `HopAccounts` group of an a to b hop, with the two tick arrays below the current price.
+fn a_to_b_hop_accounts(pool: &PoolFixture) -> Vec<AccountMeta> {
+    let ticks_in_array = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+    vec![
+        AccountMeta::new(pool.ai_dex_pool, false),
+        AccountMeta::new(pool.token_vault_a, false),
+        AccountMeta::new(pool.token_vault_b, false),
+        AccountMeta::new(tick_array_address(pool, 0), false),
+        AccountMeta::new(tick_array_address(pool, -ticks_in_array), false),
+    ]
+}
+
+#[tokio::test]
+async fn test_two_hop_swap_compact() {
+    let mut context = start_program_test().await;
+    let mut mints = [Keypair::new(), Keypair::new(), Keypair::new()];
+    mints.sort_by_key(|mint| mint.pubkey());
+    for mint in &mints {
+        create_mint(&mut context, mint, MintConfig::token()).await;
+    }
+    let [mint_input, mint_intermediate, mint_output] = mints.map(|mint| mint.pubkey());
+
+    // Input to intermediate through the first pool, intermediate to output through the second
+    let ai_dex_config = create_config(&mut context).await;
+    let pool_one = create_pool_with_mints(
+        &mut context,
+        ai_dex_config,
+        (mint_input, MintConfig::token()),
+        (mint_intermediate, MintConfig::token()),
+        0,
+    )
+    .await;
+    let pool_two = create_pool_with_mints(
+        &mut context,
+        ai_dex_config,
+        (mint_intermediate, MintConfig::token()),
+        (mint_output, MintConfig::token()),
+        0,
+    )
+    .await;
+
+    let owner = context.payer.pubkey();
+    let token_program = anchor_spl::token::ID;
+    let token_owner_account_input = create_token_account(&mut context, owner, mint_input, token_program, TOKEN_BALANCE).await;
+    let token_owner_account_intermediate =
+        create_token_account(&mut context, owner, mint_intermediate, token_program, TOKEN_BALANCE).await;
+    let token_owner_account_output = create_token_account(&mut context, owner, mint_output, token_program, TOKEN_BALANCE).await;
+
+    let position_one = open_position(&mut context, &pool_one, -1280, 1280).await;
+    increase_liquidity(&mut context, &pool_one, &position_one, token_owner_account_input, token_owner_account_intermediate, LIQUIDITY).await;
+    let position_two = open_position(&mut context, &pool_two, -1280, 1280).await;
+    increase_liquidity(&mut context, &pool_two, &position_two, token_owner_account_intermediate, token_owner_account_output, LIQUIDITY).await;
+
+    let vault_one_input_before = get_token_balance(&mut context, pool_one.token_vault_a).await;
+    let vault_one_intermediate_before = get_token_balance(&mut context, pool_one.token_vault_b).await;
+    let vault_two_intermediate_before = get_token_balance(&mut context, pool_two.token_vault_a).await;
+    let input_before = get_token_balance(&mut context, token_owner_account_input).await;
+    let output_before = get_token_balance(&mut context, token_owner_account_output).await;
+
+    let hop_accounts_one = a_to_b_hop_accounts(&pool_one);
+    let hop_accounts_two = a_to_b_hop_accounts(&pool_two);
+    let remaining_accounts_info = RemainingAccountsInfo {
+        slices: vec![
+            RemainingAccountsSlice { accounts_type: AccountsType::HopAccountsOne, length: hop_accounts_one.len() as u8 },
+            RemainingAccountsSlice { accounts_type: AccountsType::HopAccountsTwo, length: hop_accounts_two.len() as u8 },
+        ],
+    };
+    let mut instruction = ai_dex_instruction(
+        ai_dex::accounts::TwoHopSwapCompact {
+            token_mint_input: mint_input,
+            token_mint_intermediate: mint_intermediate,
+            token_mint_output: mint_output,
+            token_program_input: token_program,
+            token_program_intermediate: token_program,
+            token_program_output: token_program,
+            token_owner_account_input,
+            token_owner_account_output,
+            token_authority: owner,
+            memo_program: anchor_spl::memo::ID,
+        },
+        ai_dex::instruction::TwoHopSwapCompact {
+            amount: 1_000_000,
+            other_amount_threshold: 0,
+            amount_specified_is_input: true,
+            a_to_b_one: true,
+            a_to_b_two: true,
+            sqrt_price_limit_one: MIN_SQRT_PRICE_X64,
+            sqrt_price_limit_two: MIN_SQRT_PRICE_X64,
+            remaining_accounts_info: Some(remaining_accounts_info),
+            deadline_timestamp: None,
+            tick_index_limit_one: None,
+            tick_index_limit_two: None,
+        },
+    );
+    instruction.accounts.extend(hop_accounts_one);
+    instruction.accounts.extend(hop_accounts_two);
+    // The shared accounts, then five accounts for each hop
+    assert_eq!(instruction.accounts.len(), 10 + 5 + 5);
+    process_instructions(&mut context, &[instruction], &[]).await.unwrap();
+
+    // The intermediate token moves from the vault of the first pool to the vault of the second
+    let amount_intermediate = vault_one_intermediate_before - get_token_balance(&mut context, pool_one.token_vault_b).await;
+    assert!(amount_intermediate > 0);
+    assert_eq!(
+        get_token_balance(&mut context, pool_two.token_vault_a).await,
+        vault_two_intermediate_before + amount_intermediate
+    );
+    assert_eq!(get_token_balance(&mut context, pool_one.token_vault_a).await, vault_one_input_before + 1_000_000);
+    assert_eq!(get_token_balance(&mut context, token_owner_account_input).await, input_before - 1_000_000);
+    assert!(get_token_balance(&mut context, token_owner_account_output).await > output_before);
+    assert!(get_pool(&mut context, &pool_two).await.sqrt_price < sqrt_price_from_tick_index(0));
+}
+
+/// Registers the payer as a keeper of the config and as the reinvestments authority, returning
+/// the keeper and the reinvestments account.
+async fn register_reinvestment_keeper(context: &mut ProgramTestContext, pool: &PoolFixture) -> (Pubkey, Pubkey) {
+    let payer = context.payer.pubkey();
+    let (super_admin_account, _) = Pubkey::find_program_address(&[b"super-admin"], &ai_dex::ID);
+    let (keeper, _) =
+        Pubkey::find_program_address(&[b"keeper", pool.ai_dex_config.as_ref(), payer.as_ref()], &ai_dex::ID);
+    let (reinvestments_account, _) = Pubkey::find_program_address(&[b"reinvestments", payer.as_ref()], &ai_dex::ID);
+
+    let instructions = [
+        ai_dex_instruction(
+            ai_dex::accounts::RegisterKeeper {
+                ai_dex_config: pool.ai_dex_config,
+                config_authority: payer,
+                keeper_authority: payer,
+                keeper,
+                funder: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::RegisterKeeper { max_actions_per_window: 10, window_duration: 60 },
+        ),
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeReinvestmentsAuthority {
+                super_admin_account,
+                super_admin_authority: payer,
+                reinvestments_account,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializeReinvestments {
+                reinvestments_authority: payer,
+                default_reinvestment_fee_rate: 1_000,
+            },
+        ),
+    ];
+    process_instructions(context, &instructions, &[]).await.unwrap();
+
+    (keeper, reinvestments_account)
+}
+
+#[tokio::test]
+async fn test_position_cannot_be_modified_in_slot_of_reinvestment() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let (keeper, reinvestments_account) = register_reinvestment_keeper(&mut context, &pool).await;
+
+    let position = open_position_with_reinvestment(&mut context, &pool, -1280, 1280, true).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264, 5_632, 11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    // Earn fees in both tokens, and update the fees owed to the position
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, 1_000_000, true).await;
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, 1_000_000, false).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, 1).await;
+    let position_data = get_position(&mut context, &position).await;
+    assert!(position_data.fee_owed_a > 0 && position_data.fee_owed_b > 0);
+
+    let (reinvestment_fee_escrow, _) = Pubkey::find_program_address(
+        &[b"reinvestment_fee_escrow", reinvestments_account.as_ref(), pool.ai_dex_pool.as_ref()],
+        &ai_dex::ID,
+    );
+    let reinvest_fees = ai_dex_instruction(
+        ai_dex::accounts::ReinvestFees {
+            ai_dex_pool: pool.ai_dex_pool,
+            keeper_authority: owner,
+            keeper,
+            position: position.position,
+            position_token_account: position.position_token_account,
+            tick_array_lower: tick_array_address(&pool, tick_array_start_index(position.tick_lower_index)),
+            tick_array_upper: tick_array_address(&pool, tick_array_start_index(position.tick_upper_index)),
+            reinvestments_account,
+            reinvestment_fee_escrow,
+            system_program: anchor_lang::system_program::ID,
+        },
+        ai_dex::instruction::UpdateReinvestments {},
+    );
+    let decrease_liquidity = ai_dex_instruction(
+        modify_liquidity_accounts(&context, &pool, &position, token_owner_account_a, token_owner_account_b),
+        ai_dex::instruction::DecreaseLiquidity {
+            liquidity_amount: LIQUIDITY,
+            token_min_a: 0,
+            token_min_b: 0,
+            remaining_accounts_info: None,
+            referral_code: None,
+            deadline_timestamp: None,
+            unwrap_sol: None,
+            emit_tick_updates: None,
+        },
+    );
+
+    // The owner cannot withdraw in the slot of the reinvestment, even in the same transaction
+    let slot = context.banks_client.get_root_slot().await.unwrap() + 1;
+    context.warp_to_slot(slot).unwrap();
+    let err = process_instructions(&mut context, &[reinvest_fees.clone(), decrease_liquidity.clone()], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(ErrorCode::PositionReinvestedInSlot.into()))
+    );
+
+    process_instructions(&mut context, &[reinvest_fees], &[]).await.unwrap();
+    let position_data = get_position(&mut context, &position).await;
+    assert_eq!(position_data.fee_owed_a, 0);
+    assert_eq!(position_data.fee_owed_b, 0);
+    assert!(position_data.liquidity > LIQUIDITY + 1);
+    let last_reinvestment_slot = position_data.last_reinvestment_slot;
+    assert!(last_reinvestment_slot >= slot);
+
+    // The reinvestment fee is owed to the reinvestments account it went through
+    let escrow: ai_dex::state::ReinvestmentFeeEscrow = get_account(&mut context, reinvestment_fee_escrow).await;
+    let pool_data = get_pool(&mut context, &pool).await;
+    assert_eq!(escrow.reinvestments_account, reinvestments_account);
+    assert!(escrow.fee_owed_a > 0 && escrow.fee_owed_b > 0);
+    assert_eq!(
+        (escrow.fee_owed_a, escrow.fee_owed_b),
+        ({ pool_data.reinvestment_fee_owed_a }, { pool_data.reinvestment_fee_owed_b })
+    );
+
+    // The lease ends with the slot
+    context.warp_to_slot(last_reinvestment_slot + 1).unwrap();
+    process_instructions(&mut context, &[decrease_liquidity], &[]).await.unwrap();
+    assert_eq!(get_position(&mut context, &position).await.liquidity, position_data.liquidity - LIQUIDITY);
+}
+
+#[tokio::test]
+async fn test_open_position_with_liquidity() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    create_tick_arrays(&mut context, &pool, &[-1280, 1280]).await;
+
+    let (position_mint, position, position_token_account) = position_addresses(&pool, owner, 0, -1280, 1280);
+    let open_position_with_liquidity = |token_max_a, min_sqrt_price| {
+        ai_dex_instruction(
+            ai_dex::accounts::OpenPositionWithLiquidity {
+                funder: owner,
+                owner,
+                position,
+                position_mint,
+                position_token_account,
+                ai_dex_pool: pool.ai_dex_pool,
+                token_program: anchor_spl::token::ID,
+                system_program: anchor_lang::system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                position_index: None,
+                token_program_a: pool.token_program_a,
+                token_program_b: pool.token_program_b,
+                memo_program: anchor_spl::memo::ID,
+                token_mint_a: pool.token_mint_a,
+                token_mint_b: pool.token_mint_b,
+                token_owner_account_a,
+                token_owner_account_b,
+                token_vault_a: pool.token_vault_a,
+                token_vault_b: pool.token_vault_b,
+                tick_array_lower: tick_array_address(&pool, tick_array_start_index(-1280)),
+                tick_array_upper: tick_array_address(&pool, tick_array_start_index(1280)),
+                oracle_account: None,
+                price_update: None,
+                lp_referral: None,
+            },
+            ai_dex::instruction::OpenPositionWithLiquidity {
+                position_seed: 0,
+                tick_lower_index: -1280,
+                tick_upper_index: 1280,
+                is_reinvestment_on: false,
+                liquidity_amount: LIQUIDITY,
+                token_max_a,
+                token_max_b: u64::MAX,
+                remaining_accounts_info: None,
+                deadline_timestamp: None,
+                min_sqrt_price,
+                max_sqrt_price: None,
+            },
+        )
+    };
+
+    let sqrt_price = sqrt_price_from_tick_index(0);
+    let expected_amount_a =
+        get_amount_delta_a(sqrt_price, sqrt_price_from_tick_index(1280), LIQUIDITY, true).unwrap();
+    let expected_amount_b =
+        get_amount_delta_b(sqrt_price_from_tick_index(-1280), sqrt_price, LIQUIDITY, true).unwrap();
+
+    // The whole instruction reverts when the deposit exceeds its bounds, without opening the position
+    let err = process_instructions(&mut context, &[open_position_with_liquidity(expected_amount_a - 1, None)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::TokenLimitExceededError.into()))
+    );
+    let err = process_instructions(&mut context, &[open_position_with_liquidity(expected_amount_a, Some(sqrt_price + 1))], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::PoolPriceOutsideDepositBounds.into()))
+    );
+    assert!(context.banks_client.get_account(position).await.unwrap().is_none());
+
+    process_instructions(&mut context, &[open_position_with_liquidity(expected_amount_a, Some(sqrt_price))], &[])
+        .await
+        .unwrap();
+    let position_fixture = PositionFixture {
+        position,
+        position_token_account,
+        tick_lower_index: -1280,
+        tick_upper_index: 1280,
+    };
+    assert_eq!(get_position(&mut context, &position_fixture).await.liquidity, LIQUIDITY);
+    assert_eq!({ get_pool(&mut context, &pool).await.liquidity }, LIQUIDITY);
+    assert_eq!(get_token_balance(&mut context, position_token_account).await, 1);
+    assert_eq!(get_token_balance(&mut context, pool.token_vault_a).await, expected_amount_a);
+    assert_eq!(get_token_balance(&mut context, pool.token_vault_b).await, expected_amount_b);
+}
+
+#[tokio::test]
+async fn test_initialize_pool_with_liquidity() {
+    let mut context = start_program_test().await;
+    let mut mints = [Keypair::new(), Keypair::new()];
+    mints.sort_by_key(|mint| mint.pubkey());
+    let [mint_a, mint_b] = mints;
+    create_mint(&mut context, &mint_a, MintConfig::token()).await;
+    create_mint(&mut context, &mint_b, MintConfig::token()).await;
+    let ai_dex_config = create_config(&mut context).await;
+    let pool = initialize_pool_step_1(
+        &mut context,
+        ai_dex_config,
+        (mint_a.pubkey(), MintConfig::token()),
+        (mint_b.pubkey(), MintConfig::token()),
+        0,
+    )
+    .await;
+    // The tick arrays of the initial position are initialized between the two steps
+    create_tick_arrays(&mut context, &pool, &[-1280, 1280]).await;
+
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let (position_mint, position, position_token_account) = position_addresses(&pool, owner, 0, -1280, 1280);
+    let initialize_pool_with_liquidity = |token_amount_a| {
+        ai_dex_instruction(
+            ai_dex::accounts::InitializePoolWithLiquidity {
+                ai_dex_pool: pool.ai_dex_pool,
+                token_mint_a: pool.token_mint_a,
+                token_mint_b: pool.token_mint_b,
+                funder: owner,
+                token_vault_a: pool.token_vault_a,
+                token_vault_b: pool.token_vault_b,
+                token_program_a: pool.token_program_a,
+                token_program_b: pool.token_program_b,
+                position,
+                position_mint,
+                position_token_account,
+                position_index: None,
+                token_owner_account_a,
+                token_owner_account_b,
+                tick_array_lower: tick_array_address(&pool, tick_array_start_index(-1280)),
+                tick_array_upper: tick_array_address(&pool, tick_array_start_index(1280)),
+                oracle_account: None,
+                price_update: None,
+                token_program: anchor_spl::token::ID,
+                memo_program: anchor_spl::memo::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: anchor_lang::system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            },
+            ai_dex::instruction::InitializePoolWithLiquidity {
+                tick_spacing: TICK_SPACING,
+                start_timestamp_lp: None,
+                end_timestamp_lp: None,
+                start_timestamp_swap: None,
+                end_timestamp_swap: None,
+                lbp_fee_schedule: None,
+                position_seed: 0,
+                tick_lower_index: -1280,
+                tick_upper_index: 1280,
+                is_reinvestment_on: false,
+                token_amount_a,
+                token_amount_b: 1_000_000,
+                remaining_accounts_info: None,
+            },
+        )
+    };
+
+    // Without liquidity in the range, the pool is not finalized either
+    let err = process_instructions(&mut context, &[initialize_pool_with_liquidity(0)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::ZeroLiquidityError.into()))
+    );
+    assert!(context.banks_client.get_account(pool.token_vault_a).await.unwrap().is_none());
+
+    process_instructions(&mut context, &[initialize_pool_with_liquidity(1_000_000)], &[])
+        .await
+        .unwrap();
+    let sqrt_price = sqrt_price_from_tick_index(0);
+    let liquidity = calculate_liquidity_from_amounts(0, sqrt_price, -1280, 1280, 1_000_000, 1_000_000).unwrap();
+    assert!(liquidity > 0);
+    let pool_state = get_pool(&mut context, &pool).await;
+    assert_eq!({ pool_state.liquidity }, liquidity);
+    assert_eq!({ pool_state.token_vault_a }, pool.token_vault_a);
+    assert_eq!({ pool_state.token_vault_b }, pool.token_vault_b);
+    let position_fixture = PositionFixture {
+        position,
+        position_token_account,
+        tick_lower_index: -1280,
+        tick_upper_index: 1280,
+    };
+    assert_eq!(get_position(&mut context, &position_fixture).await.liquidity, liquidity);
+    assert_eq!(get_token_balance(&mut context, position_token_account).await, 1);
+    assert_eq!(
+        get_token_balance(&mut context, pool.token_vault_a).await,
+        get_amount_delta_a(sqrt_price, sqrt_price_from_tick_index(1280), liquidity, true).unwrap()
+    );
+    assert_eq!(
+        get_token_balance(&mut context, pool.token_vault_b).await,
+        get_amount_delta_b(sqrt_price_from_tick_index(-1280), sqrt_price, liquidity, true).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_tick_array_bounty() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let payer = context.payer.pubkey();
+    let (tick_array_bounty, _) =
+        Pubkey::find_program_address(&[b"tick_array_bounty", pool.ai_dex_pool.as_ref()], &ai_dex::ID);
+    const REWARD: u64 = 1_000_000;
+
+    let keeper = Keypair::new();
+    let instructions = [
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeTickArrayBounty {
+                ai_dex_config: pool.ai_dex_config,
+                ai_dex_pool: pool.ai_dex_pool,
+                tick_array_bounty,
+                authority: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializeTickArrayBounty { reward_per_tick_array: REWARD },
+        ),
+        ai_dex_instruction(
+            ai_dex::accounts::FundTickArrayBounty {
+                tick_array_bounty,
+                funder: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::FundTickArrayBounty { lamports: REWARD * 3 / 2 },
+        ),
+        solana_sdk::system_instruction::transfer(&payer, &keeper.pubkey(), 1_000_000_000),
+    ];
+    process_instructions(&mut context, &instructions, &[]).await.unwrap();
+
+    let ticks_in_array = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+    let initialize_tick_array = |start_tick_index: i32| {
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeTickArray {
+                ai_dex_pool: pool.ai_dex_pool,
+                funder: keeper.pubkey(),
+                tick_array: tick_array_address(&pool, start_tick_index),
+                system_program: anchor_lang::system_program::ID,
+                tick_array_bounty: Some(tick_array_bounty),
+                tick_array_funder: None,
+            },
+            ai_dex::instruction::InitializeTickArray { start_tick_index },
+        )
+    };
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let tick_array_rent = rent.minimum_balance(ai_dex::state::TickArray::LEN);
+    let bounty_rent = rent.minimum_balance(ai_dex::state::TickArrayBounty::LEN);
+
+    // (start tick index, reward): the tick arrays next to the current price are paid until the
+    // bounty runs out, the others are not
+    for (start_tick_index, reward) in [
+        (ticks_in_array, REWARD),
+        (3 * ticks_in_array, 0),
+        (-ticks_in_array, REWARD / 2),
+        (0, 0),
+    ] {
+        let keeper_balance = context.banks_client.get_balance(keeper.pubkey()).await.unwrap();
+        process_instructions(&mut context, &[initialize_tick_array(start_tick_index)], &[&keeper])
+            .await
+            .unwrap();
+        assert_eq!(
+            context.banks_client.get_balance(keeper.pubkey()).await.unwrap(),
+            keeper_balance - tick_array_rent + reward
+        );
+    }
+    // The bounty keeps its rent
+    assert_eq!(context.banks_client.get_balance(tick_array_bounty).await.unwrap(), bounty_rent);
+}
+
+#[tokio::test]
+async fn test_close_tick_array() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+
+    let keeper = Keypair::new();
+    process_instructions(
+        &mut context,
+        &[solana_sdk::system_instruction::transfer(&owner, &keeper.pubkey(), 1_000_000_000)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let ticks_in_array = TICK_SPACING as i32 * TICK_ARRAY_SIZE;
+    let tick_array_funder = |start_tick_index: i32| {
+        Pubkey::find_program_address(
+            &[b"tick_array_funder", tick_array_address(&pool, start_tick_index).as_ref()],
+            &ai_dex::ID,
+        )
+        .0
+    };
+    let initialize_tick_array = |start_tick_index: i32| {
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeTickArray {
+                ai_dex_pool: pool.ai_dex_pool,
+                funder: keeper.pubkey(),
+                tick_array: tick_array_address(&pool, start_tick_index),
+                system_program: anchor_lang::system_program::ID,
+                tick_array_bounty: None,
+                tick_array_funder: Some(tick_array_funder(start_tick_index)),
+            },
+            ai_dex::instruction::InitializeTickArray { start_tick_index },
+        )
+    };
+    let close_tick_array = |start_tick_index: i32, funder: Pubkey| {
+        ai_dex_instruction(
+            ai_dex::accounts::CloseTickArray {
+                ai_dex_pool: pool.ai_dex_pool,
+                tick_array: tick_array_address(&pool, start_tick_index),
+                tick_array_funder: tick_array_funder(start_tick_index),
+                funder,
+            },
+            ai_dex::instruction::CloseTickArray {},
+        )
+    };
+    let far_start_tick_index = 3 * ticks_in_array;
+    let near_start_tick_index = ticks_in_array;
+    process_instructions(
+        &mut context,
+        &[initialize_tick_array(far_start_tick_index), initialize_tick_array(near_start_tick_index)],
+        &[&keeper],
+    )
+    .await
+    .unwrap();
+
+    // Tick arrays next to the current price stay open for swaps
+    let err = process_instructions(&mut context, &[close_tick_array(near_start_tick_index, keeper.pubkey())], &[&keeper])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::TickArrayNearCurrentPrice.into()))
+    );
+
+    // Only the recorded funder can close the tick array
+    let err = process_instructions(&mut context, &[close_tick_array(far_start_tick_index, owner)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintHasOne.into())
+        )
+    );
+
+    // Tick arrays with initialized ticks cannot be closed
+    let position = open_position(
+        &mut context,
+        &pool,
+        far_start_tick_index + TICK_SPACING as i32,
+        far_start_tick_index + ticks_in_array + TICK_SPACING as i32,
+    )
+    .await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+    let err = process_instructions(&mut context, &[close_tick_array(far_start_tick_index, keeper.pubkey())], &[&keeper])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::TickArrayNotEmpty.into()))
+    );
+
+    // Once empty, the rent of the tick array and of its funder record goes back to the funder
+    decrease_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let refund = rent.minimum_balance(ai_dex::state::TickArray::LEN)
+        + rent.minimum_balance(ai_dex::state::TickArrayFunder::LEN);
+    let keeper_balance = context.banks_client.get_balance(keeper.pubkey()).await.unwrap();
+    process_instructions(&mut context, &[close_tick_array(far_start_tick_index, keeper.pubkey())], &[&keeper])
+        .await
+        .unwrap();
+    assert_eq!(context.banks_client.get_balance(keeper.pubkey()).await.unwrap(), keeper_balance + refund);
+    let tick_array = tick_array_address(&pool, far_start_tick_index);
+    assert!(context.banks_client.get_account(tick_array).await.unwrap().is_none());
+    let tick_array_funder = tick_array_funder(far_start_tick_index);
+    assert!(context.banks_client.get_account(tick_array_funder).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_sync_protocol_stats() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let payer = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, payer, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, payer, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264, 5_632, 11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, 1_000_000, true).await;
+    let protocol_fee_owed_a = get_pool(&mut context, &pool).await.protocol_fee_owed_a;
+    assert!(protocol_fee_owed_a > 0);
+
+    let (pool_revenue_snapshot, _) =
+        Pubkey::find_program_address(&[b"pool_revenue_snapshot", pool.ai_dex_pool.as_ref()], &ai_dex::ID);
+    let (protocol_stats, _) =
+        Pubkey::find_program_address(&[b"protocol_stats", pool.ai_dex_config.as_ref()], &ai_dex::ID);
+    let instructions = [
+        ai_dex_instruction(
+            ai_dex::accounts::InitializePoolRevenueSnapshot {
+                ai_dex_config: pool.ai_dex_config,
+                ai_dex_pool: pool.ai_dex_pool,
+                pool_revenue_snapshot,
+                config_authority: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializePoolRevenueSnapshot {},
+        ),
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeProtocolStats {
+                ai_dex_config: pool.ai_dex_config,
+                protocol_stats,
+                funder: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializeProtocolStats {},
+        ),
+    ];
+    process_instructions(&mut context, &instructions, &[]).await.unwrap();
+
+    let collect_protocol_fees = |pool_revenue_snapshot: Option<Pubkey>| {
+        let mut instruction = ai_dex_instruction(
+            ai_dex::accounts::CollectProtocolFees {
+                ai_dex_config: pool.ai_dex_config,
+                ai_dex_pool: pool.ai_dex_pool,
+                config_authority: payer,
+                token_mint_a: pool.token_mint_a,
+                token_mint_b: pool.token_mint_b,
+                token_vault_a: pool.token_vault_a,
+                token_vault_b: pool.token_vault_b,
+                token_destination_a: token_owner_account_a,
+                token_destination_b: token_owner_account_b,
+                token_program_a: pool.token_program_a,
+                token_program_b: pool.token_program_b,
+                memo_program: anchor_spl::memo::ID,
+            },
+            ai_dex::instruction::CollectProtocolFees {
+                remaining_accounts_info: pool_revenue_snapshot.map(|_| RemainingAccountsInfo {
+                    slices: vec![RemainingAccountsSlice {
+                        accounts_type: AccountsType::PoolRevenueSnapshot,
+                        length: 1,
+                    }],
+                }),
+                amount_a: None,
+                amount_b: None,
+            },
+        );
+        instruction.accounts.extend(pool_revenue_snapshot.map(|address| AccountMeta::new(address, false)));
+        instruction
+    };
+
+    // Once revenue reporting is enabled, collections must record the fees in the snapshot
+    let err = process_instructions(&mut context, &[collect_protocol_fees(None)], &[])
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::InvalidPoolRevenueSnapshot.into()))
+    );
+    process_instructions(&mut context, &[collect_protocol_fees(Some(pool_revenue_snapshot))], &[])
+        .await
+        .unwrap();
+    let snapshot: ai_dex::state::PoolRevenueSnapshot = get_account(&mut context, pool_revenue_snapshot).await;
+    assert_eq!(snapshot.unreported_protocol_fee_a, protocol_fee_owed_a);
+    assert_eq!(snapshot.unreported_protocol_fee_b, 0);
+
+    let mut sync_protocol_stats = ai_dex_instruction(
+        ai_dex::accounts::SyncProtocolStats { ai_dex_config: pool.ai_dex_config, protocol_stats },
+        ai_dex::instruction::SyncProtocolStats {},
+    );
+    sync_protocol_stats.accounts.push(AccountMeta::new(pool_revenue_snapshot, false));
+    process_instructions(&mut context, &[sync_protocol_stats.clone()], &[]).await.unwrap();
+
+    let stats: ai_dex::state::ProtocolStats = get_account(&mut context, protocol_stats).await;
+    assert_eq!(stats.pool_count, 1);
+    assert_eq!(
+        stats.protocol_fees,
+        vec![ai_dex::state::MintProtocolFees { mint: pool.token_mint_a, collected_amount: protocol_fee_owed_a as u128 }]
+    );
+    assert!(stats.last_update_timestamp > 0);
+    let snapshot: ai_dex::state::PoolRevenueSnapshot = get_account(&mut context, pool_revenue_snapshot).await;
+    assert_eq!(snapshot.unreported_protocol_fee_a, 0);
+
+    // Syncing again does not count the fees twice
+    context.warp_to_slot(100).unwrap();
+    process_instructions(&mut context, &[sync_protocol_stats], &[]).await.unwrap();
+    let stats: ai_dex::state::ProtocolStats = get_account(&mut context, protocol_stats).await;
+    assert_eq!(stats.protocol_fees[0].collected_amount, protocol_fee_owed_a as u128);
+}
+
+#[tokio::test]
+async fn test_swap_from_frozen_token_account() {
+    let mut context = start_program_test().await;
+    let mint_config = MintConfig { has_freeze_authority: true, ..MintConfig::token() };
+    let pool = create_pool(&mut context, mint_config, MintConfig::token(), 0).await;
+    let payer = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, payer, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, payer, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264, 5_632, 11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    let freeze_account = anchor_spl::token::spl_token::instruction::freeze_account(
+        &pool.token_program_a,
+        &token_owner_account_a,
+        &pool.token_mint_a,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    process_instructions(&mut context, &[freeze_account], &[]).await.unwrap();
+
+    // The swap fails with a dedicated error before the token program rejects the transfer
+    let instruction = ai_dex_instruction(
+        swap_accounts(&pool, payer, token_owner_account_a, token_owner_account_b, 0, true),
+        ai_dex::instruction::Swap {
+            amount: 1_000_000,
+            other_amount_threshold: 0,
+            sqrt_price_limit: MIN_SQRT_PRICE_X64,
+            amount_specified_is_input: true,
+            a_to_b: true,
+            remaining_accounts_info: None,
+            deadline_timestamp: None,
+            tick_index_limit: None,
+            integrator_fee_rate: None,
+            stop_at_tick_array_end: None,
+            oracle_maximum_age: None,
+        },
+    );
+    let err = process_instructions(&mut context, &[instruction], &[]).await.unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::FrozenAccountError.into()))
+    );
+
+    let thaw_account = anchor_spl::token::spl_token::instruction::thaw_account(
+        &pool.token_program_a,
+        &token_owner_account_a,
+        &pool.token_mint_a,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    process_instructions(&mut context, &[thaw_account], &[]).await.unwrap();
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, 1_000_000, true).await;
+}
+
+#[tokio::test]
+async fn test_position_index_follows_transferred_position() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let new_owner = Keypair::new();
+
+    let mut position_indexes = Vec::new();
+    for index_owner in [owner, new_owner.pubkey()] {
+        let (position_index, _) =
+            Pubkey::find_program_address(&[b"position_index", index_owner.as_ref(), &0u16.to_le_bytes()], &ai_dex::ID);
+        let instruction = ai_dex_instruction(
+            ai_dex::accounts::InitializePositionIndex {
+                owner: index_owner,
+                position_index,
+                funder: owner,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializePositionIndex { page: 0 },
+        );
+        process_instructions(&mut context, &[instruction], &[]).await.unwrap();
+        position_indexes.push(position_index);
+    }
+    let (owner_position_index, new_owner_position_index) = (position_indexes[0], position_indexes[1]);
+
+    create_tick_arrays(&mut context, &pool, &[-1280, 1280]).await;
+    let (position_mint, position, position_token_account) = position_addresses(&pool, owner, 0, -1280, 1280);
+    let open_position = ai_dex_instruction(
+        ai_dex::accounts::OpenPosition {
+            funder: owner,
+            owner,
+            position,
+            position_mint,
+            position_token_account,
+            ai_dex_pool: pool.ai_dex_pool,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            position_index: Some(owner_position_index),
+        },
+        ai_dex::instruction::OpenPosition {
+            position_seed: 0,
+            tick_lower_index: -1280,
+            tick_upper_index: 1280,
+            is_reinvestment_on: false,
+        },
+    );
+    process_instructions(&mut context, &[open_position], &[]).await.unwrap();
+    let page: PositionIndex = get_account(&mut context, owner_position_index).await;
+    assert_eq!(page.positions, vec![position]);
+
+    // Transferring the position token leaves the index untouched until the position is reindexed
+    let new_position_token_account =
+        create_token_account(&mut context, new_owner.pubkey(), position_mint, anchor_spl::token::ID, 0).await;
+    let transfer = anchor_spl::token::spl_token::instruction::transfer(
+        &anchor_spl::token::ID,
+        &position_token_account,
+        &new_position_token_account,
+        &owner,
+        &[],
+        1,
+    )
+    .unwrap();
+    process_instructions(&mut context, &[transfer], &[]).await.unwrap();
+
+    let reindex_position = ai_dex_instruction(
+        ai_dex::accounts::ReindexPosition {
+            position,
+            position_token_account: new_position_token_account,
+            previous_position_index: Some(owner_position_index),
+            position_index: Some(new_owner_position_index),
+        },
+        ai_dex::instruction::ReindexPosition {},
+    );
+    process_instructions(&mut context, &[reindex_position], &[]).await.unwrap();
+    let page: PositionIndex = get_account(&mut context, owner_position_index).await;
+    assert!(page.positions.is_empty());
+    let page: PositionIndex = get_account(&mut context, new_owner_position_index).await;
+    assert_eq!(page.positions, vec![position]);
+
+    // Closing the position removes it from the index of its new owner
+    let close_position = ai_dex_instruction(
+        ai_dex::accounts::ClosePosition {
+            position_authority: new_owner.pubkey(),
+            receiver: new_owner.pubkey(),
+            position,
+            position_mint,
+            position_token_account: new_position_token_account,
+            token_program: anchor_spl::token::ID,
+            position_index: Some(new_owner_position_index),
+        },
+        ai_dex::instruction::ClosePosition {},
+    );
+    process_instructions(&mut context, &[close_position], &[&new_owner]).await.unwrap();
+    let page: PositionIndex = get_account(&mut context, new_owner_position_index).await;
+    assert!(page.positions.is_empty());
+}
+
+#[tokio::test]
+async fn test_decrease_liquidity_below_dust_transfer_thresholds() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let payer = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, payer, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, payer, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    create_tick_arrays(&mut context, &pool, &[-11_264]).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    // A single unit of liquidity rounds down to nothing, which is not transferred at all
+    decrease_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, 1).await;
+    let position_data = get_position(&mut context, &position).await;
+    assert_eq!((position_data.principal_owed_a, position_data.principal_owed_b), (0, 0));
+
+    let instruction = ai_dex_instruction(
+        ai_dex::accounts::SetDustTransferThresholds {
+            ai_dex_config: pool.ai_dex_config,
+            ai_dex_pool: pool.ai_dex_pool,
+            config_authority: payer,
+        },
+        ai_dex::instruction::SetDustTransferThresholds {
+            dust_transfer_threshold_a: 10_000,
+            dust_transfer_threshold_b: 1_000,
+        },
+    );
+    process_instructions(&mut context, &[instruction], &[]).await.unwrap();
+
+    // Withdrawals are rounded down in favor of the pool, then owed below the thresholds
+    let sqrt_price = sqrt_price_from_tick_index(0);
+    let liquidity_delta = 100_000;
+    let expected_amount_a =
+        get_amount_delta_a(sqrt_price, sqrt_price_from_tick_index(1280), liquidity_delta, false).unwrap();
+    let expected_amount_b =
+        get_amount_delta_b(sqrt_price_from_tick_index(-1280), sqrt_price, liquidity_delta, false).unwrap();
+    assert!(expected_amount_a < 10_000 && expected_amount_b >= 1_000);
+
+    let balance_a = get_token_balance(&mut context, token_owner_account_a).await;
+    let balance_b = get_token_balance(&mut context, token_owner_account_b).await;
+    for _ in 0..2 {
+        decrease_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, liquidity_delta)
+            .await;
+    }
+    assert_eq!(get_token_balance(&mut context, token_owner_account_a).await, balance_a);
+    assert_eq!(get_token_balance(&mut context, token_owner_account_b).await, balance_b + 2 * expected_amount_b);
+    let position_data = get_position(&mut context, &position).await;
+    assert_eq!((position_data.principal_owed_a, position_data.principal_owed_b), (2 * expected_amount_a, 0));
+    assert_eq!((position_data.fee_owed_a, position_data.fee_owed_b), (0, 0));
+
+    // The accumulated dust is transferred with the fees of the position
+    collect_fees(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b).await;
+    assert_eq!(get_token_balance(&mut context, token_owner_account_a).await, balance_a + 2 * expected_amount_a);
+}
+
+#[tokio::test]
+async fn test_settle_expired_position() {
+    let mut context = start_program_test().await;
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let end_timestamp = clock.unix_timestamp as u64 + 1_000;
+    let pool = create_temporary_pool(&mut context, end_timestamp).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+
+    // Anyone can settle the position, but only once the liquidity provision window has closed
+    let settler = Keypair::new();
+    let settle_expired_position = ai_dex_instruction(
+        ai_dex::accounts::SettleExpiredPosition {
+            settler: settler.pubkey(),
+            ai_dex_pool: pool.ai_dex_pool,
+            position: position.position,
+            tick_array_lower: tick_array_address(&pool, tick_array_start_index(position.tick_lower_index)),
+            tick_array_upper: tick_array_address(&pool, tick_array_start_index(position.tick_upper_index)),
+        },
+        ai_dex::instruction::SettleExpiredPosition {},
+    );
+    let err = process_instructions(&mut context, std::slice::from_ref(&settle_expired_position), &[&settler])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(ErrorCode::PoolNotExpired.into()))
+    );
+
+    clock.unix_timestamp = end_timestamp as i64 + 1;
+    context.set_sysvar(&clock);
+    process_instructions(&mut context, &[settle_expired_position], &[&settler]).await.unwrap();
+
+    let sqrt_price = sqrt_price_from_tick_index(0);
+    let expected_amount_a = get_amount_delta_a(sqrt_price, sqrt_price_from_tick_index(1280), LIQUIDITY, false).unwrap();
+    let expected_amount_b = get_amount_delta_b(sqrt_price_from_tick_index(-1280), sqrt_price, LIQUIDITY, false).unwrap();
+    let position_data = get_position(&mut context, &position).await;
+    assert_eq!(position_data.liquidity, 0);
+    assert_eq!(position_data.principal_owed_a, expected_amount_a);
+    assert_eq!(position_data.principal_owed_b, expected_amount_b);
+    assert_eq!((position_data.fee_owed_a, position_data.fee_owed_b), (0, 0));
+    assert_eq!({ get_pool(&mut context, &pool).await.liquidity }, 0);
+
+    // The owner receives the settled tokens with the fees of the position
+    let balance_a = get_token_balance(&mut context, token_owner_account_a).await;
+    let balance_b = get_token_balance(&mut context, token_owner_account_b).await;
+    collect_fees(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b).await;
+    assert_eq!(get_token_balance(&mut context, token_owner_account_a).await, balance_a + expected_amount_a);
+    assert_eq!(get_token_balance(&mut context, token_owner_account_b).await, balance_b + expected_amount_b);
+}
+
+async fn quote_swap(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    amount: u64,
+    a_to_b: bool,
+    constant_product: bool,
+) -> ai_dex::instructions::SwapQuoteReturnData {
+    let tick_current_index = get_pool(context, pool).await.tick_current_index;
+    // Only the tick arrays of the swap accounts are used
+    let accounts =
+        swap_accounts(pool, context.payer.pubkey(), Pubkey::default(), Pubkey::default(), tick_current_index, a_to_b);
+    let instruction = ai_dex_instruction(
+        ai_dex::accounts::QuoteSwap {
+            ai_dex_pool: pool.ai_dex_pool,
+            tick_array_0: accounts.tick_array_0,
+            tick_array_1: Some(accounts.tick_array_1),
+            tick_array_2: Some(accounts.tick_array_2),
+        },
+        ai_dex::instruction::QuoteSwap {
+            amount,
+            sqrt_price_limit: if a_to_b { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 },
+            amount_specified_is_input: true,
+            a_to_b,
+            tick_index_limit: None,
+            constant_product: Some(constant_product),
+        },
+    );
+    let return_data = simulate_return_data(context, &[instruction], &[]).await;
+    AnchorDeserialize::deserialize(&mut return_data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn test_quote_swap() {
+    let mut context = start_program_test().await;
+    let pool = create_pool(&mut context, MintConfig::token(), MintConfig::token(), 0).await;
+    let owner = context.payer.pubkey();
+    let token_owner_account_a =
+        create_token_account(&mut context, owner, pool.token_mint_a, pool.token_program_a, TOKEN_BALANCE).await;
+    let token_owner_account_b =
+        create_token_account(&mut context, owner, pool.token_mint_b, pool.token_program_b, TOKEN_BALANCE).await;
+
+    // A position around the current price inside a wider one, initializing the tick arrays a swap
+    // from A to B crosses
+    let position = open_position(&mut context, &pool, -1280, 1280).await;
+    let wide_position = open_position(&mut context, &pool, -11_264, 11_200).await;
+    increase_liquidity(&mut context, &pool, &position, token_owner_account_a, token_owner_account_b, LIQUIDITY).await;
+    increase_liquidity(&mut context, &pool, &wide_position, token_owner_account_a, token_owner_account_b, LIQUIDITY)
+        .await;
+
+    // A swap that does not cross a tick is quoted the same with the constant product quote
+    let quote = quote_swap(&mut context, &pool, 1_000_000, true, false).await;
+    assert_eq!(quote_swap(&mut context, &pool, 1_000_000, true, true).await, quote);
+    assert_eq!(quote.amount_in, 1_000_000);
+    assert_eq!(quote.amount_remaining, 0);
+
+    // The constant product quote stops at the lower tick of the narrow position
+    let amount = 200_000_000;
+    let constant_product_quote = quote_swap(&mut context, &pool, amount, true, true).await;
+    assert_eq!(constant_product_quote.next_sqrt_price, sqrt_price_from_tick_index(-1280));
+    assert!(constant_product_quote.amount_remaining > 0);
+    assert_eq!(constant_product_quote.amount_in + constant_product_quote.amount_remaining, amount);
+
+    // The full quote crosses it and matches the swap
+    let quote = quote_swap(&mut context, &pool, amount, true, false).await;
+    assert_eq!(quote.amount_remaining, 0);
+    assert!(quote.next_sqrt_price < sqrt_price_from_tick_index(-1280));
+    let balance_a = get_token_balance(&mut context, token_owner_account_a).await;
+    let balance_b = get_token_balance(&mut context, token_owner_account_b).await;
+    swap_exact_input(&mut context, &pool, token_owner_account_a, token_owner_account_b, amount, true).await;
+    assert_eq!(get_token_balance(&mut context, token_owner_account_a).await, balance_a - quote.amount_in);
+    assert_eq!(get_token_balance(&mut context, token_owner_account_b).await, balance_b + quote.amount_out);
+    assert_eq!({ get_pool(&mut context, &pool).await.sqrt_price }, quote.next_sqrt_price);
+}
@@ -0,0 +1,765 @@
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::{instruction::Instruction, program_pack::Pack, system_instruction};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas, ZeroCopy};
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id, spl_associated_token_account,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee, transfer_hook, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+
+use ai_dex::math::sqrt_price_from_tick_index;
+use ai_dex::state::{AiDexPool, Position, TickArray, TICK_ARRAY_SIZE};
+
+pub const TICK_SPACING: u16 = 64;
+pub const FEE_RATE: u16 = 3000;
+pub const PROTOCOL_FEE_RATE: u16 = 300;
+/// The config rejects a swap referral reward fee rate equal to its zero default.
+pub const REFERRAL_REWARD_FEE_RATE: u16 = 100;
+
+/// Token program and extensions of a test mint.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MintConfig {
+    pub is_token_2022: bool,
+    /// Transfer fee of a Token-2022 mint, in basis points, with its maximum fee.
+    pub transfer_fee: Option<(u16, u64)>,
+    /// Adds the transfer hook extension to a Token-2022 mint, without a hook program.
+    pub has_transfer_hook: bool,
+    /// Sets the payer as freeze authority of the mint.
+    pub has_freeze_authority: bool,
+}
+
+impl MintConfig {
+    pub fn token() -> Self {
+        Self::default()
+    }
+
+    pub fn token_2022() -> Self {
+        Self { is_token_2022: true, ..Default::default() }
+    }
+
+    pub fn token_2022_with_transfer_fee(transfer_fee_bps: u16, maximum_fee: u64) -> Self {
+        Self {
+            is_token_2022: true,
+            transfer_fee: Some((transfer_fee_bps, maximum_fee)),
+            ..Default::default()
+        }
+    }
+
+    pub fn token_program(&self) -> Pubkey {
+        if self.is_token_2022 {
+            spl_token_2022::ID
+        } else {
+            anchor_spl::token::ID
+        }
+    }
+}
+
+/// A pool initialized in a program test, with the accounts of its mints and vaults.
+pub struct PoolFixture {
+    pub ai_dex_config: Pubkey,
+    pub ai_dex_pool: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_program_a: Pubkey,
+    pub token_program_b: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+}
+
+/// A position opened in a program test by the payer.
+pub struct PositionFixture {
+    pub position: Pubkey,
+    pub position_token_account: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+/// Runs the program natively in the program test. The SPL Token, Token-2022, memo and associated
+/// token programs are loaded by the program test itself.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // The entrypoint requires the accounts to live as long as their data, which the program
+    // test does not guarantee for the slice itself.
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    ai_dex::entry(program_id, accounts, data)
+}
+
+/// Starts a program test running the program natively.
+pub async fn start_program_test() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new("ai_dex", ai_dex::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    program_test.set_compute_max_units(1_400_000);
+    program_test.start_with_context().await
+}
+
+/// Starts a program test running the program built by `cargo build-sbf`, to measure its compute
+/// units. The program is loaded from `SBF_OUT_DIR`, which `cargo test-sbf` sets.
+pub async fn start_sbf_program_test() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new("ai_dex", ai_dex::ID, None);
+    program_test.prefer_bpf(true);
+    program_test.set_compute_max_units(1_400_000);
+    program_test.start_with_context().await
+}
+
+/// Signs the instructions with the payer and the given signers and processes them.
+pub async fn process_instructions(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await?;
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Simulates the instructions signed with the payer and the given signers, and returns the
+/// compute units they consume. Panics if the simulation fails.
+pub async fn simulate_compute_units(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> u64 {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    simulation.simulation_details.unwrap().units_consumed
+}
+
+/// Simulates the instructions signed with the payer and the given signers, and returns the
+/// return data of the last instruction setting it. Panics if the simulation fails.
+pub async fn simulate_return_data(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Vec<u8> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    simulation.simulation_details.unwrap().return_data.unwrap().data
+}
+
+/// Builds an instruction of the program from its accounts and arguments.
+pub fn ai_dex_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ai_dex::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub async fn get_account_data(context: &mut ProgramTestContext, address: Pubkey) -> Vec<u8> {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap_or_else(|| panic!("account {} not found", address))
+        .data
+}
+
+pub async fn get_account<T: AccountDeserialize>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    T::try_deserialize(&mut get_account_data(context, address).await.as_slice()).unwrap()
+}
+
+pub async fn get_zero_copy_account<T: ZeroCopy>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let data = get_account_data(context, address).await;
+    assert_eq!(data[..8], T::DISCRIMINATOR[..]);
+    bytemuck::pod_read_unaligned(&data[8..8 + std::mem::size_of::<T>()])
+}
+
+pub async fn get_pool(context: &mut ProgramTestContext, pool: &PoolFixture) -> AiDexPool {
+    get_zero_copy_account(context, pool.ai_dex_pool).await
+}
+
+pub async fn get_position(context: &mut ProgramTestContext, position: &PositionFixture) -> Position {
+    get_account(context, position.position).await
+}
+
+/// Returns the balance of a token account of either token program.
+pub async fn get_token_balance(context: &mut ProgramTestContext, token_account: Pubkey) -> u64 {
+    let data = get_account_data(context, token_account).await;
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .unwrap()
+        .base
+        .amount
+}
+
+/// Returns the transfer fee withheld in a Token-2022 token account.
+pub async fn get_withheld_transfer_fee(context: &mut ProgramTestContext, token_account: Pubkey) -> u64 {
+    let data = get_account_data(context, token_account).await;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data).unwrap();
+    account
+        .get_extension::<transfer_fee::TransferFeeAmount>()
+        .map(|transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+        .unwrap_or(0)
+}
+
+/// Creates a mint with 6 decimals and the payer as its mint authority.
+pub async fn create_mint(context: &mut ProgramTestContext, mint: &Keypair, config: MintConfig) {
+    let payer = context.payer.pubkey();
+    let token_program = config.token_program();
+
+    let mut extensions = vec![];
+    if config.transfer_fee.is_some() {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    if config.has_transfer_hook {
+        extensions.push(ExtensionType::TransferHook);
+    }
+    let space = if config.is_token_2022 {
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions).unwrap()
+    } else {
+        anchor_spl::token::spl_token::state::Mint::LEN
+    };
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let mut instructions = vec![system_instruction::create_account(
+        &payer,
+        &mint.pubkey(),
+        rent.minimum_balance(space),
+        space as u64,
+        &token_program,
+    )];
+    if let Some((transfer_fee_bps, maximum_fee)) = config.transfer_fee {
+        instructions.push(
+            transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_program,
+                &mint.pubkey(),
+                Some(&payer),
+                Some(&payer),
+                transfer_fee_bps,
+                maximum_fee,
+            )
+            .unwrap(),
+        );
+    }
+    if config.has_transfer_hook {
+        instructions.push(
+            transfer_hook::instruction::initialize(&token_program, &mint.pubkey(), Some(payer), None).unwrap(),
+        );
+    }
+    instructions.push(
+        spl_token_2022::instruction::initialize_mint2(
+            &token_program,
+            &mint.pubkey(),
+            &payer,
+            config.has_freeze_authority.then_some(&payer),
+            6,
+        )
+        .unwrap(),
+    );
+
+    process_instructions(context, &instructions, &[mint]).await.unwrap();
+}
+
+/// Creates the associated token account of the owner for a mint and mints the amount to it.
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    owner: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let token_account = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+
+    let mut instructions = vec![
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            &owner,
+            &mint,
+            &token_program,
+        ),
+    ];
+    if amount > 0 {
+        instructions.push(
+            spl_token_2022::instruction::mint_to(&token_program, &mint, &token_account, &payer, &[], amount)
+                .unwrap(),
+        );
+    }
+
+    process_instructions(context, &instructions, &[]).await.unwrap();
+    token_account
+}
+
+/// Creates the super admin, a config and a fee tier with the payer as every authority, and a pool
+/// of two new mints priced at the given tick.
+pub async fn create_pool(
+    context: &mut ProgramTestContext,
+    mint_config_a: MintConfig,
+    mint_config_b: MintConfig,
+    initial_tick_index: i32,
+) -> PoolFixture {
+    // The pool requires token_mint_a < token_mint_b
+    let mut mints = [Keypair::new(), Keypair::new()];
+    mints.sort_by_key(|mint| mint.pubkey());
+    let [mint_a, mint_b] = mints;
+    create_mint(context, &mint_a, mint_config_a).await;
+    create_mint(context, &mint_b, mint_config_b).await;
+
+    let ai_dex_config = create_config(context).await;
+    create_pool_with_mints(
+        context,
+        ai_dex_config,
+        (mint_a.pubkey(), mint_config_a),
+        (mint_b.pubkey(), mint_config_b),
+        initial_tick_index,
+    )
+    .await
+}
+
+/// Creates the super admin, a config of the payer and its fee tier, returning the config.
+pub async fn create_config(context: &mut ProgramTestContext) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let (super_admin_account, _) = Pubkey::find_program_address(&[b"super-admin"], &ai_dex::ID);
+    let (ai_dex_config, _) = Pubkey::find_program_address(
+        &[b"config", payer.as_ref(), PROTOCOL_FEE_RATE.to_string().as_bytes()],
+        &ai_dex::ID,
+    );
+    let (fee_tier, _) = fee_tier_address(ai_dex_config);
+
+    let instructions = [
+        ai_dex_instruction(
+            ai_dex::accounts::CreateSuperAdmin {
+                super_admin_account,
+                funder: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::CreateSuperAdmin { super_admin: payer },
+        ),
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeConfig {
+                super_admin_account,
+                super_admin_authority: payer,
+                config: ai_dex_config,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializeConfig {
+                config_authority: payer,
+                default_protocol_fee_rate: PROTOCOL_FEE_RATE,
+                default_referral_reward_fee_rate: REFERRAL_REWARD_FEE_RATE,
+            },
+        ),
+        ai_dex_instruction(
+            ai_dex::accounts::InitializeFeeTier {
+                config: ai_dex_config,
+                fee_tier,
+                funder: payer,
+                system_program: anchor_lang::system_program::ID,
+            },
+            ai_dex::instruction::InitializeFeeTier {
+                tick_spacing: TICK_SPACING,
+                default_fee_rate: FEE_RATE,
+            },
+        ),
+    ];
+    process_instructions(context, &instructions, &[]).await.unwrap();
+
+    ai_dex_config
+}
+
+fn fee_tier_address(ai_dex_config: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"fee_tier", ai_dex_config.as_ref(), &TICK_SPACING.to_le_bytes()],
+        &ai_dex::ID,
+    )
+}
+
+/// Initializes a pool of the config for existing mints, with `token_mint_a < token_mint_b`.
+pub async fn create_pool_with_mints(
+    context: &mut ProgramTestContext,
+    ai_dex_config: Pubkey,
+    mint_a: (Pubkey, MintConfig),
+    mint_b: (Pubkey, MintConfig),
+    initial_tick_index: i32,
+) -> PoolFixture {
+    let pool = initialize_pool_step_1(context, ai_dex_config, mint_a, mint_b, initial_tick_index).await;
+    initialize_pool_step_2(context, &pool, None).await;
+
+    pool
+}
+
+/// Creates the super admin, a config and a fee tier with the payer as every authority, and a
+/// temporary pool of two new mints priced at tick 0, whose liquidity provision and swap windows
+/// end at `end_timestamp`.
+pub async fn create_temporary_pool(context: &mut ProgramTestContext, end_timestamp: u64) -> PoolFixture {
+    let mut mints = [Keypair::new(), Keypair::new()];
+    mints.sort_by_key(|mint| mint.pubkey());
+    let [mint_a, mint_b] = mints;
+    create_mint(context, &mint_a, MintConfig::token()).await;
+    create_mint(context, &mint_b, MintConfig::token()).await;
+
+    let ai_dex_config = create_config(context).await;
+    let pool = initialize_pool_step_1_of_kind(
+        context,
+        ai_dex_config,
+        (mint_a.pubkey(), MintConfig::token()),
+        (mint_b.pubkey(), MintConfig::token()),
+        0,
+        true,
+    )
+    .await;
+    initialize_pool_step_2(context, &pool, Some(end_timestamp)).await;
+
+    pool
+}
+
+/// Performs the second step of the initialization of a pool, with the end of the windows of a
+/// temporary pool, which open at timestamp 0.
+async fn initialize_pool_step_2(context: &mut ProgramTestContext, pool: &PoolFixture, end_timestamp: Option<u64>) {
+    let payer = context.payer.pubkey();
+    let start_timestamp = end_timestamp.map(|_| 0);
+    let instruction = ai_dex_instruction(
+        ai_dex::accounts::InitializePoolStep2 {
+            ai_dex_pool: pool.ai_dex_pool,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            funder: payer,
+            token_vault_a: pool.token_vault_a,
+            token_vault_b: pool.token_vault_b,
+            token_program_a: pool.token_program_a,
+            token_program_b: pool.token_program_b,
+            system_program: anchor_lang::system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        },
+        ai_dex::instruction::InitializePoolStep2 {
+            tick_spacing: TICK_SPACING,
+            start_timestamp_lp: start_timestamp,
+            end_timestamp_lp: end_timestamp,
+            start_timestamp_swap: start_timestamp,
+            end_timestamp_swap: end_timestamp,
+            lbp_fee_schedule: None,
+        },
+    );
+    process_instructions(context, &[instruction], &[]).await.unwrap();
+}
+
+/// Performs the first step of the initialization of a pool of the config for existing mints,
+/// returning the pool with the addresses of its uninitialized token vaults.
+pub async fn initialize_pool_step_1(
+    context: &mut ProgramTestContext,
+    ai_dex_config: Pubkey,
+    (token_mint_a, mint_config_a): (Pubkey, MintConfig),
+    (token_mint_b, mint_config_b): (Pubkey, MintConfig),
+    initial_tick_index: i32,
+) -> PoolFixture {
+    initialize_pool_step_1_of_kind(
+        context,
+        ai_dex_config,
+        (token_mint_a, mint_config_a),
+        (token_mint_b, mint_config_b),
+        initial_tick_index,
+        false,
+    )
+    .await
+}
+
+async fn initialize_pool_step_1_of_kind(
+    context: &mut ProgramTestContext,
+    ai_dex_config: Pubkey,
+    (token_mint_a, mint_config_a): (Pubkey, MintConfig),
+    (token_mint_b, mint_config_b): (Pubkey, MintConfig),
+    initial_tick_index: i32,
+    is_temporary_pool: bool,
+) -> PoolFixture {
+    let payer = context.payer.pubkey();
+    let (fee_tier, _) = fee_tier_address(ai_dex_config);
+    let (ai_dex_pool, _) = Pubkey::find_program_address(
+        &[
+            b"ai_dex",
+            ai_dex_config.as_ref(),
+            token_mint_a.as_ref(),
+            token_mint_b.as_ref(),
+            &TICK_SPACING.to_le_bytes(),
+        ],
+        &ai_dex::ID,
+    );
+    let (token_vault_a, _) = Pubkey::find_program_address(
+        &[b"token_vault_a", ai_dex_pool.as_ref(), TICK_SPACING.to_string().as_bytes()],
+        &ai_dex::ID,
+    );
+    let (token_vault_b, _) = Pubkey::find_program_address(
+        &[b"token_vault_b", ai_dex_pool.as_ref(), TICK_SPACING.to_string().as_bytes()],
+        &ai_dex::ID,
+    );
+
+    let instructions = [
+        ai_dex_instruction(
+            ai_dex::accounts::InitializePoolStep1 {
+                ai_dex_config,
+                token_mint_a: token_mint_a,
+                token_mint_b: token_mint_b,
+                funder: payer,
+                ai_dex_pool,
+                fee_tier,
+                oracle_account: None,
+                price_update: None,
+                system_program: anchor_lang::system_program::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+                oracle_price_feed_default: None,
+            },
+            ai_dex::instruction::InitializePoolStep1 {
+                tick_spacing: TICK_SPACING,
+                is_oracle_pool: false,
+                is_temporary_pool,
+                initial_sqrt_price: Some(sqrt_price_from_tick_index(initial_tick_index)),
+                price_feed_id: None,
+                maximum_age: None,
+                is_full_range_only: None,
+            },
+        ),
+    ];
+    process_instructions(context, &instructions, &[]).await.unwrap();
+
+    PoolFixture {
+        ai_dex_config,
+        ai_dex_pool,
+        token_mint_a: token_mint_a,
+        token_mint_b: token_mint_b,
+        token_program_a: mint_config_a.token_program(),
+        token_program_b: mint_config_b.token_program(),
+        token_vault_a,
+        token_vault_b,
+    }
+}
+
+/// Returns the start tick index of the tick array containing a tick of the pool.
+pub fn tick_array_start_index(tick_index: i32) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+    tick_index.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+pub fn tick_array_address(pool: &PoolFixture, start_tick_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"tick_array", pool.ai_dex_pool.as_ref(), start_tick_index.to_string().as_bytes()],
+        &ai_dex::ID,
+    )
+    .0
+}
+
+/// Initializes the tick arrays of the pool containing the given ticks, unless they exist already.
+pub async fn create_tick_arrays(context: &mut ProgramTestContext, pool: &PoolFixture, tick_indexes: &[i32]) {
+    let mut start_tick_indexes: Vec<i32> = tick_indexes.iter().map(|tick_index| tick_array_start_index(*tick_index)).collect();
+    start_tick_indexes.sort();
+    start_tick_indexes.dedup();
+    let mut missing_start_tick_indexes = Vec::with_capacity(start_tick_indexes.len());
+    for start_tick_index in start_tick_indexes {
+        let tick_array = tick_array_address(pool, start_tick_index);
+        if context.banks_client.get_account(tick_array).await.unwrap().is_none() {
+            missing_start_tick_indexes.push(start_tick_index);
+        }
+    }
+
+    let instructions: Vec<Instruction> = missing_start_tick_indexes
+        .into_iter()
+        .map(|start_tick_index| {
+            ai_dex_instruction(
+                ai_dex::accounts::InitializeTickArray {
+                    ai_dex_pool: pool.ai_dex_pool,
+                    funder: context.payer.pubkey(),
+                    tick_array: tick_array_address(pool, start_tick_index),
+                    system_program: anchor_lang::system_program::ID,
+                    tick_array_bounty: None,
+                    tick_array_funder: None,
+                },
+                ai_dex::instruction::InitializeTickArray { start_tick_index },
+            )
+        })
+        .collect();
+    for instruction in instructions {
+        process_instructions(context, &[instruction], &[]).await.unwrap();
+    }
+}
+
+/// Opens a position of the payer and initializes the tick arrays of its bounds.
+pub async fn open_position(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> PositionFixture {
+    open_position_with_reinvestment(context, pool, tick_lower_index, tick_upper_index, false).await
+}
+
+/// Addresses of the mint, the account and the token account of a position.
+pub fn position_addresses(
+    pool: &PoolFixture,
+    owner: Pubkey,
+    position_seed: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> (Pubkey, Pubkey, Pubkey) {
+    let (position_mint, _) = Pubkey::find_program_address(
+        &[
+            b"position_mint",
+            pool.ai_dex_pool.as_ref(),
+            owner.as_ref(),
+            position_seed.to_string().as_bytes(),
+            tick_lower_index.to_string().as_bytes(),
+            tick_upper_index.to_string().as_bytes(),
+        ],
+        &ai_dex::ID,
+    );
+    let (position, _) = Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &ai_dex::ID);
+    let position_token_account =
+        get_associated_token_address_with_program_id(&owner, &position_mint, &anchor_spl::token::ID);
+    (position_mint, position, position_token_account)
+}
+
+/// Opens a position of the payer, with or without the reinvestment of its fees, and initializes
+/// the tick arrays of its bounds.
+pub async fn open_position_with_reinvestment(
+    context: &mut ProgramTestContext,
+    pool: &PoolFixture,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    is_reinvestment_on: bool,
+) -> PositionFixture {
+    create_tick_arrays(context, pool, &[tick_lower_index, tick_upper_index]).await;
+
+    let owner = context.payer.pubkey();
+    let position_seed = 0u64;
+    let (position_mint, position, position_token_account) =
+        position_addresses(pool, owner, position_seed, tick_lower_index, tick_upper_index);
+
+    let instruction = ai_dex_instruction(
+        ai_dex::accounts::OpenPosition {
+            funder: owner,
+            owner,
+            position,
+            position_mint,
+            position_token_account,
+            ai_dex_pool: pool.ai_dex_pool,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            position_index: None,
+        },
+        ai_dex::instruction::OpenPosition {
+            position_seed,
+            tick_lower_index,
+            tick_upper_index,
+            is_reinvestment_on,
+        },
+    );
+    process_instructions(context, &[instruction], &[]).await.unwrap();
+
+    PositionFixture {
+        position,
+        position_token_account,
+        tick_lower_index,
+        tick_upper_index,
+    }
+}
+
+/// Accounts of `increase_liquidity` and `decrease_liquidity` for a position of the payer.
+pub fn modify_liquidity_accounts(
+    context: &ProgramTestContext,
+    pool: &PoolFixture,
+    position: &PositionFixture,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+) -> ai_dex::accounts::ModifyLiquidity {
+    ai_dex::accounts::ModifyLiquidity {
+        ai_dex_pool: pool.ai_dex_pool,
+        token_program_a: pool.token_program_a,
+        token_program_b: pool.token_program_b,
+        memo_program: anchor_spl::memo::ID,
+        position_authority: context.payer.pubkey(),
+        position: position.position,
+        position_token_account: position.position_token_account,
+        token_mint_a: pool.token_mint_a,
+        token_mint_b: pool.token_mint_b,
+        token_owner_account_a,
+        token_owner_account_b,
+        token_vault_a: pool.token_vault_a,
+        token_vault_b: pool.token_vault_b,
+        tick_array_lower: tick_array_address(pool, tick_array_start_index(position.tick_lower_index)),
+        tick_array_upper: tick_array_address(pool, tick_array_start_index(position.tick_upper_index)),
+        oracle_account: None,
+        price_update: None,
+        lp_referral: None,
+    }
+}
+
+/// Accounts of `swap` for the payer, with the three tick arrays in the direction of the swap
+/// starting at the current tick of the pool.
+pub fn swap_accounts(
+    pool: &PoolFixture,
+    token_authority: Pubkey,
+    token_owner_account_a: Pubkey,
+    token_owner_account_b: Pubkey,
+    tick_current_index: i32,
+    a_to_b: bool,
+) -> ai_dex::accounts::Swap {
+    let ticks_in_array = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+    // A b to a swap searches from the tick after the current one, which may start the next array
+    let start_tick_index = if a_to_b {
+        tick_array_start_index(tick_current_index)
+    } else {
+        tick_array_start_index(tick_current_index + TICK_SPACING as i32)
+    };
+    let tick_arrays: Vec<Pubkey> = (0..3)
+        .map(|offset| {
+            let offset = if a_to_b { -offset } else { offset };
+            tick_array_address(pool, start_tick_index + offset * ticks_in_array)
+        })
+        .collect();
+
+    ai_dex::accounts::Swap {
+        token_program_a: pool.token_program_a,
+        token_program_b: pool.token_program_b,
+        memo_program: anchor_spl::memo::ID,
+        token_authority,
+        ai_dex_pool: pool.ai_dex_pool,
+        token_mint_a: pool.token_mint_a,
+        token_mint_b: pool.token_mint_b,
+        token_owner_account_a,
+        token_vault_a: pool.token_vault_a,
+        token_owner_account_b,
+        token_vault_b: pool.token_vault_b,
+        tick_array_0: tick_arrays[0],
+        tick_array_1: tick_arrays[1],
+        tick_array_2: tick_arrays[2],
+        oracle_account: None,
+        price_update: None,
+        swap_referral: None,
+        swap_referral_ata_a: None,
+        swap_referral_ata_b: None,
+        ai_dex_config: pool.ai_dex_config,
+        integrator_fee_account: None,
+    }
+}
+
+/// Deserializes a tick array of the pool, for assertions on its ticks.
+pub async fn get_tick_array(context: &mut ProgramTestContext, pool: &PoolFixture, start_tick_index: i32) -> TickArray {
+    get_zero_copy_account(context, tick_array_address(pool, start_tick_index)).await
+}
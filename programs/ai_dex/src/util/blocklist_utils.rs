@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, Blocklist};
+
+/// Checks the addresses of an instruction against the blocklist of the config, if the pool has
+/// the blocklist enabled.
+///
+/// The blocklist is passed in the `Blocklists` slice of the remaining accounts.
+///
+/// # Arguments
+///
+/// * `blocklists` - The `Blocklists` slice of the remaining accounts, if any.
+/// * `ai_dex_pool` - The pool of the instruction.
+/// * `addresses` - The addresses interacting with the pool, such as the signing authority and
+///   the owners of its token accounts.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidBlocklist` - If the blocklist of the config of the pool is not passed.
+/// * `ErrorCode::AddressBlocked` - If any of the addresses is blocked.
+pub fn check_blocklist(
+    blocklists: &Option<Vec<AccountInfo>>,
+    ai_dex_pool: &AiDexPool,
+    addresses: &[Pubkey],
+) -> Result<()> {
+    if ai_dex_pool.is_blocklist_enabled == 0 {
+        return Ok(());
+    }
+
+    // The blocklist is a PDA of the config, so there is a single account of the program
+    // deserializing to a blocklist of this config.
+    let blocklist = blocklists
+        .iter()
+        .flatten()
+        .filter(|account_info| account_info.owner == &crate::ID)
+        .find_map(|account_info| {
+            Blocklist::try_deserialize(&mut &account_info.try_borrow_data().ok()?[..])
+                .ok()
+                .filter(|blocklist| blocklist.ai_dex_config == ai_dex_pool.ai_dex_config)
+        })
+        .ok_or(ErrorCode::InvalidBlocklist)?;
+
+    if addresses.iter().any(|address| blocklist.is_blocked(address)) {
+        return Err(ErrorCode::AddressBlocked.into());
+    }
+
+    Ok(())
+}
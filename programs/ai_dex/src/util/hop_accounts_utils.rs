@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::{load_tick_array, AiDexPool};
+
+/// Accounts of one hop of a compact two-hop swap, passed as a `HopAccountsOne` or
+/// `HopAccountsTwo` slice of the remaining accounts.
+///
+/// The slice lists, in order:
+/// - the pool of the hop,
+/// - the vault of the pool receiving the input token of the hop,
+/// - the vault of the pool sending the output token of the hop,
+/// - one or more tick arrays of the pool, in the direction of the swap.
+///
+/// Only the tick arrays a swap crosses need to be passed, without padding the sequence to three.
+pub struct HopAccounts<'info> {
+    pub ai_dex_pool: AccountLoader<'info, AiDexPool>,
+    pub token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_vault_output: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub tick_arrays: Vec<AccountInfo<'info>>,
+}
+
+impl<'info> HopAccounts<'info> {
+    /// Number of accounts of a hop with a single tick array.
+    pub const MIN_LEN: usize = 4;
+
+    /// Loads the accounts of a hop from its remaining accounts slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `hop_accounts` - The slice of the hop, if passed, borrowed from the instruction accounts.
+    /// * `a_to_b` - The direction of the swap of the hop.
+    ///
+    /// # Errors
+    ///
+    /// * `ErrorCode::InvalidHopAccounts` - If the slice is missing or too short, or a tick array belongs to another pool.
+    /// * `ErrorCode::InvalidVault` - If a vault is not the vault of the pool for the direction of the hop.
+    pub fn load(hop_accounts: Option<&'info [AccountInfo<'info>]>, a_to_b: bool) -> Result<Self> {
+        let hop_accounts = match hop_accounts {
+            Some(hop_accounts) if hop_accounts.len() >= Self::MIN_LEN => hop_accounts,
+            _ => return Err(ErrorCode::InvalidHopAccounts.into()),
+        };
+
+        let ai_dex_pool = AccountLoader::<AiDexPool>::try_from(&hop_accounts[0])?;
+        let token_vault_input = Box::new(InterfaceAccount::<TokenAccount>::try_from(&hop_accounts[1])?);
+        let token_vault_output = Box::new(InterfaceAccount::<TokenAccount>::try_from(&hop_accounts[2])?);
+        let tick_arrays = hop_accounts[3..].to_vec();
+
+        {
+            let ai_dex_pool_data = ai_dex_pool.load()?;
+            if token_vault_input.key() != ai_dex_pool_data.input_token_vault(a_to_b)
+                || token_vault_output.key() != ai_dex_pool_data.output_token_vault(a_to_b)
+            {
+                return Err(ErrorCode::InvalidVault.into());
+            }
+        }
+        for tick_array in &tick_arrays {
+            if load_tick_array(tick_array)?.ai_dex_pool() != ai_dex_pool.key() {
+                return Err(ErrorCode::InvalidHopAccounts.into());
+            }
+        }
+
+        Ok(Self {
+            ai_dex_pool,
+            token_vault_input,
+            token_vault_output,
+            tick_arrays,
+        })
+    }
+}
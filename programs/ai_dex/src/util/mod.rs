@@ -5,6 +5,14 @@ pub mod token;
 pub mod util;
 pub mod referral_utils;
 pub mod reinvestments_utils;
+pub mod pool_snapshot_utils;
+pub mod swap_rate_limiter_utils;
+pub mod swap_callback_utils;
+pub mod blocklist_utils;
+pub mod pool_fee_analytics_utils;
+pub mod pool_revenue_snapshot_utils;
+pub mod hop_accounts_utils;
+pub mod program_version_utils;
 
 pub use remaining_accounts_utils::*;
 pub use swap_tick_sequence::*;
@@ -13,6 +21,14 @@ pub use token::*;
 pub use util::*;
 pub use referral_utils::*;
 pub use reinvestments_utils::*;
+pub use pool_snapshot_utils::*;
+pub use swap_rate_limiter_utils::*;
+pub use swap_callback_utils::*;
+pub use blocklist_utils::*;
+pub use pool_fee_analytics_utils::*;
+pub use pool_revenue_snapshot_utils::*;
+pub use hop_accounts_utils::*;
+pub use program_version_utils::*;
 
 #[cfg(test)]
 pub mod test_utils;
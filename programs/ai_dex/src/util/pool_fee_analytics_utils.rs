@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::orchestrator::swap_orchestrator::PostSwapUpdate;
+use crate::state::{AiDexPool, PoolFeeAnalytics};
+
+/// Records the fee of a swap in the fee analytics account of the pool, if the pool has fee
+/// analytics enabled.
+///
+/// The account is passed in the `PoolFeeAnalytics` slice of the remaining accounts. Must be
+/// called before the pool is updated with the swap, while its price is still the oracle price
+/// the swap was quoted at.
+///
+/// # Arguments
+///
+/// * `pool_fee_analytics` - The `PoolFeeAnalytics` slice of the remaining accounts, if any.
+/// * `ai_dex_pool` - The pool of the swap.
+/// * `swap_update` - The result of the swap.
+/// * `a_to_b` - The direction of the swap.
+/// * `timestamp` - The current timestamp.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidPoolFeeAnalytics` - If the fee analytics account of the pool is not passed as a writable account.
+pub fn record_swap_fee_analytics(
+    pool_fee_analytics: &Option<Vec<AccountInfo>>,
+    ai_dex_pool: &AccountLoader<AiDexPool>,
+    swap_update: &PostSwapUpdate,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<()> {
+    let ai_dex_pool_data = ai_dex_pool.load()?;
+    if ai_dex_pool_data.is_fee_analytics_enabled == 0 {
+        return Ok(());
+    }
+
+    let (pool_fee_analytics_info, mut pool_fee_analytics) = pool_fee_analytics
+        .iter()
+        .flatten()
+        .filter(|account_info| account_info.owner == &crate::ID && account_info.is_writable)
+        .find_map(|account_info| {
+            let pool_fee_analytics = PoolFeeAnalytics::try_deserialize(&mut &account_info.try_borrow_data().ok()?[..])
+                .ok()
+                .filter(|pool_fee_analytics| pool_fee_analytics.ai_dex_pool == ai_dex_pool.key())?;
+            Some((account_info, pool_fee_analytics))
+        })
+        .ok_or(ErrorCode::InvalidPoolFeeAnalytics)?;
+
+    pool_fee_analytics.record_swap_fee(swap_update.fee_amount, a_to_b, ai_dex_pool_data.sqrt_price, timestamp);
+    pool_fee_analytics.try_serialize(&mut &mut pool_fee_analytics_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, PoolRevenueSnapshot};
+
+/// Records protocol fees collected from a pool in the revenue snapshot of the pool, if the pool
+/// has revenue reporting enabled.
+///
+/// The account is passed in the `PoolRevenueSnapshot` slice of the remaining accounts.
+///
+/// # Arguments
+///
+/// * `pool_revenue_snapshot` - The `PoolRevenueSnapshot` slice of the remaining accounts, if any.
+/// * `ai_dex_pool` - The pool the protocol fees were collected from.
+/// * `collected_amount_a` - The protocol fees collected in token A.
+/// * `collected_amount_b` - The protocol fees collected in token B.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidPoolRevenueSnapshot` - If the revenue snapshot of the pool is not passed as a writable account.
+pub fn record_collected_protocol_fees(
+    pool_revenue_snapshot: &Option<Vec<AccountInfo>>,
+    ai_dex_pool: &AccountLoader<AiDexPool>,
+    collected_amount_a: u64,
+    collected_amount_b: u64,
+) -> Result<()> {
+    if ai_dex_pool.load()?.is_revenue_reporting_enabled == 0 {
+        return Ok(());
+    }
+
+    let (pool_revenue_snapshot_info, mut pool_revenue_snapshot) = pool_revenue_snapshot
+        .iter()
+        .flatten()
+        .filter(|account_info| account_info.owner == &crate::ID && account_info.is_writable)
+        .find_map(|account_info| {
+            let pool_revenue_snapshot = PoolRevenueSnapshot::try_deserialize(&mut &account_info.try_borrow_data().ok()?[..])
+                .ok()
+                .filter(|pool_revenue_snapshot| pool_revenue_snapshot.ai_dex_pool == ai_dex_pool.key())?;
+            Some((account_info, pool_revenue_snapshot))
+        })
+        .ok_or(ErrorCode::InvalidPoolRevenueSnapshot)?;
+
+    pool_revenue_snapshot.record_collected_protocol_fees(collected_amount_a, collected_amount_b);
+    pool_revenue_snapshot.try_serialize(&mut &mut pool_revenue_snapshot_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
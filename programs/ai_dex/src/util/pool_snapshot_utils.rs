@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, PoolSnapshot};
+use crate::util::to_timestamp_u64;
+
+/// Refreshes the pool snapshots passed in the remaining accounts of an instruction.
+///
+/// Each snapshot must belong to one of the pools of the instruction, and is refreshed from the
+/// current state of that pool. Call it once the pools are updated.
+///
+/// # Arguments
+///
+/// * `pool_snapshots` - The `PoolSnapshots` slice of the remaining accounts, if any.
+/// * `ai_dex_pools` - The pools mutated by the instruction.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidPoolSnapshot` - If an account is not a writable snapshot of one of the pools.
+pub fn refresh_pool_snapshots(
+    pool_snapshots: &Option<Vec<AccountInfo>>,
+    ai_dex_pools: &[&AccountLoader<AiDexPool>],
+) -> Result<()> {
+    let Some(pool_snapshots) = pool_snapshots else {
+        return Ok(());
+    };
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    for pool_snapshot_info in pool_snapshots {
+        if pool_snapshot_info.owner != &crate::ID || !pool_snapshot_info.is_writable {
+            return Err(ErrorCode::InvalidPoolSnapshot.into());
+        }
+        let mut pool_snapshot = PoolSnapshot::try_deserialize(&mut &pool_snapshot_info.try_borrow_data()?[..])?;
+        let ai_dex_pool = ai_dex_pools
+            .iter()
+            .find(|ai_dex_pool| ai_dex_pool.key() == pool_snapshot.ai_dex_pool)
+            .ok_or(ErrorCode::InvalidPoolSnapshot)?;
+
+        pool_snapshot.refresh(&*ai_dex_pool.load()?, clock.slot, timestamp);
+        pool_snapshot.try_serialize(&mut &mut pool_snapshot_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
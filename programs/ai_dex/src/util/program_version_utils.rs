@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EVENT_VERSION;
+use crate::state::AiDexConfig;
+
+/// Emitted whenever a config is initialized or updated, so indexers know which program version
+/// and event layout version produce the events of the config from then on.
+#[event]
+pub struct ProgramVersionEvent {
+    pub event_version: u8,
+    pub ai_dex_config: Pubkey,
+    pub program_version: String,
+    pub config_version: u8,
+}
+
+/// Emits the `ProgramVersionEvent` of a config, after it was initialized or updated.
+pub fn emit_program_version_event(ai_dex_config: &Account<AiDexConfig>) {
+    emit!(ProgramVersionEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_config: ai_dex_config.key(),
+        program_version: env!("CARGO_PKG_VERSION").to_string(),
+        config_version: ai_dex_config.version,
+    });
+}
@@ -4,15 +4,17 @@ use anchor_spl::{
     memo::Memo
 };
 use anchor_spl::token_interface::{Mint as InterfaceMint, TokenAccount as InterfaceTokenAccount, TokenInterface};
+use crate::constants::EVENT_VERSION;
 use crate::{
     constants::transfer_memo,
-    state::{AiDexPool, SwapReferral},
+    state::{AiDexPool, ReferralFeeEscrow, SwapReferral},
     errors::ErrorCode,
 };
 use super::transfer_from_vault_to_owner;
 
 #[event]
 pub struct TransferReferralFeeEvent {
+    pub event_version: u8,
     pub ai_dex_pool: Pubkey,
     pub swap_referral: Pubkey,
     pub amount: u64,
@@ -23,6 +25,16 @@ pub struct TransferReferralFeeEvent {
     pub swap_referral_ata: Pubkey,
 }
 
+#[event]
+pub struct ReferralFeeEscrowedEvent {
+    pub event_version: u8,
+    pub ai_dex_pool: Pubkey,
+    pub swap_referral: Pubkey,
+    pub referral_fee_escrow: Pubkey,
+    pub amount: u64,
+    pub a_to_b: bool,
+}
+
 pub fn transfer_referral_fee<'info>(
     swap_referral: &Account<SwapReferral>,
     swap_referral_ata_a: Option<&InterfaceAccount<'info, InterfaceTokenAccount>>,
@@ -96,6 +108,7 @@ pub fn transfer_referral_fee<'info>(
 
     // Emit the event
     emit!(TransferReferralFeeEvent {
+        event_version: EVENT_VERSION,
         ai_dex_pool: ai_dex_pool.key(),
         swap_referral: swap_referral.key(),
         amount,
@@ -107,4 +120,53 @@ pub fn transfer_referral_fee<'info>(
     });
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Accrues the referral fee of a swap to the escrow of the referral on the pool, if the escrow
+/// is passed in the `ReferralFeeEscrows` slice of the remaining accounts.
+///
+/// The fee stays in the pool vault until the referrer collects it, so the swap needs neither the
+/// transfer nor the referral token account.
+///
+/// # Returns
+///
+/// Whether the fee was escrowed. Otherwise the caller transfers it with `transfer_referral_fee`.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidReferralFeeEscrow` - If an escrow passed is not writable, or is not the
+///   escrow of a referral on a pool.
+pub fn escrow_referral_fee(
+    referral_fee_escrows: &Option<Vec<AccountInfo>>,
+    swap_referral: &Account<SwapReferral>,
+    ai_dex_pool: &AccountLoader<AiDexPool>,
+    amount: u64,
+    a_to_b: bool,
+) -> Result<bool> {
+    for account_info in referral_fee_escrows.iter().flatten() {
+        if account_info.owner != &crate::ID || !account_info.is_writable {
+            return Err(ErrorCode::InvalidReferralFeeEscrow.into());
+        }
+        let mut referral_fee_escrow = ReferralFeeEscrow::try_deserialize(&mut &account_info.try_borrow_data()?[..])
+            .map_err(|_| ErrorCode::InvalidReferralFeeEscrow)?;
+        if referral_fee_escrow.swap_referral != swap_referral.key()
+            || referral_fee_escrow.ai_dex_pool != ai_dex_pool.key()
+        {
+            continue;
+        }
+
+        referral_fee_escrow.accrue_fee(amount, a_to_b);
+        referral_fee_escrow.try_serialize(&mut &mut account_info.try_borrow_mut_data()?[..])?;
+
+        emit!(ReferralFeeEscrowedEvent {
+            event_version: EVENT_VERSION,
+            ai_dex_pool: ai_dex_pool.key(),
+            swap_referral: swap_referral.key(),
+            referral_fee_escrow: account_info.key(),
+            amount,
+            a_to_b,
+        });
+        return Ok(true);
+    }
+    Ok(false)
+}
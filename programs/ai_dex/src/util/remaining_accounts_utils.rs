@@ -10,6 +10,19 @@ pub enum AccountsType {
     TransferHookIntermediate,
     TransferHookOutput,
     TransferHookReferralFee,
+    PoolSnapshots,
+    SwapRateLimiters,
+    SupplementalTickArrays,
+    SupplementalTickArraysOne,
+    SupplementalTickArraysTwo,
+    OracleUpdates,
+    SwapCallback,
+    Blocklists,
+    PoolFeeAnalytics,
+    HopAccountsOne,
+    HopAccountsTwo,
+    PoolRevenueSnapshot,
+    ReferralFeeEscrows,
     //TickArray,
     //TickArrayOne,
     //TickArrayTwo,
@@ -35,6 +48,19 @@ pub struct ParsedRemainingAccounts<'info> {
     pub transfer_hook_intermediate: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_output: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_referral_fee: Option<Vec<AccountInfo<'info>>>,
+    pub pool_snapshots: Option<Vec<AccountInfo<'info>>>,
+    pub swap_rate_limiters: Option<Vec<AccountInfo<'info>>>,
+    pub supplemental_tick_arrays: Option<Vec<AccountInfo<'info>>>,
+    pub supplemental_tick_arrays_one: Option<Vec<AccountInfo<'info>>>,
+    pub supplemental_tick_arrays_two: Option<Vec<AccountInfo<'info>>>,
+    pub oracle_updates: Option<Vec<AccountInfo<'info>>>,
+    pub swap_callback: Option<Vec<AccountInfo<'info>>>,
+    pub blocklists: Option<Vec<AccountInfo<'info>>>,
+    pub pool_fee_analytics: Option<Vec<AccountInfo<'info>>>,
+    pub hop_accounts_one: Option<Vec<AccountInfo<'info>>>,
+    pub hop_accounts_two: Option<Vec<AccountInfo<'info>>>,
+    pub pool_revenue_snapshot: Option<Vec<AccountInfo<'info>>>,
+    pub referral_fee_escrows: Option<Vec<AccountInfo<'info>>>,
 }
 
 /// Parses the remaining accounts based on the provided information and valid account types.
@@ -134,6 +160,84 @@ pub fn parse_remaining_accounts<'info>(
           }
           parsed_remaining_accounts.transfer_hook_referral_fee = Some(accounts);
         }
+        AccountsType::PoolSnapshots => {
+          if parsed_remaining_accounts.pool_snapshots.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.pool_snapshots = Some(accounts);
+        }
+        AccountsType::SwapRateLimiters => {
+          if parsed_remaining_accounts.swap_rate_limiters.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.swap_rate_limiters = Some(accounts);
+        }
+        AccountsType::SupplementalTickArrays => {
+          if parsed_remaining_accounts.supplemental_tick_arrays.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.supplemental_tick_arrays = Some(accounts);
+        }
+        AccountsType::SupplementalTickArraysOne => {
+          if parsed_remaining_accounts.supplemental_tick_arrays_one.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.supplemental_tick_arrays_one = Some(accounts);
+        }
+        AccountsType::SupplementalTickArraysTwo => {
+          if parsed_remaining_accounts.supplemental_tick_arrays_two.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.supplemental_tick_arrays_two = Some(accounts);
+        }
+        AccountsType::OracleUpdates => {
+          if parsed_remaining_accounts.oracle_updates.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.oracle_updates = Some(accounts);
+        }
+        AccountsType::SwapCallback => {
+          if parsed_remaining_accounts.swap_callback.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.swap_callback = Some(accounts);
+        }
+        AccountsType::Blocklists => {
+          if parsed_remaining_accounts.blocklists.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.blocklists = Some(accounts);
+        }
+        AccountsType::PoolFeeAnalytics => {
+          if parsed_remaining_accounts.pool_fee_analytics.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.pool_fee_analytics = Some(accounts);
+        }
+        AccountsType::HopAccountsOne => {
+          if parsed_remaining_accounts.hop_accounts_one.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.hop_accounts_one = Some(accounts);
+        }
+        AccountsType::HopAccountsTwo => {
+          if parsed_remaining_accounts.hop_accounts_two.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.hop_accounts_two = Some(accounts);
+        }
+        AccountsType::PoolRevenueSnapshot => {
+          if parsed_remaining_accounts.pool_revenue_snapshot.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.pool_revenue_snapshot = Some(accounts);
+        }
+        AccountsType::ReferralFeeEscrows => {
+          if parsed_remaining_accounts.referral_fee_escrows.is_some() {
+            return Err(ErrorCode::DuplicateAccountTypesError.into());
+          }
+          parsed_remaining_accounts.referral_fee_escrows = Some(accounts);
+        }
       }
     }
   }
@@ -141,3 +245,103 @@ pub fn parse_remaining_accounts<'info>(
   // Return the parsed_remaining_accounts
   Ok(parsed_remaining_accounts)
 }
+
+/// Returns the accounts of a slice of the remaining accounts, borrowed from the instruction
+/// accounts rather than cloned, for accounts that must be loaded with the `'info` lifetime.
+///
+/// Must be called after `parse_remaining_accounts` validated the slices.
+pub fn remaining_accounts_slice<'a, 'info>(
+  remaining_accounts: &'a [AccountInfo<'info>],
+  remaining_accounts_info: &Option<RemainingAccountsInfo>,
+  accounts_type: AccountsType,
+) -> Option<&'a [AccountInfo<'info>]> {
+  let mut start = 0;
+  for slice in remaining_accounts_info.iter().flat_map(|info| info.slices.iter()) {
+    let end = start + slice.length as usize;
+    if slice.accounts_type == accounts_type && slice.length > 0 {
+      return remaining_accounts.get(start..end);
+    }
+    start = end;
+  }
+  None
+}
+
+#[cfg(test)]
+mod remaining_accounts_tests {
+  use super::*;
+
+  fn slice(accounts_type: AccountsType, length: u8) -> RemainingAccountsSlice {
+    RemainingAccountsSlice { accounts_type, length }
+  }
+
+  #[test]
+  fn test_parse_supplemental_tick_arrays_and_oracle_updates() {
+    let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let owner = crate::ID;
+    let mut lamports = [0u64; 3];
+    let mut data = [[0u8; 0]; 3];
+    let account_infos: Vec<AccountInfo> = keys
+      .iter()
+      .zip(lamports.iter_mut())
+      .zip(data.iter_mut())
+      .map(|((key, lamports), data)| AccountInfo::new(key, false, true, lamports, data, &owner, false, 0))
+      .collect();
+
+    let remaining_accounts_info = Some(RemainingAccountsInfo {
+      slices: vec![
+        slice(AccountsType::SupplementalTickArraysOne, 2),
+        slice(AccountsType::OracleUpdates, 1),
+      ],
+    });
+    let valid_accounts_types = [
+      AccountsType::SupplementalTickArraysOne,
+      AccountsType::SupplementalTickArraysTwo,
+      AccountsType::OracleUpdates,
+    ];
+
+    let parsed = parse_remaining_accounts(&account_infos, &remaining_accounts_info, &valid_accounts_types).unwrap();
+    let supplemental_tick_arrays_one = parsed.supplemental_tick_arrays_one.unwrap();
+    assert_eq!(*supplemental_tick_arrays_one[0].key, keys[0]);
+    assert_eq!(*supplemental_tick_arrays_one[1].key, keys[1]);
+    assert!(parsed.supplemental_tick_arrays_two.is_none());
+    assert_eq!(*parsed.oracle_updates.unwrap()[0].key, keys[2]);
+
+    // Slices of a type the instruction does not accept are rejected
+    assert_eq!(
+      parse_remaining_accounts(&account_infos, &remaining_accounts_info, &valid_accounts_types[..2]).err().unwrap(),
+      ErrorCode::InvalidRemainingAccountsSliceError.into()
+    );
+  }
+
+  #[test]
+  fn test_remaining_accounts_slice_borrows_the_accounts_of_a_slice() {
+    let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let owner = crate::ID;
+    let mut lamports = [0u64; 4];
+    let mut data = [[0u8; 0]; 4];
+    let account_infos: Vec<AccountInfo> = keys
+      .iter()
+      .zip(lamports.iter_mut())
+      .zip(data.iter_mut())
+      .map(|((key, lamports), data)| AccountInfo::new(key, false, true, lamports, data, &owner, false, 0))
+      .collect();
+
+    let remaining_accounts_info = Some(RemainingAccountsInfo {
+      slices: vec![
+        slice(AccountsType::HopAccountsOne, 1),
+        slice(AccountsType::TransferHookInput, 0),
+        slice(AccountsType::HopAccountsTwo, 3),
+      ],
+    });
+
+    let hop_accounts_one =
+      remaining_accounts_slice(&account_infos, &remaining_accounts_info, AccountsType::HopAccountsOne).unwrap();
+    assert_eq!(hop_accounts_one.len(), 1);
+    assert_eq!(*hop_accounts_one[0].key, keys[0]);
+    let hop_accounts_two =
+      remaining_accounts_slice(&account_infos, &remaining_accounts_info, AccountsType::HopAccountsTwo).unwrap();
+    assert_eq!(hop_accounts_two.iter().map(|account_info| *account_info.key).collect::<Vec<_>>(), keys[1..]);
+    assert!(remaining_accounts_slice(&account_infos, &remaining_accounts_info, AccountsType::TransferHookInput).is_none());
+    assert!(remaining_accounts_slice(&account_infos, &None, AccountsType::HopAccountsOne).is_none());
+  }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hash, instruction::{AccountMeta, Instruction}, program::invoke};
+
+use crate::errors::ErrorCode;
+
+/// Result of a swap passed to the callback program of an integrator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SwapCallbackData {
+    pub ai_dex_pool: Pubkey,
+    pub token_authority: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub sqrt_price: u128,
+    pub timestamp: u64,
+}
+
+impl SwapCallbackData {
+    /// Name of the instruction invoked on the callback program. Its discriminator follows the
+    /// Anchor convention, so Anchor programs can implement the callback as an `ai_dex_swap_callback`
+    /// instruction taking `SwapCallbackData` as its argument.
+    pub const INSTRUCTION_NAME: &'static str = "ai_dex_swap_callback";
+
+    /// Returns the instruction data of the callback: its discriminator followed by this struct.
+    pub fn to_instruction_data(&self) -> Result<Vec<u8>> {
+        let preimage = format!("global:{}", Self::INSTRUCTION_NAME);
+        let mut data = hash(preimage.as_bytes()).to_bytes()[..8].to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Invokes the callback program passed in the `SwapCallback` slice of the remaining accounts,
+/// once the swap is complete.
+///
+/// The first account of the slice is the callback program, and the following ones are passed to
+/// it with the signer and writable flags of the transaction. The program of the pool does not sign
+/// for any account.
+///
+/// # Arguments
+///
+/// * `swap_callback` - The `SwapCallback` slice of the remaining accounts, if any.
+/// * `swap_callback_data` - The result of the swap.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidSwapCallbackProgram` - If the callback program is not an executable account
+///   or is this program.
+/// * Any error returned by the callback program.
+pub fn invoke_swap_callback(
+    swap_callback: &Option<Vec<AccountInfo>>,
+    swap_callback_data: &SwapCallbackData,
+) -> Result<()> {
+    let Some((callback_program, callback_accounts)) = swap_callback.as_ref().and_then(|accounts| accounts.split_first()) else {
+        return Ok(());
+    };
+    if !callback_program.executable || callback_program.key() == crate::ID {
+        return Err(ErrorCode::InvalidSwapCallbackProgram.into());
+    }
+
+    let instruction = Instruction {
+        program_id: callback_program.key(),
+        accounts: callback_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: swap_callback_data.to_instruction_data()?,
+    };
+    let mut account_infos = callback_accounts.to_vec();
+    account_infos.push(callback_program.clone());
+    invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod swap_callback_tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_callback_instruction_data() {
+        let swap_callback_data = SwapCallbackData {
+            ai_dex_pool: Pubkey::new_unique(),
+            token_authority: Pubkey::new_unique(),
+            a_to_b: true,
+            amount_in: 1_000,
+            amount_out: 990,
+            sqrt_price: 1 << 64,
+            timestamp: 1_700_000_000,
+        };
+        let data = swap_callback_data.to_instruction_data().unwrap();
+
+        assert_eq!(data[..8], hash(b"global:ai_dex_swap_callback").to_bytes()[..8]);
+        assert_eq!(SwapCallbackData::try_from_slice(&data[8..]).unwrap(), swap_callback_data);
+    }
+}
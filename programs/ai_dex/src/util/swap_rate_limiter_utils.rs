@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{AiDexPool, SwapRateLimiter};
+
+/// Records the volume of a swap on the rate limiter attached to the pool, if any.
+///
+/// The limiter is passed in the `SwapRateLimiters` slice of the remaining accounts, and counts
+/// the amount of its token swapped in either direction.
+///
+/// # Arguments
+///
+/// * `swap_rate_limiters` - The `SwapRateLimiters` slice of the remaining accounts, if any.
+/// * `ai_dex_pool` - The pool of the swap.
+/// * `amount_a` - The amount of token A swapped.
+/// * `amount_b` - The amount of token B swapped.
+/// * `timestamp` - The current timestamp.
+///
+/// # Errors
+///
+/// * `ErrorCode::InvalidSwapRateLimiter` - If the limiter of the pool is not passed as a writable account.
+/// * `ErrorCode::SwapRateLimitExceeded` - If the swap exceeds the limit of the window.
+pub fn record_swap_volume(
+    swap_rate_limiters: &Option<Vec<AccountInfo>>,
+    ai_dex_pool: &AccountLoader<AiDexPool>,
+    amount_a: u64,
+    amount_b: u64,
+    timestamp: u64,
+) -> Result<()> {
+    let ai_dex_pool = ai_dex_pool.load()?;
+    if ai_dex_pool.swap_rate_limiter == Pubkey::default() {
+        return Ok(());
+    }
+
+    let swap_rate_limiter_info = swap_rate_limiters
+        .iter()
+        .flatten()
+        .find(|account_info| account_info.key() == ai_dex_pool.swap_rate_limiter)
+        .ok_or(ErrorCode::InvalidSwapRateLimiter)?;
+    if swap_rate_limiter_info.owner != &crate::ID || !swap_rate_limiter_info.is_writable {
+        return Err(ErrorCode::InvalidSwapRateLimiter.into());
+    }
+    let mut swap_rate_limiter = SwapRateLimiter::try_deserialize(&mut &swap_rate_limiter_info.try_borrow_data()?[..])?;
+
+    let volume = if swap_rate_limiter.token_mint == ai_dex_pool.token_mint_a {
+        amount_a
+    } else {
+        amount_b
+    };
+    swap_rate_limiter.record_volume(volume, timestamp)?;
+    swap_rate_limiter.try_serialize(&mut &mut swap_rate_limiter_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -4,14 +4,15 @@ use anchor_lang::prelude::*;
 use std::cell::RefMut;
 
 pub struct SwapTickSequence<'info> {
-    arrays: Vec<RefMut<'info, TickArray>>,
+    arrays: Vec<RefMut<'info, dyn TickArrayType>>,
+    stop_at_end: bool,
 }
 
 impl<'info> SwapTickSequence<'info> {
     pub fn new(
-        ta0: RefMut<'info, TickArray>,
-        ta1: Option<RefMut<'info, TickArray>>,
-        ta2: Option<RefMut<'info, TickArray>>,
+        ta0: RefMut<'info, dyn TickArrayType>,
+        ta1: Option<RefMut<'info, dyn TickArrayType>>,
+        ta2: Option<RefMut<'info, dyn TickArrayType>>,
     ) -> Self {
         let mut vec = Vec::with_capacity(3);
         vec.push(ta0);
@@ -21,7 +22,63 @@ impl<'info> SwapTickSequence<'info> {
         if ta2.is_some() {
             vec.push(ta2.unwrap());
         }
-        Self { arrays: vec }
+        Self { arrays: vec, stop_at_end: false }
+    }
+
+    /// Creates a sequence from tick array accounts in the direction of the swap.
+    ///
+    /// # Errors
+    /// - `InvalidTickArraySequenceError`: - No tick array is passed.
+    /// - `AccountNotMutable`: - A tick array is not writable.
+    /// - `AccountDiscriminatorMismatch`: - An account is not a tick array.
+    pub fn from_tick_arrays(tick_arrays: &'info [AccountInfo<'_>]) -> Result<Self> {
+        if tick_arrays.is_empty() {
+            return Err(ErrorCode::InvalidTickArraySequenceError.into());
+        }
+        let arrays = tick_arrays
+            .iter()
+            .map(|account_info| load_tick_array_mut(account_info))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { arrays, stop_at_end: false })
+    }
+
+    /// Appends the tick arrays passed in a `SupplementalTickArrays` slice of the remaining
+    /// accounts, so that a swap can cross more tick arrays than the instruction accounts allow.
+    ///
+    /// # Parameters
+    /// - `supplemental_tick_arrays` - The tick array accounts following the instruction ones, in
+    ///   the direction of the swap
+    /// - `ai_dex_pool` - The pool being swapped, which every tick array must belong to
+    ///
+    /// # Errors
+    /// - `AccountNotMutable`: - A tick array is not writable.
+    /// - `AccountDiscriminatorMismatch`: - An account is not a tick array.
+    /// - `InvalidTickArray`: - A tick array belongs to another pool.
+    pub fn push_supplemental_tick_arrays(
+        &mut self,
+        supplemental_tick_arrays: &'info Option<Vec<AccountInfo<'_>>>,
+        ai_dex_pool: Pubkey,
+    ) -> Result<()> {
+        for account_info in supplemental_tick_arrays.iter().flatten() {
+            let tick_array = load_tick_array_mut(account_info)?;
+            if tick_array.ai_dex_pool() != ai_dex_pool {
+                return Err(ErrorCode::InvalidTickArray.into());
+            }
+            self.arrays.push(tick_array);
+        }
+        Ok(())
+    }
+
+    /// Lets a swap stop at the end of the last tick array instead of failing with
+    /// `InvalidTickArraySequenceErrorIndexError` once every array has been crossed.
+    pub fn set_stop_at_end(&mut self, stop_at_end: bool) {
+        self.stop_at_end = stop_at_end;
+    }
+
+    /// Returns whether a swap should stop because every tick array up to `array_index` has been
+    /// crossed and the sequence was set to stop at its end.
+    pub fn is_exhausted(&self, array_index: usize) -> bool {
+        self.stop_at_end && array_index >= self.arrays.len()
     }
 
     /// Get the Tick object at the given tick-index & tick-spacing
@@ -140,20 +197,20 @@ impl<'info> SwapTickSequence<'info> {
             if array_index + 1 == self.arrays.len() {
                 // If the trade direction is from A to B, return the start tick index of the last array
                 if a_to_b {
-                    return Ok((array_index, next_array.start_tick_index));
+                    return Ok((array_index, next_array.start_tick_index()));
                 }
                 // If the trade direction is from B to A, return the last tick index of the last array
                 else {
-                    let last_tick = next_array.start_tick_index + ticks_in_array - 1;
+                    let last_tick = next_array.start_tick_index() + ticks_in_array - 1;
                     return Ok((array_index, last_tick));
             }
             }
 
             // Update the search index and array index for the next iteration
             search_index = if a_to_b {
-                next_array.start_tick_index - 1
+                next_array.start_tick_index() - 1
             } else {
-                next_array.start_tick_index + ticks_in_array - 1
+                next_array.start_tick_index() + ticks_in_array - 1
             };
 
             array_index += 1;
@@ -163,7 +220,7 @@ impl<'info> SwapTickSequence<'info> {
     }
 
     pub fn get_start_tick_index(&self, array_index: usize) -> i32 {
-        return self.arrays[array_index].start_tick_index;
+        return self.arrays[array_index].start_tick_index();
     }
 }
 
@@ -192,6 +249,62 @@ mod swap_tick_sequence_tests {
         RefCell::new(array)
     }
 
+    mod supplemental_tick_arrays {
+        use super::*;
+        use anchor_lang::Discriminator;
+
+        fn tick_array_data(ai_dex_pool: Pubkey, start_tick_index: i32) -> Vec<u8> {
+            let mut array = TickArray::default();
+            array.ai_dex_pool = ai_dex_pool;
+            array.start_tick_index = start_tick_index;
+            let mut data = TickArray::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(bytemuck::bytes_of(&array));
+            data
+        }
+
+        #[test]
+        fn push_supplemental_tick_arrays_of_the_pool() {
+            let ai_dex_pool = Pubkey::new_unique();
+            let key = Pubkey::new_unique();
+            let owner = crate::ID;
+            let mut lamports = 0;
+            let mut data = tick_array_data(ai_dex_pool, -11264);
+            let supplemental_tick_arrays =
+                Some(vec![AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0)]);
+
+            let ta0 = build_tick_array(0, vec![]);
+            let mut swap_tick_sequence = SwapTickSequence::new(ta0.borrow_mut(), None, None);
+            swap_tick_sequence
+                .push_supplemental_tick_arrays(&supplemental_tick_arrays, ai_dex_pool)
+                .unwrap();
+            swap_tick_sequence.set_stop_at_end(true);
+            assert!(!swap_tick_sequence.is_exhausted(1));
+            assert!(swap_tick_sequence.is_exhausted(2));
+        }
+
+        #[test]
+        fn push_supplemental_tick_arrays_of_another_pool() {
+            let ai_dex_pool = Pubkey::new_unique();
+            let key = Pubkey::new_unique();
+            let owner = crate::ID;
+            let mut lamports = 0;
+            // A tick array with a matching start index, but initialized for a pool of the caller
+            let mut data = tick_array_data(Pubkey::new_unique(), -11264);
+            let supplemental_tick_arrays =
+                Some(vec![AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0)]);
+
+            let ta0 = build_tick_array(0, vec![]);
+            let mut swap_tick_sequence = SwapTickSequence::new(ta0.borrow_mut(), None, None);
+            assert_eq!(
+                swap_tick_sequence
+                    .push_supplemental_tick_arrays(&supplemental_tick_arrays, ai_dex_pool)
+                    .err()
+                    .unwrap(),
+                ErrorCode::InvalidTickArray.into()
+            );
+        }
+    }
+
     mod modify_ticks {
         use super::*;
 
@@ -2,19 +2,89 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::memo::Memo;
 
-use crate::{orchestrator::swap_orchestrator::PostSwapUpdate, state::AiDexPool};
+use crate::constants::EVENT_VERSION;
+use crate::{
+    errors::ErrorCode,
+    math::{sqrt_price_from_tick_index, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    orchestrator::swap_orchestrator::PostSwapUpdate,
+    state::{AiDexPool, Tick},
+};
 
-use super::{transfer_from_owner_to_vault, transfer_from_vault_to_owner};
+use super::{
+    transfer_from_owner_to_vault_with_transfer_fee, transfer_from_vault_to_owner_with_transfer_fee,
+    verify_token_authority, MintTransferFee,
+};
 
+#[event]
+pub struct IntermediateTransferEvent {
+    pub event_version: u8,
+    pub ai_dex_one: Pubkey,
+    pub ai_dex_two: Pubkey,
+    pub token_mint_intermediate: Pubkey,
+    pub token_vault_one_intermediate: Pubkey,
+    pub token_vault_two_intermediate: Pubkey,
+    pub amount: u64,
+    pub transfer_fee: u64,
+    pub amount_received: u64,
+}
+
+
+/// Resolves the sqrt price limit of a swap, which can alternatively be given as a tick index.
+///
+/// A sqrt price limit of 0 means no limit and is expanded to the price boundary in the
+/// direction of the swap.
+///
+/// # Arguments
+///
+/// * `sqrt_price_limit` - The sqrt price limit provided by the caller, stored as Q64.64.
+/// * `tick_index_limit` - The optional tick index limit, which takes precedence over `sqrt_price_limit`.
+/// * `a_to_b` - The direction of the swap.
+///
+/// # Errors
+///
+/// Returns an error if the tick index limit is out of bounds, or if the sqrt price limit is
+/// outside of [MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64].
+pub fn resolve_sqrt_price_limit(
+    sqrt_price_limit: u128,
+    tick_index_limit: Option<i32>,
+    a_to_b: bool,
+) -> Result<u128> {
+    match tick_index_limit {
+        Some(tick_index_limit) => {
+            if Tick::check_is_out_of_bounds(tick_index_limit) {
+                return Err(ErrorCode::InvalidTickIndexError.into());
+            }
+            Ok(sqrt_price_from_tick_index(tick_index_limit))
+        }
+        None if sqrt_price_limit == 0 => {
+            if a_to_b {
+                Ok(MIN_SQRT_PRICE_X64)
+            } else {
+                Ok(MAX_SQRT_PRICE_X64)
+            }
+        }
+        None => {
+            if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_limit) {
+                return Err(ErrorCode::SqrtPriceLimitOutOfBounds.into());
+            }
+            Ok(sqrt_price_limit)
+        }
+    }
+}
 
 /// Updates the AiDex state and performs a swap between two tokens in the AiDex program.
 /// 
+/// The token authority can be the owner of the input token account, or a delegate approved for
+/// at least the deposited amount, so that smart wallets and session keys can trade from it.
+/// 
 /// # Arguments
 /// 
 /// * `ai_dex` - The mutable reference to the AiDex account.
 /// * `token_authority` - The signer for the token authority account.
 /// * `token_mint_a` - The interface account for the first token mint.
 /// * `token_mint_b` - The interface account for the second token mint.
+/// * `transfer_fee_a` - The transfer fee of the first token mint, read once by the instruction.
+/// * `transfer_fee_b` - The transfer fee of the second token mint, read once by the instruction.
 /// * `token_owner_account_a` - The interface account for the first token owner account.
 /// * `token_owner_account_b` - The interface account for the second token owner account.
 /// * `token_vault_a` - The interface account for the first token vault account.
@@ -37,6 +107,8 @@ pub fn update_and_swap_ai_dex<'info>(
     token_authority: &Signer<'info>,
     token_mint_a: &InterfaceAccount<'info, Mint>,
     token_mint_b: &InterfaceAccount<'info, Mint>,
+    transfer_fee_a: &MintTransferFee,
+    transfer_fee_b: &MintTransferFee,
     token_owner_account_a: &InterfaceAccount<'info, TokenAccount>,
     token_owner_account_b: &InterfaceAccount<'info, TokenAccount>,
     token_vault_a: &InterfaceAccount<'info, TokenAccount>,
@@ -72,6 +144,8 @@ pub fn update_and_swap_ai_dex<'info>(
         token_authority,
         token_mint_a,
         token_mint_b,
+        transfer_fee_a,
+        transfer_fee_b,
         token_owner_account_a,
         token_owner_account_b,
         token_vault_a,
@@ -96,6 +170,8 @@ pub fn update_and_swap_ai_dex<'info>(
 /// * `token_authority` - The signer for the token authority account.
 /// * `token_mint_a` - The interface account for the first token mint.
 /// * `token_mint_b` - The interface account for the second token mint.
+/// * `transfer_fee_a` - The transfer fee of the first token mint, read once by the instruction.
+/// * `transfer_fee_b` - The transfer fee of the second token mint, read once by the instruction.
 /// * `token_owner_account_a` - The interface account for the first token owner account.
 /// * `token_owner_account_b` - The interface account for the second token owner account.
 /// * `token_vault_a` - The interface account for the first token vault account.
@@ -112,12 +188,14 @@ pub fn update_and_swap_ai_dex<'info>(
 /// 
 /// # Errors
 /// 
-/// Returns an error if the swap fails.
+/// Returns an error if the token authority cannot transfer the deposit or the swap fails.
 fn perform_swap<'info>(
     ai_dex: &AccountLoader<'info, AiDexPool>,
     token_authority: &Signer<'info>,
     token_mint_a: &InterfaceAccount<'info, Mint>,
     token_mint_b: &InterfaceAccount<'info, Mint>,
+    transfer_fee_a: &MintTransferFee,
+    transfer_fee_b: &MintTransferFee,
     token_owner_account_a: &InterfaceAccount<'info, TokenAccount>,
     token_owner_account_b: &InterfaceAccount<'info, TokenAccount>,
     token_vault_a: &InterfaceAccount<'info, TokenAccount>,
@@ -135,6 +213,7 @@ fn perform_swap<'info>(
     // Transfer from user to pool
     let deposit_token_program;
     let deposit_mint;
+    let deposit_transfer_fee;
     let deposit_account_user;
     let deposit_account_pool;
     let deposit_transfer_hook_accounts;
@@ -143,6 +222,7 @@ fn perform_swap<'info>(
     // Transfer from pool to user
     let withdrawal_token_program;
     let withdrawal_mint;
+    let withdrawal_transfer_fee;
     let withdrawal_account_user;
     let withdrawal_account_pool;
     let withdrawal_transfer_hook_accounts;
@@ -151,6 +231,7 @@ fn perform_swap<'info>(
     if a_to_b {
         deposit_token_program = token_program_a;
         deposit_mint = token_mint_a;
+        deposit_transfer_fee = transfer_fee_a;
         deposit_account_user = token_owner_account_a;
         deposit_account_pool = token_vault_a;
         deposit_transfer_hook_accounts = transfer_hook_accounts_a;
@@ -158,6 +239,7 @@ fn perform_swap<'info>(
 
         withdrawal_token_program = token_program_b;
         withdrawal_mint = token_mint_b;
+        withdrawal_transfer_fee = transfer_fee_b;
         withdrawal_account_user = token_owner_account_b;
         withdrawal_account_pool = token_vault_b;
         withdrawal_transfer_hook_accounts = transfer_hook_accounts_b;
@@ -165,6 +247,7 @@ fn perform_swap<'info>(
     } else {
         deposit_token_program = token_program_b;
         deposit_mint = token_mint_b;
+        deposit_transfer_fee = transfer_fee_b;
         deposit_account_user = token_owner_account_b;
         deposit_account_pool = token_vault_b;
         deposit_transfer_hook_accounts = transfer_hook_accounts_b;
@@ -172,15 +255,18 @@ fn perform_swap<'info>(
 
         withdrawal_token_program = token_program_a;
         withdrawal_mint = token_mint_a;
+        withdrawal_transfer_fee = transfer_fee_a;
         withdrawal_account_user = token_owner_account_a;
         withdrawal_account_pool = token_vault_a;
         withdrawal_transfer_hook_accounts = transfer_hook_accounts_a;
         withdrawal_amount = amount_a;
     }
 
-    transfer_from_owner_to_vault(
+    verify_token_authority(deposit_account_user, token_authority.key, deposit_amount)?;
+    transfer_from_owner_to_vault_with_transfer_fee(
         token_authority,
         deposit_mint,
+        deposit_transfer_fee,
         deposit_account_user,
         deposit_account_pool,
         deposit_token_program,
@@ -189,11 +275,12 @@ fn perform_swap<'info>(
         deposit_amount,
     )?;
     
-    transfer_from_vault_to_owner(
+    transfer_from_vault_to_owner_with_transfer_fee(
         ai_dex,
         withdrawal_mint,
+        withdrawal_transfer_fee,
         withdrawal_account_pool,
-        withdrawal_account_user,
+        &withdrawal_account_user.to_account_info(),
         withdrawal_token_program,
         memo_program,
         withdrawal_transfer_hook_accounts,
@@ -217,6 +304,9 @@ fn perform_swap<'info>(
 /// * `token_mint_input` - The interface account for the input token mint.
 /// * `token_mint_intermediate` - The interface account for the intermediate token mint.
 /// * `token_mint_output` - The interface account for the output token mint.
+/// * `transfer_fee_input` - The transfer fee of the input token mint, read once by the instruction.
+/// * `transfer_fee_intermediate` - The transfer fee of the intermediate token mint, read once by the instruction.
+/// * `transfer_fee_output` - The transfer fee of the output token mint, read once by the instruction.
 /// * `token_program_input` - The interface for the input token program.
 /// * `token_program_intermediate` - The interface for the intermediate token program.
 /// * `token_program_output` - The interface for the output token program.
@@ -251,6 +341,10 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     token_mint_input: &InterfaceAccount<'info, Mint>,
     token_mint_intermediate: &InterfaceAccount<'info, Mint>,
     token_mint_output: &InterfaceAccount<'info, Mint>,
+    // transfer fee
+    transfer_fee_input: &MintTransferFee,
+    transfer_fee_intermediate: &MintTransferFee,
+    transfer_fee_output: &MintTransferFee,
     // token program
     token_program_input: &Interface<'info, TokenInterface>,
     token_program_intermediate: &Interface<'info, TokenInterface>,
@@ -312,9 +406,11 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     };
     let output_amount = if is_token_fee_in_two_a { swap_update_two.amount_b } else { swap_update_two.amount_a };
 
-    transfer_from_owner_to_vault(
+    verify_token_authority(token_owner_account_input, token_authority.key, input_amount)?;
+    transfer_from_owner_to_vault_with_transfer_fee(
         token_authority,
         token_mint_input,
+        transfer_fee_input,
         token_owner_account_input,
         token_vault_one_input,
         token_program_input,
@@ -324,11 +420,12 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
     )?;
 
     // Transfer from pool to pool
-    transfer_from_vault_to_owner(
+    transfer_from_vault_to_owner_with_transfer_fee(
         ai_dex_one,
         token_mint_intermediate,
+        transfer_fee_intermediate,
         token_vault_one_intermediate,
-        token_vault_two_intermediate,
+        &token_vault_two_intermediate.to_account_info(),
         token_program_intermediate,
         memo_program,
         transfer_hook_accounts_intermediate,
@@ -336,11 +433,27 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
         memo,
     )?;
 
-    transfer_from_vault_to_owner(
+    let transfer_fee_excluded_intermediate_amount =
+        transfer_fee_intermediate.calculate_transfer_fee_excluded_amount(intermediate_amount)?;
+
+    emit!(IntermediateTransferEvent {
+        event_version: EVENT_VERSION,
+        ai_dex_one: ai_dex_one.key(),
+        ai_dex_two: ai_dex_two.key(),
+        token_mint_intermediate: token_mint_intermediate.key(),
+        token_vault_one_intermediate: token_vault_one_intermediate.key(),
+        token_vault_two_intermediate: token_vault_two_intermediate.key(),
+        amount: intermediate_amount,
+        transfer_fee: transfer_fee_excluded_intermediate_amount.transfer_fee,
+        amount_received: transfer_fee_excluded_intermediate_amount.amount,
+    });
+
+    transfer_from_vault_to_owner_with_transfer_fee(
         ai_dex_two,
         token_mint_output,
+        transfer_fee_output,
         token_vault_two_output,
-        token_owner_account_output,
+        &token_owner_account_output.to_account_info(),
         token_program_output,
         memo_program,
         transfer_hook_accounts_output,
@@ -350,3 +463,59 @@ pub fn update_and_two_hop_swap_ai_dex<'info>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod resolve_sqrt_price_limit_tests {
+    use super::resolve_sqrt_price_limit;
+    use crate::errors::ErrorCode;
+    use crate::math::{sqrt_price_from_tick_index, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+
+    #[test]
+    fn test_without_tick_index_limit() {
+        let sqrt_price_limit = sqrt_price_from_tick_index(1720);
+        assert_eq!(resolve_sqrt_price_limit(sqrt_price_limit, None, true).unwrap(), sqrt_price_limit);
+        assert_eq!(resolve_sqrt_price_limit(MIN_SQRT_PRICE_X64, None, true).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(resolve_sqrt_price_limit(MAX_SQRT_PRICE_X64, None, false).unwrap(), MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_zero_sqrt_price_limit_is_no_limit() {
+        assert_eq!(resolve_sqrt_price_limit(0, None, true).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(resolve_sqrt_price_limit(0, None, false).unwrap(), MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_sqrt_price_limit_out_of_bounds() {
+        assert_eq!(
+            resolve_sqrt_price_limit(MIN_SQRT_PRICE_X64 - 1, None, true).unwrap_err(),
+            ErrorCode::SqrtPriceLimitOutOfBounds.into()
+        );
+        assert_eq!(
+            resolve_sqrt_price_limit(MAX_SQRT_PRICE_X64 + 1, None, false).unwrap_err(),
+            ErrorCode::SqrtPriceLimitOutOfBounds.into()
+        );
+    }
+
+    #[test]
+    fn test_with_tick_index_limit() {
+        assert_eq!(
+            resolve_sqrt_price_limit(12345, Some(-1720), true).unwrap(),
+            sqrt_price_from_tick_index(-1720)
+        );
+        assert_eq!(resolve_sqrt_price_limit(0, Some(MIN_TICK_INDEX), false).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(resolve_sqrt_price_limit(0, Some(MAX_TICK_INDEX), true).unwrap(), MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_tick_index_limit_out_of_bounds() {
+        assert_eq!(
+            resolve_sqrt_price_limit(0, Some(MAX_TICK_INDEX + 1), false).unwrap_err(),
+            ErrorCode::InvalidTickIndexError.into()
+        );
+        assert_eq!(
+            resolve_sqrt_price_limit(0, Some(MIN_TICK_INDEX - 1), true).unwrap_err(),
+            ErrorCode::InvalidTickIndexError.into()
+        );
+    }
+}
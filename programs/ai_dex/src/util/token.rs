@@ -1,13 +1,18 @@
-use crate::state::{AiDexPool, PositionTradeBatch, SwapReferral};
+use crate::state::{AiDexPool, PositionMetadata, PositionTradeBatch, SwapReferral};
+use crate::constants::EVENT_VERSION;
 use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint as SplMint, Token, TokenAccount as SplTokenAccount};
-use anchor_spl::metadata::{self, CreateMetadataAccountsV3, mpl_token_metadata::types::DataV2};
+use anchor_spl::metadata::{
+    self, CreateMetadataAccountsV3, VerifySizedCollectionItem,
+    mpl_token_metadata::types::{Collection, DataV2},
+};
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{TransferFee, MAX_FEE_BASIS_POINTS};
 use anchor_spl::token_interface::spl_token_2022::extension::BaseStateWithExtensions;
 use anchor_spl::token_2022::spl_token_2022::{self, extension::{self, StateWithExtensions}, state::AccountState};
 use anchor_spl::token_interface::{Mint as InterfaceMint, TokenAccount as InterfaceTokenAccount, TokenInterface};
 use anchor_spl::memo::{self, Memo, BuildMemo};
+use anchor_spl::associated_token::{self, AssociatedToken};
 use spl_transfer_hook_interface;
 use solana_program::program::invoke_signed;
 use spl_token::instruction::{burn_checked, close_account, mint_to, set_authority, AuthorityType};
@@ -17,6 +22,13 @@ use crate::constants::nft::{
     AD_METADATA_SYMBOL, AD_METADATA_URI,
 };
 
+#[event]
+pub struct FrozenTokenAccountEvent {
+    pub event_version: u8,
+    pub token_account: Pubkey,
+    pub token_mint: Pubkey,
+}
+
 /// Burns a single token from the user's position token account and closes the account.
 ///
 /// # Arguments
@@ -114,6 +126,7 @@ pub fn mint_position_token_and_remove_authority<'info>(
 /// * `position_token_account` - The position token account.
 /// * `position_metadata_account` - The position metadata account.
 /// * `metadata_update_auth` - The metadata update authority.
+/// * `position_metadata` - The optional config-level metadata overriding the default name, symbol and uri.
 /// * `funder` - The funder of the metadata account.
 /// * `metadata_program` - The metadata program.
 /// * `token_program` - The token program.
@@ -129,6 +142,7 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
     position_token_account: &Account<'info, SplTokenAccount>,
     position_metadata_account: &UncheckedAccount<'info>,
     metadata_update_auth: &UncheckedAccount<'info>,
+    position_metadata: Option<&PositionMetadata>,
     funder: &Signer<'info>,
     metadata_program: &Program<'info, metadata::Metadata>,
     token_program: &Program<'info, Token>,
@@ -142,6 +156,23 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
         token_program,
     )?;
 
+    let (name, symbol, uri) = match position_metadata {
+        Some(position_metadata) => (
+            position_metadata.name.clone(),
+            position_metadata.symbol.clone(),
+            position_metadata.uri.clone(),
+        ),
+        None => (
+            AD_METADATA_NAME.to_string(),
+            AD_METADATA_SYMBOL.to_string(),
+            AD_METADATA_URI.to_string(),
+        ),
+    };
+    // The collection is verified separately by verify_position_collection
+    let collection = position_metadata
+        .and_then(|position_metadata| position_metadata.get_collection_mint())
+        .map(|key| Collection { verified: false, key });
+
     let metadata_mint_auth_account = ai_dex.load()?;
     metadata::create_metadata_accounts_v3(
         CpiContext::new_with_signer(
@@ -158,12 +189,12 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
             &[&metadata_mint_auth_account.seeds()],
         ),
         DataV2 {
-            name: AD_METADATA_NAME.to_string(),
-            symbol: AD_METADATA_SYMBOL.to_string(),
-            uri: AD_METADATA_URI.to_string(),
+            name,
+            symbol,
+            uri,
             creators: None,
             seller_fee_basis_points: 0,
-            collection: None,
+            collection,
             uses: None,
         },
         true,
@@ -174,6 +205,68 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
     remove_position_token_mint_authority(ai_dex, position_mint, token_program)
 }
 
+/// Verifies a position NFT into the Metaplex collection configured in the position metadata.
+///
+/// The collection authority is a PDA of the config which must have been approved as a
+/// collection authority by the collection update authority.
+///
+/// # Arguments
+///
+/// * `ai_dex_config` - The config of the pool the position belongs to.
+/// * `collection_mint` - The collection mint configured in the position metadata.
+/// * `position_metadata_account` - The metadata account of the position NFT.
+/// * `collection_authority` - The `[b"position_collection_authority", ai_dex_config]` PDA.
+/// * `collection_authority_record` - The collection authority record of the PDA.
+/// * `collection_mint_account` - The collection mint account.
+/// * `collection_metadata` - The metadata account of the collection.
+/// * `collection_master_edition` - The master edition account of the collection.
+/// * `funder` - The payer of the verification.
+/// * `metadata_program` - The metadata program.
+///
+/// # Errors
+///
+/// Returns an error if the collection accounts do not match the configured collection or the
+/// verification fails.
+pub fn verify_position_collection<'info>(
+    ai_dex_config: &Pubkey,
+    collection_mint: &Pubkey,
+    position_metadata_account: &UncheckedAccount<'info>,
+    collection_authority: &UncheckedAccount<'info>,
+    collection_authority_record: &UncheckedAccount<'info>,
+    collection_mint_account: &UncheckedAccount<'info>,
+    collection_metadata: &UncheckedAccount<'info>,
+    collection_master_edition: &UncheckedAccount<'info>,
+    funder: &Signer<'info>,
+    metadata_program: &Program<'info, metadata::Metadata>,
+) -> Result<()> {
+    let (collection_authority_key, bump) = Pubkey::find_program_address(
+        &[b"position_collection_authority", ai_dex_config.as_ref()],
+        &crate::ID,
+    );
+    if collection_authority.key() != collection_authority_key
+        || collection_mint_account.key() != *collection_mint
+    {
+        return Err(ErrorCode::InvalidPositionCollection.into());
+    }
+
+    metadata::verify_sized_collection_item(
+        CpiContext::new_with_signer(
+            metadata_program.to_account_info(),
+            VerifySizedCollectionItem {
+                payer: funder.to_account_info(),
+                metadata: position_metadata_account.to_account_info(),
+                collection_authority: collection_authority.to_account_info(),
+                collection_mint: collection_mint_account.to_account_info(),
+                collection_metadata: collection_metadata.to_account_info(),
+                collection_master_edition: collection_master_edition.to_account_info(),
+            },
+            &[&[b"position_collection_authority", ai_dex_config.as_ref(), &[bump]]],
+        )
+        .with_remaining_accounts(vec![collection_authority_record.to_account_info()]),
+        Some(collection_authority_record.key()),
+    )
+}
+
 /// Mints a single position token to the specified token account.
 ///
 /// # Arguments
@@ -504,7 +597,8 @@ pub fn burn_and_close_position_trade_batch_token<'info>(
 ///
 /// # Errors
 ///
-/// Returns an error if there is an issue with logging the transfer fee, creating the transfer instruction,
+/// Returns `FrozenAccountError` if the owner's account or the vault is frozen, or an error if
+/// there is an issue with logging the transfer fee, creating the transfer instruction,
 /// preparing the account infos, handling the transfer hooks, or invoking the transfer instruction.
 pub fn transfer_from_owner_to_vault<'info>(
     authority: &Signer<'info>,
@@ -516,25 +610,47 @@ pub fn transfer_from_owner_to_vault<'info>(
     transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
     amount: u64,
 ) -> Result<()> {
-    // Handle TransferFee extension
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        // log applied transfer fee
-        // - Not must, but important for ease of investigation and replay when problems occur
-        // - Use Memo because logs risk being truncated
-        let transfer_fee_memo = format!(
-            "TFe: {}, {}",
-            u16::from(epoch_transfer_fee.transfer_fee_basis_points),
-            u64::from(epoch_transfer_fee.maximum_fee),
-        );
-        memo::build_memo(
-            CpiContext::new(
-                memo_program.to_account_info(),
-                BuildMemo {}
-            ),
-            transfer_fee_memo.as_bytes()
-        )?;
+    transfer_from_owner_to_vault_with_transfer_fee(
+        authority,
+        token_mint,
+        &MintTransferFee::load(token_mint)?,
+        token_owner_account,
+        token_vault,
+        token_program,
+        memo_program,
+        transfer_hook_accounts,
+        amount,
+    )
+}
+
+/// Transfers tokens from the owner's account to the vault, using a transfer fee already read from
+/// the mint in the instruction.
+///
+/// See `transfer_from_owner_to_vault`.
+pub fn transfer_from_owner_to_vault_with_transfer_fee<'info>(
+    authority: &Signer<'info>,
+    token_mint: &InterfaceAccount<'info, InterfaceMint>,
+    transfer_fee: &MintTransferFee,
+    token_owner_account: &InterfaceAccount<'info, InterfaceTokenAccount>,
+    token_vault: &InterfaceAccount<'info, InterfaceTokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    amount: u64,
+) -> Result<()> {
+    // Nothing to transfer, skip the transfer CPI and its memos
+    if amount == 0 {
+        return Ok(());
     }
 
+    // Handle TransferFee extension
+    // - Not must, but important for ease of investigation and replay when problems occur
+    // - Use Memo because logs risk being truncated
+    transfer_fee.log_memo(memo_program)?;
+
+    check_token_account_not_frozen(&token_owner_account.to_account_info())?;
+    check_token_account_not_frozen(&token_vault.to_account_info())?;
+
     // Create transfer instruction
     let mut instruction = spl_token_2022::instruction::transfer_checked(
         token_program.key,
@@ -643,9 +759,10 @@ fn build_and_log_memo<'info>(
 ///
 /// # Errors
 ///
-/// Returns an error if there is an issue with logging the transfer fee, logging the memo,
-/// creating the transfer instruction, preparing the account infos, handling the transfer hooks,
-/// or invoking the transfer instruction.
+/// Returns `FrozenAccountError` if the vault or the owner's account is frozen, or an error if
+/// there is an issue with logging the transfer fee, logging the memo, creating the transfer
+/// instruction, preparing the account infos, handling the transfer hooks, or invoking the
+/// transfer instruction.
 pub fn transfer_from_vault_to_owner<'info>(
     ai_dex: &AccountLoader<'info, AiDexPool>,
     token_mint: &InterfaceAccount<'info, InterfaceMint>,
@@ -657,18 +774,77 @@ pub fn transfer_from_vault_to_owner<'info>(
     amount: u64,
     memo: &[u8],
 ) -> Result<()> {
-    // Handle TransferFee extension
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        let transfer_fee_memo = format!(
-            "TFe: {}, {}",
-            u16::from(epoch_transfer_fee.transfer_fee_basis_points),
-            u64::from(epoch_transfer_fee.maximum_fee),
-        );
-        build_and_log_memo(memo_program, transfer_fee_memo.as_bytes())?;
+    transfer_from_vault_to_owner_account_info(
+        ai_dex,
+        token_mint,
+        token_vault,
+        &token_owner_account.to_account_info(),
+        token_program,
+        memo_program,
+        transfer_hook_accounts,
+        amount,
+        memo,
+    )
+}
+
+/// Transfers tokens from the vault to a token account that was not deserialized by Anchor, such
+/// as a token account created within the instruction.
+///
+/// See `transfer_from_vault_to_owner`.
+pub fn transfer_from_vault_to_owner_account_info<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    token_mint: &InterfaceAccount<'info, InterfaceMint>,
+    token_vault: &InterfaceAccount<'info, InterfaceTokenAccount>,
+    token_owner_account: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    amount: u64,
+    memo: &[u8],
+) -> Result<()> {
+    transfer_from_vault_to_owner_with_transfer_fee(
+        ai_dex,
+        token_mint,
+        &MintTransferFee::load(token_mint)?,
+        token_vault,
+        token_owner_account,
+        token_program,
+        memo_program,
+        transfer_hook_accounts,
+        amount,
+        memo,
+    )
+}
+
+/// Transfers tokens from the vault to the owner's account, using a transfer fee already read from
+/// the mint in the instruction.
+///
+/// See `transfer_from_vault_to_owner`.
+pub fn transfer_from_vault_to_owner_with_transfer_fee<'info>(
+    ai_dex: &AccountLoader<'info, AiDexPool>,
+    token_mint: &InterfaceAccount<'info, InterfaceMint>,
+    transfer_fee: &MintTransferFee,
+    token_vault: &InterfaceAccount<'info, InterfaceTokenAccount>,
+    token_owner_account: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    amount: u64,
+    memo: &[u8],
+) -> Result<()> {
+    // Nothing to transfer, skip the transfer CPI and its memos
+    if amount == 0 {
+        return Ok(());
     }
 
+    // Handle TransferFee extension
+    transfer_fee.log_memo(memo_program)?;
+
+    check_token_account_not_frozen(&token_vault.to_account_info())?;
+    check_token_account_not_frozen(token_owner_account)?;
+
     // Handle MemoTransfer extension
-    if is_transfer_memo_required(&token_owner_account)? {
+    if is_transfer_memo_required(token_owner_account)? {
         build_and_log_memo(memo_program, memo)?;
     }
 
@@ -677,7 +853,7 @@ pub fn transfer_from_vault_to_owner<'info>(
         token_program.key,
         &token_vault.key(), // from
         &token_mint.key(), // mint
-        &token_owner_account.key(), // to
+        token_owner_account.key, // to
         &ai_dex.key(), // authority
         &[],
         amount,
@@ -689,7 +865,7 @@ pub fn transfer_from_vault_to_owner<'info>(
         token_program.to_account_info(),
         token_vault.to_account_info(),
         token_mint.to_account_info(),
-        token_owner_account.to_account_info(),
+        token_owner_account.clone(),
         ai_dex.to_account_info(),
     ];
 
@@ -700,7 +876,7 @@ pub fn transfer_from_vault_to_owner<'info>(
                 &mut instruction,
                 &mut account_infos,
                 &hook_program_id,
-                token_owner_account.to_account_info(),
+                token_owner_account.clone(),
                 token_mint.to_account_info(),
                 token_vault.to_account_info(),
                 ai_dex.to_account_info(),
@@ -737,18 +913,19 @@ pub fn transfer_from_referral_to_owner<'info>(
     amount: u64,
     memo: &[u8],
 ) -> Result<()> {
-    // Handle TransferFee extension
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        let transfer_fee_memo = format!(
-            "TFe: {}, {}",
-            u16::from(epoch_transfer_fee.transfer_fee_basis_points),
-            u64::from(epoch_transfer_fee.maximum_fee),
-        );
-        build_and_log_memo(memo_program, transfer_fee_memo.as_bytes())?;
+    // Nothing to transfer, skip the transfer CPI and its memos
+    if amount == 0 {
+        return Ok(());
     }
 
+    // Handle TransferFee extension
+    MintTransferFee::load(token_mint)?.log_memo(memo_program)?;
+
+    check_token_account_not_frozen(&token_vault.to_account_info())?;
+    check_token_account_not_frozen(&token_owner_account.to_account_info())?;
+
     // Handle MemoTransfer extension
-    if is_transfer_memo_required(&token_owner_account)? {
+    if is_transfer_memo_required(&token_owner_account.to_account_info())? {
         build_and_log_memo(memo_program, memo)?;
     }
 
@@ -802,6 +979,142 @@ pub fn transfer_from_referral_to_owner<'info>(
     Ok(())
 }
 
+/// Closes a wrapped SOL token account of the signer, unwrapping its balance to native lamports.
+///
+/// The account must hold the native mint of the Token Program and be owned by `authority`, which
+/// receives the lamports of the account, including the rent.
+///
+/// # Arguments
+///
+/// * `authority` - The signer owning the token account and receiving the lamports.
+/// * `token_owner_account` - The wrapped SOL token account to close.
+/// * `token_program` - The token program of the token account.
+///
+/// # Errors
+///
+/// Returns `InvalidWsolUnwrapAccount` if the account is not a wrapped SOL account of `authority`,
+/// or an error if the close account operation fails.
+pub fn unwrap_native_token_account<'info>(
+    authority: &Signer<'info>,
+    token_owner_account: &InterfaceAccount<'info, InterfaceTokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if token_owner_account.mint != spl_token::native_mint::ID
+        || token_owner_account.owner != authority.key()
+        || token_program.key() != Token::id()
+    {
+        return Err(ErrorCode::InvalidWsolUnwrapAccount.into());
+    }
+
+    invoke_signed(
+        &close_account(
+            token_program.key,
+            &token_owner_account.key(),
+            authority.key,
+            authority.key,
+            &[],
+        )?,
+        &[
+            token_program.to_account_info(),
+            token_owner_account.to_account_info(),
+            authority.to_account_info(),
+        ],
+        &[],
+    )?;
+    Ok(())
+}
+
+/// Creates the associated token account of `authority` for `token_mint` if `token_account` does
+/// not exist yet, with `authority` paying the rent.
+///
+/// The creation is idempotent, so an existing token account is left untouched. The associated
+/// token program checks that `token_account` is the associated token account of `authority`.
+///
+/// # Arguments
+///
+/// * `authority` - The signer owning and paying for the associated token account.
+/// * `token_account` - The token account to create if needed.
+/// * `token_mint` - The mint of the token account.
+/// * `token_program` - The token program of the mint.
+/// * `associated_token_program` - The associated token program, required to create the account.
+/// * `system_program` - The system program, required to create the account.
+///
+/// # Errors
+///
+/// Returns `MissingAssociatedTokenAccountPrograms` if the account does not exist and the programs
+/// are not provided, or an error if the creation fails.
+pub fn create_associated_token_account_if_needed<'info>(
+    authority: &Signer<'info>,
+    token_account: &AccountInfo<'info>,
+    token_mint: &InterfaceAccount<'info, InterfaceMint>,
+    token_program: &Interface<'info, TokenInterface>,
+    associated_token_program: &Option<Program<'info, AssociatedToken>>,
+    system_program: &Option<Program<'info, System>>,
+) -> Result<()> {
+    if !token_account.data_is_empty() {
+        return Ok(());
+    }
+
+    let (Some(associated_token_program), Some(system_program)) =
+        (associated_token_program, system_program)
+    else {
+        return Err(ErrorCode::MissingAssociatedTokenAccountPrograms.into());
+    };
+
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: authority.to_account_info(),
+            associated_token: token_account.clone(),
+            authority: authority.to_account_info(),
+            mint: token_mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))
+}
+
+/// Deserializes a token account that was not deserialized by Anchor, checking that it is owned
+/// by the given token program.
+///
+/// # Errors
+///
+/// Returns an error if the account is not owned by the token program or is not a token account.
+pub fn load_token_account(
+    token_account: &AccountInfo,
+    token_program: &Interface<TokenInterface>,
+) -> Result<InterfaceTokenAccount> {
+    if *token_account.owner != token_program.key() {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let data = token_account.try_borrow_data()?;
+    InterfaceTokenAccount::try_deserialize(&mut &data[..])
+}
+
+/// Checks that a token account is not frozen by the freeze authority of its mint, before a
+/// transfer from or to it.
+///
+/// The token program rejects such transfers with a generic error deep in the CPI, so the check
+/// names the frozen account in a `FrozenTokenAccountEvent` instead.
+///
+/// # Errors
+///
+/// Returns `FrozenAccountError` if the token account is frozen, or an error if the account is not
+/// a token account.
+pub fn check_token_account_not_frozen(token_account: &AccountInfo) -> Result<()> {
+    let token_account_data = token_account.try_borrow_data()?;
+    let token_account_unpacked = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_data)?;
+    if token_account_unpacked.base.state == AccountState::Frozen {
+        emit!(FrozenTokenAccountEvent {
+            event_version: EVENT_VERSION,
+            token_account: token_account.key(),
+            token_mint: token_account_unpacked.base.mint,
+        });
+        return Err(ErrorCode::FrozenAccountError.into());
+    }
+    Ok(())
+}
+
 /// Retrieves the transfer hook program ID for a given token mint.
 ///
 /// This function checks if the token mint is owned by the Token Program and, if not,
@@ -849,8 +1162,7 @@ fn get_transfer_hook_program_id<'info>(
 /// # Errors
 ///
 /// Returns an error if there is an issue with borrowing data or unpacking the account data.
-fn is_transfer_memo_required<'info>(token_account: &InterfaceAccount<'info, InterfaceTokenAccount>) -> Result<bool> {
-    let token_account_info = token_account.to_account_info();
+fn is_transfer_memo_required(token_account_info: &AccountInfo) -> Result<bool> {
     if *token_account_info.owner == Token::id() {
         return Ok(false);
     }
@@ -871,7 +1183,7 @@ fn is_transfer_memo_required<'info>(token_account: &InterfaceAccount<'info, Inte
 /// This function performs several checks to determine if a token mint is supported:
 /// 1. Checks if the mint is owned by the Token Program.
 /// 2. Checks if the mint is the native mint of the Token-2022 Program.
-/// 4. Unpacks the mint data and iterates over the extension types to handle each case accordingly.
+/// 3. Unpacks the mint data and iterates over the extension types to handle each case accordingly.
 ///
 /// # Arguments
 ///
@@ -901,9 +1213,18 @@ pub fn is_supported_token_mint<'info>(
 
     let token_mint_data = token_mint_info.try_borrow_data()?;
     let token_mint_unpacked = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-    let extensions = token_mint_unpacked.get_extension_types()?;
+    let extension_type_ids = get_extension_type_ids(token_mint_unpacked.get_tlv_data())?;
+
+    for extension_type_id in extension_type_ids {
+        let extension = match extension::ExtensionType::try_from(extension_type_id) {
+            Ok(extension) => extension,
+            // ScaledUiAmount is newer than the Token-2022 version linked by the program, so it is
+            // matched on its raw type id. Like InterestBearingConfig, it only changes the UI amount.
+            Err(_) if extension_type_id == SCALED_UI_AMOUNT_EXTENSION_TYPE_ID => continue,
+            // mint has unknown extensions
+            Err(_) => return Ok(false),
+        };
 
-    for extension in extensions {
         match extension {
             // supported
             extension::ExtensionType::TransferFeeConfig |
@@ -911,6 +1232,14 @@ pub fn is_supported_token_mint<'info>(
             extension::ExtensionType::MetadataPointer => {
                 // Supported extensions
             }
+            // Supported, UI amount only
+            //
+            // The interest rate is applied to the UI representation of the balance only.
+            // AiDexProgram works with raw amounts exclusively (vault balances, liquidity math and
+            // TransferChecked), so no accrued interest is ever minted, transferred or priced in.
+            extension::ExtensionType::InterestBearingConfig => {
+                // Supported extensions
+            }
             // Supported, but non-confidential transfer only
             //
             // AiDexProgram invokes TransferChecked instruction and it supports non-confidential transfer only.
@@ -953,6 +1282,112 @@ pub fn is_supported_token_mint<'info>(
     return Ok(true);
 }
 
+/// Checks whether a supported token mint needs a token badge of the config to be used as a reward.
+///
+/// Mints with a freeze authority, a transfer hook, a permanent delegate or a close authority can
+/// freeze, block or drain the reward vault, and make `collect_reward` fail for every position.
+/// The config authority vets them and issues a `TokenBadge` before they can be used.
+///
+/// # Arguments
+///
+/// * `token_mint` - The token mint to check.
+///
+/// # Returns
+///
+/// * `Result<bool>` - Returns `Ok(true)` if the token mint needs a token badge.
+///
+/// # Errors
+///
+/// Returns an error if there is an issue with borrowing data or unpacking the mint data.
+pub fn requires_token_badge<'info>(
+    token_mint: &InterfaceAccount<'info, InterfaceMint>,
+) -> Result<bool> {
+    if token_mint.freeze_authority.is_some() {
+        return Ok(true);
+    }
+
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(false);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let token_mint_unpacked = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    let extension_type_ids = get_extension_type_ids(token_mint_unpacked.get_tlv_data())?;
+
+    Ok(extension_type_ids.into_iter().any(|extension_type_id| {
+        matches!(
+            extension::ExtensionType::try_from(extension_type_id),
+            Ok(extension::ExtensionType::TransferHook)
+                | Ok(extension::ExtensionType::PermanentDelegate)
+                | Ok(extension::ExtensionType::MintCloseAuthority)
+        )
+    }))
+}
+
+/// Checks whether a token mint has a permanent delegate.
+///
+/// `is_supported_token_mint` accepts permanent delegate mints, but the delegate can transfer or
+/// burn the tokens of any account of the mint, including the vaults of a pool.
+///
+/// # Arguments
+///
+/// * `token_mint` - The token mint to check.
+///
+/// # Returns
+///
+/// * `Result<bool>` - Returns `Ok(true)` if the token mint has a permanent delegate extension.
+///
+/// # Errors
+///
+/// Returns an error if there is an issue with borrowing data or unpacking the mint data.
+pub fn has_permanent_delegate<'info>(
+    token_mint: &InterfaceAccount<'info, InterfaceMint>,
+) -> Result<bool> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(false);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let token_mint_unpacked = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    let extension_type_ids = get_extension_type_ids(token_mint_unpacked.get_tlv_data())?;
+
+    Ok(extension_type_ids.into_iter().any(|extension_type_id| {
+        matches!(
+            extension::ExtensionType::try_from(extension_type_id),
+            Ok(extension::ExtensionType::PermanentDelegate)
+        )
+    }))
+}
+
+/// Token-2022 extension type id of the ScaledUiAmount mint extension.
+pub const SCALED_UI_AMOUNT_EXTENSION_TYPE_ID: u16 = 25;
+
+/// Returns the raw type ids of all extensions written in the given TLV data.
+///
+/// Unlike `get_extension_types`, this does not fail on extension types that are unknown to the
+/// linked Token-2022 version, which lets the caller decide how to handle them.
+fn get_extension_type_ids(tlv_data: &[u8]) -> Result<Vec<u16>> {
+    let mut extension_type_ids = vec![];
+    let mut start_index = 0;
+    // Each entry is a 2 byte type, a 2 byte length and `length` bytes of value
+    while start_index + 4 <= tlv_data.len() {
+        let extension_type_id = u16::from_le_bytes([tlv_data[start_index], tlv_data[start_index + 1]]);
+        // Uninitialized, nothing is written after an uninitialized entry
+        if extension_type_id == 0 {
+            break;
+        }
+        let length = u16::from_le_bytes([tlv_data[start_index + 2], tlv_data[start_index + 3]]) as usize;
+        extension_type_ids.push(extension_type_id);
+        start_index += 4 + length;
+    }
+    if start_index > tlv_data.len() {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    Ok(extension_type_ids)
+}
+
 #[derive(Debug)]
 pub struct TransferFeeIncludedAmount {
     pub amount: u64,
@@ -969,52 +1404,95 @@ pub fn calculate_transfer_fee_excluded_amount<'info>(
     token_mint: &InterfaceAccount<'info, InterfaceMint>,
     transfer_fee_included_amount: u64,
 ) -> Result<TransferFeeExcludedAmount> {
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        let transfer_fee = epoch_transfer_fee.calculate_fee(transfer_fee_included_amount).unwrap();
-        let transfer_fee_excluded_amount = transfer_fee_included_amount.checked_sub(transfer_fee).unwrap();
-        return Ok(TransferFeeExcludedAmount { amount: transfer_fee_excluded_amount, transfer_fee });            
-    }
-
-    Ok(TransferFeeExcludedAmount { amount: transfer_fee_included_amount, transfer_fee: 0 })
-} 
+    MintTransferFee::load(token_mint)?.calculate_transfer_fee_excluded_amount(transfer_fee_included_amount)
+}
 
 pub fn calculate_transfer_fee_included_amount<'info>(
     token_mint: &InterfaceAccount<'info, InterfaceMint>,
     transfer_fee_excluded_amount: u64,
 ) -> Result<TransferFeeIncludedAmount> {
-    if transfer_fee_excluded_amount == 0 {
-        return Ok(TransferFeeIncludedAmount { amount: 0, transfer_fee: 0 });
+    MintTransferFee::load(token_mint)?.calculate_transfer_fee_included_amount(transfer_fee_excluded_amount)
+}
+
+/// The transfer fee of a mint for the current epoch.
+///
+/// Reading it unpacks the mint with its extensions, so instructions that quote, check slippage
+/// and transfer the same token read it once and reuse it.
+#[derive(Clone, Debug, Default)]
+pub struct MintTransferFee {
+    pub epoch_transfer_fee: Option<TransferFee>,
+}
+
+impl MintTransferFee {
+    /// Reads the transfer fee of the mint for the current epoch.
+    pub fn load(token_mint: &InterfaceAccount<'_, InterfaceMint>) -> Result<Self> {
+        Ok(Self { epoch_transfer_fee: get_epoch_transfer_fee(token_mint)? })
     }
 
-    // now transfer_fee_excluded_amount > 0
+    pub fn calculate_transfer_fee_excluded_amount(
+        &self,
+        transfer_fee_included_amount: u64,
+    ) -> Result<TransferFeeExcludedAmount> {
+        if let Some(epoch_transfer_fee) = &self.epoch_transfer_fee {
+            let transfer_fee = epoch_transfer_fee.calculate_fee(transfer_fee_included_amount).unwrap();
+            let transfer_fee_excluded_amount = transfer_fee_included_amount.checked_sub(transfer_fee).unwrap();
+            return Ok(TransferFeeExcludedAmount { amount: transfer_fee_excluded_amount, transfer_fee });
+        }
 
-    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
-        let transfer_fee: u64 = if u16::from(epoch_transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
-            // edge-case: if transfer fee rate is 100%, current SPL implementation returns 0 as inverse fee.
-            // https://github.com/solana-labs/solana-program-library/blob/fe1ac9a2c4e5d85962b78c3fc6aaf028461e9026/token/program-2022/src/extension/transfer_fee/mod.rs#L95
-            
-            // But even if transfer fee is 100%, we can use maximum_fee as transfer fee.
-            // if transfer_fee_excluded_amount + maximum_fee > u64 max, the following checked_add should fail.
-            u64::from(epoch_transfer_fee.maximum_fee)
-        } else {
-            epoch_transfer_fee.calculate_inverse_fee(transfer_fee_excluded_amount)
-                .ok_or(ErrorCode::TransferFeeCalculationError)?
-        };
+        Ok(TransferFeeExcludedAmount { amount: transfer_fee_included_amount, transfer_fee: 0 })
+    }
+
+    pub fn calculate_transfer_fee_included_amount(
+        &self,
+        transfer_fee_excluded_amount: u64,
+    ) -> Result<TransferFeeIncludedAmount> {
+        if transfer_fee_excluded_amount == 0 {
+            return Ok(TransferFeeIncludedAmount { amount: 0, transfer_fee: 0 });
+        }
+
+        // now transfer_fee_excluded_amount > 0
 
-        let transfer_fee_included_amount = transfer_fee_excluded_amount.checked_add(transfer_fee)
-            .ok_or(ErrorCode::TransferFeeCalculationError)?;
+        if let Some(epoch_transfer_fee) = &self.epoch_transfer_fee {
+            let transfer_fee: u64 = if u16::from(epoch_transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
+                // edge-case: if transfer fee rate is 100%, current SPL implementation returns 0 as inverse fee.
+                // https://github.com/solana-labs/solana-program-library/blob/fe1ac9a2c4e5d85962b78c3fc6aaf028461e9026/token/program-2022/src/extension/transfer_fee/mod.rs#L95
 
-        // verify transfer fee calculation for safety
-        let transfer_fee_verification = epoch_transfer_fee.calculate_fee(transfer_fee_included_amount).unwrap();
-        if transfer_fee != transfer_fee_verification {
-            // We believe this should never happen
-            return Err(ErrorCode::TransferFeeCalculationError.into());
+                // But even if transfer fee is 100%, we can use maximum_fee as transfer fee.
+                // if transfer_fee_excluded_amount + maximum_fee > u64 max, the following checked_add should fail.
+                u64::from(epoch_transfer_fee.maximum_fee)
+            } else {
+                epoch_transfer_fee.calculate_inverse_fee(transfer_fee_excluded_amount)
+                    .ok_or(ErrorCode::TransferFeeCalculationError)?
+            };
+
+            let transfer_fee_included_amount = transfer_fee_excluded_amount.checked_add(transfer_fee)
+                .ok_or(ErrorCode::TransferFeeCalculationError)?;
+
+            // verify transfer fee calculation for safety
+            let transfer_fee_verification = epoch_transfer_fee.calculate_fee(transfer_fee_included_amount).unwrap();
+            if transfer_fee != transfer_fee_verification {
+                // We believe this should never happen
+                return Err(ErrorCode::TransferFeeCalculationError.into());
+            }
+
+            return Ok(TransferFeeIncludedAmount { amount: transfer_fee_included_amount, transfer_fee });
         }
 
-        return Ok(TransferFeeIncludedAmount { amount: transfer_fee_included_amount, transfer_fee });
+        Ok(TransferFeeIncludedAmount { amount: transfer_fee_excluded_amount, transfer_fee: 0 })
     }
 
-    Ok(TransferFeeIncludedAmount { amount: transfer_fee_excluded_amount, transfer_fee: 0 })
+    /// Logs the applied transfer fee in a memo, if the mint has one.
+    pub fn log_memo(&self, memo_program: &Program<'_, Memo>) -> Result<()> {
+        if let Some(epoch_transfer_fee) = &self.epoch_transfer_fee {
+            let transfer_fee_memo = format!(
+                "TFe: {}, {}",
+                u16::from(epoch_transfer_fee.transfer_fee_basis_points),
+                u64::from(epoch_transfer_fee.maximum_fee),
+            );
+            build_and_log_memo(memo_program, transfer_fee_memo.as_bytes())?;
+        }
+        Ok(())
+    }
 }
 
 pub fn get_epoch_transfer_fee<'info>(
@@ -1144,4 +1622,152 @@ mod fuzz_tests {
             let _ = calculate_transfer_fee_included_amount(&interface_account_mint, amount)?;
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod mint_transfer_fee_tests {
+    use super::*;
+
+    fn mint_transfer_fee(transfer_fee_basis_points: u16, maximum_fee: u64) -> MintTransferFee {
+        MintTransferFee {
+            epoch_transfer_fee: Some(TransferFee {
+                epoch: 0.into(),
+                maximum_fee: maximum_fee.into(),
+                transfer_fee_basis_points: transfer_fee_basis_points.into(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_without_transfer_fee() {
+        let transfer_fee = MintTransferFee::default();
+        let excluded = transfer_fee.calculate_transfer_fee_excluded_amount(1_000).unwrap();
+        assert_eq!((excluded.amount, excluded.transfer_fee), (1_000, 0));
+        let included = transfer_fee.calculate_transfer_fee_included_amount(1_000).unwrap();
+        assert_eq!((included.amount, included.transfer_fee), (1_000, 0));
+    }
+
+    #[test]
+    fn test_with_transfer_fee() {
+        let transfer_fee = mint_transfer_fee(100, 1_000);
+        let excluded = transfer_fee.calculate_transfer_fee_excluded_amount(10_000).unwrap();
+        assert_eq!((excluded.amount, excluded.transfer_fee), (9_900, 100));
+        let included = transfer_fee.calculate_transfer_fee_included_amount(9_900).unwrap();
+        assert_eq!((included.amount, included.transfer_fee), (10_000, 100));
+
+        let capped = mint_transfer_fee(MAX_FEE_BASIS_POINTS, 50);
+        let included = capped.calculate_transfer_fee_included_amount(1_000).unwrap();
+        assert_eq!((included.amount, included.transfer_fee), (1_050, 50));
+    }
+}
+
+#[cfg(test)]
+mod is_supported_token_mint_tests {
+    use super::*;
+
+    const TRANSFER_FEE_CONFIG: (u16, usize) = (1, 108);
+    const NON_TRANSFERABLE: (u16, usize) = (9, 0);
+    const INTEREST_BEARING_CONFIG: (u16, usize) = (10, 52);
+    const SCALED_UI_AMOUNT: (u16, usize) = (SCALED_UI_AMOUNT_EXTENSION_TYPE_ID, 56);
+    const UNKNOWN_EXTENSION: (u16, usize) = (1000, 8);
+    const MINT_CLOSE_AUTHORITY: (u16, usize) = (3, 32);
+    const PERMANENT_DELEGATE: (u16, usize) = (12, 32);
+    const TRANSFER_HOOK: (u16, usize) = (14, 64);
+
+    fn build_mint_data(extensions: &[(u16, usize)]) -> Vec<u8> {
+        let mut data = vec![0u8; 82 + 83];
+        // is_initialized
+        data[45] = 1;
+        // account_type: Mint
+        data.push(1);
+        for (extension_type_id, length) in extensions {
+            data.extend_from_slice(&extension_type_id.to_le_bytes());
+            data.extend_from_slice(&(*length as u16).to_le_bytes());
+            data.extend(std::iter::repeat_n(0u8, *length));
+        }
+        data
+    }
+
+    fn is_supported(extensions: &[(u16, usize)]) -> bool {
+        check_mint(build_mint_data(extensions), is_supported_token_mint)
+    }
+
+    fn requires_badge(data: Vec<u8>) -> bool {
+        check_mint(data, requires_token_badge)
+    }
+
+    fn check_mint(
+        mut data: Vec<u8>,
+        check: fn(&InterfaceAccount<InterfaceMint>) -> Result<bool>,
+    ) -> bool {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let owner = anchor_spl::token_2022::ID;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let interface_account_mint = InterfaceAccount::<InterfaceMint>::try_from(&account_info).unwrap();
+
+        check(&interface_account_mint).unwrap()
+    }
+
+    #[test]
+    fn test_interest_bearing_config_is_supported() {
+        assert!(is_supported(&[INTEREST_BEARING_CONFIG]));
+        assert!(is_supported(&[TRANSFER_FEE_CONFIG, INTEREST_BEARING_CONFIG]));
+    }
+
+    #[test]
+    fn test_scaled_ui_amount_is_supported() {
+        assert!(is_supported(&[SCALED_UI_AMOUNT]));
+        assert!(is_supported(&[SCALED_UI_AMOUNT, TRANSFER_FEE_CONFIG]));
+    }
+
+    #[test]
+    fn test_unsupported_extensions_are_rejected() {
+        assert!(!is_supported(&[NON_TRANSFERABLE]));
+        assert!(!is_supported(&[INTEREST_BEARING_CONFIG, UNKNOWN_EXTENSION]));
+    }
+
+    #[test]
+    fn test_risky_reward_mints_require_token_badge() {
+        assert!(!requires_badge(build_mint_data(&[TRANSFER_FEE_CONFIG, INTEREST_BEARING_CONFIG])));
+
+        assert!(requires_badge(build_mint_data(&[TRANSFER_HOOK])));
+        assert!(requires_badge(build_mint_data(&[TRANSFER_FEE_CONFIG, PERMANENT_DELEGATE])));
+        assert!(requires_badge(build_mint_data(&[MINT_CLOSE_AUTHORITY])));
+
+        // freeze authority set
+        let mut data = build_mint_data(&[TRANSFER_FEE_CONFIG]);
+        data[46] = 1;
+        assert!(requires_badge(data));
+    }
+
+    #[test]
+    fn test_has_permanent_delegate() {
+        assert!(is_supported(&[TRANSFER_FEE_CONFIG, PERMANENT_DELEGATE]));
+        assert!(check_mint(build_mint_data(&[TRANSFER_FEE_CONFIG, PERMANENT_DELEGATE]), has_permanent_delegate));
+        assert!(!check_mint(build_mint_data(&[TRANSFER_HOOK, MINT_CLOSE_AUTHORITY]), has_permanent_delegate));
+    }
+
+    #[test]
+    fn test_get_extension_type_ids() {
+        let data = build_mint_data(&[TRANSFER_FEE_CONFIG, SCALED_UI_AMOUNT]);
+        assert_eq!(
+            get_extension_type_ids(&data[166..]).unwrap(),
+            vec![TRANSFER_FEE_CONFIG.0, SCALED_UI_AMOUNT.0]
+        );
+
+        // length runs past the end of the data
+        let mut truncated = data[166..].to_vec();
+        truncated.truncate(truncated.len() - 1);
+        assert!(get_extension_type_ids(&truncated).is_err());
+    }
+}
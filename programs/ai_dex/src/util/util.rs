@@ -3,6 +3,7 @@ use anchor_lang::{
     ToAccountInfo,
 };
 use anchor_spl::token::TokenAccount;
+use anchor_spl::token_2022::spl_token_2022;
 use solana_program::program_option::COption;
 use std::convert::TryFrom;
 
@@ -70,6 +71,42 @@ pub fn verify_position_authority<'info>(
     Ok(())
 }
 
+/// Verifies that a signer can transfer an amount out of a token account, either as its owner or
+/// as its delegate.
+///
+/// A delegate must have been approved for at least `amount`, so that smart wallets and session
+/// keys can trade from a token account without holding its funds.
+///
+/// # Arguments
+///
+/// * `token_account` - The token account the tokens are transferred from.
+/// * `token_authority` - The key signing the transfer.
+/// * `amount` - The amount to transfer, including any transfer fee.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The signer is neither the owner nor the delegate of the token account.
+/// * The signer is the delegate and the delegated amount is lower than `amount`.
+pub fn verify_token_authority(
+    token_account: &spl_token_2022::state::Account,
+    token_authority: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if token_account.owner == *token_authority {
+        return Ok(());
+    }
+    match token_account.delegate {
+        COption::Some(ref delegate) if delegate == token_authority => {
+            if token_account.delegated_amount < amount {
+                return Err(ErrorCode::InsufficientDelegatedAmount.into());
+            }
+            Ok(())
+        }
+        _ => Err(ErrorCode::InvalidTokenAuthority.into()),
+    }
+}
+
 /// Validates the owner of an account.
 ///
 /// This function checks if the provided owner matches the expected owner and if the owner is a signer.
@@ -104,3 +141,166 @@ fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) ->
 pub fn to_timestamp_u64(t: i64) -> Result<u64> {
     u64::try_from(t).or(Err(ErrorCode::TimestampConversionError.into()))
 }
+
+/// Returns the current timestamp of the cluster as `u64`.
+///
+/// When the program is built with the `mock-clock` feature, a `MockClock` account passed as the
+/// last remaining account overrides the cluster clock, so that tests can run time-dependent logic
+/// deterministically.
+///
+/// # Arguments
+///
+/// * `remaining_accounts` - The remaining accounts of the instruction.
+///
+/// # Errors
+///
+/// This function returns an error if the clock cannot be read or the timestamp conversion is invalid.
+pub fn current_timestamp_u64(remaining_accounts: &[AccountInfo]) -> Result<u64> {
+    #[cfg(feature = "mock-clock")]
+    if let Some(unix_timestamp) = crate::state::MockClock::read_override(remaining_accounts)? {
+        return to_timestamp_u64(unix_timestamp);
+    }
+    #[cfg(not(feature = "mock-clock"))]
+    let _ = remaining_accounts;
+
+    to_timestamp_u64(Clock::get()?.unix_timestamp)
+}
+
+/// Checks that the current timestamp has not passed the caller-provided deadline.
+///
+/// # Arguments
+///
+/// * `timestamp` - The current on-chain timestamp.
+/// * `deadline_timestamp` - The optional deadline after which the instruction must not execute.
+///
+/// # Errors
+///
+/// This function returns an error if the deadline is set and the current timestamp exceeds it.
+pub fn check_deadline(timestamp: u64, deadline_timestamp: Option<u64>) -> Result<()> {
+    if let Some(deadline_timestamp) = deadline_timestamp {
+        if timestamp > deadline_timestamp {
+            return Err(ErrorCode::DeadlineExceeded.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns whether the token mints are in the canonical order of a pool, with `token_mint_a`
+/// strictly lower than `token_mint_b`.
+pub fn is_canonical_token_mint_order(token_mint_a: &Pubkey, token_mint_b: &Pubkey) -> bool {
+    token_mint_a.lt(token_mint_b)
+}
+
+/// Orders a pair of token mints the way pools store them.
+///
+/// Callers building a pool address or a CPI from an arbitrary pair can use this to get
+/// `(token_mint_a, token_mint_b)` without knowing the convention.
+///
+/// # Arguments
+///
+/// * `mint_x` - One token mint of the pair.
+/// * `mint_y` - The other token mint of the pair.
+///
+/// # Returns
+///
+/// The canonical `(token_mint_a, token_mint_b)` and whether the given pair had to be reversed.
+pub fn canonicalize_token_mints(mint_x: Pubkey, mint_y: Pubkey) -> (Pubkey, Pubkey, bool) {
+    if mint_y.lt(&mint_x) {
+        (mint_y, mint_x, true)
+    } else {
+        (mint_x, mint_y, false)
+    }
+}
+
+#[cfg(test)]
+mod verify_token_authority_tests {
+    use super::*;
+
+    fn token_account(owner: Pubkey, delegate: Option<Pubkey>, delegated_amount: u64) -> spl_token_2022::state::Account {
+        spl_token_2022::state::Account {
+            owner,
+            delegate: delegate.map_or(COption::None, COption::Some),
+            delegated_amount,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_owner_can_transfer() {
+        let owner = Pubkey::new_unique();
+        assert!(verify_token_authority(&token_account(owner, None, 0), &owner, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_delegate_within_delegated_amount() {
+        let delegate = Pubkey::new_unique();
+        let account = token_account(Pubkey::new_unique(), Some(delegate), 100);
+        assert!(verify_token_authority(&account, &delegate, 100).is_ok());
+        assert_eq!(
+            verify_token_authority(&account, &delegate, 101).unwrap_err(),
+            ErrorCode::InsufficientDelegatedAmount.into()
+        );
+    }
+
+    #[test]
+    fn test_other_signer_rejected() {
+        let account = token_account(Pubkey::new_unique(), Some(Pubkey::new_unique()), 100);
+        assert_eq!(
+            verify_token_authority(&account, &Pubkey::new_unique(), 1).unwrap_err(),
+            ErrorCode::InvalidTokenAuthority.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod check_deadline_tests {
+    use super::check_deadline;
+    use crate::errors::ErrorCode;
+
+    #[test]
+    fn test_no_deadline() {
+        assert!(check_deadline(u64::MAX, None).is_ok());
+    }
+
+    #[test]
+    fn test_deadline_not_reached() {
+        assert!(check_deadline(100, Some(100)).is_ok());
+        assert!(check_deadline(99, Some(100)).is_ok());
+    }
+
+    #[test]
+    fn test_deadline_exceeded() {
+        let result = check_deadline(101, Some(100));
+        assert_eq!(result.unwrap_err(), ErrorCode::DeadlineExceeded.into());
+    }
+}
+
+#[cfg(test)]
+mod canonicalize_token_mints_tests {
+    use super::{canonicalize_token_mints, is_canonical_token_mint_order};
+    use anchor_lang::prelude::Pubkey;
+
+    #[test]
+    fn test_canonical_pair_is_unchanged() {
+        let mint_x = Pubkey::new_from_array([1; 32]);
+        let mint_y = Pubkey::new_from_array([2; 32]);
+        assert!(is_canonical_token_mint_order(&mint_x, &mint_y));
+        assert_eq!(canonicalize_token_mints(mint_x, mint_y), (mint_x, mint_y, false));
+    }
+
+    #[test]
+    fn test_reversed_pair_is_swapped() {
+        let mint_x = Pubkey::new_from_array([2; 32]);
+        let mint_y = Pubkey::new_from_array([1; 32]);
+        assert!(!is_canonical_token_mint_order(&mint_x, &mint_y));
+        assert_eq!(canonicalize_token_mints(mint_x, mint_y), (mint_y, mint_x, true));
+    }
+
+    #[test]
+    fn test_identical_mints_are_not_canonical() {
+        let mint = Pubkey::new_from_array([1; 32]);
+        assert!(!is_canonical_token_mint_order(&mint, &mint));
+        assert_eq!(canonicalize_token_mints(mint, mint), (mint, mint, false));
+    }
+}